@@ -0,0 +1,913 @@
+//! Provides bootstrap procedures for statistics of samples that are
+//! arbitrary closures, drawing their randomness from the supplied rng:
+//! confidence intervals by ordinary and block resampling, the comparison of
+//! two samples and the Bayesian bootstrap.
+
+use crate::distribution::{ContinuousCDF, Exp, Normal};
+use crate::fp;
+use crate::statistics::{Data, OrderStatistics};
+use crate::{Result, StatsError};
+use rand::distributions::Distribution;
+use rand::Rng;
+
+/// The result of `bootstrap_diff_test`, comparing a statistic of two
+/// independent samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootstrapDiffTest {
+    estimate: f64,
+    differences: Vec<f64>,
+    confidence_interval: (f64, f64),
+    p_value: f64,
+}
+
+impl BootstrapDiffTest {
+    /// Returns the difference `statistic(a) - statistic(b)` of the statistic
+    /// of the original samples
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the bootstrap distribution of the difference, i.e. the
+    /// difference of the statistic for every pair of resamples, in the order
+    /// in which they were drawn
+    pub fn differences(&self) -> &[f64] {
+        &self.differences
+    }
+
+    /// Returns the percentile bootstrap confidence interval of the
+    /// difference
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        self.confidence_interval
+    }
+
+    /// Returns the two-sided p-value of the null hypothesis that the
+    /// statistic is the same for both populations
+    pub fn p_value(&self) -> f64 {
+        self.p_value
+    }
+}
+
+/// Tests whether `statistic` differs between the populations of the
+/// independent samples `a` and `b` by resampling both samples with
+/// replacement `resamples` times, and returns the bootstrap distribution of
+/// the difference `statistic(a) - statistic(b)` together with a percentile
+/// confidence interval at level `confidence` and a p-value
+///
+/// # Remarks
+///
+/// The p-value is computed by centering the bootstrap distribution at zero,
+/// which approximates the distribution of the difference under the null
+/// hypothesis, as the fraction of centered differences at least as large in
+/// absolute value as the observed difference, counting the observed
+/// difference itself so it is never zero. Resamples for which the
+/// difference is `NaN` are discarded.
+///
+/// # Formula
+///
+/// ```ignore
+/// p = (1 + #{i : |d_i - d| >= |d|}) / (1 + B)
+/// ```
+///
+/// where `d` is the observed difference, `d_i` are the bootstrap differences
+/// and `B` is their number
+///
+/// # Errors
+///
+/// Returns an error if `a` or `b` is empty, if `resamples` is zero, if
+/// `confidence` is not in `(0, 1)`, if the statistic of either sample is
+/// `NaN` or if it is `NaN` for every resample
+///
+/// # Examples
+///
+/// ```
+/// use statrs::bootstrap;
+/// use statrs::statistics::{Data, Median};
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let a = [1.2, 3.1, 2.2, 5.3, 2.8, 3.6, 2.4, 4.1];
+/// let b = [6.3, 4.9, 7.2, 5.8, 8.1, 6.6, 5.1, 7.7];
+/// let median = |x: &[f64]| Data::new(x.to_vec()).median();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let test = bootstrap::bootstrap_diff_test(&a, &b, median, 1000, 0.95, &mut rng).unwrap();
+/// assert!(test.confidence_interval().1 < 0.0);
+/// assert!(test.p_value() < 0.01);
+/// ```
+pub fn bootstrap_diff_test<F, R>(
+    a: &[f64],
+    b: &[f64],
+    statistic: F,
+    resamples: usize,
+    confidence: f64,
+    rng: &mut R,
+) -> Result<BootstrapDiffTest>
+where
+    F: Fn(&[f64]) -> f64,
+    R: Rng + ?Sized,
+{
+    if a.is_empty() || b.is_empty() || resamples == 0 {
+        return Err(StatsError::BadParams);
+    }
+    if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+    }
+    let estimate = statistic(a) - statistic(b);
+    if estimate.is_nan() {
+        return Err(StatsError::BadParams);
+    }
+    let mut resample_a = vec![0.0; a.len()];
+    let mut resample_b = vec![0.0; b.len()];
+    let mut differences = Vec::with_capacity(resamples);
+    for _ in 0..resamples {
+        resample(a, &mut resample_a, rng);
+        resample(b, &mut resample_b, rng);
+        let d = statistic(&resample_a) - statistic(&resample_b);
+        if !d.is_nan() {
+            differences.push(d);
+        }
+    }
+    if differences.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    let extreme = differences
+        .iter()
+        .filter(|&&d| (d - estimate).abs() >= estimate.abs())
+        .count();
+    let p_value = (1.0 + extreme as f64) / (1.0 + differences.len() as f64);
+    let alpha = 0.5 * (1.0 - confidence);
+    let mut data = Data::new(differences.clone());
+    let confidence_interval = (data.quantile(alpha), data.quantile(1.0 - alpha));
+    Ok(BootstrapDiffTest {
+        estimate,
+        differences,
+        confidence_interval,
+        p_value,
+    })
+}
+
+/// Draws `draws` values from the posterior distribution of `statistic` for
+/// the population of `sample` by the Bayesian bootstrap of Rubin (1981),
+/// which reweights the data points with flat Dirichlet weights instead of
+/// resampling them
+///
+/// # Remarks
+///
+/// `statistic` is called with the data points and their weights, which are
+/// positive and sum to one, and must compute the weighted version of the
+/// statistic, e.g. `sum_i w_i * x_i` for the mean. The posterior is that of
+/// a model placing its mass on the observed values only, so it is only
+/// meaningful for statistics that are smooth functionals of the
+/// distribution such as means, variances or correlations.
+///
+/// # Errors
+///
+/// Returns an error if `sample` is empty or `draws` is zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::bootstrap;
+/// use statrs::statistics::Statistics;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let sample = [2.1, 3.5, 1.8, 4.2, 2.9, 3.3, 2.5, 3.9];
+/// let weighted_mean = |x: &[f64], w: &[f64]| x.iter().zip(w).map(|(x, w)| x * w).sum();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let posterior = bootstrap::bayesian_bootstrap(&sample, weighted_mean, 4000, &mut rng).unwrap();
+/// assert!((posterior.mean() - sample.mean()).abs() < 0.02);
+/// ```
+pub fn bayesian_bootstrap<F, R>(
+    sample: &[f64],
+    statistic: F,
+    draws: usize,
+    rng: &mut R,
+) -> Result<Vec<f64>>
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    R: Rng + ?Sized,
+{
+    if sample.is_empty() || draws == 0 {
+        return Err(StatsError::BadParams);
+    }
+    // normalized standard exponential variables are flat Dirichlet
+    let exp = Exp::new(1.0)?;
+    let mut weights = vec![0.0; sample.len()];
+    Ok((0..draws)
+        .map(|_| {
+            for w in weights.iter_mut() {
+                *w = exp.sample(rng);
+            }
+            let total: f64 = weights.iter().sum();
+            for w in weights.iter_mut() {
+                *w /= total;
+            }
+            statistic(sample, &weights)
+        })
+        .collect())
+}
+
+/// The resampling scheme of `bootstrap`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resampling {
+    /// Draws the data points independently with replacement, for
+    /// independent observations
+    Ordinary,
+    /// Concatenates blocks of consecutive data points of the given length
+    /// starting at uniformly drawn positions, truncating the last block, so
+    /// that the dependence of a time series within a block is preserved
+    MovingBlock(usize),
+    /// Like `MovingBlock`, but with blocks that wrap around the end of the
+    /// sample, so that every data point is equally likely to be drawn
+    CircularBlock(usize),
+}
+
+impl Resampling {
+    /// Returns the number of consecutive data points drawn together
+    fn block_len(&self) -> usize {
+        match *self {
+            Resampling::Ordinary => 1,
+            Resampling::MovingBlock(len) | Resampling::CircularBlock(len) => len,
+        }
+    }
+}
+
+/// The bootstrap distribution of a statistic returned by `bootstrap`, from
+/// which standard errors and confidence intervals are computed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bootstrap {
+    estimate: f64,
+    replicates: Vec<f64>,
+    jackknife: Vec<f64>,
+}
+
+impl Bootstrap {
+    /// Returns the statistic of the original sample
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the statistic of every resample for which it is not `NaN`,
+    /// in the order in which the resamples were drawn
+    pub fn replicates(&self) -> &[f64] {
+        &self.replicates
+    }
+
+    /// Returns the bootstrap estimate of the bias of the statistic, the
+    /// mean of the replicates minus the estimate
+    pub fn bias(&self) -> f64 {
+        self.replicates.iter().sum::<f64>() / self.replicates.len() as f64 - self.estimate
+    }
+
+    /// Returns the bootstrap estimate of the standard error of the
+    /// statistic, the standard deviation of the replicates
+    pub fn std_error(&self) -> f64 {
+        let n = self.replicates.len() as f64;
+        let mean = self.replicates.iter().sum::<f64>() / n;
+        let ss: f64 = self
+            .replicates
+            .iter()
+            .map(|x| (x - mean) * (x - mean))
+            .sum();
+        (ss / (n - 1.0)).sqrt()
+    }
+
+    /// Returns the percentile confidence interval at level `confidence`,
+    /// the `α / 2` and `1 - α / 2` quantiles of the replicates with
+    /// `α = 1 - confidence`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `confidence` is not in `(0, 1)`
+    pub fn percentile_ci(&self, confidence: f64) -> Result<(f64, f64)> {
+        let alpha = 0.5 * (1.0 - check_confidence(confidence)?);
+        Ok(self.quantiles(alpha, 1.0 - alpha))
+    }
+
+    /// Returns the basic confidence interval at level `confidence`, the
+    /// percentile interval reflected about the estimate
+    ///
+    /// ```ignore
+    /// (2 θ^ - q(1 - α / 2), 2 θ^ - q(α / 2))
+    /// ```
+    ///
+    /// where `q` are the quantiles of the replicates
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `confidence` is not in `(0, 1)`
+    pub fn basic_ci(&self, confidence: f64) -> Result<(f64, f64)> {
+        let alpha = 0.5 * (1.0 - check_confidence(confidence)?);
+        let (lower, upper) = self.quantiles(alpha, 1.0 - alpha);
+        Ok((2.0 * self.estimate - upper, 2.0 * self.estimate - lower))
+    }
+
+    /// Returns the bias-corrected and accelerated (BCa) confidence interval
+    /// of Efron (1987) at level `confidence`, the percentile interval with
+    /// levels adjusted for the median bias and the skewness of the
+    /// statistic, which is second order accurate
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// z0 = Φ^-1((#{θ*_i < θ^} + #{θ*_i = θ^} / 2) / B)
+    /// a = Σ_j (θ_(.) - θ_(j))^3 / (6 (Σ_j (θ_(.) - θ_(j))^2)^(3/2))
+    /// α_1 = Φ(z0 + (z0 + z_(α/2)) / (1 - a (z0 + z_(α/2))))
+    /// α_2 = Φ(z0 + (z0 + z_(1-α/2)) / (1 - a (z0 + z_(1-α/2))))
+    /// ```
+    ///
+    /// where `θ*_i` are the `B` replicates, `θ_(j)` the jackknife values of
+    /// the statistic with the `j`th data point, or block of data points for
+    /// block resampling, left out and `θ_(.)` their mean, and the interval
+    /// is `(q(α_1), q(α_2))`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `confidence` is not in `(0, 1)`, if the estimate
+    /// lies outside the range of the replicates, or if the acceleration is
+    /// undefined because a jackknife value is `NaN` or the sample too small
+    /// to leave a data point out
+    pub fn bca_ci(&self, confidence: f64) -> Result<(f64, f64)> {
+        let alpha = 0.5 * (1.0 - check_confidence(confidence)?);
+        let below = self
+            .replicates
+            .iter()
+            .filter(|&&x| x < self.estimate)
+            .count();
+        let equal = self
+            .replicates
+            .iter()
+            .filter(|&&x| x == self.estimate)
+            .count();
+        let p0 = (below as f64 + 0.5 * equal as f64) / self.replicates.len() as f64;
+        if p0 <= 0.0 || p0 >= 1.0 {
+            return Err(StatsError::BadParams);
+        }
+        if self.jackknife.is_empty() || self.jackknife.iter().any(|x| x.is_nan()) {
+            return Err(StatsError::BadParams);
+        }
+
+        let mean = self.jackknife.iter().sum::<f64>() / self.jackknife.len() as f64;
+        let (ss, cubes) = self.jackknife.iter().fold((0.0, 0.0), |(ss, cubes), x| {
+            let d = mean - x;
+            (ss + d * d, cubes + d * d * d)
+        });
+        let acceleration = if ss > 0.0 {
+            cubes / (6.0 * fp::powf(ss, 1.5))
+        } else {
+            0.0
+        };
+
+        let normal = Normal::new(0.0, 1.0)?;
+        let z0 = normal.inverse_cdf(p0);
+        let adjust = |p: f64| {
+            let z = z0 + normal.inverse_cdf(p);
+            normal.cdf(z0 + z / (1.0 - acceleration * z))
+        };
+        Ok(self.quantiles(adjust(alpha), adjust(1.0 - alpha)))
+    }
+
+    /// Returns the quantiles `p` and `q` of the replicates
+    fn quantiles(&self, p: f64, q: f64) -> (f64, f64) {
+        let mut data = Data::new(self.replicates.clone());
+        (data.quantile(p), data.quantile(q))
+    }
+}
+
+/// Estimates the sampling distribution of `statistic` for the population of
+/// `sample` by computing it for `resamples` resamples of the data drawn
+/// with the scheme `resampling`, and returns the replicates from which the
+/// standard error and percentile, basic and BCa confidence intervals of the
+/// statistic are computed
+///
+/// # Remarks
+///
+/// Resamples for which the statistic is `NaN` are discarded. The jackknife
+/// values needed by `Bootstrap::bca_ci` are computed up front by leaving
+/// out each data point in turn, or for block resampling each block of
+/// consecutive data points that fits in the sample, which costs a further
+/// `n` evaluations of the statistic. The block length of a block scheme
+/// should grow with the sample size and the strength of the dependence;
+/// a common choice is of the order of `n^(1/3)`.
+///
+/// # Errors
+///
+/// Returns an error if `sample` is empty, if `resamples` is zero, if the
+/// block length of a block scheme is zero or not smaller than the length
+/// of `sample`, if the statistic of the sample is `NaN` or if it is `NaN`
+/// for every resample
+///
+/// # Examples
+///
+/// ```
+/// use statrs::bootstrap::{self, Resampling};
+/// use statrs::statistics::Statistics;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let sample = [2.1, 3.5, 1.8, 4.2, 2.9, 3.3, 2.5, 3.9, 9.6, 2.2];
+/// let mean = |x: &[f64]| x.mean();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let boot = bootstrap::bootstrap(&sample, mean, 2000, Resampling::Ordinary, &mut rng).unwrap();
+/// let (lower, upper) = boot.bca_ci(0.95).unwrap();
+/// assert!(lower < boot.estimate() && boot.estimate() < upper);
+/// // the outlier skews the interval to the right
+/// assert!(upper - boot.estimate() > boot.estimate() - lower);
+/// ```
+pub fn bootstrap<F, R>(
+    sample: &[f64],
+    statistic: F,
+    resamples: usize,
+    resampling: Resampling,
+    rng: &mut R,
+) -> Result<Bootstrap>
+where
+    F: Fn(&[f64]) -> f64,
+    R: Rng + ?Sized,
+{
+    let estimate = check_bootstrap(sample, &statistic, resamples, resampling)?;
+    let mut out = vec![0.0; sample.len()];
+    let replicates = (0..resamples)
+        .map(|_| {
+            draw(sample, resampling, &mut out, rng);
+            statistic(&out)
+        })
+        .filter(|x| !x.is_nan())
+        .collect();
+    let jackknife = jackknife(sample, &statistic, resampling.block_len());
+    finish_bootstrap(estimate, replicates, jackknife)
+}
+
+/// Computes the bootstrap distribution of `statistic` like `bootstrap`,
+/// evaluating the resamples in parallel on the `rayon` thread pool,
+/// available with the `rayon` feature
+///
+/// # Remarks
+///
+/// The resamples are split into fixed chunks, each drawn from its own
+/// stream seeded in order from `rng`, so the replicates only depend on the
+/// state of `rng` and not on the number of threads or the scheduling. They
+/// differ from the replicates of `bootstrap` with the same `rng`.
+///
+/// # Errors
+///
+/// Returns an error under the same conditions as `bootstrap`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::bootstrap::{self, Resampling};
+/// use statrs::statistics::Statistics;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let sample: Vec<f64> = (0..100).map(|i| (i as f64).sin()).collect();
+/// let std_dev = |x: &[f64]| x.std_dev();
+/// let boot = bootstrap::par_bootstrap(&sample, std_dev, 4000, Resampling::Ordinary, &mut StdRng::seed_from_u64(0)).unwrap();
+/// let again = bootstrap::par_bootstrap(&sample, std_dev, 4000, Resampling::Ordinary, &mut StdRng::seed_from_u64(0)).unwrap();
+/// assert_eq!(boot, again);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_bootstrap<F, R>(
+    sample: &[f64],
+    statistic: F,
+    resamples: usize,
+    resampling: Resampling,
+    rng: &mut R,
+) -> Result<Bootstrap>
+where
+    F: Fn(&[f64]) -> f64 + Sync,
+    R: Rng + ?Sized,
+{
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rayon::prelude::*;
+
+    let estimate = check_bootstrap(sample, &statistic, resamples, resampling)?;
+    let streams: Vec<StdRng> = (0..resamples.div_ceil(PAR_CHUNK_SIZE))
+        .map(|_| StdRng::from_seed(rng.gen()))
+        .collect();
+    let replicates = streams
+        .into_par_iter()
+        .enumerate()
+        .flat_map_iter(|(i, mut stream)| {
+            let len = PAR_CHUNK_SIZE.min(resamples - i * PAR_CHUNK_SIZE);
+            let mut out = vec![0.0; sample.len()];
+            (0..len)
+                .map(|_| {
+                    draw(sample, resampling, &mut out, &mut stream);
+                    statistic(&out)
+                })
+                .filter(|x| !x.is_nan())
+                .collect::<Vec<f64>>()
+        })
+        .collect();
+    let jackknife = jackknife(sample, &statistic, resampling.block_len());
+    finish_bootstrap(estimate, replicates, jackknife)
+}
+
+/// The number of resamples drawn from a single stream by `par_bootstrap`,
+/// which fixes the split of the work independently of the number of threads
+#[cfg(feature = "rayon")]
+const PAR_CHUNK_SIZE: usize = 64;
+
+/// Returns `confidence` if it is in `(0, 1)`
+fn check_confidence(confidence: f64) -> Result<f64> {
+    if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+        Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0))
+    } else {
+        Ok(confidence)
+    }
+}
+
+/// Validates the arguments of `bootstrap` and returns the statistic of
+/// `sample`
+fn check_bootstrap<F>(
+    sample: &[f64],
+    statistic: &F,
+    resamples: usize,
+    resampling: Resampling,
+) -> Result<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    if sample.is_empty() || resamples == 0 {
+        return Err(StatsError::BadParams);
+    }
+    if resampling != Resampling::Ordinary {
+        let len = resampling.block_len();
+        if len == 0 || len >= sample.len() {
+            return Err(StatsError::BadParams);
+        }
+    }
+    let estimate = statistic(sample);
+    if estimate.is_nan() {
+        return Err(StatsError::BadParams);
+    }
+    Ok(estimate)
+}
+
+/// Assembles the result of `bootstrap`, failing if every replicate was
+/// discarded
+fn finish_bootstrap(estimate: f64, replicates: Vec<f64>, jackknife: Vec<f64>) -> Result<Bootstrap> {
+    if replicates.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    Ok(Bootstrap {
+        estimate,
+        replicates,
+        jackknife,
+    })
+}
+
+/// Returns the statistic of `sample` with each block of `block_len`
+/// consecutive data points left out in turn, which is empty if no data
+/// point would be left
+fn jackknife<F>(sample: &[f64], statistic: &F, block_len: usize) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    if block_len >= sample.len() {
+        return Vec::new();
+    }
+    let mut rest = Vec::with_capacity(sample.len() - block_len);
+    (0..=sample.len() - block_len)
+        .map(|start| {
+            rest.clear();
+            rest.extend_from_slice(&sample[..start]);
+            rest.extend_from_slice(&sample[start + block_len..]);
+            statistic(&rest)
+        })
+        .collect()
+}
+
+/// Fills `out` with data points drawn from `sample` with the scheme
+/// `resampling`
+fn draw<R: Rng + ?Sized>(sample: &[f64], resampling: Resampling, out: &mut [f64], rng: &mut R) {
+    let n = sample.len();
+    match resampling {
+        Resampling::Ordinary => resample(sample, out, rng),
+        Resampling::MovingBlock(len) => {
+            for block in out.chunks_mut(len) {
+                let start = rng.gen_range(0..=n - len);
+                block.copy_from_slice(&sample[start..start + block.len()]);
+            }
+        }
+        Resampling::CircularBlock(len) => {
+            for block in out.chunks_mut(len) {
+                let start = rng.gen_range(0..n);
+                for (i, x) in block.iter_mut().enumerate() {
+                    *x = sample[(start + i) % n];
+                }
+            }
+        }
+    }
+}
+
+/// Fills `out` with values drawn from `sample` with replacement
+fn resample<R: Rng + ?Sized>(sample: &[f64], out: &mut [f64], rng: &mut R) {
+    for x in out.iter_mut() {
+        *x = sample[rng.gen_range(0..sample.len())];
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use crate::statistics::{Median, Statistics};
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn mean(x: &[f64]) -> f64 {
+        x.mean()
+    }
+
+    #[test]
+    fn test_bad_params() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let a = [1.0, 2.0, 3.0];
+        assert!(bootstrap_diff_test(&[], &a, mean, 10, 0.9, &mut rng).is_err());
+        assert!(bootstrap_diff_test(&a, &[], mean, 10, 0.9, &mut rng).is_err());
+        assert!(bootstrap_diff_test(&a, &a, mean, 0, 0.9, &mut rng).is_err());
+        assert!(bootstrap_diff_test(&a, &a, mean, 10, 1.0, &mut rng).is_err());
+        assert!(bootstrap_diff_test(&a, &a, mean, 10, f64::NAN, &mut rng).is_err());
+        assert!(bootstrap_diff_test(&a, &a, |_| f64::NAN, 10, 0.9, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_no_difference() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let a: Vec<f64> = (0..201).map(|_| n.sample(&mut rng)).collect();
+        let b: Vec<f64> = a.iter().rev().cloned().collect();
+        let median = |x: &[f64]| Data::new(x.to_vec()).median();
+        let test = bootstrap_diff_test(&a, &b, median, 2000, 0.95, &mut rng).unwrap();
+        assert_eq!(test.differences().len(), 2000);
+        assert_eq!(test.estimate(), 0.0);
+        let (low, high) = test.confidence_interval();
+        assert!(low < 0.0 && 0.0 < high);
+        assert_eq!(test.p_value(), 1.0);
+        let test = bootstrap_diff_test(&a, &b, mean, 2000, 0.95, &mut rng).unwrap();
+        assert_almost_eq!(test.estimate(), 0.0, 1e-15);
+        assert!(test.p_value() > 0.9);
+    }
+
+    #[test]
+    fn test_difference() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let a: Vec<f64> = (0..200).map(|_| n.sample(&mut rng)).collect();
+        let b: Vec<f64> = (0..200).map(|_| n.sample(&mut rng) + 1.0).collect();
+        let median = |x: &[f64]| Data::new(x.to_vec()).median();
+        let test = bootstrap_diff_test(&a, &b, median, 2000, 0.95, &mut rng).unwrap();
+        let (low, high) = test.confidence_interval();
+        assert!(low < -1.0 + 0.3 && -1.0 - 0.3 < high && high < 0.0);
+        // the p-value can not be smaller than 1 / (1 + B)
+        assert!(test.p_value() >= 1.0 / 2001.0 && test.p_value() < 0.005);
+    }
+
+    #[test]
+    fn test_p_value_matches_normal_approximation() {
+        // the standard error of the mean difference is sqrt(2 / 400) = 0.0707
+        let mut rng = StdRng::seed_from_u64(3);
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let a: Vec<f64> = (0..400).map(|_| n.sample(&mut rng)).collect();
+        let b: Vec<f64> = (0..400).map(|_| n.sample(&mut rng)).collect();
+        let test = bootstrap_diff_test(&a, &b, mean, 5000, 0.9, &mut rng).unwrap();
+        let sd = test.differences().std_dev();
+        let z = test.estimate().abs() / sd;
+        let expected = 2.0 * (1.0 - crate::distribution::ContinuousCDF::cdf(&n, z));
+        assert!((test.p_value() - expected).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let a = [1.0, 4.0, 2.0, 8.0, 5.0];
+        let b = [3.0, 3.5, 7.0, 1.0];
+        let x = bootstrap_diff_test(&a, &b, mean, 100, 0.9, &mut StdRng::seed_from_u64(4)).unwrap();
+        let y = bootstrap_diff_test(&a, &b, mean, 100, 0.9, &mut StdRng::seed_from_u64(4)).unwrap();
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn test_bayesian_bad_params() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(bayesian_bootstrap(&[], |_, _| 0.0, 10, &mut rng).is_err());
+        assert!(bayesian_bootstrap(&[1.0], |_, _| 0.0, 0, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_bayesian_weights() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let sample = [1.0, 2.0, 3.0, 4.0];
+        let draws = bayesian_bootstrap(&sample, |x, w| {
+            assert_eq!(x, &sample);
+            assert!(w.iter().all(|&w| w > 0.0));
+            w.iter().sum()
+        }, 100, &mut rng).unwrap();
+        assert_eq!(draws.len(), 100);
+        assert!(draws.iter().all(|&s| (s - 1.0).abs() < 1e-14));
+        // a single data point always gets all the weight
+        let draws = bayesian_bootstrap(&[7.0], |x, w| x[0] * w[0], 10, &mut rng).unwrap();
+        assert!(draws.iter().all(|&d| d == 7.0));
+    }
+
+    #[test]
+    fn test_bayesian_mean() {
+        // the posterior of the mean has the sample mean as its mean and
+        // variance sum_i (x_i - mean)^2 / (n (n + 1))
+        let mut rng = StdRng::seed_from_u64(6);
+        let sample = [2.0, 3.0, 5.0, 7.0, 11.0, 13.0];
+        let weighted_mean = |x: &[f64], w: &[f64]| x.iter().zip(w).map(|(x, w)| x * w).sum();
+        let draws = bayesian_bootstrap(&sample, weighted_mean, 20000, &mut rng).unwrap();
+        let m = mean(&sample);
+        let ss: f64 = sample.iter().map(|x| (x - m).powi(2)).sum();
+        assert!((draws.iter().mean() - m).abs() < 0.03);
+        assert!((draws.iter().population_variance() / (ss / 42.0) - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_bayesian_reproducible() {
+        let sample = [1.0, 4.0, 2.0, 8.0, 5.0];
+        let stat = |x: &[f64], w: &[f64]| x.iter().zip(w).map(|(x, w)| x * x * w).sum();
+        let x = bayesian_bootstrap(&sample, stat, 50, &mut StdRng::seed_from_u64(7)).unwrap();
+        let y = bayesian_bootstrap(&sample, stat, 50, &mut StdRng::seed_from_u64(7)).unwrap();
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn test_bootstrap_bad_params() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let a = [1.0, 2.0, 3.0, 4.0];
+        assert!(bootstrap(&[], mean, 10, Resampling::Ordinary, &mut rng).is_err());
+        assert!(bootstrap(&a, mean, 0, Resampling::Ordinary, &mut rng).is_err());
+        assert!(bootstrap(&a, mean, 10, Resampling::MovingBlock(0), &mut rng).is_err());
+        assert!(bootstrap(&a, mean, 10, Resampling::MovingBlock(4), &mut rng).is_err());
+        assert!(bootstrap(&a, mean, 10, Resampling::CircularBlock(4), &mut rng).is_err());
+        assert!(bootstrap(&a, |_| f64::NAN, 10, Resampling::Ordinary, &mut rng).is_err());
+        // the statistic of the sample is defined, but of no resample
+        assert!(bootstrap(&a, |x| if x == a { 0.0 } else { f64::NAN }, 10, Resampling::Ordinary, &mut rng).is_err());
+
+        let boot = bootstrap(&a, mean, 10, Resampling::MovingBlock(3), &mut rng).unwrap();
+        assert!(boot.percentile_ci(0.0).is_err());
+        assert!(boot.basic_ci(1.0).is_err());
+        assert!(boot.bca_ci(f64::NAN).is_err());
+        // a single data point can not be left out
+        let boot = bootstrap(&[1.0], mean, 10, Resampling::Ordinary, &mut rng).unwrap();
+        assert!(boot.bca_ci(0.9).is_err());
+    }
+
+    #[test]
+    fn test_bootstrap_mean() {
+        // the standard error of the mean of 400 standard normal values is 0.05
+        let mut rng = StdRng::seed_from_u64(8);
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let sample: Vec<f64> = (0..400).map(|_| n.sample(&mut rng)).collect();
+        let boot = bootstrap(&sample, mean, 4000, Resampling::Ordinary, &mut rng).unwrap();
+        assert_eq!(boot.replicates().len(), 4000);
+        assert_eq!(boot.estimate(), mean(&sample));
+        let se = sample.std_dev() / 20.0;
+        assert!((boot.std_error() / se - 1.0).abs() < 0.05);
+        assert!(boot.bias().abs() < 0.005);
+        // all intervals are close to the normal theory interval
+        let normal = (boot.estimate() - 1.96 * se, boot.estimate() + 1.96 * se);
+        for (lower, upper) in vec![boot.percentile_ci(0.95), boot.basic_ci(0.95), boot.bca_ci(0.95)].into_iter().map(|ci| ci.unwrap()) {
+            assert!((lower - normal.0).abs() < 0.01);
+            assert!((upper - normal.1).abs() < 0.01);
+        }
+        let (lower, upper) = boot.percentile_ci(0.95).unwrap();
+        let mut data = Data::new(boot.replicates().to_vec());
+        assert_almost_eq!(lower, data.quantile(0.025), 1e-15);
+        assert_almost_eq!(upper, data.quantile(0.975), 1e-15);
+        let (basic_lower, basic_upper) = boot.basic_ci(0.95).unwrap();
+        assert_almost_eq!(basic_lower, 2.0 * boot.estimate() - upper, 1e-15);
+        assert_almost_eq!(basic_upper, 2.0 * boot.estimate() - lower, 1e-15);
+    }
+
+    #[test]
+    fn test_bca() {
+        // without median bias or skewness of the jackknife values the BCa
+        // interval is the percentile interval
+        let boot = Bootstrap {
+            estimate: 5.0,
+            replicates: (0..=10).map(|i| i as f64).collect(),
+            jackknife: vec![1.0, 2.0, 3.0],
+        };
+        let (lower, upper) = boot.bca_ci(0.8).unwrap();
+        assert_almost_eq!(lower, 0.4666666666666667, 1e-9);
+        assert_almost_eq!(upper, 9.533333333333333, 1e-9);
+        let (p_lower, p_upper) = boot.percentile_ci(0.8).unwrap();
+        assert_almost_eq!(p_lower, 0.4666666666666667, 1e-14);
+        assert_almost_eq!(p_upper, 9.533333333333333, 1e-14);
+        // the deviations of the jackknife values from their mean are
+        // (4, 1, 1, 1, -2, -5) / 3
+        let boot = Bootstrap {
+            estimate: 7.0,
+            replicates: (0..=10).map(|i| i as f64).collect(),
+            jackknife: vec![0.0, 1.0, 1.0, 1.0, 2.0, 3.0],
+        };
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let z0 = normal.inverse_cdf(7.5 / 11.0);
+        let a = -22.0 / 9.0 / (6.0 * (16.0f64 / 3.0).powf(1.5));
+        let adjust = |p: f64| {
+            let z = z0 + normal.inverse_cdf(p);
+            normal.cdf(z0 + z / (1.0 - a * z))
+        };
+        let (lower, upper) = boot.bca_ci(0.8).unwrap();
+        let mut data = Data::new(boot.replicates.clone());
+        assert_almost_eq!(lower, data.quantile(adjust(0.1)), 1e-12);
+        assert_almost_eq!(upper, data.quantile(adjust(0.9)), 1e-12);
+        assert!(lower > 1.0 && upper > 9.6);
+        // the estimate must lie within the replicates
+        let boot = Bootstrap { estimate: 11.0, ..boot };
+        assert!(boot.bca_ci(0.8).is_err());
+    }
+
+    #[test]
+    fn test_bca_skewed() {
+        // the sampling distribution of the mean of exponential data is skewed
+        // to the right, which the BCa interval follows more closely than the
+        // percentile interval
+        let mut rng = StdRng::seed_from_u64(9);
+        let exp = Exp::new(1.0).unwrap();
+        let sample: Vec<f64> = (0..30).map(|_| exp.sample(&mut rng)).collect();
+        let boot = bootstrap(&sample, mean, 4000, Resampling::Ordinary, &mut rng).unwrap();
+        let (p_lower, p_upper) = boot.percentile_ci(0.95).unwrap();
+        let (lower, upper) = boot.bca_ci(0.95).unwrap();
+        assert!(lower > p_lower && upper > p_upper);
+        assert!(upper - boot.estimate() > boot.estimate() - lower);
+    }
+
+    #[test]
+    fn test_block_draws() {
+        let sample: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        let mut rng = StdRng::seed_from_u64(10);
+        let mut out = [0.0; 10];
+        for _ in 0..100 {
+            draw(&sample, Resampling::MovingBlock(3), &mut out, &mut rng);
+            for block in out.chunks(3) {
+                assert!(block.windows(2).all(|w| w[1] == w[0] + 1.0));
+            }
+            draw(&sample, Resampling::CircularBlock(4), &mut out, &mut rng);
+            for block in out.chunks(4) {
+                assert!(block.windows(2).all(|w| w[1] == (w[0] + 1.0) % 10.0));
+            }
+        }
+        // the circular blocks wrap around the end of the sample
+        let wraps = (0..200).any(|_| {
+            draw(&sample, Resampling::CircularBlock(4), &mut out, &mut rng);
+            out[..4].contains(&9.0) && out[..4].contains(&0.0)
+        });
+        assert!(wraps);
+        assert_eq!(jackknife(&sample, &|x: &[f64]| x.len() as f64, 3), vec![7.0; 8]);
+        assert_eq!(jackknife(&sample, &|x: &[f64]| x[0], 3)[..2], [3.0, 0.0]);
+    }
+
+    #[test]
+    fn test_block_bootstrap() {
+        // for an AR(1) series with coefficient 0.6 the standard error of the
+        // mean is sqrt((1 + 0.6) / (1 - 0.6)) = 2 times that of independent
+        // data, which only the block bootstrap picks up
+        let mut rng = StdRng::seed_from_u64(11);
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let mut x = 0.0;
+        let series: Vec<f64> = (0..4000).map(|_| {
+            x = 0.6 * x + n.sample(&mut rng);
+            x
+        }).collect();
+        let ordinary = bootstrap(&series, mean, 1000, Resampling::Ordinary, &mut rng).unwrap();
+        let moving = bootstrap(&series, mean, 1000, Resampling::MovingBlock(40), &mut rng).unwrap();
+        let circular = bootstrap(&series, mean, 1000, Resampling::CircularBlock(40), &mut rng).unwrap();
+        assert!((moving.std_error() / ordinary.std_error() - 2.0).abs() < 0.25);
+        assert!((circular.std_error() / ordinary.std_error() - 2.0).abs() < 0.25);
+        assert!(moving.bca_ci(0.9).is_ok());
+    }
+
+    #[test]
+    fn test_bootstrap_reproducible() {
+        let sample = [1.0, 4.0, 2.0, 8.0, 5.0, 3.0];
+        let x = bootstrap(&sample, mean, 100, Resampling::CircularBlock(2), &mut StdRng::seed_from_u64(12)).unwrap();
+        let y = bootstrap(&sample, mean, 100, Resampling::CircularBlock(2), &mut StdRng::seed_from_u64(12)).unwrap();
+        assert_eq!(x, y);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_bootstrap() {
+        let mut rng = StdRng::seed_from_u64(13);
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let sample: Vec<f64> = (0..400).map(|_| n.sample(&mut rng)).collect();
+        // the chunks do not divide the resamples
+        let par = par_bootstrap(&sample, mean, 4001, Resampling::MovingBlock(5), &mut StdRng::seed_from_u64(14)).unwrap();
+        let again = par_bootstrap(&sample, mean, 4001, Resampling::MovingBlock(5), &mut StdRng::seed_from_u64(14)).unwrap();
+        assert_eq!(par, again);
+        assert_eq!(par.replicates().len(), 4001);
+        let seq = bootstrap(&sample, mean, 4001, Resampling::MovingBlock(5), &mut rng).unwrap();
+        assert_eq!(par.estimate(), seq.estimate());
+        assert!((par.std_error() / seq.std_error() - 1.0).abs() < 0.1);
+        assert!(par_bootstrap(&sample, mean, 0, Resampling::Ordinary, &mut rng).is_err());
+    }
+}
@@ -0,0 +1,452 @@
+//! Provides tail probabilities of the normal, chi-squared, Student's t and
+//! F distributions enclosed in intervals that account for the error of the
+//! special-function approximations behind them, for certifying that a
+//! p-value lies below or above a threshold
+
+use crate::fp;
+use crate::function::{beta, erf, gamma};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The factor by which the first-order error estimates are inflated
+const SAFETY: f64 = 4.0;
+
+/// The relative accuracy of `erfc`, whose rational approximations are
+/// offset by constants rounded to ten digits
+const ERFC_ACCURACY: f64 = 1e-10;
+
+/// The accuracy, in units of `f64::EPSILON` relative to `|ln Γ(x)| + 1`,
+/// of the Lanczos approximation of `ln_gamma`
+const LN_GAMMA_ULPS: f64 = 64.0;
+
+/// The relative accuracy, in units of `f64::EPSILON`, assumed for the
+/// converged series and continued fractions of the incomplete gamma and
+/// beta functions, allowing for the rounding error accumulated over their
+/// terms
+const EXPANSION_ULPS: f64 = 1024.0;
+
+/// A closed interval `[lower, upper]` of probabilities enclosing a value
+/// that could only be approximated
+///
+/// # Examples
+///
+/// ```
+/// use statrs::certified::normal_sf;
+///
+/// let p = normal_sf(3.0);
+/// assert!(p.lower() <= 0.0013498980316301 && 0.0013498980316301 <= p.upper());
+/// assert!(p.is_below(0.005));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Interval {
+    lower: f64,
+    upper: f64,
+}
+
+impl Interval {
+    /// Constructs the interval `[lower, upper]`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either bound is `NaN` or if `lower > upper`
+    pub fn new(lower: f64, upper: f64) -> Result<Interval> {
+        if lower.is_nan() || upper.is_nan() || lower > upper {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(Interval { lower, upper })
+        }
+    }
+
+    /// Returns the lower bound of the interval
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Returns the upper bound of the interval
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// Returns the width `upper - lower` of the interval
+    pub fn width(&self) -> f64 {
+        self.upper - self.lower
+    }
+
+    /// Returns whether `x` lies in the interval
+    pub fn contains(&self, x: f64) -> bool {
+        self.lower <= x && x <= self.upper
+    }
+
+    /// Returns whether the whole interval lies below `threshold`, certifying
+    /// that the enclosed value does
+    pub fn is_below(&self, threshold: f64) -> bool {
+        self.upper < threshold
+    }
+
+    /// Returns whether the whole interval lies above `threshold`, certifying
+    /// that the enclosed value does
+    pub fn is_above(&self, threshold: f64) -> bool {
+        self.lower > threshold
+    }
+}
+
+/// Returns the interval around the probability `value` with relative error
+/// `rel` and absolute error `abs`, both before the safety factor, clamped to
+/// `[0, 1]`. Results in the subnormal range have no relative accuracy, so
+/// the absolute error is at least `f64::MIN_POSITIVE`.
+fn enclose(value: f64, rel: f64, abs: f64) -> Interval {
+    let err = SAFETY * (rel * value + abs) + f64::MIN_POSITIVE;
+    Interval {
+        lower: (value - err).max(0.0),
+        upper: (value + err).min(1.0),
+    }
+}
+
+/// Returns the absolute error of a computed `ln_gamma(x)`
+fn ln_gamma_error(x: f64) -> f64 {
+    LN_GAMMA_ULPS * f64::EPSILON * (gamma::ln_gamma(x).abs() + 1.0)
+}
+
+/// Computes the upper tail probability `P(Z > x)` of the standard normal
+/// distribution, enclosed in an interval
+///
+/// # Remarks
+///
+/// The tail is `erfc(x / sqrt(2)) / 2`, and `erfc` is accurate to about
+/// `1e-10` relative. For large `x` the rounding of the argument and of
+/// `x^2` inside `erfc` are amplified by the decay `exp(-x^2 / 2)`, adding a
+/// relative error growing as `x^2 ε`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::certified::normal_sf;
+///
+/// // a five sigma discovery
+/// let p = normal_sf(5.0);
+/// assert!(p.contains(2.866515718791939e-7));
+/// assert!(p.width() < 1e-15);
+/// ```
+pub fn normal_sf(x: f64) -> Interval {
+    if x.is_nan() {
+        return Interval {
+            lower: f64::NAN,
+            upper: f64::NAN,
+        };
+    }
+    let z = x * f64::consts::FRAC_1_SQRT_2;
+    let value = 0.5 * erf::erfc(z);
+    let z2 = if z > 0.0 { z * z } else { 0.0 };
+    // conditioning on the argument, |d ln erfc(z) / d ln z| <= 2z^2 + 2,
+    // times its rounding of 2ε, plus the rounding of z^2 inside erfc
+    let rel = (2.0 * (2.0 * z2 + 2.0) + z2) * f64::EPSILON + ERFC_ACCURACY;
+    enclose(value, rel, 0.0)
+}
+
+/// Computes the upper tail probability `P(X > x)` of the chi-squared
+/// distribution with `freedom` degrees of freedom, enclosed in an interval
+///
+/// # Remarks
+///
+/// The tail is the regularized upper incomplete gamma function
+/// `Q(k / 2, x / 2)`, whose prefactor `exp(a ln(y) - y - ln Γ(a))` carries
+/// the rounding error of its exponent into a relative error of the result.
+///
+/// # Errors
+///
+/// Returns an error if `freedom` is not positive and finite or if `x` is
+/// `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::certified::chi_squared_sf;
+///
+/// let p = chi_squared_sf(30.0, 4.0).unwrap();
+/// assert!(p.contains(4.894437128029217e-6));
+/// assert!(p.is_below(1e-5));
+/// ```
+pub fn chi_squared_sf(x: f64, freedom: f64) -> Result<Interval> {
+    if !freedom.is_finite() || freedom <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("freedom"));
+    }
+    if x.is_nan() {
+        return Err(StatsError::BadParams);
+    }
+    if x <= 0.0 {
+        return Ok(Interval {
+            lower: 1.0,
+            upper: 1.0,
+        });
+    }
+    if x == f64::INFINITY {
+        return Ok(Interval {
+            lower: 0.0,
+            upper: 0.0,
+        });
+    }
+    let (a, y) = (freedom / 2.0, x / 2.0);
+    let value = gamma::gamma_ur(a, y);
+    let exponent = (a * fp::ln(y)).abs() + y + gamma::ln_gamma(a).abs();
+    let rel = 4.0 * exponent * f64::EPSILON + ln_gamma_error(a) + EXPANSION_ULPS * f64::EPSILON;
+    // the series branch returns 1 - P(a, y), absolutely accurate only
+    let abs = if y < 1.0 || y <= a {
+        rel + 2.0 * f64::EPSILON
+    } else {
+        0.0
+    };
+    Ok(enclose(value, rel, abs))
+}
+
+/// Computes the upper tail probability `P(T > t)` of the standard Student's
+/// t distribution with `freedom` degrees of freedom, enclosed in an
+/// interval
+///
+/// # Remarks
+///
+/// The tail is `I_x(ν / 2, 1 / 2) / 2` with `x = ν / (ν + t^2)`, and the
+/// enclosure accounts for the rounding of `x` as well as the error of the
+/// regularized incomplete beta function. The error bound of `ln_gamma`
+/// scales with `ln Γ(ν / 2)`, so the interval widens to a relative `1e-6`
+/// at a million degrees of freedom.
+///
+/// # Errors
+///
+/// Returns an error if `freedom` is not positive and finite or if `t` is
+/// `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::certified::students_t_sf;
+///
+/// let p = students_t_sf(4.0, 10.0).unwrap();
+/// assert!(p.contains(0.0012591663123683));
+/// // a two-sided test at the 1% level
+/// assert!(p.is_below(0.005));
+/// ```
+pub fn students_t_sf(t: f64, freedom: f64) -> Result<Interval> {
+    if !freedom.is_finite() || freedom <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("freedom"));
+    }
+    if t.is_nan() {
+        return Err(StatsError::BadParams);
+    }
+    if t == 0.0 {
+        return Ok(Interval {
+            lower: 0.5,
+            upper: 0.5,
+        });
+    }
+    if t.is_infinite() {
+        let p = if t > 0.0 { 0.0 } else { 1.0 };
+        return Ok(Interval { lower: p, upper: p });
+    }
+    let x = freedom / (freedom + t * t);
+    let y = t * t / (freedom + t * t);
+    let half = beta_reg_enclosure(freedom / 2.0, 0.5, x, y);
+    let tail = Interval {
+        lower: 0.5 * half.lower,
+        upper: 0.5 * half.upper,
+    };
+    Ok(if t > 0.0 { tail } else { complement(tail) })
+}
+
+/// Computes the upper tail probability `P(F > x)` of the F distribution
+/// with `freedom_1` and `freedom_2` degrees of freedom, enclosed in an
+/// interval
+///
+/// # Remarks
+///
+/// The tail is `I_y(d_2 / 2, d_1 / 2)` with `y = d_2 / (d_2 + d_1 x)`, and
+/// the enclosure accounts for the rounding of `y` as well as the error of
+/// the regularized incomplete beta function.
+///
+/// # Errors
+///
+/// Returns an error if either degrees of freedom is not positive and finite
+/// or if `x` is `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::certified::fisher_snedecor_sf;
+///
+/// let p = fisher_snedecor_sf(8.0, 3.0, 20.0).unwrap();
+/// assert!(p.contains(0.0010666272827782));
+/// ```
+pub fn fisher_snedecor_sf(x: f64, freedom_1: f64, freedom_2: f64) -> Result<Interval> {
+    if !freedom_1.is_finite() || freedom_1 <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("freedom_1"));
+    }
+    if !freedom_2.is_finite() || freedom_2 <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("freedom_2"));
+    }
+    if x.is_nan() {
+        return Err(StatsError::BadParams);
+    }
+    if x <= 0.0 {
+        return Ok(Interval {
+            lower: 1.0,
+            upper: 1.0,
+        });
+    }
+    if x == f64::INFINITY {
+        return Ok(Interval {
+            lower: 0.0,
+            upper: 0.0,
+        });
+    }
+    let y = freedom_2 / (freedom_2 + freedom_1 * x);
+    let y_c = freedom_1 * x / (freedom_2 + freedom_1 * x);
+    Ok(beta_reg_enclosure(freedom_2 / 2.0, freedom_1 / 2.0, y, y_c))
+}
+
+/// Returns the interval enclosing `1 - p` for `p` in `i`, widened by the
+/// rounding of the subtraction
+fn complement(i: Interval) -> Interval {
+    Interval {
+        lower: (1.0 - i.upper - f64::EPSILON).max(0.0),
+        upper: (1.0 - i.lower + f64::EPSILON).min(1.0),
+    }
+}
+
+/// Encloses the regularized incomplete beta function `I_x(a, b)` given `x`
+/// and `y = 1 - x`, each computed with a relative error of up to `4ε`.
+/// Past the mean the complement `1 - I_y(b, a)` is enclosed instead, as
+/// `beta_reg` would only compute `1 - x` itself.
+fn beta_reg_enclosure(a: f64, b: f64, x: f64, y: f64) -> Interval {
+    if x < (a + 1.0) / (a + b + 2.0) {
+        beta_reg_lower_enclosure(a, b, x, y)
+    } else {
+        complement(beta_reg_lower_enclosure(b, a, y, x))
+    }
+}
+
+/// Encloses `I_x(a, b)` for an `x` below the mean, where `beta_reg`
+/// evaluates its continued fraction directly
+fn beta_reg_lower_enclosure(a: f64, b: f64, x: f64, y: f64) -> Interval {
+    let ln_beta = gamma::ln_gamma(a) + gamma::ln_gamma(b) - gamma::ln_gamma(a + b);
+    let (ln_x, ln_y) = (fp::ln(x), fp::ln(y));
+    // beta_reg rounds an x within ε of zero to zero, where the leading term
+    // x^a (1 - x)^b / (a B(a, b)) of the series is accurate to a relative
+    // r / (1 - r) with r = (a + b) x / (a + 1)
+    let (value, truncation) = if x <= 2.0 * f64::EPSILON {
+        let r = (a + b) * x / (a + 1.0);
+        (fp::exp(a * ln_x + b * ln_y - ln_beta) / a, r / (1.0 - r))
+    } else {
+        (beta::beta_reg(a, b, x), 0.0)
+    };
+    let exponent = gamma::ln_gamma(a + b).abs()
+        + gamma::ln_gamma(a).abs()
+        + gamma::ln_gamma(b).abs()
+        + (a * ln_x).abs()
+        + (b * ln_y).abs();
+    let rel = 4.0 * exponent * f64::EPSILON
+        + (a + b) * f64::EPSILON
+        + ln_gamma_error(a)
+        + ln_gamma_error(b)
+        + ln_gamma_error(a + b)
+        + EXPANSION_ULPS * f64::EPSILON
+        + truncation;
+    // conditioning on x, |d I_x / d ln x| = x^a (1 - x)^(b - 1) / B(a, b)
+    let sensitivity = fp::exp(a * ln_x + (b - 1.0) * ln_y - ln_beta);
+    enclose(value, rel, 4.0 * f64::EPSILON * sensitivity)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(i: Interval, p: f64) {
+        assert!(i.contains(p), "{} not in [{}, {}]", p, i.lower(), i.upper());
+        // the enclosure is tight enough to be useful
+        assert!(i.width() <= 1e-8 * p + 3.0 * f64::MIN_POSITIVE, "[{}, {}] too wide for {}", i.lower(), i.upper(), p);
+    }
+
+    #[test]
+    fn test_interval() {
+        let i = Interval::new(0.1, 0.2).unwrap();
+        assert_eq!(i.lower(), 0.1);
+        assert_eq!(i.upper(), 0.2);
+        assert!(i.contains(0.15) && i.contains(0.1) && !i.contains(0.25));
+        assert!(i.is_below(0.21) && !i.is_below(0.2));
+        assert!(i.is_above(0.05) && !i.is_above(0.1));
+        assert!(Interval::new(0.2, 0.1).is_err());
+        assert!(Interval::new(f64::NAN, 0.1).is_err());
+    }
+
+    // reference values from mpmath with 50 digits
+    #[test]
+    fn test_encloses_references() {
+        let normal = [
+            (-5.0, 0.99999971334842812),
+            (-0.3, 0.61791142218895263),
+            (0.0, 0.5),
+            (0.2, 0.42074029056089697),
+            (1.0, 0.15865525393145705),
+            (2.0, 0.022750131948179207),
+            (3.0, 0.0013498980316300945),
+            (5.0, 2.8665157187919391e-7),
+            (8.0, 6.2209605742717841e-16),
+            (10.0, 7.6198530241605261e-24),
+            (15.0, 3.6709661993127509e-51),
+            (20.0, 2.7536241186062337e-89),
+            (30.0, 4.9067139271481871e-198),
+            (37.0, 5.7255712225245768e-300),
+        ];
+        for &(x, p) in normal.iter() {
+            check(normal_sf(x), p);
+        }
+        let chi_squared = [
+            (0.1, 1.0, 0.75182963404584928),
+            (0.5, 0.02, 0.010448418226994892),
+            (1.5, 0.3, 0.056778660575127202),
+            (3.84, 1.0, 0.050043521248705103),
+            (30.0, 4.0, 4.8944371280292126e-6),
+            (10.0, 10.0, 0.44049328506521241),
+            (120.0, 100.0, 0.08440668109369183),
+            (200.0, 100.0, 1.1784500720979422e-8),
+            (1500.0, 1000.0, 1.0454640385979657e-22),
+            (500.0, 3.0, 4.7716720319127585e-108),
+            (0.001, 50.0, 1.0),
+            (1300.0, 1000.0, 3.8897364994868525e-10),
+        ];
+        for &(x, k, p) in chi_squared.iter() {
+            check(chi_squared_sf(x, k).unwrap(), p);
+        }
+        let students_t = [
+            (0.001, 10.0, 0.49961089168737049),
+            (-2.0, 5.0, 0.94903026058507082),
+            (1.0, 1.0, 0.25),
+            (2.5, 3.0, 0.043853323504032774),
+            (4.0, 10.0, 0.0012591663123683461),
+            (10.0, 2.0, 0.0049262285116628454),
+            (50.0, 30.0, 9.3577088296113563e-31),
+            (6.0, 10000.0, 1.0208092364613077e-9),
+            (2.0, 0.5, 0.22275744509156562),
+            (100000.0, 1.0, 3.1830988617318034e-6),
+            (-40.0, 12.0, 0.99999999999998074),
+        ];
+        for &(t, v, p) in students_t.iter() {
+            check(students_t_sf(t, v).unwrap(), p);
+        }
+        // the error bounds of ln_gamma grow with ν / 2
+        let p = 2.8669989354453708e-7;
+        let i = students_t_sf(5.0, 1e6).unwrap();
+        assert!(i.contains(p) && i.width() < 1e-5 * p);
+        let fisher_snedecor = [
+            (8.0, 3.0, 20.0, 0.0010666272827782088),
+            (0.5, 2.0, 2.0, 0.66666666666666667),
+            (1.0, 10.0, 10.0, 0.5),
+            (50.0, 5.0, 100.0, 1.0553494582665974e-25),
+            (3.0, 1.0, 1000.0, 0.083572877130776914),
+            (0.01, 4.0, 7.0, 0.99974817070327159),
+            (200.0, 2.0, 3.0, 0.00064227987306295993),
+        ];
+        for &(x, d1, d2, p) in fisher_snedecor.iter() {
+            check(fisher_snedecor_sf(x, d1, d2).unwrap(), p);
+        }
+    }
+}
@@ -0,0 +1,572 @@
+//! Provides Bayesian inference with conjugate priors, whose posteriors
+//! after observing data are in the same family as the priors and are
+//! updated in closed form from sufficient statistics of the data
+
+use crate::distribution::{
+    Beta, BetaBinomial, ContinuousCDF, Gamma, InverseGamma, NegativeBinomial, Normal, StudentsT,
+};
+use crate::{Result, StatsError};
+
+/// Returns the equal-tailed interval holding probability `level` of the
+/// continuous distribution `dist`
+fn equal_tailed<D: ContinuousCDF<f64, f64>>(dist: &D, level: f64) -> Result<(f64, f64)> {
+    if level.is_nan() || level <= 0.0 || level >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("level", 0.0, 1.0));
+    }
+    let tail = (1.0 - level) / 2.0;
+    Ok((dist.inverse_cdf(tail), dist.inverse_cdf(1.0 - tail)))
+}
+
+/// Checks that `x` is positive and finite
+fn check_positive(x: f64, name: &'static str) -> Result<()> {
+    if x.is_finite() && x > 0.0 {
+        Ok(())
+    } else {
+        Err(StatsError::ArgMustBePositive(name))
+    }
+}
+
+/// The beta prior of the probability of success of binomial observations
+/// with a fixed number of trials each, the beta-binomial model
+///
+/// # Examples
+///
+/// ```
+/// use statrs::conjugate::BetaBinomialModel;
+/// use statrs::statistics::Distribution;
+///
+/// // a uniform prior for single trials, updated with 7 successes in 10
+/// let mut model = BetaBinomialModel::new(1.0, 1.0, 1).unwrap();
+/// model.update(&[1, 1, 0, 1, 1, 0, 1, 1, 0, 1]).unwrap();
+/// assert_eq!((model.alpha(), model.beta()), (8.0, 4.0));
+/// assert_eq!(model.posterior().mean().unwrap(), 8.0 / 12.0);
+/// let (lower, upper) = model.credible_interval(0.95).unwrap();
+/// assert!(lower < 0.7 && upper > 0.7);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BetaBinomialModel {
+    alpha: f64,
+    beta: f64,
+    trials: u64,
+}
+
+impl BetaBinomialModel {
+    /// Constructs the model with the prior `Beta(alpha, beta)` of the
+    /// probability of success of observations counting the successes in
+    /// `trials` trials
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `alpha` or `beta` are not positive and finite
+    /// or if `trials` is zero
+    pub fn new(alpha: f64, beta: f64, trials: u64) -> Result<BetaBinomialModel> {
+        check_positive(alpha, "alpha")?;
+        check_positive(beta, "beta")?;
+        if trials == 0 {
+            return Err(StatsError::ArgMustBePositive("trials"));
+        }
+        Ok(BetaBinomialModel {
+            alpha,
+            beta,
+            trials,
+        })
+    }
+
+    /// Updates the posterior with observations of the number of successes,
+    /// adding the successes to `alpha` and the failures to `beta`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the posterior unchanged, if an observation
+    /// exceeds the number of trials
+    pub fn update(&mut self, data: &[u64]) -> Result<()> {
+        if data.iter().any(|&x| x > self.trials) {
+            return Err(StatsError::ArgLte("data", self.trials as f64));
+        }
+        let successes: u64 = data.iter().sum();
+        self.alpha += successes as f64;
+        self.beta += (data.len() as u64 * self.trials - successes) as f64;
+        Ok(())
+    }
+
+    /// Returns the first shape of the posterior
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Returns the second shape of the posterior
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Returns the number of trials of each observation
+    pub fn trials(&self) -> u64 {
+        self.trials
+    }
+
+    /// Returns the posterior of the probability of success
+    pub fn posterior(&self) -> Beta {
+        Beta::new(self.alpha, self.beta).unwrap()
+    }
+
+    /// Returns the posterior predictive distribution of the next
+    /// observation, the beta-binomial distribution
+    pub fn predictive(&self) -> BetaBinomial {
+        BetaBinomial::new(self.trials, self.alpha, self.beta).unwrap()
+    }
+
+    /// Returns the equal-tailed posterior credible interval of the
+    /// probability of success holding probability `level`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)`
+    pub fn credible_interval(&self, level: f64) -> Result<(f64, f64)> {
+        equal_tailed(&self.posterior(), level)
+    }
+}
+
+/// The gamma prior of the rate of Poisson observations, the gamma-Poisson
+/// model
+///
+/// # Examples
+///
+/// ```
+/// use statrs::conjugate::GammaPoissonModel;
+/// use statrs::statistics::{DiscreteDistribution, Distribution};
+///
+/// let mut model = GammaPoissonModel::new(2.0, 1.0).unwrap();
+/// model.update(&[3, 5, 4]).unwrap();
+/// assert_eq!((model.shape(), model.rate()), (14.0, 4.0));
+/// assert_eq!(model.posterior().mean().unwrap(), 3.5);
+/// assert!((model.predictive().mean().unwrap() - 3.5).abs() < 1e-14);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GammaPoissonModel {
+    shape: f64,
+    rate: f64,
+}
+
+impl GammaPoissonModel {
+    /// Constructs the model with the prior `Gamma(shape, rate)` of the
+    /// Poisson rate
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `shape` or `rate` are not positive and finite
+    pub fn new(shape: f64, rate: f64) -> Result<GammaPoissonModel> {
+        check_positive(shape, "shape")?;
+        check_positive(rate, "rate")?;
+        Ok(GammaPoissonModel { shape, rate })
+    }
+
+    /// Updates the posterior with Poisson counts, adding their sum to the
+    /// shape and their number to the rate
+    ///
+    /// # Errors
+    ///
+    /// Never returns an error; the signature matches the other models
+    pub fn update(&mut self, data: &[u64]) -> Result<()> {
+        self.shape += data.iter().sum::<u64>() as f64;
+        self.rate += data.len() as f64;
+        Ok(())
+    }
+
+    /// Returns the shape of the posterior
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the rate of the posterior
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the posterior of the Poisson rate
+    pub fn posterior(&self) -> Gamma {
+        Gamma::new(self.shape, self.rate).unwrap()
+    }
+
+    /// Returns the posterior predictive distribution of the next count, the
+    /// negative binomial distribution with `r = shape` and
+    /// `p = rate / (rate + 1)`
+    pub fn predictive(&self) -> NegativeBinomial {
+        NegativeBinomial::new(self.shape, self.rate / (self.rate + 1.0)).unwrap()
+    }
+
+    /// Returns the equal-tailed posterior credible interval of the Poisson
+    /// rate holding probability `level`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)`
+    pub fn credible_interval(&self, level: f64) -> Result<(f64, f64)> {
+        equal_tailed(&self.posterior(), level)
+    }
+}
+
+/// The normal prior of the mean of normal observations with a known
+/// standard deviation
+///
+/// # Examples
+///
+/// ```
+/// use statrs::conjugate::NormalKnownVarianceModel;
+/// use statrs::statistics::Distribution;
+///
+/// // a prior as informative as a single observation
+/// let mut model = NormalKnownVarianceModel::new(0.0, 2.0, 2.0).unwrap();
+/// model.update(&[3.0]).unwrap();
+/// assert_eq!(model.mean(), 1.5);
+/// assert_eq!(model.std_dev(), 2f64.sqrt());
+/// assert!((model.predictive().variance().unwrap() - 6.0).abs() < 1e-14);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NormalKnownVarianceModel {
+    mean: f64,
+    precision: f64,
+    sigma: f64,
+}
+
+impl NormalKnownVarianceModel {
+    /// Constructs the model with the prior `N(mean, std_dev²)` of the mean
+    /// of observations with standard deviation `sigma`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mean` is not finite or if `std_dev` or `sigma`
+    /// are not positive and finite
+    pub fn new(mean: f64, std_dev: f64, sigma: f64) -> Result<NormalKnownVarianceModel> {
+        if !mean.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        check_positive(std_dev, "std_dev")?;
+        check_positive(sigma, "sigma")?;
+        Ok(NormalKnownVarianceModel {
+            mean,
+            precision: 1.0 / (std_dev * std_dev),
+            sigma,
+        })
+    }
+
+    /// Updates the posterior with observations, adding `n / σ²` to the
+    /// precision and moving the mean to the precision-weighted average of
+    /// the prior mean and the sample mean
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the posterior unchanged, if an observation
+    /// is not finite
+    pub fn update(&mut self, data: &[f64]) -> Result<()> {
+        if data.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        let data_precision = data.len() as f64 / (self.sigma * self.sigma);
+        let sum: f64 = data.iter().sum();
+        let precision = self.precision + data_precision;
+        self.mean = (self.precision * self.mean + sum / (self.sigma * self.sigma)) / precision;
+        self.precision = precision;
+        Ok(())
+    }
+
+    /// Returns the mean of the posterior
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the standard deviation of the posterior
+    pub fn std_dev(&self) -> f64 {
+        self.precision.recip().sqrt()
+    }
+
+    /// Returns the known standard deviation of the observations
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Returns the posterior of the mean
+    pub fn posterior(&self) -> Normal {
+        Normal::new(self.mean, self.std_dev()).unwrap()
+    }
+
+    /// Returns the posterior predictive distribution of the next
+    /// observation, the normal distribution whose variance is the sum of
+    /// the posterior and observation variances
+    pub fn predictive(&self) -> Normal {
+        let variance = self.precision.recip() + self.sigma * self.sigma;
+        Normal::new(self.mean, variance.sqrt()).unwrap()
+    }
+
+    /// Returns the equal-tailed posterior credible interval of the mean
+    /// holding probability `level`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)`
+    pub fn credible_interval(&self, level: f64) -> Result<(f64, f64)> {
+        equal_tailed(&self.posterior(), level)
+    }
+}
+
+/// The normal-inverse-gamma prior of the mean and variance of normal
+/// observations, under which the variance `σ²` has the inverse gamma
+/// distribution with `shape` and `rate` and, given the variance, the mean
+/// is normal with mean `mean` and variance `σ² / kappa`
+///
+/// # Remarks
+///
+/// The marginal posteriors of the mean and of the next observation are
+/// location-scale Student's t distributions with `2 shape` degrees of
+/// freedom. Updates accumulate the sample mean and sum of squared
+/// deviations of each batch of data, so updating with several batches is
+/// the same as updating with their concatenation.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::conjugate::NormalInverseGammaModel;
+/// use statrs::distribution::ContinuousCDF;
+///
+/// let mut model = NormalInverseGammaModel::new(0.0, 1.0, 1.0, 1.0).unwrap();
+/// model.update(&[1.0, 2.0, 3.0]).unwrap();
+/// assert_eq!(model.mean(), 1.5);
+/// assert_eq!((model.kappa(), model.shape()), (4.0, 2.5));
+/// assert_eq!(model.rate(), 3.5);
+/// let t = model.posterior_mean();
+/// assert!((t.cdf(1.5) - 0.5).abs() < 1e-15);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NormalInverseGammaModel {
+    mean: f64,
+    kappa: f64,
+    shape: f64,
+    rate: f64,
+}
+
+impl NormalInverseGammaModel {
+    /// Constructs the model with the prior of the mean `N(mean, σ² /
+    /// kappa)` given the variance `σ²`, whose prior is
+    /// `InverseGamma(shape, rate)`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mean` is not finite or if `kappa`, `shape` or
+    /// `rate` are not positive and finite
+    pub fn new(mean: f64, kappa: f64, shape: f64, rate: f64) -> Result<NormalInverseGammaModel> {
+        if !mean.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        check_positive(kappa, "kappa")?;
+        check_positive(shape, "shape")?;
+        check_positive(rate, "rate")?;
+        Ok(NormalInverseGammaModel {
+            mean,
+            kappa,
+            shape,
+            rate,
+        })
+    }
+
+    /// Updates the posterior with observations
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// kappa' = kappa + n
+    /// mean' = (kappa mean + n x̄) / kappa'
+    /// shape' = shape + n / 2
+    /// rate' = rate + S / 2 + kappa n (x̄ - mean)² / (2 kappa')
+    /// ```
+    ///
+    /// where `x̄` is the sample mean and `S` the sum of squared deviations
+    /// from it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the posterior unchanged, if an observation
+    /// is not finite
+    pub fn update(&mut self, data: &[f64]) -> Result<()> {
+        if data.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        if data.is_empty() {
+            return Ok(());
+        }
+        let n = data.len() as f64;
+        let sample_mean = data.iter().sum::<f64>() / n;
+        let squares: f64 = data
+            .iter()
+            .map(|x| (x - sample_mean) * (x - sample_mean))
+            .sum();
+        let kappa = self.kappa + n;
+        let shift = sample_mean - self.mean;
+        self.rate += squares / 2.0 + self.kappa * n * shift * shift / (2.0 * kappa);
+        self.mean = (self.kappa * self.mean + n * sample_mean) / kappa;
+        self.kappa = kappa;
+        self.shape += n / 2.0;
+        Ok(())
+    }
+
+    /// Returns the posterior mean of the mean
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the number of pseudo-observations the posterior of the mean
+    /// is worth
+    pub fn kappa(&self) -> f64 {
+        self.kappa
+    }
+
+    /// Returns the shape of the posterior of the variance
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the rate of the posterior of the variance
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the marginal posterior of the mean, the Student's t
+    /// distribution with location `mean`, scale `sqrt(rate / (shape kappa))`
+    /// and `2 shape` degrees of freedom
+    pub fn posterior_mean(&self) -> StudentsT {
+        let scale = (self.rate / (self.shape * self.kappa)).sqrt();
+        StudentsT::new(self.mean, scale, 2.0 * self.shape).unwrap()
+    }
+
+    /// Returns the marginal posterior of the variance
+    pub fn posterior_variance(&self) -> InverseGamma {
+        InverseGamma::new(self.shape, self.rate).unwrap()
+    }
+
+    /// Returns the posterior predictive distribution of the next
+    /// observation, the Student's t distribution with location `mean`,
+    /// scale `sqrt(rate (kappa + 1) / (shape kappa))` and `2 shape` degrees
+    /// of freedom
+    pub fn predictive(&self) -> StudentsT {
+        let scale = (self.rate * (self.kappa + 1.0) / (self.shape * self.kappa)).sqrt();
+        StudentsT::new(self.mean, scale, 2.0 * self.shape).unwrap()
+    }
+
+    /// Returns the equal-tailed credible interval of the mean holding
+    /// posterior probability `level`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)`
+    pub fn credible_interval(&self, level: f64) -> Result<(f64, f64)> {
+        equal_tailed(&self.posterior_mean(), level)
+    }
+
+    /// Returns the equal-tailed credible interval of the variance holding
+    /// posterior probability `level`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)`
+    pub fn variance_credible_interval(&self, level: f64) -> Result<(f64, f64)> {
+        equal_tailed(&self.posterior_variance(), level)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Continuous, Discrete};
+    use crate::statistics::{DiscreteDistribution, Distribution};
+
+    #[test]
+    fn test_beta_binomial() {
+        let mut model = BetaBinomialModel::new(2.0, 3.0, 10).unwrap();
+        model.update(&[3, 7]).unwrap();
+        model.update(&[]).unwrap();
+        assert_eq!((model.alpha(), model.beta(), model.trials()), (12.0, 13.0, 10));
+        assert!(model.update(&[11]).is_err());
+        assert_eq!((model.alpha(), model.beta()), (12.0, 13.0));
+        let predictive = model.predictive();
+        assert_eq!(predictive.n(), 10);
+        assert_almost_eq!(predictive.mean().unwrap(), 4.8, 1e-14);
+        // mpmath: betaincinv(12, 13, 0.025) and betaincinv(12, 13, 0.975)
+        let (lower, upper) = model.credible_interval(0.95).unwrap();
+        assert_almost_eq!(lower, 0.29124177983621195, 1e-12);
+        assert_almost_eq!(upper, 0.671791924304822, 1e-12);
+        assert!(model.credible_interval(1.0).is_err());
+        assert!(model.credible_interval(f64::NAN).is_err());
+        assert!(BetaBinomialModel::new(0.0, 1.0, 1).is_err());
+        assert!(BetaBinomialModel::new(1.0, 1.0, 0).is_err());
+    }
+
+    #[test]
+    fn test_gamma_poisson() {
+        let mut model = GammaPoissonModel::new(2.0, 0.5).unwrap();
+        model.update(&[0, 4, 2, 6]).unwrap();
+        assert_eq!((model.shape(), model.rate()), (14.0, 4.5));
+        // the predictive is the gamma mixture of Poissons
+        let predictive = model.predictive();
+        let gamma = model.posterior();
+        let step = 1e-3;
+        let mixture: f64 = (1..40000).map(|i| {
+            let lambda = i as f64 * step;
+            gamma.pdf(lambda) * (-lambda + 3.0 * lambda.ln()).exp() / 6.0
+        }).sum::<f64>() * step;
+        assert_almost_eq!(predictive.pmf(3), mixture, 1e-8);
+        assert_almost_eq!(predictive.variance().unwrap(), 14.0 / 4.5 * (1.0 + 1.0 / 4.5), 1e-13);
+        // mpmath: the 5% and 95% quantiles of Gamma(14, 4.5)
+        let (lower, upper) = model.credible_interval(0.9).unwrap();
+        assert_almost_eq!(lower, 1.8808750049358328, 1e-10);
+        assert_almost_eq!(upper, 4.593015350158599, 1e-10);
+        assert!(GammaPoissonModel::new(1.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_normal_known_variance() {
+        let mut model = NormalKnownVarianceModel::new(1.0, 2.0, 3.0).unwrap();
+        let data = [2.0, 4.5, 3.5, 5.0];
+        model.update(&data[..2]).unwrap();
+        model.update(&data[2..]).unwrap();
+        // precisions 1/4 and 4/9
+        let precision = 0.25 + 4.0 / 9.0;
+        assert_almost_eq!(model.mean(), (0.25 * 1.0 + 15.0 / 9.0) / precision, 1e-14);
+        assert_almost_eq!(model.std_dev(), precision.recip().sqrt(), 1e-15);
+        assert_almost_eq!(model.predictive().std_dev().unwrap(), (precision.recip() + 9.0).sqrt(), 1e-14);
+        let (lower, upper) = model.credible_interval(0.95).unwrap();
+        assert_almost_eq!(lower, model.mean() - 1.959963984540054 * model.std_dev(), 1e-9);
+        assert_almost_eq!(upper, model.mean() + 1.959963984540054 * model.std_dev(), 1e-9);
+        assert!(model.update(&[f64::NAN]).is_err());
+        assert!(NormalKnownVarianceModel::new(f64::NAN, 1.0, 1.0).is_err());
+        assert!(NormalKnownVarianceModel::new(0.0, 1.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_normal_inverse_gamma() {
+        let data = [4.1, 5.3, 3.8, 6.0, 5.1, 4.4];
+        let mut batched = NormalInverseGammaModel::new(5.0, 2.0, 3.0, 4.0).unwrap();
+        batched.update(&data[..1]).unwrap();
+        batched.update(&data[1..4]).unwrap();
+        batched.update(&data[4..]).unwrap();
+        let mut model = NormalInverseGammaModel::new(5.0, 2.0, 3.0, 4.0).unwrap();
+        model.update(&data).unwrap();
+        assert_almost_eq!(batched.mean(), model.mean(), 1e-14);
+        assert_almost_eq!(batched.rate(), model.rate(), 1e-13);
+        assert_eq!((model.kappa(), model.shape()), (8.0, 6.0));
+        let mean = data.iter().sum::<f64>() / 6.0;
+        let squares: f64 = data.iter().map(|x| (x - mean) * (x - mean)).sum();
+        assert_almost_eq!(model.mean(), (10.0 + data.iter().sum::<f64>()) / 8.0, 1e-14);
+        assert_almost_eq!(model.rate(), 4.0 + squares / 2.0 + 12.0 * (mean - 5.0).powi(2) / 16.0, 1e-13);
+        let t = model.posterior_mean();
+        assert_eq!(t.freedom(), 12.0);
+        assert_almost_eq!(t.scale(), (model.rate() / 48.0).sqrt(), 1e-15);
+        assert_almost_eq!(model.predictive().scale(), (model.rate() * 9.0 / 48.0).sqrt(), 1e-15);
+        let (lower, upper) = model.credible_interval(0.95).unwrap();
+        assert_almost_eq!(lower, model.mean() - crate::critical_values::students_t(12.0, 0.025).unwrap() * t.scale(), 1e-9);
+        assert_almost_eq!(upper + lower, 2.0 * model.mean(), 1e-12);
+        let (lower, upper) = model.variance_credible_interval(0.5).unwrap();
+        let posterior = model.posterior_variance();
+        assert_almost_eq!(posterior.cdf(lower), 0.25, 1e-12);
+        assert_almost_eq!(posterior.cdf(upper), 0.75, 1e-12);
+        assert!(NormalInverseGammaModel::new(0.0, 0.0, 1.0, 1.0).is_err());
+    }
+}
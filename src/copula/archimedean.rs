@@ -0,0 +1,473 @@
+use crate::copula::fit::{self, CopulaFit, OneParameterFamily};
+use crate::copula::{clamp_unit, Copula};
+use crate::distribution::{Exp, Gamma};
+use crate::fp;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// Implements the [Clayton
+/// copula](https://en.wikipedia.org/wiki/Copula_(probability_theory)#Most_important_Archimedean_copulas)
+/// with parameter `θ > 0`, an Archimedean copula with lower tail dependence
+///
+/// # Examples
+///
+/// ```
+/// use statrs::copula::{Clayton, Copula};
+///
+/// let c = Clayton::new(2.0).unwrap();
+/// assert!((c.cdf(0.5, 0.5) - 1.0 / 7f64.sqrt()).abs() < 1e-15);
+/// assert_eq!(c.kendall_tau(), 0.5);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Clayton {
+    theta: f64,
+}
+
+impl Clayton {
+    /// Constructs a new Clayton copula with parameter `theta`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `theta` is `NaN`, infinite or not positive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::copula::Clayton;
+    ///
+    /// let mut result = Clayton::new(1.5);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Clayton::new(0.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(theta: f64) -> Result<Clayton> {
+        if !theta.is_finite() || theta <= 0.0 {
+            Err(StatsError::ArgMustBePositive("theta"))
+        } else {
+            Ok(Clayton { theta })
+        }
+    }
+
+    /// Fits a Clayton copula to the paired samples `x` and `y` by
+    /// maximizing the pseudo-likelihood of their pseudo-observations
+    ///
+    /// # Remarks
+    ///
+    /// The standard error is derived from the observed information of the
+    /// pseudo-likelihood and does not account for the estimation of the
+    /// margins by ranks, so it tends to be somewhat optimistic
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` and `y` differ in length, contain fewer than
+    /// two observations or contain a `NaN`, or if the maximization fails
+    /// (e.g. for perfectly dependent data)
+    pub fn fit(x: &[f64], y: &[f64]) -> Result<CopulaFit<Clayton>> {
+        fit::fit_mle(x, y)
+    }
+
+    /// Fits a Clayton copula to the paired samples `x` and `y` by inverting
+    /// their sample Kendall's tau, `θ = 2 * τ / (1 - τ)`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` and `y` differ in length, contain fewer than
+    /// two observations or contain a `NaN`, or if the sample Kendall's tau
+    /// is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::copula::Clayton;
+    ///
+    /// let fit = Clayton::fit_tau(&[1.0, 2.0, 3.0, 4.0], &[2.0, 1.0, 4.0, 3.0]).unwrap();
+    /// assert!((fit.parameter() - 1.0).abs() < 1e-15);
+    /// ```
+    pub fn fit_tau(x: &[f64], y: &[f64]) -> Result<CopulaFit<Clayton>> {
+        fit::fit_tau(x, y)
+    }
+
+    /// Returns the parameter of the Clayton copula
+    pub fn theta(&self) -> f64 {
+        self.theta
+    }
+
+    /// Returns Kendall's tau of the Clayton copula
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// θ / (θ + 2)
+    /// ```
+    pub fn kendall_tau(&self) -> f64 {
+        self.theta / (self.theta + 2.0)
+    }
+
+    /// Calculates the copula density at `u` and `v`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 + θ) * (u * v)^(-θ - 1) * (u^-θ + v^-θ - 1)^(-2 - 1 / θ)
+    /// ```
+    pub fn pdf(&self, u: f64, v: f64) -> f64 {
+        fp::exp(self.ln_pdf(u, v))
+    }
+
+    /// Calculates the log of the copula density at `u` and `v`
+    pub fn ln_pdf(&self, u: f64, v: f64) -> f64 {
+        let t = self.theta;
+        let s = fp::powf(u, -t) + fp::powf(v, -t) - 1.0;
+        fp::ln_1p(t) - (t + 1.0) * (fp::ln(u) + fp::ln(v)) - (2.0 + 1.0 / t) * fp::ln(s)
+    }
+}
+
+impl Copula for Clayton {
+    /// Calculates the Clayton copula at `u` and `v`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (u^-θ + v^-θ - 1)^(-1 / θ)
+    /// ```
+    fn cdf(&self, u: f64, v: f64) -> f64 {
+        let (u, v) = (clamp_unit(u), clamp_unit(v));
+        if u == 0.0 || v == 0.0 {
+            return 0.0;
+        }
+        fp::powf(
+            fp::powf(u, -self.theta) + fp::powf(v, -self.theta) - 1.0,
+            -1.0 / self.theta,
+        )
+    }
+}
+
+impl ::rand::distributions::Distribution<(f64, f64)> for Clayton {
+    /// Samples from the Clayton copula with the Marshall-Olkin algorithm,
+    /// mixing independent exponential variables over a gamma frailty
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (f64, f64) {
+        let frailty = rng.sample(Gamma::new(1.0 / self.theta, 1.0).unwrap());
+        let exp = Exp::new(1.0).unwrap();
+        let generator = |e: f64| fp::exp(-fp::ln_1p(e / frailty) / self.theta);
+        (generator(rng.sample(exp)), generator(rng.sample(exp)))
+    }
+}
+
+impl OneParameterFamily for Clayton {
+    fn from_parameter(theta: f64) -> Result<Self> {
+        Clayton::new(theta)
+    }
+
+    fn ln_density(&self, u: f64, v: f64) -> f64 {
+        self.ln_pdf(u, v)
+    }
+
+    fn tau_to_parameter(tau: f64) -> Option<f64> {
+        if tau > 0.0 && tau < 1.0 {
+            Some(2.0 * tau / (1.0 - tau))
+        } else {
+            None
+        }
+    }
+
+    fn tau_to_parameter_derivative(tau: f64) -> f64 {
+        2.0 / ((1.0 - tau) * (1.0 - tau))
+    }
+
+    fn from_unconstrained(eta: f64) -> f64 {
+        fp::exp(eta)
+    }
+
+    fn to_unconstrained(theta: f64) -> f64 {
+        fp::ln(theta)
+    }
+
+    fn from_unconstrained_derivative(eta: f64) -> f64 {
+        fp::exp(eta)
+    }
+}
+
+/// Implements the [Gumbel
+/// copula](https://en.wikipedia.org/wiki/Copula_(probability_theory)#Most_important_Archimedean_copulas)
+/// with parameter `θ >= 1`, an Archimedean extreme value copula with upper
+/// tail dependence
+///
+/// # Examples
+///
+/// ```
+/// use statrs::copula::{Copula, Gumbel};
+///
+/// let c = Gumbel::new(1.0).unwrap();
+/// assert!((c.cdf(0.3, 0.5) - 0.15).abs() < 1e-15);
+/// assert_eq!(Gumbel::new(2.0).unwrap().kendall_tau(), 0.5);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Gumbel {
+    theta: f64,
+}
+
+impl Gumbel {
+    /// Constructs a new Gumbel copula with parameter `theta`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `theta` is `NaN`, infinite or less than `1`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::copula::Gumbel;
+    ///
+    /// let mut result = Gumbel::new(1.5);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Gumbel::new(0.5);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(theta: f64) -> Result<Gumbel> {
+        if !theta.is_finite() || theta < 1.0 {
+            Err(StatsError::ArgGte("theta", 1.0))
+        } else {
+            Ok(Gumbel { theta })
+        }
+    }
+
+    /// Fits a Gumbel copula to the paired samples `x` and `y` by
+    /// maximizing the pseudo-likelihood of their pseudo-observations
+    ///
+    /// # Remarks
+    ///
+    /// The standard error is derived from the observed information of the
+    /// pseudo-likelihood and does not account for the estimation of the
+    /// margins by ranks, so it tends to be somewhat optimistic
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` and `y` differ in length, contain fewer than
+    /// two observations or contain a `NaN`, or if the maximization fails
+    /// (e.g. for perfectly dependent data)
+    pub fn fit(x: &[f64], y: &[f64]) -> Result<CopulaFit<Gumbel>> {
+        fit::fit_mle(x, y)
+    }
+
+    /// Fits a Gumbel copula to the paired samples `x` and `y` by inverting
+    /// their sample Kendall's tau, `θ = 1 / (1 - τ)`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` and `y` differ in length, contain fewer than
+    /// two observations or contain a `NaN`, or if the sample Kendall's tau
+    /// is not in `[0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::copula::Gumbel;
+    ///
+    /// let fit = Gumbel::fit_tau(&[1.0, 2.0, 3.0, 4.0], &[2.0, 1.0, 4.0, 3.0]).unwrap();
+    /// assert!((fit.parameter() - 1.5).abs() < 1e-15);
+    /// ```
+    pub fn fit_tau(x: &[f64], y: &[f64]) -> Result<CopulaFit<Gumbel>> {
+        fit::fit_tau(x, y)
+    }
+
+    /// Returns the parameter of the Gumbel copula
+    pub fn theta(&self) -> f64 {
+        self.theta
+    }
+
+    /// Returns Kendall's tau of the Gumbel copula
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 1 - 1 / θ
+    /// ```
+    pub fn kendall_tau(&self) -> f64 {
+        1.0 - 1.0 / self.theta
+    }
+
+    /// Calculates the copula density at `u` and `v`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// C(u, v) * (x * y)^(θ - 1) * s^(1 / θ - 2) * (s^(1 / θ) + θ - 1) / (u * v)
+    /// ```
+    ///
+    /// where `x = -ln(u)`, `y = -ln(v)`, `s = x^θ + y^θ` and `C` is the
+    /// Gumbel copula
+    pub fn pdf(&self, u: f64, v: f64) -> f64 {
+        fp::exp(self.ln_pdf(u, v))
+    }
+
+    /// Calculates the log of the copula density at `u` and `v`
+    pub fn ln_pdf(&self, u: f64, v: f64) -> f64 {
+        let t = self.theta;
+        let (x, y) = (-fp::ln(u), -fp::ln(v));
+        let s = fp::powf(x, t) + fp::powf(y, t);
+        let a = fp::powf(s, 1.0 / t);
+        -a + x
+            + y
+            + (t - 1.0) * (fp::ln(x) + fp::ln(y))
+            + (1.0 / t - 2.0) * fp::ln(s)
+            + fp::ln(a + t - 1.0)
+    }
+}
+
+impl Copula for Gumbel {
+    /// Calculates the Gumbel copula at `u` and `v`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// exp(-((-ln(u))^θ + (-ln(v))^θ)^(1 / θ))
+    /// ```
+    fn cdf(&self, u: f64, v: f64) -> f64 {
+        let (u, v) = (clamp_unit(u), clamp_unit(v));
+        if u == 0.0 || v == 0.0 {
+            return 0.0;
+        }
+        let s = fp::powf(-fp::ln(u), self.theta) + fp::powf(-fp::ln(v), self.theta);
+        fp::exp(-fp::powf(s, 1.0 / self.theta))
+    }
+}
+
+impl ::rand::distributions::Distribution<(f64, f64)> for Gumbel {
+    /// Samples from the Gumbel copula with the Marshall-Olkin algorithm,
+    /// mixing independent exponential variables over a positive stable
+    /// frailty generated by the Chambers-Mallows-Stuck method
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (f64, f64) {
+        let alpha = 1.0 / self.theta;
+        let exp = Exp::new(1.0).unwrap();
+        let angle = PI * rng.gen::<f64>();
+        let w = rng.sample(exp);
+        let frailty = fp::sin(alpha * angle) / fp::powf(fp::sin(angle), 1.0 / alpha)
+            * fp::powf(fp::sin((1.0 - alpha) * angle) / w, (1.0 - alpha) / alpha);
+        let generator = |e: f64| fp::exp(-fp::powf(e / frailty, alpha));
+        (generator(rng.sample(exp)), generator(rng.sample(exp)))
+    }
+}
+
+impl OneParameterFamily for Gumbel {
+    fn from_parameter(theta: f64) -> Result<Self> {
+        Gumbel::new(theta)
+    }
+
+    fn ln_density(&self, u: f64, v: f64) -> f64 {
+        self.ln_pdf(u, v)
+    }
+
+    fn tau_to_parameter(tau: f64) -> Option<f64> {
+        if (0.0..1.0).contains(&tau) {
+            Some(1.0 / (1.0 - tau))
+        } else {
+            None
+        }
+    }
+
+    fn tau_to_parameter_derivative(tau: f64) -> f64 {
+        1.0 / ((1.0 - tau) * (1.0 - tau))
+    }
+
+    fn from_unconstrained(eta: f64) -> f64 {
+        1.0 + fp::exp(eta)
+    }
+
+    fn to_unconstrained(theta: f64) -> f64 {
+        fp::ln(theta - 1.0)
+    }
+
+    fn from_unconstrained_derivative(eta: f64) -> f64 {
+        fp::exp(eta)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn numeric_density<C: Copula>(c: &C, u: f64, v: f64) -> f64 {
+        let h = 1e-4;
+        (c.cdf(u + h, v + h) - c.cdf(u + h, v - h) - c.cdf(u - h, v + h) + c.cdf(u - h, v - h)) / (4.0 * h * h)
+    }
+
+    #[test]
+    fn test_bad_create() {
+        assert!(Clayton::new(f64::NAN).is_err());
+        assert!(Clayton::new(-1.0).is_err());
+        assert!(Clayton::new(f64::INFINITY).is_err());
+        assert!(Gumbel::new(f64::NAN).is_err());
+        assert!(Gumbel::new(0.99).is_err());
+        assert!(Gumbel::new(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_cdf() {
+        let c = Clayton::new(1.0).unwrap();
+        assert_almost_eq!(c.cdf(0.5, 0.25), 1.0 / 5.0, 1e-15);
+        assert_eq!(c.cdf(0.0, 0.5), 0.0);
+        assert_almost_eq!(c.cdf(1.0, 0.3), 0.3, 1e-15);
+        let g = Gumbel::new(2.0).unwrap();
+        let (x, y) = (-(0.3f64.ln()), -(0.6f64.ln()));
+        assert_almost_eq!(g.cdf(0.3, 0.6), (-(x * x + y * y).sqrt()).exp(), 1e-15);
+        assert_eq!(g.cdf(0.0, 0.5), 0.0);
+        assert_almost_eq!(g.cdf(1.0, 0.3), 0.3, 1e-15);
+    }
+
+    #[test]
+    fn test_pdf() {
+        for &(u, v) in &[(0.2, 0.3), (0.5, 0.5), (0.8, 0.4), (0.9, 0.95)] {
+            let c = Clayton::new(2.5).unwrap();
+            assert_almost_eq!(c.pdf(u, v), numeric_density(&c, u, v), 1e-5);
+            let g = Gumbel::new(1.7).unwrap();
+            assert_almost_eq!(g.pdf(u, v), numeric_density(&g, u, v), 1e-5);
+        }
+        assert_almost_eq!(Gumbel::new(1.0).unwrap().pdf(0.3, 0.7), 1.0, 1e-14);
+    }
+
+    #[test]
+    fn test_sample_kendall_tau() {
+        use crate::copula::EmpiricalCopula;
+        let mut rng = StdRng::seed_from_u64(5);
+        let c = Clayton::new(2.0).unwrap();
+        let (x, y): (Vec<f64>, Vec<f64>) = (0..2000).map(|_| c.sample(&mut rng)).unzip();
+        assert_almost_eq!(EmpiricalCopula::new(&x, &y).unwrap().kendall_tau(), 0.5, 0.03);
+        let g = Gumbel::new(3.0).unwrap();
+        let (x, y): (Vec<f64>, Vec<f64>) = (0..2000).map(|_| g.sample(&mut rng)).unzip();
+        assert_almost_eq!(EmpiricalCopula::new(&x, &y).unwrap().kendall_tau(), 2.0 / 3.0, 0.03);
+    }
+
+    #[test]
+    fn test_fit_round_trip() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let c = Clayton::new(3.0).unwrap();
+        let (x, y): (Vec<f64>, Vec<f64>) = (0..500).map(|_| c.sample(&mut rng)).unzip();
+        let mle = Clayton::fit(&x, &y).unwrap();
+        assert!((mle.parameter() - 3.0).abs() < 3.0 * mle.std_error());
+        let tau = Clayton::fit_tau(&x, &y).unwrap();
+        assert!((tau.parameter() - 3.0).abs() < 3.0 * tau.std_error());
+
+        let g = Gumbel::new(1.8).unwrap();
+        let (x, y): (Vec<f64>, Vec<f64>) = (0..500).map(|_| g.sample(&mut rng)).unzip();
+        let mle = Gumbel::fit(&x, &y).unwrap();
+        assert!((mle.parameter() - 1.8).abs() < 3.0 * mle.std_error());
+        assert_eq!(mle.copula().theta(), mle.parameter());
+        let tau = Gumbel::fit_tau(&x, &y).unwrap();
+        assert!((tau.parameter() - 1.8).abs() < 3.0 * tau.std_error());
+    }
+
+    #[test]
+    fn test_fit_tau_out_of_range() {
+        let x = [1.0, 2.0, 3.0, 4.0];
+        let y = [4.0, 2.0, 3.0, 1.0];
+        assert!(Clayton::fit_tau(&x, &y).is_err());
+        assert!(Gumbel::fit_tau(&x, &y).is_err());
+    }
+}
@@ -0,0 +1,376 @@
+use crate::copula::{clamp_unit, Copula};
+use crate::fp;
+use crate::statistics::{Data, OrderStatistics, RankTieBreaker};
+use crate::{Result, StatsError};
+
+/// Constructs the empirical copula of the paired samples `x` and `y`.
+/// This is a convenience wrapper around `EmpiricalCopula::new`.
+///
+/// # Errors
+///
+/// Returns an error if `x` and `y` differ in length, contain fewer than
+/// two observations or contain a `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::copula::empirical_copula;
+///
+/// let c = empirical_copula(&[1.0, 2.0, 3.0], &[10.0, 20.0, 30.0]).unwrap();
+/// assert_eq!(c.kendall_tau(), 1.0);
+/// ```
+pub fn empirical_copula(x: &[f64], y: &[f64]) -> Result<EmpiricalCopula> {
+    EmpiricalCopula::new(x, y)
+}
+
+/// Implements the [empirical
+/// copula](https://en.wikipedia.org/wiki/Copula_(probability_theory)#Empirical_copulas)
+/// of a bivariate sample.
+///
+/// The observations are mapped to pseudo-observations `U_i = R_i / (n + 1)`,
+/// where `R_i` is the rank of the `i`-th observation among its margin (ties
+/// receive their average rank), so the copula only depends on the ranks of
+/// the data.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::copula::{Copula, EmpiricalCopula, Independence};
+///
+/// let x = [1.0, 2.0, 3.0, 4.0];
+/// let y = [2.0, 1.0, 4.0, 3.0];
+/// let c = EmpiricalCopula::new(&x, &y).unwrap();
+/// assert_eq!(c.cdf(0.5, 0.5), 0.5);
+/// assert!(c.cramer_von_mises(&Independence) > 0.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmpiricalCopula {
+    u: Vec<f64>,
+    v: Vec<f64>,
+}
+
+impl EmpiricalCopula {
+    /// Constructs a new empirical copula from the paired samples `x` and `y`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` and `y` differ in length, contain fewer than
+    /// two observations or contain a `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::copula::EmpiricalCopula;
+    ///
+    /// let mut result = EmpiricalCopula::new(&[1.0, 2.0], &[3.0, 4.0]);
+    /// assert!(result.is_ok());
+    ///
+    /// result = EmpiricalCopula::new(&[1.0, 2.0], &[3.0]);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(x: &[f64], y: &[f64]) -> Result<EmpiricalCopula> {
+        if x.len() != y.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        if x.len() < 2 || x.iter().chain(y.iter()).any(|a| a.is_nan()) {
+            return Err(StatsError::BadParams);
+        }
+        Ok(EmpiricalCopula {
+            u: pseudo_observations(x),
+            v: pseudo_observations(y),
+        })
+    }
+
+    /// Returns the number of observations backing the copula
+    pub fn len(&self) -> usize {
+        self.u.len()
+    }
+
+    /// Returns `true` if the copula holds no observations. Since
+    /// construction requires at least two observations this is always
+    /// `false`.
+    pub fn is_empty(&self) -> bool {
+        self.u.is_empty()
+    }
+
+    /// Returns the pseudo-observations of the first margin
+    pub fn u(&self) -> &[f64] {
+        &self.u
+    }
+
+    /// Returns the pseudo-observations of the second margin
+    pub fn v(&self) -> &[f64] {
+        &self.v
+    }
+
+    /// Returns Kendall's rank correlation coefficient of the sample,
+    /// corrected for ties (tau-b)
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if either margin is constant. The computation
+    /// compares every pair of observations and is therefore `O(n^2)`.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (n_c - n_d) / sqrt((n_0 - n_1) * (n_0 - n_2))
+    /// ```
+    ///
+    /// where `n_c` and `n_d` are the number of concordant and discordant
+    /// pairs, `n_0 = n(n - 1) / 2` and `n_1`, `n_2` are the number of pairs
+    /// tied in the first and second margin respectively
+    pub fn kendall_tau(&self) -> f64 {
+        let n = self.len();
+        let mut concordant = 0.0;
+        let mut discordant = 0.0;
+        let mut ties_u = 0.0;
+        let mut ties_v = 0.0;
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let du = self.u[i] - self.u[j];
+                let dv = self.v[i] - self.v[j];
+                let s = du * dv;
+                if s > 0.0 {
+                    concordant += 1.0;
+                } else if s < 0.0 {
+                    discordant += 1.0;
+                }
+                if du == 0.0 {
+                    ties_u += 1.0;
+                }
+                if dv == 0.0 {
+                    ties_v += 1.0;
+                }
+            }
+        }
+        let n0 = (n * (n - 1)) as f64 / 2.0;
+        (concordant - discordant) / ((n0 - ties_u) * (n0 - ties_v)).sqrt()
+    }
+
+    /// Returns the estimated asymptotic standard error of `kendall_tau`
+    /// (Genest & Favre, 2007)
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sqrt((16 / n^2) * Σ (W_i + W'_i - 2 * W̄)^2)
+    /// ```
+    ///
+    /// where `W_i = #{j : U_j <= U_i, V_j <= V_i} / n`,
+    /// `W'_i = #{j : U_j >= U_i, V_j >= V_i} / n` and `W̄` is the mean of
+    /// the `W_i`
+    pub fn kendall_tau_std_error(&self) -> f64 {
+        let n = self.len() as f64;
+        let (w, w_rev): (Vec<f64>, Vec<f64>) = self
+            .u
+            .iter()
+            .zip(self.v.iter())
+            .map(|(&ui, &vi)| {
+                let mut below = 0.0;
+                let mut above = 0.0;
+                for (&uj, &vj) in self.u.iter().zip(self.v.iter()) {
+                    if uj <= ui && vj <= vi {
+                        below += 1.0;
+                    }
+                    if uj >= ui && vj >= vi {
+                        above += 1.0;
+                    }
+                }
+                (below / n, above / n)
+            })
+            .unzip();
+        let w_mean = w.iter().sum::<f64>() / n;
+        let sum_sq = w
+            .iter()
+            .zip(w_rev.iter())
+            .map(|(a, b)| fp::powi(a + b - 2.0 * w_mean, 2))
+            .sum::<f64>();
+        (16.0 * sum_sq).sqrt() / n
+    }
+
+    /// Returns Spearman's rank correlation coefficient of the sample, i.e.
+    /// the Pearson correlation of the ranks
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if either margin is constant
+    pub fn spearman_rho(&self) -> f64 {
+        let n = self.len() as f64;
+        let mean_u = self.u.iter().sum::<f64>() / n;
+        let mean_v = self.v.iter().sum::<f64>() / n;
+        let mut cov = 0.0;
+        let mut var_u = 0.0;
+        let mut var_v = 0.0;
+        for (u, v) in self.u.iter().zip(self.v.iter()) {
+            cov += (u - mean_u) * (v - mean_v);
+            var_u += (u - mean_u) * (u - mean_u);
+            var_v += (v - mean_v) * (v - mean_v);
+        }
+        cov / (var_u * var_v).sqrt()
+    }
+
+    /// Returns the Cramér-von Mises goodness-of-fit statistic between the
+    /// empirical copula and the (typically fitted) parametric `copula`.
+    /// Larger values indicate a worse fit.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ (C_n(U_i, V_i) - C(U_i, V_i))^2
+    /// ```
+    ///
+    /// where `C_n` is the empirical copula, `C` is `copula` and `(U_i, V_i)`
+    /// are the pseudo-observations
+    pub fn cramer_von_mises<C: Copula + ?Sized>(&self, copula: &C) -> f64 {
+        self.u
+            .iter()
+            .zip(self.v.iter())
+            .map(|(&u, &v)| {
+                let d = self.cdf(u, v) - copula.cdf(u, v);
+                d * d
+            })
+            .sum()
+    }
+
+    /// Returns the Kolmogorov-Smirnov goodness-of-fit statistic between the
+    /// empirical copula and the (typically fitted) parametric `copula`,
+    /// evaluated at the pseudo-observations. Larger values indicate a
+    /// worse fit.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sqrt(n) * max_i |C_n(U_i, V_i) - C(U_i, V_i)|
+    /// ```
+    ///
+    /// where `C_n` is the empirical copula, `C` is `copula` and `(U_i, V_i)`
+    /// are the pseudo-observations
+    pub fn kolmogorov_smirnov<C: Copula + ?Sized>(&self, copula: &C) -> f64 {
+        let sup = self
+            .u
+            .iter()
+            .zip(self.v.iter())
+            .map(|(&u, &v)| (self.cdf(u, v) - copula.cdf(u, v)).abs())
+            .fold(0.0, f64::max);
+        (self.len() as f64).sqrt() * sup
+    }
+}
+
+impl Copula for EmpiricalCopula {
+    /// Calculates the empirical copula at `u` and `v`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 / n) * Σ 1(U_i <= u, V_i <= v)
+    /// ```
+    ///
+    /// where `(U_i, V_i)` are the pseudo-observations
+    fn cdf(&self, u: f64, v: f64) -> f64 {
+        let (u, v) = (clamp_unit(u), clamp_unit(v));
+        let count = self
+            .u
+            .iter()
+            .zip(self.v.iter())
+            .filter(|&(&ui, &vi)| ui <= u && vi <= v)
+            .count();
+        count as f64 / self.len() as f64
+    }
+}
+
+/// Maps `x` to its pseudo-observations `R_i / (n + 1)`
+pub(crate) fn pseudo_observations(x: &[f64]) -> Vec<f64> {
+    let scale = x.len() as f64 + 1.0;
+    Data::new(x.to_vec())
+        .ranks(RankTieBreaker::Average)
+        .into_iter()
+        .map(|r| r / scale)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::copula::Independence;
+
+    #[test]
+    fn test_bad_create() {
+        assert!(EmpiricalCopula::new(&[1.0, 2.0], &[1.0]).is_err());
+        assert!(EmpiricalCopula::new(&[1.0], &[1.0]).is_err());
+        assert!(EmpiricalCopula::new(&[1.0, f64::NAN], &[1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_pseudo_observations() {
+        let c = EmpiricalCopula::new(&[3.0, 1.0, 2.0], &[5.0, 5.0, 4.0]).unwrap();
+        assert_eq!(c.u(), &[0.75, 0.25, 0.5]);
+        assert_eq!(c.v(), &[0.625, 0.625, 0.25]);
+    }
+
+    #[test]
+    fn test_comonotone_and_countermonotone() {
+        let x = [0.3, 1.2, -4.0, 8.0, 2.5];
+        let y: Vec<f64> = x.iter().map(|a: &f64| a.exp()).collect();
+        let z: Vec<f64> = x.iter().map(|a| -a).collect();
+        let c = EmpiricalCopula::new(&x, &y).unwrap();
+        assert_eq!(c.kendall_tau(), 1.0);
+        assert_almost_eq!(c.spearman_rho(), 1.0, 1e-15);
+        let c = EmpiricalCopula::new(&x, &z).unwrap();
+        assert_eq!(c.kendall_tau(), -1.0);
+        assert_almost_eq!(c.spearman_rho(), -1.0, 1e-15);
+    }
+
+    #[test]
+    fn test_kendall_tau_with_ties() {
+        let x = [1.0, 2.0, 2.0, 3.0, 4.0, 5.0];
+        let y = [1.0, 3.0, 2.0, 2.0, 5.0, 4.0];
+        let c = EmpiricalCopula::new(&x, &y).unwrap();
+        assert_almost_eq!(c.kendall_tau(), 9.0 / 14.0, 1e-15);
+        assert_almost_eq!(c.spearman_rho(), 55.0 / 68.0, 1e-15);
+    }
+
+    #[test]
+    fn test_kendall_tau_std_error() {
+        let x: Vec<f64> = (0..50).map(|i| i as f64).collect();
+        let y: Vec<f64> = (0..50).map(|i| ((i * 37) % 50) as f64).collect();
+        // comonotone data has a degenerate (zero) asymptotic variance
+        assert_almost_eq!(
+            EmpiricalCopula::new(&x, &x)
+                .unwrap()
+                .kendall_tau_std_error(),
+            0.0,
+            1e-14
+        );
+        // under independence the standard error is close to sqrt(4 / (9 * n))
+        let se = EmpiricalCopula::new(&x, &y)
+            .unwrap()
+            .kendall_tau_std_error();
+        assert!(se > 0.05 && se < 0.15);
+    }
+
+    #[test]
+    fn test_cdf() {
+        let c = EmpiricalCopula::new(&[1.0, 2.0, 3.0, 4.0], &[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(c.cdf(0.0, 0.0), 0.0);
+        assert_eq!(c.cdf(1.0, 1.0), 1.0);
+        assert_eq!(c.cdf(0.5, 1.0), 0.5);
+        assert_eq!(c.cdf(0.4, 0.6), 0.5);
+        assert_eq!(c.cdf(0.3, 0.9), 0.25);
+        assert_eq!(c.cdf(2.0, -1.0), 0.0);
+    }
+
+    #[test]
+    fn test_goodness_of_fit() {
+        let x: Vec<f64> = (0..50).map(|i| i as f64).collect();
+        // a deterministic shuffle that is close to independent of x
+        let y: Vec<f64> = (0..50).map(|i| ((i * 37) % 50) as f64).collect();
+        let indep = EmpiricalCopula::new(&x, &y).unwrap();
+        let comonotone = EmpiricalCopula::new(&x, &x).unwrap();
+        assert!(indep.cramer_von_mises(&Independence) < comonotone.cramer_von_mises(&Independence));
+        assert!(
+            indep.kolmogorov_smirnov(&Independence) < comonotone.kolmogorov_smirnov(&Independence)
+        );
+        assert_eq!(comonotone.cramer_von_mises(&comonotone), 0.0);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::copula::EmpiricalCopula;
+use crate::function::optimize;
+use crate::{Result, StatsError};
+
+/// The result of fitting a one-parameter copula family to paired samples,
+/// holding the fitted copula together with the estimated standard error of
+/// its dependence parameter
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CopulaFit<C> {
+    copula: C,
+    parameter: f64,
+    std_error: f64,
+}
+
+impl<C> CopulaFit<C> {
+    /// Returns the fitted copula
+    pub fn copula(&self) -> &C {
+        &self.copula
+    }
+
+    /// Consumes the fit, returning the fitted copula
+    pub fn into_copula(self) -> C {
+        self.copula
+    }
+
+    /// Returns the estimated dependence parameter
+    pub fn parameter(&self) -> f64 {
+        self.parameter
+    }
+
+    /// Returns the estimated asymptotic standard error of the dependence
+    /// parameter
+    pub fn std_error(&self) -> f64 {
+        self.std_error
+    }
+}
+
+/// Describes a copula family with a single dependence parameter `θ` so that
+/// the rank-based estimators can be shared between families
+pub(crate) trait OneParameterFamily: Sized {
+    /// Constructs the member of the family with parameter `theta`
+    fn from_parameter(theta: f64) -> Result<Self>;
+
+    /// Returns the log density of the copula at `u` and `v`
+    fn ln_density(&self, u: f64, v: f64) -> f64;
+
+    /// Maps Kendall's tau to the parameter, returning `None` if `tau` is
+    /// outside the range attainable by the family
+    fn tau_to_parameter(tau: f64) -> Option<f64>;
+
+    /// Returns the derivative of `tau_to_parameter` at `tau`
+    fn tau_to_parameter_derivative(tau: f64) -> f64;
+
+    /// Maps an unconstrained value to the parameter space
+    fn from_unconstrained(eta: f64) -> f64;
+
+    /// Inverse of `from_unconstrained`
+    fn to_unconstrained(theta: f64) -> f64;
+
+    /// Returns the derivative of `from_unconstrained` at `eta`
+    fn from_unconstrained_derivative(eta: f64) -> f64;
+}
+
+/// Estimates the parameter of the family `C` by inverting the sample
+/// Kendall's tau of `x` and `y`. The standard error follows from the
+/// asymptotic standard error of tau by the delta method.
+pub(crate) fn fit_tau<C: OneParameterFamily>(x: &[f64], y: &[f64]) -> Result<CopulaFit<C>> {
+    let emp = EmpiricalCopula::new(x, y)?;
+    let tau = emp.kendall_tau();
+    let parameter = C::tau_to_parameter(tau).ok_or(StatsError::BadParams)?;
+    let std_error = C::tau_to_parameter_derivative(tau).abs() * emp.kendall_tau_std_error();
+    Ok(CopulaFit {
+        copula: C::from_parameter(parameter)?,
+        parameter,
+        std_error,
+    })
+}
+
+/// Estimates the parameter of the family `C` by maximizing the
+/// pseudo-likelihood of the pseudo-observations of `x` and `y`, starting
+/// from the Kendall's tau estimate when it is admissible. The standard error
+/// is taken from the observed information.
+pub(crate) fn fit_mle<C: OneParameterFamily>(x: &[f64], y: &[f64]) -> Result<CopulaFit<C>> {
+    let emp = EmpiricalCopula::new(x, y)?;
+    let (u, v) = (emp.u(), emp.v());
+    let ln_likelihood = |eta: f64| -> f64 {
+        match C::from_parameter(C::from_unconstrained(eta)) {
+            Ok(c) => {
+                let ll: f64 = u
+                    .iter()
+                    .zip(v.iter())
+                    .map(|(&a, &b)| c.ln_density(a, b))
+                    .sum();
+                if ll.is_nan() {
+                    f64::NEG_INFINITY
+                } else {
+                    ll
+                }
+            }
+            Err(_) => f64::NEG_INFINITY,
+        }
+    };
+    let start = C::tau_to_parameter(emp.kendall_tau())
+        .map(C::to_unconstrained)
+        .filter(|eta| eta.is_finite())
+        .unwrap_or(0.0);
+    let (eta, neg_ll) =
+        optimize::brent_minimize(|eta| -ln_likelihood(eta), start, start + 0.1, 1e-10)?;
+    if !neg_ll.is_finite() {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+    let h = 1e-4 * eta.abs().max(1.0);
+    let information = (-ln_likelihood(eta + h) - ln_likelihood(eta - h) - 2.0 * neg_ll) / (h * h);
+    let parameter = C::from_unconstrained(eta);
+    let std_error = if information > 0.0 {
+        C::from_unconstrained_derivative(eta).abs() / information.sqrt()
+    } else {
+        f64::NAN
+    };
+    Ok(CopulaFit {
+        copula: C::from_parameter(parameter)?,
+        parameter,
+        std_error,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::copula::{Clayton, GaussianCopula};
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn sample<C: Distribution<(f64, f64)>>(c: &C, n: usize, seed: u64) -> (Vec<f64>, Vec<f64>) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..n).map(|_| c.sample(&mut rng)).unzip()
+    }
+
+    // the pseudo log-likelihood of the family at `theta` itself, without
+    // the reparameterization used by `fit_mle`
+    fn ln_likelihood<C: OneParameterFamily>(x: &[f64], y: &[f64], theta: f64) -> f64 {
+        let emp = EmpiricalCopula::new(x, y).unwrap();
+        let c = C::from_parameter(theta).unwrap();
+        emp.u().iter().zip(emp.v().iter()).map(|(&u, &v)| c.ln_density(u, v)).sum()
+    }
+
+    #[test]
+    fn test_fit_tau() {
+        let (x, y) = sample(&GaussianCopula::new(0.6).unwrap(), 1000, 11);
+        let fit = fit_tau::<GaussianCopula>(&x, &y).unwrap();
+        assert!((fit.parameter() - 0.6).abs() < 3.0 * fit.std_error());
+        assert_eq!(fit.copula().rho(), fit.parameter());
+        // the delta method scales the standard error of tau by the slope of
+        // the map from tau to the parameter
+        let emp = EmpiricalCopula::new(&x, &y).unwrap();
+        let (tau, h) = (emp.kendall_tau(), 1e-6);
+        let slope = (GaussianCopula::tau_to_parameter(tau + h).unwrap()
+            - GaussianCopula::tau_to_parameter(tau - h).unwrap()) / (2.0 * h);
+        assert_almost_eq!(fit.std_error(), slope * emp.kendall_tau_std_error(), 1e-8);
+
+        let (x, y) = sample(&Clayton::new(2.0).unwrap(), 1000, 12);
+        let fit = fit_tau::<Clayton>(&x, &y).unwrap();
+        assert!((fit.parameter() - 2.0).abs() < 3.0 * fit.std_error());
+        assert!(fit.std_error() > 0.05 && fit.std_error() < 0.3);
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        let n = 1000;
+        let (x, y) = sample(&GaussianCopula::new(0.6).unwrap(), n, 13);
+        let fit = fit_mle::<GaussianCopula>(&x, &y).unwrap();
+        assert!((fit.parameter() - 0.6).abs() < 3.0 * fit.std_error());
+        // the Fisher information of the correlation of a bivariate normal
+        // with known margins is n (1 + ρ^2) / (1 - ρ^2)^2
+        let rho = fit.parameter();
+        let expected = (1.0 - rho * rho) / (n as f64 * (1.0 + rho * rho)).sqrt();
+        assert!((fit.std_error() / expected - 1.0).abs() < 0.1);
+
+        let (x, y) = sample(&Clayton::new(2.0).unwrap(), n, 14);
+        let fit = fit_mle::<Clayton>(&x, &y).unwrap();
+        assert!((fit.parameter() - 2.0).abs() < 3.0 * fit.std_error());
+        // the fitted parameter is a stationary point of the likelihood and
+        // the standard error matches the curvature in the parameter itself
+        let (theta, h) = (fit.parameter(), 1e-3);
+        let ll = |t: f64| ln_likelihood::<Clayton>(&x, &y, t);
+        let slope = (ll(theta + h) - ll(theta - h)) / (2.0 * h);
+        let curvature = (ll(theta + h) - 2.0 * ll(theta) + ll(theta - h)) / (h * h);
+        assert!(slope.abs() < 1e-3 * curvature.abs());
+        assert_almost_eq!(fit.std_error(), 1.0 / (-curvature).sqrt(), 1e-3 * fit.std_error());
+    }
+
+    #[test]
+    fn test_fit_mle_inadmissible_start() {
+        // negatively dependent data has no Clayton tau estimate, so the
+        // search starts from the unconstrained origin and approaches the
+        // independence boundary
+        let x: Vec<f64> = (0..200).map(|i| i as f64).collect();
+        let y: Vec<f64> = x.iter().map(|&i| (i * 0.37).sin() - i / 200.0).collect();
+        let fit = fit_mle::<Clayton>(&x, &y).unwrap();
+        assert!(fit.parameter() < 0.1);
+        assert!(fit_tau::<Clayton>(&x, &y).is_err());
+    }
+
+    #[test]
+    fn test_fit_bad_input() {
+        assert!(fit_tau::<GaussianCopula>(&[1.0, 2.0], &[1.0]).is_err());
+        assert!(fit_mle::<Clayton>(&[1.0, 2.0], &[1.0]).is_err());
+    }
+}
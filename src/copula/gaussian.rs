@@ -0,0 +1,333 @@
+use crate::copula::fit::{self, CopulaFit, OneParameterFamily};
+use crate::copula::{clamp_unit, Copula};
+use crate::distribution::{ContinuousCDF, Normal};
+use crate::fp;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// Nodes and weights of the 20 point Gauss-Legendre rule on `[-1, 1]`,
+/// listed for the positive nodes only
+const GAUSS_LEGENDRE_20: [(f64, f64); 10] = [
+    (0.993_128_599_185_094_9, 0.017_614_007_139_152_264),
+    (0.963_971_927_277_913_8, 0.040_601_429_800_387_05),
+    (0.912_234_428_251_326, 0.062_672_048_334_109_04),
+    (0.839_116_971_822_218_9, 0.083_276_741_576_704_74),
+    (0.746_331_906_460_150_8, 0.101_930_119_817_240_48),
+    (0.636_053_680_726_515, 0.118_194_531_961_518_31),
+    (0.510_867_001_950_827_1, 0.131_688_638_449_176_5),
+    (0.373_706_088_715_419_55, 0.142_096_109_318_382_15),
+    (0.227_785_851_141_645_07, 0.149_172_986_472_603_82),
+    (0.076_526_521_133_497_34, 0.152_753_387_130_725_98),
+];
+
+/// Implements the bivariate [Gaussian
+/// copula](https://en.wikipedia.org/wiki/Copula_(probability_theory)#Gaussian_copula)
+/// with correlation `ρ`, i.e. the copula of a bivariate normal distribution
+///
+/// # Examples
+///
+/// ```
+/// use statrs::copula::{Copula, GaussianCopula};
+///
+/// let c = GaussianCopula::new(0.5).unwrap();
+/// assert!((c.cdf(0.5, 0.5) - 1.0 / 3.0).abs() < 1e-12);
+/// assert!((c.kendall_tau() - 1.0 / 3.0).abs() < 1e-15);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GaussianCopula {
+    rho: f64,
+}
+
+impl GaussianCopula {
+    /// Constructs a new Gaussian copula with correlation `rho`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `rho` is `NaN` or not in `(-1, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::copula::GaussianCopula;
+    ///
+    /// let mut result = GaussianCopula::new(0.3);
+    /// assert!(result.is_ok());
+    ///
+    /// result = GaussianCopula::new(1.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(rho: f64) -> Result<GaussianCopula> {
+        if rho.is_nan() || rho <= -1.0 || rho >= 1.0 {
+            Err(StatsError::ArgIntervalExcl("rho", -1.0, 1.0))
+        } else {
+            Ok(GaussianCopula { rho })
+        }
+    }
+
+    /// Fits a Gaussian copula to the paired samples `x` and `y` by
+    /// maximizing the pseudo-likelihood of their pseudo-observations
+    ///
+    /// # Remarks
+    ///
+    /// The standard error is derived from the observed information of the
+    /// pseudo-likelihood and does not account for the estimation of the
+    /// margins by ranks, so it tends to be somewhat optimistic
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` and `y` differ in length, contain fewer than
+    /// two observations or contain a `NaN`, or if the maximization fails
+    /// (e.g. for perfectly dependent data)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::copula::GaussianCopula;
+    ///
+    /// let x = [0.1, 0.5, 0.9, 1.3, 2.0, 2.2, 3.1, 3.3];
+    /// let y = [0.3, 0.2, 1.1, 0.9, 2.5, 1.7, 2.9, 3.6];
+    /// let fit = GaussianCopula::fit(&x, &y).unwrap();
+    /// assert!(fit.parameter() > 0.5);
+    /// assert!(fit.std_error() > 0.0);
+    /// ```
+    pub fn fit(x: &[f64], y: &[f64]) -> Result<CopulaFit<GaussianCopula>> {
+        fit::fit_mle(x, y)
+    }
+
+    /// Fits a Gaussian copula to the paired samples `x` and `y` by inverting
+    /// their sample Kendall's tau, `ρ = sin(π * τ / 2)`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` and `y` differ in length, contain fewer than
+    /// two observations or contain a `NaN`, or if the sample is perfectly
+    /// concordant or discordant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::copula::GaussianCopula;
+    ///
+    /// let fit = GaussianCopula::fit_tau(&[1.0, 2.0, 3.0, 4.0], &[2.0, 1.0, 4.0, 3.0]).unwrap();
+    /// assert!((fit.parameter() - 0.5).abs() < 1e-15);
+    /// ```
+    pub fn fit_tau(x: &[f64], y: &[f64]) -> Result<CopulaFit<GaussianCopula>> {
+        fit::fit_tau(x, y)
+    }
+
+    /// Returns the correlation of the Gaussian copula
+    pub fn rho(&self) -> f64 {
+        self.rho
+    }
+
+    /// Returns Kendall's tau of the Gaussian copula
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 2 * asin(ρ) / π
+    /// ```
+    pub fn kendall_tau(&self) -> f64 {
+        2.0 * fp::asin(self.rho) / PI
+    }
+
+    /// Returns Spearman's rho of the Gaussian copula
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 6 * asin(ρ / 2) / π
+    /// ```
+    pub fn spearman_rho(&self) -> f64 {
+        6.0 * fp::asin(self.rho / 2.0) / PI
+    }
+
+    /// Calculates the copula density at `u` and `v`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// exp(-(ρ^2 * (a^2 + b^2) - 2 * ρ * a * b) / (2 * (1 - ρ^2))) / sqrt(1 - ρ^2)
+    /// ```
+    ///
+    /// where `a = Φ^-1(u)`, `b = Φ^-1(v)` and `Φ^-1` is the inverse cdf of
+    /// the standard normal distribution
+    ///
+    /// # Panics
+    ///
+    /// If `u` or `v` is not in `[0, 1]`
+    pub fn pdf(&self, u: f64, v: f64) -> f64 {
+        fp::exp(self.ln_pdf(u, v))
+    }
+
+    /// Calculates the log of the copula density at `u` and `v`
+    ///
+    /// # Panics
+    ///
+    /// If `u` or `v` is not in `[0, 1]`
+    pub fn ln_pdf(&self, u: f64, v: f64) -> f64 {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let a = normal.inverse_cdf(u);
+        let b = normal.inverse_cdf(v);
+        let r2 = self.rho * self.rho;
+        -0.5 * fp::ln_1p(-r2) - (r2 * (a * a + b * b) - 2.0 * self.rho * a * b) / (2.0 * (1.0 - r2))
+    }
+}
+
+impl Copula for GaussianCopula {
+    /// Calculates the Gaussian copula at `u` and `v`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Φ_2(Φ^-1(u), Φ^-1(v); ρ)
+    /// ```
+    ///
+    /// where `Φ_2` is the cdf of the standard bivariate normal distribution
+    /// with correlation `ρ`, evaluated with the Drezner-Wesolowsky integral
+    /// representation and 20 point Gauss-Legendre quadrature
+    fn cdf(&self, u: f64, v: f64) -> f64 {
+        let (u, v) = (clamp_unit(u), clamp_unit(v));
+        if u == 0.0 || v == 0.0 {
+            return 0.0;
+        }
+        if u == 1.0 {
+            return v;
+        }
+        if v == 1.0 {
+            return u;
+        }
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let a = normal.inverse_cdf(u);
+        let b = normal.inverse_cdf(v);
+        let half = 0.5 * fp::asin(self.rho);
+        let integrand = |t: f64| {
+            let s = fp::sin(t);
+            fp::exp(-(a * a + b * b - 2.0 * a * b * s) / (2.0 * (1.0 - s * s)))
+        };
+        let integral = GAUSS_LEGENDRE_20
+            .iter()
+            .map(|&(x, w)| w * (integrand(half * (1.0 + x)) + integrand(half * (1.0 - x))))
+            .sum::<f64>()
+            * half;
+        (u * v + integral / (2.0 * PI)).clamp(0.0, u.min(v))
+    }
+}
+
+impl ::rand::distributions::Distribution<(f64, f64)> for GaussianCopula {
+    /// Samples from the Gaussian copula by transforming a pair of
+    /// correlated standard normal variables with the standard normal cdf
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (f64, f64) {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let z1 = rng.sample(normal);
+        let z2 = self.rho * z1 + (1.0 - self.rho * self.rho).sqrt() * rng.sample(normal);
+        (normal.cdf(z1), normal.cdf(z2))
+    }
+}
+
+impl OneParameterFamily for GaussianCopula {
+    fn from_parameter(theta: f64) -> Result<Self> {
+        GaussianCopula::new(theta)
+    }
+
+    fn ln_density(&self, u: f64, v: f64) -> f64 {
+        self.ln_pdf(u, v)
+    }
+
+    fn tau_to_parameter(tau: f64) -> Option<f64> {
+        if tau.abs() < 1.0 {
+            Some(fp::sin(PI * tau / 2.0))
+        } else {
+            None
+        }
+    }
+
+    fn tau_to_parameter_derivative(tau: f64) -> f64 {
+        PI / 2.0 * fp::cos(PI * tau / 2.0)
+    }
+
+    fn from_unconstrained(eta: f64) -> f64 {
+        fp::tanh(eta)
+    }
+
+    fn to_unconstrained(theta: f64) -> f64 {
+        theta.atanh()
+    }
+
+    fn from_unconstrained_derivative(eta: f64) -> f64 {
+        1.0 - fp::powi(fp::tanh(eta), 2)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{ContinuousCDF, Normal};
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bad_create() {
+        assert!(GaussianCopula::new(f64::NAN).is_err());
+        assert!(GaussianCopula::new(-1.0).is_err());
+        assert!(GaussianCopula::new(1.5).is_err());
+    }
+
+    #[test]
+    fn test_cdf() {
+        for &rho in &[-0.99, -0.7, -0.2, 0.0, 0.4, 0.9, 0.999] {
+            let c = GaussianCopula::new(rho).unwrap();
+            // the orthant probability has a closed form
+            assert_almost_eq!(c.cdf(0.5, 0.5), 0.25 + rho.asin() / (2.0 * PI), 1e-12);
+            assert_eq!(c.cdf(0.0, 0.3), 0.0);
+            assert_eq!(c.cdf(1.0, 0.3), 0.3);
+            assert_eq!(c.cdf(0.3, 1.0), 0.3);
+        }
+        // P(Z1 <= 1, Z2 <= -0.5) with correlation 0.5
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let c = GaussianCopula::new(0.5).unwrap();
+        assert_almost_eq!(c.cdf(normal.cdf(1.0), normal.cdf(-0.5)), 0.29609063435417526, 1e-10);
+        assert_almost_eq!(GaussianCopula::new(0.0).unwrap().cdf(0.3, 0.6), 0.18, 1e-15);
+    }
+
+    #[test]
+    fn test_pdf() {
+        let c = GaussianCopula::new(0.0).unwrap();
+        assert_almost_eq!(c.pdf(0.3, 0.8), 1.0, 1e-15);
+        // the density is the mixed partial derivative of the cdf
+        let c = GaussianCopula::new(0.6).unwrap();
+        let (u, v, h) = (0.3, 0.7, 1e-4);
+        let numeric = (c.cdf(u + h, v + h) - c.cdf(u + h, v - h) - c.cdf(u - h, v + h) + c.cdf(u - h, v - h)) / (4.0 * h * h);
+        assert_almost_eq!(c.pdf(u, v), numeric, 1e-5);
+    }
+
+    #[test]
+    fn test_dependence_measures() {
+        let c = GaussianCopula::new(0.5).unwrap();
+        assert_almost_eq!(c.kendall_tau(), 1.0 / 3.0, 1e-15);
+        assert_almost_eq!(c.spearman_rho(), 6.0 / PI * 0.25f64.asin(), 1e-15);
+    }
+
+    #[test]
+    fn test_fit_round_trip() {
+        let c = GaussianCopula::new(0.6).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let (x, y): (Vec<f64>, Vec<f64>) = (0..500).map(|_| c.sample(&mut rng)).unzip();
+        let mle = GaussianCopula::fit(&x, &y).unwrap();
+        assert!((mle.parameter() - 0.6).abs() < 3.0 * mle.std_error());
+        assert!(mle.std_error() > 0.01 && mle.std_error() < 0.1);
+        assert_eq!(mle.copula().rho(), mle.parameter());
+        let tau = GaussianCopula::fit_tau(&x, &y).unwrap();
+        assert!((tau.parameter() - 0.6).abs() < 3.0 * tau.std_error());
+        assert!(tau.std_error() > 0.01 && tau.std_error() < 0.1);
+    }
+
+    #[test]
+    fn test_fit_bad_input() {
+        assert!(GaussianCopula::fit(&[1.0, 2.0], &[1.0]).is_err());
+        assert!(GaussianCopula::fit_tau(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+//! Provides bivariate copulas together with rank-based dependence
+//! diagnostics such as Kendall's tau and Spearman's rho, and estimators
+//! fitting parametric copula families to paired samples
+pub use self::archimedean::{Clayton, Gumbel};
+pub use self::empirical::{empirical_copula, EmpiricalCopula};
+pub use self::fit::CopulaFit;
+pub use self::gaussian::GaussianCopula;
+
+mod archimedean;
+mod empirical;
+mod fit;
+mod gaussian;
+
+/// The `Copula` trait provides an interface for bivariate copulas, i.e.
+/// joint distribution functions on the unit square with uniform marginals
+pub trait Copula {
+    /// Returns the copula `C(u, v)` evaluated at `u` and `v`.
+    /// May panic depending on the implementor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::copula::{Copula, Independence};
+    ///
+    /// let c = Independence;
+    /// assert_eq!(c.cdf(0.5, 0.5), 0.25);
+    /// ```
+    fn cdf(&self, u: f64, v: f64) -> f64;
+}
+
+/// Implements the independence (product) copula `C(u, v) = uv`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::copula::{Copula, Independence};
+///
+/// assert_eq!(Independence.cdf(0.2, 0.5), 0.1);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Independence;
+
+impl Copula for Independence {
+    /// Calculates the independence copula at `u` and `v`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// u * v
+    /// ```
+    fn cdf(&self, u: f64, v: f64) -> f64 {
+        clamp_unit(u) * clamp_unit(v)
+    }
+}
+
+/// Restricts `x` to the unit interval, the domain of every copula
+fn clamp_unit(x: f64) -> f64 {
+    x.clamp(0.0, 1.0)
+}
@@ -0,0 +1,421 @@
+//! Provides upper-tail critical values of the t, chi-squared, F,
+//! studentized range and Kolmogorov-Smirnov statistics, cached in a table
+//! shared between threads so that tests repeated in a loop solve for each
+//! quantile only once
+//!
+//! Every function returns the value exceeded by the statistic with
+//! probability `alpha` under the null hypothesis, so a two-sided t test at
+//! level `α` uses `students_t(freedom, α / 2)`.
+//!
+//! The cache holds at most 4096 values and is emptied when a new value
+//! would exceed that, so sweeping over many parameters or levels costs a
+//! bounded amount of memory at the price of solving again afterwards.
+
+use crate::distribution::{ChiSquared, ContinuousCDF, FisherSnedecor, Normal, StudentsT};
+use crate::fp;
+use crate::function::gamma;
+use crate::function::kolmogorov::kolmogorov_sf;
+use crate::quadrature::GaussRule;
+use crate::{Result, StatsError};
+use std::collections::HashMap;
+use std::f64;
+use std::sync::Mutex;
+
+/// Identifies a table of critical values, with the bits of its parameters
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Table {
+    StudentsT(u64),
+    ChiSquared(u64),
+    FisherSnedecor(u64, u64),
+    StudentizedRange(u64, u64),
+    Kolmogorov,
+}
+
+/// The number of critical values kept before the cache is emptied
+const CAPACITY: usize = 4096;
+
+lazy_static! {
+    static ref CACHE: Mutex<HashMap<(Table, u64), f64>> = Mutex::new(HashMap::new());
+}
+
+/// Returns the critical value of `table` at `alpha` from the cache, calling
+/// `solve` and caching its result on a miss. The lock is not held while
+/// solving, so two threads missing on the same key may both solve it.
+fn cached<F>(table: Table, alpha: f64, solve: F) -> f64
+where
+    F: FnOnce() -> f64,
+{
+    let key = (table, alpha.to_bits());
+    if let Some(&value) = CACHE.lock().unwrap().get(&key) {
+        return value;
+    }
+    let value = solve();
+    insert_bounded(&mut CACHE.lock().unwrap(), key, value);
+    value
+}
+
+/// Inserts `value` at `key`, first emptying `cache` if it is full
+fn insert_bounded(cache: &mut HashMap<(Table, u64), f64>, key: (Table, u64), value: f64) {
+    if cache.len() >= CAPACITY && !cache.contains_key(&key) {
+        cache.clear();
+    }
+    cache.insert(key, value);
+}
+
+/// Returns the number of critical values currently cached, which is at
+/// most 4096
+pub fn cached_len() -> usize {
+    CACHE.lock().unwrap().len()
+}
+
+/// Empties the cache of critical values
+///
+/// # Examples
+///
+/// ```
+/// use statrs::critical_values;
+///
+/// critical_values::chi_squared(3.0, 0.05).unwrap();
+/// assert!(critical_values::cached_len() > 0);
+/// critical_values::clear_cache();
+/// assert_eq!(critical_values::cached_len(), 0);
+/// ```
+pub fn clear_cache() {
+    CACHE.lock().unwrap().clear()
+}
+
+fn check_alpha(alpha: f64) -> Result<()> {
+    if alpha > 0.0 && alpha < 1.0 {
+        Ok(())
+    } else {
+        Err(StatsError::ArgIntervalExcl("alpha", 0.0, 1.0))
+    }
+}
+
+fn check_freedom(name: &'static str, freedom: f64) -> Result<()> {
+    if freedom.is_finite() && freedom > 0.0 {
+        Ok(())
+    } else {
+        Err(StatsError::ArgMustBePositive(name))
+    }
+}
+
+/// Returns the upper `alpha` critical value of the standard Student's t
+/// distribution with `freedom` degrees of freedom
+///
+/// # Errors
+///
+/// Returns an error if `freedom` is not positive and finite or if `alpha`
+/// is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::critical_values;
+///
+/// // a two-sided test at the 5% level
+/// let t = critical_values::students_t(10.0, 0.025).unwrap();
+/// assert!((t - 2.2281388519649385).abs() < 1e-9);
+/// ```
+pub fn students_t(freedom: f64, alpha: f64) -> Result<f64> {
+    check_freedom("freedom", freedom)?;
+    check_alpha(alpha)?;
+    let dist = StudentsT::new(0.0, 1.0, freedom)?;
+    Ok(cached(Table::StudentsT(freedom.to_bits()), alpha, || {
+        -dist.inverse_cdf(alpha)
+    }))
+}
+
+/// Returns the upper `alpha` critical value of the chi-squared
+/// distribution with `freedom` degrees of freedom
+///
+/// # Errors
+///
+/// Returns an error if `freedom` is not positive and finite or if `alpha`
+/// is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::critical_values;
+///
+/// let x = critical_values::chi_squared(1.0, 0.05).unwrap();
+/// assert!((x - 3.841458820694124).abs() < 1e-9);
+/// ```
+pub fn chi_squared(freedom: f64, alpha: f64) -> Result<f64> {
+    check_freedom("freedom", freedom)?;
+    check_alpha(alpha)?;
+    let dist = ChiSquared::new(freedom)?;
+    Ok(cached(Table::ChiSquared(freedom.to_bits()), alpha, || {
+        dist.inverse_cdf(1.0 - alpha)
+    }))
+}
+
+/// Returns the upper `alpha` critical value of the F distribution with
+/// `freedom_1` and `freedom_2` degrees of freedom
+///
+/// # Errors
+///
+/// Returns an error if either degrees of freedom is not positive and
+/// finite or if `alpha` is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::critical_values;
+///
+/// let f = critical_values::fisher_snedecor(3.0, 20.0, 0.05).unwrap();
+/// assert!((f - 3.0983912121407795).abs() < 1e-9);
+/// ```
+pub fn fisher_snedecor(freedom_1: f64, freedom_2: f64, alpha: f64) -> Result<f64> {
+    check_freedom("freedom_1", freedom_1)?;
+    check_freedom("freedom_2", freedom_2)?;
+    check_alpha(alpha)?;
+    let dist = FisherSnedecor::new(freedom_1, freedom_2)?;
+    let table = Table::FisherSnedecor(freedom_1.to_bits(), freedom_2.to_bits());
+    Ok(cached(table, alpha, || dist.inverse_cdf(1.0 - alpha)))
+}
+
+/// Returns the upper `alpha` critical value of the studentized range of
+/// `groups` means with `freedom` degrees of freedom for the pooled standard
+/// deviation, as used by Tukey's honestly significant difference test
+///
+/// # Remarks
+///
+/// The distribution function
+/// `∫ g_ν(s) k ∫ φ(z) (Φ(z) - Φ(z - qs))^(k - 1) dz ds`, where `g_ν` is the
+/// density of `sqrt(χ²_ν / ν)`, is computed by Gauss-Legendre quadrature
+/// over panels in `z` and `s`, accurate to about `1e-7`,
+/// and inverted by bisection. `freedom` may be infinite for a known
+/// standard deviation.
+///
+/// # Errors
+///
+/// Returns an error if `groups` is less than `2`, if `freedom` is not
+/// positive or if `alpha` is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::critical_values;
+///
+/// let q = critical_values::studentized_range(3, 10.0, 0.05).unwrap();
+/// assert!((q - 3.877).abs() < 1e-3);
+/// ```
+pub fn studentized_range(groups: u64, freedom: f64, alpha: f64) -> Result<f64> {
+    if groups < 2 {
+        return Err(StatsError::BadParams);
+    }
+    if freedom.is_nan() || freedom <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("freedom"));
+    }
+    check_alpha(alpha)?;
+    let table = Table::StudentizedRange(groups, freedom.to_bits());
+    Ok(cached(table, alpha, || {
+        let (k, target) = (groups as f64, 1.0 - alpha);
+        let cdf = |q: f64| {
+            if freedom.is_infinite() {
+                return range_cdf(q, k);
+            }
+            // s = sqrt(χ²_ν / ν) has mean about 1 and standard deviation
+            // about 1 / sqrt(2ν), and the weights are normalized to absorb
+            // the truncation of its density
+            let spread = 10.0 / (2.0 * freedom).sqrt();
+            let (mut total, mut sum) = (0.0, 0.0);
+            for (s, w) in composite_nodes((1.0 - spread).max(0.0), 1.0 + spread, 8) {
+                let ln_density = 0.5 * freedom * fp::ln(freedom) + (freedom - 1.0) * fp::ln(s)
+                    - 0.5 * freedom * s * s
+                    - gamma::ln_gamma(0.5 * freedom)
+                    - (0.5 * freedom - 1.0) * f64::consts::LN_2;
+                let w = w * fp::exp(ln_density);
+                total += w;
+                sum += w * range_cdf(q * s, k);
+            }
+            sum / total
+        };
+        let mut high = 1.0;
+        while cdf(high) < target {
+            high *= 2.0;
+        }
+        bisect(0.0, high, |q| cdf(q) < target)
+    }))
+}
+
+/// Returns the nodes and weights of the composite 16-point Gauss-Legendre
+/// rule on `panels` equal panels of `[low, high]`
+fn composite_nodes(low: f64, high: f64, panels: usize) -> impl Iterator<Item = (f64, f64)> {
+    lazy_static! {
+        static ref RULE: GaussRule = GaussRule::legendre(16).unwrap();
+    }
+    let width = (high - low) / panels as f64;
+    (0..panels).flat_map(move |i| {
+        let mid = low + (i as f64 + 0.5) * width;
+        RULE.nodes()
+            .iter()
+            .zip(RULE.weights())
+            .map(move |(&x, &w)| (mid + 0.5 * width * x, width * w))
+    })
+}
+
+/// Returns the distribution function at `w` of the range of `k` standard
+/// normal variables
+fn range_cdf(w: f64, k: f64) -> f64 {
+    if w <= 0.0 {
+        return 0.0;
+    }
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    // the integrand is negligible outside of [-8, 8]
+    let sum: f64 = composite_nodes(-8.0, 8.0, 12)
+        .map(|(z, weight)| {
+            let inside = normal.cdf(z) - normal.cdf(z - w);
+            weight * fp::exp(-0.5 * z * z) * fp::powf(inside, k - 1.0)
+        })
+        .sum();
+    (k * sum / (2.0 * f64::consts::PI).sqrt()).min(1.0)
+}
+
+/// Returns the point between `low` and `high` where `below` turns false,
+/// for `below` true at `low` and false at `high`
+fn bisect<F>(mut low: f64, mut high: f64, below: F) -> f64
+where
+    F: Fn(f64) -> bool,
+{
+    while high - low > 1e-12 * high {
+        let mid = 0.5 * (low + high);
+        if below(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    0.5 * (low + high)
+}
+
+/// Returns the upper `alpha` critical value of the Kolmogorov-Smirnov
+/// statistic `D` with effective sample size `effective_n`, using the
+/// asymptotic distribution with Stephens' small sample correction as in
+/// `drift::ks_p_value`
+///
+/// # Errors
+///
+/// Returns an error if `effective_n` is not positive and finite or if
+/// `alpha` is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::critical_values;
+/// use statrs::drift::ks_p_value;
+///
+/// let d = critical_values::kolmogorov_smirnov(100.0, 0.05).unwrap();
+/// assert!((ks_p_value(d, 100.0) - 0.05).abs() < 1e-10);
+/// ```
+pub fn kolmogorov_smirnov(effective_n: f64, alpha: f64) -> Result<f64> {
+    check_freedom("effective_n", effective_n)?;
+    check_alpha(alpha)?;
+    let c = cached(Table::Kolmogorov, alpha, || {
+        bisect(0.0, 10.0, |x| kolmogorov_sf(x) > alpha)
+    });
+    let sqrt_n = effective_n.sqrt();
+    Ok(c / (sqrt_n + 0.12 + 0.11 / sqrt_n))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_students_t() {
+        assert_almost_eq!(students_t(10.0, 0.025).unwrap(), 2.2281388519649385, 1e-9);
+        assert_almost_eq!(students_t(1.0, 0.05).unwrap(), 6.313751514675043, 1e-10);
+        assert_almost_eq!(students_t(30.0, 0.995).unwrap(), -2.749995653567864, 1e-10);
+        assert!(students_t(0.0, 0.05).is_err());
+        assert!(students_t(f64::INFINITY, 0.05).is_err());
+        assert!(students_t(5.0, 1.0).is_err());
+        assert!(students_t(5.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_chi_squared() {
+        assert_almost_eq!(chi_squared(1.0, 0.05).unwrap(), 3.841458820694124, 1e-9);
+        assert_almost_eq!(chi_squared(10.0, 0.01).unwrap(), 23.209251158954356, 1e-9);
+        assert!(chi_squared(-1.0, 0.05).is_err());
+        assert!(chi_squared(1.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_fisher_snedecor() {
+        assert_almost_eq!(fisher_snedecor(3.0, 20.0, 0.05).unwrap(), 3.0983912121407795, 1e-9);
+        assert_almost_eq!(fisher_snedecor(1.0, 1.0, 0.1).unwrap(), 39.86345818906144, 1e-8);
+        assert!(fisher_snedecor(3.0, 0.0, 0.05).is_err());
+    }
+
+    #[test]
+    fn test_range_cdf() {
+        // the range of two normals is |Z_1 - Z_2|
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        for &w in [0.1, 0.5, 1.0, 2.0, 4.0, 8.0].iter() {
+            let expected = 2.0 * normal.cdf(w / 2f64.sqrt()) - 1.0;
+            assert_almost_eq!(range_cdf(w, 2.0), expected, 1e-10);
+        }
+        assert_eq!(range_cdf(0.0, 5.0), 0.0);
+    }
+
+    #[test]
+    fn test_studentized_range() {
+        // for two groups the studentized range is sqrt(2) |t|
+        let q = studentized_range(2, 5.0, 0.05).unwrap();
+        assert_almost_eq!(q, 2f64.sqrt() * students_t(5.0, 0.025).unwrap(), 1e-7);
+        // checked against the distribution function integrated by mpmath
+        assert_almost_eq!(studentized_range(3, 10.0, 0.05).unwrap(), 3.876776750172212, 1e-6);
+        assert_almost_eq!(studentized_range(4, f64::INFINITY, 0.01).unwrap(), 4.402800862177173, 1e-6);
+        // and against tables
+        assert_almost_eq!(studentized_range(5, 20.0, 0.05).unwrap(), 4.232, 1e-3);
+        assert_almost_eq!(studentized_range(10, 2.0, 0.05).unwrap(), 13.99, 1e-2);
+        assert!(studentized_range(1, 5.0, 0.05).is_err());
+        assert!(studentized_range(3, 0.0, 0.05).is_err());
+    }
+
+    #[test]
+    fn test_kolmogorov_smirnov() {
+        // the asymptotic 5% critical value of sqrt(n) D is 1.3580986393225507
+        let d = kolmogorov_smirnov(1e12, 0.05).unwrap();
+        assert_almost_eq!(d * (1e6 + 0.12), 1.3580986393225507, 1e-7);
+        let d = kolmogorov_smirnov(40.0, 0.01).unwrap();
+        assert_almost_eq!(crate::drift::ks_p_value(d, 40.0), 0.01, 1e-10);
+        assert!(kolmogorov_smirnov(0.0, 0.05).is_err());
+    }
+
+    #[test]
+    fn test_cache_is_shared() {
+        let expected = chi_squared(7.5, 0.123).unwrap();
+        assert!(cached_len() > 0);
+        let handles: Vec<_> = (0..4)
+            .map(|_| thread::spawn(|| chi_squared(7.5, 0.123).unwrap()))
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+        let key = (Table::ChiSquared(7.5f64.to_bits()), 0.123f64.to_bits());
+        assert_eq!(CACHE.lock().unwrap().get(&key), Some(&expected));
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let key = |i: u64| (Table::StudentsT(i), 0.05f64.to_bits());
+        let mut cache = HashMap::new();
+        for i in 0..CAPACITY as u64 {
+            insert_bounded(&mut cache, key(i), i as f64);
+        }
+        assert_eq!(cache.len(), CAPACITY);
+        // replacing a cached value keeps the others
+        insert_bounded(&mut cache, key(0), 1.0);
+        assert_eq!(cache.len(), CAPACITY);
+        // and a new one empties the cache first
+        insert_bounded(&mut cache, key(CAPACITY as u64), 2.0);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&key(CAPACITY as u64)), Some(&2.0));
+    }
+}
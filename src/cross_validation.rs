@@ -0,0 +1,315 @@
+//! Provides reproducible generators of train/test index splits for
+//! evaluating models fitted to data, such as k-fold, stratified k-fold and
+//! repeated cross-validation and bootstrap out-of-bag splits. All generators
+//! draw their randomness from the supplied rng, so seeding it reproduces the
+//! splits.
+
+use crate::{Result, StatsError};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// Represents a single split of the indices `0..n` of a data set into
+/// training and test indices
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fold {
+    train: Vec<usize>,
+    test: Vec<usize>,
+}
+
+impl Fold {
+    /// Returns the training indices. These are sorted in increasing order
+    /// and, for bootstrap splits, may contain duplicates.
+    pub fn train(&self) -> &[usize] {
+        &self.train
+    }
+
+    /// Returns the test indices in increasing order
+    pub fn test(&self) -> &[usize] {
+        &self.test
+    }
+}
+
+/// Splits the indices `0..n` into `k` folds of sizes differing by at most
+/// one after shuffling them, and returns the `k` splits that use one fold
+/// as test set and the remaining folds for training
+///
+/// # Errors
+///
+/// Returns an error if `k < 2` or `k > n`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::cross_validation;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let folds = cross_validation::k_fold(10, 3, &mut rng).unwrap();
+/// assert_eq!(folds.len(), 3);
+/// assert_eq!(folds[0].test().len(), 4);
+/// assert_eq!(folds[0].train().len(), 6);
+/// ```
+pub fn k_fold<R: Rng + ?Sized>(n: usize, k: usize, rng: &mut R) -> Result<Vec<Fold>> {
+    if k < 2 || k > n {
+        return Err(StatsError::BadParams);
+    }
+    let mut indices: Vec<usize> = (0..n).collect();
+    indices.shuffle(rng);
+    let mut assignment = vec![0; n];
+    let mut start = 0;
+    for fold in 0..k {
+        let size = n / k + if fold < n % k { 1 } else { 0 };
+        for &i in &indices[start..start + size] {
+            assignment[i] = fold;
+        }
+        start += size;
+    }
+    Ok(folds_from_assignment(&assignment, k))
+}
+
+/// Splits the indices `0..labels.len()` into `k` folds like `k_fold`, but
+/// keeps the proportion of every label in each fold as close as possible to
+/// its proportion in `labels`
+///
+/// # Remarks
+///
+/// The indices of every label are shuffled and dealt to the folds in turn,
+/// continuing with the next fold where the previous label stopped, so fold
+/// sizes also differ by at most one.
+///
+/// # Errors
+///
+/// Returns an error if `k < 2` or `k > labels.len()`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::cross_validation;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let labels = [0, 0, 0, 0, 1, 1, 1, 1];
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let folds = cross_validation::stratified_k_fold(&labels, 2, &mut rng).unwrap();
+/// for fold in &folds {
+///     let ones = fold.test().iter().filter(|&&i| labels[i] == 1).count();
+///     assert_eq!(ones, 2);
+/// }
+/// ```
+pub fn stratified_k_fold<L, R>(labels: &[L], k: usize, rng: &mut R) -> Result<Vec<Fold>>
+where
+    L: Ord,
+    R: Rng + ?Sized,
+{
+    let n = labels.len();
+    if k < 2 || k > n {
+        return Err(StatsError::BadParams);
+    }
+    let mut strata: BTreeMap<&L, Vec<usize>> = BTreeMap::new();
+    for (i, label) in labels.iter().enumerate() {
+        strata.entry(label).or_default().push(i);
+    }
+    let mut assignment = vec![0; n];
+    let mut fold = 0;
+    for indices in strata.values_mut() {
+        indices.shuffle(rng);
+        for &i in indices.iter() {
+            assignment[i] = fold;
+            fold = (fold + 1) % k;
+        }
+    }
+    Ok(folds_from_assignment(&assignment, k))
+}
+
+/// Returns the splits of `repeats` independent runs of `k_fold`, i.e.
+/// `repeats * k` splits where every consecutive block of `k` splits
+/// partitions the indices `0..n`
+///
+/// # Errors
+///
+/// Returns an error if `repeats` is zero, `k < 2` or `k > n`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::cross_validation;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let folds = cross_validation::repeated_k_fold(10, 5, 3, &mut rng).unwrap();
+/// assert_eq!(folds.len(), 15);
+/// ```
+pub fn repeated_k_fold<R: Rng + ?Sized>(
+    n: usize,
+    k: usize,
+    repeats: usize,
+    rng: &mut R,
+) -> Result<Vec<Fold>> {
+    if repeats == 0 {
+        return Err(StatsError::BadParams);
+    }
+    let mut folds = Vec::with_capacity(repeats * k);
+    for _ in 0..repeats {
+        folds.extend(k_fold(n, k, rng)?);
+    }
+    Ok(folds)
+}
+
+/// Returns `resamples` bootstrap splits of the indices `0..n`, each training
+/// on `n` indices drawn with replacement and testing on the out-of-bag
+/// indices that were not drawn
+///
+/// # Remarks
+///
+/// On average a fraction of about `1 / e ≈ 0.368` of the indices is out of
+/// bag. The test set of a split may be empty, which is very unlikely unless
+/// `n` is small.
+///
+/// # Errors
+///
+/// Returns an error if `n` or `resamples` is zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::cross_validation;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let folds = cross_validation::bootstrap_oob(100, 20, &mut rng).unwrap();
+/// for fold in &folds {
+///     assert_eq!(fold.train().len(), 100);
+///     assert!(fold.test().iter().all(|i| fold.train().binary_search(i).is_err()));
+/// }
+/// ```
+pub fn bootstrap_oob<R: Rng + ?Sized>(
+    n: usize,
+    resamples: usize,
+    rng: &mut R,
+) -> Result<Vec<Fold>> {
+    if n == 0 || resamples == 0 {
+        return Err(StatsError::BadParams);
+    }
+    Ok((0..resamples)
+        .map(|_| {
+            let mut train: Vec<usize> = (0..n).map(|_| rng.gen_range(0..n)).collect();
+            train.sort_unstable();
+            let mut drawn = vec![false; n];
+            for &i in &train {
+                drawn[i] = true;
+            }
+            let test = (0..n).filter(|&i| !drawn[i]).collect();
+            Fold { train, test }
+        })
+        .collect())
+}
+
+/// Builds the `k` splits from the fold index of every data point
+fn folds_from_assignment(assignment: &[usize], k: usize) -> Vec<Fold> {
+    (0..k)
+        .map(|fold| {
+            let (test, train) = (0..assignment.len()).partition(|&i| assignment[i] == fold);
+            Fold { train, test }
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn check_partition(folds: &[Fold], n: usize) {
+        let mut seen = vec![0; n];
+        for fold in folds {
+            assert_eq!(fold.train().len() + fold.test().len(), n);
+            for &i in fold.test() {
+                seen[i] += 1;
+                assert!(fold.train().binary_search(&i).is_err());
+            }
+        }
+        assert!(seen.iter().all(|&c| c == 1));
+    }
+
+    #[test]
+    fn test_bad_params() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(k_fold(10, 1, &mut rng).is_err());
+        assert!(k_fold(3, 4, &mut rng).is_err());
+        assert!(stratified_k_fold(&[1, 2], 3, &mut rng).is_err());
+        assert!(repeated_k_fold(10, 2, 0, &mut rng).is_err());
+        assert!(bootstrap_oob(0, 5, &mut rng).is_err());
+        assert!(bootstrap_oob(5, 0, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_k_fold() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let folds = k_fold(23, 5, &mut rng).unwrap();
+        check_partition(&folds, 23);
+        let sizes: Vec<usize> = folds.iter().map(|f| f.test().len()).collect();
+        assert_eq!(sizes, vec![5, 5, 5, 4, 4]);
+        // leave-one-out
+        let folds = k_fold(4, 4, &mut rng).unwrap();
+        check_partition(&folds, 4);
+        assert!(folds.iter().all(|f| f.test().len() == 1));
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let a = k_fold(50, 5, &mut StdRng::seed_from_u64(7)).unwrap();
+        let b = k_fold(50, 5, &mut StdRng::seed_from_u64(7)).unwrap();
+        assert_eq!(a, b);
+        let c = k_fold(50, 5, &mut StdRng::seed_from_u64(8)).unwrap();
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_stratified_k_fold() {
+        let labels: Vec<&str> = (0..30).map(|i| if i % 3 == 0 { "a" } else { "b" }).collect();
+        let mut rng = StdRng::seed_from_u64(2);
+        let folds = stratified_k_fold(&labels, 5, &mut rng).unwrap();
+        check_partition(&folds, 30);
+        for fold in &folds {
+            assert_eq!(fold.test().len(), 6);
+            let a = fold.test().iter().filter(|&&i| labels[i] == "a").count();
+            assert_eq!(a, 2);
+        }
+    }
+
+    #[test]
+    fn test_repeated_k_fold() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let folds = repeated_k_fold(12, 3, 4, &mut rng).unwrap();
+        assert_eq!(folds.len(), 12);
+        for block in folds.chunks(3) {
+            check_partition(block, 12);
+        }
+        assert_ne!(folds[0..3], folds[3..6]);
+    }
+
+    #[test]
+    fn test_bootstrap_oob() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let folds = bootstrap_oob(1000, 10, &mut rng).unwrap();
+        for fold in &folds {
+            assert_eq!(fold.train().len(), 1000);
+            assert!(fold.train().windows(2).all(|w| w[0] <= w[1]));
+            assert!(fold.test().iter().all(|i| fold.train().binary_search(i).is_err()));
+            let distinct = {
+                let mut t = fold.train().to_vec();
+                t.dedup();
+                t.len()
+            };
+            assert_eq!(distinct + fold.test().len(), 1000);
+            // about 1 / e of the indices are out of bag
+            assert!((fold.test().len() as f64 / 1000.0 - (-1f64).exp()).abs() < 0.05);
+        }
+    }
+}
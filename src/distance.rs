@@ -0,0 +1,710 @@
+//! Provides distances between probability distributions: the Hellinger
+//! and total variation distances, in closed form for pairs of the same
+//! family where known and numerically otherwise, and the Wasserstein
+//! distances between univariate distributions
+
+use crate::distribution::{
+    tanh_sinh, Bernoulli, Beta, Categorical, Continuous, ContinuousCDF, Discrete, DiscreteCDF, Exp,
+    Gamma, LogNormal, Normal, Poisson, Uniform,
+};
+use crate::fp;
+use crate::function::{beta, gamma};
+use crate::statistics::{Distribution, Max, Min};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The `Hellinger` trait provides the closed-form [Hellinger
+/// distance](https://en.wikipedia.org/wiki/Hellinger_distance)
+/// `H(P, Q) = sqrt(1 - ∫ sqrt(p(x) q(x)) dx)` between two distributions of
+/// the same family. It lies in `[0, 1]` and its square is bounded by the
+/// total variation distance. For other pairs see `hellinger_continuous` and
+/// `hellinger_discrete`.
+pub trait Hellinger<Rhs = Self> {
+    /// Returns the Hellinger distance between `self` and `other`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distance::Hellinger;
+    /// use statrs::distribution::Exp;
+    ///
+    /// let p = Exp::new(1.0).unwrap();
+    /// let q = Exp::new(4.0).unwrap();
+    /// // the Bhattacharyya coefficient is 2 sqrt(λ_p λ_q) / (λ_p + λ_q) = 0.8
+    /// assert!((p.hellinger_distance(&q) - 0.2f64.sqrt()).abs() < 1e-15);
+    /// ```
+    fn hellinger_distance(&self, other: &Rhs) -> f64;
+}
+
+/// The `TotalVariation` trait provides the closed-form [total variation
+/// distance](https://en.wikipedia.org/wiki/Total_variation_distance_of_probability_measures)
+/// `sup_A |P(A) - Q(A)|` between two distributions of the same family. For
+/// other pairs see `total_variation_continuous` and
+/// `total_variation_discrete`.
+pub trait TotalVariation<Rhs = Self> {
+    /// Returns the total variation distance between `self` and `other`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distance::TotalVariation;
+    /// use statrs::distribution::Uniform;
+    ///
+    /// let p = Uniform::new(0.0, 1.0).unwrap();
+    /// let q = Uniform::new(0.5, 1.5).unwrap();
+    /// assert_eq!(p.total_variation(&q), 0.5);
+    /// ```
+    fn total_variation(&self, other: &Rhs) -> f64;
+}
+
+/// Returns the Hellinger distance `sqrt(1 - BC)` from the logarithm of the
+/// Bhattacharyya coefficient `BC`, avoiding the cancellation in `1 - BC`
+fn from_ln_bc(ln_bc: f64) -> f64 {
+    (-fp::exp_m1(ln_bc)).max(0.0).sqrt()
+}
+
+/// Returns the total variation distance of two distributions on the real
+/// line from the difference `g = F_p - F_q` of their cdfs and the sorted
+/// points where their densities cross, given whether `p > q` left of the
+/// first crossing
+fn from_crossings<G: Fn(f64) -> f64>(g: G, crossings: &[f64], p_above_first: bool) -> f64 {
+    let mut bounds = vec![f64::NEG_INFINITY];
+    bounds.extend_from_slice(crossings);
+    bounds.push(f64::INFINITY);
+    let at = |x: f64| if x.is_infinite() { 0.0 } else { g(x) };
+    bounds
+        .windows(2)
+        .enumerate()
+        .filter(|&(i, _)| (i % 2 == 0) == p_above_first)
+        .map(|(_, w)| at(w[1]) - at(w[0]))
+        .sum::<f64>()
+        .max(0.0)
+}
+
+impl Hellinger for Normal {
+    /// Calculates the Hellinger distance between two normal distributions
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// H^2 = 1 - sqrt(2σ_p σ_q / (σ_p^2 + σ_q^2)) e^(-(μ_p - μ_q)^2 / (4(σ_p^2 + σ_q^2)))
+    /// ```
+    ///
+    /// where `μ` is the mean and `σ` the standard deviation
+    fn hellinger_distance(&self, other: &Normal) -> f64 {
+        let (m1, s1) = (self.mean().unwrap(), self.std_dev().unwrap());
+        let (m2, s2) = (other.mean().unwrap(), other.std_dev().unwrap());
+        let v = s1 * s1 + s2 * s2;
+        from_ln_bc(0.5 * fp::ln(2.0 * s1 * s2 / v) - fp::powi(m1 - m2, 2) / (4.0 * v))
+    }
+}
+
+impl TotalVariation for Normal {
+    /// Calculates the total variation distance between two normal
+    /// distributions exactly from their cdfs at the at most two points
+    /// where their densities cross
+    fn total_variation(&self, other: &Normal) -> f64 {
+        let (m1, s1) = (self.mean().unwrap(), self.std_dev().unwrap());
+        let (m2, s2) = (other.mean().unwrap(), other.std_dev().unwrap());
+        let g = |x: f64| self.cdf(x) - other.cdf(x);
+        if s1 == s2 {
+            if m1 == m2 {
+                return 0.0;
+            }
+            // a single crossing half way, with p above on the side of its mean
+            return from_crossings(g, &[0.5 * (m1 + m2)], m1 < m2);
+        }
+        // ln p(x) = ln q(x) is the quadratic a x^2 + b x + c = 0
+        let (v1, v2) = (s1 * s1, s2 * s2);
+        let a = 0.5 / v2 - 0.5 / v1;
+        let b = m1 / v1 - m2 / v2;
+        let c = 0.5 * m2 * m2 / v2 - 0.5 * m1 * m1 / v1 + fp::ln(s2 / s1);
+        let disc = (b * b - 4.0 * a * c).max(0.0).sqrt();
+        // numerically stable roots of the quadratic
+        let r = -0.5 * (b + b.signum() * disc);
+        let (x1, x2) = if r == 0.0 {
+            (0.0, 0.0)
+        } else {
+            let (u, w) = (r / a, c / r);
+            (u.min(w), u.max(w))
+        };
+        // the narrower density is above between the crossings
+        from_crossings(g, &[x1, x2], s1 > s2)
+    }
+}
+
+impl Hellinger for LogNormal {
+    /// Calculates the Hellinger distance between two log-normal
+    /// distributions, which equals that of the underlying normal
+    /// distributions
+    fn hellinger_distance(&self, other: &LogNormal) -> f64 {
+        let p = Normal::new(self.location(), self.scale()).unwrap();
+        let q = Normal::new(other.location(), other.scale()).unwrap();
+        p.hellinger_distance(&q)
+    }
+}
+
+impl TotalVariation for LogNormal {
+    /// Calculates the total variation distance between two log-normal
+    /// distributions, which equals that of the underlying normal
+    /// distributions
+    fn total_variation(&self, other: &LogNormal) -> f64 {
+        let p = Normal::new(self.location(), self.scale()).unwrap();
+        let q = Normal::new(other.location(), other.scale()).unwrap();
+        p.total_variation(&q)
+    }
+}
+
+impl Hellinger for Exp {
+    /// Calculates the Hellinger distance between two exponential
+    /// distributions
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// H^2 = 1 - 2 sqrt(λ_p λ_q) / (λ_p + λ_q)
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn hellinger_distance(&self, other: &Exp) -> f64 {
+        let (l1, l2) = (self.rate(), other.rate());
+        from_ln_bc(fp::ln(2.0 * (l1 * l2).sqrt() / (l1 + l2)))
+    }
+}
+
+impl TotalVariation for Exp {
+    /// Calculates the total variation distance between two exponential
+    /// distributions
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// |e^(-λ_q x) - e^(-λ_p x)|,    x = ln(λ_p / λ_q) / (λ_p - λ_q)
+    /// ```
+    ///
+    /// where `λ` is the rate and `x` is the point where the densities cross
+    fn total_variation(&self, other: &Exp) -> f64 {
+        let (l1, l2) = (self.rate(), other.rate());
+        if l1 == l2 {
+            return 0.0;
+        }
+        let x = fp::ln(l1 / l2) / (l1 - l2);
+        (fp::exp(-l2 * x) - fp::exp(-l1 * x)).abs()
+    }
+}
+
+impl Hellinger for Gamma {
+    /// Calculates the Hellinger distance between two gamma distributions
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// H^2 = 1 - Γ((α_p + α_q) / 2) / sqrt(Γ(α_p) Γ(α_q))
+    ///         * β_p^(α_p / 2) β_q^(α_q / 2) / ((β_p + β_q) / 2)^((α_p + α_q) / 2)
+    /// ```
+    ///
+    /// where `α` is the shape and `β` the rate
+    fn hellinger_distance(&self, other: &Gamma) -> f64 {
+        let (a1, b1) = (self.shape(), self.rate());
+        let (a2, b2) = (other.shape(), other.rate());
+        let a = 0.5 * (a1 + a2);
+        from_ln_bc(
+            gamma::ln_gamma(a) - 0.5 * (gamma::ln_gamma(a1) + gamma::ln_gamma(a2))
+                + 0.5 * (a1 * fp::ln(b1) + a2 * fp::ln(b2))
+                - a * fp::ln(0.5 * (b1 + b2)),
+        )
+    }
+}
+
+impl Hellinger for Beta {
+    /// Calculates the Hellinger distance between two beta distributions
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// H^2 = 1 - B((α_p + α_q) / 2, (β_p + β_q) / 2) / sqrt(B(α_p, β_p) B(α_q, β_q))
+    /// ```
+    ///
+    /// where `α` and `β` are the shape parameters and `B` is the beta
+    /// function
+    fn hellinger_distance(&self, other: &Beta) -> f64 {
+        let (a1, b1) = (self.shape_a(), self.shape_b());
+        let (a2, b2) = (other.shape_a(), other.shape_b());
+        from_ln_bc(
+            beta::ln_beta(0.5 * (a1 + a2), 0.5 * (b1 + b2))
+                - 0.5 * (beta::ln_beta(a1, b1) + beta::ln_beta(a2, b2)),
+        )
+    }
+}
+
+/// Returns the length of the overlap of the supports of two uniform
+/// distributions
+fn uniform_overlap(p: &Uniform, q: &Uniform) -> f64 {
+    (p.max().min(q.max()) - p.min().max(q.min())).max(0.0)
+}
+
+impl Hellinger for Uniform {
+    /// Calculates the Hellinger distance between two uniform distributions
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// H^2 = 1 - o / sqrt(l_p l_q)
+    /// ```
+    ///
+    /// where `l` is the length of the support and `o` the length of the
+    /// overlap of the supports
+    fn hellinger_distance(&self, other: &Uniform) -> f64 {
+        let (l1, l2) = (self.max() - self.min(), other.max() - other.min());
+        (1.0 - uniform_overlap(self, other) / (l1 * l2).sqrt())
+            .max(0.0)
+            .sqrt()
+    }
+}
+
+impl TotalVariation for Uniform {
+    /// Calculates the total variation distance between two uniform
+    /// distributions
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 1 - o / max(l_p, l_q)
+    /// ```
+    ///
+    /// where `l` is the length of the support and `o` the length of the
+    /// overlap of the supports
+    fn total_variation(&self, other: &Uniform) -> f64 {
+        let (l1, l2) = (self.max() - self.min(), other.max() - other.min());
+        1.0 - uniform_overlap(self, other) / l1.max(l2)
+    }
+}
+
+impl Hellinger for Poisson {
+    /// Calculates the Hellinger distance between two poisson distributions
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// H^2 = 1 - e^(-(sqrt(λ_p) - sqrt(λ_q))^2 / 2)
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn hellinger_distance(&self, other: &Poisson) -> f64 {
+        from_ln_bc(-0.5 * fp::powi(self.lambda().sqrt() - other.lambda().sqrt(), 2))
+    }
+}
+
+impl TotalVariation for Poisson {
+    /// Calculates the total variation distance between two poisson
+    /// distributions exactly from their cdfs at the point where their mass
+    /// functions cross
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// |F_p(k) - F_q(k)|,    k = floor((λ_q - λ_p) / ln(λ_q / λ_p))
+    /// ```
+    ///
+    /// where `λ` is the rate and `F` the cdf
+    fn total_variation(&self, other: &Poisson) -> f64 {
+        let (l1, l2) = (self.lambda(), other.lambda());
+        if l1 == l2 {
+            return 0.0;
+        }
+        let k = ((l2 - l1) / fp::ln(l2 / l1)).floor() as u64;
+        (self.cdf(k) - other.cdf(k)).abs()
+    }
+}
+
+impl Hellinger for Bernoulli {
+    /// Calculates the Hellinger distance between two bernoulli
+    /// distributions
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// H^2 = 1 - sqrt(p q) - sqrt((1 - p)(1 - q))
+    /// ```
+    ///
+    /// where `p` and `q` are the success probabilities
+    fn hellinger_distance(&self, other: &Bernoulli) -> f64 {
+        let (p, q) = (self.p(), other.p());
+        (1.0 - (p * q).sqrt() - ((1.0 - p) * (1.0 - q)).sqrt())
+            .max(0.0)
+            .sqrt()
+    }
+}
+
+impl TotalVariation for Bernoulli {
+    /// Calculates the total variation distance between two bernoulli
+    /// distributions, i.e. `|p - q|` for the success probabilities `p` and
+    /// `q`
+    fn total_variation(&self, other: &Bernoulli) -> f64 {
+        (self.p() - other.p()).abs()
+    }
+}
+
+impl Hellinger for Categorical {
+    /// Calculates the Hellinger distance between two categorical
+    /// distributions, where categories beyond the end of either distribution
+    /// have probability zero
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// H^2 = 1 - Σ sqrt(p_i q_i)
+    /// ```
+    fn hellinger_distance(&self, other: &Categorical) -> f64 {
+        let bc: f64 = (0..=self.max().min(other.max()))
+            .map(|i| (self.pmf(i) * other.pmf(i)).sqrt())
+            .sum();
+        (1.0 - bc).max(0.0).sqrt()
+    }
+}
+
+impl TotalVariation for Categorical {
+    /// Calculates the total variation distance between two categorical
+    /// distributions, where categories beyond the end of either distribution
+    /// have probability zero
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ |p_i - q_i| / 2
+    /// ```
+    fn total_variation(&self, other: &Categorical) -> f64 {
+        0.5 * (0..=self.max().max(other.max()))
+            .map(|i| (self.pmf(i) - other.pmf(i)).abs())
+            .sum::<f64>()
+    }
+}
+
+/// Computes the Hellinger distance between the continuous distributions `p`
+/// and `q` by tanh-sinh quadrature of the Bhattacharyya coefficient
+/// `∫ sqrt(q(x) / p(x)) dP(x)` over the quantiles of `p`
+///
+/// # Remarks
+///
+/// The quadrature assumes the integrand is smooth over the support of `p`,
+/// so if the support of `q` ends inside that of `p` the distributions
+/// should be passed the other way round, the distance being symmetric.
+/// The integrand is unbounded in the tails of `p` when `q` has the heavier
+/// tails, which limits the accuracy to about `1e-8`. Passing the
+/// distribution with the heavier tails as `p` gives about `1e-10`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distance::{hellinger_continuous, Hellinger};
+/// use statrs::distribution::Normal;
+///
+/// let p = Normal::new(1.0, 2.0).unwrap();
+/// let q = Normal::new(0.0, 1.0).unwrap();
+/// assert!((hellinger_continuous(&p, &q) - p.hellinger_distance(&q)).abs() < 1e-10);
+/// ```
+pub fn hellinger_continuous<P, Q>(p: &P, q: &Q) -> f64
+where
+    P: ContinuousCDF<f64, f64> + Continuous<f64, f64>,
+    Q: Continuous<f64, f64>,
+{
+    let bc: f64 = tanh_sinh()
+        .map(|(t, t_c, w)| {
+            let x = if t <= 0.5 {
+                p.inverse_cdf(t)
+            } else {
+                p.inverse_cdf(1.0 - t_c)
+            };
+            let r = fp::exp(0.5 * (q.ln_pdf(x) - p.ln_pdf(x)));
+            if r.is_nan() {
+                0.0
+            } else {
+                w * r
+            }
+        })
+        .sum();
+    (1.0 - bc).max(0.0).sqrt()
+}
+
+/// Returns the points at which `total_variation_continuous` compares the
+/// densities, the quantiles of `d` at the tanh-sinh nodes and on a uniform
+/// grid
+fn scan_nodes<D: ContinuousCDF<f64, f64>>(d: &D) -> Vec<f64> {
+    tanh_sinh()
+        .map(|(t, t_c, _)| {
+            if t <= 0.5 {
+                d.inverse_cdf(t)
+            } else {
+                d.inverse_cdf(1.0 - t_c)
+            }
+        })
+        .chain((0..503).map(|i| d.inverse_cdf((i as f64 + 0.5) / 503.0)))
+        .filter(|x| x.is_finite())
+        .collect()
+}
+
+/// Computes the total variation distance between the continuous
+/// distributions `p` and `q` from the difference of their cdfs between the
+/// points where their densities cross
+///
+/// # Remarks
+///
+/// The crossings are located by scanning the densities at about a thousand
+/// quantiles of each of `p` and `q`, concentrated towards their tails, and
+/// refined by bisection, so crossings closer together than the scan
+/// resolves are missed.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distance::{total_variation_continuous, TotalVariation};
+/// use statrs::distribution::Normal;
+///
+/// let p = Normal::new(0.0, 1.0).unwrap();
+/// let q = Normal::new(1.0, 2.0).unwrap();
+/// let tv = total_variation_continuous(&p, &q);
+/// assert!((tv - p.total_variation(&q)).abs() < 1e-12);
+/// ```
+pub fn total_variation_continuous<P, Q>(p: &P, q: &Q) -> f64
+where
+    P: ContinuousCDF<f64, f64> + Continuous<f64, f64>,
+    Q: ContinuousCDF<f64, f64> + Continuous<f64, f64>,
+{
+    let above = |x: f64| p.ln_pdf(x) > q.ln_pdf(x);
+    let mut nodes = scan_nodes(p);
+    nodes.extend(scan_nodes(q));
+    nodes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    nodes.dedup();
+    let first = match nodes.first() {
+        Some(&x) => above(x),
+        None => return 0.0,
+    };
+    let mut crossings = Vec::new();
+    for w in nodes.windows(2) {
+        let (mut a, mut b) = (w[0], w[1]);
+        let side = above(a);
+        if side == above(b) {
+            continue;
+        }
+        // bisect down to adjacent floating point numbers
+        for _ in 0..200 {
+            let mid = 0.5 * (a + b);
+            if mid <= a || mid >= b {
+                break;
+            }
+            if above(mid) == side {
+                a = mid;
+            } else {
+                b = mid;
+            }
+        }
+        crossings.push(b);
+    }
+    from_crossings(|x| p.cdf(x) - q.cdf(x), &crossings, first)
+}
+
+/// Returns the smallest and one past the largest value to sum over so that
+/// both discrete distributions have negligible mass outside
+fn discrete_range<P, Q>(p: &P, q: &Q) -> (u64, u64)
+where
+    P: DiscreteCDF<u64, f64>,
+    Q: DiscreteCDF<u64, f64>,
+{
+    let lower = p.min().min(q.min());
+    let mut upper = lower;
+    while (upper < p.max() && p.sf(upper) >= 1e-17) || (upper < q.max() && q.sf(upper) >= 1e-17) {
+        upper += 1;
+    }
+    (lower, upper + 1)
+}
+
+/// Computes the Hellinger distance between the discrete distributions `p`
+/// and `q` by summing `sqrt(p(k) q(k))` over the union of their supports,
+/// up to where both upper tails drop below `1e-17`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distance::hellinger_discrete;
+/// use statrs::distribution::{Binomial, Poisson};
+///
+/// let p = Binomial::new(0.01, 100).unwrap();
+/// let q = Poisson::new(1.0).unwrap();
+/// assert!(hellinger_discrete(&p, &q) < 0.01);
+/// ```
+pub fn hellinger_discrete<P, Q>(p: &P, q: &Q) -> f64
+where
+    P: DiscreteCDF<u64, f64> + Discrete<u64, f64>,
+    Q: DiscreteCDF<u64, f64> + Discrete<u64, f64>,
+{
+    let (lower, upper) = discrete_range(p, q);
+    let bc: f64 = (lower..upper).map(|k| (p.pmf(k) * q.pmf(k)).sqrt()).sum();
+    (1.0 - bc).max(0.0).sqrt()
+}
+
+/// Computes the total variation distance between the discrete
+/// distributions `p` and `q` by summing `|p(k) - q(k)| / 2` over the union
+/// of their supports, up to where both upper tails drop below `1e-17`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distance::{total_variation_discrete, TotalVariation};
+/// use statrs::distribution::Poisson;
+///
+/// let p = Poisson::new(2.0).unwrap();
+/// let q = Poisson::new(3.0).unwrap();
+/// assert!((total_variation_discrete(&p, &q) - p.total_variation(&q)).abs() < 1e-15);
+/// ```
+pub fn total_variation_discrete<P, Q>(p: &P, q: &Q) -> f64
+where
+    P: DiscreteCDF<u64, f64> + Discrete<u64, f64>,
+    Q: DiscreteCDF<u64, f64> + Discrete<u64, f64>,
+{
+    let (lower, upper) = discrete_range(p, q);
+    0.5 * (lower..upper)
+        .map(|k| (p.pmf(k) - q.pmf(k)).abs())
+        .sum::<f64>()
+}
+
+/// Computes the [Wasserstein
+/// distance](https://en.wikipedia.org/wiki/Wasserstein_metric) of order
+/// `order` between the univariate distributions `p` and `q` by tanh-sinh
+/// quadrature over their quantile functions
+///
+/// # Formula
+///
+/// ```ignore
+/// W_r(P, Q) = (∫_0^1 |F^-1(u) - G^-1(u)|^r du)^(1 / r)
+/// ```
+///
+/// where `F^-1` and `G^-1` are the inverse cdfs of `p` and `q`
+///
+/// # Remarks
+///
+/// The distance is only finite if both distributions have finite moments of
+/// order `order`, which is not checked.
+///
+/// # Errors
+///
+/// Returns an error if `order` is smaller than one or `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distance::wasserstein;
+/// use statrs::distribution::Normal;
+///
+/// // W_2 between normal distributions is sqrt((μ_p - μ_q)^2 + (σ_p - σ_q)^2)
+/// let p = Normal::new(0.0, 1.0).unwrap();
+/// let q = Normal::new(3.0, 5.0).unwrap();
+/// assert!((wasserstein(&p, &q, 2.0).unwrap() - 5.0).abs() < 1e-9);
+/// ```
+pub fn wasserstein<P, Q>(p: &P, q: &Q, order: f64) -> Result<f64>
+where
+    P: ContinuousCDF<f64, f64>,
+    Q: ContinuousCDF<f64, f64>,
+{
+    if order.is_nan() || order < 1.0 {
+        return Err(StatsError::ArgGte("order", 1.0));
+    }
+    let integral: f64 = tanh_sinh()
+        .map(|(t, t_c, w)| {
+            let d = if t <= 0.5 {
+                p.inverse_cdf(t) - q.inverse_cdf(t)
+            } else {
+                p.inverse_cdf(1.0 - t_c) - q.inverse_cdf(1.0 - t_c)
+            };
+            w * fp::powf(d.abs(), order)
+        })
+        .sum();
+    Ok(fp::powf(integral, 1.0 / order))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hellinger_closed_forms() {
+        let n = [Normal::new(0.0, 1.0).unwrap(), Normal::new(1.5, 0.4).unwrap()];
+        assert_almost_eq!(n[0].hellinger_distance(&n[1]), hellinger_continuous(&n[0], &n[1]), 1e-10);
+        assert_almost_eq!(n[1].hellinger_distance(&n[0]), hellinger_continuous(&n[1], &n[0]), 1e-7);
+        assert_eq!(n[0].hellinger_distance(&n[0]), 0.0);
+        let ln = [LogNormal::new(0.0, 1.0).unwrap(), LogNormal::new(0.3, 0.6).unwrap()];
+        assert_almost_eq!(ln[0].hellinger_distance(&ln[1]), hellinger_continuous(&ln[0], &ln[1]), 1e-10);
+        let e = [Exp::new(1.0).unwrap(), Exp::new(2.5).unwrap()];
+        assert_almost_eq!(e[0].hellinger_distance(&e[1]), hellinger_continuous(&e[0], &e[1]), 1e-10);
+        let g = [Gamma::new(2.0, 1.0).unwrap(), Gamma::new(3.5, 2.5).unwrap()];
+        assert_almost_eq!(g[0].hellinger_distance(&g[1]), hellinger_continuous(&g[0], &g[1]), 1e-10);
+        let b = [Beta::new(2.0, 5.0).unwrap(), Beta::new(1.5, 1.5).unwrap()];
+        assert_almost_eq!(b[0].hellinger_distance(&b[1]), hellinger_continuous(&b[0], &b[1]), 1e-9);
+        let u = [Uniform::new(0.0, 2.0).unwrap(), Uniform::new(1.0, 4.0).unwrap()];
+        assert_almost_eq!(u[0].hellinger_distance(&u[1]), (1.0 - 1.0 / 6f64.sqrt()).sqrt(), 1e-15);
+        let nested = Uniform::new(0.5, 1.5).unwrap();
+        assert_almost_eq!(nested.hellinger_distance(&u[0]), hellinger_continuous(&nested, &u[0]), 1e-10);
+        assert_eq!(u[0].hellinger_distance(&Uniform::new(3.0, 4.0).unwrap()), 1.0);
+    }
+
+    #[test]
+    fn test_total_variation_closed_forms() {
+        let n = [Normal::new(0.0, 1.0).unwrap(), Normal::new(1.5, 0.4).unwrap(), Normal::new(-0.5, 1.0).unwrap()];
+        for (i, j) in [(0, 1), (1, 0), (0, 2), (2, 0), (1, 2)].iter() {
+            assert_almost_eq!(n[*i].total_variation(&n[*j]), total_variation_continuous(&n[*i], &n[*j]), 1e-12);
+        }
+        // equal variances: 2Φ(|Δμ| / 2σ) - 1
+        assert_almost_eq!(n[0].total_variation(&n[2]), 2.0 * n[0].cdf(0.25) - 1.0, 1e-15);
+        assert_eq!(n[0].total_variation(&n[0]), 0.0);
+        let ln = [LogNormal::new(0.0, 1.0).unwrap(), LogNormal::new(0.3, 0.6).unwrap()];
+        assert_almost_eq!(ln[0].total_variation(&ln[1]), total_variation_continuous(&ln[0], &ln[1]), 1e-12);
+        let e = [Exp::new(1.0).unwrap(), Exp::new(2.5).unwrap()];
+        assert_almost_eq!(e[0].total_variation(&e[1]), total_variation_continuous(&e[0], &e[1]), 1e-12);
+        assert_almost_eq!(e[1].total_variation(&e[0]), e[0].total_variation(&e[1]), 1e-15);
+        let u = [Uniform::new(0.0, 2.0).unwrap(), Uniform::new(1.0, 4.0).unwrap()];
+        assert_almost_eq!(u[0].total_variation(&u[1]), 2.0 / 3.0, 1e-15);
+        assert_almost_eq!(u[0].total_variation(&u[1]), total_variation_continuous(&u[0], &u[1]), 1e-12);
+    }
+
+    #[test]
+    fn test_cross_family() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let e = Exp::new(1.0).unwrap();
+        let tv = total_variation_continuous(&n, &e);
+        assert_almost_eq!(tv, total_variation_continuous(&e, &n), 1e-12);
+        // reference value from numerical integration with mpmath
+        let h = hellinger_continuous(&e, &n);
+        assert_almost_eq!(h, 0.55742743137718194661, 1e-10);
+        assert!(h * h <= tv && tv <= 2f64.sqrt() * h);
+    }
+
+    #[test]
+    fn test_discrete() {
+        let p = [Poisson::new(2.0).unwrap(), Poisson::new(3.7).unwrap()];
+        assert_almost_eq!(p[0].hellinger_distance(&p[1]), hellinger_discrete(&p[0], &p[1]), 1e-14);
+        assert_almost_eq!(p[0].total_variation(&p[1]), total_variation_discrete(&p[0], &p[1]), 1e-14);
+        assert_almost_eq!(p[1].total_variation(&p[0]), total_variation_discrete(&p[1], &p[0]), 1e-14);
+        let b = [Bernoulli::new(0.2).unwrap(), Bernoulli::new(0.7).unwrap()];
+        assert_almost_eq!(b[0].hellinger_distance(&b[1]), hellinger_discrete(&b[0], &b[1]), 1e-15);
+        assert_almost_eq!(b[0].total_variation(&b[1]), 0.5, 1e-15);
+        let c = [Categorical::new(&[1.0, 2.0, 1.0]).unwrap(), Categorical::new(&[1.0, 1.0]).unwrap()];
+        assert_almost_eq!(c[0].hellinger_distance(&c[1]), hellinger_discrete(&c[0], &c[1]), 1e-15);
+        assert_almost_eq!(c[0].total_variation(&c[1]), 0.25, 1e-15);
+        assert_almost_eq!(c[0].total_variation(&c[1]), total_variation_discrete(&c[0], &c[1]), 1e-15);
+    }
+
+    #[test]
+    fn test_wasserstein() {
+        let p = Normal::new(0.0, 1.0).unwrap();
+        let q = Normal::new(2.0, 1.0).unwrap();
+        assert_almost_eq!(wasserstein(&p, &q, 1.0).unwrap(), 2.0, 1e-12);
+        assert_almost_eq!(wasserstein(&p, &q, 3.0).unwrap(), 2.0, 1e-12);
+        // quantiles of exponential distributions scale with 1 / λ
+        let e = [Exp::new(1.0).unwrap(), Exp::new(4.0).unwrap()];
+        assert_almost_eq!(wasserstein(&e[0], &e[1], 1.0).unwrap(), 0.75, 1e-9);
+        assert_almost_eq!(wasserstein(&e[0], &e[1], 2.0).unwrap(), 0.75 * 2f64.sqrt(), 1e-9);
+        assert_eq!(wasserstein(&p, &p, 1.0).unwrap(), 0.0);
+        assert!(wasserstein(&p, &q, 0.5).is_err());
+        assert!(wasserstein(&p, &q, f64::NAN).is_err());
+    }
+}
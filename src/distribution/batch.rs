@@ -0,0 +1,103 @@
+use rand::distributions::{DistIter, Distribution};
+use rand::Rng;
+
+/// The `SampleBatch` trait provides batch sampling for distributions whose
+/// samplers have setup costs, such as the constants of a rejection method,
+/// by computing them once in a dedicated sampler and reusing it for every
+/// draw
+///
+/// # Remarks
+///
+/// The batch methods draw exactly the same values as repeated calls to
+/// `sample` with the same source of randomness. The iterator is named
+/// `samples` rather than `sample_iter` so that it is not shadowed by the
+/// by-value `sample_iter` of `rand`'s `Distribution`, which redoes the setup
+/// for every draw.
+pub trait SampleBatch<T> {
+    /// The sampler holding the precomputed setup
+    type Sampler: Distribution<T>;
+
+    /// Returns a sampler for the distribution which can be reused for many
+    /// draws
+    fn sampler(&self) -> Self::Sampler;
+
+    /// Returns an infinite iterator of samples drawn using `rng`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Beta, SampleBatch};
+    ///
+    /// let n = Beta::new(2.0, 5.0).unwrap();
+    /// let mean = n.samples(rand::thread_rng()).take(10000).sum::<f64>() / 10000.0;
+    /// assert!((mean - 2.0 / 7.0).abs() < 0.02);
+    /// ```
+    fn samples<R: Rng>(&self, rng: R) -> DistIter<Self::Sampler, R, T> {
+        self.sampler().sample_iter(rng)
+    }
+
+    /// Fills `out` with samples drawn using `rng`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Poisson, SampleBatch};
+    ///
+    /// let n = Poisson::new(50.0).unwrap();
+    /// let mut samples = [0.0; 100];
+    /// n.sample_fill(&mut rand::thread_rng(), &mut samples);
+    /// assert!(samples.iter().all(|&x| x >= 0.0 && x.fract() == 0.0));
+    /// ```
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [T]) {
+        let sampler = self.sampler();
+        for x in out.iter_mut() {
+            *x = sampler.sample(rng);
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::{Beta, ChiSquared, Dirichlet, Erlang, Gamma, Poisson};
+    use nalgebra::DVector;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // the batch methods must draw the same values as repeated calls to
+    // `sample` from the same seed
+    fn check_matches_sample<D: SampleBatch<f64> + Distribution<f64>>(dist: &D) {
+        let mut rng = StdRng::seed_from_u64(1);
+        let expected: Vec<f64> = (0..100).map(|_| dist.sample(&mut rng)).collect();
+        let mut filled = vec![0.0; 100];
+        dist.sample_fill(&mut StdRng::seed_from_u64(1), &mut filled);
+        assert_eq!(filled, expected);
+        let iterated: Vec<f64> = dist.samples(StdRng::seed_from_u64(1)).take(100).collect();
+        assert_eq!(iterated, expected);
+    }
+
+    #[test]
+    fn test_sample_batch() {
+        check_matches_sample(&Gamma::new(0.3, 2.0).unwrap());
+        check_matches_sample(&Gamma::new(4.5, 0.5).unwrap());
+        check_matches_sample(&Erlang::new(3, 2.0).unwrap());
+        check_matches_sample(&ChiSquared::new(5.0).unwrap());
+        check_matches_sample(&Beta::new(0.5, 3.0).unwrap());
+        check_matches_sample(&Poisson::new(4.0).unwrap());
+        check_matches_sample(&Poisson::new(300.0).unwrap());
+    }
+
+    #[test]
+    fn test_sample_batch_dirichlet() {
+        let dist = Dirichlet::new(vec![0.5, 1.0, 4.0]).unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        let expected: Vec<DVector<f64>> = (0..20).map(|_| dist.sample(&mut rng)).collect();
+        let mut filled = vec![DVector::zeros(3); 20];
+        dist.sample_fill(&mut StdRng::seed_from_u64(1), &mut filled);
+        assert_eq!(filled, expected);
+        for x in &filled {
+            assert!((x.sum() - 1.0).abs() < 1e-12);
+        }
+    }
+}
@@ -1,6 +1,11 @@
-use crate::distribution::{Binomial, Discrete, DiscreteCDF};
+use crate::distribution::{
+    fit, Binomial, CharacteristicFunction, Discrete, DiscreteCDF, Fit, KullbackLeibler,
+    Parameterized,
+};
+use crate::fp;
 use crate::statistics::*;
-use crate::Result;
+use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 
 /// Implements the
@@ -49,6 +54,21 @@ impl Bernoulli {
         Binomial::new(p, 1).map(|b| Bernoulli { b })
     }
 
+    /// Constructs a new bernoulli distribution from parameters already known to
+    /// be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(p: f64) -> Bernoulli {
+        debug_assert!(Bernoulli::new(p).is_ok(), "invalid bernoulli parameters");
+        Bernoulli {
+            b: Binomial::new_unchecked(p, 1),
+        }
+    }
+
     /// Returns the probability of success `p` of the
     /// bernoulli distribution.
     ///
@@ -179,6 +199,18 @@ impl Distribution<f64> for Bernoulli {
     fn skewness(&self) -> Option<f64> {
         self.b.skewness()
     }
+    /// Returns the cumulant of order `k` of the bernoulli distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// κ_1 = p, κ_(k + 1) = p(1 - p) * dκ_k / dp
+    /// ```
+    ///
+    /// where `p` is the probability of success
+    fn cumulant(&self, k: u32) -> Option<f64> {
+        self.b.cumulant(k)
+    }
 }
 
 impl Median<f64> for Bernoulli {
@@ -238,3 +270,66 @@ impl Discrete<u64, f64> for Bernoulli {
         self.b.ln_pmf(x)
     }
 }
+
+impl CharacteristicFunction<f64, f64> for Bernoulli {
+    /// Calculates the characteristic function for the bernoulli
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 1 - p + pe^(it)
+    /// ```
+    ///
+    /// where `p` is the success probability
+    fn cf(&self, t: f64) -> Complex<f64> {
+        self.b.cf(t)
+    }
+}
+
+impl KullbackLeibler for Bernoulli {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// bernoulli distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// p ln(p / q) + (1 - p) ln((1 - p) / (1 - q))
+    /// ```
+    ///
+    /// where `p` and `q` are the success probabilities and terms with a
+    /// zero factor in front are zero
+    fn kl_divergence(&self, other: &Bernoulli) -> f64 {
+        let term = |p: f64, q: f64| if p == 0.0 { 0.0 } else { p * fp::ln(p / q) };
+        term(self.p(), other.p()) + term(1.0 - self.p(), 1.0 - other.p())
+    }
+}
+
+impl Parameterized for Bernoulli {
+    fn param_names() -> &'static [&'static str] {
+        &["p"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.p()]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Bernoulli> {
+        match *params {
+            [p] => Bernoulli::new(p),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for Bernoulli {
+    type Sample = u64;
+
+    /// Fits the probability of success as the fraction of ones
+    fn fit_mle(data: &[u64]) -> Result<Bernoulli> {
+        if data.iter().any(|&k| k > 1) {
+            return Err(StatsError::BadParams);
+        }
+        Bernoulli::new(fit::count_mean(data)?)
+    }
+}
@@ -1,10 +1,14 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{
+    fit, Continuous, ContinuousCDF, Fit, GammaSampler, KullbackLeibler, Parameterized, SampleBatch,
+};
+use crate::fp;
 use crate::function::{beta, gamma};
 use crate::is_zero;
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use core::f64::INFINITY as INF;
 use rand::Rng;
+use std::f64;
 
 /// Implements the [Beta](https://en.wikipedia.org/wiki/Beta_distribution)
 /// distribution
@@ -26,6 +30,30 @@ pub struct Beta {
     shape_b: f64,
 }
 
+/// Selects how `Beta::fit_mle_with_boundary` treats observations of exactly
+/// `0` or `1`, at which the beta log-likelihood is infinite for some shapes
+/// so that the maximum likelihood estimate does not exist
+///
+/// # Remarks
+///
+/// Such observations are common in practice, e.g. rates of conversion
+/// measured on small groups, whose fraction of zeros and ones is rather
+/// modelled separately with a zero-one inflated beta distribution when it
+/// is large.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BetaBoundary {
+    /// Fails with an error, as `fit_mle` does
+    Reject,
+    /// Drops the observations at the boundary and fits the rest
+    Discard,
+    /// Maps every observation `x` of a sample of size `n` to
+    /// `(x (n - 1) + 1/2) / n`, the transformation of Smithson & Verkuilen
+    /// (2006) which shrinks the sample towards `1/2` just enough to move
+    /// the boundary into the open interval. Samples without boundary
+    /// observations are fitted unchanged.
+    Squeeze,
+}
+
 impl Beta {
     /// Constructs a new beta distribution with shapeA (α) of `shape_a`
     /// and shapeB (β) of `shape_b`
@@ -58,6 +86,87 @@ impl Beta {
         Ok(Beta { shape_a, shape_b })
     }
 
+    /// Constructs a new beta distribution from parameters already known to be
+    /// valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(shape_a: f64, shape_b: f64) -> Beta {
+        debug_assert!(
+            Beta::new(shape_a, shape_b).is_ok(),
+            "invalid beta parameters"
+        );
+        Beta { shape_a, shape_b }
+    }
+
+    /// Constructs a new beta distribution with mean `mu` and concentration
+    /// (prior sample size) `kappa`, i.e. with shapes `α = μκ` and
+    /// `β = (1 - μ)κ`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mu` is not in `(0, 1)` or if `kappa` is not
+    /// positive and finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Beta;
+    ///
+    /// let n = Beta::from_mean_concentration(0.25, 8.0).unwrap();
+    /// assert_eq!(n.shape_a(), 2.0);
+    /// assert_eq!(n.shape_b(), 6.0);
+    /// ```
+    pub fn from_mean_concentration(mu: f64, kappa: f64) -> Result<Beta> {
+        if mu.is_nan() || mu <= 0.0 || mu >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("mu", 0.0, 1.0));
+        }
+        if !kappa.is_finite() || kappa <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("kappa"));
+        }
+        Beta::new(mu * kappa, (1.0 - mu) * kappa)
+    }
+
+    /// Constructs a new beta distribution with mean `mu` and variance
+    /// `var`, which is the method of moments fit given a sample mean and
+    /// variance. The concentration is `μ(1 - μ) / var - 1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mu` is not in `(0, 1)` or if `var` is not in
+    /// `(0, μ(1 - μ))`, the variances attainable by a beta distribution
+    /// with mean `mu`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Beta;
+    /// use statrs::statistics::Distribution;
+    ///
+    /// let n = Beta::from_mean_variance(0.25, 0.0125).unwrap();
+    /// assert!((n.concentration() - 14.0).abs() < 1e-12);
+    /// assert!((n.variance().unwrap() - 0.0125).abs() < 1e-15);
+    ///
+    /// // a Bernoulli(0.25) variable has the largest variance, 0.1875
+    /// assert!(Beta::from_mean_variance(0.25, 0.1875).is_err());
+    /// ```
+    pub fn from_mean_variance(mu: f64, var: f64) -> Result<Beta> {
+        if mu.is_nan() || mu <= 0.0 || mu >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("mu", 0.0, 1.0));
+        }
+        let max_var = mu * (1.0 - mu);
+        if var.is_nan() || var <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("var"));
+        }
+        if var >= max_var {
+            return Err(StatsError::ArgLt("var", max_var));
+        }
+        Beta::from_mean_concentration(mu, max_var / var - 1.0)
+    }
+
     /// Returns the shapeA (α) of the beta distribution
     ///
     /// # Examples
@@ -85,17 +194,174 @@ impl Beta {
     pub fn shape_b(&self) -> f64 {
         self.shape_b
     }
+
+    /// Returns the anti-mode of the beta distribution, the minimum of the
+    /// density of a U-shaped distribution with `α < 1` and `β < 1`, or
+    /// `None` otherwise
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 - α) / (2 - α - β)
+    /// ```
+    ///
+    /// where `α` is shapeA and `β` is shapeB
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Beta;
+    ///
+    /// let n = Beta::new(0.5, 0.5).unwrap();
+    /// assert_eq!(n.anti_mode(), Some(0.5));
+    /// assert_eq!(Beta::new(2.0, 0.5).unwrap().anti_mode(), None);
+    /// ```
+    pub fn anti_mode(&self) -> Option<f64> {
+        if self.shape_a < 1.0 && self.shape_b < 1.0 {
+            Some((1.0 - self.shape_a) / (2.0 - self.shape_a - self.shape_b))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the concentration `α + β` of the beta distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Beta;
+    ///
+    /// let n = Beta::new(2.0, 3.0).unwrap();
+    /// assert_eq!(n.concentration(), 5.0);
+    /// ```
+    pub fn concentration(&self) -> f64 {
+        self.shape_a + self.shape_b
+    }
+
+    /// Fits a beta distribution to `data` by the method of moments, with the
+    /// concentration `α + β = mean (1 - mean) / var - 1` split in the
+    /// proportions `mean` and `1 - mean`, the variance taken with the
+    /// divisor `n`
+    ///
+    /// # Remarks
+    ///
+    /// Unlike the maximum likelihood estimates, the moment estimates exist
+    /// for samples containing observations of exactly `0` or `1`, and serve
+    /// as the starting values of `fit_mle`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty, has an observation outside
+    /// `[0, 1]`, has zero variance or only takes the values `0` and `1`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Beta;
+    ///
+    /// let n = Beta::fit_moments(&[0.0, 0.25, 0.5, 0.25]).unwrap();
+    /// assert_eq!(n.shape_a(), 1.25);
+    /// assert_eq!(n.shape_b(), 3.75);
+    /// ```
+    pub fn fit_moments(data: &[f64]) -> Result<Beta> {
+        fit::check_sample(data, |x| (0.0..=1.0).contains(&x))?;
+        let (mean, variance) = fit::mean_variance(data);
+        if variance <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let common = mean * (1.0 - mean) / variance - 1.0;
+        if common <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        Beta::new(mean * common, (1.0 - mean) * common)
+    }
+
+    /// Fits a beta distribution to `data` by maximum likelihood as
+    /// `fit_mle`, treating observations of exactly `0` or `1` according to
+    /// `boundary`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has an observation outside `[0, 1]` or
+    /// not finite, if it has a boundary observation and `boundary` is
+    /// `Reject`, or if the remaining sample has no estimate as for `fit_mle`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Beta, BetaBoundary};
+    ///
+    /// let rates = [0.0, 0.12, 0.3, 0.05, 0.21, 1.0, 0.08, 0.17];
+    /// assert!(Beta::fit_mle_with_boundary(&rates, BetaBoundary::Reject).is_err());
+    /// let squeezed = Beta::fit_mle_with_boundary(&rates, BetaBoundary::Squeeze).unwrap();
+    /// let discarded = Beta::fit_mle_with_boundary(&rates, BetaBoundary::Discard).unwrap();
+    /// assert!(squeezed.shape_a() < discarded.shape_a());
+    /// ```
+    pub fn fit_mle_with_boundary(data: &[f64], boundary: BetaBoundary) -> Result<Beta> {
+        fit::check_sample(data, |x| (0.0..=1.0).contains(&x))?;
+        if !data.iter().any(|&x| x == 0.0 || x == 1.0) {
+            return Beta::fit_mle(data);
+        }
+        match boundary {
+            BetaBoundary::Reject => Err(StatsError::BadParams),
+            BetaBoundary::Discard => {
+                let inner: Vec<f64> = data
+                    .iter()
+                    .cloned()
+                    .filter(|&x| x > 0.0 && x < 1.0)
+                    .collect();
+                Beta::fit_mle(&inner)
+            }
+            BetaBoundary::Squeeze => {
+                let n = data.len() as f64;
+                let squeezed: Vec<f64> = data.iter().map(|&x| (x * (n - 1.0) + 0.5) / n).collect();
+                Beta::fit_mle(&squeezed)
+            }
+        }
+    }
 }
 
 impl ::rand::distributions::Distribution<f64> for Beta {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        ::rand::distributions::Distribution::sample(&self.sampler(), rng)
+    }
+}
+
+/// A sampler for the beta distribution which sets up the samplers of its
+/// two underlying gamma distributions once, for drawing many samples
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BetaSampler {
+    gamma_a: GammaSampler,
+    gamma_b: GammaSampler,
+}
+
+impl ::rand::distributions::Distribution<f64> for BetaSampler {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
         // Generated by sampling two gamma distributions and normalizing.
-        let x = super::gamma::sample_unchecked(rng, self.shape_a, 1.0);
-        let y = super::gamma::sample_unchecked(rng, self.shape_b, 1.0);
+        if self.gamma_a.boosted() || self.gamma_b.boosted() {
+            // gamma samples of small shapes can underflow to zero, so they
+            // are normalized on the log scale
+            let ln_x = self.gamma_a.sample_ln(rng);
+            let ln_y = self.gamma_b.sample_ln(rng);
+            return 1.0 / (1.0 + fp::exp(ln_y - ln_x));
+        }
+        let x = ::rand::distributions::Distribution::sample(&self.gamma_a, rng);
+        let y = ::rand::distributions::Distribution::sample(&self.gamma_b, rng);
         x / (x + y)
     }
 }
 
+impl SampleBatch<f64> for Beta {
+    type Sampler = BetaSampler;
+
+    fn sampler(&self) -> BetaSampler {
+        BetaSampler {
+            gamma_a: GammaSampler::new(self.shape_a, 1.0),
+            gamma_b: GammaSampler::new(self.shape_b, 1.0),
+        }
+    }
+}
+
 impl ContinuousCDF<f64, f64> for Beta {
     /// Calculates the cumulative distribution function for the beta
     /// distribution
@@ -128,6 +394,33 @@ impl ContinuousCDF<f64, f64> for Beta {
             beta::beta_reg(self.shape_a, self.shape_b, x)
         }
     }
+
+    /// Calculates the survival function for the beta
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// I_(1 - x)(β, α)
+    /// ```
+    ///
+    /// where `α` is shapeA, `β` is shapeB, and `I_x` is the regularized
+    /// lower incomplete beta function
+    fn sf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            1.0
+        } else if x >= 1.0 {
+            0.0
+        } else if self.shape_a.is_infinite() {
+            1.0
+        } else if self.shape_b.is_infinite() {
+            0.0
+        } else if ulps_eq!(self.shape_a, 1.0) && ulps_eq!(self.shape_b, 1.0) {
+            1.0 - x
+        } else {
+            beta::beta_reg(self.shape_b, self.shape_a, 1.0 - x)
+        }
+    }
 }
 
 impl Min<f64> for Beta {
@@ -241,21 +534,74 @@ impl Distribution<f64> for Beta {
         };
         Some(skew)
     }
+    /// Returns the raw moment of order `k` of the beta distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// prod_{i = 0}^{k - 1} (α + i) / (α + β + i)
+    /// ```
+    ///
+    /// where `α` is shapeA and `β` is shapeB
+    fn moment(&self, k: u32) -> Option<f64> {
+        let mean = self.mean()?;
+        if self.shape_a.is_infinite() || self.shape_b.is_infinite() {
+            // degenerate at its mean
+            return Some(fp::powi(mean, k as i32));
+        }
+        Some(
+            (0..k)
+                .map(|i| (self.shape_a + i as f64) / (self.shape_a + self.shape_b + i as f64))
+                .product(),
+        )
+    }
 }
 
-impl Mode<Option<f64>> for Beta {
-    /// Returns the mode of the Beta distribution.
+impl Median<f64> for Beta {
+    /// Returns the median of the beta distribution
     ///
     /// # Remarks
     ///
-    /// Since the mode is technically only calculate for `α > 1, β > 1`, those
-    /// are the only values we allow. We may consider relaxing this constraint
-    /// in
-    /// the future.
+    /// Uses the closed forms for the symmetric case and for either shape
+    /// equal to one, and the inverse cdf otherwise
     ///
-    /// # Panics
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if α = β: 1 / 2
+    /// if α = 1: 1 - 2^(-1 / β)
+    /// if β = 1: 2^(-1 / α)
+    /// else: F^-1(1 / 2)
+    /// ```
     ///
-    /// If `α <= 1` or `β <= 1`
+    /// where `α` is shapeA, `β` is shapeB and `F` is the cdf
+    fn median(&self) -> f64 {
+        if self.shape_a.is_infinite() {
+            1.0
+        } else if self.shape_b.is_infinite() {
+            0.0
+        } else if self.shape_a == self.shape_b {
+            0.5
+        } else if self.shape_a == 1.0 {
+            -fp::exp_m1(-f64::consts::LN_2 / self.shape_b)
+        } else if self.shape_b == 1.0 {
+            fp::exp(-f64::consts::LN_2 / self.shape_a)
+        } else {
+            self.inverse_cdf(0.5)
+        }
+    }
+}
+
+impl Mode<Option<f64>> for Beta {
+    /// Returns the mode of the Beta distribution.
+    ///
+    /// # Remarks
+    ///
+    /// For `α ≤ 1` or `β ≤ 1` the density is monotone and the mode lies on
+    /// the boundary, at `0` if `α ≤ 1 ≤ β` and at `1` if `β ≤ 1 ≤ α`. The
+    /// mode is `None` when it is not unique: for the uniform `α = β = 1` and
+    /// for the U-shaped `α < 1, β < 1`, whose density has poles at both ends
+    /// and a minimum at the `anti_mode`.
     ///
     /// # Formula
     ///
@@ -265,14 +611,15 @@ impl Mode<Option<f64>> for Beta {
     ///
     /// where `α` is shapeA and `β` is shapeB
     fn mode(&self) -> Option<f64> {
-        // TODO: perhaps relax constraint in order to allow calculation
-        // of 'anti-mode;
-        if self.shape_a <= 1.0 || self.shape_b <= 1.0 {
+        let (a, b) = (self.shape_a, self.shape_b);
+        if a < 1.0 && b < 1.0 || a == 1.0 && b == 1.0 {
             None
-        } else if self.shape_a.is_infinite() {
+        } else if a <= 1.0 && b >= 1.0 {
+            Some(0.0)
+        } else if a >= 1.0 && b <= 1.0 || a.is_infinite() {
             Some(1.0)
         } else {
-            Some((self.shape_a - 1.0) / (self.shape_a + self.shape_b - 2.0))
+            Some((a - 1.0) / (a + b - 2.0))
         }
     }
 }
@@ -308,11 +655,11 @@ impl Continuous<f64, f64> for Beta {
         } else if ulps_eq!(self.shape_a, 1.0) && ulps_eq!(self.shape_b, 1.0) {
             1.0
         } else if self.shape_a > 80.0 || self.shape_b > 80.0 {
-            self.ln_pdf(x).exp()
+            fp::exp(self.ln_pdf(x))
         } else {
             let bb = gamma::gamma(self.shape_a + self.shape_b)
                 / (gamma::gamma(self.shape_a) * gamma::gamma(self.shape_b));
-            bb * x.powf(self.shape_a - 1.0) * (1.0 - x).powf(self.shape_b - 1.0)
+            bb * fp::powf(x, self.shape_a - 1.0) * fp::powf(1.0 - x, self.shape_b - 1.0)
         }
     }
 
@@ -354,20 +701,101 @@ impl Continuous<f64, f64> for Beta {
             } else if is_zero(x) {
                 -INF
             } else {
-                (self.shape_a - 1.0) * x.ln()
+                (self.shape_a - 1.0) * fp::ln(x)
             };
             let cc = if ulps_eq!(self.shape_b, 1.0) && ulps_eq!(x, 1.0) {
                 0.0
             } else if ulps_eq!(x, 1.0) {
                 -INF
             } else {
-                (self.shape_b - 1.0) * (1.0 - x).ln()
+                (self.shape_b - 1.0) * fp::ln(1.0 - x)
             };
             aa + bb + cc
         }
     }
 }
 
+impl KullbackLeibler for Beta {
+    /// Calculates the Kullback-Leibler divergence of `other` from the beta
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(B(α_q, β_q) / B(α_p, β_p)) + (α_p - α_q) ψ(α_p) + (β_p - β_q) ψ(β_p)
+    ///     + (α_q - α_p + β_q - β_p) ψ(α_p + β_p)
+    /// ```
+    ///
+    /// where `α` and `β` are the shape parameters, `B` is the beta function
+    /// and `ψ` the digamma function
+    fn kl_divergence(&self, other: &Beta) -> f64 {
+        let (a, b) = (self.shape_a, self.shape_b);
+        let (c, d) = (other.shape_a, other.shape_b);
+        beta::ln_beta(c, d) - beta::ln_beta(a, b)
+            + (a - c) * gamma::digamma(a)
+            + (b - d) * gamma::digamma(b)
+            + (c - a + d - b) * gamma::digamma(a + b)
+    }
+}
+
+impl Parameterized for Beta {
+    fn param_names() -> &'static [&'static str] {
+        &["shape_a", "shape_b"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.shape_a, self.shape_b]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Beta> {
+        match *params {
+            [shape_a, shape_b] => Beta::new(shape_a, shape_b),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for Beta {
+    type Sample = f64;
+
+    /// Fits the shapes `α` and `β` solving `ψ(α) - ψ(α + β) = mean(ln(x))`
+    /// and `ψ(β) - ψ(α + β) = mean(ln(1 - x))` by Newton's method from the
+    /// method of moments estimates, failing for observations of exactly `0`
+    /// or `1`, which `fit_mle_with_boundary` can discard or squeeze instead
+    fn fit_mle(data: &[f64]) -> Result<Beta> {
+        fit::check_sample(data, |x| x > 0.0 && x < 1.0)?;
+        let n = data.len() as f64;
+        let ln_x = data.iter().map(|x| fp::ln(*x)).sum::<f64>() / n;
+        let ln_1mx = data.iter().map(|x| fp::ln_1p(-x)).sum::<f64>() / n;
+        let start = Beta::fit_moments(data)?;
+        let (mut a, mut b) = (start.shape_a, start.shape_b);
+        for _ in 0..100 {
+            let psi_ab = gamma::digamma(a + b);
+            let g1 = gamma::digamma(a) - psi_ab - ln_x;
+            let g2 = gamma::digamma(b) - psi_ab - ln_1mx;
+            let t_ab = gamma::trigamma(a + b);
+            let (j11, j22) = (gamma::trigamma(a) - t_ab, gamma::trigamma(b) - t_ab);
+            let det = j11 * j22 - t_ab * t_ab;
+            let da = (j22 * g1 + t_ab * g2) / det;
+            let db = (t_ab * g1 + j11 * g2) / det;
+            if !(da.is_finite() && db.is_finite()) {
+                return Err(StatsError::ComputationFailedToConverge);
+            }
+            // the step is halved until both shapes stay positive
+            let mut step = 1.0;
+            while a - step * da <= 0.0 || b - step * db <= 0.0 {
+                step /= 2.0;
+            }
+            a -= step * da;
+            b -= step * db;
+            if (step * da).abs() <= 1e-12 * a && (step * db).abs() <= 1e-12 * b {
+                return Beta::new(a, b);
+            }
+        }
+        Err(StatsError::ComputationFailedToConverge)
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
@@ -408,6 +836,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_new_unchecked() {
+        for &(a, b) in [(1.0, 1.0), (0.5, 2.0), (INF, 1.0)].iter() {
+            assert_eq!(Beta::new_unchecked(a, b), Beta::new(a, b).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_from_mean_concentration() {
+        let n = Beta::from_mean_concentration(0.3, 10.0).unwrap();
+        assert_almost_eq!(n.shape_a(), 3.0, 1e-15);
+        assert_almost_eq!(n.shape_b(), 7.0, 1e-15);
+        assert_almost_eq!(n.mean().unwrap(), 0.3, 1e-15);
+        assert_almost_eq!(n.concentration(), 10.0, 1e-15);
+        assert!(matches!(Beta::from_mean_concentration(0.0, 1.0), Err(StatsError::ArgIntervalExcl("mu", _, _))));
+        assert!(Beta::from_mean_concentration(1.0, 1.0).is_err());
+        assert!(Beta::from_mean_concentration(f64::NAN, 1.0).is_err());
+        assert!(matches!(Beta::from_mean_concentration(0.5, 0.0), Err(StatsError::ArgMustBePositive("kappa"))));
+        assert!(Beta::from_mean_concentration(0.5, INF).is_err());
+    }
+
+    #[test]
+    fn test_from_mean_variance() {
+        for &(mu, var) in [(0.5, 0.05), (0.1, 0.001), (0.9, 0.08)].iter() {
+            let n = Beta::from_mean_variance(mu, var).unwrap();
+            assert_almost_eq!(n.mean().unwrap(), mu, 1e-15);
+            assert_almost_eq!(n.variance().unwrap(), var, 1e-15);
+        }
+        assert!(matches!(Beta::from_mean_variance(0.5, 0.25), Err(StatsError::ArgLt("var", _))));
+        assert!(matches!(Beta::from_mean_variance(0.5, 0.0), Err(StatsError::ArgMustBePositive("var"))));
+        assert!(Beta::from_mean_variance(0.5, f64::NAN).is_err());
+        assert!(Beta::from_mean_variance(1.5, 0.01).is_err());
+    }
+
     #[test]
     fn test_mean() {
         let f = |x: Beta| x.mean().unwrap();
@@ -473,17 +935,39 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn test_mode_shape_a_lte_1() {
-        let mode = |x: Beta| x.mode().unwrap();
-        get_value((1.0, 5.0), mode);
+    fn test_median() {
+        let median = |x: Beta| x.median();
+        test_case((2.0, 2.0), 0.5, median);
+        test_case((1.0, 3.0), 0.2062994740159002, median);
+        test_case((3.0, 1.0), 0.7937005259840998, median);
+        test_case_special((2.0, 3.0), 0.38572756813238954827550275, 1e-14, median);
+        test_case_special((0.5, 4.0), 0.058710801339783609541543706, 1e-14, median);
+        test_case((92.0, INF), 0.0, median);
+        test_case((INF, 2.0), 1.0, median);
     }
 
     #[test]
-    #[should_panic]
-    fn test_mode_shape_b_lte_1() {
+    fn test_mode_boundary() {
         let mode = |x: Beta| x.mode().unwrap();
-        get_value((5.0, 1.0), mode);
+        test_case((1.0, 5.0), 0.0, mode);
+        test_case((0.5, 5.0), 0.0, mode);
+        test_case((0.5, 1.0), 0.0, mode);
+        test_case((5.0, 1.0), 1.0, mode);
+        test_case((5.0, 0.5), 1.0, mode);
+        test_case((1.0, 0.5), 1.0, mode);
+        test_case((0.5, INF), 0.0, mode);
+        test_case((INF, 0.5), 1.0, mode);
+        test_none((1.0, 1.0), |x: Beta| x.mode());
+        test_none((0.5, 0.5), |x: Beta| x.mode());
+    }
+
+    #[test]
+    fn test_anti_mode() {
+        let anti_mode = |x: Beta| x.anti_mode().unwrap();
+        test_case((0.5, 0.5), 0.5, anti_mode);
+        test_case((0.2, 0.6), 0.6666666666666666, anti_mode);
+        test_none((1.0, 0.5), |x: Beta| x.anti_mode());
+        test_none((2.0, 2.0), |x: Beta| x.anti_mode());
     }
 
     #[test]
@@ -594,6 +1078,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sf() {
+        let sf = |arg: f64| move |x: Beta| x.sf(arg);
+        let test = [
+            ((1.0, 1.0), 0.0, 1.0),
+            ((1.0, 1.0), 0.5, 0.5),
+            ((1.0, 1.0), 1.0, 0.0),
+            ((9.0, 1.0), 0.5, 1.0 - 0.001953125),
+            ((1.0, INF), 0.5, 0.0),
+            ((INF, 1.0), 0.5, 1.0),
+        ];
+        for &(arg, x, expect) in test.iter() {
+            test_case(arg, expect, sf(x));
+        }
+        test_case_special((2.0, 3.0), 0.3125, 1e-15, sf(0.5));
+        test_case_special((2.0, 3.0), 3.997e-9, 1e-22, sf(0.999));
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        for &arg in &[(1.0, 1.0), (2.0, 3.0), (0.5, 0.5), (5.0, 100.0)] {
+            let n = try_create(arg);
+            for &p in &[1e-8, 0.01, 0.25, 0.5, 0.9, 0.999] {
+                let x = n.inverse_cdf(p);
+                assert!((0.0..=1.0).contains(&x));
+                assert_almost_eq!(n.cdf(x), p, 1e-13 * p.max(0.01));
+            }
+        }
+        // the median of Beta(a, a) is 1/2
+        test_case_special((2.5, 2.5), 0.5, 1e-15, |x| x.inverse_cdf(0.5));
+        test_case_special((1.0, 1.0), 0.3, 1e-15, |x| x.inverse_cdf(0.3));
+    }
+
     #[test]
     fn test_cdf_input_lt_0() {
         let cdf = |arg: f64| move |x: Beta| x.cdf(arg);
@@ -611,4 +1128,41 @@ mod tests {
         test::check_continuous_distribution(&try_create((1.2, 3.4)), 0.0, 1.0);
         test::check_continuous_distribution(&try_create((4.5, 6.7)), 0.0, 1.0);
     }
+
+    #[test]
+    fn test_moment() {
+        let n = try_create((2.0, 5.0));
+        assert_almost_eq!(n.moment(2).unwrap(), 0.10714285714285714, 1e-16);
+        assert_almost_eq!(n.moment(3).unwrap(), 0.047619047619047616, 1e-16);
+        assert_almost_eq!(n.central_moment(3).unwrap(), 0.0024295432458697765, 1e-16);
+        assert_almost_eq!(n.central_moment(4).unwrap(), 0.001874219075385256, 1e-16);
+        assert_eq!(try_create((f64::INFINITY, 1.0)).moment(3).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_sample_small_shapes() {
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        // both gamma samples of tiny shapes underflow to zero most of the
+        // time, which must not yield NaN
+        let mut rng = StdRng::seed_from_u64(1);
+        for &(a, b) in &[(0.001f64, 0.001f64), (0.01, 0.5), (0.2, 0.6), (0.5, 4.0)] {
+            let n = try_create((a, b));
+            let x: Vec<f64> = (0..50000).map(|_| n.sample(&mut rng)).collect();
+            assert!(x.iter().all(|&x| (0.0..=1.0).contains(&x)));
+            let mean = x.iter().sum::<f64>() / 50000.0;
+            let sd = n.variance().unwrap().sqrt();
+            assert!((mean - a / (a + b)).abs() < 5.0 * sd / 50000f64.sqrt());
+        }
+    }
+
+    #[test]
+    fn test_cumulant() {
+        // falls back to the conversion of the central moments
+        let n = try_create((2.0, 5.0));
+        assert_almost_eq!(n.cumulant(3).unwrap(), 0.0024295432458697765, 1e-16);
+        assert_almost_eq!(n.cumulant(4).unwrap(), -7.809246147438568e-05, 1e-16);
+    }
 }
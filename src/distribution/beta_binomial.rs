@@ -0,0 +1,275 @@
+use crate::distribution::{integer_param, Beta, Binomial, Discrete, DiscreteCDF, Parameterized};
+use crate::fp;
+use crate::function::{beta, factorial};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the
+/// [Beta-binomial](https://en.wikipedia.org/wiki/Beta-binomial_distribution)
+/// distribution, the number of successes in `n` trials whose probability
+/// of success is drawn once from the beta distribution with shapes `alpha`
+/// and `beta`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{BetaBinomial, Discrete};
+/// use statrs::statistics::Distribution;
+///
+/// let n = BetaBinomial::new(4, 1.0, 1.0).unwrap();
+/// assert_eq!(n.mean().unwrap(), 2.0);
+/// assert!((n.pmf(3) - 0.2).abs() < 1e-13);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BetaBinomial {
+    n: u64,
+    alpha: f64,
+    beta: f64,
+}
+
+impl BetaBinomial {
+    /// Constructs a new beta-binomial distribution of the successes in `n`
+    /// trials with the probability of success beta distributed with shapes
+    /// `alpha` and `beta`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `alpha` or `beta` are not positive and finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::BetaBinomial;
+    ///
+    /// let mut result = BetaBinomial::new(10, 2.0, 3.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = BetaBinomial::new(10, 0.0, 3.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(n: u64, alpha: f64, beta: f64) -> Result<BetaBinomial> {
+        if !(alpha.is_finite() && alpha > 0.0) {
+            return Err(StatsError::ArgMustBePositive("alpha"));
+        }
+        if !(beta.is_finite() && beta > 0.0) {
+            return Err(StatsError::ArgMustBePositive("beta"));
+        }
+        Ok(BetaBinomial { n, alpha, beta })
+    }
+
+    /// Returns the number of trials
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Returns the first shape of the beta distribution of the probability
+    /// of success
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Returns the second shape of the beta distribution of the probability
+    /// of success
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for BetaBinomial {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let p = ::rand::distributions::Distribution::sample(
+            &Beta::new_unchecked(self.alpha, self.beta),
+            rng,
+        );
+        ::rand::distributions::Distribution::sample(&Binomial::new_unchecked(p, self.n), rng)
+    }
+}
+
+impl DiscreteCDF<u64, f64> for BetaBinomial {
+    /// Calculates the cumulative distribution function for the
+    /// beta-binomial distribution at `x`, the sum of the pmf up to `x`
+    fn cdf(&self, x: u64) -> f64 {
+        if x >= self.n {
+            1.0
+        } else {
+            (0..=x).map(|k| self.pmf(k)).sum::<f64>().min(1.0)
+        }
+    }
+
+    /// Calculates the survival function for the beta-binomial
+    /// distribution at `x`, the sum of the pmf above `x`
+    fn sf(&self, x: u64) -> f64 {
+        if x >= self.n {
+            0.0
+        } else {
+            (x + 1..=self.n).map(|k| self.pmf(k)).sum::<f64>().min(1.0)
+        }
+    }
+}
+
+impl Min<u64> for BetaBinomial {
+    /// Returns the minimum value in the domain of the beta-binomial
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 0
+    /// ```
+    fn min(&self) -> u64 {
+        0
+    }
+}
+
+impl Max<u64> for BetaBinomial {
+    /// Returns the maximum value in the domain of the beta-binomial
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// n
+    /// ```
+    fn max(&self) -> u64 {
+        self.n
+    }
+}
+
+impl Distribution<f64> for BetaBinomial {
+    /// Returns the mean of the beta-binomial distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// n α / (α + β)
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        Some(self.n as f64 * self.alpha / (self.alpha + self.beta))
+    }
+    /// Returns the variance of the beta-binomial distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// n α β (α + β + n) / ((α + β)^2 (α + β + 1))
+    /// ```
+    fn variance(&self) -> Option<f64> {
+        let n = self.n as f64;
+        let s = self.alpha + self.beta;
+        Some(n * self.alpha * self.beta * (s + n) / (s * s * (s + 1.0)))
+    }
+}
+
+impl Discrete<u64, f64> for BetaBinomial {
+    /// Calculates the probability mass function for the beta-binomial
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (n choose x) B(x + α, n - x + β) / B(α, β)
+    /// ```
+    ///
+    /// where `B` is the beta function
+    fn pmf(&self, x: u64) -> f64 {
+        fp::exp(self.ln_pmf(x))
+    }
+
+    /// Calculates the log probability mass function for the beta-binomial
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln((n choose x) B(x + α, n - x + β) / B(α, β))
+    /// ```
+    fn ln_pmf(&self, x: u64) -> f64 {
+        if x > self.n {
+            f64::NEG_INFINITY
+        } else {
+            factorial::ln_binomial(self.n, x)
+                + beta::ln_beta(x as f64 + self.alpha, (self.n - x) as f64 + self.beta)
+                - beta::ln_beta(self.alpha, self.beta)
+        }
+    }
+}
+
+impl Parameterized for BetaBinomial {
+    fn param_names() -> &'static [&'static str] {
+        &["n", "alpha", "beta"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.n as f64, self.alpha, self.beta]
+    }
+
+    fn from_params(params: &[f64]) -> Result<BetaBinomial> {
+        match *params {
+            [n, alpha, beta] => BetaBinomial::new(integer_param(n)?, alpha, beta),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::statistics::*;
+    use crate::distribution::{BetaBinomial, Discrete, DiscreteCDF};
+    use crate::distribution::internal::*;
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_create() {
+        let n = BetaBinomial::new(10, 2.0, 3.0).unwrap();
+        assert_eq!((n.n(), n.alpha(), n.beta()), (10, 2.0, 3.0));
+        assert_eq!((n.min(), n.max()), (0, 10));
+        assert!(BetaBinomial::new(10, f64::NAN, 3.0).is_err());
+        assert!(BetaBinomial::new(10, 2.0, -1.0).is_err());
+        assert!(BetaBinomial::new(10, 2.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_pmf() {
+        // uniform probabilities of success give uniform counts
+        let n = BetaBinomial::new(4, 1.0, 1.0).unwrap();
+        for k in 0..=4 {
+            assert_almost_eq!(n.pmf(k), 0.2, 1e-13);
+        }
+        assert_eq!(n.pmf(5), 0.0);
+        assert_eq!(n.ln_pmf(5), f64::NEG_INFINITY);
+        // mpmath: binomial(10, 3) * beta(5, 10) / beta(2, 3)
+        let n = BetaBinomial::new(10, 2.0, 3.0).unwrap();
+        assert_almost_eq!(n.pmf(3), 0.14385614385614386, 1e-13);
+        assert_almost_eq!(n.ln_pmf(3), 0.14385614385614386f64.ln(), 1e-12);
+        assert_almost_eq!(n.cdf(3), 0.45454545454545453, 1e-12);
+        assert_almost_eq!(n.sf(3), 1.0 - 0.45454545454545453, 1e-12);
+        assert_eq!(n.cdf(10), 1.0);
+        assert_eq!(n.sf(10), 0.0);
+        test::check_discrete_distribution(&n, 10);
+    }
+
+    #[test]
+    fn test_moments() {
+        let n = BetaBinomial::new(10, 2.0, 3.0).unwrap();
+        let mean: f64 = (0..=10).map(|k| k as f64 * n.pmf(k)).sum();
+        let variance: f64 = (0..=10).map(|k| (k as f64 - mean).powi(2) * n.pmf(k)).sum();
+        assert_almost_eq!(n.mean().unwrap(), mean, 1e-11);
+        assert_almost_eq!(n.variance().unwrap(), variance, 1e-11);
+        assert_eq!(n.mean().unwrap(), 4.0);
+        assert_eq!(n.variance().unwrap(), 6.0);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let n = BetaBinomial::new(10, 2.0, 3.0).unwrap();
+        let x: Vec<f64> = (0..20000).map(|_| n.sample(&mut rng)).collect();
+        let fraction = x.iter().filter(|&&x| x == 3.0).count() as f64 / 20000.0;
+        assert!((fraction - n.pmf(3)).abs() < 0.01);
+    }
+}
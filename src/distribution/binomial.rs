@@ -1,8 +1,12 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{
+    fit, integer_param, CharacteristicFunction, Discrete, DiscreteCDF, Fit, Parameterized,
+};
+use crate::fp;
 use crate::function::{beta, factorial};
 use crate::is_zero;
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 use std::f64;
 
@@ -56,6 +60,19 @@ impl Binomial {
         }
     }
 
+    /// Constructs a new binomial distribution from parameters already known to
+    /// be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(p: f64, n: u64) -> Binomial {
+        debug_assert!(Binomial::new(p, n).is_ok(), "invalid binomial parameters");
+        Binomial { p, n }
+    }
+
     /// Returns the probability of success `p` of
     /// the binomial distribution.
     ///
@@ -119,6 +136,24 @@ impl DiscreteCDF<u64, f64> for Binomial {
             beta::beta_reg((self.n - k) as f64, k as f64 + 1.0, 1.0 - self.p)
         }
     }
+
+    /// Calculates the survival function for the
+    /// binomial distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// I_(p)(x + 1, n - x)
+    /// ```
+    ///
+    /// where `I_(x)(a, b)` is the regularized incomplete beta function
+    fn sf(&self, x: u64) -> f64 {
+        if x >= self.n {
+            0.0
+        } else {
+            beta::beta_reg(x as f64 + 1.0, (self.n - x) as f64, self.p)
+        }
+    }
 }
 
 impl Min<u64> for Binomial {
@@ -185,7 +220,7 @@ impl Distribution<f64> for Binomial {
         } else {
             (0..self.n + 1).fold(0.0, |acc, x| {
                 let p = self.pmf(x);
-                acc - p * p.ln()
+                acc - p * fp::ln(p)
             })
         };
         Some(entr)
@@ -200,6 +235,23 @@ impl Distribution<f64> for Binomial {
     fn skewness(&self) -> Option<f64> {
         Some((1.0 - 2.0 * self.p) / (self.n as f64 * self.p * (1.0 - self.p)).sqrt())
     }
+    /// Returns the cumulant of order `k` of the binomial distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// κ_k = n * κ_k(Bernoulli(p))
+    /// ```
+    ///
+    /// where the cumulants of the bernoulli distribution follow from its
+    /// raw moments, which all equal `p`
+    fn cumulant(&self, k: u32) -> Option<f64> {
+        if k == 0 {
+            return Some(0.0);
+        }
+        let bernoulli = moments_to_cumulants(&vec![self.p; k as usize])?;
+        Some(self.n as f64 * bernoulli[k as usize - 1])
+    }
 }
 
 impl Median<f64> for Binomial {
@@ -260,10 +312,11 @@ impl Discrete<u64, f64> for Binomial {
                 0.0
             }
         } else {
-            (factorial::ln_binomial(self.n as u64, x as u64)
-                + x as f64 * self.p.ln()
-                + (self.n - x) as f64 * (1.0 - self.p).ln())
-            .exp()
+            fp::exp(
+                factorial::ln_binomial(self.n as u64, x as u64)
+                    + x as f64 * fp::ln(self.p)
+                    + (self.n - x) as f64 * fp::ln(1.0 - self.p),
+            )
         }
     }
 
@@ -292,18 +345,116 @@ impl Discrete<u64, f64> for Binomial {
             }
         } else {
             factorial::ln_binomial(self.n as u64, x as u64)
-                + x as f64 * self.p.ln()
-                + (self.n - x) as f64 * (1.0 - self.p).ln()
+                + x as f64 * fp::ln(self.p)
+                + (self.n - x) as f64 * fp::ln(1.0 - self.p)
         }
     }
 }
 
+impl CharacteristicFunction<f64, f64> for Binomial {
+    /// Calculates the characteristic function for the binomial
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 - p + pe^(it))^n
+    /// ```
+    ///
+    /// where `p` is the success probability and `n` the number of trials
+    fn cf(&self, t: f64) -> Complex<f64> {
+        (super::poisson::exp_m1_i(t) * self.p + 1.0).powf(self.n as f64)
+    }
+}
+
+impl Parameterized for Binomial {
+    fn param_names() -> &'static [&'static str] {
+        &["p", "n"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.p, self.n as f64]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Binomial> {
+        match *params {
+            [p, n] => Binomial::new(p, integer_param(n)?),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for Binomial {
+    type Sample = u64;
+
+    /// Fits both the number of trials `n` and the probability `p`,
+    /// maximizing the profile likelihood of `n` with `p = mean(x) / n` over
+    /// the integers `n >= max(x)`
+    ///
+    /// # Remarks
+    ///
+    /// The estimate only exists if the sample variance is smaller than the
+    /// sample mean, otherwise the likelihood increases towards that of a
+    /// Poisson distribution as `n` grows. Even then it is very unstable
+    /// unless `p` is large, so when the number of trials is known, `p` is
+    /// better estimated as `mean(x) / n`.
+    fn fit_mle(data: &[u64]) -> Result<Binomial> {
+        let mean = fit::count_mean(data)?;
+        let max = *data.iter().max().unwrap();
+        let variance = data
+            .iter()
+            .map(|&k| fp::powi(k as f64 - mean, 2))
+            .sum::<f64>()
+            / data.len() as f64;
+        if max == 0 || variance >= mean {
+            return Err(StatsError::BadParams);
+        }
+        if variance == 0.0 {
+            return Binomial::new(1.0, max);
+        }
+        let profile = |n: u64| {
+            let p = mean / n as f64;
+            data.iter()
+                .map(|&k| {
+                    factorial::ln_binomial(n, k)
+                        + k as f64 * fp::ln(p)
+                        + (n - k) as f64 * fp::ln_1p(-p)
+                })
+                .sum::<f64>()
+        };
+        // the profile is unimodal, so its maximum is bracketed by doubling
+        // the distance to max(x) and then found by ternary search
+        let (mut lo, mut mid, mut hi) = (max, max, max + 1);
+        while profile(hi) >= profile(mid) {
+            if hi - max > 1 << 40 {
+                return Err(StatsError::ComputationFailedToConverge);
+            }
+            lo = mid;
+            mid = hi;
+            hi = max + 2 * (hi - max);
+        }
+        while hi - lo > 2 {
+            let m1 = lo + (hi - lo) / 3;
+            let m2 = hi - (hi - lo) / 3;
+            if profile(m1) < profile(m2) {
+                lo = m1 + 1;
+            } else {
+                hi = m2;
+            }
+        }
+        let n = (lo..=hi)
+            .max_by(|&a, &b| profile(a).partial_cmp(&profile(b)).unwrap())
+            .unwrap();
+        Binomial::new(mean / n as f64, n)
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use std::fmt::Debug;
     use crate::statistics::*;
-    use crate::distribution::{DiscreteCDF, Discrete, Binomial};
+    use crate::distribution::{CharacteristicFunction, DiscreteCDF, Discrete, Binomial};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -429,7 +580,11 @@ mod tests {
         test_case(0.0, 10, 0.0, pmf(1));
         test_case(0.0, 10, 0.0, pmf(10));
         test_case(0.3, 1, 0.69999999999999995559107901499373838305473327636719, pmf(0));
+        // the `exp(ln p)` round trip through musl lands one ulp low under `deterministic`
+        #[cfg(not(feature = "deterministic"))]
         test_case(0.3, 1, 0.2999999999999999888977697537484345957636833190918, pmf(1));
+        #[cfg(feature = "deterministic")]
+        test_almost(0.3, 1, 0.2999999999999999888977697537484345957636833190918, 1e-16, pmf(1));
         test_case(0.3, 3, 0.34299999999999993471888615204079956461021032657166, pmf(0));
         test_almost(0.3, 3, 0.44099999999999992772448109690231306411849135972008, 1e-15, pmf(1));
         test_almost(0.3, 3, 0.026999999999999997002397833512077451789759292859569, 1e-16, pmf(3));
@@ -504,6 +659,14 @@ mod tests {
         test_case(1.0, 10, 1.0, cdf(10));
     }
 
+    #[test]
+    fn test_sf() {
+        let sf = |arg: u64| move |x: Binomial| x.sf(arg);
+        test_almost(0.5, 10, 11.0 / 1024.0, 1e-15, sf(8));
+        test_case(0.5, 10, 0.0, sf(10));
+        test_almost(0.1, 40, 1.0933757002362588e-23, 1e-35, sf(30));
+    }
+
     #[test]
     fn test_cdf_upper_bound() {
         let cdf = |arg: u64| move |x: Binomial| x.cdf(arg);
@@ -515,4 +678,20 @@ mod tests {
         test::check_discrete_distribution(&try_create(0.3, 5), 5);
         test::check_discrete_distribution(&try_create(0.7, 10), 10);
     }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(0.3, 10).cf(0.5);
+        assert_almost_eq!(phi.re, 0.06798519997031749, 1e-14);
+        assert_almost_eq!(phi.im, 0.7650192450564124, 1e-14);
+    }
+
+    #[test]
+    fn test_cumulant() {
+        let n = try_create(0.3, 10);
+        assert_almost_eq!(n.cumulant(1).unwrap(), 3.0, 1e-14);
+        assert_almost_eq!(n.cumulant(2).unwrap(), 2.1, 1e-14);
+        assert_almost_eq!(n.cumulant(3).unwrap(), 0.84, 1e-14);
+        assert_almost_eq!(n.cumulant(4).unwrap(), -0.546, 1e-14);
+    }
 }
@@ -1,4 +1,5 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{dimension_param, Discrete, DiscreteCDF, KullbackLeibler, Parameterized};
+use crate::fp;
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
@@ -9,6 +10,11 @@ use std::f64;
 /// distribution, also known as the generalized Bernoulli or discrete
 /// distribution
 ///
+/// # Remarks
+///
+/// Samples are drawn in constant time from an alias table built once by
+/// the constructor.
+///
 /// # Examples
 ///
 /// ```
@@ -25,6 +31,7 @@ use std::f64;
 pub struct Categorical {
     norm_pmf: Vec<f64>,
     cdf: Vec<f64>,
+    alias: AliasTable,
 }
 
 impl Categorical {
@@ -56,16 +63,33 @@ impl Categorical {
         if !super::internal::is_valid_multinomial(prob_mass, true) {
             Err(StatsError::BadParams)
         } else {
-            // extract un-normalized cdf
-            let cdf = prob_mass_to_cdf(prob_mass);
-            // extract normalized probability mass
-            let sum = cdf[cdf.len() - 1];
-            let mut norm_pmf = vec![0.0; prob_mass.len()];
-            norm_pmf
-                .iter_mut()
-                .zip(prob_mass.iter())
-                .for_each(|(np, pm)| *np = *pm / sum);
-            Ok(Categorical { norm_pmf, cdf })
+            Ok(Categorical::new_unchecked(prob_mass))
+        }
+    }
+
+    /// Constructs a new categorical distribution from parameters already known
+    /// to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(prob_mass: &[f64]) -> Categorical {
+        debug_assert!(
+            super::internal::is_valid_multinomial(prob_mass, true),
+            "invalid categorical parameters"
+        );
+        // extract un-normalized cdf
+        let cdf = prob_mass_to_cdf(prob_mass);
+        // extract normalized probability mass
+        let sum = cdf[cdf.len() - 1];
+        let norm_pmf = prob_mass.iter().map(|pm| pm / sum).collect();
+        let alias = AliasTable::new(prob_mass);
+        Categorical {
+            norm_pmf,
+            cdf,
+            alias,
         }
     }
 
@@ -76,7 +100,7 @@ impl Categorical {
 
 impl ::rand::distributions::Distribution<f64> for Categorical {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        sample_unchecked(rng, &self.cdf)
+        self.alias.sample(rng) as f64
     }
 }
 
@@ -213,7 +237,7 @@ impl Distribution<f64> for Categorical {
             .norm_pmf
             .iter()
             .filter(|&&p| p > 0.0)
-            .map(|p| p * p.ln())
+            .map(|p| p * fp::ln(*p))
             .sum::<f64>();
         Some(entr)
     }
@@ -247,21 +271,10 @@ impl Discrete<u64, f64> for Categorical {
     /// Calculates the log probability mass function for the categorical
     /// distribution at `x`
     fn ln_pmf(&self, x: u64) -> f64 {
-        self.pmf(x).ln()
+        fp::ln(self.pmf(x))
     }
 }
 
-/// Draws a sample from the categorical distribution described by `cdf`
-/// without doing any bounds checking
-pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, cdf: &[f64]) -> f64 {
-    let draw = rng.gen::<f64>() * cdf.last().unwrap();
-    cdf.iter()
-        .enumerate()
-        .find(|(_, val)| **val >= draw)
-        .map(|(i, _)| i)
-        .unwrap() as f64
-}
-
 /// Computes the cdf from the given probability masses. Performs
 /// no parameter or bounds checking.
 pub fn prob_mass_to_cdf(prob_mass: &[f64]) -> Vec<f64> {
@@ -274,6 +287,50 @@ pub fn prob_mass_to_cdf(prob_mass: &[f64]) -> Vec<f64> {
     cdf
 }
 
+/// Walker's alias table for drawing indices with probability proportional
+/// to given masses in constant time, built with Vose's method
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AliasTable {
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    /// Builds the table for the probability masses `prob_mass`, which need
+    /// not be normalized. Performs no parameter or bounds checking.
+    pub(crate) fn new(prob_mass: &[f64]) -> AliasTable {
+        let k = prob_mass.len();
+        let sum: f64 = prob_mass.iter().sum();
+        let mut prob: Vec<f64> = prob_mass.iter().map(|p| p * k as f64 / sum).collect();
+        let mut alias: Vec<usize> = (0..k).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..k).partition(|&i| prob[i] < 1.0);
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            alias[s] = l;
+            prob[l] -= 1.0 - prob[s];
+            if prob[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // whatever is left over has a scaled mass of one up to rounding
+        for i in small.into_iter().chain(large) {
+            prob[i] = 1.0;
+        }
+        AliasTable { prob, alias }
+    }
+
+    /// Draws an index using `rng` as the source of randomness
+    pub(crate) fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let i = rng.gen_range(0..self.prob.len());
+        if rng.gen::<f64>() < self.prob[i] {
+            i
+        } else {
+            self.alias[i]
+        }
+    }
+}
+
 // Returns the index of val if placed into the sorted search array.
 // If val is greater than all elements, it therefore would return
 // the length of the array (N). If val is less than all elements, it would
@@ -314,12 +371,56 @@ fn test_binary_index() {
     assert_eq!(5, binary_index(&arr, 10.1));
 }
 
+impl KullbackLeibler for Categorical {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// categorical distribution, where categories beyond the end of either
+    /// distribution have probability zero
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ p_i ln(p_i / q_i)
+    /// ```
+    ///
+    /// where `p` and `q` are the normalized probability masses, terms with
+    /// `p_i = 0` are zero and terms with `p_i > 0 = q_i` are infinite
+    fn kl_divergence(&self, other: &Categorical) -> f64 {
+        self.norm_pmf
+            .iter()
+            .enumerate()
+            .filter(|&(_, &p)| p > 0.0)
+            .map(|(i, &p)| {
+                let q = other.norm_pmf.get(i).cloned().unwrap_or(0.0);
+                p * fp::ln(p / q)
+            })
+            .sum()
+    }
+}
+
+impl Parameterized for Categorical {
+    fn param_names() -> &'static [&'static str] {
+        &["k", "prob_mass"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        let mut params = vec![self.norm_pmf.len() as f64];
+        params.extend_from_slice(&self.norm_pmf);
+        params
+    }
+
+    fn from_params(params: &[f64]) -> Result<Categorical> {
+        let (_, prob_mass) = dimension_param(params, |k| k)?;
+        Categorical::new(prob_mass)
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use std::fmt::Debug;
     use crate::statistics::*;
     use crate::distribution::{Categorical, Discrete, DiscreteCDF};
+    use crate::distribution::categorical::AliasTable;
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -372,6 +473,12 @@ mod tests {
         bad_create_case(&[0.0, 0.0]);
     }
 
+    #[test]
+    fn test_new_unchecked() {
+        let prob_mass = [0.0, 1.0, 2.0, 1.0];
+        assert_eq!(Categorical::new_unchecked(&prob_mass), Categorical::new(&prob_mass).unwrap());
+    }
+
     #[test]
     fn test_mean() {
         let mean = |x: Categorical| x.mean().unwrap();
@@ -491,4 +598,41 @@ mod tests {
         test::check_discrete_distribution(&try_create(&[1.0, 2.0, 3.0, 4.0]), 4);
         test::check_discrete_distribution(&try_create(&[0.0, 1.0, 2.0, 3.0, 4.0]), 5);
     }
+
+    #[test]
+    fn test_alias_table() {
+        // the masses implied by the table are those it was built from
+        for masses in &[vec![1.0], vec![1.0, 2.0, 3.0, 4.0], vec![0.0, 3.0, 0.0, 1.0, 1.0], vec![1e-12, 1.0, 1e12], vec![0.1; 10]] {
+            let table = AliasTable::new(masses);
+            let k = masses.len();
+            let mut implied = vec![0.0; k];
+            for i in 0..k {
+                implied[i] += table.prob[i] / k as f64;
+                implied[table.alias[i]] += (1.0 - table.prob[i]) / k as f64;
+            }
+            let sum: f64 = masses.iter().sum();
+            for i in 0..k {
+                assert_almost_eq!(implied[i], masses[i] / sum, 1e-14);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        use rand::distributions::Distribution as RandDistribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let n = try_create(&[0.0, 3.0, 1.0, 0.0, 4.0]);
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut counts = [0.0f64; 5];
+        for _ in 0..80000 {
+            counts[n.sample(&mut rng) as usize] += 1.0;
+        }
+        assert_eq!(counts[0], 0.0);
+        assert_eq!(counts[3], 0.0);
+        assert!((counts[1] / 80000.0 - 0.375).abs() < 0.01);
+        assert!((counts[2] / 80000.0 - 0.125).abs() < 0.01);
+        assert!((counts[4] / 80000.0 - 0.5).abs() < 0.01);
+    }
 }
@@ -1,6 +1,9 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{CharacteristicFunction, Continuous, ContinuousCDF, Parameterized};
+use crate::fp;
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
+use rand::distributions::Open01;
 use rand::Rng;
 use std::f64;
 
@@ -50,6 +53,22 @@ impl Cauchy {
         }
     }
 
+    /// Constructs a new cauchy distribution from parameters already known to be
+    /// valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(location: f64, scale: f64) -> Cauchy {
+        debug_assert!(
+            Cauchy::new(location, scale).is_ok(),
+            "invalid cauchy parameters"
+        );
+        Cauchy { location, scale }
+    }
+
     /// Returns the location of the cauchy distribution
     ///
     /// # Examples
@@ -81,7 +100,7 @@ impl Cauchy {
 
 impl ::rand::distributions::Distribution<f64> for Cauchy {
     fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
-        self.location + self.scale * (f64::consts::PI * (r.gen::<f64>() - 0.5)).tan()
+        self.inverse_cdf(r.sample(Open01))
     }
 }
 
@@ -97,7 +116,34 @@ impl ContinuousCDF<f64, f64> for Cauchy {
     ///
     /// where `x_0` is the location and `γ` is the scale
     fn cdf(&self, x: f64) -> f64 {
-        (1.0 / f64::consts::PI) * ((x - self.location) / self.scale).atan() + 0.5
+        (1.0 / f64::consts::PI) * fp::atan((x - self.location) / self.scale) + 0.5
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// cauchy distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// x_0 + γ * tan(π * (p - 0.5))
+    /// ```
+    ///
+    /// where `x_0` is the location and `γ` is the scale
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == 0.0 {
+            f64::NEG_INFINITY
+        } else if p == 1.0 {
+            f64::INFINITY
+        } else {
+            self.location + self.scale * fp::tan(f64::consts::PI * (p - 0.5))
+        }
     }
 }
 
@@ -140,7 +186,7 @@ impl Distribution<f64> for Cauchy {
     ///
     /// where `γ` is the scale
     fn entropy(&self) -> Option<f64> {
-        Some((4.0 * f64::consts::PI * self.scale).ln())
+        Some(fp::ln(4.0 * f64::consts::PI * self.scale))
     }
 }
 
@@ -202,10 +248,44 @@ impl Continuous<f64, f64> for Cauchy {
     ///
     /// where `x_0` is the location and `γ` is the scale
     fn ln_pdf(&self, x: f64) -> f64 {
-        -(f64::consts::PI
-            * self.scale
-            * (1.0 + ((x - self.location) / self.scale) * ((x - self.location) / self.scale)))
-            .ln()
+        -fp::ln(
+            f64::consts::PI
+                * self.scale
+                * (1.0 + ((x - self.location) / self.scale) * ((x - self.location) / self.scale)),
+        )
+    }
+}
+
+impl CharacteristicFunction<f64, f64> for Cauchy {
+    /// Calculates the characteristic function for the cauchy
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(ix_0 t - γ|t|)
+    /// ```
+    ///
+    /// where `x_0` is the location and `γ` is the scale
+    fn cf(&self, t: f64) -> Complex<f64> {
+        Complex::from_polar(fp::exp(-self.scale * t.abs()), self.location * t)
+    }
+}
+
+impl Parameterized for Cauchy {
+    fn param_names() -> &'static [&'static str] {
+        &["location", "scale"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.location, self.scale]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Cauchy> {
+        match *params {
+            [location, scale] => Cauchy::new(location, scale),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
     }
 }
 
@@ -213,7 +293,7 @@ impl Continuous<f64, f64> for Cauchy {
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::*;
-    use crate::distribution::{ContinuousCDF, Continuous, Cauchy};
+    use crate::distribution::{CharacteristicFunction, ContinuousCDF, Continuous, Cauchy};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -410,9 +490,27 @@ mod tests {
         test_case(f64::INFINITY, 1.0, 0.0, cdf(5.0));
     }
 
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Cauchy| x.inverse_cdf(arg);
+        test_case(1.0, 2.0, 1.0, inverse_cdf(0.5));
+        test_almost(1.0, 2.0, 7.155367074350506, 1e-13, inverse_cdf(0.9));
+        test_almost(1.0, 2.0, -5.155367074350506, 1e-13, inverse_cdf(0.1));
+        test_almost(0.0, 1.0, 1.0, 1e-15, inverse_cdf(0.75));
+        test_case(0.0, 1.0, f64::NEG_INFINITY, inverse_cdf(0.0));
+        test_case(0.0, 1.0, f64::INFINITY, inverse_cdf(1.0));
+    }
+
     #[test]
     fn test_continuous() {
         test::check_continuous_distribution(&try_create(-1.2, 3.4), -1500.0, 1500.0);
         test::check_continuous_distribution(&try_create(-4.5, 6.7), -5000.0, 5000.0);
     }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(1.0, 2.0).cf(-0.5);
+        assert_almost_eq!(phi.re, 0.32284458245003306, 1e-15);
+        assert_almost_eq!(phi.im, -0.17637079922503196, 1e-15);
+    }
 }
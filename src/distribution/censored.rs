@@ -0,0 +1,464 @@
+use crate::distribution::discretize::tanh_sinh;
+use crate::distribution::{Continuous, ContinuousCDF, Observation};
+use crate::fp;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the [censored
+/// distribution](https://en.wikipedia.org/wiki/Censoring_(statistics)) of a
+/// continuous distribution, which records values below `lower` as `lower`
+/// and values above `upper` as `upper`
+///
+/// # Remarks
+///
+/// The censored distribution is a mixture of point masses at the bounds and
+/// the parent's density between them, as in the Tobit model. It therefore
+/// has a cdf and quantiles but no density. Moments are computed from the
+/// point masses and by tanh-sinh quadrature of the parent's quantile
+/// function between the bounds.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Censored, ContinuousCDF, Normal};
+/// use statrs::statistics::Distribution;
+///
+/// let n = Censored::new(Normal::new(0.0, 1.0).unwrap(), 0.0, f64::INFINITY).unwrap();
+/// assert_eq!(n.mass_lower(), 0.5);
+/// assert_eq!(n.cdf(0.0), 0.5);
+/// assert!((n.mean().unwrap() - 1.0 / (2.0 * std::f64::consts::PI).sqrt()).abs() < 1e-12);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Censored<D> {
+    parent: D,
+    lower: f64,
+    upper: f64,
+    mass_lower: f64,
+    mass_upper: f64,
+}
+
+impl<D: ContinuousCDF<f64, f64>> Censored<D> {
+    /// Constructs the distribution of `parent` censored below at `lower`
+    /// and above at `upper`, either of which may be infinite to censor on
+    /// one side only
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lower` or `upper` are `NaN` or if
+    /// `lower >= upper`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Censored, Exp};
+    ///
+    /// let exp = Exp::new(1.0).unwrap();
+    /// assert!(Censored::new(exp, f64::NEG_INFINITY, 2.0).is_ok());
+    /// assert!(Censored::new(exp, 2.0, 1.0).is_err());
+    /// ```
+    pub fn new(parent: D, lower: f64, upper: f64) -> Result<Censored<D>> {
+        if lower.is_nan() || upper.is_nan() || lower >= upper {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Censored::new_unchecked(parent, lower, upper))
+    }
+
+    /// Constructs a new censored distribution from parameters already known to
+    /// be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(parent: D, lower: f64, upper: f64) -> Censored<D> {
+        debug_assert!(lower < upper, "invalid censored parameters");
+        Censored {
+            mass_lower: parent.cdf(lower),
+            mass_upper: parent.sf(upper),
+            parent,
+            lower,
+            upper,
+        }
+    }
+
+    /// Returns the parent distribution
+    pub fn parent(&self) -> &D {
+        &self.parent
+    }
+
+    /// Returns the lower censoring bound
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Returns the upper censoring bound
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// Returns the probability mass at the lower bound, i.e. the probability
+    /// that the parent falls at or below `lower`
+    pub fn mass_lower(&self) -> f64 {
+        self.mass_lower
+    }
+
+    /// Returns the probability mass at the upper bound, i.e. the probability
+    /// that the parent falls above `upper`
+    pub fn mass_upper(&self) -> f64 {
+        self.mass_upper
+    }
+
+    /// Classifies the recorded value `x` of the censored distribution as a
+    /// left-censored observation if it equals the lower bound, as a
+    /// right-censored observation if it equals the upper bound and as an
+    /// exact observation otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Censored, Normal, Observation};
+    ///
+    /// let n = Censored::new(Normal::new(0.0, 1.0).unwrap(), -1.0, 1.0).unwrap();
+    /// assert_eq!(n.classify(-1.0), Observation::LeftCensored(-1.0));
+    /// assert_eq!(n.classify(0.5), Observation::Exact(0.5));
+    /// ```
+    pub fn classify(&self, x: f64) -> Observation {
+        if x <= self.lower {
+            Observation::LeftCensored(self.lower)
+        } else if x >= self.upper {
+            Observation::RightCensored(self.upper)
+        } else {
+            Observation::Exact(x)
+        }
+    }
+
+    /// Returns the log-likelihood of `observations` under the parent
+    /// distribution, summing the log density of exact observations and the
+    /// log probability of the event described by censored ones
+    ///
+    /// # Remarks
+    ///
+    /// Observations may have individual censoring points, as is common for
+    /// survival data; use `classify` to convert values recorded by this
+    /// censored distribution, which gives the Tobit log-likelihood.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum ln f(x) + sum ln F(l) + sum ln S(r) + sum ln(F(b) - F(a))
+    /// ```
+    ///
+    /// over exact observations `x`, left-censored observations `l`,
+    /// right-censored observations `r` and interval-censored observations
+    /// `[a, b]`, where `f`, `F` and `S` are the pdf, cdf and survival function
+    /// of the parent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Censored, Exp, Observation};
+    ///
+    /// let n = Censored::new(Exp::new(2.0).unwrap(), 0.0, 1.0).unwrap();
+    /// let obs = [Observation::Exact(0.5), Observation::RightCensored(1.0)];
+    /// // ln(2 e^-1) + ln(e^-2)
+    /// assert!((n.ln_likelihood(&obs) - (2f64.ln() - 3.0)).abs() < 1e-14);
+    /// ```
+    pub fn ln_likelihood(&self, observations: &[Observation]) -> f64
+    where
+        D: Continuous<f64, f64>,
+    {
+        observations
+            .iter()
+            .map(|obs| obs.ln_likelihood(&self.parent))
+            .sum()
+    }
+
+    /// Computes `E[f(X)]` from the point masses at the bounds and by
+    /// tanh-sinh quadrature of `f(F^-1(u))` over the probabilities between
+    /// them
+    fn expectation<F: Fn(f64) -> f64>(&self, f: F) -> f64 {
+        let len = 1.0 - self.mass_lower - self.mass_upper;
+        let mut total = 0.0;
+        if self.mass_lower > 0.0 {
+            total += self.mass_lower * f(self.lower);
+        }
+        if self.mass_upper > 0.0 {
+            total += self.mass_upper * f(self.upper);
+        }
+        if len > 0.0 {
+            total += len
+                * tanh_sinh()
+                    .map(|(t, t_c, w)| {
+                        let p = if t <= 0.5 {
+                            self.mass_lower + len * t
+                        } else {
+                            1.0 - self.mass_upper - len * t_c
+                        };
+                        let x = self.parent.inverse_cdf(p).max(self.lower).min(self.upper);
+                        w * f(x)
+                    })
+                    .sum::<f64>();
+        }
+        total
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ::rand::distributions::Distribution<f64> for Censored<D>
+where
+    D: ::rand::distributions::Distribution<f64>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.parent.sample(rng).max(self.lower).min(self.upper)
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f64, f64> for Censored<D> {
+    /// Calculates the cumulative distribution function for the censored
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if x < a: 0
+    /// if a <= x < b: F(x)
+    /// if x >= b: 1
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent and `a` and `b` are the lower and
+    /// upper bounds
+    fn cdf(&self, x: f64) -> f64 {
+        if x < self.lower {
+            0.0
+        } else if x >= self.upper {
+            1.0
+        } else {
+            self.parent.cdf(x)
+        }
+    }
+
+    /// Calculates the survival function for the censored distribution at
+    /// `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if x < a: 1
+    /// if a <= x < b: S(x)
+    /// if x >= b: 0
+    /// ```
+    ///
+    /// where `S` is the survival function of the parent and `a` and `b` are
+    /// the lower and upper bounds
+    fn sf(&self, x: f64) -> f64 {
+        if x < self.lower {
+            1.0
+        } else if x >= self.upper {
+            0.0
+        } else {
+            self.parent.sf(x)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// censored distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if p <= F(a): a
+    /// if F(a) < p <= F(b): F^-1(p)
+    /// if p > F(b): b
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent and `a` and `b` are the lower and
+    /// upper bounds
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p <= self.mass_lower {
+            self.min()
+        } else if p > 1.0 - self.mass_upper {
+            self.max()
+        } else {
+            self.parent.inverse_cdf(p).max(self.lower).min(self.upper)
+        }
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Min<f64> for Censored<D> {
+    /// Returns the minimum value in the domain of the censored
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// max(a, min)
+    /// ```
+    ///
+    /// where `a` is the lower bound and `min` the minimum of the parent
+    fn min(&self) -> f64 {
+        self.lower.max(self.parent.min())
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Max<f64> for Censored<D> {
+    /// Returns the maximum value in the domain of the censored
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// min(b, max)
+    /// ```
+    ///
+    /// where `b` is the upper bound and `max` the maximum of the parent
+    fn max(&self) -> f64 {
+        self.upper.min(self.parent.max())
+    }
+}
+
+impl<D> Distribution<f64> for Censored<D>
+where
+    D: ContinuousCDF<f64, f64> + ::rand::distributions::Distribution<f64>,
+{
+    /// Returns the mean of the censored distribution, computed numerically
+    fn mean(&self) -> Option<f64> {
+        Some(self.expectation(|x| x))
+    }
+    /// Returns the variance of the censored distribution, computed
+    /// numerically
+    fn variance(&self) -> Option<f64> {
+        self.central_moment(2)
+    }
+    /// Returns the skewness of the censored distribution, computed
+    /// numerically
+    fn skewness(&self) -> Option<f64> {
+        Some(self.central_moment(3)? / fp::powf(self.central_moment(2)?, 1.5))
+    }
+    /// Returns the `k`th raw moment of the censored distribution, computed
+    /// numerically
+    fn moment(&self, k: u32) -> Option<f64> {
+        Some(self.expectation(|x| fp::powi(x, k as i32)))
+    }
+    /// Returns the `k`th central moment of the censored distribution,
+    /// computed numerically
+    fn central_moment(&self, k: u32) -> Option<f64> {
+        let mean = self.mean()?;
+        Some(self.expectation(|x| fp::powi(x - mean, k as i32)))
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Median<f64> for Censored<D> {
+    /// Returns the median of the censored distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// min(max(F^-1(1 / 2), a), b)
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent and `a` and `b` are the lower and
+    /// upper bounds
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::{Exp, Normal};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bad_create() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        assert!(Censored::new(n, f64::NAN, 1.0).is_err());
+        assert!(Censored::new(n, 0.0, f64::NAN).is_err());
+        assert!(Censored::new(n, 1.0, 1.0).is_err());
+        assert!(Censored::new(n, 2.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_masses_and_cdf() {
+        let n = Censored::new(Normal::new(0.0, 1.0).unwrap(), -1.0, 2.0).unwrap();
+        assert_eq!((n.lower(), n.upper()), (-1.0, 2.0));
+        assert_eq!((n.min(), n.max()), (-1.0, 2.0));
+        assert_almost_eq!(n.mass_lower(), 0.15865525393145705, 1e-10);
+        assert_almost_eq!(n.mass_upper(), 0.022750131948179209, 1e-10);
+        assert_eq!(n.cdf(-1.5), 0.0);
+        assert_eq!(n.cdf(-1.0), n.mass_lower());
+        assert_almost_eq!(n.cdf(0.0), 0.5, 1e-10);
+        assert_eq!(n.cdf(2.0), 1.0);
+        assert_eq!(n.sf(-1.5), 1.0);
+        assert_eq!(n.sf(2.0), 0.0);
+        assert_almost_eq!(n.sf(1.0), 0.15865525393145705, 1e-10);
+        assert_eq!(n.inverse_cdf(0.1), -1.0);
+        assert_eq!(n.inverse_cdf(0.99), 2.0);
+        assert_almost_eq!(n.inverse_cdf(0.5), 0.0, 1e-14);
+        assert_almost_eq!(n.median(), 0.0, 1e-14);
+        // the exponential distribution has its minimum above the lower bound
+        let e = Censored::new(Exp::new(1.0).unwrap(), -1.0, 1.0).unwrap();
+        assert_eq!(e.mass_lower(), 0.0);
+        assert_eq!(e.min(), 0.0);
+        assert_eq!(e.inverse_cdf(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_moments() {
+        // E[min(X, 1)] = 1 - e^-1 and E[min(X, 1)^2] = 2 - 4e^-1 for X ~ Exp(1)
+        let e = Censored::new(Exp::new(1.0).unwrap(), f64::NEG_INFINITY, 1.0).unwrap();
+        let m = 1.0 - (-1f64).exp();
+        assert_almost_eq!(e.mean().unwrap(), m, 1e-13);
+        assert_almost_eq!(e.moment(2).unwrap(), 2.0 - 4.0 * (-1f64).exp(), 1e-13);
+        assert_almost_eq!(e.variance().unwrap(), 2.0 - 4.0 * (-1f64).exp() - m * m, 1e-13);
+        // the standard normal censored symmetrically keeps its mean and skewness
+        let n = Censored::new(Normal::new(0.0, 1.0).unwrap(), -1.0, 1.0).unwrap();
+        assert_almost_eq!(n.mean().unwrap(), 0.0, 1e-13);
+        assert_almost_eq!(n.skewness().unwrap(), 0.0, 1e-12);
+        // E[X^2] = 2 Φ(-1) + (1 - 2 Φ(-1)) - 2 φ(1)
+        let phi = 0.24197072451914337;
+        assert_almost_eq!(n.variance().unwrap(), 1.0 - 2.0 * phi, 1e-10);
+    }
+
+    #[test]
+    fn test_ln_likelihood() {
+        let n = Censored::new(Normal::new(1.0, 2.0).unwrap(), 0.0, 3.0).unwrap();
+        let parent = *n.parent();
+        let data = [-0.5, 0.0, 0.7, 2.9, 3.0, 4.2];
+        let observations: Vec<_> = data.iter().map(|&x| n.classify(x)).collect();
+        assert_eq!(observations[0], Observation::LeftCensored(0.0));
+        assert_eq!(observations[1], Observation::LeftCensored(0.0));
+        assert_eq!(observations[2], Observation::Exact(0.7));
+        assert_eq!(observations[4], Observation::RightCensored(3.0));
+        assert_eq!(observations[5], Observation::RightCensored(3.0));
+        let expected = 2.0 * parent.cdf(0.0).ln()
+            + parent.ln_pdf(0.7)
+            + parent.ln_pdf(2.9)
+            + 2.0 * parent.sf(3.0).ln();
+        assert_almost_eq!(n.ln_likelihood(&observations), expected, 1e-13);
+        let interval = [Observation::IntervalCensored(-1.0, 3.0)];
+        assert_almost_eq!(n.ln_likelihood(&interval), parent.prob_between(-1.0, 3.0).ln(), 1e-15);
+        assert_eq!(n.ln_likelihood(&[Observation::IntervalCensored(2.0, 1.0)]), f64::NEG_INFINITY);
+        assert_eq!(n.ln_likelihood(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = Censored::new(Normal::new(0.0, 1.0).unwrap(), -1.0, 2.0).unwrap();
+        let samples: Vec<f64> = (0..20000).map(|_| n.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| (-1.0..=2.0).contains(&x)));
+        let at_lower = samples.iter().filter(|&&x| x == -1.0).count() as f64 / 20000.0;
+        assert!((at_lower - n.mass_lower()).abs() < 0.01);
+        let mean = samples.iter().sum::<f64>() / 20000.0;
+        assert!((mean - n.mean().unwrap()).abs() < 0.02);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
+use crate::fp;
 use crate::function::gamma;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -52,6 +53,19 @@ impl Chi {
         }
     }
 
+    /// Constructs a new chi distribution from parameters already known to be
+    /// valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(freedom: f64) -> Chi {
+        debug_assert!(Chi::new(freedom).is_ok(), "invalid chi parameters");
+        Chi { freedom }
+    }
+
     /// Returns the degrees of freedom of
     /// the chi distribution.
     ///
@@ -72,7 +86,7 @@ impl ::rand::distributions::Distribution<f64> for Chi {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
         (0..self.freedom as i64)
             .fold(0.0, |acc, _| {
-                acc + super::normal::sample_unchecked(rng, 0.0, 1.0).powf(2.0)
+                acc + fp::powf(super::normal::sample_unchecked(rng, 0.0, 1.0), 2.0)
             })
             .sqrt()
     }
@@ -202,7 +216,7 @@ impl Distribution<f64> for Chi {
         }
         let entr = gamma::ln_gamma(self.freedom / 2.0)
             + (self.freedom
-                - (2.0f64).ln()
+                - fp::ln(2.0f64)
                 - (self.freedom - 1.0) * gamma::digamma(self.freedom / 2.0))
                 / 2.0;
         Some(entr)
@@ -227,6 +241,29 @@ impl Distribution<f64> for Chi {
     }
 }
 
+impl Median<f64> for Chi {
+    /// Returns the median of the chi distribution
+    ///
+    /// # Remarks
+    ///
+    /// Returns `INF` if `freedom` is `INF`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(1 / 2)
+    /// ```
+    ///
+    /// where `F` is the cdf
+    fn median(&self) -> f64 {
+        if self.freedom.is_infinite() {
+            f64::INFINITY
+        } else {
+            self.inverse_cdf(0.5)
+        }
+    }
+}
+
 impl Mode<Option<f64>> for Chi {
     /// Returns the mode for the chi distribution
     ///
@@ -264,11 +301,11 @@ impl Continuous<f64, f64> for Chi {
         if self.freedom == f64::INFINITY || x == f64::INFINITY || x <= 0.0 {
             0.0
         } else if self.freedom > 160.0 {
-            self.ln_pdf(x).exp()
+            fp::exp(self.ln_pdf(x))
         } else {
-            (2.0f64).powf(1.0 - self.freedom / 2.0)
-                * x.powf(self.freedom - 1.0)
-                * (-x * x / 2.0).exp()
+            fp::powf(2.0f64, 1.0 - self.freedom / 2.0)
+                * fp::powf(x, self.freedom - 1.0)
+                * fp::exp(-x * x / 2.0)
                 / gamma::gamma(self.freedom / 2.0)
         }
     }
@@ -285,13 +322,30 @@ impl Continuous<f64, f64> for Chi {
         if self.freedom == f64::INFINITY || x == f64::INFINITY || x <= 0.0 {
             f64::NEG_INFINITY
         } else {
-            (1.0 - self.freedom / 2.0) * (2.0f64).ln() + ((self.freedom - 1.0) * x.ln())
+            (1.0 - self.freedom / 2.0) * fp::ln(2.0f64) + ((self.freedom - 1.0) * fp::ln(x))
                 - x * x / 2.0
                 - gamma::ln_gamma(self.freedom / 2.0)
         }
     }
 }
 
+impl Parameterized for Chi {
+    fn param_names() -> &'static [&'static str] {
+        &["freedom"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.freedom]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Chi> {
+        match *params {
+            [freedom] => Chi::new(freedom),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
@@ -440,6 +494,15 @@ mod tests {
         test_case(f64::INFINITY, f64::INFINITY, mode);
     }
 
+    #[test]
+    fn test_median() {
+        let median = |x: Chi| x.median();
+        test_almost(0.5, 0.29554628183373720246333971, 1e-14, median);
+        test_almost(2.0, (2.0 * f64::consts::LN_2).sqrt(), 1e-14, median);
+        test_almost(3.0, 1.5381722544550523344481320, 1e-14, median);
+        test_case(f64::INFINITY, f64::INFINITY, median);
+    }
+
     #[test]
     #[should_panic]
     fn test_mode_freedom_lt_1() {
@@ -1,6 +1,10 @@
-use crate::distribution::{Continuous, ContinuousCDF, Gamma};
+use crate::distribution::{
+    CharacteristicFunction, Continuous, ContinuousCDF, Gamma, GammaSampler, Parameterized,
+    SampleBatch,
+};
 use crate::statistics::*;
-use crate::Result;
+use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 use std::f64;
 
@@ -52,6 +56,25 @@ impl ChiSquared {
         Gamma::new(freedom / 2.0, 0.5).map(|g| ChiSquared { freedom, g })
     }
 
+    /// Constructs a new chi-squared distribution from parameters already known
+    /// to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(freedom: f64) -> ChiSquared {
+        debug_assert!(
+            ChiSquared::new(freedom).is_ok(),
+            "invalid chi-squared parameters"
+        );
+        ChiSquared {
+            freedom,
+            g: Gamma::new_unchecked(freedom / 2.0, 0.5),
+        }
+    }
+
     /// Returns the degrees of freedom of the chi-squared
     /// distribution
     ///
@@ -102,6 +125,14 @@ impl ::rand::distributions::Distribution<f64> for ChiSquared {
     }
 }
 
+impl SampleBatch<f64> for ChiSquared {
+    type Sampler = GammaSampler;
+
+    fn sampler(&self) -> GammaSampler {
+        self.g.sampler()
+    }
+}
+
 impl ContinuousCDF<f64, f64> for ChiSquared {
     /// Calculates the cumulative distribution function for the
     /// chi-squared distribution at `x`
@@ -117,6 +148,13 @@ impl ContinuousCDF<f64, f64> for ChiSquared {
     fn cdf(&self, x: f64) -> f64 {
         self.g.cdf(x)
     }
+
+    /// Calculates the survival function for the
+    /// chi-squared distribution at `x` as the survival function of the
+    /// underlying gamma distribution
+    fn sf(&self, x: f64) -> f64 {
+        self.g.sf(x)
+    }
 }
 
 impl Min<f64> for ChiSquared {
@@ -199,6 +237,30 @@ impl Distribution<f64> for ChiSquared {
     fn skewness(&self) -> Option<f64> {
         self.g.skewness()
     }
+    /// Returns the raw moment of order `k` of the chi-squared distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// prod_{i = 0}^{k - 1} (n + 2i)
+    /// ```
+    ///
+    /// where `n` is the degrees of freedom
+    fn moment(&self, k: u32) -> Option<f64> {
+        self.g.moment(k)
+    }
+    /// Returns the cumulant of order `k` of the chi-squared distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// κ_k = 2^(k - 1) * (k - 1)! * n for k > 0
+    /// ```
+    ///
+    /// where `n` is the degrees of freedom
+    fn cumulant(&self, k: u32) -> Option<f64> {
+        self.g.cumulant(k)
+    }
 }
 
 impl Median<f64> for ChiSquared {
@@ -264,11 +326,44 @@ impl Continuous<f64, f64> for ChiSquared {
     }
 }
 
+impl CharacteristicFunction<f64, f64> for ChiSquared {
+    /// Calculates the characteristic function for the chi-squared
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 - 2it)^(-k / 2)
+    /// ```
+    ///
+    /// where `k` is the degrees of freedom
+    fn cf(&self, t: f64) -> Complex<f64> {
+        self.g.cf(t)
+    }
+}
+
+impl Parameterized for ChiSquared {
+    fn param_names() -> &'static [&'static str] {
+        &["freedom"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.freedom]
+    }
+
+    fn from_params(params: &[f64]) -> Result<ChiSquared> {
+        match *params {
+            [freedom] => ChiSquared::new(freedom),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
-    use crate::statistics::Median;
-    use crate::distribution::ChiSquared;
+    use crate::statistics::{Distribution, Median};
+    use crate::distribution::{CharacteristicFunction, ChiSquared};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -311,4 +406,24 @@ mod tests {
         test::check_continuous_distribution(&try_create(2.0), 0.0, 10.0);
         test::check_continuous_distribution(&try_create(5.0), 0.0, 50.0);
     }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(3.0).cf(0.4);
+        assert_almost_eq!(phi.re, 0.36576453124996566, 1e-14);
+        assert_almost_eq!(phi.im, 0.5851118060956898, 1e-14);
+    }
+
+    #[test]
+    fn test_moment() {
+        let n = try_create(3.0);
+        assert_eq!(n.moment(2).unwrap(), 15.0);
+    }
+
+    #[test]
+    fn test_cumulant() {
+        let n = try_create(3.0);
+        assert_eq!(n.cumulant(2).unwrap(), 6.0);
+        assert_eq!(n.cumulant(3).unwrap(), 24.0);
+    }
 }
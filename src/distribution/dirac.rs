@@ -1,6 +1,7 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{CharacteristicFunction, Continuous, ContinuousCDF, Parameterized};
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 
 /// Implements the [Dirac Delta](https://en.wikipedia.org/wiki/Dirac_delta_function#As_a_distribution)
@@ -43,6 +44,19 @@ impl Dirac {
             Ok(Dirac(v))
         }
     }
+
+    /// Constructs a new dirac distribution from parameters already known to be
+    /// valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(v: f64) -> Dirac {
+        debug_assert!(Dirac::new(v).is_ok(), "invalid dirac parameters");
+        Dirac(v)
+    }
 }
 
 impl ::rand::distributions::Distribution<f64> for Dirac {
@@ -138,6 +152,20 @@ impl Distribution<f64> for Dirac {
     fn skewness(&self) -> Option<f64> {
         Some(0.0)
     }
+    /// Returns the cumulant of order `k` of the dirac distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// κ_1 = v, κ_k = 0 for k != 1
+    /// ```
+    fn cumulant(&self, k: u32) -> Option<f64> {
+        if k == 1 {
+            Some(self.0)
+        } else {
+            Some(0.0)
+        }
+    }
 }
 
 impl Median<f64> for Dirac {
@@ -170,11 +198,44 @@ impl Mode<Option<f64>> for Dirac {
     }
 }
 
+impl CharacteristicFunction<f64, f64> for Dirac {
+    /// Calculates the characteristic function for the dirac
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(ivt)
+    /// ```
+    ///
+    /// where `v` is the point of the dirac distribution
+    fn cf(&self, t: f64) -> Complex<f64> {
+        Complex::from_polar(1.0, self.0 * t)
+    }
+}
+
+impl Parameterized for Dirac {
+    fn param_names() -> &'static [&'static str] {
+        &["v"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.0]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Dirac> {
+        match *params {
+            [v] => Dirac::new(v),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::*;
-    use crate::distribution::{ContinuousCDF, Continuous, Dirac};
+    use crate::distribution::{CharacteristicFunction, ContinuousCDF, Continuous, Dirac};
     use crate::consts::ACC;
 
     fn try_create(v: f64) -> Dirac {
@@ -275,4 +336,18 @@ mod tests {
         test_case(f64::INFINITY, 0.0, cdf(1.0));
         test_case(f64::INFINITY, 1.0, cdf(f64::INFINITY));
     }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(3.0).cf(0.5);
+        assert_almost_eq!(phi.re, 0.0707372016677029, 1e-15);
+        assert_almost_eq!(phi.im, 0.9974949866040544, 1e-15);
+    }
+
+    #[test]
+    fn test_cumulant() {
+        let n = try_create(3.0);
+        assert_eq!(n.cumulant(1).unwrap(), 3.0);
+        assert_eq!(n.cumulant(2).unwrap(), 0.0);
+    }
 }
@@ -1,4 +1,7 @@
-use crate::distribution::Continuous;
+use crate::distribution::{
+    dimension_param, Beta, Continuous, GammaSampler, KullbackLeibler, Parameterized, SampleBatch,
+};
+use crate::fp;
 use crate::function::gamma;
 use crate::statistics::*;
 use crate::{prec, Result, StatsError};
@@ -65,6 +68,21 @@ impl Dirichlet {
         }
     }
 
+    /// Constructs a new dirichlet distribution from parameters already known to
+    /// be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(alpha: Vec<f64>) -> Dirichlet {
+        debug_assert!(is_valid_alpha(&alpha), "invalid dirichlet parameters");
+        Dirichlet {
+            alpha: DVector::from_vec(alpha),
+        }
+    }
+
     /// Constructs a new dirichlet distribution with the given
     /// concentration parameter (alpha) repeated `n` times
     ///
@@ -107,6 +125,198 @@ impl Dirichlet {
     fn alpha_sum(&self) -> f64 {
         self.alpha.fold(0.0, |acc, x| acc + x)
     }
+
+    /// Returns the beta distribution of the `i`th component of the
+    /// dirichlet distribution
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `i` is not a valid component index
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// X_i ~ Beta(α_i, α_0 - α_i)
+    /// ```
+    ///
+    /// where `α_i` is the `i`th concentration parameter and `α_0` is the sum
+    /// of all concentration parameters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Beta, Dirichlet};
+    ///
+    /// let n = Dirichlet::new(vec![1.0, 2.0, 3.0]).unwrap();
+    /// assert_eq!(n.marginal(1).unwrap(), Beta::new(2.0, 4.0).unwrap());
+    /// assert!(n.marginal(3).is_err());
+    /// ```
+    pub fn marginal(&self, i: usize) -> Result<Beta> {
+        if i >= self.alpha.len() {
+            return Err(StatsError::ArgLt("i", self.alpha.len() as f64));
+        }
+        let a = self.alpha[i];
+        Beta::new(a, self.alpha_sum() - a)
+    }
+
+    /// Returns the dirichlet distribution of the sums of the components
+    /// within each of `groups`, using the aggregation property of the
+    /// dirichlet distribution
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `groups` does not partition the component indices,
+    /// i.e. if an index is out of range, missing or repeated, if a group is
+    /// empty, or if there are fewer than two groups
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (Σ_{i ∈ G_1} X_i, ..., Σ_{i ∈ G_m} X_i) ~ Dir(Σ_{i ∈ G_1} α_i, ..., Σ_{i ∈ G_m} α_i)
+    /// ```
+    ///
+    /// where `G_1, ..., G_m` are the groups
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Dirichlet;
+    ///
+    /// let n = Dirichlet::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+    /// let agg = n.aggregate(&[vec![0, 3], vec![1, 2]]).unwrap();
+    /// assert_eq!(agg, Dirichlet::new(vec![5.0, 5.0]).unwrap());
+    /// ```
+    pub fn aggregate(&self, groups: &[Vec<usize>]) -> Result<Dirichlet> {
+        let mut seen = vec![false; self.alpha.len()];
+        let mut alpha = Vec::with_capacity(groups.len());
+        for group in groups {
+            if group.is_empty() {
+                return Err(StatsError::BadParams);
+            }
+            let mut sum = 0.0;
+            for &i in group {
+                if i >= seen.len() || seen[i] {
+                    return Err(StatsError::BadParams);
+                }
+                seen[i] = true;
+                sum += self.alpha[i];
+            }
+            alpha.push(sum);
+        }
+        if seen.iter().any(|&s| !s) {
+            return Err(StatsError::BadParams);
+        }
+        Dirichlet::new(alpha)
+    }
+
+    /// Returns the distribution of the components indexed by `subset`,
+    /// renormalized to sum to one, conditional on the values of the remaining
+    /// components. By the neutrality of the dirichlet distribution this does
+    /// not depend on the values conditioned on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `subset` contains fewer than two indices, or an
+    /// index that is out of range or repeated
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// X_S / Σ_{i ∈ S} X_i | X_{-S} ~ Dir(α_S)
+    /// ```
+    ///
+    /// where `S` is `subset` and `α_S` are the corresponding concentration
+    /// parameters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Dirichlet;
+    ///
+    /// let n = Dirichlet::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+    /// let cond = n.condition(&[3, 1]).unwrap();
+    /// assert_eq!(cond, Dirichlet::new(vec![4.0, 2.0]).unwrap());
+    /// ```
+    pub fn condition(&self, subset: &[usize]) -> Result<Dirichlet> {
+        let mut seen = vec![false; self.alpha.len()];
+        let mut alpha = Vec::with_capacity(subset.len());
+        for &i in subset {
+            if i >= seen.len() || seen[i] {
+                return Err(StatsError::BadParams);
+            }
+            seen[i] = true;
+            alpha.push(self.alpha[i]);
+        }
+        Dirichlet::new(alpha)
+    }
+
+    /// Calculates the log probability density function for the dirichlet
+    /// distribution given the logarithms `ln_x` of the components of `x`.
+    ///
+    /// # Remarks
+    ///
+    /// For very small concentration parameters almost all of the mass lies
+    /// on points whose components underflow to `0.0` in double precision, so
+    /// those points cannot be passed to `ln_pdf`. Working with the
+    /// logarithms of the components avoids the underflow.
+    ///
+    /// # Panics
+    ///
+    /// If any element in `ln_x` is not in `(-INF, 0]`, the exponentiated
+    /// elements do not sum to `1` with a tolerance of `1e-4`, or if `ln_x`
+    /// is not the same length as the vector of concentration parameters
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ((α_i - 1) * ln(x_i)) - ln(B(α))
+    /// ```
+    ///
+    /// where `B(α)` is the multivariate beta function of the concentration
+    /// parameters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Dirichlet;
+    /// use nalgebra::DVector;
+    ///
+    /// let n = Dirichlet::new(vec![1e-3, 1e-3]).unwrap();
+    /// let ln_x = DVector::from_vec(vec![-1000.0, 0.0]);
+    /// assert!(n.ln_pdf_from_ln(&ln_x).is_finite());
+    /// ```
+    pub fn ln_pdf_from_ln(&self, ln_x: &DVector<f64>) -> f64 {
+        if self.alpha.len() != ln_x.len() {
+            panic!("Arguments must have correct dimensions.");
+        }
+        if ln_x.iter().any(|&x| !x.is_finite() || x > 0.0) {
+            panic!("Arguments must be in (-INF, 0]");
+        }
+        let max = ln_x.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let ln_sum = max + fp::ln(ln_x.iter().map(|&x| fp::exp(x - max)).sum::<f64>());
+        if !prec::almost_eq(ln_sum, 0.0, 1e-4) {
+            panic!("Arguments must sum to 1");
+        }
+        let term = ln_x
+            .iter()
+            .zip(self.alpha.iter())
+            .fold(0.0, |acc, (&x, &a)| acc + (a - 1.0) * x);
+        term - self.ln_multivariate_beta()
+    }
+
+    /// Returns `ln(B(α))`, evaluating the log-gamma terms of concentration
+    /// parameters below one via `ln Γ(a) = ln Γ(a + 1) - ln(a)` so that
+    /// they keep their precision as `a` approaches zero
+    fn ln_multivariate_beta(&self) -> f64 {
+        let ln_gamma = |a: f64| {
+            if a < 1.0 {
+                gamma::ln_gamma(a + 1.0) - fp::ln(a)
+            } else {
+                gamma::ln_gamma(a)
+            }
+        };
+        self.alpha.iter().fold(0.0, |acc, &a| acc + ln_gamma(a)) - ln_gamma(self.alpha_sum())
+    }
     /// Returns the entropy of the dirichlet distribution
     ///
     /// # Formula
@@ -138,12 +348,37 @@ impl Dirichlet {
 
 impl ::rand::distributions::Distribution<DVector<f64>> for Dirichlet {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DVector<f64> {
+        ::rand::distributions::Distribution::sample(&self.sampler(), rng)
+    }
+}
+
+/// A sampler for the dirichlet distribution which sets up the samplers of
+/// its underlying gamma distributions once, for drawing many samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirichletSampler {
+    gammas: Vec<GammaSampler>,
+}
+
+impl ::rand::distributions::Distribution<DVector<f64>> for DirichletSampler {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DVector<f64> {
+        if self.gammas.iter().any(|g| g.boosted()) {
+            // gamma samples of small shapes can underflow to zero, so they
+            // are normalized on the log scale
+            let ln_samples: Vec<f64> = self.gammas.iter().map(|g| g.sample_ln(rng)).collect();
+            let max = ln_samples.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let mut samples: Vec<f64> = ln_samples.iter().map(|&x| fp::exp(x - max)).collect();
+            let sum: f64 = samples.iter().sum();
+            for x in samples.iter_mut() {
+                *x /= sum;
+            }
+            return DVector::from_vec(samples);
+        }
         let mut sum = 0.0;
         let mut samples: Vec<_> = self
-            .alpha
+            .gammas
             .iter()
-            .map(|&a| {
-                let sample = super::gamma::sample_unchecked(rng, a, 1.0);
+            .map(|g| {
+                let sample = ::rand::distributions::Distribution::sample(g, rng);
                 sum += sample;
                 sample
             })
@@ -153,6 +388,20 @@ impl ::rand::distributions::Distribution<DVector<f64>> for Dirichlet {
     }
 }
 
+impl SampleBatch<DVector<f64>> for Dirichlet {
+    type Sampler = DirichletSampler;
+
+    fn sampler(&self) -> DirichletSampler {
+        DirichletSampler {
+            gammas: self
+                .alpha
+                .iter()
+                .map(|&a| GammaSampler::new(a, 1.0))
+                .collect(),
+        }
+    }
+}
+
 impl MeanN<DVector<f64>> for Dirichlet {
     /// Returns the means of the dirichlet distribution
     ///
@@ -231,7 +480,7 @@ impl<'a> Continuous<&'a DVector<f64>, f64> for Dirichlet {
     /// `Π` is the product from `1` to `K`, `Σ` is the sum from `1` to `K`,
     /// and `K` is the number of concentration parameters
     fn pdf(&self, x: &DVector<f64>) -> f64 {
-        self.ln_pdf(x).exp()
+        fp::exp(self.ln_pdf(x))
     }
 
     /// Calculates the log probabiliy density function for the dirichlet
@@ -273,22 +522,17 @@ impl<'a> Continuous<&'a DVector<f64>, f64> for Dirichlet {
         if x.iter().any(|&x| x <= 0.0 || x >= 1.0) {
             panic!("Arguments must be in (0, 1)");
         }
-        let (term, sum_xi, sum_alpha) = x
+        let (term, sum_xi) = x
             .iter()
-            .enumerate()
-            .map(|pair| (pair.1, self.alpha[pair.0]))
-            .fold((0.0, 0.0, 0.0), |acc, pair| {
-                (
-                    acc.0 + (pair.1 - 1.0) * pair.0.ln() - gamma::ln_gamma(pair.1),
-                    acc.1 + pair.0,
-                    acc.2 + pair.1,
-                )
+            .zip(self.alpha.iter())
+            .fold((0.0, 0.0), |acc, (&x, &a)| {
+                (acc.0 + (a - 1.0) * fp::ln(x), acc.1 + x)
             });
 
         if !prec::almost_eq(sum_xi, 1.0, 1e-4) {
             panic!();
         } else {
-            term + gamma::ln_gamma(sum_alpha)
+            term - self.ln_multivariate_beta()
         }
     }
 }
@@ -299,6 +543,57 @@ fn is_valid_alpha(a: &[f64]) -> bool {
     a.len() >= 2 && super::internal::is_valid_multinomial(a, false)
 }
 
+impl KullbackLeibler for Dirichlet {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// dirichlet distribution
+    ///
+    /// # Panics
+    ///
+    /// If the distributions have a different number of components
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(Γ(α_0)) - Σ ln(Γ(α_i)) - ln(Γ(β_0)) + Σ ln(Γ(β_i)) + Σ (α_i - β_i) (ψ(α_i) - ψ(α_0))
+    /// ```
+    ///
+    /// where `α` and `β` are the concentration parameters of `self` and
+    /// `other`, `α_0` and `β_0` their sums, `Γ` is the gamma function and
+    /// `ψ` the digamma function
+    fn kl_divergence(&self, other: &Dirichlet) -> f64 {
+        if self.alpha.len() != other.alpha.len() {
+            panic!("dirichlet distributions must have the same number of components");
+        }
+        let a0 = self.alpha_sum();
+        let digamma_a0 = gamma::digamma(a0);
+        self.alpha.iter().zip(other.alpha.iter()).fold(
+            gamma::ln_gamma(a0) - gamma::ln_gamma(other.alpha_sum()),
+            |acc, (&a, &b)| {
+                acc - gamma::ln_gamma(a)
+                    + gamma::ln_gamma(b)
+                    + (a - b) * (gamma::digamma(a) - digamma_a0)
+            },
+        )
+    }
+}
+
+impl Parameterized for Dirichlet {
+    fn param_names() -> &'static [&'static str] {
+        &["k", "alpha"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        let mut params = vec![self.alpha.len() as f64];
+        params.extend(self.alpha.iter());
+        params
+    }
+
+    fn from_params(params: &[f64]) -> Result<Dirichlet> {
+        let (_, alpha) = dimension_param(params, |k| k)?;
+        Dirichlet::new(alpha.to_vec())
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
@@ -411,6 +706,71 @@ mod tests {
         assert_almost_eq!(n.ln_pdf(&dvec![0.1,0.2,0.3,0.4]), 0.8314656481199253f64.ln(), 1e-14);
     }
 
+    #[test]
+    fn test_ln_pdf_from_ln() {
+        let n = try_create(&[0.1, 0.3, 0.5, 0.8]);
+        let x = dvec![0.01, 0.03, 0.5, 0.46];
+        assert_almost_eq!(n.ln_pdf_from_ln(&x.map(|x: f64| x.ln())), n.ln_pdf(&x), 1e-12);
+
+        // components of order 1e-400 are not representable but their logs are
+        let n = try_create(&[1e-10, 1e-10, 1e-10]);
+        let ln_x = dvec![-921.0, -1000.0, 0.0];
+        // ln B(α) = 3 ln Γ(1e-10) - ln Γ(3e-10) ≈ ln(3e-10) - 3 ln(1e-10)
+        let ln_b = (3e-10f64).ln() - 3.0 * (1e-10f64).ln();
+        let expected = (1e-10 - 1.0) * (-921.0 - 1000.0) - ln_b;
+        assert_almost_eq!(n.ln_pdf_from_ln(&ln_x), expected, 1e-7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ln_pdf_from_ln_bad_input_sum() {
+        let n = try_create(&[0.1, 0.3, 0.5, 0.8]);
+        n.ln_pdf_from_ln(&dvec![-1.0, -1.0, -1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_small_alpha_ln_pdf() {
+        let n = try_create(&[1e-8, 1e-8]);
+        // ln B(α) = 2 ln Γ(1e-8) - ln Γ(2e-8) ≈ ln(2e-8) - 2 ln(1e-8)
+        let ln_b = (2e-8f64).ln() - 2.0 * (1e-8f64).ln();
+        let expected = (1e-8 - 1.0) * (0.25f64.ln() + 0.75f64.ln()) - ln_b;
+        assert_almost_eq!(n.ln_pdf(&dvec![0.25, 0.75]), expected, 1e-7);
+    }
+
+    #[test]
+    fn test_marginal() {
+        let n = try_create(&[0.1, 0.3, 0.5, 0.8]);
+        let b = n.marginal(2).unwrap();
+        assert_eq!(b.shape_a(), 0.5);
+        assert_almost_eq!(b.shape_b(), 1.2, 1e-15);
+        assert_almost_eq!(b.mean().unwrap(), n.mean().unwrap()[2], 1e-15);
+        assert_almost_eq!(b.variance().unwrap(), n.variance().unwrap()[(2, 2)], 1e-15);
+        assert!(n.marginal(4).is_err());
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let n = try_create(&[0.1, 0.3, 0.5, 0.8]);
+        let agg = n.aggregate(&[vec![2], vec![0, 3, 1]]).unwrap();
+        assert_eq!(agg.alpha()[0], 0.5);
+        assert_almost_eq!(agg.alpha()[1], 1.2, 1e-15);
+        assert!(n.aggregate(&[vec![0, 1, 2, 3]]).is_err());
+        assert!(n.aggregate(&[vec![0, 1], vec![2]]).is_err());
+        assert!(n.aggregate(&[vec![0, 1], vec![1, 2, 3]]).is_err());
+        assert!(n.aggregate(&[vec![0, 1], vec![2, 3, 4]]).is_err());
+        assert!(n.aggregate(&[vec![0, 1], vec![2, 3], vec![]]).is_err());
+    }
+
+    #[test]
+    fn test_condition() {
+        let n = try_create(&[0.1, 0.3, 0.5, 0.8]);
+        let cond = n.condition(&[3, 0]).unwrap();
+        assert_eq!(cond.alpha(), &dvec![0.8, 0.1]);
+        assert!(n.condition(&[1]).is_err());
+        assert!(n.condition(&[1, 1]).is_err());
+        assert!(n.condition(&[1, 4]).is_err());
+    }
+
     #[test]
     #[should_panic]
     fn test_pdf_bad_input_length() {
@@ -452,4 +812,48 @@ mod tests {
         let n = try_create(&[0.1, 0.3, 0.5, 0.8]);
         n.ln_pdf(&dvec![0.5, 0.25, 0.8, 0.9]);
     }
+
+    #[test]
+    fn test_sample_small_alpha() {
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let alpha = [0.001, 0.05, 0.5, 2.0];
+        let n = Dirichlet::new(alpha.to_vec()).unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut sum = DVector::zeros(4);
+        for _ in 0..20000 {
+            let x = n.sample(&mut rng);
+            assert!(x.iter().all(|&x| (0.0..=1.0).contains(&x)));
+            assert!((x.sum() - 1.0).abs() < 1e-12);
+            sum += x;
+        }
+        let total: f64 = alpha.iter().sum();
+        for (i, &a) in alpha.iter().enumerate() {
+            let mean = a / total;
+            let sd = (mean * (1.0 - mean) / (total + 1.0)).sqrt();
+            assert!((sum[i] / 20000.0 - mean).abs() < 5.0 * sd / 20000f64.sqrt());
+        }
+    }
+
+    #[test]
+    fn test_kl_divergence() {
+        use crate::distribution::{Beta, KullbackLeibler};
+        // with two components the divergence is that of the beta marginals
+        let p = try_create(&[2.0, 3.0]);
+        let q = try_create(&[0.7, 1.4]);
+        let expected = Beta::new(2.0, 3.0).unwrap().kl_divergence(&Beta::new(0.7, 1.4).unwrap());
+        assert_almost_eq!(p.kl_divergence(&q), expected, 1e-14);
+        let r = try_create(&[0.1, 0.3, 0.5, 0.8]);
+        assert_eq!(r.kl_divergence(&r), 0.0);
+        assert!(r.kl_divergence(&try_create(&[1.0, 1.0, 1.0, 1.0])) > 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_kl_divergence_bad_dim() {
+        use crate::distribution::KullbackLeibler;
+        try_create(&[1.0, 2.0]).kl_divergence(&try_create(&[1.0, 2.0, 3.0]));
+    }
 }
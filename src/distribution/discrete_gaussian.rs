@@ -0,0 +1,412 @@
+use crate::distribution::discrete_laplace;
+use crate::distribution::{integer_param, Discrete, DiscreteCDF, Parameterized};
+use crate::fp;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+use std::f64::consts::PI;
+
+/// Implements the [Discrete
+/// Gaussian](https://en.wikipedia.org/wiki/Discrete_Gaussian_distribution)
+/// distribution on the integers, whose probability mass at `x` is
+/// proportional to `exp(-(x - μ)^2 / (2 * σ^2))`
+///
+/// # Remarks
+///
+/// Sampling uses the rejection sampler of Canonne, Kamath & Steinke (2020)
+/// with a discrete Laplace proposal. Like the sampler of `DiscreteLaplace`
+/// it only draws uniform variables and never evaluates `exp`, so it is exact
+/// up to the resolution of the uniform draws. The variance is slightly
+/// smaller than `σ^2`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Discrete, DiscreteGaussian};
+/// use statrs::statistics::Distribution;
+///
+/// let n = DiscreteGaussian::new(0, 2.0).unwrap();
+/// assert_eq!(n.mean().unwrap(), 0.0);
+/// assert_eq!(n.pmf(1), n.pmf(-1));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DiscreteGaussian {
+    mu: i64,
+    sigma: f64,
+    ln_norm: f64,
+    variance: f64,
+}
+
+impl DiscreteGaussian {
+    /// Constructs a new discrete Gaussian distribution centred at `mu` with
+    /// scale `sigma`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `sigma` is `NaN`, infinite or not positive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::DiscreteGaussian;
+    ///
+    /// let mut result = DiscreteGaussian::new(0, 1.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = DiscreteGaussian::new(0, 0.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(mu: i64, sigma: f64) -> Result<DiscreteGaussian> {
+        if !sigma.is_finite() || sigma <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("sigma"));
+        }
+        Ok(DiscreteGaussian::new_unchecked(mu, sigma))
+    }
+
+    /// Constructs a new discrete gaussian distribution from parameters already
+    /// known to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(mu: i64, sigma: f64) -> DiscreteGaussian {
+        debug_assert!(
+            sigma.is_finite() && sigma > 0.0,
+            "invalid discrete gaussian parameters"
+        );
+        let (norm, second_moment) = theta_sums(sigma);
+        DiscreteGaussian {
+            mu,
+            sigma,
+            ln_norm: fp::ln(norm),
+            variance: second_moment / norm,
+        }
+    }
+
+    /// Returns the location `μ` of the discrete Gaussian distribution
+    pub fn mu(&self) -> i64 {
+        self.mu
+    }
+
+    /// Returns the scale `σ` of the discrete Gaussian distribution
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Returns `P(X - μ <= -k)` for `k >= 1`, summing the tail outwards
+    fn tail(&self, k: f64) -> f64 {
+        let mut sum = 0.0;
+        let mut j = k;
+        loop {
+            let term = fp::exp(self.ln_unnormalized(j));
+            sum += term;
+            if term <= sum * f64::EPSILON * 1e-2 || term == 0.0 {
+                break;
+            }
+            j += 1.0;
+        }
+        fp::exp(fp::ln(sum) - self.ln_norm)
+    }
+
+    fn ln_unnormalized(&self, d: f64) -> f64 {
+        -d * d / (2.0 * self.sigma * self.sigma)
+    }
+}
+
+/// Returns `(Σ exp(-k^2 / (2σ^2)), Σ k^2 exp(-k^2 / (2σ^2)))` over all
+/// integers `k`, summing directly for small `σ` and using the Poisson
+/// summation (Jacobi theta) identities otherwise
+fn theta_sums(sigma: f64) -> (f64, f64) {
+    let s2 = sigma * sigma;
+    if sigma < 4.0 {
+        let limit = (38.0 * sigma).ceil() as i64 + 1;
+        let mut norm = 1.0;
+        let mut second = 0.0;
+        for k in 1..=limit {
+            let k = k as f64;
+            let term = fp::exp(-k * k / (2.0 * s2));
+            norm += 2.0 * term;
+            second += 2.0 * k * k * term;
+        }
+        (norm, second)
+    } else {
+        let scale = (2.0 * PI).sqrt() * sigma;
+        let mut norm = 1.0;
+        let mut second = s2;
+        for j in 1..=3 {
+            let j2 = (j * j) as f64;
+            let term = fp::exp(-2.0 * PI * PI * s2 * j2);
+            norm += 2.0 * term;
+            second += 2.0 * (s2 - 4.0 * PI * PI * s2 * s2 * j2) * term;
+        }
+        (scale * norm, scale * second)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for DiscreteGaussian {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let t = self.sigma.floor() + 1.0;
+        let s2 = self.sigma * self.sigma;
+        loop {
+            let y = discrete_laplace::sample_unchecked(rng, t);
+            let d = y.abs() - s2 / t;
+            if discrete_laplace::sample_bernoulli_exp(rng, d * d / (2.0 * s2)) {
+                return self.mu as f64 + y;
+            }
+        }
+    }
+}
+
+impl DiscreteCDF<i64, f64> for DiscreteGaussian {
+    /// Calculates the cumulative distribution function for the
+    /// discrete Gaussian distribution at `x`
+    ///
+    /// # Remarks
+    ///
+    /// The cdf is evaluated by summing the mass of the smaller tail, so its
+    /// cost grows linearly with `σ`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ_{k <= x} exp(-(k - μ)^2 / (2σ^2)) / Σ_k exp(-(k - μ)^2 / (2σ^2))
+    /// ```
+    fn cdf(&self, x: i64) -> f64 {
+        let d = x as f64 - self.mu as f64;
+        if d < 0.0 {
+            self.tail(-d)
+        } else {
+            1.0 - self.tail(d + 1.0)
+        }
+    }
+
+    /// Calculates the survival function for the discrete Gaussian
+    /// distribution at `x`, summing the mass of the smaller tail like `cdf`
+    fn sf(&self, x: i64) -> f64 {
+        let d = x as f64 - self.mu as f64;
+        if d < 0.0 {
+            1.0 - self.tail(-d)
+        } else {
+            self.tail(d + 1.0)
+        }
+    }
+}
+
+impl Min<i64> for DiscreteGaussian {
+    /// Returns the minimum value in the domain of the discrete Gaussian
+    /// distribution representable by a 64-bit integer
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// i64::MIN
+    /// ```
+    fn min(&self) -> i64 {
+        i64::MIN
+    }
+}
+
+impl Max<i64> for DiscreteGaussian {
+    /// Returns the maximum value in the domain of the discrete Gaussian
+    /// distribution representable by a 64-bit integer
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// i64::MAX
+    /// ```
+    fn max(&self) -> i64 {
+        i64::MAX
+    }
+}
+
+impl Distribution<f64> for DiscreteGaussian {
+    /// Returns the mean of the discrete Gaussian distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        Some(self.mu as f64)
+    }
+    /// Returns the variance of the discrete Gaussian distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ_k k^2 exp(-k^2 / (2σ^2)) / Σ_k exp(-k^2 / (2σ^2))
+    /// ```
+    fn variance(&self) -> Option<f64> {
+        Some(self.variance)
+    }
+    /// Returns the entropy of the discrete Gaussian distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(Z) + Var(X) / (2σ^2)
+    /// ```
+    ///
+    /// where `Z = Σ_k exp(-k^2 / (2σ^2))`
+    fn entropy(&self) -> Option<f64> {
+        Some(self.ln_norm + self.variance / (2.0 * self.sigma * self.sigma))
+    }
+    /// Returns the skewness of the discrete Gaussian distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 0
+    /// ```
+    fn skewness(&self) -> Option<f64> {
+        Some(0.0)
+    }
+}
+
+impl Median<f64> for DiscreteGaussian {
+    /// Returns the median of the discrete Gaussian distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ
+    /// ```
+    fn median(&self) -> f64 {
+        self.mu as f64
+    }
+}
+
+impl Mode<Option<i64>> for DiscreteGaussian {
+    /// Returns the mode of the discrete Gaussian distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ
+    /// ```
+    fn mode(&self) -> Option<i64> {
+        Some(self.mu)
+    }
+}
+
+impl Discrete<i64, f64> for DiscreteGaussian {
+    /// Calculates the probability mass function for the discrete Gaussian
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// exp(-(x - μ)^2 / (2σ^2)) / Σ_k exp(-(k - μ)^2 / (2σ^2))
+    /// ```
+    fn pmf(&self, x: i64) -> f64 {
+        fp::exp(self.ln_pmf(x))
+    }
+
+    /// Calculates the log probability mass function for the discrete
+    /// Gaussian distribution at `x`
+    fn ln_pmf(&self, x: i64) -> f64 {
+        self.ln_unnormalized(x as f64 - self.mu as f64) - self.ln_norm
+    }
+}
+
+impl Parameterized for DiscreteGaussian {
+    fn param_names() -> &'static [&'static str] {
+        &["mu", "sigma"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.mu as f64, self.sigma]
+    }
+
+    fn from_params(params: &[f64]) -> Result<DiscreteGaussian> {
+        match *params {
+            [mu, sigma] => DiscreteGaussian::new(integer_param(mu)?, sigma),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::statistics::*;
+    use crate::distribution::{DiscreteCDF, Discrete, DiscreteGaussian};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bad_create() {
+        assert!(DiscreteGaussian::new(0, f64::NAN).is_err());
+        assert!(DiscreteGaussian::new(0, -1.0).is_err());
+        assert!(DiscreteGaussian::new(0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_pmf_sums_to_one() {
+        for &sigma in &[0.1, 0.7, 3.9, 4.0, 12.5] {
+            let n = DiscreteGaussian::new(3, sigma).unwrap();
+            let total: f64 = (-500..=500).map(|x| n.pmf(x)).sum();
+            assert_almost_eq!(total, 1.0, 1e-13);
+            let var: f64 = (-500..=500).map(|x| ((x - 3) * (x - 3)) as f64 * n.pmf(x)).sum();
+            assert_almost_eq!(n.variance().unwrap(), var, 1e-11 * sigma * sigma);
+        }
+    }
+
+    #[test]
+    fn test_pmf() {
+        // a single term dominates for small sigma
+        let n = DiscreteGaussian::new(0, 0.1).unwrap();
+        assert_almost_eq!(n.pmf(0), 1.0, 1e-20);
+        let n = DiscreteGaussian::new(-2, 1.0).unwrap();
+        let z: f64 = (-40..=40).map(|k: i32| (-(k * k) as f64 / 2.0).exp()).sum();
+        assert_almost_eq!(n.pmf(0), (-2f64).exp() / z, 1e-15);
+        assert_almost_eq!(n.ln_pmf(-2), -z.ln(), 1e-15);
+    }
+
+    #[test]
+    fn test_variance_close_to_sigma_squared() {
+        // the deviation from sigma^2 is exponentially small for large sigma
+        let n = DiscreteGaussian::new(0, 10.0).unwrap();
+        assert_almost_eq!(n.variance().unwrap(), 100.0, 1e-9);
+        assert!(DiscreteGaussian::new(0, 0.5).unwrap().variance().unwrap() < 0.25);
+    }
+
+    #[test]
+    fn test_cdf() {
+        let n = DiscreteGaussian::new(5, 2.5).unwrap();
+        let mut acc = 0.0;
+        for x in -30..=40 {
+            acc += n.pmf(x);
+            assert_almost_eq!(n.cdf(x), acc, 1e-14);
+            assert_almost_eq!(n.sf(x), 1.0 - acc, 1e-14);
+        }
+        assert_almost_eq!(n.cdf(4) + n.pmf(5) / 2.0, 0.5, 1e-15);
+    }
+
+    #[test]
+    fn test_entropy() {
+        let n = DiscreteGaussian::new(0, 1.5).unwrap();
+        let h: f64 = (-60..=60).map(|x| -n.pmf(x) * n.ln_pmf(x)).sum();
+        assert_almost_eq!(n.entropy().unwrap(), h, 1e-14);
+    }
+
+    #[test]
+    fn test_sample() {
+        let n = DiscreteGaussian::new(2, 3.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(17);
+        let samples: Vec<f64> = (0..100_000).map(|_| n.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|x| x.fract() == 0.0));
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let var = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / samples.len() as f64;
+        assert_almost_eq!(mean, 2.0, 0.03);
+        assert_almost_eq!(var, n.variance().unwrap(), 0.1);
+        let freq = samples.iter().filter(|&&x| x == 2.0).count() as f64 / samples.len() as f64;
+        assert_almost_eq!(freq, n.pmf(2), 0.005);
+    }
+}
@@ -0,0 +1,445 @@
+use crate::distribution::{
+    integer_param, CharacteristicFunction, Discrete, DiscreteCDF, Parameterized,
+};
+use crate::fp;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use num_complex::Complex;
+use rand::Rng;
+use std::f64;
+
+/// Implements the discrete Laplace (two-sided geometric) distribution on the
+/// integers, whose probability mass at `x` is proportional to
+/// `exp(-|x - μ| / b)`
+///
+/// # Remarks
+///
+/// Sampling is exact in the sense of Canonne, Kamath & Steinke (2020): it
+/// only draws uniform variables and never evaluates `exp`, so the output
+/// distribution is not perturbed by rounding errors in the probabilities.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Discrete, DiscreteLaplace};
+/// use statrs::statistics::Distribution;
+///
+/// let n = DiscreteLaplace::new(0, 2.0).unwrap();
+/// assert_eq!(n.mean().unwrap(), 0.0);
+/// assert_eq!(n.pmf(3), n.pmf(-3));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DiscreteLaplace {
+    mu: i64,
+    b: f64,
+    q: f64,
+}
+
+impl DiscreteLaplace {
+    /// Constructs a new discrete Laplace distribution centred at `mu` with
+    /// scale `b`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `b` is `NaN`, infinite or not positive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::DiscreteLaplace;
+    ///
+    /// let mut result = DiscreteLaplace::new(0, 1.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = DiscreteLaplace::new(0, 0.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(mu: i64, b: f64) -> Result<DiscreteLaplace> {
+        if !b.is_finite() || b <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("b"));
+        }
+        Ok(DiscreteLaplace::new_unchecked(mu, b))
+    }
+
+    /// Constructs a new discrete laplace distribution from parameters already
+    /// known to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(mu: i64, b: f64) -> DiscreteLaplace {
+        debug_assert!(
+            b.is_finite() && b > 0.0,
+            "invalid discrete laplace parameters"
+        );
+        DiscreteLaplace {
+            mu,
+            b,
+            q: fp::exp(-1.0 / b),
+        }
+    }
+
+    /// Returns the location `μ` of the discrete Laplace distribution
+    pub fn mu(&self) -> i64 {
+        self.mu
+    }
+
+    /// Returns the scale `b` of the discrete Laplace distribution
+    pub fn b(&self) -> f64 {
+        self.b
+    }
+
+    /// Returns `ln((1 - q) / (1 + q))`, the log of the mass at `μ`
+    fn ln_norm(&self) -> f64 {
+        fp::ln(-fp::exp_m1(-1.0 / self.b)) - fp::ln_1p(self.q)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for DiscreteLaplace {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.mu as f64 + sample_unchecked(rng, self.b)
+    }
+}
+
+/// Returns a draw of the discrete Laplace distribution centred at zero with
+/// scale `b`, as a signed geometric variable where the negative zero is
+/// rejected
+pub(crate) fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, b: f64) -> f64 {
+    loop {
+        let y = sample_geometric(rng, b);
+        if rng.gen::<bool>() {
+            return y;
+        } else if y != 0.0 {
+            return -y;
+        }
+    }
+}
+
+/// Returns a draw `Y` with `P(Y >= k) = exp(-k / b)`, written as
+/// `Y = U + t * V` for `t = ceil(b)` where `U` is a truncated geometric
+/// variable on `{0, ..., t - 1}` and `V` is geometric with success
+/// probability `exp(-t / b)`
+fn sample_geometric<R: Rng + ?Sized>(rng: &mut R, b: f64) -> f64 {
+    let t = b.ceil();
+    let u = loop {
+        let u = rng.gen_range(0.0..t).floor();
+        if sample_bernoulli_exp(rng, u / b) {
+            break u;
+        }
+    };
+    let mut v = 0.0;
+    while sample_bernoulli_exp(rng, t / b) {
+        v += 1.0;
+    }
+    u + t * v
+}
+
+/// Returns `true` with probability `exp(-gamma)` for `gamma >= 0` without
+/// evaluating the exponential (Canonne, Kamath & Steinke, 2020, Algorithm 1)
+pub(crate) fn sample_bernoulli_exp<R: Rng + ?Sized>(rng: &mut R, gamma: f64) -> bool {
+    let mut gamma = gamma;
+    while gamma > 1.0 {
+        if !sample_bernoulli_exp(rng, 1.0) {
+            return false;
+        }
+        gamma -= 1.0;
+    }
+    let mut k = 1.0;
+    while rng.gen::<f64>() < gamma / k {
+        k += 1.0;
+    }
+    k % 2.0 == 1.0
+}
+
+impl DiscreteCDF<i64, f64> for DiscreteLaplace {
+    /// Calculates the cumulative distribution function for the
+    /// discrete Laplace distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if x < μ {
+    ///     q^(μ - x) / (1 + q)
+    /// } else {
+    ///     1 - q^(x - μ + 1) / (1 + q)
+    /// }
+    /// ```
+    ///
+    /// where `q = exp(-1 / b)`
+    fn cdf(&self, x: i64) -> f64 {
+        let d = x as f64 - self.mu as f64;
+        if d < 0.0 {
+            fp::exp(d / self.b - fp::ln_1p(self.q))
+        } else {
+            1.0 - fp::exp(-(d + 1.0) / self.b - fp::ln_1p(self.q))
+        }
+    }
+
+    /// Calculates the survival function for the discrete Laplace
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if x < μ {
+    ///     1 - q^(μ - x) / (1 + q)
+    /// } else {
+    ///     q^(x - μ + 1) / (1 + q)
+    /// }
+    /// ```
+    ///
+    /// where `q = exp(-1 / b)`
+    fn sf(&self, x: i64) -> f64 {
+        let d = x as f64 - self.mu as f64;
+        if d < 0.0 {
+            1.0 - fp::exp(d / self.b - fp::ln_1p(self.q))
+        } else {
+            fp::exp(-(d + 1.0) / self.b - fp::ln_1p(self.q))
+        }
+    }
+}
+
+impl Min<i64> for DiscreteLaplace {
+    /// Returns the minimum value in the domain of the discrete Laplace
+    /// distribution representable by a 64-bit integer
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// i64::MIN
+    /// ```
+    fn min(&self) -> i64 {
+        i64::MIN
+    }
+}
+
+impl Max<i64> for DiscreteLaplace {
+    /// Returns the maximum value in the domain of the discrete Laplace
+    /// distribution representable by a 64-bit integer
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// i64::MAX
+    /// ```
+    fn max(&self) -> i64 {
+        i64::MAX
+    }
+}
+
+impl Distribution<f64> for DiscreteLaplace {
+    /// Returns the mean of the discrete Laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        Some(self.mu as f64)
+    }
+    /// Returns the variance of the discrete Laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 2q / (1 - q)^2
+    /// ```
+    ///
+    /// where `q = exp(-1 / b)`
+    fn variance(&self) -> Option<f64> {
+        let one_minus_q = -fp::exp_m1(-1.0 / self.b);
+        Some(2.0 * self.q / (one_minus_q * one_minus_q))
+    }
+    /// Returns the entropy of the discrete Laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln((1 + q) / (1 - q)) + 2q / ((1 - q^2) * b)
+    /// ```
+    ///
+    /// where `q = exp(-1 / b)`
+    fn entropy(&self) -> Option<f64> {
+        let one_minus_q2 = -fp::exp_m1(-2.0 / self.b);
+        Some(-self.ln_norm() + 2.0 * self.q / (one_minus_q2 * self.b))
+    }
+    /// Returns the skewness of the discrete Laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 0
+    /// ```
+    fn skewness(&self) -> Option<f64> {
+        Some(0.0)
+    }
+}
+
+impl Median<f64> for DiscreteLaplace {
+    /// Returns the median of the discrete Laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ
+    /// ```
+    fn median(&self) -> f64 {
+        self.mu as f64
+    }
+}
+
+impl Mode<Option<i64>> for DiscreteLaplace {
+    /// Returns the mode of the discrete Laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ
+    /// ```
+    fn mode(&self) -> Option<i64> {
+        Some(self.mu)
+    }
+}
+
+impl Discrete<i64, f64> for DiscreteLaplace {
+    /// Calculates the probability mass function for the discrete Laplace
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 - q) / (1 + q) * q^|x - μ|
+    /// ```
+    ///
+    /// where `q = exp(-1 / b)`
+    fn pmf(&self, x: i64) -> f64 {
+        fp::exp(self.ln_pmf(x))
+    }
+
+    /// Calculates the log probability mass function for the discrete
+    /// Laplace distribution at `x`
+    fn ln_pmf(&self, x: i64) -> f64 {
+        self.ln_norm() - (x as f64 - self.mu as f64).abs() / self.b
+    }
+}
+
+impl CharacteristicFunction<f64, f64> for DiscreteLaplace {
+    /// Calculates the characteristic function for the discrete Laplace
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(iμt) (1 - q)^2 / (1 - 2q cos(t) + q^2)
+    /// ```
+    ///
+    /// where `μ` is the location and `q = e^(-1 / b)`
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let s = fp::sin(0.5 * t);
+        let one_minus_q = -fp::exp_m1(-1.0 / self.b);
+        let denom = one_minus_q * one_minus_q + 4.0 * self.q * s * s;
+        Complex::from_polar(one_minus_q * one_minus_q / denom, self.mu as f64 * t)
+    }
+}
+
+impl Parameterized for DiscreteLaplace {
+    fn param_names() -> &'static [&'static str] {
+        &["mu", "b"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.mu as f64, self.b]
+    }
+
+    fn from_params(params: &[f64]) -> Result<DiscreteLaplace> {
+        match *params {
+            [mu, b] => DiscreteLaplace::new(integer_param(mu)?, b),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::statistics::*;
+    use crate::distribution::{CharacteristicFunction, DiscreteCDF, Discrete, DiscreteLaplace};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bad_create() {
+        assert!(DiscreteLaplace::new(0, f64::NAN).is_err());
+        assert!(DiscreteLaplace::new(0, -1.0).is_err());
+        assert!(DiscreteLaplace::new(0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_pmf() {
+        let n = DiscreteLaplace::new(1, 2.0).unwrap();
+        let q = (-0.5f64).exp();
+        assert_almost_eq!(n.pmf(1), (1.0 - q) / (1.0 + q), 1e-15);
+        assert_almost_eq!(n.pmf(4), (1.0 - q) / (1.0 + q) * q.powi(3), 1e-15);
+        assert_almost_eq!(n.ln_pmf(-2), ((1.0 - q) / (1.0 + q)).ln() - 1.5, 1e-14);
+        let total: f64 = (-300..=300).map(|x| n.pmf(x)).sum();
+        assert_almost_eq!(total, 1.0, 1e-13);
+    }
+
+    #[test]
+    fn test_moments() {
+        for &b in &[0.3, 1.0, 7.5] {
+            let n = DiscreteLaplace::new(0, b).unwrap();
+            let var: f64 = (-2000..=2000).map(|x| (x * x) as f64 * n.pmf(x)).sum();
+            assert_almost_eq!(n.variance().unwrap(), var, 1e-10 * var);
+            let h: f64 = (-2000..=2000).map(|x| -n.pmf(x) * n.ln_pmf(x)).sum();
+            assert_almost_eq!(n.entropy().unwrap(), h, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cdf() {
+        let n = DiscreteLaplace::new(-3, 1.7).unwrap();
+        let mut acc = 0.0;
+        for x in -200..=30 {
+            acc += n.pmf(x);
+            assert_almost_eq!(n.cdf(x), acc, 1e-14);
+            assert_almost_eq!(n.sf(x), 1.0 - acc, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let n = DiscreteLaplace::new(4, 2.5).unwrap();
+        let mut rng = StdRng::seed_from_u64(5);
+        let samples: Vec<f64> = (0..100_000).map(|_| n.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|x| x.fract() == 0.0));
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let var = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / samples.len() as f64;
+        assert_almost_eq!(mean, 4.0, 0.05);
+        assert_almost_eq!(var, n.variance().unwrap(), 0.25);
+        for &x in &[2, 4, 5, 9] {
+            let freq = samples.iter().filter(|&&s| s == x as f64).count() as f64 / samples.len() as f64;
+            assert_almost_eq!(freq, n.pmf(x), 0.005);
+        }
+    }
+
+    #[test]
+    fn test_bernoulli_exp() {
+        let mut rng = StdRng::seed_from_u64(6);
+        for &gamma in &[0.0, 0.4, 1.0, 2.7] {
+            let hits = (0..100_000).filter(|_| super::sample_bernoulli_exp(&mut rng, gamma)).count();
+            assert_almost_eq!(hits as f64 / 100_000.0, (-gamma).exp(), 0.005);
+        }
+    }
+
+    #[test]
+    fn test_cf() {
+        let phi = DiscreteLaplace::new(1, 2.0).unwrap().cf(0.8);
+        assert_almost_eq!(phi.re, 0.2063446110545672, 1e-14);
+        assert_almost_eq!(phi.im, 0.2124603675813434, 1e-14);
+    }
+}
@@ -1,6 +1,10 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{
+    integer_param, CharacteristicFunction, Discrete, DiscreteCDF, Parameterized,
+};
+use crate::fp;
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 
 /// Implements the [Discrete
@@ -49,6 +53,22 @@ impl DiscreteUniform {
             Ok(DiscreteUniform { min, max })
         }
     }
+
+    /// Constructs a new discrete uniform distribution from parameters already
+    /// known to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(min: i64, max: i64) -> DiscreteUniform {
+        debug_assert!(
+            DiscreteUniform::new(min, max).is_ok(),
+            "invalid discrete uniform parameters"
+        );
+        DiscreteUniform { min, max }
+    }
 }
 
 impl ::rand::distributions::Distribution<f64> for DiscreteUniform {
@@ -139,7 +159,7 @@ impl Distribution<f64> for DiscreteUniform {
     /// ```
     fn entropy(&self) -> Option<f64> {
         let diff = (self.max - self.min) as f64;
-        Some((diff + 1.0).ln())
+        Some(fp::ln(diff + 1.0))
     }
     /// Returns the skewness of the discrete uniform distribution
     ///
@@ -219,19 +239,60 @@ impl Discrete<i64, f64> for DiscreteUniform {
     /// ```
     fn ln_pmf(&self, x: i64) -> f64 {
         if x >= self.min && x <= self.max {
-            -((self.max - self.min + 1) as f64).ln()
+            -fp::ln((self.max - self.min + 1) as f64)
         } else {
             f64::NEG_INFINITY
         }
     }
 }
 
+impl CharacteristicFunction<f64, f64> for DiscreteUniform {
+    /// Calculates the characteristic function for the discrete uniform
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(it(min + max) / 2) * sin(nt / 2) / (n sin(t / 2))
+    /// ```
+    ///
+    /// where `n = max - min + 1`
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let n = (self.max - self.min + 1) as f64;
+        let s = fp::sin(0.5 * t);
+        let kernel = if s == 0.0 {
+            1.0
+        } else {
+            fp::sin(0.5 * n * t) / (n * s)
+        };
+        Complex::from_polar(kernel, 0.5 * t * (self.min as f64 + self.max as f64))
+    }
+}
+
+impl Parameterized for DiscreteUniform {
+    fn param_names() -> &'static [&'static str] {
+        &["min", "max"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.min as f64, self.max as f64]
+    }
+
+    fn from_params(params: &[f64]) -> Result<DiscreteUniform> {
+        match *params {
+            [min, max] => DiscreteUniform::new(integer_param(min)?, integer_param(max)?),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use std::fmt::Debug;
     use crate::statistics::*;
-    use crate::distribution::{DiscreteCDF, Discrete, DiscreteUniform};
+    use crate::distribution::{CharacteristicFunction, DiscreteCDF, Discrete, DiscreteUniform};
+    use num_complex::Complex;
     use crate::consts::ACC;
 
     fn try_create(min: i64, max: i64) -> DiscreteUniform {
@@ -375,4 +436,12 @@ mod tests {
         let cdf = |arg: i64| move |x: DiscreteUniform| x.cdf(arg);
         test_case(0, 3, 1.0, cdf(5));
     }
+
+    #[test]
+    fn test_cf() {
+        assert_eq!(try_create(-2, 5).cf(0.0), Complex::new(1.0, 0.0));
+        let phi = try_create(-2, 5).cf(0.6);
+        assert_almost_eq!(phi.re, 0.17759980399224082, 1e-14);
+        assert_almost_eq!(phi.im, 0.22380385243615167, 1e-14);
+    }
 }
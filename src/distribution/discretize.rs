@@ -0,0 +1,326 @@
+use crate::distribution::{Categorical, ContinuousCDF};
+use crate::fp;
+use crate::quadrature::GaussRule;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Selects how `discretize` places the support points of the discrete
+/// approximation of a continuous distribution
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DiscretizeMethod {
+    /// Splits the support into bins of equal probability, each represented
+    /// by its conditional mean
+    EqualProbability,
+    /// Splits `[lower, upper]` into bins of equal width, the outermost of
+    /// which are extended to the bounds of the support. Every bin is
+    /// represented by its conditional mean and carries its probability.
+    EqualWidth { lower: f64, upper: f64 },
+    /// Places the points and probabilities of the Gaussian quadrature rule
+    /// of the distribution, which matches its first `2n - 1` moments
+    Gaussian,
+}
+
+/// Represents a discrete approximation of a continuous distribution as
+/// returned by `discretize`, with the `k`th support point carrying the
+/// probability of the `k`th category of a categorical distribution
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{discretize, DiscretizeMethod, Normal};
+///
+/// let n = Normal::new(0.0, 1.0).unwrap();
+/// let d = discretize(&n, 5, DiscretizeMethod::EqualProbability).unwrap();
+/// assert_eq!(d.points().len(), 5);
+/// assert!(d.points()[2].abs() < 1e-10);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discretization {
+    points: Vec<f64>,
+    probabilities: Vec<f64>,
+    categorical: Categorical,
+}
+
+impl Discretization {
+    /// Returns the support points in increasing order
+    pub fn points(&self) -> &[f64] {
+        &self.points
+    }
+
+    /// Returns the probabilities of the support points, which sum to one
+    pub fn probabilities(&self) -> &[f64] {
+        &self.probabilities
+    }
+
+    /// Returns the categorical distribution over the indices of the
+    /// support points
+    pub fn categorical(&self) -> &Categorical {
+        &self.categorical
+    }
+
+    /// Returns the expected value of `f` under the discrete approximation
+    pub fn expectation<F>(&self, mut f: F) -> f64
+    where
+        F: FnMut(f64) -> f64,
+    {
+        self.points
+            .iter()
+            .zip(&self.probabilities)
+            .map(|(&x, &p)| p * f(x))
+            .sum()
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for Discretization {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.points[self.categorical.sample(rng) as usize]
+    }
+}
+
+/// Approximates the continuous distribution `dist` by a discrete
+/// distribution on `n` points, e.g. to build lattices or scenario trees for
+/// stochastic programming
+///
+/// # Remarks
+///
+/// All methods only need the inverse cdf of `dist`. Expectations over bins
+/// are computed as integrals of the inverse cdf over the corresponding
+/// probabilities with tanh-sinh quadrature, so the bin representatives
+/// preserve the mean of the distribution. The Gaussian points are computed
+/// by the discretized Stieltjes procedure on the same quadrature grid and
+/// are intended for small `n`.
+///
+/// # Errors
+///
+/// Returns an error if `n` is zero, if the bounds of
+/// `DiscretizeMethod::EqualWidth` are not finite or `lower >= upper`, or if
+/// `n` is too large for the Gaussian points to be computed
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{discretize, DiscretizeMethod, Exp};
+///
+/// let exp = Exp::new(1.0).unwrap();
+/// let d = discretize(&exp, 2, DiscretizeMethod::Gaussian).unwrap();
+/// // the Gauss-Laguerre nodes 2 -+ sqrt(2)
+/// assert!((d.points()[0] - (2.0 - 2f64.sqrt())).abs() < 1e-8);
+/// assert!((d.expectation(|x| x * x * x) - 6.0).abs() < 1e-6);
+/// ```
+pub fn discretize<D>(dist: &D, n: usize, method: DiscretizeMethod) -> Result<Discretization>
+where
+    D: ContinuousCDF<f64, f64> + ?Sized,
+{
+    if n == 0 {
+        return Err(StatsError::BadParams);
+    }
+    let (points, probabilities) = match method {
+        DiscretizeMethod::EqualProbability => {
+            let width = 1.0 / n as f64;
+            let points = (0..n)
+                .map(|k| {
+                    let (p, q) = (k as f64 * width, (k + 1) as f64 * width);
+                    quantile_integral(dist, p, q.min(1.0)) / width
+                })
+                .collect();
+            (points, vec![width; n])
+        }
+        DiscretizeMethod::EqualWidth { lower, upper } => {
+            if !lower.is_finite() || !upper.is_finite() || lower >= upper {
+                return Err(StatsError::BadParams);
+            }
+            let width = (upper - lower) / n as f64;
+            let mut points = Vec::with_capacity(n);
+            let mut probabilities = Vec::with_capacity(n);
+            for k in 0..n {
+                let a = if k == 0 {
+                    f64::NEG_INFINITY
+                } else {
+                    lower + k as f64 * width
+                };
+                let b = if k == n - 1 {
+                    f64::INFINITY
+                } else {
+                    lower + (k + 1) as f64 * width
+                };
+                let mass = dist.prob_between(a, b);
+                let (p, q) = (dist.cdf(a), dist.cdf(b));
+                let point = if mass > 0.0 && q > p {
+                    quantile_integral(dist, p, q) / (q - p)
+                } else {
+                    lower + (k as f64 + 0.5) * width
+                };
+                points.push(point);
+                probabilities.push(mass.max(0.0));
+            }
+            (points, probabilities)
+        }
+        DiscretizeMethod::Gaussian => {
+            let rule = stieltjes_rule(dist, n)?;
+            (rule.nodes().to_vec(), rule.weights().to_vec())
+        }
+    };
+    let categorical = Categorical::new(&probabilities)?;
+    let total: f64 = probabilities.iter().sum();
+    Ok(Discretization {
+        points,
+        probabilities: probabilities.iter().map(|p| p / total).collect(),
+        categorical,
+    })
+}
+
+/// Abscissae `(t, 1 - t)` and weights of the tanh-sinh rule on `[0, 1]`
+/// with step `1/16` truncated where the weights drop below double precision
+pub(crate) fn tanh_sinh() -> impl Iterator<Item = (f64, f64, f64)> {
+    (-48..=48).map(|k| {
+        let s = k as f64 / 16.0;
+        let v = f64::consts::PI * fp::sinh(s);
+        let t = 1.0 / (1.0 + fp::exp(v));
+        let t_c = 1.0 / (1.0 + fp::exp(-v));
+        let w = f64::consts::PI * fp::cosh(s) * t * t_c / 16.0;
+        (t, t_c, w)
+    })
+}
+
+/// Computes the integral of `F^-1(u)` over `u` in `[p, q]` where `F` is the
+/// cdf of `dist`, i.e. the expectation of `X` restricted to the event
+/// `F^-1(p) < X <= F^-1(q)`
+fn quantile_integral<D>(dist: &D, p: f64, q: f64) -> f64
+where
+    D: ContinuousCDF<f64, f64> + ?Sized,
+{
+    let len = q - p;
+    tanh_sinh()
+        .map(|(t, t_c, w)| {
+            // evaluate from the nearer end to keep the distance to it exact
+            let u = if t <= 0.5 { p + len * t } else { q - len * t_c };
+            w * dist.inverse_cdf(u)
+        })
+        .sum::<f64>()
+        * len
+}
+
+/// Computes the `n`-point Gaussian quadrature rule of `dist` by running the
+/// Stieltjes procedure on the measure discretized by `tanh_sinh`
+fn stieltjes_rule<D>(dist: &D, n: usize) -> Result<GaussRule>
+where
+    D: ContinuousCDF<f64, f64> + ?Sized,
+{
+    let (xs, mut ws): (Vec<f64>, Vec<f64>) = tanh_sinh()
+        .map(|(t, t_c, w)| {
+            let u = if t <= 0.5 { t } else { 1.0 - t_c };
+            (dist.inverse_cdf(u), w)
+        })
+        .filter(|(x, w)| x.is_finite() && *w > 0.0)
+        .unzip();
+    if n > xs.len() / 2 {
+        return Err(StatsError::BadParams);
+    }
+    let total: f64 = ws.iter().sum();
+    for w in &mut ws {
+        *w /= total;
+    }
+    // orthonormal polynomials evaluated at the nodes, starting from q_0 = 1
+    let mut prev = vec![0.0; xs.len()];
+    let mut curr = vec![1.0; xs.len()];
+    let mut alpha = Vec::with_capacity(n);
+    let mut beta = vec![0.0];
+    for k in 0..n {
+        let a: f64 = (0..xs.len())
+            .map(|j| ws[j] * xs[j] * curr[j] * curr[j])
+            .sum();
+        alpha.push(a);
+        if k + 1 == n {
+            break;
+        }
+        let next: Vec<f64> = (0..xs.len())
+            .map(|j| (xs[j] - a) * curr[j] - beta[k] * prev[j])
+            .collect();
+        let norm = (0..xs.len())
+            .map(|j| ws[j] * next[j] * next[j])
+            .sum::<f64>()
+            .sqrt();
+        if norm == 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        beta.push(norm);
+        prev = curr;
+        curr = next.iter().map(|x| x / norm).collect();
+    }
+    GaussRule::golub_welsch(n, |k| alpha[k], |k| beta[k])
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::{Exp, Normal, Uniform};
+    use crate::quadrature::GaussQuadrature;
+
+    #[test]
+    fn test_bad_params() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        assert!(discretize(&n, 0, DiscretizeMethod::Gaussian).is_err());
+        assert!(discretize(&n, 3, DiscretizeMethod::EqualWidth { lower: 1.0, upper: 1.0 }).is_err());
+        assert!(discretize(&n, 3, DiscretizeMethod::EqualWidth { lower: f64::NEG_INFINITY, upper: 1.0 }).is_err());
+    }
+
+    #[test]
+    fn test_equal_probability() {
+        let u = Uniform::new(0.0, 2.0).unwrap();
+        let d = discretize(&u, 4, DiscretizeMethod::EqualProbability).unwrap();
+        for (k, x) in d.points().iter().enumerate() {
+            assert_almost_eq!(*x, 0.25 + 0.5 * k as f64, 1e-12);
+            assert_eq!(d.probabilities()[k], 0.25);
+        }
+
+        // conditional means of the exponential distribution on its halves
+        let exp = Exp::new(1.0).unwrap();
+        let d = discretize(&exp, 2, DiscretizeMethod::EqualProbability).unwrap();
+        let median = 2f64.ln();
+        assert_almost_eq!(d.points()[0], 2.0 * (1.0 - 0.5 * (1.0 + median)), 1e-10);
+        assert_almost_eq!(d.points()[1], 1.0 + median, 1e-10);
+        assert_almost_eq!(d.expectation(|x| x), 1.0, 1e-10);
+    }
+
+    #[test]
+    fn test_equal_width() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let d = discretize(&n, 4, DiscretizeMethod::EqualWidth { lower: -1.0, upper: 1.0 }).unwrap();
+        let pdf = |x: f64| (-0.5 * x * x).exp() / (2.0 * f64::consts::PI).sqrt();
+        assert_almost_eq!(d.probabilities()[0], n.cdf(-0.5), 1e-14);
+        assert_almost_eq!(d.probabilities()[1], n.cdf(0.0) - n.cdf(-0.5), 1e-14);
+        // E[X | a < X <= b] = (pdf(a) - pdf(b)) / (cdf(b) - cdf(a))
+        assert_almost_eq!(d.points()[1], (pdf(-0.5) - pdf(0.0)) / d.probabilities()[1], 1e-9);
+        assert_almost_eq!(d.points()[3], pdf(0.5) / n.sf(0.5), 1e-9);
+        assert_almost_eq!(d.expectation(|x| x), 0.0, 1e-9);
+    }
+
+    #[test]
+    fn test_gaussian() {
+        let n = Normal::new(1.0, 2.0).unwrap();
+        let d = discretize(&n, 3, DiscretizeMethod::Gaussian).unwrap();
+        let rule = n.gauss_rule(3).unwrap();
+        for k in 0..3 {
+            assert_almost_eq!(d.points()[k], rule.nodes()[k], 1e-8);
+            assert_almost_eq!(d.probabilities()[k], rule.weights()[k], 1e-8);
+        }
+        assert_almost_eq!(d.expectation(|x| (x - 1.0).powi(4)), 48.0, 1e-6);
+    }
+
+    #[test]
+    fn test_sample() {
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let u = Uniform::new(0.0, 1.0).unwrap();
+        let d = discretize(&u, 3, DiscretizeMethod::EqualProbability).unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let x = d.sample(&mut rng);
+            assert!(d.points().iter().any(|&p| p == x));
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::distribution::discretize::tanh_sinh;
+use crate::distribution::{Continuous, ContinuousCDF, Discrete, DiscreteCDF};
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Computes the Kullback-Leibler divergence `D_KL(P || Q)` of the
+/// continuous distribution `q` from `p` by tanh-sinh quadrature of
+/// `ln(p(x) / q(x))` over the quantiles of `p`, for pairs of distributions
+/// without a closed form
+///
+/// # Remarks
+///
+/// The result is accurate to about `1e-10` relative for smooth densities.
+/// It is infinite if `q` vanishes where `p` does not, as detected at the
+/// quadrature nodes.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{kl_divergence_quadrature, KullbackLeibler, Normal};
+///
+/// let p = Normal::new(0.0, 1.0).unwrap();
+/// let q = Normal::new(0.5, 2.0).unwrap();
+/// let kl = kl_divergence_quadrature(&p, &q);
+/// assert!((kl - p.kl_divergence(&q)).abs() < 1e-12);
+/// ```
+pub fn kl_divergence_quadrature<P, Q>(p: &P, q: &Q) -> f64
+where
+    P: ContinuousCDF<f64, f64> + Continuous<f64, f64>,
+    Q: Continuous<f64, f64>,
+{
+    tanh_sinh()
+        .map(|(t, t_c, w)| {
+            let x = if t <= 0.5 {
+                p.inverse_cdf(t)
+            } else {
+                p.inverse_cdf(1.0 - t_c)
+            };
+            w * ln_ratio(p.ln_pdf(x), q.ln_pdf(x))
+        })
+        .sum::<f64>()
+        .max(0.0)
+}
+
+/// Computes the Kullback-Leibler divergence `D_KL(P || Q)` of the discrete
+/// distribution `q` from `p` by summing `p(k) ln(p(k) / q(k))` over the
+/// support of `p`, for pairs of distributions without a closed form
+///
+/// # Remarks
+///
+/// The summation stops once the remaining upper tail probability of `p`
+/// drops below `1e-17`. The result is infinite if `q` vanishes where `p`
+/// does not within the summed range.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{kl_divergence_discrete, Binomial, Poisson};
+///
+/// // the Poisson approximation to the binomial distribution
+/// let p = Binomial::new(0.01, 100).unwrap();
+/// let q = Poisson::new(1.0).unwrap();
+/// assert!(kl_divergence_discrete(&p, &q) < 1e-3);
+/// // a poisson variable may exceed the three trials of the binomial one
+/// let b = Binomial::new(0.5, 3).unwrap();
+/// assert!(kl_divergence_discrete(&q, &b).is_infinite());
+/// ```
+pub fn kl_divergence_discrete<P, Q>(p: &P, q: &Q) -> f64
+where
+    P: DiscreteCDF<u64, f64> + Discrete<u64, f64>,
+    Q: Discrete<u64, f64>,
+{
+    let mut sum = 0.0;
+    let mut k = p.min();
+    loop {
+        let p_k = p.pmf(k);
+        if p_k > 0.0 {
+            sum += p_k * ln_ratio(p.ln_pmf(k), q.ln_pmf(k));
+            if sum.is_infinite() {
+                return sum;
+            }
+        }
+        if k >= p.max() || p.sf(k) < 1e-17 {
+            return sum.max(0.0);
+        }
+        k += 1;
+    }
+}
+
+/// Estimates the Kullback-Leibler divergence `D_KL(P || Q)` of the
+/// continuous distribution `q` from `p` as the mean of `ln(p(X) / q(X))`
+/// over `n` draws `X` from `p`. Unlike the quadrature the estimate only
+/// requires sampling from `p`, at the cost of an error decreasing as
+/// `1 / sqrt(n)`.
+///
+/// # Errors
+///
+/// Returns an error if `n` is zero
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::distribution::{kl_divergence_monte_carlo, KullbackLeibler, Laplace, Normal};
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let p = Normal::new(0.0, 1.0).unwrap();
+/// let q = Laplace::new(0.0, 1.0).unwrap();
+/// let kl = kl_divergence_monte_carlo(&p, &q, 10000, &mut rng).unwrap();
+/// assert!(kl > 0.0 && kl < 0.2);
+/// ```
+pub fn kl_divergence_monte_carlo<P, Q, R>(p: &P, q: &Q, n: usize, rng: &mut R) -> Result<f64>
+where
+    P: Continuous<f64, f64> + ::rand::distributions::Distribution<f64>,
+    Q: Continuous<f64, f64>,
+    R: Rng + ?Sized,
+{
+    if n == 0 {
+        return Err(StatsError::BadParams);
+    }
+    let sum: f64 = (0..n)
+        .map(|_| {
+            let x = p.sample(rng);
+            ln_ratio(p.ln_pdf(x), q.ln_pdf(x))
+        })
+        .sum();
+    Ok(sum / n as f64)
+}
+
+/// Returns `ln(p / q)` from the log densities, taking points outside the
+/// support of `p` to contribute nothing
+fn ln_ratio(ln_p: f64, ln_q: f64) -> f64 {
+    if ln_p == f64::NEG_INFINITY {
+        0.0
+    } else {
+        ln_p - ln_q
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::{
+        Bernoulli, Beta, Binomial, Categorical, Exp, Gamma, KullbackLeibler, Laplace,
+        LogNormal, Normal, Poisson, StudentsT, Uniform,
+    };
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_quadrature_matches_closed_forms() {
+        let normals = [Normal::new(0.0, 1.0).unwrap(), Normal::new(-1.0, 0.5).unwrap()];
+        assert_almost_eq!(kl_divergence_quadrature(&normals[0], &normals[1]), normals[0].kl_divergence(&normals[1]), 1e-10);
+        assert_almost_eq!(kl_divergence_quadrature(&normals[1], &normals[0]), normals[1].kl_divergence(&normals[0]), 1e-10);
+        let ln = [LogNormal::new(0.2, 0.7).unwrap(), LogNormal::new(0.0, 1.3).unwrap()];
+        assert_almost_eq!(kl_divergence_quadrature(&ln[0], &ln[1]), ln[0].kl_divergence(&ln[1]), 1e-10);
+        let exp = [Exp::new(0.5).unwrap(), Exp::new(3.0).unwrap()];
+        assert_almost_eq!(kl_divergence_quadrature(&exp[0], &exp[1]), exp[0].kl_divergence(&exp[1]), 1e-10);
+        let gamma = [Gamma::new(2.5, 1.5).unwrap(), Gamma::new(0.8, 0.4).unwrap()];
+        assert_almost_eq!(kl_divergence_quadrature(&gamma[0], &gamma[1]), gamma[0].kl_divergence(&gamma[1]), 1e-9);
+        assert_almost_eq!(kl_divergence_quadrature(&gamma[1], &gamma[0]), gamma[1].kl_divergence(&gamma[0]), 1e-9);
+        let beta = [Beta::new(2.0, 3.0).unwrap(), Beta::new(0.7, 1.4).unwrap()];
+        assert_almost_eq!(kl_divergence_quadrature(&beta[0], &beta[1]), beta[0].kl_divergence(&beta[1]), 1e-9);
+        let laplace = [Laplace::new(0.0, 1.0).unwrap(), Laplace::new(0.7, 2.0).unwrap()];
+        // reference value from numerical integration with mpmath
+        assert_almost_eq!(laplace[0].kl_divergence(&laplace[1]), 0.29143983245565006, 1e-15);
+        let uniform = [Uniform::new(0.2, 0.5).unwrap(), Uniform::new(0.0, 1.0).unwrap()];
+        assert_almost_eq!(kl_divergence_quadrature(&uniform[0], &uniform[1]), uniform[0].kl_divergence(&uniform[1]), 1e-12);
+        assert!(kl_divergence_quadrature(&uniform[1], &uniform[0]).is_infinite());
+    }
+
+    #[test]
+    fn test_quadrature_cross_family() {
+        // D_KL(t_ν || N(0, 1)) approaches zero as ν grows
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let t5 = kl_divergence_quadrature(&StudentsT::new(0.0, 1.0, 5.0).unwrap(), &n);
+        let t50 = kl_divergence_quadrature(&StudentsT::new(0.0, 1.0, 50.0).unwrap(), &n);
+        assert!(t5 > t50 && t50 > 0.0);
+        // the normal from a gamma with the same mean and variance
+        let g = Gamma::new(4.0, 2.0).unwrap();
+        let kl = kl_divergence_quadrature(&n, &g);
+        assert!(kl.is_infinite());
+    }
+
+    #[test]
+    fn test_discrete_matches_closed_forms() {
+        let p = [Poisson::new(2.0).unwrap(), Poisson::new(3.5).unwrap()];
+        assert_almost_eq!(kl_divergence_discrete(&p[0], &p[1]), p[0].kl_divergence(&p[1]), 1e-14);
+        let b = [Bernoulli::new(0.3).unwrap(), Bernoulli::new(0.6).unwrap()];
+        assert_almost_eq!(kl_divergence_discrete(&b[0], &b[1]), b[0].kl_divergence(&b[1]), 1e-15);
+        let c = [Categorical::new(&[1.0, 2.0, 3.0]).unwrap(), Categorical::new(&[3.0, 2.0, 1.0]).unwrap()];
+        assert_almost_eq!(kl_divergence_discrete(&c[0], &c[1]), c[0].kl_divergence(&c[1]), 1e-15);
+        // D_KL(Bin(n, p) || Bin(n, q)) = n D_KL(Ber(p) || Ber(q))
+        let bin = [Binomial::new(0.3, 10).unwrap(), Binomial::new(0.6, 10).unwrap()];
+        assert_almost_eq!(kl_divergence_discrete(&bin[0], &bin[1]), 10.0 * b[0].kl_divergence(&b[1]), 1e-14);
+    }
+
+    #[test]
+    fn test_cross_entropy() {
+        use crate::statistics::Distribution;
+
+        // -E_P[ln q(X)] by quadrature over the quantiles of p
+        fn quadrature<P, Q>(p: &P, q: &Q) -> f64
+        where
+            P: ContinuousCDF<f64, f64>,
+            Q: Continuous<f64, f64>,
+        {
+            tanh_sinh()
+                .map(|(t, t_c, w)| {
+                    let x = if t <= 0.5 { p.inverse_cdf(t) } else { p.inverse_cdf(1.0 - t_c) };
+                    -w * q.ln_pdf(x)
+                })
+                .sum()
+        }
+        let normals = [Normal::new(0.0, 1.0).unwrap(), Normal::new(-1.0, 0.5).unwrap()];
+        assert_almost_eq!(normals[0].cross_entropy(&normals[1]).unwrap(), quadrature(&normals[0], &normals[1]), 1e-10);
+        let gamma = [Gamma::new(2.5, 1.5).unwrap(), Gamma::new(0.8, 0.4).unwrap()];
+        assert_almost_eq!(gamma[0].cross_entropy(&gamma[1]).unwrap(), quadrature(&gamma[0], &gamma[1]), 1e-9);
+        let beta = [Beta::new(2.0, 3.0).unwrap(), Beta::new(0.7, 1.4).unwrap()];
+        assert_almost_eq!(beta[0].cross_entropy(&beta[1]).unwrap(), quadrature(&beta[0], &beta[1]), 1e-9);
+        let exp = [Exp::new(0.5).unwrap(), Exp::new(3.0).unwrap()];
+        // λ_q E_P[X] - ln(λ_q)
+        assert_almost_eq!(exp[0].cross_entropy(&exp[1]).unwrap(), 6.0 - 3f64.ln(), 1e-14);
+        assert_eq!(exp[0].cross_entropy(&exp[0]), exp[0].entropy());
+        let b = [Bernoulli::new(0.3).unwrap(), Bernoulli::new(0.6).unwrap()];
+        assert_almost_eq!(b[0].cross_entropy(&b[1]).unwrap(), -0.3 * 0.6f64.ln() - 0.7 * 0.4f64.ln(), 1e-15);
+        let uniform = [Uniform::new(0.2, 0.5).unwrap(), Uniform::new(0.0, 1.0).unwrap()];
+        assert_almost_eq!(uniform[0].cross_entropy(&uniform[1]).unwrap(), 0.0, 1e-15);
+        assert!(uniform[1].cross_entropy(&uniform[0]).unwrap().is_infinite());
+    }
+
+    #[test]
+    fn test_entropy_base() {
+        use crate::statistics::Distribution;
+
+        let b = Bernoulli::new(0.5).unwrap();
+        assert_almost_eq!(b.entropy_base(2.0).unwrap(), 1.0, 1e-15);
+        let n = Normal::new(0.0, 1.0).unwrap();
+        assert_almost_eq!(n.entropy_base(10.0).unwrap(), n.entropy().unwrap() / 10f64.ln(), 1e-15);
+        assert_almost_eq!(n.entropy_base(std::f64::consts::E).unwrap(), n.entropy().unwrap(), 1e-15);
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let p = Normal::new(0.0, 1.0).unwrap();
+        let q = Normal::new(1.0, 2.0).unwrap();
+        let kl = kl_divergence_monte_carlo(&p, &q, 100_000, &mut rng).unwrap();
+        assert_almost_eq!(kl, p.kl_divergence(&q), 0.01);
+        assert!(kl_divergence_monte_carlo(&p, &q, 0, &mut rng).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Provides object-safe interfaces to univariate distributions, for storing
+//! distributions of different types together and dispatching at runtime
+
+use crate::distribution::{Continuous, ContinuousCDF, Discrete, DiscreteCDF};
+use num_traits::{Bounded, Num};
+use rand::distributions::Distribution;
+use rand::RngCore;
+
+/// The `DynContinuous` trait is an object-safe interface to univariate
+/// continuous distributions, so that distributions of different types can
+/// be stored together and dispatched at runtime, e.g. as
+/// `Vec<Box<dyn DynContinuous>>`
+///
+/// # Remarks
+///
+/// The trait is implemented for every type implementing `Continuous`,
+/// `ContinuousCDF` and `rand`'s `Distribution` over `f64`. Its methods
+/// share their names with those traits, so it is intended to be used
+/// through trait objects; calling e.g. `pdf` on a concrete distribution
+/// with both traits in scope is ambiguous, which is why the trait is not
+/// re-exported from `statrs::distribution`. Methods of a trait object can
+/// be called without importing the trait.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::dynamic::DynContinuous;
+/// use statrs::distribution::{Exp, Normal, Uniform};
+///
+/// let config: Vec<Box<dyn DynContinuous>> = vec![
+///     Box::new(Normal::new(0.0, 1.0).unwrap()),
+///     Box::new(Exp::new(2.0).unwrap()),
+///     Box::new(Uniform::new(-1.0, 1.0).unwrap()),
+/// ];
+/// let mut rng = rand::thread_rng();
+/// for d in &config {
+///     let x = d.sample(&mut rng);
+///     assert!(d.pdf(x) > 0.0);
+///     assert!((d.inverse_cdf(d.cdf(x)) - x).abs() < 1e-8 * (1.0 + x.abs()));
+/// }
+/// ```
+pub trait DynContinuous {
+    /// Returns the probability density function calculated at `x`
+    fn pdf(&self, x: f64) -> f64;
+
+    /// Returns the log of the probability density function calculated at
+    /// `x`
+    fn ln_pdf(&self, x: f64) -> f64;
+
+    /// Returns the cumulative distribution function calculated at `x`
+    fn cdf(&self, x: f64) -> f64;
+
+    /// Returns the survival function calculated at `x`
+    fn sf(&self, x: f64) -> f64;
+
+    /// Returns the inverse of the cumulative distribution function
+    /// calculated at `p`
+    fn inverse_cdf(&self, p: f64) -> f64;
+
+    /// Draws a sample from the distribution using `rng`
+    fn sample(&self, rng: &mut dyn RngCore) -> f64;
+}
+
+impl<D> DynContinuous for D
+where
+    D: Continuous<f64, f64> + ContinuousCDF<f64, f64> + Distribution<f64>,
+{
+    fn pdf(&self, x: f64) -> f64 {
+        Continuous::pdf(self, x)
+    }
+
+    fn ln_pdf(&self, x: f64) -> f64 {
+        Continuous::ln_pdf(self, x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        ContinuousCDF::cdf(self, x)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        ContinuousCDF::sf(self, x)
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        ContinuousCDF::inverse_cdf(self, p)
+    }
+
+    fn sample(&self, rng: &mut dyn RngCore) -> f64 {
+        Distribution::sample(self, rng)
+    }
+}
+
+/// The `DynDiscrete` trait is an object-safe interface to univariate
+/// discrete distributions over `K`, so that distributions of different
+/// types can be stored together and dispatched at runtime, e.g. as
+/// `Vec<Box<dyn DynDiscrete<u64>>>`
+///
+/// # Remarks
+///
+/// The trait is implemented for every type implementing `Discrete`,
+/// `DiscreteCDF` and `rand`'s `Distribution` over `f64`, and like those
+/// traits samples are returned as `f64`. Its methods share their names with
+/// those traits, so it is intended to be used through trait objects.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::dynamic::DynDiscrete;
+/// use statrs::distribution::{Binomial, Geometric, Poisson};
+///
+/// let config: Vec<Box<dyn DynDiscrete<u64>>> = vec![
+///     Box::new(Poisson::new(3.0).unwrap()),
+///     Box::new(Binomial::new(0.5, 10).unwrap()),
+///     Box::new(Geometric::new(0.2).unwrap()),
+/// ];
+/// let mut rng = rand::thread_rng();
+/// for d in &config {
+///     let x = d.sample(&mut rng) as u64;
+///     assert!(d.pmf(x) > 0.0);
+///     assert!(d.inverse_cdf(d.cdf(x)) <= x);
+/// }
+/// ```
+pub trait DynDiscrete<K> {
+    /// Returns the probability mass function calculated at `x`
+    fn pmf(&self, x: K) -> f64;
+
+    /// Returns the log of the probability mass function calculated at `x`
+    fn ln_pmf(&self, x: K) -> f64;
+
+    /// Returns the cumulative distribution function calculated at `x`
+    fn cdf(&self, x: K) -> f64;
+
+    /// Returns the survival function calculated at `x`
+    fn sf(&self, x: K) -> f64;
+
+    /// Returns the smallest `x` for which the cumulative distribution
+    /// function is at least `p`
+    fn inverse_cdf(&self, p: f64) -> K;
+
+    /// Draws a sample from the distribution using `rng`
+    fn sample(&self, rng: &mut dyn RngCore) -> f64;
+}
+
+impl<K, D> DynDiscrete<K> for D
+where
+    K: Bounded + Clone + Num,
+    D: Discrete<K, f64> + DiscreteCDF<K, f64> + Distribution<f64>,
+{
+    fn pmf(&self, x: K) -> f64 {
+        Discrete::pmf(self, x)
+    }
+
+    fn ln_pmf(&self, x: K) -> f64 {
+        Discrete::ln_pmf(self, x)
+    }
+
+    fn cdf(&self, x: K) -> f64 {
+        DiscreteCDF::cdf(self, x)
+    }
+
+    fn sf(&self, x: K) -> f64 {
+        DiscreteCDF::sf(self, x)
+    }
+
+    fn inverse_cdf(&self, p: f64) -> K {
+        DiscreteCDF::inverse_cdf(self, p)
+    }
+
+    fn sample(&self, rng: &mut dyn RngCore) -> f64 {
+        Distribution::sample(self, rng)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::{Binomial, DiscreteGaussian, Exp, Gamma, Normal, Poisson, Uniform};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_dyn_continuous() {
+        let concrete = (Normal::new(1.0, 2.0).unwrap(), Gamma::new(2.0, 3.0).unwrap(), Exp::new(0.5).unwrap());
+        let boxed: Vec<Box<dyn DynContinuous>> = vec![Box::new(concrete.0), Box::new(concrete.1), Box::new(concrete.2)];
+        let xs = [0.1, 0.5, 2.0, 7.5];
+        for x in xs {
+            assert_eq!(boxed[0].pdf(x), Continuous::pdf(&concrete.0, x));
+            assert_eq!(boxed[1].ln_pdf(x), Continuous::ln_pdf(&concrete.1, x));
+            assert_eq!(boxed[2].cdf(x), ContinuousCDF::cdf(&concrete.2, x));
+            assert_eq!(boxed[0].sf(x), ContinuousCDF::sf(&concrete.0, x));
+        }
+        assert_eq!(boxed[1].inverse_cdf(0.3), ContinuousCDF::inverse_cdf(&concrete.1, 0.3));
+        // sampling through the trait object consumes the rng like the
+        // concrete sampler
+        let mut a = StdRng::seed_from_u64(7);
+        let mut b = StdRng::seed_from_u64(7);
+        for _ in 0..10 {
+            assert_eq!(boxed[1].sample(&mut a), Distribution::sample(&concrete.1, &mut b));
+        }
+        let mut rng = StdRng::seed_from_u64(7);
+        let u: &dyn DynContinuous = &Uniform::new(2.0, 3.0).unwrap();
+        let x = u.sample(&mut rng);
+        assert!((2.0..3.0).contains(&x));
+    }
+
+    #[test]
+    fn test_dyn_discrete() {
+        let p = Poisson::new(3.0).unwrap();
+        let b = Binomial::new(0.3, 12).unwrap();
+        let boxed: Vec<Box<dyn DynDiscrete<u64>>> = vec![Box::new(p), Box::new(b)];
+        for x in 0..10 {
+            assert_eq!(boxed[0].pmf(x), Discrete::pmf(&p, x));
+            assert_eq!(boxed[1].ln_pmf(x), Discrete::ln_pmf(&b, x));
+            assert_eq!(boxed[0].cdf(x), DiscreteCDF::cdf(&p, x));
+            assert_eq!(boxed[1].sf(x), DiscreteCDF::sf(&b, x));
+        }
+        assert_eq!(boxed[1].inverse_cdf(0.6), DiscreteCDF::inverse_cdf(&b, 0.6));
+        let g: Box<dyn DynDiscrete<i64>> = Box::new(DiscreteGaussian::new(-2, 1.5).unwrap());
+        assert!(g.pmf(-2) > g.pmf(0));
+        let mut a = StdRng::seed_from_u64(7);
+        let mut c = StdRng::seed_from_u64(7);
+        for _ in 0..10 {
+            assert_eq!(boxed[0].sample(&mut a), Distribution::sample(&p, &mut c));
+        }
+    }
+}
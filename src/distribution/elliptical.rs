@@ -0,0 +1,330 @@
+use crate::distribution::Continuous;
+use crate::fp;
+use crate::statistics::{Max, MeanN, Min, Mode, VarianceN};
+use crate::{Result, StatsError};
+use nalgebra::{Cholesky, DMatrix, DVector};
+use rand::Rng;
+use std::f64;
+
+/// The `RadialGenerator` trait describes the spherical part of an elliptical
+/// distribution, i.e. the distribution of `Z` in `X = μ + L * Z` where `L` is
+/// the Cholesky factor of the scale matrix `Σ`.
+///
+/// A spherical distribution has a density that only depends on the squared
+/// radius `q = transpose(z) * z`, so a radial generator only needs to know
+/// its normalized log density as a function of `q`, how to sample `Z` and
+/// by how much its covariance is inflated relative to the identity.
+pub trait RadialGenerator {
+    /// Returns the log density of the standard spherical distribution in
+    /// `dim` dimensions evaluated at a point with squared radius `q`
+    fn ln_density(&self, q: f64, dim: usize) -> f64;
+
+    /// Samples a standard spherical random vector of length `dim`
+    fn sample_spherical<R: Rng + ?Sized>(&self, dim: usize, rng: &mut R) -> DVector<f64>;
+
+    /// Returns the factor `c` such that the covariance matrix of the
+    /// elliptical distribution is `c * Σ`, or `None` if the covariance
+    /// does not exist
+    fn variance_scale(&self, dim: usize) -> Option<f64>;
+
+    /// Returns whether the mean of the spherical distribution exists
+    fn has_mean(&self) -> bool {
+        true
+    }
+}
+
+/// Implements an [elliptical
+/// distribution](https://en.wikipedia.org/wiki/Elliptical_distribution)
+/// with location `μ`, positive-definite scale matrix `Σ` and a radial
+/// generator `R` that determines the family (e.g. normal, student's T or
+/// Laplace)
+///
+/// # Remarks
+///
+/// The scale matrix is factorized once on construction and the Cholesky
+/// factor is shared between density evaluation and sampling. The scale
+/// matrix is only equal to the covariance matrix for the multivariate
+/// normal distribution.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, Elliptical, GaussianGenerator, MultivariateNormal};
+/// use nalgebra::DVector;
+///
+/// let a = Elliptical::with_generator(vec![0., 0.], vec![1., 0., 0., 1.], GaussianGenerator).unwrap();
+/// let b = MultivariateNormal::new(vec![0., 0.], vec![1., 0., 0., 1.]).unwrap();
+/// let x = DVector::from_vec(vec![1., 1.]);
+/// assert_eq!(a.pdf(&x), b.pdf(&x));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Elliptical<R> {
+    dim: usize,
+    location: DVector<f64>,
+    scale: DMatrix<f64>,
+    scale_chol: DMatrix<f64>,
+    ln_det: f64,
+    generator: R,
+}
+
+impl<R: RadialGenerator> Elliptical<R> {
+    /// Constructs a new elliptical distribution with location `location`,
+    /// scale matrix `scale` (in column-major order) and radial generator
+    /// `generator`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given scale matrix is not `dim * dim`,
+    /// symmetric and positive-definite, where `dim` is the length of
+    /// `location`
+    pub fn with_generator(location: Vec<f64>, scale: Vec<f64>, generator: R) -> Result<Self> {
+        let dim = location.len();
+        // Check that the dimensions match before shaping the scale matrix
+        if scale.len() != dim * dim {
+            return Err(StatsError::BadParams);
+        }
+        let location = DVector::from_vec(location);
+        let scale = DMatrix::from_vec(dim, dim, scale);
+        // Check that the provided scale matrix is symmetric
+        if scale.lower_triangle() != scale.upper_triangle().transpose()
+        // Check that location and scale do not contain NaN
+            || location.iter().any(|f| f.is_nan())
+            || scale.iter().any(|f| f.is_nan())
+        {
+            return Err(StatsError::BadParams);
+        }
+        match Cholesky::new(scale.clone()) {
+            None => Err(StatsError::BadParams),
+            Some(cholesky_decomp) => {
+                let scale_chol = cholesky_decomp.unpack();
+                let ln_det = 2.0
+                    * scale_chol
+                        .diagonal()
+                        .iter()
+                        .map(|x| fp::ln(*x))
+                        .sum::<f64>();
+                Ok(Elliptical {
+                    dim,
+                    location,
+                    scale,
+                    scale_chol,
+                    ln_det,
+                    generator,
+                })
+            }
+        }
+    }
+
+    /// Returns the dimension of the distribution
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Returns the location vector `μ` of the distribution
+    pub fn location(&self) -> &DVector<f64> {
+        &self.location
+    }
+
+    /// Returns the scale matrix `Σ` of the distribution
+    pub fn scale(&self) -> &DMatrix<f64> {
+        &self.scale
+    }
+
+    /// Returns the radial generator of the distribution
+    pub fn generator(&self) -> &R {
+        &self.generator
+    }
+
+    /// Returns the squared Mahalanobis distance of `x` from the location
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// transpose(x - μ) * inv(Σ) * (x - μ)
+    /// ```
+    ///
+    /// computed by forward substitution with the Cholesky factor of `Σ`
+    ///
+    /// # Panics
+    ///
+    /// If `x` does not have the same dimension as the distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::MultivariateNormal;
+    /// use nalgebra::DVector;
+    ///
+    /// let mvn = MultivariateNormal::new(vec![0., 0.], vec![4., 0., 0., 1.]).unwrap();
+    /// assert_eq!(mvn.mahalanobis_squared(&DVector::from_vec(vec![2., 1.])), 2.0);
+    /// ```
+    pub fn mahalanobis_squared(&self, x: &DVector<f64>) -> f64 {
+        let dv = x - &self.location;
+        self.scale_chol
+            .solve_lower_triangular(&dv)
+            .unwrap()
+            .norm_squared()
+    }
+}
+
+impl<R: RadialGenerator> ::rand::distributions::Distribution<DVector<f64>> for Elliptical<R> {
+    /// Samples from the elliptical distribution
+    ///
+    /// # Formula
+    /// L * Z + μ
+    ///
+    /// where `L` is the Cholesky decomposition of the scale matrix,
+    /// `Z` is a sample from the spherical distribution of the radial
+    /// generator, and `μ` is the location vector
+    fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> DVector<f64> {
+        let z = self.generator.sample_spherical(self.dim, rng);
+        (&self.scale_chol * z) + &self.location
+    }
+}
+
+impl<R> Min<DVector<f64>> for Elliptical<R> {
+    /// Returns the minimum value in the domain of the
+    /// elliptical distribution represented by a real vector
+    fn min(&self) -> DVector<f64> {
+        DVector::from_vec(vec![f64::NEG_INFINITY; self.dim])
+    }
+}
+
+impl<R> Max<DVector<f64>> for Elliptical<R> {
+    /// Returns the maximum value in the domain of the
+    /// elliptical distribution represented by a real vector
+    fn max(&self) -> DVector<f64> {
+        DVector::from_vec(vec![f64::INFINITY; self.dim])
+    }
+}
+
+impl<R: RadialGenerator> MeanN<DVector<f64>> for Elliptical<R> {
+    /// Returns the mean of the elliptical distribution
+    ///
+    /// # Remarks
+    ///
+    /// This is the location used to construct the distribution, or `None`
+    /// if the mean of the radial generator does not exist
+    fn mean(&self) -> Option<DVector<f64>> {
+        if self.generator.has_mean() {
+            Some(self.location.clone())
+        } else {
+            None
+        }
+    }
+}
+
+impl<R: RadialGenerator> VarianceN<DMatrix<f64>> for Elliptical<R> {
+    /// Returns the covariance matrix of the elliptical distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// c * Σ
+    /// ```
+    ///
+    /// where `c` is the variance scale of the radial generator and `Σ` the
+    /// scale matrix
+    fn variance(&self) -> Option<DMatrix<f64>> {
+        let c = self.generator.variance_scale(self.dim)?;
+        if c == 1.0 {
+            Some(self.scale.clone())
+        } else {
+            Some(self.scale.scale(c))
+        }
+    }
+}
+
+impl<R> Mode<DVector<f64>> for Elliptical<R> {
+    /// Returns the mode of the elliptical distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ
+    /// ```
+    ///
+    /// where `μ` is the location
+    fn mode(&self) -> DVector<f64> {
+        self.location.clone()
+    }
+}
+
+impl<'a, R: RadialGenerator> Continuous<&'a DVector<f64>, f64> for Elliptical<R> {
+    /// Calculates the probability density function for the elliptical
+    /// distribution at `x`. Equivalent to ln_pdf(x).exp().
+    fn pdf(&self, x: &'a DVector<f64>) -> f64 {
+        fp::exp(self.ln_pdf(x))
+    }
+
+    /// Calculates the log probability density function for the elliptical
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(g(transpose(x - μ) * inv(Σ) * (x - μ))) - ln(det(Σ)) / 2
+    /// ```
+    ///
+    /// where `g` is the spherical density of the radial generator, `μ` is
+    /// the location and `Σ` is the scale matrix
+    fn ln_pdf(&self, x: &'a DVector<f64>) -> f64 {
+        let q = self.mahalanobis_squared(x);
+        self.generator.ln_density(q, self.dim) - 0.5 * self.ln_det
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::distribution::{
+        Continuous, Elliptical, GaussianGenerator, LaplaceGenerator, MultivariateLaplace,
+        MultivariateNormal, MultivariateStudent, StudentsTGenerator,
+    };
+    use crate::StatsError;
+    use nalgebra::DVector;
+
+    // the scale [[2, 0.5], [0.5, 1]] has determinant 1.75 and the point
+    // x = [0, 1] is at squared mahalanobis distance 44 / 7 from [1, -1]
+    const LOCATION: [f64; 2] = [1.0, -1.0];
+    const SCALE: [f64; 4] = [2.0, 0.5, 0.5, 1.0];
+
+    #[test]
+    fn test_student_ln_pdf() {
+        let generator = StudentsTGenerator::new(5.0).unwrap();
+        let t = Elliptical::with_generator(LOCATION.to_vec(), SCALE.to_vec(), generator).unwrap();
+        let x = DVector::from_vec(vec![0.0, 1.0]);
+        assert_almost_eq!(t.mahalanobis_squared(&x), 44.0 / 7.0, 1e-14);
+        // mpmath: lnΓ((ν + k) / 2) - lnΓ(ν / 2) - k / 2 ln(νπ) - ln(det Σ) / 2 - (ν + k) / 2 ln(1 + q / ν)
+        assert_almost_eq!(t.ln_pdf(&x), -4.96703422879868417733, 1e-14);
+        let u = MultivariateStudent::new(LOCATION.to_vec(), SCALE.to_vec(), 5.0).unwrap();
+        assert_eq!(t.ln_pdf(&x), u.ln_pdf(&x));
+    }
+
+    #[test]
+    fn test_laplace_ln_pdf() {
+        let l = Elliptical::with_generator(LOCATION.to_vec(), SCALE.to_vec(), LaplaceGenerator).unwrap();
+        let x = DVector::from_vec(vec![0.0, 1.0]);
+        // mpmath: -ln(2π) - sqrt(q) - ln(det Σ) / 2, as Γ(k / 2) = Γ(k) = 1 for k = 2
+        assert_almost_eq!(l.ln_pdf(&x), -4.62481764248909170110, 1e-14);
+        let m = MultivariateLaplace::new(LOCATION.to_vec(), SCALE.to_vec()).unwrap();
+        assert_eq!(l.ln_pdf(&x), m.ln_pdf(&x));
+    }
+
+    #[test]
+    fn test_bad_scale() {
+        let bad = |scale: Vec<f64>| {
+            Elliptical::with_generator(LOCATION.to_vec(), scale, GaussianGenerator).unwrap_err()
+        };
+        // symmetric but not positive-definite
+        assert!(matches!(bad(vec![1.0, 2.0, 2.0, 1.0]), StatsError::BadParams));
+        assert!(matches!(bad(vec![1.0, 0.0, 0.0, 0.0]), StatsError::BadParams));
+        // not symmetric
+        assert!(matches!(bad(vec![2.0, 0.5, 0.0, 1.0]), StatsError::BadParams));
+        assert!(matches!(bad(vec![2.0, f64::NAN, f64::NAN, 1.0]), StatsError::BadParams));
+        // a scale of the wrong size is rejected rather than reshaped
+        assert!(matches!(bad(vec![2.0, 0.5, 1.0]), StatsError::BadParams));
+        assert!(matches!(bad(vec![1.0; 9]), StatsError::BadParams));
+        assert!(MultivariateNormal::new(vec![0.0; 3], vec![1.0, 0.0, 0.0, 1.0]).is_err());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::distribution::{Continuous, ContinuousCDF, Uniform};
+use crate::distribution::{Continuous, ContinuousCDF};
+use crate::fp;
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use ::num_traits::float::Float;
@@ -23,31 +24,70 @@ impl<T: PartialOrd> Ord for NonNAN<T> {
     }
 }
 
+/// Selects how `Empirical::inverse_cdf` interpolates between the data
+/// points
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EmpiricalInterpolation {
+    /// The generalized inverse `inf { x | F(x) >= p }` of the step cdf, the
+    /// smallest data point whose cumulative weight reaches `p`
+    Step,
+    /// The average of `inf { x | F(x) >= p }` and `inf { x | F(x) > p }`,
+    /// which differ only where `p` is exactly the cumulative weight of a
+    /// data point; this generalizes the sample median to all probabilities
+    Midpoint,
+    /// Linear interpolation between the data points placed at the middle
+    /// of their jumps of the cdf, i.e. at `(k - 1/2) / n` for the `k`-th of
+    /// `n` unweighted points, and constant beyond the outermost ones
+    Linear,
+}
+
 /// Implements the [Empirical
 /// Distribution](https://en.wikipedia.org/wiki/Empirical_distribution_function)
+/// of a sample whose observations may be weighted
+///
+/// # Remarks
+///
+/// Observations added by `add` have unit weight. The variance is the
+/// unbiased weighted variance for reliability weights, which is the
+/// sample variance for unit weights, and the confidence bands of the cdf
+/// use the effective sample size `(Σ w)² / Σ w²` of Kish in place of the
+/// number of observations.
 ///
 /// # Examples
 ///
 /// ```
-/// use statrs::distribution::{Continuous, Empirical};
+/// use statrs::distribution::{Continuous, ContinuousCDF, Empirical, EmpiricalInterpolation};
 /// use statrs::statistics::Distribution;
 ///
 /// let samples = vec![0.0, 5.0, 10.0];
 ///
 /// let empirical = Empirical::from_vec(samples);
 /// assert_eq!(empirical.mean().unwrap(), 5.0);
+///
+/// let weighted = Empirical::from_weighted(&[0.0, 5.0, 10.0], &[1.0, 2.0, 1.0])
+///     .unwrap()
+///     .with_interpolation(EmpiricalInterpolation::Linear);
+/// assert_eq!(weighted.cdf(5.0), 0.75);
+/// assert_eq!(weighted.inverse_cdf(0.5), 5.0);
+/// let (lower, upper) = weighted.cdf_band(5.0, 0.95).unwrap();
+/// assert!(lower < 0.75 && upper == 1.0);
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct Empirical {
+    // the total weight of the observations
     sum: f64,
+    // the sum of the squared weights of the observations
+    sum_sq: f64,
     mean_and_var: Option<(f64, f64)>,
-    // keys are data points, values are number of data points with equal value
-    data: BTreeMap<NonNAN<f64>, u64>,
+    // keys are data points, values are the number of data points with
+    // equal value and the sum of their weights
+    data: BTreeMap<NonNAN<f64>, (u64, f64)>,
+    interpolation: EmpiricalInterpolation,
 }
 
 impl Empirical {
-    /// Constructs a new discrete uniform distribution with a minimum value
-    /// of `min` and a maximum value of `max`.
+    /// Constructs a new empirical distribution without samples, whose
+    /// inverse cdf is the step function
     ///
     /// # Examples
     ///
@@ -61,8 +101,10 @@ impl Empirical {
     pub fn new() -> Result<Empirical> {
         Ok(Empirical {
             sum: 0.,
+            sum_sq: 0.,
             mean_and_var: None,
             data: BTreeMap::new(),
+            interpolation: EmpiricalInterpolation::Step,
         })
     }
     pub fn from_vec(src: Vec<f64>) -> Empirical {
@@ -72,86 +114,262 @@ impl Empirical {
         }
         empirical
     }
+    /// Constructs the empirical distribution of `data` with the
+    /// observation `data[i]` weighted by `weights[i]`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` and `weights` differ in length or if a
+    /// weight is not positive and finite
+    pub fn from_weighted(data: &[f64], weights: &[f64]) -> Result<Empirical> {
+        if data.len() != weights.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        let mut empirical = Empirical::new().unwrap();
+        for (&x, &w) in data.iter().zip(weights) {
+            empirical.add_weighted(x, w)?;
+        }
+        Ok(empirical)
+    }
+    /// Replaces the interpolation of the inverse cdf
+    pub fn with_interpolation(self, interpolation: EmpiricalInterpolation) -> Empirical {
+        Empirical {
+            interpolation,
+            ..self
+        }
+    }
+    /// Returns the interpolation of the inverse cdf
+    pub fn interpolation(&self) -> EmpiricalInterpolation {
+        self.interpolation
+    }
+    /// Adds an observation of `data_point` with unit weight, ignoring a NaN
+    /// `data_point`
     pub fn add(&mut self, data_point: f64) {
+        self.add_weighted(data_point, 1.0).unwrap();
+    }
+    /// Adds an observation of `data_point` with weight `weight`, ignoring
+    /// a NaN `data_point` like `add`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `weight` is not positive and finite
+    pub fn add_weighted(&mut self, data_point: f64, weight: f64) -> Result<()> {
+        if !(weight.is_finite() && weight > 0.0) {
+            return Err(StatsError::ArgMustBePositive("weight"));
+        }
         if !data_point.is_nan() {
-            self.sum += 1.;
+            self.sum += weight;
+            self.sum_sq += weight * weight;
             match self.mean_and_var {
                 Some((mean, var)) => {
                     let sum = self.sum;
-                    let var = var + (sum - 1.) * (data_point - mean) * (data_point - mean) / sum;
-                    let mean = mean + (data_point - mean) / sum;
+                    let var = var
+                        + weight * (sum - weight) * (data_point - mean) * (data_point - mean) / sum;
+                    let mean = mean + weight * (data_point - mean) / sum;
                     self.mean_and_var = Some((mean, var));
                 }
                 None => {
                     self.mean_and_var = Some((data_point, 0.));
                 }
             }
-            *self.data.entry(NonNAN(data_point)).or_insert(0) += 1;
+            let entry = self.data.entry(NonNAN(data_point)).or_insert((0, 0.));
+            entry.0 += 1;
+            entry.1 += weight;
         }
+        Ok(())
     }
+    /// Removes an observation of `data_point` which was added without a
+    /// weight
     pub fn remove(&mut self, data_point: f64) {
+        self.remove_weighted(data_point, 1.0).unwrap();
+    }
+    /// Removes an observation of `data_point` which was added with weight
+    /// `weight`, doing nothing if there is no observation of `data_point`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `weight` is not positive and finite
+    pub fn remove_weighted(&mut self, data_point: f64, weight: f64) -> Result<()> {
+        if !(weight.is_finite() && weight > 0.0) {
+            return Err(StatsError::ArgMustBePositive("weight"));
+        }
         if !data_point.is_nan() {
-            if let (Some(val), Some((mean, var))) =
+            if let (Some((count, total)), Some((mean, var))) =
                 (self.data.remove(&NonNAN(data_point)), self.mean_and_var)
             {
-                if val == 1 && self.data.is_empty() {
+                if count == 1 && self.data.is_empty() {
                     self.mean_and_var = None;
                     self.sum = 0.;
-                    return;
+                    self.sum_sq = 0.;
+                    return Ok(());
                 };
                 // reset mean and var
-                let mean = (self.sum * mean - data_point) / (self.sum - 1.);
-                let var =
-                    var - (self.sum - 1.) * (data_point - mean) * (data_point - mean) / self.sum;
-                self.sum -= 1.;
-                if val != 1 {
-                    self.data.insert(NonNAN(data_point), val - 1);
+                let mean = (self.sum * mean - weight * data_point) / (self.sum - weight);
+                let var = var
+                    - weight * (self.sum - weight) * (data_point - mean) * (data_point - mean)
+                        / self.sum;
+                self.sum -= weight;
+                self.sum_sq -= weight * weight;
+                if count != 1 {
+                    self.data
+                        .insert(NonNAN(data_point), (count - 1, total - weight));
                 };
                 self.mean_and_var = Some((mean, var));
             }
         }
+        Ok(())
+    }
+    // Returns the distinct data points in increasing order along with the
+    // number of times each was added.
+    pub(crate) fn counts(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        self.data.iter().map(|(key, &(count, _))| (key.0, count))
+    }
+    // Returns the distinct data points in increasing order along with the
+    // sum of their weights.
+    fn weights(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.data.iter().map(|(key, &(_, weight))| (key.0, weight))
+    }
+    /// Returns the total weight of the observations, their number if all
+    /// were added without weights
+    pub fn total_weight(&self) -> f64 {
+        self.sum
+    }
+    /// Returns the effective sample size `(Σ w)² / Σ w²` of Kish, the
+    /// number of observations if all were added without weights
+    pub fn effective_sample_size(&self) -> f64 {
+        if self.sum_sq > 0.0 {
+            self.sum * self.sum / self.sum_sq
+        } else {
+            0.0
+        }
+    }
+    /// Returns the half width `ε = sqrt(ln(2 / (1 - confidence)) / (2 n))`
+    /// of the confidence band of the cdf by the
+    /// [Dvoretzky-Kiefer-Wolfowitz
+    /// inequality](https://en.wikipedia.org/wiki/Dvoretzky%E2%80%93Kiefer%E2%80%93Wolfowitz_inequality),
+    /// which contains the true cdf everywhere with probability at least
+    /// `confidence` for `n` independent observations. `n` is the effective
+    /// sample size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `confidence` is not in `(0, 1)` or if there are
+    /// no samples
+    pub fn dkw_epsilon(&self, confidence: f64) -> Result<f64> {
+        if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+        }
+        if self.data.is_empty() {
+            return Err(StatsError::BadParams);
+        }
+        let n = self.effective_sample_size();
+        Ok((fp::ln(2.0 / (1.0 - confidence)) / (2.0 * n)).sqrt())
+    }
+    /// Returns the lower and upper bounds at `x` of the
+    /// Dvoretzky-Kiefer-Wolfowitz confidence band of the cdf, the cdf
+    /// less and plus `dkw_epsilon(confidence)` clamped to `[0, 1]`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `confidence` is not in `(0, 1)` or if there are
+    /// no samples
+    pub fn cdf_band(&self, x: f64, confidence: f64) -> Result<(f64, f64)> {
+        let epsilon = self.dkw_epsilon(confidence)?;
+        let cdf = self.cdf(x);
+        Ok(((cdf - epsilon).max(0.0), (cdf + epsilon).min(1.0)))
+    }
+    /// Draws `n` observations with replacement, each with probability
+    /// proportional to its weight, in time `O(m + n log m)` for `m`
+    /// distinct data points
+    ///
+    /// # Panics
+    ///
+    /// If `n` is positive and the number of samples is zero
+    pub fn sample_with_replacement<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Vec<f64> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut points = Vec::with_capacity(self.data.len());
+        let mut cumulative = Vec::with_capacity(self.data.len());
+        let mut sum = 0.0;
+        for (x, w) in self.weights() {
+            sum += w;
+            points.push(x);
+            cumulative.push(sum);
+        }
+        let last = points.len() - 1;
+        (0..n)
+            .map(|_| {
+                let u = rng.gen::<f64>() * sum;
+                points[cumulative.partition_point(|&c| c <= u).min(last)]
+            })
+            .collect()
+    }
+    // Returns `inf { x | F(x) >= p }` if `strict` is false and
+    // `inf { x | F(x) > p }` otherwise, comparing cumulative weights with
+    // `p` times the total weight so that unit weights are compared exactly.
+    fn step_inverse(&self, p: f64, strict: bool) -> f64 {
+        let target = p * self.sum;
+        let mut cumulative = 0.0;
+        for (x, w) in self.weights() {
+            cumulative += w;
+            if cumulative > target || (!strict && cumulative >= target) {
+                return x;
+            }
+        }
+        self.max()
+    }
+    // Interpolates linearly between the data points placed at the middle
+    // of their jumps of the cdf.
+    fn linear_inverse(&self, p: f64) -> f64 {
+        let target = p * self.sum;
+        let mut cumulative = 0.0;
+        let mut previous: Option<(f64, f64)> = None;
+        for (x, w) in self.weights() {
+            let position = cumulative + w / 2.0;
+            if position >= target {
+                return match previous {
+                    Some((x0, p0)) => x0 + (x - x0) * (target - p0) / (position - p0),
+                    None => x,
+                };
+            }
+            previous = Some((x, position));
+            cumulative += w;
+        }
+        self.max()
     }
-    // Due to issues with rounding and floating-point accuracy the default
-    // implementation may be ill-behaved.
-    // Specialized inverse cdfs should be used whenever possible.
-    // Performs a binary search on the domain of `cdf` to obtain an approximation
-    // of `F^-1(p) := inf { x | F(x) >= p }`. Needless to say, performance may
-    // may be lacking.
-    // This function is identical to the default method implementation in the
-    // `ContinuousCDF` trait and is used to implement the rand trait `Distribution`.
-    fn __inverse_cdf(&self, p: f64) -> f64 {
-        if p == 0.0 {
-            return self.min();
-        };
-        if p == 1.0 {
-            return self.max();
-        };
-        let mut high = 2.0;
-        let mut low = -high;
-        while self.cdf(low) > p {
-            low = low + low;
-        }
-        while self.cdf(high) < p {
-            high = high + high;
-        }
-        let mut i = 16;
-        while i != 0 {
-            let mid = (high + low) / 2.0;
-            if self.cdf(mid) >= p {
-                high = mid;
-            } else {
-                low = mid;
+    // Returns the inverse cdf at `p` by the given interpolation.
+    fn quantile(&self, p: f64, interpolation: EmpiricalInterpolation) -> f64 {
+        if self.data.is_empty() {
+            panic!("inverse cdf of an empirical distribution without samples");
+        }
+        match interpolation {
+            EmpiricalInterpolation::Step => self.step_inverse(p, false),
+            EmpiricalInterpolation::Midpoint => {
+                (self.step_inverse(p, false) + self.step_inverse(p, true)) / 2.0
             }
-            i -= 1;
+            EmpiricalInterpolation::Linear => self.linear_inverse(p),
         }
-        (high + low) / 2.0
     }
 }
 
 impl ::rand::distributions::Distribution<f64> for Empirical {
+    /// Draws an observation, each with probability proportional to its
+    /// weight
+    ///
+    /// # Panics
+    ///
+    /// If the number of samples is zero
     fn sample<R: ?Sized + Rng>(&self, rng: &mut R) -> f64 {
-        let uniform = Uniform::new(0.0, 1.0).unwrap();
-        self.__inverse_cdf(uniform.sample(rng))
+        let u = rng.gen::<f64>() * self.sum;
+        let mut cumulative = 0.0;
+        for (x, w) in self.weights() {
+            cumulative += w;
+            if cumulative > u {
+                return x;
+            }
+        }
+        self.max()
     }
 }
 
@@ -174,20 +392,44 @@ impl Distribution<f64> for Empirical {
         self.mean_and_var.map(|(mean, _)| mean)
     }
     fn variance(&self) -> Option<f64> {
-        self.mean_and_var.map(|(_, var)| var / (self.sum - 1.))
+        self.mean_and_var
+            .map(|(_, var)| var / (self.sum - self.sum_sq / self.sum))
+    }
+}
+
+/// Panics if number of samples is zero
+impl Median<f64> for Empirical {
+    /// Returns the sample median, i.e. the middle data point or the average
+    /// of the two middle data points if the number of samples is even, or
+    /// the weighted median if the samples are weighted
+    fn median(&self) -> f64 {
+        self.quantile(0.5, EmpiricalInterpolation::Midpoint)
     }
 }
 
 impl ContinuousCDF<f64, f64> for Empirical {
     fn cdf(&self, x: f64) -> f64 {
-        let mut sum = 0;
-        for (keys, values) in &self.data {
-            if keys.0 > x {
-                return sum as f64 / self.sum;
+        let mut sum = 0.;
+        for (point, weight) in self.weights() {
+            if point > x {
+                return sum / self.sum;
             }
-            sum += values;
+            sum += weight;
+        }
+        sum / self.sum
+    }
+
+    /// Calculates the inverse cdf at `p` by the interpolation of the
+    /// distribution
+    ///
+    /// # Panics
+    ///
+    /// If `p` is not in `[0, 1]` or if the number of samples is zero
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
         }
-        sum as f64 / self.sum
+        self.quantile(p, self.interpolation)
     }
 }
 
@@ -221,4 +463,139 @@ mod tests {
         // due to the mean and variance being calculated in a streaming way
         assert_eq!(unchanged, empirical);
     }
+
+    #[test]
+    fn test_median() {
+        let mut empirical = Empirical::from_vec(vec![3.0, 1.0, 2.0]);
+        assert_eq!(empirical.median(), 2.0);
+        empirical.add(10.0);
+        assert_eq!(empirical.median(), 2.5);
+        empirical.add(2.0);
+        empirical.add(2.0);
+        assert_eq!(empirical.median(), 2.0);
+        let single = Empirical::from_vec(vec![4.0]);
+        assert_eq!(single.median(), 4.0);
+    }
+
+    #[test]
+    fn test_weighted() {
+        let mut weighted =
+            Empirical::from_weighted(&[3.0, 1.0, 2.0, 1.0], &[0.5, 1.0, 2.5, 1.0]).unwrap();
+        assert_eq!(weighted.total_weight(), 5.0);
+        assert_eq!(weighted.cdf(0.5), 0.0);
+        assert_eq!(weighted.cdf(1.0), 0.4);
+        assert_eq!(weighted.cdf(2.9), 0.9);
+        assert_eq!(weighted.cdf(3.0), 1.0);
+        // the weighted mean and the unbiased variance for reliability weights
+        let mean = (1.5 + 1.0 + 5.0 + 1.0) / 5.0;
+        let m2 = 0.5 * (3.0 - mean) * (3.0 - mean)
+            + 2.0 * (1.0 - mean) * (1.0 - mean)
+            + 2.5 * (2.0 - mean) * (2.0 - mean);
+        let sum_sq = 0.25 + 1.0 + 6.25 + 1.0;
+        assert_almost_eq!(weighted.mean().unwrap(), mean, 1e-15);
+        assert_almost_eq!(
+            weighted.variance().unwrap(),
+            m2 / (5.0 - sum_sq / 5.0),
+            1e-15
+        );
+        assert_almost_eq!(weighted.effective_sample_size(), 25.0 / sum_sq, 1e-15);
+        assert_eq!(weighted.median(), 2.0);
+        // unit weights agree with unweighted observations
+        let unit = Empirical::from_weighted(&[3.0, 1.0, 2.0], &[1.0, 1.0, 1.0]).unwrap();
+        assert_eq!(unit, Empirical::from_vec(vec![3.0, 1.0, 2.0]));
+        assert_eq!(unit.effective_sample_size(), 3.0);
+        let unchanged = weighted.clone();
+        weighted.add_weighted(2.0, 0.25).unwrap();
+        weighted.remove_weighted(2.0, 0.25).unwrap();
+        assert_almost_eq!(weighted.mean().unwrap(), unchanged.mean().unwrap(), 1e-15);
+        assert_almost_eq!(
+            weighted.variance().unwrap(),
+            unchanged.variance().unwrap(),
+            1e-14
+        );
+        assert!(weighted.add_weighted(1.0, 0.0).is_err());
+        assert!(weighted.add_weighted(1.0, f64::NAN).is_err());
+        assert!(weighted.remove_weighted(1.0, -1.0).is_err());
+        assert!(Empirical::from_weighted(&[1.0], &[1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let empirical = Empirical::from_vec(vec![4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(empirical.interpolation(), EmpiricalInterpolation::Step);
+        assert_eq!(empirical.inverse_cdf(0.0), 1.0);
+        assert_eq!(empirical.inverse_cdf(0.25), 1.0);
+        assert_eq!(empirical.inverse_cdf(0.3), 2.0);
+        assert_eq!(empirical.inverse_cdf(0.5), 2.0);
+        assert_eq!(empirical.inverse_cdf(1.0), 4.0);
+        let midpoint = empirical
+            .clone()
+            .with_interpolation(EmpiricalInterpolation::Midpoint);
+        assert_eq!(midpoint.inverse_cdf(0.25), 1.5);
+        assert_eq!(midpoint.inverse_cdf(0.3), 2.0);
+        assert_eq!(midpoint.inverse_cdf(0.5), 2.5);
+        assert_eq!(midpoint.inverse_cdf(1.0), 4.0);
+        // the points sit at (k - 1/2) / 4, as in R's quantile type 5
+        let linear = empirical.with_interpolation(EmpiricalInterpolation::Linear);
+        assert_eq!(linear.inverse_cdf(0.1), 1.0);
+        assert_eq!(linear.inverse_cdf(0.125), 1.0);
+        assert_eq!(linear.inverse_cdf(0.25), 1.5);
+        assert_eq!(linear.inverse_cdf(0.5), 2.5);
+        assert_almost_eq!(linear.inverse_cdf(0.8), 3.7, 1e-15);
+        assert_eq!(linear.inverse_cdf(0.9), 4.0);
+        // a point of weight 2 is the middle of a jump of one half
+        let weighted = Empirical::from_weighted(&[0.0, 1.0, 2.0], &[1.0, 2.0, 1.0])
+            .unwrap()
+            .with_interpolation(EmpiricalInterpolation::Linear);
+        assert_eq!(weighted.inverse_cdf(0.5), 1.0);
+        assert_almost_eq!(weighted.inverse_cdf(0.25), 1.0 / 3.0, 1e-15);
+        assert_eq!(weighted.inverse_cdf(0.875), 2.0);
+    }
+
+    #[test]
+    fn test_dkw() {
+        let empirical = Empirical::from_vec((0..100).map(|i| i as f64).collect());
+        let epsilon = empirical.dkw_epsilon(0.95).unwrap();
+        assert_almost_eq!(epsilon, 0.13581015157406195, 1e-15);
+        assert_eq!(
+            empirical.cdf_band(49.0, 0.95).unwrap(),
+            (0.5 - epsilon, 0.5 + epsilon)
+        );
+        assert_eq!(empirical.cdf_band(-1.0, 0.95).unwrap(), (0.0, epsilon));
+        assert_eq!(
+            empirical.cdf_band(99.0, 0.95).unwrap(),
+            (1.0 - epsilon, 1.0)
+        );
+        // weights which halve the effective sample size widen the band
+        let weights: Vec<f64> = (0..100).map(|i| if i < 50 { 1.0 } else { 1e-9 }).collect();
+        let weighted =
+            Empirical::from_weighted(&(0..100).map(|i| i as f64).collect::<Vec<_>>(), &weights)
+                .unwrap();
+        assert_almost_eq!(
+            weighted.dkw_epsilon(0.95).unwrap(),
+            epsilon * 2f64.sqrt(),
+            1e-6
+        );
+        assert!(empirical.dkw_epsilon(1.0).is_err());
+        assert!(empirical.dkw_epsilon(f64::NAN).is_err());
+        assert!(Empirical::new().unwrap().dkw_epsilon(0.95).is_err());
+    }
+
+    #[test]
+    fn test_sample() {
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(4);
+        let weighted = Empirical::from_weighted(&[1.0, 2.0, 3.0], &[1.0, 2.0, 7.0]).unwrap();
+        let x = weighted.sample_with_replacement(20000, &mut rng);
+        let y: Vec<f64> = (0..20000).map(|_| weighted.sample(&mut rng)).collect();
+        for sample in &[x, y] {
+            for &(point, p) in &[(1.0, 0.1), (2.0, 0.2), (3.0, 0.7)] {
+                let fraction = sample.iter().filter(|&&x| x == point).count() as f64 / 20000.0;
+                assert!((fraction - p).abs() < 0.01);
+            }
+        }
+        assert!(weighted.sample_with_replacement(0, &mut rng).is_empty());
+    }
 }
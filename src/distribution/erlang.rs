@@ -1,6 +1,10 @@
-use crate::distribution::{Continuous, ContinuousCDF, Gamma};
+use crate::distribution::{
+    integer_param, CharacteristicFunction, Continuous, ContinuousCDF, Gamma, GammaSampler,
+    Parameterized, SampleBatch,
+};
 use crate::statistics::*;
-use crate::Result;
+use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 
 /// Implements the [Erlang](https://en.wikipedia.org/wiki/Erlang_distribution)
@@ -49,6 +53,24 @@ impl Erlang {
         Gamma::new(shape as f64, rate).map(|g| Erlang { g })
     }
 
+    /// Constructs a new erlang distribution from parameters already known to be
+    /// valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(shape: u64, rate: f64) -> Erlang {
+        debug_assert!(
+            Erlang::new(shape, rate).is_ok(),
+            "invalid erlang parameters"
+        );
+        Erlang {
+            g: Gamma::new_unchecked(shape as f64, rate),
+        }
+    }
+
     /// Returns the shape (k) of the erlang distribution
     ///
     /// # Examples
@@ -84,6 +106,14 @@ impl ::rand::distributions::Distribution<f64> for Erlang {
     }
 }
 
+impl SampleBatch<f64> for Erlang {
+    type Sampler = GammaSampler;
+
+    fn sampler(&self) -> GammaSampler {
+        self.g.sampler()
+    }
+}
+
 impl ContinuousCDF<f64, f64> for Erlang {
     /// Calculates the cumulative distribution function for the erlang
     /// distribution
@@ -100,6 +130,13 @@ impl ContinuousCDF<f64, f64> for Erlang {
     fn cdf(&self, x: f64) -> f64 {
         self.g.cdf(x)
     }
+
+    /// Calculates the survival function for the
+    /// erlang distribution at `x` as the survival function of the
+    /// underlying gamma distribution
+    fn sf(&self, x: f64) -> f64 {
+        self.g.sf(x)
+    }
 }
 
 impl Min<f64> for Erlang {
@@ -187,6 +224,45 @@ impl Distribution<f64> for Erlang {
     fn skewness(&self) -> Option<f64> {
         self.g.skewness()
     }
+    /// Returns the raw moment of order `k` of the erlang distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// prod_{i = 0}^{k - 1} (k + i) / λ
+    /// ```
+    ///
+    /// where `k` is the shape and `λ` is the rate
+    fn moment(&self, k: u32) -> Option<f64> {
+        self.g.moment(k)
+    }
+    /// Returns the cumulant of order `k` of the erlang distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// κ_k = n * (k - 1)! / λ^k for k > 0
+    /// ```
+    ///
+    /// where `n` is the shape and `λ` is the rate
+    fn cumulant(&self, k: u32) -> Option<f64> {
+        self.g.cumulant(k)
+    }
+}
+
+impl Median<f64> for Erlang {
+    /// Returns the median of the erlang distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(1 / 2)
+    /// ```
+    ///
+    /// where `F` is the cdf
+    fn median(&self) -> f64 {
+        self.g.median()
+    }
 }
 
 impl Mode<Option<f64>> for Erlang {
@@ -250,10 +326,44 @@ impl Continuous<f64, f64> for Erlang {
     }
 }
 
+impl CharacteristicFunction<f64, f64> for Erlang {
+    /// Calculates the characteristic function for the erlang
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 - it / λ)^(-k)
+    /// ```
+    ///
+    /// where `k` is the shape and `λ` is the rate
+    fn cf(&self, t: f64) -> Complex<f64> {
+        self.g.cf(t)
+    }
+}
+
+impl Parameterized for Erlang {
+    fn param_names() -> &'static [&'static str] {
+        &["shape", "rate"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.shape() as f64, self.rate()]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Erlang> {
+        match *params {
+            [shape, rate] => Erlang::new(integer_param(shape)?, rate),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
-    use crate::distribution::Erlang;
+    use crate::statistics::{Distribution, Median};
+    use crate::distribution::{CharacteristicFunction, Erlang};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -297,4 +407,23 @@ mod tests {
         test::check_continuous_distribution(&try_create(2, 1.5), 0.0, 20.0);
         test::check_continuous_distribution(&try_create(3, 0.5), 0.0, 20.0);
     }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(3, 0.5).cf(0.4);
+        assert_almost_eq!(phi.re, -0.208572133312053, 1e-14);
+        assert_almost_eq!(phi.im, 0.42802629097082157, 1e-14);
+    }
+
+    #[test]
+    fn test_moment() {
+        let n = try_create(3, 0.5);
+        assert_eq!(n.moment(2).unwrap(), 48.0);
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(try_create(1, 2.0).median(), std::f64::consts::LN_2 / 2.0);
+        assert_almost_eq!(try_create(5, 1.5).median(), 3.1139392551973224802084500, 1e-14);
+    }
 }
@@ -1,6 +1,11 @@
-use crate::distribution::{ziggurat, Continuous, ContinuousCDF};
+use crate::distribution::{
+    fit, ziggurat, CharacteristicFunction, Continuous, ContinuousCDF, Fit, KullbackLeibler,
+    Parameterized,
+};
+use crate::fp;
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 use std::f64;
 
@@ -52,6 +57,19 @@ impl Exp {
         }
     }
 
+    /// Constructs a new exponential distribution from parameters already known
+    /// to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(rate: f64) -> Exp {
+        debug_assert!(Exp::new(rate).is_ok(), "invalid exponential parameters");
+        Exp { rate }
+    }
+
     /// Returns the rate of the exponential distribution
     ///
     /// # Examples
@@ -88,9 +106,99 @@ impl ContinuousCDF<f64, f64> for Exp {
         if x < 0.0 {
             0.0
         } else {
-            1.0 - (-self.rate * x).exp()
+            1.0 - fp::exp(-self.rate * x)
+        }
+    }
+
+    /// Calculates the log cumulative distribution function for the
+    /// exponential distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(1 - e^(-λ * x))
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn ln_cdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            fp::ln(-fp::exp_m1(-self.rate * x))
         }
     }
+
+    /// Calculates the survival function for the
+    /// exponential distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(-λ * x)
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn sf(&self, x: f64) -> f64 {
+        fp::exp(self.ln_sf(x))
+    }
+
+    /// Calculates the log survival function for the
+    /// exponential distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -λ * x
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn ln_sf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            0.0
+        } else {
+            -self.rate * x
+        }
+    }
+
+    /// Calculates the probability that an exponentially distributed random
+    /// variable falls between `a` and `b`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(-λ * a) * (1 - e^(-λ * (b - a)))
+    /// ```
+    ///
+    /// where `λ` is the rate and `a` is clamped to be non-negative
+    fn prob_between(&self, a: f64, b: f64) -> f64 {
+        let a = a.max(0.0);
+        if b <= a {
+            0.0
+        } else {
+            -fp::exp(-self.rate * a) * fp::exp_m1(-self.rate * (b - a))
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// exponential distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -ln(1 - p) / λ
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        -fp::ln_1p(-p) / self.rate
+    }
 }
 
 impl Min<f64> for Exp {
@@ -156,7 +264,7 @@ impl Distribution<f64> for Exp {
     ///
     /// where `λ` is the rate
     fn entropy(&self) -> Option<f64> {
-        Some(1.0 - self.rate.ln())
+        Some(1.0 - fp::ln(self.rate))
     }
     /// Returns the skewness of the exponential distribution
     ///
@@ -168,6 +276,34 @@ impl Distribution<f64> for Exp {
     fn skewness(&self) -> Option<f64> {
         Some(2.0)
     }
+    /// Returns the raw moment of order `k` of the exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// k! / λ^k
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn moment(&self, k: u32) -> Option<f64> {
+        Some((1..=k).map(|i| i as f64 / self.rate).product())
+    }
+    /// Returns the cumulant of order `k` of the exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// κ_k = (k - 1)! / λ^k for k > 0
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn cumulant(&self, k: u32) -> Option<f64> {
+        if k == 0 {
+            return Some(0.0);
+        }
+        let factorial: f64 = (1..k).map(|i| i as f64 / self.rate).product();
+        Some(factorial / self.rate)
+    }
 }
 
 impl Median<f64> for Exp {
@@ -213,7 +349,7 @@ impl Continuous<f64, f64> for Exp {
         if x < 0.0 {
             0.0
         } else {
-            self.rate * (-self.rate * x).exp()
+            self.rate * fp::exp(-self.rate * x)
         }
     }
 
@@ -231,17 +367,81 @@ impl Continuous<f64, f64> for Exp {
         if x < 0.0 {
             f64::NEG_INFINITY
         } else {
-            self.rate.ln() - self.rate * x
+            fp::ln(self.rate) - self.rate * x
+        }
+    }
+}
+
+impl CharacteristicFunction<f64, f64> for Exp {
+    /// Calculates the characteristic function for the exponential
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// λ / (λ - it)
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn cf(&self, t: f64) -> Complex<f64> {
+        Complex::new(self.rate, 0.0) / Complex::new(self.rate, -t)
+    }
+}
+
+impl KullbackLeibler for Exp {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// λ_q / λ_p - 1 - ln(λ_q / λ_p)
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn kl_divergence(&self, other: &Exp) -> f64 {
+        let r = other.rate / self.rate;
+        r - 1.0 - fp::ln(r)
+    }
+}
+
+impl Parameterized for Exp {
+    fn param_names() -> &'static [&'static str] {
+        &["rate"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.rate]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Exp> {
+        match *params {
+            [rate] => Exp::new(rate),
+            _ => Err(StatsError::ContainersMustBeSameLength),
         }
     }
 }
 
+impl Fit for Exp {
+    type Sample = f64;
+
+    /// Fits the rate as the reciprocal of the sample mean
+    fn fit_mle(data: &[f64]) -> Result<Exp> {
+        fit::check_sample(data, |x| x >= 0.0)?;
+        let mean = fit::mean(data);
+        if mean == 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        Exp::new(1.0 / mean)
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use std::f64;
     use crate::statistics::*;
-    use crate::distribution::{ContinuousCDF, Continuous, Exp};
+    use crate::distribution::{CharacteristicFunction, ContinuousCDF, Continuous, Exp};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -439,6 +639,49 @@ mod tests {
         test_case(f64::INFINITY, 1.0, cdf(f64::INFINITY));
     }
 
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Exp| x.inverse_cdf(arg);
+        test_case(1.0, 0.0, inverse_cdf(0.0));
+        test_almost(2.0, 0.17833747196936617, 1e-15, inverse_cdf(0.3));
+        test_almost(0.5, 13.815510557964272, 1e-12, inverse_cdf(0.999));
+        test_case(1.0, f64::INFINITY, inverse_cdf(1.0));
+        // accurate for tiny probabilities
+        test_almost(1.0, 1e-20, 1e-35, inverse_cdf(1e-20));
+        let n = Exp::new(1.5).unwrap();
+        for &p in [0.01, 0.25, 0.5, 0.75, 0.99].iter() {
+            assert_almost_eq!(n.cdf(n.inverse_cdf(p)), p, 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_sf() {
+        let sf = |arg: f64| move |x: Exp| x.sf(arg);
+        test_case(1.0, 1.0, sf(-1.0));
+        test_case(1.0, 1.0, sf(0.0));
+        test_almost(1.0, 0.9048374180359595, 1e-16, sf(0.1));
+        test_case(1.0, (-50f64).exp(), sf(50.0));
+        test_case(0.5, -400.0, |x| x.ln_sf(800.0));
+    }
+
+    #[test]
+    fn test_prob_between() {
+        test_almost(1.0, 0.6321205588285577, 1e-16, |x| x.prob_between(-1.0, 1.0));
+        test_case(1.0, 0.0, |x| x.prob_between(2.0, 1.0));
+        test_case(1.0, 0.0, |x| x.prob_between(-2.0, -1.0));
+        // cdf(41) - cdf(40) rounds to zero
+        test_almost(1.0, 2.6854720659566e-18, 1e-31, |x| x.prob_between(40.0, 41.0));
+    }
+
+    #[test]
+    fn test_ln_cdf() {
+        test_case(1.0, f64::NEG_INFINITY, |x| x.ln_cdf(-1.0));
+        test_case(1.0, f64::NEG_INFINITY, |x| x.ln_cdf(0.0));
+        // cdf(x) = λx + O(x^2) near zero
+        test_almost(2.0, (2e-20f64).ln(), 1e-12, |x| x.ln_cdf(1e-20));
+        test_almost(1.0, (1.0 - (-1.0f64).exp()).ln(), 1e-15, |x| x.ln_cdf(1.0));
+    }
+
     #[test]
     fn test_neg_cdf() {
         let cdf = |arg: f64| move |x: Exp| x.cdf(arg);
@@ -451,4 +694,25 @@ mod tests {
         test::check_continuous_distribution(&try_create(1.5), 0.0, 20.0);
         test::check_continuous_distribution(&try_create(2.5), 0.0, 50.0);
     }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(2.0).cf(3.0);
+        assert_almost_eq!(phi.re, 0.3076923076923077, 1e-15);
+        assert_almost_eq!(phi.im, 0.46153846153846156, 1e-15);
+    }
+
+    #[test]
+    fn test_moment() {
+        let n = try_create(2.0);
+        assert_eq!(n.moment(3).unwrap(), 0.75);
+        assert_almost_eq!(n.central_moment(3).unwrap(), 0.25, 1e-15);
+    }
+
+    #[test]
+    fn test_cumulant() {
+        let n = try_create(2.0);
+        assert_eq!(n.cumulant(1).unwrap(), 0.5);
+        assert_eq!(n.cumulant(3).unwrap(), 0.25);
+    }
 }
@@ -1,4 +1,5 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
+use crate::fp;
 use crate::function::beta;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -58,6 +59,25 @@ impl FisherSnedecor {
         }
     }
 
+    /// Constructs a new fisher-snedecor distribution from parameters already
+    /// known to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(freedom_1: f64, freedom_2: f64) -> FisherSnedecor {
+        debug_assert!(
+            FisherSnedecor::new(freedom_1, freedom_2).is_ok(),
+            "invalid fisher-snedecor parameters"
+        );
+        FisherSnedecor {
+            freedom_1,
+            freedom_2,
+        }
+    }
+
     /// Returns the first degree of freedom for the
     /// fisher-snedecor distribution
     ///
@@ -243,6 +263,21 @@ impl Distribution<f64> for FisherSnedecor {
     }
 }
 
+impl Median<f64> for FisherSnedecor {
+    /// Returns the median of the fisher-snedecor distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(1 / 2)
+    /// ```
+    ///
+    /// where `F` is the cdf
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
 impl Mode<Option<f64>> for FisherSnedecor {
     /// Returns the mode for the fisher-snedecor distribution
     ///
@@ -296,8 +331,12 @@ impl Continuous<f64, f64> for FisherSnedecor {
         if x.is_infinite() || x <= 0.0 {
             0.0
         } else {
-            ((self.freedom_1 * x).powf(self.freedom_1) * self.freedom_2.powf(self.freedom_2)
-                / (self.freedom_1 * x + self.freedom_2).powf(self.freedom_1 + self.freedom_2))
+            (fp::powf(self.freedom_1 * x, self.freedom_1)
+                * fp::powf(self.freedom_2, self.freedom_2)
+                / fp::powf(
+                    self.freedom_1 * x + self.freedom_2,
+                    self.freedom_1 + self.freedom_2,
+                ))
             .sqrt()
                 / (x * beta::beta(self.freedom_1 / 2.0, self.freedom_2 / 2.0))
         }
@@ -322,7 +361,24 @@ impl Continuous<f64, f64> for FisherSnedecor {
     /// where `d1` is the first degree of freedom, `d2` is
     /// the second degree of freedom, and `β` is the beta function
     fn ln_pdf(&self, x: f64) -> f64 {
-        self.pdf(x).ln()
+        fp::ln(self.pdf(x))
+    }
+}
+
+impl Parameterized for FisherSnedecor {
+    fn param_names() -> &'static [&'static str] {
+        &["freedom_1", "freedom_2"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.freedom_1, self.freedom_2]
+    }
+
+    fn from_params(params: &[f64]) -> Result<FisherSnedecor> {
+        match *params {
+            [freedom_1, freedom_2] => FisherSnedecor::new(freedom_1, freedom_2),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
     }
 }
 
@@ -362,13 +418,23 @@ mod tests {
         where F: Fn(FisherSnedecor) -> f64
     {
         let x = get_value(freedom_1, freedom_2, eval);
+        // the expectations pin the platform libm to the last bit; musl's
+        // rounding under `deterministic` can land a few ulps away
+        #[cfg(not(feature = "deterministic"))]
         assert_eq!(expected, x);
+        #[cfg(feature = "deterministic")]
+        assert_relative_eq!(expected, x, max_relative = 8.0 * f64::EPSILON);
     }
 
     fn test_almost<F>(freedom_1: f64, freedom_2: f64, expected: f64, acc: f64, eval: F)
         where F: Fn(FisherSnedecor) -> f64
     {
         let x = get_value(freedom_1, freedom_2, eval);
+        // the density raises ratios to powers of the degrees of freedom, which
+        // amplifies musl's different last-bit rounding under `deterministic`
+        // to about 1e-14 relative, above some of the absolute tolerances here
+        #[cfg(feature = "deterministic")]
+        let acc = acc.max(1e-14 * expected.abs());
         assert_almost_eq!(expected, x, acc);
     }
 
@@ -458,6 +524,14 @@ mod tests {
         test_case(10.0, 10.0, 2.0 / 3.0, mode);
     }
 
+    #[test]
+    fn test_median() {
+        let median = |x: FisherSnedecor| x.median();
+        test_almost(1.0, 1.0, 1.0, 1e-14, median);
+        test_almost(5.0, 10.0, 0.93193316085104794519954239, 1e-14, median);
+        test_almost(10.0, 5.0, 1.0 / 0.93193316085104794519954239, 1e-13, median);
+    }
+
     #[test]
     #[should_panic]
     fn test_mode_with_low_d1() {
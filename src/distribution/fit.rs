@@ -0,0 +1,1030 @@
+use crate::distribution::{
+    ChiSquared, Continuous, ContinuousCDF, Discrete, Observation, Parameterized,
+};
+use crate::fp;
+use crate::function::optimize;
+use crate::{Result, StatsError};
+
+/// The maximum number of iterations of the Newton solvers used for
+/// likelihood equations without closed form solutions
+const MAX_ITER: usize = 100;
+
+/// The maximum number of restarts of the simplex searches of `fit_mse`
+/// and `Fit::profile_ci` from their previous result
+const SIMPLEX_RESTARTS: usize = 5;
+
+/// The maximum number of doublings of the step away from the estimate in
+/// the search for a bracket of a bound of a profile likelihood interval,
+/// beyond which the interval is taken to be unbounded
+const MAX_DOUBLINGS: usize = 60;
+
+/// The `Fit` trait provides [maximum likelihood
+/// estimation](https://en.wikipedia.org/wiki/Maximum_likelihood_estimation)
+/// of the parameters of a univariate distribution family from a sample
+///
+/// # Remarks
+///
+/// Continuous families are fitted to samples of `f64` and discrete families
+/// to samples of `u64`, the types over which they implement `Continuous`
+/// and `Discrete`. The estimates are given in closed form where the
+/// likelihood equations have one, and otherwise found by Newton iterations
+/// from moment-based starting values. The estimates are those of the
+/// maximum likelihood and so in general biased, e.g. the standard deviation
+/// of a normal is estimated with the divisor `n`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Fit, Gamma, Poisson};
+///
+/// let gamma = Gamma::fit_mle(&[0.8, 1.9, 1.1, 3.2, 0.4, 2.2]).unwrap();
+/// assert!(gamma.shape() > 1.0);
+/// let poisson = Poisson::fit_mle(&[2, 4, 3, 5]).unwrap();
+/// assert_eq!(poisson.lambda(), 3.5);
+/// ```
+pub trait Fit: Sized {
+    /// The type of the observations
+    type Sample;
+
+    /// Returns the member of the family maximizing the likelihood of `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or has an observation outside
+    /// the support of the family or not finite, if the maximum likelihood
+    /// estimate does not exist, e.g. for a sample that is too small or
+    /// degenerate, or if the iterations fail to converge
+    fn fit_mle(data: &[Self::Sample]) -> Result<Self>;
+
+    /// Returns the profile likelihood confidence interval at level `level`
+    /// of the parameter at `param_index` in `Parameterized::params`, the
+    /// values `ψ` of the parameter for which the likelihood ratio test of
+    /// `ψ` against the maximum likelihood estimate does not reject
+    ///
+    /// ```ignore
+    /// 2 (ln L(θ^) - max ln L(θ | θ_i = ψ)) <= χ²_1(level)
+    /// ```
+    ///
+    /// where `χ²_1(level)` is the `level` quantile of the chi-squared
+    /// distribution with one degree of freedom
+    ///
+    /// # Remarks
+    ///
+    /// Unlike the symmetric Wald interval `θ^_i ± z se`, the interval follows
+    /// the shape of the likelihood, which is far from quadratic for shape
+    /// parameters and small samples, and is invariant under
+    /// reparameterization. The other parameters are maximized with
+    /// `optimize::nelder_mead` over the values accepted by
+    /// `Parameterized::from_params` and each bound is located by Brent's
+    /// method after stepping away from the estimate with doubling steps. A
+    /// bound is the edge of the parameter space if the likelihood stays
+    /// above the cutoff up to it, and infinite if it does so indefinitely.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `fit_mle` fails for `data`, if `param_index` is
+    /// out of range or if `level` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, Fit};
+    ///
+    /// let data = [0.63, 1.47, 2.19, 0.31, 3.85, 1.02, 0.88, 2.61, 1.73, 0.55];
+    /// let rate = Exp::fit_mle(&data).unwrap().rate();
+    /// let (lower, upper) = Exp::profile_ci(&data, 0, 0.95).unwrap();
+    /// // the interval of the rate is skewed to the right of the estimate
+    /// assert!(lower < rate && rate < upper);
+    /// assert!(upper - rate > rate - lower);
+    /// ```
+    fn profile_ci(data: &[Self::Sample], param_index: usize, level: f64) -> Result<(f64, f64)>
+    where
+        Self: Parameterized + Likelihood<Self::Sample>,
+    {
+        if level.is_nan() || level <= 0.0 || level >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("level", 0.0, 1.0));
+        }
+        let mle = Self::fit_mle(data)?;
+        let params = mle.params();
+        if param_index >= params.len() {
+            return Err(StatsError::ArgLt("param_index", params.len() as f64));
+        }
+        let chi_squared = ChiSquared::new(1.0).unwrap();
+        let target = mle.ln_likelihood(data) - 0.5 * chi_squared.inverse_cdf(level);
+        let ln_likelihood = |d: &Self| d.ln_likelihood(data);
+        let lower = profile_bound(ln_likelihood, &params, param_index, target, -1.0)?;
+        let upper = profile_bound(ln_likelihood, &params, param_index, target, 1.0)?;
+        Ok((lower, upper))
+    }
+}
+
+/// The `Likelihood` trait computes the log-likelihood of a sample of type
+/// `S` under a distribution, the sum of the log densities of the
+/// observations for continuous distributions over `f64` and of the log
+/// probability masses for discrete distributions over `u64`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Exp, Likelihood, Poisson};
+///
+/// let exp = Exp::new(2.0).unwrap();
+/// assert!((exp.ln_likelihood(&[0.5, 1.0]) - (2.0 * 2f64.ln() - 3.0)).abs() < 1e-14);
+/// let poisson = Poisson::new(1.0).unwrap();
+/// assert!((poisson.ln_likelihood(&[0, 1]) + 2.0).abs() < 1e-14);
+/// ```
+pub trait Likelihood<S> {
+    /// Returns the log-likelihood of `data`, `NEG_INFINITY` if an
+    /// observation is outside the support
+    fn ln_likelihood(&self, data: &[S]) -> f64;
+}
+
+impl<D: Continuous<f64, f64>> Likelihood<f64> for D {
+    fn ln_likelihood(&self, data: &[f64]) -> f64 {
+        data.iter().map(|&x| self.ln_pdf(x)).sum()
+    }
+}
+
+impl<D: Discrete<u64, f64>> Likelihood<u64> for D {
+    fn ln_likelihood(&self, data: &[u64]) -> f64 {
+        data.iter().map(|&x| self.ln_pmf(x)).sum()
+    }
+}
+
+/// An estimated parameter of a fitted distribution together with its
+/// standard error and a confidence interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterEstimate {
+    name: &'static str,
+    estimate: f64,
+    std_error: f64,
+    confidence_interval: (f64, f64),
+}
+
+impl ParameterEstimate {
+    /// Constructs the estimate `estimate` of the parameter `name` with
+    /// standard error `std_error` and confidence interval
+    /// `confidence_interval`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `estimate` is not finite, if `std_error` is
+    /// negative or `NaN`, or if the bounds of the interval are `NaN` or
+    /// decreasing
+    pub fn new(
+        name: &'static str,
+        estimate: f64,
+        std_error: f64,
+        confidence_interval: (f64, f64),
+    ) -> Result<ParameterEstimate> {
+        let (lower, upper) = confidence_interval;
+        if !estimate.is_finite()
+            || std_error.is_nan()
+            || std_error < 0.0
+            || lower.is_nan()
+            || upper.is_nan()
+            || lower > upper
+        {
+            return Err(StatsError::BadParams);
+        }
+        Ok(ParameterEstimate {
+            name,
+            estimate,
+            std_error,
+            confidence_interval,
+        })
+    }
+
+    /// Returns the name of the parameter
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the estimate of the parameter
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the standard error of the estimate
+    pub fn std_error(&self) -> f64 {
+        self.std_error
+    }
+
+    /// Returns the confidence interval of the parameter
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        self.confidence_interval
+    }
+}
+
+/// The result of fitting a distribution to a sample, the fitted
+/// distribution together with the estimates of its parameters, their
+/// standard errors and confidence intervals at a common level
+///
+/// # Remarks
+///
+/// The reported parameters need not be those the distribution is
+/// constructed from, e.g. `Normal::fit` reports the variance rather than
+/// the standard deviation, and are looked up by name with `param`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitResult<D> {
+    dist: D,
+    confidence: f64,
+    params: Vec<ParameterEstimate>,
+}
+
+impl<D> FitResult<D> {
+    /// Constructs the result of fitting `dist`, with the estimates `params`
+    /// and their confidence intervals at level `confidence`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `confidence` is not in `(0, 1)`
+    pub fn new(dist: D, confidence: f64, params: Vec<ParameterEstimate>) -> Result<FitResult<D>> {
+        if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+        }
+        Ok(FitResult {
+            dist,
+            confidence,
+            params,
+        })
+    }
+
+    /// Returns the fitted distribution
+    pub fn dist(&self) -> &D {
+        &self.dist
+    }
+
+    /// Consumes the result, returning the fitted distribution
+    pub fn into_dist(self) -> D {
+        self.dist
+    }
+
+    /// Returns the level of the confidence intervals
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+
+    /// Returns the estimated parameters
+    pub fn params(&self) -> &[ParameterEstimate] {
+        &self.params
+    }
+
+    /// Returns the estimate of the parameter `name`, if it is reported
+    pub fn param(&self, name: &str) -> Option<&ParameterEstimate> {
+        self.params.iter().find(|p| p.name == name)
+    }
+}
+
+/// Fits the family of `start` to `data` by [maximum spacing
+/// estimation](https://en.wikipedia.org/wiki/Maximum_spacing_estimation),
+/// maximizing the mean of the logs of the spacings
+///
+/// ```ignore
+/// F(x_(1)), F(x_(2)) - F(x_(1)), ..., 1 - F(x_(n))
+/// ```
+///
+/// of the fitted cdf `F` over the sorted sample, starting the search from
+/// the parameters of `start`
+///
+/// # Remarks
+///
+/// Maximum spacing estimates are consistent and as efficient as maximum
+/// likelihood estimates where the latter behave well, but unlike them
+/// remain so where the likelihood is unbounded or maximized on the edge of
+/// the parameter space, as for families whose support depends on a
+/// parameter, e.g. the bounds of a uniform or the scale of a Pareto.
+/// Spacings between tied observations are replaced by the density at the
+/// tie. The parameters are searched with `optimize::nelder_mead` over the
+/// values accepted by `Parameterized::from_params`, restarting the search
+/// from its result until it no longer improves.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or not finite, if a spacing
+/// vanishes under `start`, e.g. because its support does not cover the
+/// sample, or if the search fails to converge
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{fit_mse, Uniform};
+/// use statrs::statistics::{Max, Min};
+///
+/// let data = [1.2, 3.4, 2.2, 4.0, 2.9];
+/// let uniform = fit_mse(&data, &Uniform::new(0.0, 5.0).unwrap()).unwrap();
+/// // the bounds extend the range of the sample by a spacing on average
+/// assert!((uniform.min() - 0.5).abs() < 1e-6);
+/// assert!((uniform.max() - 4.7).abs() < 1e-6);
+/// ```
+pub fn fit_mse<D>(data: &[f64], start: &D) -> Result<D>
+where
+    D: Parameterized + Continuous<f64, f64> + ContinuousCDF<f64, f64>,
+{
+    check_sample(data, |_| true)?;
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let objective = |params: &[f64]| match D::from_params(params) {
+        Ok(dist) => -mean_log_spacing(&dist, &sorted),
+        Err(_) => f64::INFINITY,
+    };
+    let (params, _) = minimize(objective, start.params())?;
+    D::from_params(&params)
+}
+
+/// Fits the family of `start` to the possibly censored observations `data`
+/// by maximum likelihood, starting the search from the parameters of
+/// `start`
+///
+/// # Remarks
+///
+/// The log-likelihood is that of `Likelihood<Observation>`, summing the log
+/// density of exact observations and the log probability of the events
+/// described by censored ones, so any continuous family can be fitted to
+/// survival or detection-limited data, and a `Truncated` family to data
+/// that is also truncated. The parameters are searched with
+/// `optimize::nelder_mead` over the values accepted by
+/// `Parameterized::from_params`, restarting the search from its result
+/// until it no longer improves. The estimates exist only if the data
+/// carries enough exact information, e.g. a sample of right-censored
+/// observations alone has no finite maximum.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or has an observation of zero
+/// probability for every distribution (see `Observation`), if the
+/// likelihood of `data` under `start` vanishes, or if the search fails to
+/// converge
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{fit_censored, Exp, Observation};
+///
+/// // lifetimes with two units still running at 3.0
+/// let data = [
+///     Observation::Exact(0.5),
+///     Observation::Exact(1.5),
+///     Observation::Exact(2.0),
+///     Observation::RightCensored(3.0),
+///     Observation::RightCensored(3.0),
+/// ];
+/// let exp = fit_censored(&data, &Exp::new(1.0).unwrap()).unwrap();
+/// // the rate is the number of failures over the total time on test
+/// assert!((exp.rate() - 3.0 / 10.0).abs() < 1e-6);
+/// ```
+pub fn fit_censored<D>(data: &[Observation], start: &D) -> Result<D>
+where
+    D: Parameterized + Continuous<f64, f64> + ContinuousCDF<f64, f64>,
+{
+    if data.is_empty() || data.iter().any(|obs| !obs.is_valid()) {
+        return Err(StatsError::BadParams);
+    }
+    let objective = |params: &[f64]| match D::from_params(params) {
+        Ok(dist) => -dist.ln_likelihood(data),
+        Err(_) => f64::INFINITY,
+    };
+    let (params, _) = minimize(objective, start.params())?;
+    D::from_params(&params)
+}
+
+/// Minimizes `f` with `optimize::nelder_mead` from `start`, restarting the
+/// search from its result until it no longer improves
+fn minimize<F>(mut f: F, start: Vec<f64>) -> Result<(Vec<f64>, f64)>
+where
+    F: FnMut(&[f64]) -> f64,
+{
+    let mut params = start;
+    let mut value = f64::INFINITY;
+    for _ in 0..SIMPLEX_RESTARTS {
+        let (next, next_value) = optimize::nelder_mead(&mut f, &params, 1e-15)?;
+        let improved = next_value < value - 1e-15 * (1.0 + next_value.abs());
+        params = next;
+        value = next_value;
+        if !improved {
+            break;
+        }
+    }
+    Ok((params, value))
+}
+
+/// Returns the bound in the direction `sign` of the profile likelihood
+/// interval of the parameter at `index`, the value at which the maximum of
+/// `ln_likelihood` over the other parameters falls to `target`, searching
+/// away from the estimates `mle`
+fn profile_bound<D, F>(
+    ln_likelihood: F,
+    mle: &[f64],
+    index: usize,
+    target: f64,
+    sign: f64,
+) -> Result<f64>
+where
+    D: Parameterized,
+    F: Fn(&D) -> f64,
+{
+    // the maximum over the other parameters, started from the maximizer at
+    // the previous value of the parameter
+    let mut nuisance: Vec<f64> = mle
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != index)
+        .map(|(_, &x)| x)
+        .collect();
+    let mut excess = |psi: f64| -> f64 {
+        let with = |nuisance: &[f64]| -> Vec<f64> {
+            let mut params = nuisance.to_vec();
+            params.insert(index, psi);
+            params
+        };
+        let value = |nuisance: &[f64]| match D::from_params(&with(nuisance)) {
+            Ok(dist) => ln_likelihood(&dist),
+            Err(_) => f64::NEG_INFINITY,
+        };
+        let max = if nuisance.is_empty() {
+            value(&nuisance)
+        } else {
+            match minimize(|x| -value(x), nuisance.clone()) {
+                Ok((x, min)) => {
+                    nuisance = x;
+                    -min
+                }
+                Err(_) => f64::NEG_INFINITY,
+            }
+        };
+        if max.is_nan() {
+            f64::NEG_INFINITY
+        } else {
+            max - target
+        }
+    };
+
+    let estimate = mle[index];
+    let step = if estimate != 0.0 {
+        0.1 * estimate.abs()
+    } else {
+        0.1
+    };
+    let tol = 1e-10 * (estimate.abs() + step);
+    // a bracket [inner, outer] of the bound, the profile being above the
+    // target at inner and below it, or outside the parameter space, at
+    // outer
+    let mut inner = estimate;
+    let mut outer = estimate + sign * step;
+    let mut outer_excess = excess(outer);
+    let mut doublings = 0;
+    while outer_excess >= 0.0 {
+        if doublings == MAX_DOUBLINGS {
+            return Ok(sign * f64::INFINITY);
+        }
+        inner = outer;
+        outer = estimate + sign * step * fp::powi(2f64, doublings as i32 + 1);
+        outer_excess = excess(outer);
+        doublings += 1;
+    }
+    // bisect while the outer end is outside the parameter space, which
+    // ends either at the edge of the space or with a finite bracket
+    while outer_excess == f64::NEG_INFINITY {
+        if (outer - inner).abs() <= tol {
+            return Ok(inner);
+        }
+        let mid = 0.5 * (inner + outer);
+        let mid_excess = excess(mid);
+        if mid_excess >= 0.0 {
+            inner = mid;
+        } else {
+            outer = mid;
+            outer_excess = mid_excess;
+        }
+    }
+    optimize::brent_root(&mut excess, inner, outer, tol)
+}
+
+/// Returns the mean of the logs of the spacings of the cdf of `dist` over
+/// the sorted sample `sorted`, with the spacings between tied observations
+/// replaced by the density at the tie
+fn mean_log_spacing<D>(dist: &D, sorted: &[f64]) -> f64
+where
+    D: Continuous<f64, f64> + ContinuousCDF<f64, f64>,
+{
+    let n = sorted.len();
+    let mut sum = fp::ln(dist.cdf(sorted[0])) + fp::ln(dist.sf(sorted[n - 1]));
+    for pair in sorted.windows(2) {
+        sum += if pair[0] == pair[1] {
+            dist.ln_pdf(pair[1])
+        } else {
+            fp::ln(dist.prob_between(pair[0], pair[1]))
+        };
+    }
+    sum / (n + 1) as f64
+}
+
+/// Returns an error if `data` is empty or has an observation that is not
+/// finite or does not satisfy `in_support`
+pub(crate) fn check_sample<F: Fn(f64) -> bool>(data: &[f64], in_support: F) -> Result<()> {
+    if data.is_empty() || data.iter().any(|&x| !(x.is_finite() && in_support(x))) {
+        Err(StatsError::BadParams)
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the mean of `data`
+pub(crate) fn mean(data: &[f64]) -> f64 {
+    data.iter().sum::<f64>() / data.len() as f64
+}
+
+/// Returns the mean of the counts `data`, failing if it is empty
+pub(crate) fn count_mean(data: &[u64]) -> Result<f64> {
+    if data.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    Ok(data.iter().map(|&k| k as f64).sum::<f64>() / data.len() as f64)
+}
+
+/// Returns the mean of `data` and its variance with the divisor `n`
+pub(crate) fn mean_variance(data: &[f64]) -> (f64, f64) {
+    let mean = mean(data);
+    let variance = data.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / data.len() as f64;
+    (mean, variance)
+}
+
+/// Finds the root of the decreasing or increasing function `f` on the
+/// positive reals by Newton's method from `x0`, where `f` returns its value
+/// and derivative. Steps leaving the positive reals are replaced by
+/// halving the current iterate.
+pub(crate) fn newton_positive<F>(f: F, x0: f64) -> Result<f64>
+where
+    F: Fn(f64) -> (f64, f64),
+{
+    let mut x = x0;
+    for _ in 0..MAX_ITER {
+        let (y, dy) = f(x);
+        if !(y.is_finite() && dy.is_finite()) || dy == 0.0 {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+        let next = x - y / dy;
+        let next = if next > 0.0 { next } else { x / 2.0 };
+        if y == 0.0 || (next - x).abs() <= 1e-12 * x {
+            return Ok(next);
+        }
+        x = next;
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::{
+        dispersion_test, Bernoulli, Beta, BetaBoundary, Binomial, Censored, Continuous, Discrete, Exp, Gamma, Geometric, Laplace, LogNormal,
+        NegativeBinomial, Normal, Observation, Parameterized, Pareto, Poisson, Uniform, Weibull,
+    };
+    use crate::statistics::DiscreteDistribution;
+    use crate::statistics::{Distribution, Max, Min};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const DATA: [f64; 10] = [0.63, 1.47, 2.19, 0.31, 3.85, 1.02, 0.88, 2.61, 1.73, 0.55];
+    const UNIT_DATA: [f64; 10] = [0.12, 0.45, 0.33, 0.71, 0.58, 0.26, 0.83, 0.39, 0.64, 0.18];
+
+    fn ln_likelihood<D: Continuous<f64, f64>>(dist: &D, data: &[f64]) -> f64 {
+        data.iter().map(|&x| dist.ln_pdf(x)).sum()
+    }
+
+    #[test]
+    fn test_fit_closed_forms() {
+        let normal = Normal::fit_mle(&DATA).unwrap();
+        assert_almost_eq!(normal.mean().unwrap(), 1.524, 1e-14);
+        assert_almost_eq!(normal.std_dev().unwrap(), 1.0473318480787261, 1e-14);
+        assert_almost_eq!(Exp::fit_mle(&DATA).unwrap().rate(), 1.0 / 1.524, 1e-14);
+        let log_normal = LogNormal::fit_mle(&DATA).unwrap();
+        let ln_data: Vec<f64> = DATA.iter().map(|x| x.ln()).collect();
+        assert_almost_eq!(log_normal.location(), ln_data.iter().sum::<f64>() / 10.0, 1e-14);
+        let uniform = Uniform::fit_mle(&DATA).unwrap();
+        assert_eq!((uniform.min(), uniform.max()), (0.31, 3.85));
+        let laplace = Laplace::fit_mle(&DATA).unwrap();
+        assert_almost_eq!(laplace.location(), 1.245, 1e-14);
+        assert_almost_eq!(laplace.scale(), 0.846, 1e-14);
+        assert_eq!(Poisson::fit_mle(&[0, 3, 1, 4]).unwrap().lambda(), 2.0);
+        assert_eq!(Geometric::fit_mle(&[1, 3, 2, 2]).unwrap().p(), 0.5);
+        assert_eq!(Bernoulli::fit_mle(&[0, 1, 1, 1]).unwrap().p(), 0.75);
+    }
+
+    #[test]
+    fn test_fit_iterative() {
+        // reference values from solving the likelihood equations in
+        // arbitrary precision
+        let gamma = Gamma::fit_mle(&DATA).unwrap();
+        assert_almost_eq!(gamma.shape(), 2.129714924806267477548221094571007545041, 1e-11);
+        assert_almost_eq!(gamma.rate(), 1.397450738061855300228491531870739858951, 1e-11);
+        let weibull = Weibull::fit_mle(&DATA).unwrap();
+        assert_almost_eq!(weibull.shape(), 1.52745522580497925623234767046086387627, 1e-11);
+        assert_almost_eq!(weibull.scale(), 1.70077694631135876226289859712305078949, 1e-11);
+        let beta = Beta::fit_mle(&UNIT_DATA).unwrap();
+        assert_almost_eq!(beta.shape_a(), 1.962277443359281402735765688748265915942, 1e-11);
+        assert_almost_eq!(beta.shape_b(), 2.396972373303151671255665438600815383448, 1e-11);
+        let binomial = Binomial::fit_mle(&[12, 15, 9, 14, 11, 13, 10, 16, 12, 14, 8, 13]).unwrap();
+        assert_eq!(binomial.n(), 21);
+        assert_almost_eq!(binomial.p(), 12.25 / 21.0, 1e-15);
+        // the estimates are local maxima of the likelihood
+        let ln_l = ln_likelihood(&gamma, &DATA);
+        for &(da, db) in &[(1e-4, 0.0), (-1e-4, 0.0), (0.0, 1e-4), (0.0, -1e-4)] {
+            let other = Gamma::new(gamma.shape() + da, gamma.rate() + db).unwrap();
+            assert!(ln_likelihood(&other, &DATA) < ln_l);
+            let other = Weibull::new(weibull.shape() + da, weibull.scale() + db).unwrap();
+            assert!(ln_likelihood(&other, &DATA) < ln_likelihood(&weibull, &DATA));
+            let other = Beta::new(beta.shape_a() + da, beta.shape_b() + db).unwrap();
+            assert!(ln_likelihood(&other, &UNIT_DATA) < ln_likelihood(&beta, &UNIT_DATA));
+        }
+    }
+
+    #[test]
+    fn test_gamma_fit_large_shapes() {
+        // reference values from solving the likelihood equation in
+        // arbitrary precision; near-constant samples have huge shapes whose
+        // estimates lose no precision to cancellation
+        let cases: [(&[f64], f64, f64); 3] = [
+            (&[9.1, 10.3, 11.2, 9.8, 10.6, 8.9, 10.1, 11.0], 166.7605819593211126723911, 16.47018093425393705406332),
+            (&[1000.001, 999.998, 1000.003, 1000.0, 999.997, 1000.001], 249999958330.1820247902241, 249999958.3301820247902241),
+            (&[5.0, 5.0000001, 4.9999999, 5.0], 4999999971956867.023838230, 999999994391373.4047676460),
+        ];
+        for &(data, shape, rate) in &cases {
+            let gamma = Gamma::fit_mle(data).unwrap();
+            assert!((gamma.shape() / shape - 1.0).abs() < 1e-9);
+            assert!((gamma.rate() / rate - 1.0).abs() < 1e-9);
+        }
+        let mut rng = StdRng::seed_from_u64(4);
+        let x: Vec<f64> = (0..20000).map(|_| Gamma::new(1e4, 1.0).unwrap().sample(&mut rng)).collect();
+        assert!((Gamma::fit_mle(&x).unwrap().shape() / 1e4 - 1.0).abs() < 0.03);
+        assert!(Gamma::fit_mle(&[1e300, 1e300]).is_err());
+    }
+
+    #[test]
+    fn test_beta_fit_boundary() {
+        let data = [0.0, 0.12, 0.33, 0.71, 1.0, 0.26, 0.0, 0.39, 0.64, 0.18];
+        assert!(Beta::fit_mle(&data).is_err());
+        assert!(Beta::fit_mle_with_boundary(&data, BetaBoundary::Reject).is_err());
+        let inner: Vec<f64> = data.iter().cloned().filter(|&x| x > 0.0 && x < 1.0).collect();
+        assert_eq!(Beta::fit_mle_with_boundary(&data, BetaBoundary::Discard).unwrap(), Beta::fit_mle(&inner).unwrap());
+        let squeezed: Vec<f64> = data.iter().map(|x| (9.0 * x + 0.5) / 10.0).collect();
+        let beta = Beta::fit_mle_with_boundary(&data, BetaBoundary::Squeeze).unwrap();
+        assert_eq!(beta, Beta::fit_mle(&squeezed).unwrap());
+        assert!(beta.shape_a() < Beta::fit_mle(&inner).unwrap().shape_a());
+        // samples inside the open interval are fitted unchanged
+        for &boundary in &[BetaBoundary::Reject, BetaBoundary::Discard, BetaBoundary::Squeeze] {
+            assert_eq!(Beta::fit_mle_with_boundary(&UNIT_DATA, boundary).unwrap(), Beta::fit_mle(&UNIT_DATA).unwrap());
+        }
+        // rates rounded to zero in a large sample barely move the estimates
+        let mut rng = StdRng::seed_from_u64(5);
+        let x: Vec<f64> = (0..20000)
+            .map(|_| Beta::new(0.8, 12.0).unwrap().sample(&mut rng))
+            .map(|x| if x < 1e-4 { 0.0 } else { x })
+            .collect();
+        assert!(x.contains(&0.0));
+        let beta = Beta::fit_mle_with_boundary(&x, BetaBoundary::Squeeze).unwrap();
+        assert!((beta.shape_a() - 0.8).abs() < 0.03 && (beta.shape_b() - 12.0).abs() < 0.6);
+        assert!(Beta::fit_mle_with_boundary(&[0.0, 1.0, 1.0], BetaBoundary::Discard).is_err());
+        assert!(Beta::fit_mle_with_boundary(&[0.5, 1.5], BetaBoundary::Squeeze).is_err());
+    }
+
+    #[test]
+    fn test_weibull_fit_censored() {
+        let right_censored = |times: &[f64], censored: &[bool]| -> Vec<Observation> {
+            times
+                .iter()
+                .zip(censored)
+                .map(|(&x, &c)| if c { Observation::RightCensored(x) } else { Observation::Exact(x) })
+                .collect()
+        };
+        // reference values from solving the profile likelihood equation in
+        // arbitrary precision
+        let censored = [false, false, false, true, true, false, false, true, false, false];
+        let observations = right_censored(&DATA, &censored);
+        let weibull = Weibull::fit_mle_censored(&observations).unwrap();
+        assert_almost_eq!(weibull.shape(), 1.409248999727686940840607470658979846416, 1e-11);
+        assert_almost_eq!(weibull.scale(), 2.140699319502731635021581105700093252670, 1e-11);
+        let hours = right_censored(&[410.0, 580.0, 720.0, 850.0, 1000.0, 1000.0, 1000.0], &[false, false, false, false, true, true, true]);
+        let weibull = Weibull::fit_mle_censored(&hours).unwrap();
+        assert_almost_eq!(weibull.shape(), 2.645121771713603213879603234449357043825, 1e-11);
+        assert_almost_eq!(weibull.scale(), 1036.803377327023012869792668461936577969, 1e-8);
+        // the estimates maximize the censored likelihood, also of the
+        // parent of a censored distribution
+        let ln_l = |shape: f64, scale: f64| Weibull::new(shape, scale).unwrap().ln_likelihood(&observations);
+        let (shape, scale) = (1.409248999727687, 2.140699319502732);
+        for &(ds, dl) in &[(1e-4, 0.0), (-1e-4, 0.0), (0.0, 1e-4), (0.0, -1e-4)] {
+            assert!(ln_l(shape + ds, scale + dl) < ln_l(shape, scale));
+        }
+        let censored_weibull = Censored::new(Weibull::new(shape, scale).unwrap(), f64::NEG_INFINITY, f64::INFINITY).unwrap();
+        assert_eq!(censored_weibull.ln_likelihood(&observations), ln_l(shape, scale));
+        // without censoring the fit is the plain maximum likelihood fit
+        let exact: Vec<Observation> = DATA.iter().map(|&x| Observation::Exact(x)).collect();
+        assert_eq!(Weibull::fit_mle_censored(&exact).unwrap(), Weibull::fit_mle(&DATA).unwrap());
+        // censoring at a fixed time hides most of the large lifetimes
+        let mut rng = StdRng::seed_from_u64(6);
+        let lifetimes: Vec<f64> = (0..20000).map(|_| Weibull::new(1.5, 100.0).unwrap().sample(&mut rng)).collect();
+        let censored: Vec<bool> = lifetimes.iter().map(|&x| x > 120.0).collect();
+        let times: Vec<f64> = lifetimes.iter().map(|&x| x.min(120.0)).collect();
+        let weibull = Weibull::fit_mle_censored(&right_censored(&times, &censored)).unwrap();
+        assert!((weibull.shape() - 1.5).abs() < 0.05 && (weibull.scale() - 100.0).abs() < 2.0);
+        assert!(Weibull::fit_mle(&times).unwrap().scale() < 90.0);
+        // left- and interval-censored observations are fitted by fit_censored
+        // from the fit of the others
+        let mut mixed = observations.clone();
+        mixed.push(Observation::LeftCensored(0.4));
+        mixed.push(Observation::IntervalCensored(2.0, 3.0));
+        let weibull = Weibull::fit_mle_censored(&mixed).unwrap();
+        let ln_l = |shape: f64, scale: f64| Weibull::new(shape, scale).unwrap().ln_likelihood(&mixed);
+        let (shape, scale) = (weibull.shape(), weibull.scale());
+        for &(ds, dl) in &[(1e-4, 0.0), (-1e-4, 0.0), (0.0, 1e-4), (0.0, -1e-4)] {
+            assert!(ln_l(shape + ds, scale + dl) < ln_l(shape, scale));
+        }
+        assert!(Weibull::fit_mle_censored(&[]).is_err());
+        assert!(Weibull::fit_mle_censored(&right_censored(&[1.0, 2.0], &[true, true])).is_err());
+        assert!(Weibull::fit_mle_censored(&right_censored(&[1.0, 2.0], &[true, false])).is_err());
+        assert!(Weibull::fit_mle_censored(&right_censored(&[1.0, -2.0], &[false, false])).is_err());
+        assert!(Weibull::fit_mle_censored(&right_censored(&[1.0, 2.0, 2.0], &[false, true, false])).is_ok());
+        assert!(Weibull::fit_mle_censored(&[Observation::LeftCensored(1.0), Observation::IntervalCensored(1.0, 2.0)]).is_err());
+    }
+
+    #[test]
+    fn test_count_fit_dispersion() {
+        // reference values computed in arbitrary precision
+        let claims = [0, 0, 3, 0, 1, 7, 0, 0, 2, 0, 5, 0, 0, 1, 9, 0];
+        let negative_binomial = NegativeBinomial::fit_mle(&claims).unwrap();
+        assert_almost_eq!(negative_binomial.mean().unwrap(), 1.75, 1e-14);
+        assert_almost_eq!(negative_binomial.dispersion(), 3.104769819395170689453523433910522681150, 1e-11);
+        let negative_binomial = NegativeBinomial::fit_mle(&[0, 3, 1, 7, 0, 2, 12, 4]).unwrap();
+        assert_almost_eq!(negative_binomial.dispersion(), 1.072227547376536284306014093679086368794, 1e-11);
+        let test = dispersion_test(&claims).unwrap();
+        assert_almost_eq!(test.statistic(), 9.394418664335559967039789382250137236213, 1e-13);
+        assert_almost_eq!(test.p_value(), 2.876732784426926904498239575857074880887e-21, 1e-30);
+        let test = dispersion_test(&[3, 4, 2, 5, 3, 3, 4, 1, 3, 4, 2, 5]).unwrap();
+        assert_almost_eq!(test.statistic(), -1.428869016623520557281749043578436645313, 1e-14);
+        assert_almost_eq!(test.p_value(), 0.9234790579205899580928503979042793677594, 1e-10);
+        // the test picks the model that generated the counts
+        let mut rng = StdRng::seed_from_u64(7);
+        let poisson = Poisson::new(4.0).unwrap();
+        let k: Vec<u64> = (0..2000).map(|_| poisson.sample(&mut rng) as u64).collect();
+        assert!(dispersion_test(&k).unwrap().p_value() > 0.01);
+        let gamma = Gamma::new(2.0, 0.5).unwrap();
+        let k: Vec<u64> = (0..2000).map(|_| Poisson::new(gamma.sample(&mut rng)).unwrap().sample(&mut rng) as u64).collect();
+        assert!(dispersion_test(&k).unwrap().p_value() < 1e-6);
+        let negative_binomial = NegativeBinomial::fit_mle(&k).unwrap();
+        assert!((negative_binomial.dispersion() - 0.5).abs() < 0.05);
+        // equidispersed counts have no negative binomial estimate
+        assert!(NegativeBinomial::fit_mle(&[3, 4, 2, 5, 3, 3, 4, 1, 3, 4, 2, 5]).is_err());
+        assert!(NegativeBinomial::fit_mle(&[]).is_err());
+        assert!(dispersion_test(&[0, 0, 0]).is_err());
+        assert!(dispersion_test(&[4]).is_err());
+    }
+
+    #[test]
+    fn test_fit_recovers_parameters() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let x: Vec<f64> = (0..20000).map(|_| Gamma::new(0.3, 2.0).unwrap().sample(&mut rng)).collect();
+        let gamma = Gamma::fit_mle(&x).unwrap();
+        assert!((gamma.shape() - 0.3).abs() < 0.01 && (gamma.rate() - 2.0).abs() < 0.1);
+        let x: Vec<f64> = (0..20000).map(|_| Weibull::new(4.0, 1e6).unwrap().sample(&mut rng)).collect();
+        let weibull = Weibull::fit_mle(&x).unwrap();
+        assert!((weibull.shape() - 4.0).abs() < 0.1 && (weibull.scale() / 1e6 - 1.0).abs() < 0.01);
+        let x: Vec<f64> = (0..20000).map(|_| Beta::new(0.5, 8.0).unwrap().sample(&mut rng)).collect();
+        let beta = Beta::fit_mle(&x).unwrap();
+        assert!((beta.shape_a() - 0.5).abs() < 0.02 && (beta.shape_b() - 8.0).abs() < 0.4);
+        let k: Vec<u64> = (0..20000).map(|_| Binomial::new(0.7, 30).unwrap().sample(&mut rng) as u64).collect();
+        let binomial = Binomial::fit_mle(&k).unwrap();
+        assert!((binomial.n() as f64 - 30.0).abs() <= 2.0);
+        assert!(binomial.pmf(21) > 0.0);
+    }
+
+    #[test]
+    fn test_fit_moments() {
+        // the fitted distributions match the mean and variance of the sample
+        let (mean, variance) = mean_variance(&DATA);
+        let gamma = Gamma::fit_moments(&DATA).unwrap();
+        assert_almost_eq!(gamma.mean().unwrap(), mean, 1e-14);
+        assert_almost_eq!(gamma.variance().unwrap(), variance, 1e-14);
+        let log_normal = LogNormal::fit_moments(&DATA).unwrap();
+        assert_almost_eq!(log_normal.mean().unwrap(), mean, 1e-14);
+        assert_almost_eq!(log_normal.variance().unwrap(), variance, 1e-13);
+        let uniform = Uniform::fit_moments(&DATA).unwrap();
+        assert_almost_eq!(uniform.mean().unwrap(), mean, 1e-14);
+        assert_almost_eq!(uniform.variance().unwrap(), variance, 1e-14);
+        let (mean, variance) = mean_variance(&UNIT_DATA);
+        let beta = Beta::fit_moments(&UNIT_DATA).unwrap();
+        assert_almost_eq!(beta.mean().unwrap(), mean, 1e-14);
+        assert_almost_eq!(beta.variance().unwrap(), variance, 1e-14);
+        let counts = [0, 3, 1, 7, 0, 2, 12, 4];
+        let negative_binomial = NegativeBinomial::fit_moments(&counts).unwrap();
+        assert_almost_eq!(negative_binomial.mean().unwrap(), 3.625, 1e-14);
+        assert_almost_eq!(negative_binomial.variance().unwrap(), 14.734375, 1e-13);
+        // the moment estimates are close to the likelihood estimates
+        let mut rng = StdRng::seed_from_u64(2);
+        let x: Vec<f64> = (0..20000).map(|_| Gamma::new(3.0, 0.5).unwrap().sample(&mut rng)).collect();
+        let (mle, moments) = (Gamma::fit_mle(&x).unwrap(), Gamma::fit_moments(&x).unwrap());
+        assert!((mle.shape() - moments.shape()).abs() < 0.1);
+        assert!((moments.shape() - 3.0).abs() < 0.1 && (moments.rate() - 0.5).abs() < 0.02);
+        // and exist for samples without likelihood estimates
+        assert!(Gamma::fit_mle(&[0.0, 1.0]).is_err());
+        assert_eq!(Gamma::fit_moments(&[0.0, 1.0]).unwrap().shape(), 1.0);
+        assert!(Beta::fit_mle(&[0.0, 0.5, 1.0]).is_err());
+        assert_almost_eq!(Beta::fit_moments(&[0.0, 0.5, 1.0]).unwrap().shape_a(), 0.25, 1e-15);
+        assert!(LogNormal::fit_moments(&[0.0, 1.0]).is_ok());
+        assert!(Gamma::fit_moments(&[2.0, 2.0]).is_err());
+        assert!(Gamma::fit_moments(&[-1.0, 2.0]).is_err());
+        assert!(Beta::fit_moments(&[0.0, 1.0]).is_err());
+        assert!(Beta::fit_moments(&[0.5, 1.5]).is_err());
+        assert!(LogNormal::fit_moments(&[]).is_err());
+        assert!(Uniform::fit_moments(&[1.0, f64::NAN]).is_err());
+        assert!(NegativeBinomial::fit_moments(&[1, 2, 3]).is_err());
+        assert!(NegativeBinomial::fit_moments(&[0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_normal_fit() {
+        // reference values from the exact quantiles in arbitrary precision
+        let fit = Normal::fit(&DATA).unwrap();
+        assert_eq!(fit.confidence(), 0.95);
+        assert_almost_eq!(fit.dist().std_dev().unwrap(), 1.218782222222222222222f64.sqrt(), 1e-14);
+        let mean = fit.param("mean").unwrap();
+        assert_eq!((mean.name(), mean.estimate()), ("mean", 1.524));
+        assert_almost_eq!(mean.std_error(), 0.3491106160262420639502030730309497012099, 1e-14);
+        assert_almost_eq!(mean.confidence_interval().0, 0.7342569193473425063940268414056552814898, 1e-9);
+        assert_almost_eq!(mean.confidence_interval().1, 2.31374308065265749360597315859434471851, 1e-9);
+        let variance = fit.param("variance").unwrap();
+        assert_almost_eq!(variance.estimate(), 1.218782222222222222222222222222222222222, 1e-14);
+        assert_almost_eq!(variance.std_error(), 0.5745394494152953523743583586446350596946, 1e-14);
+        assert_almost_eq!(variance.confidence_interval().0, 0.5766269197053054342154614001107702391748, 1e-9);
+        assert_almost_eq!(variance.confidence_interval().1, 4.062021423235346813565031060469808087335, 1e-9);
+        assert_eq!(fit.params().len(), 2);
+        assert!(fit.param("std_dev").is_none());
+        // the intervals cover the true parameters at their nominal level
+        // even for small samples
+        let mut rng = StdRng::seed_from_u64(3);
+        let normal = Normal::new(2.0, 3.0).unwrap();
+        let (mut mean_hits, mut variance_hits) = (0, 0);
+        for _ in 0..2000 {
+            let x: Vec<f64> = (0..5).map(|_| normal.sample(&mut rng)).collect();
+            let fit = Normal::fit_with_confidence(&x, 0.9).unwrap();
+            let (lower, upper) = fit.param("mean").unwrap().confidence_interval();
+            mean_hits += (lower < 2.0 && 2.0 < upper) as usize;
+            let (lower, upper) = fit.param("variance").unwrap().confidence_interval();
+            variance_hits += (lower < 9.0 && 9.0 < upper) as usize;
+        }
+        assert!((mean_hits as f64 / 2000.0 - 0.9).abs() < 0.02);
+        assert!((variance_hits as f64 / 2000.0 - 0.9).abs() < 0.02);
+        assert!(Normal::fit(&[1.0]).is_err());
+        assert!(Normal::fit(&[1.0, 1.0]).is_err());
+        assert!(Normal::fit_with_confidence(&DATA, 1.0).is_err());
+        assert!(ParameterEstimate::new("x", 1.0, -1.0, (0.0, 2.0)).is_err());
+        assert!(ParameterEstimate::new("x", 1.0, 1.0, (2.0, 0.0)).is_err());
+        assert!(FitResult::new(normal, f64::NAN, vec![]).is_err());
+        assert_eq!(FitResult::new(normal, 0.5, vec![]).unwrap().into_dist(), normal);
+    }
+
+    #[test]
+    fn test_fit_bad_data() {
+        assert!(Normal::fit_mle(&[]).is_err());
+        assert!(Normal::fit_mle(&[1.0, 1.0]).is_err());
+        assert!(Normal::fit_mle(&[1.0, f64::NAN]).is_err());
+        assert!(Exp::fit_mle(&[1.0, -1.0]).is_err());
+        assert!(Exp::fit_mle(&[0.0, 0.0]).is_err());
+        assert!(LogNormal::fit_mle(&[0.0, 1.0]).is_err());
+        assert!(Gamma::fit_mle(&[2.0, 2.0, 2.0]).is_err());
+        assert!(Gamma::fit_mle(&[0.0, 2.0]).is_err());
+        assert!(Weibull::fit_mle(&[3.0]).is_err());
+        assert!(Beta::fit_mle(&[0.5, 1.0]).is_err());
+        assert!(Beta::fit_mle(&[0.5, 0.5]).is_err());
+        assert!(Uniform::fit_mle(&[f64::INFINITY]).is_err());
+        assert!(Poisson::fit_mle(&[]).is_err());
+        assert!(Poisson::fit_mle(&[0, 0]).is_err());
+        assert!(Geometric::fit_mle(&[0, 1]).is_err());
+        assert!(Bernoulli::fit_mle(&[0, 2]).is_err());
+        // overdispersed counts have no binomial estimate
+        assert!(Binomial::fit_mle(&[0, 5, 1, 9]).is_err());
+        assert!(Binomial::fit_mle(&[0, 0]).is_err());
+        let binomial = Binomial::fit_mle(&[4, 4, 4]).unwrap();
+        assert_eq!((binomial.p(), binomial.n()), (1.0, 4));
+    }
+
+    #[test]
+    fn test_fit_mse() {
+        // the uniform estimates extend the range by a mean spacing
+        let uniform = fit_mse(&DATA, &Uniform::new(0.0, 5.0).unwrap()).unwrap();
+        let spacing = (3.85 - 0.31) / 9.0;
+        assert_almost_eq!(uniform.min(), 0.31 - spacing, 1e-6);
+        assert_almost_eq!(uniform.max(), 3.85 + spacing, 1e-6);
+        // the mean log spacing is locally maximal
+        let mut sorted = DATA.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let gamma = fit_mse(&DATA, &Gamma::new(1.0, 1.0).unwrap()).unwrap();
+        let best = mean_log_spacing(&gamma, &sorted);
+        for &(da, db) in &[(1e-3, 0.0), (-1e-3, 0.0), (0.0, 1e-3), (0.0, -1e-3)] {
+            let other = Gamma::new(gamma.shape() * (1.0 + da), gamma.rate() * (1.0 + db)).unwrap();
+            assert!(mean_log_spacing(&other, &sorted) < best);
+        }
+        // the Pareto scale lies below the smallest observation, where the
+        // likelihood is maximized on the edge of the parameter space
+        let mut rng = StdRng::seed_from_u64(7);
+        let pareto = Pareto::new(2.0, 3.0).unwrap();
+        let x: Vec<f64> = (0..500).map(|_| pareto.sample(&mut rng)).collect();
+        let min = x.iter().cloned().fold(f64::INFINITY, f64::min);
+        let fitted = fit_mse(&x, &Pareto::new(1.0, 1.0).unwrap()).unwrap();
+        assert!(fitted.scale() < min && fitted.scale() > min - 0.01);
+        assert!((fitted.shape() / 3.0 - 1.0).abs() < 0.15);
+        // the spacings of ties are replaced by the density
+        let normal = fit_mse(&[1.0, 2.0, 2.0, 3.0, 4.5], &Normal::new(0.0, 1.0).unwrap()).unwrap();
+        assert!(normal.params().iter().all(|p| p.is_finite()));
+        assert!(fit_mse(&[], &Normal::new(0.0, 1.0).unwrap()).is_err());
+        assert!(fit_mse(&[1.0, f64::NAN], &Normal::new(0.0, 1.0).unwrap()).is_err());
+        assert!(fit_mse(&[0.5, 2.0], &Uniform::new(0.0, 1.0).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_profile_ci() {
+        // reference values from solving the likelihood ratio equations in
+        // arbitrary precision, in closed form for the normal mean
+        let (lower, upper) = Normal::profile_ci(&DATA, 0, 0.95).unwrap();
+        assert_almost_eq!(lower, 0.8072395411888154, 1e-7);
+        assert_almost_eq!(upper, 2.2407604588111846, 1e-7);
+        let (lower, upper) = Normal::profile_ci(&DATA, 1, 0.95).unwrap();
+        assert_almost_eq!(lower, 0.7143943992263932, 1e-7);
+        assert_almost_eq!(upper, 1.7489546400277722, 1e-7);
+        let (lower, upper) = Exp::profile_ci(&DATA, 0, 0.95).unwrap();
+        assert_almost_eq!(lower, 0.3287903898563382, 1e-9);
+        assert_almost_eq!(upper, 1.1508756425393205, 1e-9);
+        let (lower, upper) = Poisson::profile_ci(&[2, 4, 3, 5], 0, 0.95).unwrap();
+        assert_almost_eq!(lower, 1.9717666187404924, 1e-9);
+        assert_almost_eq!(upper, 5.666533253477288, 1e-9);
+
+        // the interval of a gamma shape is skewed to the right, and at its
+        // bounds the profile, maximized by the rate a / mean, is at the
+        // cutoff
+        let gamma = Gamma::fit_mle(&DATA).unwrap();
+        let (lower, upper) = Gamma::profile_ci(&DATA, 0, 0.95).unwrap();
+        assert!(upper - gamma.shape() > gamma.shape() - lower);
+        let profile = |a: f64| ln_likelihood(&Gamma::new(a, a / 1.524).unwrap(), &DATA);
+        let ln_l = ln_likelihood(&gamma, &DATA);
+        assert_almost_eq!(2.0 * (ln_l - profile(lower)), 3.841458820694126, 1e-6);
+        assert_almost_eq!(2.0 * (ln_l - profile(upper)), 3.841458820694126, 1e-6);
+
+        // the likelihood of a uniform vanishes above the smallest
+        // observation, which bounds the interval of the lower end
+        let (lower, upper) = Uniform::profile_ci(&DATA, 0, 0.95).unwrap();
+        assert_almost_eq!(lower, -0.439626508440802, 1e-7);
+        assert_almost_eq!(upper, 0.31, 1e-8);
+        // wider at higher levels
+        let (wide_lower, wide_upper) = Exp::profile_ci(&DATA, 0, 0.99).unwrap();
+        assert!(wide_lower < 0.3287903898563382 && wide_upper > 1.1508756425393205);
+
+        assert!(Exp::profile_ci(&DATA, 1, 0.95).is_err());
+        assert!(Exp::profile_ci(&DATA, 0, 1.0).is_err());
+        assert!(Exp::profile_ci(&DATA, 0, f64::NAN).is_err());
+        assert!(Exp::profile_ci(&[], 0, 0.95).is_err());
+    }
+
+    #[test]
+    fn test_fit_censored() {
+        // the shared likelihood reproduces the Weibull censored fit
+        let censored = [false, false, false, true, true, false, false, true, false, false];
+        let observations: Vec<Observation> = DATA
+            .iter()
+            .zip(&censored)
+            .map(|(&x, &c)| if c { Observation::RightCensored(x) } else { Observation::Exact(x) })
+            .collect();
+        let weibull = fit_censored(&observations, &Weibull::new(1.0, 1.0).unwrap()).unwrap();
+        assert_almost_eq!(weibull.shape(), 1.409248999727687, 1e-6);
+        assert_almost_eq!(weibull.scale(), 2.140699319502732, 1e-6);
+        // exact observations give the plain maximum likelihood fit
+        let exact: Vec<Observation> = DATA.iter().map(|&x| Observation::Exact(x)).collect();
+        let gamma = fit_censored(&exact, &Gamma::new(1.0, 1.0).unwrap()).unwrap();
+        assert_almost_eq!(gamma.shape(), 2.129714924806267, 1e-6);
+        assert_almost_eq!(gamma.rate(), 1.397450738061855, 1e-6);
+
+        // values below a detection limit of 4 and readings rounded to
+        // integers still recover the parameters of a large sample
+        let mut rng = StdRng::seed_from_u64(8);
+        let normal = Normal::new(5.0, 2.0).unwrap();
+        let observations: Vec<Observation> = (0..4000)
+            .map(|_| {
+                let x: f64 = normal.sample(&mut rng);
+                if x <= 4.0 {
+                    Observation::LeftCensored(4.0)
+                } else {
+                    Observation::IntervalCensored(x.ceil() - 1.0, x.ceil())
+                }
+            })
+            .collect();
+        let fitted = fit_censored(&observations, &Normal::new(0.0, 1.0).unwrap()).unwrap();
+        assert!((fitted.mean().unwrap() - 5.0).abs() < 0.1);
+        assert!((fitted.std_dev().unwrap() - 2.0).abs() < 0.1);
+
+        assert!(fit_censored(&[], &Exp::new(1.0).unwrap()).is_err());
+        assert!(fit_censored(&[Observation::Exact(f64::NAN)], &Exp::new(1.0).unwrap()).is_err());
+        assert!(fit_censored(&[Observation::IntervalCensored(2.0, 1.0)], &Exp::new(1.0).unwrap()).is_err());
+        // the start must give the data a positive likelihood
+        assert!(fit_censored(&[Observation::Exact(2.0)], &Uniform::new(0.0, 1.0).unwrap()).is_err());
+    }
+}
@@ -1,9 +1,16 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{
+    fit, CharacteristicFunction, Continuous, ContinuousCDF, Fit, KullbackLeibler, Parameterized,
+    SampleBatch,
+};
+use crate::fp;
 use crate::function::gamma;
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use core::f64::INFINITY as INF;
+use num_complex::Complex;
+use rand::distributions::Open01;
 use rand::Rng;
+use std::f64;
 
 /// Implements the [Gamma](https://en.wikipedia.org/wiki/Gamma_distribution)
 /// distribution
@@ -57,6 +64,19 @@ impl Gamma {
         Ok(Gamma { shape, rate })
     }
 
+    /// Constructs a new gamma distribution from parameters already known to be
+    /// valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(shape: f64, rate: f64) -> Gamma {
+        debug_assert!(Gamma::new(shape, rate).is_ok(), "invalid gamma parameters");
+        Gamma { shape, rate }
+    }
+
     /// Returns the shape (α) of the gamma distribution
     ///
     /// # Examples
@@ -84,6 +104,41 @@ impl Gamma {
     pub fn rate(&self) -> f64 {
         self.rate
     }
+
+    /// Fits a gamma distribution to `data` by the method of moments, matching
+    /// the mean `kθ` and variance `kθ²`, which gives the shape `mean² / var`
+    /// and the rate `mean / var` with the variance taken with the divisor `n`
+    ///
+    /// # Remarks
+    ///
+    /// The estimates are less efficient than those of `fit_mle` but are
+    /// found in closed form and, unlike the maximum likelihood estimates,
+    /// exist for samples containing zeros, so they serve as starting values
+    /// and as a fallback when the likelihood equations fail to converge.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty, has a negative or non-finite
+    /// observation, or has zero variance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Fit, Gamma};
+    ///
+    /// let data = [0.0, 1.0, 2.0, 3.0];
+    /// let gamma = Gamma::fit_mle(&data).or_else(|_| Gamma::fit_moments(&data)).unwrap();
+    /// assert_eq!(gamma.shape(), 1.8);
+    /// assert_eq!(gamma.rate(), 1.2);
+    /// ```
+    pub fn fit_moments(data: &[f64]) -> Result<Gamma> {
+        fit::check_sample(data, |x| x >= 0.0)?;
+        let (mean, variance) = fit::mean_variance(data);
+        if variance <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        Gamma::new(mean * mean / variance, mean / variance)
+    }
 }
 
 impl ::rand::distributions::Distribution<f64> for Gamma {
@@ -118,6 +173,31 @@ impl ContinuousCDF<f64, f64> for Gamma {
             gamma::gamma_lr(self.shape, x * self.rate)
         }
     }
+
+    /// Calculates the survival function for the gamma
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 / Γ(α)) * Γ(α, β * x)
+    /// ```
+    ///
+    /// where `α` is the shape, `β` is the rate, `Γ` is the gamma function,
+    /// and `Γ(a, x)` is the upper incomplete gamma function
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else if ulps_eq!(x, self.shape) && self.rate.is_infinite() {
+            0.0
+        } else if self.rate.is_infinite() {
+            1.0
+        } else if x.is_infinite() {
+            0.0
+        } else {
+            gamma::gamma_ur(self.shape, x * self.rate)
+        }
+    }
 }
 
 impl Min<f64> for Gamma {
@@ -186,7 +266,7 @@ impl Distribution<f64> for Gamma {
     /// where `α` is the shape, `β` is the rate, `Γ` is the gamma function,
     /// and `ψ` is the digamma function
     fn entropy(&self) -> Option<f64> {
-        let entr = self.shape - self.rate.ln()
+        let entr = self.shape - fp::ln(self.rate)
             + gamma::ln_gamma(self.shape)
             + (1.0 - self.shape) * gamma::digamma(self.shape);
         Some(entr)
@@ -203,6 +283,65 @@ impl Distribution<f64> for Gamma {
     fn skewness(&self) -> Option<f64> {
         Some(2.0 / self.shape.sqrt())
     }
+    /// Returns the raw moment of order `k` of the gamma distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// prod_{i = 0}^{k - 1} (α + i) / β
+    /// ```
+    ///
+    /// where `α` is the shape and `β` is the rate
+    fn moment(&self, k: u32) -> Option<f64> {
+        Some(
+            (0..k)
+                .map(|i| (self.shape + i as f64) / self.rate)
+                .product(),
+        )
+    }
+    /// Returns the cumulant of order `k` of the gamma distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// κ_k = α * (k - 1)! / β^k for k > 0
+    /// ```
+    ///
+    /// where `α` is the shape and `β` is the rate
+    fn cumulant(&self, k: u32) -> Option<f64> {
+        if k == 0 {
+            return Some(0.0);
+        }
+        let factorial: f64 = (1..k).map(|i| i as f64 / self.rate).product();
+        Some(self.shape * factorial / self.rate)
+    }
+}
+
+impl Median<f64> for Gamma {
+    /// Returns the median of the gamma distribution
+    ///
+    /// # Remarks
+    ///
+    /// Uses the closed form of the exponential distribution if the shape is
+    /// one and returns the mean if the shape or rate is `INF`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if α = 1: ln(2) / β
+    /// else: F^-1(1 / 2)
+    /// ```
+    ///
+    /// where `α` is the shape, `β` is the rate and `F` is the cdf
+    fn median(&self) -> f64 {
+        if self.shape.is_infinite() || self.rate.is_infinite() {
+            self.shape / self.rate
+        } else if self.shape == 1.0 {
+            f64::consts::LN_2 / self.rate
+        } else {
+            self.inverse_cdf(0.5)
+        }
+    }
 }
 
 impl Mode<Option<f64>> for Gamma {
@@ -240,13 +379,15 @@ impl Continuous<f64, f64> for Gamma {
         if x < 0.0 {
             0.0
         } else if ulps_eq!(self.shape, 1.0) {
-            self.rate * (-self.rate * x).exp()
+            self.rate * fp::exp(-self.rate * x)
         } else if self.shape > 160.0 {
-            self.ln_pdf(x).exp()
+            fp::exp(self.ln_pdf(x))
         } else if x.is_infinite() {
             0.0
         } else {
-            self.rate.powf(self.shape) * x.powf(self.shape - 1.0) * (-self.rate * x).exp()
+            fp::powf(self.rate, self.shape)
+                * fp::powf(x, self.shape - 1.0)
+                * fp::exp(-self.rate * x)
                 / gamma::gamma(self.shape)
         }
     }
@@ -271,11 +412,11 @@ impl Continuous<f64, f64> for Gamma {
         if x < 0.0 {
             f64::NEG_INFINITY
         } else if ulps_eq!(self.shape, 1.0) {
-            self.rate.ln() - self.rate * x
+            fp::ln(self.rate) - self.rate * x
         } else if x.is_infinite() {
             f64::NEG_INFINITY
         } else {
-            self.shape * self.rate.ln() + (self.shape - 1.0) * x.ln()
+            self.shape * fp::ln(self.rate) + (self.shape - 1.0) * fp::ln(x)
                 - self.rate * x
                 - gamma::ln_gamma(self.shape)
         }
@@ -292,34 +433,244 @@ impl Continuous<f64, f64> for Gamma {
 /// Pages 363-372
 /// </div>
 /// <br />
+/// For a shape `α < 1` a sample `Y` of shape `α + 1` is drawn instead and
+/// transformed to `Y U^(1 / α)` for a uniform `U`, which keeps the
+/// acceptance rate above 95% for any shape. The transformation is done on
+/// the log scale so that it does not underflow before the final result.
 pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, shape: f64, rate: f64) -> f64 {
-    let mut a = shape;
-    let mut afix = 1.0;
-    if shape < 1.0 {
-        a = shape + 1.0;
-        afix = rng.gen::<f64>().powf(1.0 / shape);
-    }
-
-    let d = a - 1.0 / 3.0;
-    let c = 1.0 / (9.0 * d).sqrt();
-    loop {
-        let mut x;
-        let mut v;
+    ::rand::distributions::Distribution::sample(&GammaSampler::new(shape, rate), rng)
+}
+
+/// A sampler for the gamma distribution which computes the constants of
+/// the Marsaglia & Tsang method once, for drawing many samples
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Gamma, SampleBatch};
+///
+/// let n = Gamma::new(3.0, 2.0).unwrap();
+/// let mut samples = vec![0.0; 1000];
+/// n.sample_fill(&mut rand::thread_rng(), &mut samples);
+/// assert!(samples.iter().all(|&x| x > 0.0));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GammaSampler {
+    shape: f64,
+    rate: f64,
+    d: f64,
+    c: f64,
+}
+
+impl GammaSampler {
+    pub(crate) fn new(shape: f64, rate: f64) -> GammaSampler {
+        let a = if shape < 1.0 { shape + 1.0 } else { shape };
+        let d = a - 1.0 / 3.0;
+        GammaSampler {
+            shape,
+            rate,
+            d,
+            c: 1.0 / (9.0 * d).sqrt(),
+        }
+    }
+}
+
+impl GammaSampler {
+    /// Returns `true` if the sampler transforms samples of shape `α + 1`
+    pub(crate) fn boosted(&self) -> bool {
+        self.shape < 1.0
+    }
+
+    /// Draws the log of a sample, which is finite even for shapes so small
+    /// that the sample itself underflows to zero
+    pub(crate) fn sample_ln<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let ln_boost = if self.boosted() {
+            let u: f64 = rng.sample(Open01);
+            fp::ln(u) / self.shape
+        } else {
+            0.0
+        };
+        fp::ln(self.sample_boosted(rng) / self.rate) + ln_boost
+    }
+
+    /// Draws a sample of rate one and shape `α`, or `α + 1` if `α < 1`, by
+    /// the method of Marsaglia & Tsang
+    fn sample_boosted<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let (d, c) = (self.d, self.c);
         loop {
-            x = super::normal::sample_unchecked(rng, 0.0, 1.0);
-            v = 1.0 + c * x;
-            if v > 0.0 {
-                break;
-            };
+            let mut x;
+            let mut v;
+            loop {
+                x = super::normal::sample_unchecked(rng, 0.0, 1.0);
+                v = 1.0 + c * x;
+                if v > 0.0 {
+                    break;
+                };
+            }
+
+            v *= v * v;
+            x *= x;
+            let u: f64 = rng.gen();
+            if u < 1.0 - 0.0331 * x * x || fp::ln(u) < 0.5 * x + d * (1.0 - v + fp::ln(v)) {
+                return d * v;
+            }
+        }
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for GammaSampler {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        if self.boosted() {
+            fp::exp(self.sample_ln(rng))
+        } else {
+            self.sample_boosted(rng) / self.rate
+        }
+    }
+}
+
+impl SampleBatch<f64> for Gamma {
+    type Sampler = GammaSampler;
+
+    fn sampler(&self) -> GammaSampler {
+        GammaSampler::new(self.shape, self.rate)
+    }
+}
+
+impl CharacteristicFunction<f64, f64> for Gamma {
+    /// Calculates the characteristic function for the gamma
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 - it / β)^(-α)
+    /// ```
+    ///
+    /// where `α` is the shape and `β` is the rate
+    fn cf(&self, t: f64) -> Complex<f64> {
+        Complex::new(1.0, -t / self.rate).powf(-self.shape)
+    }
+}
+
+impl KullbackLeibler for Gamma {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// gamma distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (α_p - α_q) ψ(α_p) - ln(Γ(α_p)) + ln(Γ(α_q)) + α_q ln(β_p / β_q) + α_p (β_q - β_p) / β_p
+    /// ```
+    ///
+    /// where `α` is the shape, `β` the rate, `Γ` the gamma function and `ψ`
+    /// the digamma function
+    fn kl_divergence(&self, other: &Gamma) -> f64 {
+        (self.shape - other.shape) * gamma::digamma(self.shape) - gamma::ln_gamma(self.shape)
+            + gamma::ln_gamma(other.shape)
+            + other.shape * fp::ln(self.rate / other.rate)
+            + self.shape * (other.rate - self.rate) / self.rate
+    }
+}
+
+impl Parameterized for Gamma {
+    fn param_names() -> &'static [&'static str] {
+        &["shape", "rate"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.shape, self.rate]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Gamma> {
+        match *params {
+            [shape, rate] => Gamma::new(shape, rate),
+            _ => Err(StatsError::ContainersMustBeSameLength),
         }
+    }
+}
+
+impl Fit for Gamma {
+    type Sample = f64;
 
-        v *= v * v;
-        x *= x;
-        let u: f64 = rng.gen();
-        if u < 1.0 - 0.0331 * x * x || u.ln() < 0.5 * x + d * (1.0 - v + v.ln()) {
-            return afix * d * v / rate;
+    /// Fits the shape `k` solving `ln(k) - ψ(k) = s` for
+    /// `s = ln(mean(x)) - mean(ln(x))`, and the rate `k / mean(x)`
+    ///
+    /// # Remarks
+    ///
+    /// The shape equation is solved by Newton's method in `1 / k`, the
+    /// generalized Newton iteration of Minka (2002), in which the left-hand
+    /// side is nearly linear so that a few steps from Minka's initial
+    /// approximation reach full precision for any shape. For near-constant
+    /// samples `s` is the small difference of two logarithms and `k` is
+    /// large, so `s` is accumulated as `mean(d - ln(1 + d))` over the
+    /// relative deviations `d = (x - mean(x)) / mean(x)` and `ln(k) - ψ(k)`
+    /// is evaluated by its asymptotic expansion, avoiding cancellation in
+    /// both. A sample without variance has no estimate.
+    fn fit_mle(data: &[f64]) -> Result<Gamma> {
+        fit::check_sample(data, |x| x > 0.0)?;
+        let mean = fit::mean(data);
+        let s = data
+            .iter()
+            .map(|&x| deviation_minus_ln_1p((x - mean) / mean))
+            .sum::<f64>()
+            / data.len() as f64;
+        if s <= 0.0 {
+            return Err(StatsError::BadParams);
         }
+        let k0 = (3.0 - s + ((s - 3.0) * (s - 3.0) + 24.0 * s).sqrt()) / (12.0 * s);
+        let inv_shape = fit::newton_positive(
+            |u| {
+                let k = 1.0 / u;
+                let (y, dy) = ln_minus_digamma(k);
+                (y - s, -dy * k * k)
+            },
+            1.0 / k0,
+        )?;
+        let shape = 1.0 / inv_shape;
+        Gamma::new(shape, shape / mean)
+    }
+}
+
+/// Returns `d - ln(1 + d)`, by its Taylor series for small `|d|` where the
+/// difference cancels
+fn deviation_minus_ln_1p(d: f64) -> f64 {
+    if d.abs() < 0.1 {
+        // d² (1/2 - d/3 + d²/4 - ...), accurate to the 18th order
+        d * d * (2..19).rev().fold(0.0, |acc, j| 1.0 / j as f64 - d * acc)
+    } else {
+        d - fp::ln_1p(d)
+    }
+}
+
+/// Returns `ln(k) - ψ(k)` and its derivative `1 / k - ψ'(k)`, by the
+/// asymptotic expansion in the Bernoulli numbers for `k >= 10`, where the
+/// direct difference loses the relative precision of the small result
+fn ln_minus_digamma(k: f64) -> (f64, f64) {
+    if k < 10.0 {
+        return (fp::ln(k) - gamma::digamma(k), 1.0 / k - gamma::trigamma(k));
     }
+    // the Bernoulli numbers B_2j in the terms B_2j / (2j k^2j)
+    let bernoulli = [
+        1.0 / 6.0,
+        -1.0 / 30.0,
+        1.0 / 42.0,
+        -1.0 / 30.0,
+        5.0 / 66.0,
+        -691.0 / 2730.0,
+        7.0 / 6.0,
+    ];
+    let r = 1.0 / k;
+    let r2 = r * r;
+    let (value, derivative) =
+        bernoulli
+            .iter()
+            .enumerate()
+            .rev()
+            .fold((0.0, 0.0), |(value, derivative), (j, b)| {
+                let order = 2.0 * (j + 1) as f64;
+                (value * r2 + b / order, derivative * r2 + b)
+            });
+    (0.5 * r + r2 * value, -0.5 * r2 - r * r2 * derivative)
 }
 
 #[cfg(all(test, feature = "nightly"))]
@@ -361,6 +712,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_new_unchecked() {
+        for &(shape, rate) in [(1.0, 0.1), (10.0, 1.0), (10.0, INF)].iter() {
+            assert_eq!(
+                Gamma::new_unchecked(shape, rate),
+                Gamma::new(shape, rate).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "invalid gamma parameters")]
+    fn test_new_unchecked_invalid() {
+        Gamma::new_unchecked(-1.0, 1.0);
+    }
+
     #[test]
     fn test_mean() {
         let f = |x: Gamma| x.mean().unwrap();
@@ -434,6 +802,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_median() {
+        let median = |x: Gamma| x.median();
+        test_case((1.0, 2.0), f64::consts::LN_2 / 2.0, median);
+        test_case_special((3.0, 2.0), 1.3370301568617801589567286, 1e-14, median);
+        test_case_special((0.1, 1.0), 0.00059339110446022594266362998, 1e-16, median);
+        test_case((10.0, INF), 0.0, median);
+        test_case((INF, 1.0), INF, median);
+    }
+
     #[test]
     fn test_min_max() {
         let f = |x: Gamma| x.min();
@@ -529,6 +907,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sf() {
+        let f = |arg: f64| move |x: Gamma| x.sf(arg);
+        let test = [
+            ((1.0, 0.1), 0.0, 1.0),
+            ((10.0, INF), 1.0, 1.0),
+            ((10.0, INF), 10.0, 0.0),
+        ];
+        for &(arg, x, res) in test.iter() {
+            test_case(arg, res, f(x));
+        }
+        test_case_special((1.0, 1.0), 1.9287498479639178e-22, 1e-35, f(50.0));
+        test_case_special((10.0, 1.0), 7.121750862815577e-06, 1e-18, f(30.0));
+        test_case_special((10.0, 1.0), 0.457929714471852208314165, 1e-15, f(10.0));
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        for &arg in &[(1.0, 0.1), (1.0, 1.0), (10.0, 10.0), (0.5, 2.0)] {
+            let n = try_create(arg);
+            for &p in &[1e-6, 0.01, 0.25, 0.5, 0.9, 0.999] {
+                let x = n.inverse_cdf(p);
+                assert_almost_eq!(n.cdf(x), p, 1e-14 * p.max(0.01));
+            }
+        }
+        // the exponential special case has a closed form quantile
+        let n = try_create((1.0, 1.0));
+        assert_almost_eq!(n.inverse_cdf(0.5), 2f64.ln(), 1e-15);
+        test_case((1.0, 1.0), 0.0, |x| x.inverse_cdf(0.0));
+        test_case((1.0, 1.0), INF, |x| x.inverse_cdf(1.0));
+    }
+
     #[test]
     fn test_cdf_at_zero() {
         test_case((1.0, 0.1), 0.0, |x| x.cdf(0.0));
@@ -539,4 +949,82 @@ mod tests {
         test::check_continuous_distribution(&try_create((1.0, 0.5)), 0.0, 20.0);
         test::check_continuous_distribution(&try_create((9.0, 2.0)), 0.0, 20.0);
     }
+
+    #[test]
+    fn test_sample_moments() {
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(1);
+        for &(shape, rate) in &[
+            (0.05f64, 1.0f64),
+            (0.3, 2.0),
+            (0.9, 0.5),
+            (1.0, 1.0),
+            (4.5, 3.0),
+        ] {
+            let n = try_create((shape, rate));
+            let x: Vec<f64> = (0..100000).map(|_| n.sample(&mut rng)).collect();
+            assert!(x.iter().all(|&x| x >= 0.0 && x.is_finite()));
+            let mean = x.iter().sum::<f64>() / 100000.0;
+            let var = x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 99999.0;
+            // the standard error of the variance grows with the kurtosis
+            // 6 / shape
+            let sd = (shape / rate.powi(2)).sqrt();
+            assert!((mean - n.mean().unwrap()).abs() < 5.0 * sd / 100000f64.sqrt());
+            assert!(
+                (var / n.variance().unwrap() - 1.0).abs()
+                    < 5.0 * ((2.0 + 6.0 / shape) / 100000.0).sqrt()
+            );
+            // E[ln X] = ψ(α) - ln β tests the lower tail, where the variance
+            // ψ'(α) of ln X is below 1 / α² + π² / 6
+            let ln_mean = x.iter().map(|x| x.ln()).sum::<f64>() / 100000.0;
+            let expected = gamma::digamma(shape) - rate.ln();
+            assert!((ln_mean - expected).abs() < 5.0 * ((shape.powi(-2) + 2.0) / 100000.0).sqrt());
+        }
+    }
+
+    #[test]
+    fn test_sample_tiny_shape() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        // samples of shape 0.001 underflow to zero most of the time, while
+        // their logs stay finite with mean ψ(α) - ln β
+        let sampler = GammaSampler::new(0.001, 2.0);
+        let mut rng = StdRng::seed_from_u64(2);
+        let x: Vec<f64> = (0..20000).map(|_| sampler.sample_ln(&mut rng)).collect();
+        assert!(x.iter().all(|x| x.is_finite()));
+        let ln_mean = x.iter().sum::<f64>() / 20000.0;
+        let expected = gamma::digamma(0.001) - 2f64.ln();
+        assert!((ln_mean - expected).abs() < 5.0 * ((1e6f64 + 2.0) / 20000.0).sqrt());
+    }
+
+    #[test]
+    fn test_cf() {
+        assert_eq!(try_create((3.5, 2.0)).cf(0.0), Complex::new(1.0, 0.0));
+        let phi = try_create((3.5, 2.0)).cf(1.5);
+        assert_almost_eq!(phi.re, -0.28847241382371386, 1e-14);
+        assert_almost_eq!(phi.im, 0.35566679134088414, 1e-14);
+    }
+
+    #[test]
+    fn test_moment() {
+        let n = try_create((3.5, 2.0));
+        assert_eq!(n.moment(2).unwrap(), 3.9375);
+        assert_eq!(n.moment(3).unwrap(), 10.828125);
+        // 2α / β^3 and 3α(α + 2) / β^4
+        assert_almost_eq!(n.central_moment(3).unwrap(), 0.875, 1e-14);
+        assert_almost_eq!(n.central_moment(4).unwrap(), 3.609375, 1e-14);
+    }
+
+    #[test]
+    fn test_cumulant() {
+        let n = try_create((3.5, 2.0));
+        assert_eq!(n.cumulant(1).unwrap(), 1.75);
+        assert_eq!(n.cumulant(2).unwrap(), 0.875);
+        assert_eq!(n.cumulant(3).unwrap(), 0.875);
+        assert_eq!(n.cumulant(4).unwrap(), 1.3125);
+    }
 }
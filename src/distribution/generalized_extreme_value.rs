@@ -0,0 +1,623 @@
+use crate::consts::EULER_MASCHERONI;
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
+use crate::fp;
+use crate::function::{gamma, optimize};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::distributions::Open01;
+use rand::Rng;
+use std::f64;
+
+/// The skewness `12 sqrt(6) ζ(3) / π^3` of the Gumbel case `ξ = 0`
+const GUMBEL_SKEWNESS: f64 = 1.1395470994046486574927930193898461;
+
+/// Shapes closer to zero than this are fit with the limiting Gumbel
+/// formulas, which the general ones only approach through cancellation
+const GUMBEL_SHAPE: f64 = 1e-8;
+
+/// Implements the [generalized extreme
+/// value](https://en.wikipedia.org/wiki/Generalized_extreme_value_distribution)
+/// distribution, the limit distribution of the normalized maxima of
+/// samples, which unifies the Gumbel (`ξ = 0`), Fréchet (`ξ > 0`) and
+/// reversed Weibull (`ξ < 0`) families
+///
+/// The shape `ξ` follows the sign convention in which a positive shape
+/// gives a heavy upper tail, the negative of the `k` used by Hosking
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, GeneralizedExtremeValue};
+///
+/// let n = GeneralizedExtremeValue::new(0.0, 1.0, 0.5).unwrap();
+/// assert_eq!(n.cdf(0.0), (-1f64).exp());
+/// // the support of a positive shape is bounded below by μ - σ / ξ
+/// assert_eq!(n.cdf(-2.0), 0.0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GeneralizedExtremeValue {
+    location: f64,
+    scale: f64,
+    shape: f64,
+}
+
+impl GeneralizedExtremeValue {
+    /// Constructs a new generalized extreme value distribution with the
+    /// given location, scale and shape
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if location or shape are not finite or scale is not
+    /// positive and finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    ///
+    /// let mut result = GeneralizedExtremeValue::new(0.0, 1.0, -0.2);
+    /// assert!(result.is_ok());
+    ///
+    /// result = GeneralizedExtremeValue::new(0.0, 0.0, -0.2);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(location: f64, scale: f64, shape: f64) -> Result<GeneralizedExtremeValue> {
+        if !(location.is_finite() && shape.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        if !(scale.is_finite() && scale > 0.0) {
+            return Err(StatsError::ArgMustBePositive("scale"));
+        }
+        Ok(GeneralizedExtremeValue {
+            location,
+            scale,
+            shape,
+        })
+    }
+
+    /// Estimates the generalized extreme value distribution `data` was
+    /// drawn from by matching its first three L-moments to those of the
+    /// sample, following Hosking, Wallis and Wood (1985)
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// τ_3 = 2 (1 - 3^(-k)) / (1 - 2^(-k)) - 3
+    /// σ = λ_2 k / ((1 - 2^(-k)) Γ(1 + k))
+    /// μ = λ_1 - σ (1 - Γ(1 + k)) / k
+    /// ξ = -k
+    /// ```
+    ///
+    /// where `λ_1`, `λ_2` and `τ_3` are the sample L-moments and L-skewness,
+    /// the first equation is solved numerically for `k`, and `μ`, `σ` and
+    /// `ξ` are the location, scale and shape
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample L-moments of `data` cannot be
+    /// computed or its L-skewness is too close to `-1` to be matched
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    ///
+    /// let data = [2.1, 3.5, 1.7, 2.9, 5.2, 2.4, 3.1, 4.0, 9.6, 2.7];
+    /// let n = GeneralizedExtremeValue::fit_l_moments(&data).unwrap();
+    /// // the skewed sample is fit with a heavy upper tail
+    /// assert!(n.shape() > 0.0);
+    /// ```
+    pub fn fit_l_moments(data: &[f64]) -> Result<GeneralizedExtremeValue> {
+        let l = l_moments(data)?;
+        let t3 = l.l_skewness();
+        // τ_3 decreases from 1 at k = -1 towards -1 as k grows
+        let k = optimize::brent_root(|k| l_skewness(k) - t3, -1.0, 50.0, 1e-14)?;
+        if k.abs() < GUMBEL_SHAPE {
+            let scale = l.l2() / f64::consts::LN_2;
+            return GeneralizedExtremeValue::new(l.l1() - EULER_MASCHERONI * scale, scale, 0.0);
+        }
+        let g = gamma::gamma(1.0 + k);
+        let scale = l.l2() * k / (-fp::exp_m1(-k * f64::consts::LN_2) * g);
+        GeneralizedExtremeValue::new(l.l1() - scale * (1.0 - g) / k, scale, -k)
+    }
+
+    /// Returns the location of the generalized extreme value distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    ///
+    /// let n = GeneralizedExtremeValue::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.location(), 1.0);
+    /// ```
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale of the generalized extreme value distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    ///
+    /// let n = GeneralizedExtremeValue::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.scale(), 2.0);
+    /// ```
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the shape of the generalized extreme value distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    ///
+    /// let n = GeneralizedExtremeValue::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.shape(), 0.1);
+    /// ```
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns whether `x` is outside the support
+    fn outside(&self, x: f64) -> bool {
+        x < self.min() || x > self.max()
+    }
+
+    /// Returns `ln t(x)`, where `t(x) = (1 + ξ z)^(-1 / ξ)` and the cdf is
+    /// `exp(-t(x))`
+    fn ln_t(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+        if self.shape == 0.0 {
+            return -z;
+        }
+        let y = self.shape * z;
+        if y <= -1.0 {
+            // below the lower bound of a positive shape or above the upper
+            // bound of a negative one
+            if self.shape > 0.0 {
+                f64::INFINITY
+            } else {
+                f64::NEG_INFINITY
+            }
+        } else {
+            -fp::ln_1p(y) / self.shape
+        }
+    }
+
+    /// Returns `Γ(1 - k ξ)`, which exists for `k ξ < 1`
+    fn g(&self, k: f64) -> f64 {
+        gamma::gamma(1.0 - k * self.shape)
+    }
+}
+
+/// Returns the L-skewness of a generalized extreme value distribution with
+/// shape `-k`
+fn l_skewness(k: f64) -> f64 {
+    if k == 0.0 {
+        2.0 * fp::ln(3f64) / f64::consts::LN_2 - 3.0
+    } else {
+        2.0 * fp::exp_m1(-k * fp::ln(3f64)) / fp::exp_m1(-k * f64::consts::LN_2) - 3.0
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for GeneralizedExtremeValue {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.inverse_cdf(rng.sample(Open01))
+    }
+}
+
+impl ContinuousCDF<f64, f64> for GeneralizedExtremeValue {
+    /// Calculates the cumulative distribution function for the generalized
+    /// extreme value distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// exp(-(1 + ξ (x - μ) / σ)^(-1 / ξ))
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `exp(-exp(-(x - μ) / σ))` for `ξ = 0`
+    fn cdf(&self, x: f64) -> f64 {
+        fp::exp(-fp::exp(self.ln_t(x)))
+    }
+
+    /// Calculates the survival function for the generalized extreme value
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 1 - exp(-(1 + ξ (x - μ) / σ)^(-1 / ξ))
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `1 - exp(-exp(-(x - μ) / σ))` for `ξ = 0`
+    fn sf(&self, x: f64) -> f64 {
+        -fp::exp_m1(-fp::exp(self.ln_t(x)))
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// generalized extreme value distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + σ ((-ln(p))^(-ξ) - 1) / ξ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `μ - σ ln(-ln(p))` for `ξ = 0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let y = fp::ln(-fp::ln(p));
+        if self.shape == 0.0 {
+            self.location - self.scale * y
+        } else {
+            self.location + self.scale * fp::exp_m1(-self.shape * y) / self.shape
+        }
+    }
+}
+
+impl Min<f64> for GeneralizedExtremeValue {
+    /// Returns the minimum value in the domain of the generalized extreme
+    /// value distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ > 0 {
+    ///     μ - σ / ξ
+    /// } else {
+    ///     NEG_INF
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape
+    fn min(&self) -> f64 {
+        if self.shape > 0.0 {
+            self.location - self.scale / self.shape
+        } else {
+            f64::NEG_INFINITY
+        }
+    }
+}
+
+impl Max<f64> for GeneralizedExtremeValue {
+    /// Returns the maximum value in the domain of the generalized extreme
+    /// value distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ < 0 {
+    ///     μ - σ / ξ
+    /// } else {
+    ///     INF
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape
+    fn max(&self) -> f64 {
+        if self.shape < 0.0 {
+            self.location - self.scale / self.shape
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+impl Distribution<f64> for GeneralizedExtremeValue {
+    /// Returns the mean of the generalized extreme value distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ >= 1 {
+    ///     None
+    /// } else {
+    ///     μ + σ (Γ(1 - ξ) - 1) / ξ
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale, `ξ` is the shape and `Γ`
+    /// is the gamma function, with the limit `μ + γ σ` for `ξ = 0` where `γ`
+    /// is the Euler-Mascheroni constant
+    fn mean(&self) -> Option<f64> {
+        if self.shape >= 1.0 {
+            None
+        } else if self.shape == 0.0 {
+            Some(self.location + EULER_MASCHERONI * self.scale)
+        } else {
+            Some(self.location + self.scale * (self.g(1.0) - 1.0) / self.shape)
+        }
+    }
+    /// Returns the variance of the generalized extreme value distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ >= 1 / 2 {
+    ///     None
+    /// } else {
+    ///     σ^2 (g_2 - g_1^2) / ξ^2
+    /// }
+    /// ```
+    ///
+    /// where `σ` is the scale, `ξ` is the shape and `g_k = Γ(1 - k ξ)`, with
+    /// the limit `π^2 σ^2 / 6` for `ξ = 0`
+    fn variance(&self) -> Option<f64> {
+        if self.shape >= 0.5 {
+            None
+        } else if self.shape == 0.0 {
+            let s = f64::consts::PI * self.scale;
+            Some(s * s / 6.0)
+        } else {
+            let g1 = self.g(1.0);
+            let s = self.scale / self.shape;
+            Some(s * s * (self.g(2.0) - g1 * g1))
+        }
+    }
+    /// Returns the entropy of the generalized extreme value distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(σ) + γ ξ + γ + 1
+    /// ```
+    ///
+    /// where `σ` is the scale, `ξ` is the shape and `γ` is the
+    /// Euler-Mascheroni constant
+    fn entropy(&self) -> Option<f64> {
+        Some(fp::ln(self.scale) + EULER_MASCHERONI * (self.shape + 1.0) + 1.0)
+    }
+    /// Returns the skewness of the generalized extreme value distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ >= 1 / 3 {
+    ///     None
+    /// } else {
+    ///     sgn(ξ) (g_3 - 3 g_1 g_2 + 2 g_1^3) / (g_2 - g_1^2)^(3 / 2)
+    /// }
+    /// ```
+    ///
+    /// where `ξ` is the shape and `g_k = Γ(1 - k ξ)`, with the limit
+    /// `12 sqrt(6) ζ(3) / π^3` for `ξ = 0`
+    fn skewness(&self) -> Option<f64> {
+        if self.shape >= 1.0 / 3.0 {
+            None
+        } else if self.shape == 0.0 {
+            Some(GUMBEL_SKEWNESS)
+        } else {
+            let (g1, g2, g3) = (self.g(1.0), self.g(2.0), self.g(3.0));
+            let skewness = (g3 - 3.0 * g1 * g2 + 2.0 * g1 * g1 * g1) / fp::powf(g2 - g1 * g1, 1.5);
+            Some(skewness * self.shape.signum())
+        }
+    }
+}
+
+impl Median<f64> for GeneralizedExtremeValue {
+    /// Returns the median of the generalized extreme value distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + σ (ln(2)^(-ξ) - 1) / ξ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `μ - σ ln(ln(2))` for `ξ = 0`
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Mode<Option<f64>> for GeneralizedExtremeValue {
+    /// Returns the mode of the generalized extreme value distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ <= -1 {
+    ///     μ - σ / ξ
+    /// } else {
+    ///     μ + σ ((1 + ξ)^(-ξ) - 1) / ξ
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `μ` for `ξ = 0`
+    fn mode(&self) -> Option<f64> {
+        if self.shape <= -1.0 {
+            // the density increases up to the upper bound of the support
+            Some(self.max())
+        } else if self.shape == 0.0 {
+            Some(self.location)
+        } else {
+            let y = -self.shape * fp::ln_1p(self.shape);
+            Some(self.location + self.scale * fp::exp_m1(y) / self.shape)
+        }
+    }
+}
+
+impl Continuous<f64, f64> for GeneralizedExtremeValue {
+    /// Calculates the probability density function for the generalized
+    /// extreme value distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// t^(ξ + 1) exp(-t) / σ,    t = (1 + ξ (x - μ) / σ)^(-1 / ξ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with `t = exp(-(x - μ) / σ)` for `ξ = 0`
+    fn pdf(&self, x: f64) -> f64 {
+        fp::exp(self.ln_pdf(x))
+    }
+
+    /// Calculates the log probability density function for the generalized
+    /// extreme value distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (ξ + 1) ln(t) - t - ln(σ),    t = (1 + ξ (x - μ) / σ)^(-1 / ξ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with `t = exp(-(x - μ) / σ)` for `ξ = 0`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if self.outside(x) || x.is_infinite() {
+            return f64::NEG_INFINITY;
+        }
+        let ln_t = self.ln_t(x);
+        (self.shape + 1.0) * ln_t - fp::exp(ln_t) - fp::ln(self.scale)
+    }
+}
+
+impl Parameterized for GeneralizedExtremeValue {
+    fn param_names() -> &'static [&'static str] {
+        &["location", "scale", "shape"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.location, self.scale, self.shape]
+    }
+
+    fn from_params(params: &[f64]) -> Result<GeneralizedExtremeValue> {
+        match *params {
+            [location, scale, shape] => GeneralizedExtremeValue::new(location, scale, shape),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::statistics::*;
+    use crate::distribution::{Continuous, ContinuousCDF, GeneralizedExtremeValue, Gumbel};
+    use crate::distribution::internal::*;
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn try_create(location: f64, scale: f64, shape: f64) -> GeneralizedExtremeValue {
+        GeneralizedExtremeValue::new(location, scale, shape).unwrap()
+    }
+
+    #[test]
+    fn test_create() {
+        let n = try_create(1.0, 2.0, 0.5);
+        assert_eq!((n.location(), n.scale(), n.shape()), (1.0, 2.0, 0.5));
+        assert_eq!((n.min(), n.max()), (-3.0, f64::INFINITY));
+        let n = try_create(1.0, 2.0, -0.5);
+        assert_eq!((n.min(), n.max()), (f64::NEG_INFINITY, 5.0));
+        assert!(GeneralizedExtremeValue::new(f64::NAN, 1.0, 0.0).is_err());
+        assert!(GeneralizedExtremeValue::new(0.0, -1.0, 0.0).is_err());
+        assert!(GeneralizedExtremeValue::new(0.0, 1.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_cdf_pdf() {
+        // mpmath: exp(-(1 + xi z)^(-1 / xi)) and its derivative
+        let n = try_create(1.0, 2.0, 0.3);
+        assert_almost_eq!(n.cdf(3.0), 0.6589875266751007, 1e-15);
+        assert_almost_eq!(n.sf(3.0), 0.3410124733248993, 1e-15);
+        assert_almost_eq!(n.pdf(3.0), 0.10570430424864885, 1e-15);
+        assert_almost_eq!(n.inverse_cdf(0.6589875266751007), 3.0, 1e-13);
+        assert_eq!(n.cdf(-6.0), 0.0);
+        assert_eq!(n.pdf(-6.0), 0.0);
+        assert_eq!(n.inverse_cdf(0.0), n.min());
+        test::check_continuous_distribution(&n, -3.0, 1000.0);
+
+        let n = try_create(1.0, 2.0, -0.3);
+        assert_almost_eq!(n.cdf(3.0), 0.7374543635627546, 1e-15);
+        assert_almost_eq!(n.sf(3.0), 0.2625456364372454, 1e-15);
+        assert_almost_eq!(n.pdf(3.0), 0.16042322672492858, 1e-15);
+        assert_eq!(n.cdf(8.0), 1.0);
+        assert_eq!(n.sf(8.0), 0.0);
+        assert_eq!(n.pdf(8.0), 0.0);
+        assert_eq!(n.inverse_cdf(1.0), n.max());
+        test::check_continuous_distribution(&n, -20.0, n.max());
+    }
+
+    #[test]
+    fn test_gumbel_limit() {
+        let n = try_create(1.0, 2.0, 0.0);
+        let g = Gumbel::new(1.0, 2.0).unwrap();
+        for &x in &[-3.0, 0.0, 1.0, 4.5, 20.0] {
+            assert_eq!(n.cdf(x), g.cdf(x));
+            assert_eq!(n.sf(x), g.sf(x));
+            assert_almost_eq!(n.pdf(x), g.pdf(x), 1e-15);
+        }
+        assert_eq!(n.mean(), g.mean());
+        assert_eq!(n.variance(), g.variance());
+        assert_eq!(n.skewness(), g.skewness());
+        assert_eq!(n.entropy(), g.entropy());
+        assert_almost_eq!(n.median(), g.median(), 1e-15);
+        // tiny shapes are continuous with the limit
+        let m = try_create(1.0, 2.0, 1e-9);
+        assert_almost_eq!(m.cdf(4.5), g.cdf(4.5), 1e-8);
+        assert_almost_eq!(m.mean().unwrap(), g.mean().unwrap(), 1e-6);
+    }
+
+    #[test]
+    fn test_moments() {
+        // mpmath: closed forms, checked against quadrature of the quantile
+        // function
+        let n = try_create(1.0, 2.0, 0.2);
+        assert_almost_eq!(n.mean().unwrap(), 2.642297137253034, 1e-13);
+        assert_almost_eq!(n.variance().unwrap(), 13.376142249191526, 1e-11);
+        assert_almost_eq!(n.skewness().unwrap(), 3.5350716046213946, 1e-10);
+        assert_almost_eq!(n.entropy().unwrap(), 2.3858059784417847, 1e-15);
+        assert_almost_eq!(n.median(), 1.7605608513900512, 1e-15);
+        assert_almost_eq!(n.mode().unwrap(), 0.641925040026272, 1e-15);
+        let n = try_create(1.0, 2.0, -0.2);
+        assert_almost_eq!(n.mean().unwrap(), 1.818312576002394, 1e-13);
+        assert_almost_eq!(n.variance().unwrap(), 4.422997798311733, 1e-11);
+        assert_almost_eq!(n.skewness().unwrap(), 0.2541096037067475, 1e-10);
+        assert_almost_eq!(n.entropy().unwrap(), 2.154919712481172, 1e-15);
+        assert_almost_eq!(n.median(), 1.7068040986839472, 1e-15);
+        assert_almost_eq!(n.mode().unwrap(), 1.4364750020996301, 1e-15);
+        assert_eq!(try_create(1.0, 2.0, -1.5).mode().unwrap(), 1.0 + 2.0 / 1.5);
+        assert_eq!(try_create(1.0, 2.0, 1.0).mean(), None);
+        assert_eq!(try_create(1.0, 2.0, 0.5).variance(), None);
+        assert_eq!(try_create(1.0, 2.0, 0.4).skewness(), None);
+    }
+
+    #[test]
+    fn test_fit_l_moments() {
+        let mut rng = StdRng::seed_from_u64(12);
+        for &shape in &[-0.3, 0.0, 0.2] {
+            let n = try_create(10.0, 3.0, shape);
+            let data: Vec<f64> = (0..20000).map(|_| n.sample(&mut rng)).collect();
+            let fit = GeneralizedExtremeValue::fit_l_moments(&data).unwrap();
+            assert!((fit.location() - 10.0).abs() < 0.1);
+            assert!((fit.scale() - 3.0).abs() < 0.1);
+            assert!((fit.shape() - shape).abs() < 0.03);
+        }
+        // mpmath: Hosking's equations solved for the sample L-moments
+        let data = [2.1, 3.5, 1.7, 2.9, 5.2, 2.4, 3.1, 4.0, 9.6, 2.7];
+        let fit = GeneralizedExtremeValue::fit_l_moments(&data).unwrap();
+        assert_almost_eq!(fit.location(), 2.526776620753464, 1e-12);
+        assert_almost_eq!(fit.scale(), 0.8957036696037879, 1e-12);
+        assert_almost_eq!(fit.shape(), 0.43815549153833837, 1e-12);
+        assert_almost_eq!(fit.mean().unwrap(), l_moments(&data).unwrap().l1(), 1e-12);
+        assert!(GeneralizedExtremeValue::fit_l_moments(&[1.0, 2.0]).is_err());
+    }
+}
@@ -0,0 +1,610 @@
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
+use crate::fp;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::distributions::Open01;
+use rand::Rng;
+use std::f64;
+
+/// Implements the [generalized
+/// Pareto](https://en.wikipedia.org/wiki/Generalized_Pareto_distribution)
+/// distribution, the limit distribution of the excesses over a high
+/// threshold, which includes the exponential (`ξ = 0`), Pareto (`ξ > 0`)
+/// and bounded (`ξ < 0`) tails
+///
+/// The shape `ξ` follows the sign convention in which a positive shape
+/// gives a heavy upper tail, the negative of the `k` used by Hosking
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, GeneralizedPareto};
+///
+/// // a zero shape is the shifted exponential distribution
+/// let n = GeneralizedPareto::new(1.0, 2.0, 0.0).unwrap();
+/// assert_eq!(n.sf(3.0), (-1f64).exp());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GeneralizedPareto {
+    location: f64,
+    scale: f64,
+    shape: f64,
+}
+
+impl GeneralizedPareto {
+    /// Constructs a new generalized Pareto distribution with the given
+    /// location, scale and shape
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if location or shape are not finite or scale is not
+    /// positive and finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let mut result = GeneralizedPareto::new(0.0, 1.0, 0.2);
+    /// assert!(result.is_ok());
+    ///
+    /// result = GeneralizedPareto::new(0.0, -1.0, 0.2);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(location: f64, scale: f64, shape: f64) -> Result<GeneralizedPareto> {
+        if !(location.is_finite() && shape.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        if !(scale.is_finite() && scale > 0.0) {
+            return Err(StatsError::ArgMustBePositive("scale"));
+        }
+        Ok(GeneralizedPareto {
+            location,
+            scale,
+            shape,
+        })
+    }
+
+    /// Estimates the generalized Pareto distribution `data` was drawn from
+    /// by matching its first three L-moments to those of the sample,
+    /// following Hosking and Wallis (1987)
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// k = (1 - 3 τ_3) / (1 + τ_3)
+    /// σ = λ_2 (1 + k) (2 + k)
+    /// μ = λ_1 - σ / (1 + k)
+    /// ξ = -k
+    /// ```
+    ///
+    /// where `λ_1`, `λ_2` and `τ_3` are the sample L-moments and
+    /// L-skewness, and `μ`, `σ` and `ξ` are the location, scale and shape
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample L-moments of `data` cannot be
+    /// computed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let data = [2.1, 3.5, 1.7, 2.9, 5.2, 2.4, 3.1, 4.0, 9.6, 2.7];
+    /// let n = GeneralizedPareto::fit_l_moments(&data).unwrap();
+    /// assert!(n.shape() > 0.0);
+    /// ```
+    pub fn fit_l_moments(data: &[f64]) -> Result<GeneralizedPareto> {
+        let l = l_moments(data)?;
+        let t3 = l.l_skewness();
+        let k = (1.0 - 3.0 * t3) / (1.0 + t3);
+        let scale = l.l2() * (1.0 + k) * (2.0 + k);
+        GeneralizedPareto::new(l.l1() - scale / (1.0 + k), scale, -k)
+    }
+
+    /// Estimates the generalized Pareto distribution of the excesses `data`
+    /// over the known threshold `location` by matching the first two
+    /// L-moments to those of the sample, the usual peaks over threshold
+    /// fit
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// k = (λ_1 - μ) / λ_2 - 2
+    /// σ = (λ_1 - μ) (1 + k)
+    /// ξ = -k
+    /// ```
+    ///
+    /// where `λ_1` and `λ_2` are the sample L-moments, and `μ`, `σ` and `ξ`
+    /// are the location, scale and shape
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample L-moments of `data` cannot be
+    /// computed, if `location` is not finite or greater than a value of
+    /// `data`, or if the sample L-moments imply a shape of at least `1`,
+    /// for which the mean does not exist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let data = [2.1, 3.5, 1.7, 2.9, 5.2, 2.4, 3.1, 4.0, 9.6, 2.7];
+    /// let n = GeneralizedPareto::fit_l_moments_with_location(&data, 1.0).unwrap();
+    /// assert_eq!(n.location(), 1.0);
+    /// ```
+    pub fn fit_l_moments_with_location(data: &[f64], location: f64) -> Result<GeneralizedPareto> {
+        let l = l_moments(data)?;
+        if !location.is_finite() || data.iter().any(|&x| x < location) {
+            return Err(StatsError::BadParams);
+        }
+        let excess = l.l1() - location;
+        let k = excess / l.l2() - 2.0;
+        if k <= -1.0 {
+            return Err(StatsError::BadParams);
+        }
+        GeneralizedPareto::new(location, excess * (1.0 + k), -k)
+    }
+
+    /// Returns the location of the generalized Pareto distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let n = GeneralizedPareto::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.location(), 1.0);
+    /// ```
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale of the generalized Pareto distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let n = GeneralizedPareto::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.scale(), 2.0);
+    /// ```
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the shape of the generalized Pareto distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let n = GeneralizedPareto::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.shape(), 0.1);
+    /// ```
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the log of the survival function inside the support,
+    /// `-ln(1 + ξ z) / ξ`
+    fn ln_sf(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+        if self.shape == 0.0 {
+            -z
+        } else {
+            -fp::ln_1p(self.shape * z) / self.shape
+        }
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for GeneralizedPareto {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.inverse_cdf(rng.sample(Open01))
+    }
+}
+
+impl ContinuousCDF<f64, f64> for GeneralizedPareto {
+    /// Calculates the cumulative distribution function for the generalized
+    /// Pareto distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 1 - (1 + ξ (x - μ) / σ)^(-1 / ξ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `1 - exp(-(x - μ) / σ)` for `ξ = 0`
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= self.location {
+            0.0
+        } else if x >= self.max() {
+            1.0
+        } else {
+            -fp::exp_m1(self.ln_sf(x))
+        }
+    }
+
+    /// Calculates the survival function for the generalized Pareto
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 + ξ (x - μ) / σ)^(-1 / ξ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `exp(-(x - μ) / σ)` for `ξ = 0`
+    fn sf(&self, x: f64) -> f64 {
+        if x <= self.location {
+            1.0
+        } else if x >= self.max() {
+            0.0
+        } else {
+            fp::exp(self.ln_sf(x))
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// generalized Pareto distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + σ ((1 - p)^(-ξ) - 1) / ξ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `μ - σ ln(1 - p)` for `ξ = 0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let y = fp::ln_1p(-p);
+        if self.shape == 0.0 {
+            self.location - self.scale * y
+        } else {
+            self.location + self.scale * fp::exp_m1(-self.shape * y) / self.shape
+        }
+    }
+}
+
+impl Min<f64> for GeneralizedPareto {
+    /// Returns the minimum value in the domain of the generalized Pareto
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ
+    /// ```
+    ///
+    /// where `μ` is the location
+    fn min(&self) -> f64 {
+        self.location
+    }
+}
+
+impl Max<f64> for GeneralizedPareto {
+    /// Returns the maximum value in the domain of the generalized Pareto
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ < 0 {
+    ///     μ - σ / ξ
+    /// } else {
+    ///     INF
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape
+    fn max(&self) -> f64 {
+        if self.shape < 0.0 {
+            self.location - self.scale / self.shape
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+impl Distribution<f64> for GeneralizedPareto {
+    /// Returns the mean of the generalized Pareto distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ >= 1 {
+    ///     None
+    /// } else {
+    ///     μ + σ / (1 - ξ)
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape
+    fn mean(&self) -> Option<f64> {
+        if self.shape >= 1.0 {
+            None
+        } else {
+            Some(self.location + self.scale / (1.0 - self.shape))
+        }
+    }
+    /// Returns the variance of the generalized Pareto distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ >= 1 / 2 {
+    ///     None
+    /// } else {
+    ///     σ^2 / ((1 - ξ)^2 (1 - 2ξ))
+    /// }
+    /// ```
+    ///
+    /// where `σ` is the scale and `ξ` is the shape
+    fn variance(&self) -> Option<f64> {
+        if self.shape >= 0.5 {
+            None
+        } else {
+            let s = self.scale / (1.0 - self.shape);
+            Some(s * s / (1.0 - 2.0 * self.shape))
+        }
+    }
+    /// Returns the entropy of the generalized Pareto distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(σ) + ξ + 1
+    /// ```
+    ///
+    /// where `σ` is the scale and `ξ` is the shape
+    fn entropy(&self) -> Option<f64> {
+        Some(fp::ln(self.scale) + self.shape + 1.0)
+    }
+    /// Returns the skewness of the generalized Pareto distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ >= 1 / 3 {
+    ///     None
+    /// } else {
+    ///     2 (1 + ξ) sqrt(1 - 2ξ) / (1 - 3ξ)
+    /// }
+    /// ```
+    ///
+    /// where `ξ` is the shape
+    fn skewness(&self) -> Option<f64> {
+        if self.shape >= 1.0 / 3.0 {
+            None
+        } else {
+            Some(
+                2.0 * (1.0 + self.shape) * (1.0 - 2.0 * self.shape).sqrt()
+                    / (1.0 - 3.0 * self.shape),
+            )
+        }
+    }
+}
+
+impl Median<f64> for GeneralizedPareto {
+    /// Returns the median of the generalized Pareto distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + σ (2^ξ - 1) / ξ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `μ + σ ln(2)` for `ξ = 0`
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Mode<Option<f64>> for GeneralizedPareto {
+    /// Returns the mode of the generalized Pareto distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if ξ < -1 {
+    ///     μ - σ / ξ
+    /// } else {
+    ///     μ
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape
+    fn mode(&self) -> Option<f64> {
+        if self.shape < -1.0 {
+            // the density increases up to the upper bound of the support
+            Some(self.max())
+        } else {
+            Some(self.location)
+        }
+    }
+}
+
+impl Continuous<f64, f64> for GeneralizedPareto {
+    /// Calculates the probability density function for the generalized
+    /// Pareto distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 + ξ (x - μ) / σ)^(-1 / ξ - 1) / σ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `exp(-(x - μ) / σ) / σ` for `ξ = 0`
+    fn pdf(&self, x: f64) -> f64 {
+        fp::exp(self.ln_pdf(x))
+    }
+
+    /// Calculates the log probability density function for the generalized
+    /// Pareto distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -(1 / ξ + 1) ln(1 + ξ (x - μ) / σ) - ln(σ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `ξ` is the shape,
+    /// with the limit `-(x - μ) / σ - ln(σ)` for `ξ = 0`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x < self.location || x > self.max() {
+            f64::NEG_INFINITY
+        } else {
+            (1.0 + self.shape) * self.ln_sf(x) - fp::ln(self.scale)
+        }
+    }
+}
+
+impl Parameterized for GeneralizedPareto {
+    fn param_names() -> &'static [&'static str] {
+        &["location", "scale", "shape"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.location, self.scale, self.shape]
+    }
+
+    fn from_params(params: &[f64]) -> Result<GeneralizedPareto> {
+        match *params {
+            [location, scale, shape] => GeneralizedPareto::new(location, scale, shape),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::statistics::*;
+    use crate::distribution::{Continuous, ContinuousCDF, Exp, GeneralizedPareto};
+    use crate::distribution::internal::*;
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn try_create(location: f64, scale: f64, shape: f64) -> GeneralizedPareto {
+        GeneralizedPareto::new(location, scale, shape).unwrap()
+    }
+
+    #[test]
+    fn test_create() {
+        let n = try_create(1.0, 2.0, -0.5);
+        assert_eq!((n.location(), n.scale(), n.shape()), (1.0, 2.0, -0.5));
+        assert_eq!((n.min(), n.max()), (1.0, 5.0));
+        assert_eq!(try_create(1.0, 2.0, 0.5).max(), f64::INFINITY);
+        assert!(GeneralizedPareto::new(0.0, 1.0, f64::NAN).is_err());
+        assert!(GeneralizedPareto::new(0.0, 0.0, 0.1).is_err());
+        assert!(GeneralizedPareto::new(f64::INFINITY, 1.0, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_cdf_pdf() {
+        // mpmath: 1 - (1 + xi z)^(-1 / xi) and its derivative
+        let n = try_create(1.0, 2.0, 0.3);
+        assert_almost_eq!(n.cdf(3.0), 0.5829493276858539, 1e-15);
+        assert_almost_eq!(n.sf(3.0), 0.4170506723141461, 1e-15);
+        assert_almost_eq!(n.pdf(3.0), 0.16040410473621004, 1e-15);
+        assert_almost_eq!(n.inverse_cdf(0.5829493276858539), 3.0, 1e-13);
+        assert_eq!((n.cdf(0.0), n.sf(0.0), n.pdf(0.0)), (0.0, 1.0, 0.0));
+        assert_almost_eq!(n.pdf(1.0), 0.5, 1e-15);
+        test::check_continuous_distribution(&n, 1.0, 1000.0);
+
+        let n = try_create(1.0, 2.0, -0.3);
+        assert_almost_eq!(n.cdf(3.0), 0.695448927402288, 1e-15);
+        assert_almost_eq!(n.sf(3.0), 0.30455107259771204, 1e-15);
+        assert_almost_eq!(n.pdf(3.0), 0.21753648042693717, 1e-15);
+        assert_eq!((n.cdf(8.0), n.sf(8.0), n.pdf(8.0)), (1.0, 0.0, 0.0));
+        assert_eq!(n.inverse_cdf(1.0), n.max());
+        test::check_continuous_distribution(&n, 1.0, n.max());
+    }
+
+    #[test]
+    fn test_special_cases() {
+        // a zero shape is the shifted exponential distribution and a shape
+        // of -1 the uniform distribution
+        let n = try_create(1.0, 2.0, 0.0);
+        let e = Exp::new(0.5).unwrap();
+        for &x in &[1.5, 3.0, 10.0] {
+            assert_almost_eq!(n.cdf(x), e.cdf(x - 1.0), 1e-15);
+            assert_almost_eq!(n.pdf(x), e.pdf(x - 1.0), 1e-15);
+        }
+        assert_eq!(n.mean().unwrap(), 3.0);
+        assert_almost_eq!(n.median(), 1.0 + 2.0 * 2f64.ln(), 1e-15);
+        let n = try_create(1.0, 2.0, -1.0);
+        assert_eq!(n.max(), 3.0);
+        assert_almost_eq!(n.cdf(2.5), 0.75, 1e-15);
+        assert_almost_eq!(n.pdf(2.5), 0.5, 1e-15);
+    }
+
+    #[test]
+    fn test_moments() {
+        // mpmath: closed forms, checked against quadrature of the quantile
+        // function
+        let n = try_create(1.0, 2.0, 0.2);
+        assert_almost_eq!(n.mean().unwrap(), 3.5, 1e-14);
+        assert_almost_eq!(n.variance().unwrap(), 10.416666666666667, 1e-13);
+        assert_almost_eq!(n.skewness().unwrap(), 4.6475800154489, 1e-13);
+        assert_almost_eq!(n.entropy().unwrap(), 1.8931471805599453, 1e-15);
+        assert_almost_eq!(n.median(), 2.48698354997035, 1e-15);
+        assert_eq!(n.mode().unwrap(), 1.0);
+        assert_eq!(try_create(1.0, 2.0, -1.5).mode().unwrap(), 1.0 + 2.0 / 1.5);
+        assert_eq!(try_create(1.0, 2.0, 1.0).mean(), None);
+        assert_eq!(try_create(1.0, 2.0, 0.5).variance(), None);
+        assert_eq!(try_create(1.0, 2.0, 0.4).skewness(), None);
+    }
+
+    #[test]
+    fn test_fit_l_moments() {
+        let mut rng = StdRng::seed_from_u64(13);
+        for &shape in &[-0.3, 0.0, 0.2] {
+            let n = try_create(10.0, 3.0, shape);
+            let data: Vec<f64> = (0..20000).map(|_| n.sample(&mut rng)).collect();
+            let fit = GeneralizedPareto::fit_l_moments(&data).unwrap();
+            assert!((fit.location() - 10.0).abs() < 0.05);
+            assert!((fit.scale() - 3.0).abs() < 0.1);
+            assert!((fit.shape() - shape).abs() < 0.03);
+            let fit = GeneralizedPareto::fit_l_moments_with_location(&data, 10.0).unwrap();
+            assert!((fit.scale() - 3.0).abs() < 0.1);
+            assert!((fit.shape() - shape).abs() < 0.03);
+        }
+
+        // mpmath: Hosking's equations for the sample L-moments
+        let data = [2.1, 3.5, 1.7, 2.9, 5.2, 2.4, 3.1, 4.0, 9.6, 2.7];
+        let fit = GeneralizedPareto::fit_l_moments(&data).unwrap();
+        assert_almost_eq!(fit.location(), 1.7732508143322476, 1e-13);
+        assert_almost_eq!(fit.scale(), 1.3519443856168235, 1e-13);
+        assert_almost_eq!(fit.shape(), 0.30553745928338762, 1e-13);
+        let fit = GeneralizedPareto::fit_l_moments_with_location(&data, 1.0).unwrap();
+        assert_eq!(fit.location(), 1.0);
+        assert_almost_eq!(fit.scale(), 3.7196131528046422, 1e-13);
+        assert_almost_eq!(fit.shape(), -0.36750483558994197, 1e-13);
+        assert_almost_eq!(fit.mean().unwrap(), l_moments(&data).unwrap().l1(), 1e-13);
+
+        assert!(GeneralizedPareto::fit_l_moments(&[1.0, 2.0]).is_err());
+        assert!(GeneralizedPareto::fit_l_moments_with_location(&data, 2.0).is_err());
+        assert!(GeneralizedPareto::fit_l_moments_with_location(&data, f64::NAN).is_err());
+        // excesses with a spread this large relative to their mean imply a
+        // shape of at least 1
+        let heavy = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 100.0];
+        assert!(GeneralizedPareto::fit_l_moments_with_location(&heavy, 0.0).is_err());
+    }
+}
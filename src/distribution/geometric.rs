@@ -1,6 +1,8 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{fit, CharacteristicFunction, Discrete, DiscreteCDF, Fit, Parameterized};
+use crate::fp;
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::distributions::OpenClosed01;
 use rand::Rng;
 use std::{f64, u64};
@@ -52,6 +54,19 @@ impl Geometric {
         }
     }
 
+    /// Constructs a new geometric distribution from parameters already known to
+    /// be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(p: f64) -> Geometric {
+        debug_assert!(Geometric::new(p).is_ok(), "invalid geometric parameters");
+        Geometric { p }
+    }
+
     /// Returns the probability `p` of the geometric
     /// distribution
     ///
@@ -74,7 +89,7 @@ impl ::rand::distributions::Distribution<f64> for Geometric {
             1.0
         } else {
             let x: f64 = r.sample(OpenClosed01);
-            x.log(1.0 - self.p).ceil()
+            (fp::ln(x) / fp::ln(1.0 - self.p)).ceil()
         }
     }
 }
@@ -95,9 +110,33 @@ impl DiscreteCDF<u64, f64> for Geometric {
             // 1 - (1 - p) ^ x = 1 - exp(log(1 - p)*x)
             //                 = -expm1(log1p(-p)*x))
             //                 = -((-p).ln_1p() * x).exp_m1()
-            -((-self.p).ln_1p() * (x as f64)).exp_m1()
+            -fp::exp_m1(fp::ln_1p(-self.p) * (x as f64))
         }
     }
+
+    /// Calculates the survival function for the geometric
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 - p) ^ x
+    /// ```
+    fn sf(&self, x: u64) -> f64 {
+        fp::exp(self.ln_sf(x))
+    }
+
+    /// Calculates the log survival function for the geometric
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// x * ln(1 - p)
+    /// ```
+    fn ln_sf(&self, x: u64) -> f64 {
+        fp::ln_1p(-self.p) * x as f64
+    }
 }
 
 impl Min<u64> for Geometric {
@@ -160,7 +199,9 @@ impl Distribution<f64> for Geometric {
     /// ```
     fn entropy(&self) -> Option<f64> {
         let inv = 1.0 / self.p;
-        Some(-inv * (1. - self.p).log(2.0) + (inv - 1.).log(2.0))
+        Some(
+            -inv * (fp::ln(1. - self.p) / f64::consts::LN_2) + fp::ln(inv - 1.) / f64::consts::LN_2,
+        )
     }
     /// Returns the skewness of the geometric distribution
     ///
@@ -201,7 +242,7 @@ impl Median<f64> for Geometric {
     /// ceil(-1 / log_2(1 - p))
     /// ```
     fn median(&self) -> f64 {
-        (-f64::consts::LN_2 / (1.0 - self.p).ln()).ceil()
+        (-f64::consts::LN_2 / fp::ln(1.0 - self.p)).ceil()
     }
 }
 
@@ -218,7 +259,7 @@ impl Discrete<u64, f64> for Geometric {
         if x == 0 {
             0.0
         } else {
-            (1.0 - self.p).powi(x as i32 - 1) * self.p
+            fp::powi(1.0 - self.p, x as i32 - 1) * self.p
         }
     }
 
@@ -238,8 +279,55 @@ impl Discrete<u64, f64> for Geometric {
         } else if ulps_eq!(self.p, 1.0) {
             f64::NEG_INFINITY
         } else {
-            ((x - 1) as f64 * (1.0 - self.p).ln()) + self.p.ln()
+            ((x - 1) as f64 * fp::ln(1.0 - self.p)) + fp::ln(self.p)
+        }
+    }
+}
+
+impl CharacteristicFunction<f64, f64> for Geometric {
+    /// Calculates the characteristic function for the geometric
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// pe^(it) / (1 - (1 - p)e^(it))
+    /// ```
+    ///
+    /// where `p` is the success probability
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let denom = Complex::new(self.p, 0.0) - super::poisson::exp_m1_i(t) * (1.0 - self.p);
+        Complex::from_polar(self.p, t) / denom
+    }
+}
+
+impl Parameterized for Geometric {
+    fn param_names() -> &'static [&'static str] {
+        &["p"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.p]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Geometric> {
+        match *params {
+            [p] => Geometric::new(p),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for Geometric {
+    type Sample = u64;
+
+    /// Fits the probability of success as the reciprocal of the sample mean
+    /// of the numbers of trials, which are at least one
+    fn fit_mle(data: &[u64]) -> Result<Geometric> {
+        if data.contains(&0) {
+            return Err(StatsError::BadParams);
         }
+        Geometric::new(1.0 / fit::count_mean(data)?)
     }
 }
 
@@ -248,7 +336,7 @@ impl Discrete<u64, f64> for Geometric {
 mod tests {
     use std::fmt::Debug;
     use crate::statistics::*;
-    use crate::distribution::{DiscreteCDF, Discrete, Geometric};
+    use crate::distribution::{CharacteristicFunction, DiscreteCDF, Discrete, Geometric};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -407,6 +495,14 @@ mod tests {
         test_almost(0.5, 0.75, 1e-15, cdf(2));
     }
 
+    #[test]
+    fn test_sf() {
+        let sf = |arg: u64| move |x: Geometric| x.sf(arg);
+        test_case(1.0, 0.0, sf(1));
+        test_almost(0.5, 0.25, 1e-15, sf(2));
+        test_almost(0.5, 0.5f64.powi(100), 1e-40, sf(100));
+    }
+
     #[test]
     fn test_cdf_small_p() {
         //
@@ -463,4 +559,11 @@ mod tests {
         test::check_discrete_distribution(&try_create(0.6), 100);
         test::check_discrete_distribution(&try_create(1.0), 1);
     }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(0.3).cf(0.5);
+        assert_almost_eq!(phi.re, 0.2038176947263749, 1e-14);
+        assert_almost_eq!(phi.im, 0.5502533978059293, 1e-14);
+    }
 }
@@ -0,0 +1,406 @@
+use crate::consts::EULER_MASCHERONI;
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
+use crate::fp;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::distributions::Open01;
+use rand::Rng;
+use std::f64;
+
+/// The skewness `12 sqrt(6) ζ(3) / π^3` of every Gumbel distribution
+const SKEWNESS: f64 = 1.1395470994046486574927930193898461;
+
+/// Implements the [Gumbel](https://en.wikipedia.org/wiki/Gumbel_distribution)
+/// distribution, the type I extreme value distribution of the maxima of
+/// samples from distributions with exponential tails
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, Gumbel};
+/// use statrs::statistics::Mode;
+///
+/// let n = Gumbel::new(0.0, 1.0).unwrap();
+/// assert_eq!(n.mode().unwrap(), 0.0);
+/// assert_eq!(n.cdf(0.0), (-1f64).exp());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Gumbel {
+    location: f64,
+    scale: f64,
+}
+
+impl Gumbel {
+    /// Constructs a new Gumbel distribution with the given location and
+    /// scale
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if location is not finite or scale is not positive
+    /// and finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Gumbel;
+    ///
+    /// let mut result = Gumbel::new(0.0, 1.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Gumbel::new(0.0, -1.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(location: f64, scale: f64) -> Result<Gumbel> {
+        if !location.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        if !(scale.is_finite() && scale > 0.0) {
+            return Err(StatsError::ArgMustBePositive("scale"));
+        }
+        Ok(Gumbel { location, scale })
+    }
+
+    /// Estimates the Gumbel distribution `data` was drawn from by matching
+    /// its first two L-moments to those of the sample
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// β = λ_2 / ln(2)
+    /// μ = λ_1 - γ β
+    /// ```
+    ///
+    /// where `λ_1` and `λ_2` are the sample L-moments, `μ` is the location,
+    /// `β` is the scale and `γ` is the Euler-Mascheroni constant
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample L-moments of `data` cannot be
+    /// computed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Gumbel;
+    ///
+    /// let data = [2.1, 3.5, 1.7, 2.9, 5.2, 2.4, 3.1, 4.0];
+    /// let n = Gumbel::fit_l_moments(&data).unwrap();
+    /// assert!(n.scale() > 0.0);
+    /// ```
+    pub fn fit_l_moments(data: &[f64]) -> Result<Gumbel> {
+        let l = l_moments(data)?;
+        let scale = l.l2() / f64::consts::LN_2;
+        Gumbel::new(l.l1() - EULER_MASCHERONI * scale, scale)
+    }
+
+    /// Returns the location of the Gumbel distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Gumbel;
+    ///
+    /// let n = Gumbel::new(1.0, 2.0).unwrap();
+    /// assert_eq!(n.location(), 1.0);
+    /// ```
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale of the Gumbel distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Gumbel;
+    ///
+    /// let n = Gumbel::new(1.0, 2.0).unwrap();
+    /// assert_eq!(n.scale(), 2.0);
+    /// ```
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the standardized value `(x - μ) / β`
+    fn z(&self, x: f64) -> f64 {
+        (x - self.location) / self.scale
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for Gumbel {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.inverse_cdf(rng.sample(Open01))
+    }
+}
+
+impl ContinuousCDF<f64, f64> for Gumbel {
+    /// Calculates the cumulative distribution function for the Gumbel
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// exp(-exp(-(x - μ) / β))
+    /// ```
+    ///
+    /// where `μ` is the location and `β` is the scale
+    fn cdf(&self, x: f64) -> f64 {
+        fp::exp(-fp::exp(-self.z(x)))
+    }
+
+    /// Calculates the survival function for the Gumbel distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 1 - exp(-exp(-(x - μ) / β))
+    /// ```
+    ///
+    /// where `μ` is the location and `β` is the scale
+    fn sf(&self, x: f64) -> f64 {
+        -fp::exp_m1(-fp::exp(-self.z(x)))
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// Gumbel distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ - β ln(-ln(p))
+    /// ```
+    ///
+    /// where `μ` is the location and `β` is the scale
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        self.location - self.scale * fp::ln(-fp::ln(p))
+    }
+}
+
+impl Min<f64> for Gumbel {
+    /// Returns the minimum value in the domain of the Gumbel distribution
+    /// representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// NEG_INF
+    /// ```
+    fn min(&self) -> f64 {
+        f64::NEG_INFINITY
+    }
+}
+
+impl Max<f64> for Gumbel {
+    /// Returns the maximum value in the domain of the Gumbel distribution
+    /// representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// INF
+    /// ```
+    fn max(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Distribution<f64> for Gumbel {
+    /// Returns the mean of the Gumbel distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + γ β
+    /// ```
+    ///
+    /// where `μ` is the location, `β` is the scale and `γ` is the
+    /// Euler-Mascheroni constant
+    fn mean(&self) -> Option<f64> {
+        Some(self.location + EULER_MASCHERONI * self.scale)
+    }
+    /// Returns the variance of the Gumbel distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// π^2 β^2 / 6
+    /// ```
+    ///
+    /// where `β` is the scale
+    fn variance(&self) -> Option<f64> {
+        let s = f64::consts::PI * self.scale;
+        Some(s * s / 6.0)
+    }
+    /// Returns the entropy of the Gumbel distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(β) + γ + 1
+    /// ```
+    ///
+    /// where `β` is the scale and `γ` is the Euler-Mascheroni constant
+    fn entropy(&self) -> Option<f64> {
+        Some(fp::ln(self.scale) + EULER_MASCHERONI + 1.0)
+    }
+    /// Returns the skewness of the Gumbel distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 12 sqrt(6) ζ(3) / π^3
+    /// ```
+    ///
+    /// where `ζ` is the Riemann zeta function
+    fn skewness(&self) -> Option<f64> {
+        Some(SKEWNESS)
+    }
+}
+
+impl Median<f64> for Gumbel {
+    /// Returns the median of the Gumbel distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ - β ln(ln(2))
+    /// ```
+    ///
+    /// where `μ` is the location and `β` is the scale
+    fn median(&self) -> f64 {
+        self.location - self.scale * fp::ln(f64::consts::LN_2)
+    }
+}
+
+impl Mode<Option<f64>> for Gumbel {
+    /// Returns the mode of the Gumbel distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ
+    /// ```
+    ///
+    /// where `μ` is the location
+    fn mode(&self) -> Option<f64> {
+        Some(self.location)
+    }
+}
+
+impl Continuous<f64, f64> for Gumbel {
+    /// Calculates the probability density function for the Gumbel
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// exp(-(z + exp(-z))) / β,    z = (x - μ) / β
+    /// ```
+    ///
+    /// where `μ` is the location and `β` is the scale
+    fn pdf(&self, x: f64) -> f64 {
+        fp::exp(self.ln_pdf(x))
+    }
+
+    /// Calculates the log probability density function for the Gumbel
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -(z + exp(-z)) - ln(β),    z = (x - μ) / β
+    /// ```
+    ///
+    /// where `μ` is the location and `β` is the scale
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x.is_infinite() {
+            return f64::NEG_INFINITY;
+        }
+        let z = self.z(x);
+        -(z + fp::exp(-z)) - fp::ln(self.scale)
+    }
+}
+
+impl Parameterized for Gumbel {
+    fn param_names() -> &'static [&'static str] {
+        &["location", "scale"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.location, self.scale]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Gumbel> {
+        match *params {
+            [location, scale] => Gumbel::new(location, scale),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::statistics::*;
+    use crate::distribution::{Continuous, ContinuousCDF, Gumbel};
+    use crate::distribution::internal::*;
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_create() {
+        let n = Gumbel::new(1.0, 2.0).unwrap();
+        assert_eq!((n.location(), n.scale()), (1.0, 2.0));
+        assert_eq!((n.min(), n.max()), (f64::NEG_INFINITY, f64::INFINITY));
+        assert!(Gumbel::new(f64::NAN, 1.0).is_err());
+        assert!(Gumbel::new(0.0, 0.0).is_err());
+        assert!(Gumbel::new(0.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_cdf_pdf() {
+        // mpmath: exp(-exp(-z)) and exp(-(z + exp(-z))) / scale
+        let n = Gumbel::new(1.0, 2.0).unwrap();
+        assert_almost_eq!(n.cdf(3.0), 0.6922006275553464, 1e-15);
+        assert_almost_eq!(n.sf(3.0), 0.3077993724446536, 1e-15);
+        assert_almost_eq!(n.pdf(3.0), 0.12732319002179125, 1e-15);
+        assert_almost_eq!(n.sf(61.0), 9.357622968839737e-14, 1e-27);
+        assert_almost_eq!(n.inverse_cdf(0.6922006275553464), 3.0, 1e-13);
+        assert_eq!(n.inverse_cdf(0.0), f64::NEG_INFINITY);
+        assert_eq!(n.inverse_cdf(1.0), f64::INFINITY);
+        test::check_continuous_distribution(&n, -10.0, 40.0);
+    }
+
+    #[test]
+    fn test_moments() {
+        let n = Gumbel::new(1.0, 2.0).unwrap();
+        assert_almost_eq!(n.mean().unwrap(), 2.1544313298030657, 1e-15);
+        assert_almost_eq!(n.variance().unwrap(), 6.579736267392906, 1e-14);
+        assert_almost_eq!(n.entropy().unwrap(), 2.2703628454614782, 1e-15);
+        assert_almost_eq!(n.median(), 1.7330258411633288, 1e-15);
+        assert_almost_eq!(n.cdf(n.median()), 0.5, 1e-15);
+        assert_eq!(n.skewness().unwrap(), 1.1395470994046486);
+    }
+
+    #[test]
+    fn test_fit_l_moments() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let n = Gumbel::new(10.0, 3.0).unwrap();
+        let data: Vec<f64> = (0..20000).map(|_| n.sample(&mut rng)).collect();
+        let fit = Gumbel::fit_l_moments(&data).unwrap();
+        assert!((fit.location() - 10.0).abs() < 0.1);
+        assert!((fit.scale() - 3.0).abs() < 0.1);
+        // the fit reproduces the sample L-moments exactly
+        let l = l_moments(&data).unwrap();
+        assert_almost_eq!(fit.mean().unwrap(), l.l1(), 1e-12);
+        assert!(Gumbel::fit_l_moments(&[1.0, 2.0]).is_err());
+    }
+}
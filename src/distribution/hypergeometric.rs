@@ -1,4 +1,5 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{integer_param, Discrete, DiscreteCDF, Parameterized};
+use crate::fp;
 use crate::function::factorial;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -54,6 +55,26 @@ impl Hypergeometric {
         }
     }
 
+    /// Constructs a new hypergeometric distribution from parameters already
+    /// known to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(population: u64, successes: u64, draws: u64) -> Hypergeometric {
+        debug_assert!(
+            Hypergeometric::new(population, successes, draws).is_ok(),
+            "invalid hypergeometric parameters"
+        );
+        Hypergeometric {
+            population,
+            successes,
+            draws,
+        }
+    }
+
     /// Returns the population size of the hypergeometric
     /// distribution
     ///
@@ -157,10 +178,11 @@ impl DiscreteCDF<u64, f64> for Hypergeometric {
             let k = x;
             let ln_denom = factorial::ln_binomial(self.population, self.draws);
             (0..k + 1).fold(0.0, |acc, i| {
-                acc + (factorial::ln_binomial(self.successes, i)
-                    + factorial::ln_binomial(self.population - self.successes, self.draws - i)
-                    - ln_denom)
-                    .exp()
+                acc + fp::exp(
+                    factorial::ln_binomial(self.successes, i)
+                        + factorial::ln_binomial(self.population - self.successes, self.draws - i)
+                        - ln_denom,
+                )
             })
         }
     }
@@ -273,6 +295,21 @@ impl Distribution<f64> for Hypergeometric {
     }
 }
 
+impl Median<f64> for Hypergeometric {
+    /// Returns the median of the hypergeometric distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(1 / 2)
+    /// ```
+    ///
+    /// where `F` is the cdf
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5) as f64
+    }
+}
+
 impl Mode<Option<u64>> for Hypergeometric {
     /// Returns the mode of the hypergeometric distribution
     ///
@@ -326,6 +363,31 @@ impl Discrete<u64, f64> for Hypergeometric {
     }
 }
 
+impl Parameterized for Hypergeometric {
+    fn param_names() -> &'static [&'static str] {
+        &["population", "successes", "draws"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![
+            self.population as f64,
+            self.successes as f64,
+            self.draws as f64,
+        ]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Hypergeometric> {
+        match *params {
+            [population, successes, draws] => Hypergeometric::new(
+                integer_param(population)?,
+                integer_param(successes)?,
+                integer_param(draws)?,
+            ),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
@@ -451,6 +513,14 @@ mod tests {
         test_case(10, 5, 3, 2, mode);
     }
 
+    #[test]
+    fn test_median() {
+        let median = |x: Hypergeometric| x.median();
+        test_case(0, 0, 0, 0.0, median);
+        test_case(10, 9, 9, 8.0, median);
+        test_case(100, 30, 20, 6.0, median);
+    }
+
     #[test]
     fn test_min() {
         let min = |x: Hypergeometric| x.min();
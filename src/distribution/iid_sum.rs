@@ -0,0 +1,810 @@
+use crate::distribution::{
+    Bernoulli, Binomial, Cauchy, ChiSquared, Continuous, ContinuousCDF, Dirac, Discrete,
+    DiscreteCDF, Erlang, Exp, Gamma, NegativeBinomial, Normal, Poisson,
+};
+use crate::fp;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use num_complex::Complex;
+use rand::Rng;
+use std::f64;
+
+/// The largest number of lattice points of the distribution of a sum
+const MAX_LATTICE: u64 = 1 << 18;
+
+/// The largest number of lattice cells used to discretize a continuous
+/// summand
+const MAX_CELLS: u64 = 1 << 14;
+
+/// The smallest number of lattice cells used to discretize a continuous
+/// summand
+const MIN_CELLS: u64 = 64;
+
+/// The tail probability of a summand cut off by the discretization
+const TAIL: f64 = 1e-15;
+
+/// The `IidSumExact` trait is implemented by distributions whose family is
+/// closed under sums of independent, identically distributed variables, so
+/// that the distribution of such a sum is known in closed form
+pub trait IidSumExact {
+    /// The distribution of the sum
+    type Sum;
+
+    /// Returns the distribution of the sum of `n` independent copies of
+    /// `self`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is zero or the parameters of the sum cannot
+    /// be represented
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Bernoulli, Binomial, IidSumExact};
+    ///
+    /// let b = Bernoulli::new(0.3).unwrap();
+    /// assert_eq!(b.iid_sum(10).unwrap(), Binomial::new(0.3, 10).unwrap());
+    /// ```
+    fn iid_sum(&self, n: u64) -> Result<Self::Sum>;
+}
+
+fn check_n(n: u64) -> Result<f64> {
+    if n == 0 {
+        Err(StatsError::ArgMustBePositive("n"))
+    } else {
+        Ok(n as f64)
+    }
+}
+
+impl IidSumExact for Normal {
+    type Sum = Normal;
+    /// `N(nμ, nσ^2)`
+    fn iid_sum(&self, n: u64) -> Result<Normal> {
+        let nf = check_n(n)?;
+        Normal::new(
+            nf * self.mean().unwrap(),
+            nf.sqrt() * self.std_dev().unwrap(),
+        )
+    }
+}
+
+impl IidSumExact for Gamma {
+    type Sum = Gamma;
+    /// `Gamma(nα, β)`
+    fn iid_sum(&self, n: u64) -> Result<Gamma> {
+        Gamma::new(check_n(n)? * self.shape(), self.rate())
+    }
+}
+
+impl IidSumExact for Exp {
+    type Sum = Gamma;
+    /// `Gamma(n, λ)`
+    fn iid_sum(&self, n: u64) -> Result<Gamma> {
+        Gamma::new(check_n(n)?, self.rate())
+    }
+}
+
+impl IidSumExact for Erlang {
+    type Sum = Erlang;
+    /// `Erlang(nk, λ)`
+    fn iid_sum(&self, n: u64) -> Result<Erlang> {
+        check_n(n)?;
+        let shape = self.shape().checked_mul(n).ok_or(StatsError::BadParams)?;
+        Erlang::new(shape, self.rate())
+    }
+}
+
+impl IidSumExact for ChiSquared {
+    type Sum = ChiSquared;
+    /// `χ^2(nk)`
+    fn iid_sum(&self, n: u64) -> Result<ChiSquared> {
+        ChiSquared::new(check_n(n)? * self.freedom())
+    }
+}
+
+impl IidSumExact for Cauchy {
+    type Sum = Cauchy;
+    /// `Cauchy(n x_0, n γ)`
+    fn iid_sum(&self, n: u64) -> Result<Cauchy> {
+        let nf = check_n(n)?;
+        Cauchy::new(nf * self.location(), nf * self.scale())
+    }
+}
+
+impl IidSumExact for Dirac {
+    type Sum = Dirac;
+    /// `δ(nv)`
+    fn iid_sum(&self, n: u64) -> Result<Dirac> {
+        Dirac::new(check_n(n)? * self.mean().unwrap())
+    }
+}
+
+impl IidSumExact for Poisson {
+    type Sum = Poisson;
+    /// `Poisson(nλ)`
+    fn iid_sum(&self, n: u64) -> Result<Poisson> {
+        Poisson::new(check_n(n)? * self.lambda())
+    }
+}
+
+impl IidSumExact for Bernoulli {
+    type Sum = Binomial;
+    /// `Binomial(n, p)`
+    fn iid_sum(&self, n: u64) -> Result<Binomial> {
+        check_n(n)?;
+        Binomial::new(self.p(), n)
+    }
+}
+
+impl IidSumExact for Binomial {
+    type Sum = Binomial;
+    /// `Binomial(nm, p)`
+    fn iid_sum(&self, n: u64) -> Result<Binomial> {
+        check_n(n)?;
+        let trials = self.n().checked_mul(n).ok_or(StatsError::BadParams)?;
+        Binomial::new(self.p(), trials)
+    }
+}
+
+impl IidSumExact for NegativeBinomial {
+    type Sum = NegativeBinomial;
+    /// `NB(nr, p)`
+    fn iid_sum(&self, n: u64) -> Result<NegativeBinomial> {
+        NegativeBinomial::new(check_n(n)? * self.r(), self.p())
+    }
+}
+
+/// Implements the distribution of the sum of `n` independent copies of a
+/// continuous distribution for which no closed form is available
+///
+/// # Remarks
+///
+/// Every summand is rounded to a fine lattice covering all but a tail
+/// probability of `1e-15` on either side of the parent and the exact
+/// distribution of the sum of the rounded summands is computed by raising
+/// its discrete Fourier transform to the `n`th power. The density is then
+/// piecewise constant and the cdf piecewise linear between lattice points,
+/// with errors of the order of the squared lattice spacing, typically
+/// below `1e-6`. The lattice is limited to `2^18` points, so `n` may be at
+/// most `4095`; for larger `n` the normal approximation is usually adequate.
+/// Heavy-tailed parents are poorly resolved since the lattice has to span
+/// their extreme quantiles. Moments are computed exactly from those of the
+/// parent and sampling sums `n` draws from the parent.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, IidSum, Uniform};
+///
+/// // the Irwin-Hall distribution
+/// let s = IidSum::new(Uniform::new(0.0, 1.0).unwrap(), 3).unwrap();
+/// assert!((s.cdf(1.0) - 1.0 / 6.0).abs() < 1e-6);
+/// assert!((s.cdf(1.5) - 0.5).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct IidSum<D> {
+    parent: D,
+    n: u64,
+    start: f64,
+    step: f64,
+    masses: Vec<f64>,
+    cum_masses: Vec<f64>,
+    cum_masses_upper: Vec<f64>,
+}
+
+impl<D: ContinuousCDF<f64, f64>> IidSum<D> {
+    /// Constructs the distribution of the sum of `n` independent copies of
+    /// `parent`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is zero or larger than `4095`, or if the
+    /// quantiles of `parent` bounding the lattice are not finite
+    pub fn new(parent: D, n: u64) -> Result<IidSum<D>> {
+        check_n(n)?;
+        let cells = ((MAX_LATTICE - 1) / n).min(MAX_CELLS);
+        if cells < MIN_CELLS {
+            return Err(StatsError::BadParams);
+        }
+        let lower = if parent.min().is_finite() {
+            parent.min()
+        } else {
+            parent.inverse_cdf(TAIL)
+        };
+        let upper = if parent.max().is_finite() {
+            parent.max()
+        } else {
+            parent.inverse_cdf(1.0 - TAIL)
+        };
+        if !lower.is_finite() || !upper.is_finite() || upper <= lower {
+            return Err(StatsError::BadParams);
+        }
+        let step = (upper - lower) / cells as f64;
+        let cells = cells as usize;
+        // the mass rounded to every lattice point of a summand
+        let mut summand = Vec::with_capacity(cells + 1);
+        let mut below = parent.cdf(lower + 0.5 * step);
+        summand.push(below);
+        for k in 1..cells {
+            let cdf = parent.cdf(lower + (k as f64 + 0.5) * step);
+            summand.push((cdf - below).max(0.0));
+            below = cdf;
+        }
+        summand.push(parent.sf(upper - 0.5 * step));
+        let masses = convolution_power(&summand, n);
+        let (cum_masses, cum_masses_upper) = cumulate(&masses);
+        Ok(IidSum {
+            parent,
+            n,
+            start: n as f64 * lower,
+            step,
+            masses,
+            cum_masses,
+            cum_masses_upper,
+        })
+    }
+
+    /// Returns the parent distribution
+    pub fn parent(&self) -> &D {
+        &self.parent
+    }
+
+    /// Returns the number of summands
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Returns the lattice index of the cell containing `x` together with
+    /// the position of `x` within the cell in `[0, 1)`, or `None` if `x`
+    /// lies outside the lattice
+    fn locate(&self, x: f64) -> Option<(usize, f64)> {
+        let u = (x - self.start) / self.step + 0.5;
+        if u.is_nan() || u < 0.0 || u >= self.masses.len() as f64 {
+            None
+        } else {
+            let k = u.floor();
+            Some((k as usize, u - k))
+        }
+    }
+}
+
+impl<D: ::rand::distributions::Distribution<f64>> ::rand::distributions::Distribution<f64>
+    for IidSum<D>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        (0..self.n).map(|_| self.parent.sample(rng)).sum()
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f64, f64> for IidSum<D> {
+    /// Calculates the cumulative distribution function at `x` by linear
+    /// interpolation of the cdf of the lattice sum
+    fn cdf(&self, x: f64) -> f64 {
+        match self.locate(x) {
+            Some((k, frac)) => {
+                let below = if k == 0 { 0.0 } else { self.cum_masses[k - 1] };
+                (below + frac * self.masses[k]).min(1.0)
+            }
+            None if x < self.start => 0.0,
+            None => 1.0,
+        }
+    }
+
+    /// Calculates the survival function at `x` by linear interpolation of
+    /// the survival function of the lattice sum
+    fn sf(&self, x: f64) -> f64 {
+        match self.locate(x) {
+            Some((k, frac)) => {
+                let above = self.cum_masses_upper.get(k + 1).cloned().unwrap_or(0.0);
+                (above + (1.0 - frac) * self.masses[k]).min(1.0)
+            }
+            None if x < self.start => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function at `p` by
+    /// inverting the piecewise linear cdf
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == 0.0 {
+            return self.min();
+        }
+        if p == 1.0 {
+            return self.max();
+        }
+        let k = self
+            .cum_masses
+            .partition_point(|&c| c < p)
+            .min(self.masses.len() - 1);
+        let below = if k == 0 { 0.0 } else { self.cum_masses[k - 1] };
+        let frac = if self.masses[k] > 0.0 {
+            ((p - below) / self.masses[k]).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.start + (k as f64 - 0.5 + frac) * self.step
+    }
+}
+
+impl<D: Min<f64>> Min<f64> for IidSum<D> {
+    /// Returns the minimum value in the domain of the sum, i.e. `n` times
+    /// the minimum of the parent
+    fn min(&self) -> f64 {
+        self.n as f64 * self.parent.min()
+    }
+}
+
+impl<D: Max<f64>> Max<f64> for IidSum<D> {
+    /// Returns the maximum value in the domain of the sum, i.e. `n` times
+    /// the maximum of the parent
+    fn max(&self) -> f64 {
+        self.n as f64 * self.parent.max()
+    }
+}
+
+impl<D: Distribution<f64>> Distribution<f64> for IidSum<D> {
+    /// Returns the mean of the sum, i.e. `n μ` where `μ` is the mean of the
+    /// parent
+    fn mean(&self) -> Option<f64> {
+        Some(self.n as f64 * self.parent.mean()?)
+    }
+    /// Returns the variance of the sum, i.e. `n σ^2` where `σ^2` is the
+    /// variance of the parent
+    fn variance(&self) -> Option<f64> {
+        Some(self.n as f64 * self.parent.variance()?)
+    }
+    /// Returns the skewness of the sum, i.e. `γ / sqrt(n)` where `γ` is the
+    /// skewness of the parent
+    fn skewness(&self) -> Option<f64> {
+        Some(self.parent.skewness()? / (self.n as f64).sqrt())
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Median<f64> for IidSum<D> {
+    /// Returns the median of the sum
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Continuous<f64, f64> for IidSum<D> {
+    /// Calculates the probability density function at `x`, which is
+    /// constant within every lattice cell
+    fn pdf(&self, x: f64) -> f64 {
+        match self.locate(x) {
+            Some((k, _)) => self.masses[k] / self.step,
+            None => 0.0,
+        }
+    }
+
+    /// Calculates the log probability density function at `x`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        fp::ln(self.pdf(x))
+    }
+}
+
+/// Implements the distribution of the sum of `n` independent copies of a
+/// discrete distribution on the non-negative integers for which no closed
+/// form is available
+///
+/// # Remarks
+///
+/// The probability mass function of the sum is computed exactly, up to
+/// rounding errors of about `1e-16` relative to its largest mass, by raising
+/// the discrete Fourier transform of the parent's pmf to the `n`th power.
+/// The parent's upper tail beyond a probability of `1e-17` is neglected. The
+/// support of the sum is limited to `2^18` points.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Categorical, Discrete, IidSumDiscrete};
+///
+/// // the sum of two dice
+/// let d = Categorical::new(&[0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0]).unwrap();
+/// let s = IidSumDiscrete::new(d, 2).unwrap();
+/// assert!((s.pmf(7) - 1.0 / 6.0).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct IidSumDiscrete<D> {
+    parent: D,
+    n: u64,
+    start: u64,
+    masses: Vec<f64>,
+    cum_masses: Vec<f64>,
+    cum_masses_upper: Vec<f64>,
+}
+
+impl<D> IidSumDiscrete<D>
+where
+    D: DiscreteCDF<u64, f64> + Discrete<u64, f64>,
+{
+    /// Constructs the distribution of the sum of `n` independent copies of
+    /// `parent`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is zero or if the support of the sum spans
+    /// more than `2^18` points
+    pub fn new(parent: D, n: u64) -> Result<IidSumDiscrete<D>> {
+        check_n(n)?;
+        let lower = parent.min();
+        let mut upper = lower;
+        while upper < parent.max() && parent.sf(upper) > TAIL * 1e-2 {
+            upper = upper.saturating_add((upper - lower).max(1));
+        }
+        let upper = upper.min(parent.max());
+        let width = upper - lower;
+        match width.checked_mul(n) {
+            Some(w) if w < MAX_LATTICE => {}
+            _ => return Err(StatsError::BadParams),
+        }
+        let summand: Vec<f64> = (lower..=upper).map(|k| parent.pmf(k)).collect();
+        let masses = convolution_power(&summand, n);
+        let (cum_masses, cum_masses_upper) = cumulate(&masses);
+        Ok(IidSumDiscrete {
+            parent,
+            n,
+            start: lower * n,
+            masses,
+            cum_masses,
+            cum_masses_upper,
+        })
+    }
+
+    /// Returns the parent distribution
+    pub fn parent(&self) -> &D {
+        &self.parent
+    }
+
+    /// Returns the number of summands
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+}
+
+impl<D: ::rand::distributions::Distribution<f64>> ::rand::distributions::Distribution<f64>
+    for IidSumDiscrete<D>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        (0..self.n).map(|_| self.parent.sample(rng)).sum()
+    }
+}
+
+impl<D> DiscreteCDF<u64, f64> for IidSumDiscrete<D>
+where
+    D: DiscreteCDF<u64, f64> + Discrete<u64, f64>,
+{
+    /// Calculates the cumulative distribution function at `x`
+    fn cdf(&self, x: u64) -> f64 {
+        if x < self.start {
+            0.0
+        } else {
+            let k = (x - self.start) as usize;
+            self.cum_masses.get(k).cloned().unwrap_or(1.0).min(1.0)
+        }
+    }
+
+    /// Calculates the survival function at `x`
+    fn sf(&self, x: u64) -> f64 {
+        if x < self.start {
+            1.0
+        } else {
+            let k = (x - self.start) as usize;
+            self.cum_masses_upper
+                .get(k + 1)
+                .cloned()
+                .unwrap_or(0.0)
+                .min(1.0)
+        }
+    }
+}
+
+impl<D: DiscreteCDF<u64, f64>> Min<u64> for IidSumDiscrete<D> {
+    /// Returns the minimum value in the domain of the sum, i.e. `n` times
+    /// the minimum of the parent
+    fn min(&self) -> u64 {
+        self.start
+    }
+}
+
+impl<D: DiscreteCDF<u64, f64>> Max<u64> for IidSumDiscrete<D> {
+    /// Returns the maximum value in the domain of the sum, i.e. `n` times
+    /// the maximum of the parent, saturating at `u64::MAX`
+    fn max(&self) -> u64 {
+        self.parent.max().saturating_mul(self.n)
+    }
+}
+
+impl<D: Distribution<f64>> Distribution<f64> for IidSumDiscrete<D> {
+    /// Returns the mean of the sum, i.e. `n μ` where `μ` is the mean of the
+    /// parent
+    fn mean(&self) -> Option<f64> {
+        Some(self.n as f64 * self.parent.mean()?)
+    }
+    /// Returns the variance of the sum, i.e. `n σ^2` where `σ^2` is the
+    /// variance of the parent
+    fn variance(&self) -> Option<f64> {
+        Some(self.n as f64 * self.parent.variance()?)
+    }
+    /// Returns the skewness of the sum, i.e. `γ / sqrt(n)` where `γ` is the
+    /// skewness of the parent
+    fn skewness(&self) -> Option<f64> {
+        Some(self.parent.skewness()? / (self.n as f64).sqrt())
+    }
+}
+
+impl<D> Discrete<u64, f64> for IidSumDiscrete<D>
+where
+    D: DiscreteCDF<u64, f64> + Discrete<u64, f64>,
+{
+    /// Calculates the probability mass function at `x`
+    fn pmf(&self, x: u64) -> f64 {
+        if x < self.start {
+            0.0
+        } else {
+            self.masses
+                .get((x - self.start) as usize)
+                .cloned()
+                .unwrap_or(0.0)
+        }
+    }
+
+    /// Calculates the log probability mass function at `x`
+    fn ln_pmf(&self, x: u64) -> f64 {
+        fp::ln(self.pmf(x))
+    }
+}
+
+/// Returns the `n`-fold convolution of the masses `p` with themselves,
+/// computed by raising their discrete Fourier transform to the `n`th power
+/// on a lattice large enough to avoid wrap-around
+fn convolution_power(p: &[f64], n: u64) -> Vec<f64> {
+    let len = (p.len() - 1) * n as usize + 1;
+    if n == 1 {
+        return p.to_vec();
+    }
+    let size = len.next_power_of_two();
+    let mut spectrum = vec![Complex::new(0.0, 0.0); size];
+    for (s, &v) in spectrum.iter_mut().zip(p) {
+        *s = Complex::new(v, 0.0);
+    }
+    fft(&mut spectrum, false);
+    for s in spectrum.iter_mut() {
+        *s = s.powu(n as u32);
+    }
+    fft(&mut spectrum, true);
+    // masses below the rounding noise of the transform are zero
+    let noise = 1e-16 * p.len().max(16) as f64;
+    spectrum[..len]
+        .iter()
+        .map(|c| if c.re > noise { c.re } else { 0.0 })
+        .collect()
+}
+
+/// Returns the cumulative sums of `masses` from below and from above,
+/// normalized to total one
+fn cumulate(masses: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let total: f64 = masses.iter().sum();
+    let mut acc = 0.0;
+    let mut lower: Vec<f64> = masses
+        .iter()
+        .map(|m| {
+            acc += m / total;
+            acc
+        })
+        .collect();
+    let mut acc = 0.0;
+    let mut upper: Vec<f64> = masses
+        .iter()
+        .rev()
+        .map(|m| {
+            acc += m / total;
+            acc
+        })
+        .collect();
+    upper.reverse();
+    // the full mass is one, not the rounded sum
+    if let (Some(l), Some(u)) = (lower.last_mut(), upper.first_mut()) {
+        *l = 1.0;
+        *u = 1.0;
+    }
+    (lower, upper)
+}
+
+/// Computes the discrete Fourier transform of `a` in place, or its inverse
+/// if `inverse` is true, with the iterative radix-2 Cooley-Tukey algorithm.
+/// The length of `a` must be a power of two.
+pub(crate) fn fft(a: &mut [Complex<f64>], inverse: bool) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let twiddles: Vec<Complex<f64>> = (0..n / 2)
+        .map(|k| Complex::from_polar(1.0, sign * 2.0 * f64::consts::PI * k as f64 / n as f64))
+        .collect();
+    let mut len = 2;
+    while len <= n {
+        let stride = n / len;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let u = a[start + k];
+                let v = a[start + k + len / 2] * twiddles[k * stride];
+                a[start + k] = u + v;
+                a[start + k + len / 2] = u - v;
+            }
+        }
+        len <<= 1;
+    }
+    if inverse {
+        let scale = 1.0 / n as f64;
+        for x in a.iter_mut() {
+            *x *= scale;
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::{Categorical, Uniform};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_exact() {
+        let n = Normal::new(1.0, 2.0).unwrap().iid_sum(4).unwrap();
+        assert_eq!(n, Normal::new(4.0, 4.0).unwrap());
+        assert_eq!(Gamma::new(1.5, 2.0).unwrap().iid_sum(3).unwrap(), Gamma::new(4.5, 2.0).unwrap());
+        assert_eq!(Exp::new(2.0).unwrap().iid_sum(3).unwrap(), Gamma::new(3.0, 2.0).unwrap());
+        assert_eq!(Erlang::new(2, 1.5).unwrap().iid_sum(3).unwrap(), Erlang::new(6, 1.5).unwrap());
+        assert_eq!(ChiSquared::new(2.0).unwrap().iid_sum(5).unwrap(), ChiSquared::new(10.0).unwrap());
+        assert_eq!(Cauchy::new(1.0, 0.5).unwrap().iid_sum(4).unwrap(), Cauchy::new(4.0, 2.0).unwrap());
+        assert_eq!(Dirac::new(1.5).unwrap().iid_sum(2).unwrap(), Dirac::new(3.0).unwrap());
+        assert_eq!(Poisson::new(0.5).unwrap().iid_sum(6).unwrap(), Poisson::new(3.0).unwrap());
+        assert_eq!(Bernoulli::new(0.2).unwrap().iid_sum(7).unwrap(), Binomial::new(0.2, 7).unwrap());
+        assert_eq!(Binomial::new(0.2, 3).unwrap().iid_sum(7).unwrap(), Binomial::new(0.2, 21).unwrap());
+        assert_eq!(NegativeBinomial::new(2.5, 0.4).unwrap().iid_sum(2).unwrap(), NegativeBinomial::new(5.0, 0.4).unwrap());
+        assert!(Poisson::new(0.5).unwrap().iid_sum(0).is_err());
+        assert!(Binomial::new(0.2, u64::MAX).unwrap().iid_sum(2).is_err());
+    }
+
+    #[test]
+    fn test_bad_create() {
+        let u = Uniform::new(0.0, 1.0).unwrap();
+        assert!(IidSum::new(u, 0).is_err());
+        assert!(IidSum::new(u, 4096).is_err());
+        assert!(IidSum::new(u, 4095).is_ok());
+        let d = Categorical::new(&[1.0; 101]).unwrap();
+        assert!(IidSumDiscrete::new(d.clone(), 0).is_err());
+        assert!(IidSumDiscrete::new(d, 10000).is_err());
+    }
+
+    #[test]
+    fn test_discrete_matches_closed_form() {
+        let p = Poisson::new(1.3).unwrap();
+        let s = IidSumDiscrete::new(p, 5).unwrap();
+        let exact = p.iid_sum(5).unwrap();
+        assert_eq!(s.n(), 5);
+        assert_eq!(s.min(), 0);
+        for x in 0..30 {
+            assert_almost_eq!(s.pmf(x), exact.pmf(x), 1e-14);
+            assert_almost_eq!(s.cdf(x), exact.cdf(x), 1e-13);
+            assert_almost_eq!(s.sf(x), exact.sf(x), 1e-13);
+        }
+        assert_almost_eq!(s.mean().unwrap(), 6.5, 1e-14);
+        assert_almost_eq!(s.variance().unwrap(), 6.5, 1e-14);
+        let b = Binomial::new(0.3, 4).unwrap();
+        let s = IidSumDiscrete::new(b, 3).unwrap();
+        let exact = b.iid_sum(3).unwrap();
+        for x in 0..=12 {
+            assert_almost_eq!(s.pmf(x), exact.pmf(x), 1e-15);
+        }
+        assert_eq!(s.pmf(13), 0.0);
+        assert_eq!(s.max(), 12);
+        test::check_discrete_distribution(&s, 12);
+    }
+
+    #[test]
+    fn test_dice() {
+        let d = Categorical::new(&[0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0]).unwrap();
+        let s = IidSumDiscrete::new(d.clone(), 2).unwrap();
+        assert_eq!(s.min(), 0);
+        assert_eq!(s.pmf(1), 0.0);
+        for x in 2..=12 {
+            assert_almost_eq!(s.pmf(x), (6.0 - (x as f64 - 7.0).abs()) / 36.0, 1e-15);
+        }
+        assert_eq!(s.cdf(12), 1.0);
+        assert_almost_eq!(s.cdf(4), 6.0 / 36.0, 1e-15);
+        assert_eq!(s.inverse_cdf(0.5), 7);
+        let three = IidSumDiscrete::new(d, 3).unwrap();
+        // 27 of the 216 outcomes of three dice sum to 10
+        assert_almost_eq!(three.pmf(10), 27.0 / 216.0, 1e-15);
+    }
+
+    #[test]
+    fn test_irwin_hall() {
+        let s = IidSum::new(Uniform::new(0.0, 1.0).unwrap(), 3).unwrap();
+        let cdf = |x: f64| {
+            if x <= 1.0 {
+                x.powi(3) / 6.0
+            } else if x <= 2.0 {
+                (-2.0 * x.powi(3) + 9.0 * x * x - 9.0 * x + 3.0) / 6.0
+            } else {
+                1.0 - (3.0 - x).powi(3) / 6.0
+            }
+        };
+        let pdf = |x: f64| {
+            if x <= 1.0 {
+                x * x / 2.0
+            } else if x <= 2.0 {
+                (-2.0 * x * x + 6.0 * x - 3.0) / 2.0
+            } else {
+                (3.0 - x).powi(2) / 2.0
+            }
+        };
+        for &x in &[0.2, 0.7, 1.0, 1.3, 1.5, 2.2, 2.9] {
+            assert_almost_eq!(s.cdf(x), cdf(x), 1e-7);
+            assert_almost_eq!(s.sf(x), 1.0 - cdf(x), 1e-7);
+            assert_almost_eq!(s.pdf(x), pdf(x), 1e-3);
+        }
+        assert_eq!((s.min(), s.max()), (0.0, 3.0));
+        assert_eq!(s.cdf(-0.1), 0.0);
+        assert_eq!(s.cdf(3.1), 1.0);
+        assert_eq!(s.pdf(3.1), 0.0);
+        assert_almost_eq!(s.mean().unwrap(), 1.5, 1e-15);
+        assert_almost_eq!(s.variance().unwrap(), 0.25, 1e-15);
+        assert_almost_eq!(s.median(), 1.5, 1e-9);
+        for &p in &[0.01, 0.3, 0.8, 0.999] {
+            assert_almost_eq!(s.cdf(s.inverse_cdf(p)), p, 1e-12);
+        }
+        test::check_continuous_distribution(&s, 0.0, 3.0);
+    }
+
+    #[test]
+    fn test_exp_matches_gamma() {
+        let e = Exp::new(2.0).unwrap();
+        let s = IidSum::new(e, 5).unwrap();
+        let exact = e.iid_sum(5).unwrap();
+        for &x in &[0.5, 1.0, 2.5, 4.0, 8.0] {
+            assert_almost_eq!(s.cdf(x), exact.cdf(x), 1e-6);
+            assert_almost_eq!(s.pdf(x), exact.pdf(x), 1e-3);
+        }
+        assert_almost_eq!(s.skewness().unwrap(), 2.0 / 5f64.sqrt(), 1e-15);
+        assert_eq!(*s.parent(), e);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let s = IidSum::new(Uniform::new(0.0, 1.0).unwrap(), 4).unwrap();
+        let samples: Vec<f64> = (0..20000).map(|_| s.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| (0.0..=4.0).contains(&x)));
+        let below = samples.iter().filter(|&&x| x <= 1.5).count() as f64 / 20000.0;
+        assert!((below - s.cdf(1.5)).abs() < 0.01);
+        let d = IidSumDiscrete::new(Categorical::new(&[0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0]).unwrap(), 2).unwrap();
+        let sevens = (0..20000).filter(|_| d.sample(&mut rng) == 7.0).count() as f64 / 20000.0;
+        assert!((sevens - 1.0 / 6.0).abs() < 0.01);
+    }
+}
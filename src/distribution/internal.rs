@@ -17,7 +17,7 @@ pub fn is_valid_multinomial(arr: &[f64], incl_zero: bool) -> bool {
 pub mod test {
     use super::is_valid_multinomial;
     use crate::consts::ACC;
-    use crate::distribution::{Continuous, ContinuousCDF, Discrete, DiscreteCDF};
+    use crate::distribution::{Continuous, ContinuousCDF, Discrete, DiscreteCDF, Parameterized};
 
     #[macro_export]
     macro_rules! testing_boiler {
@@ -196,4 +196,99 @@ pub mod test {
         let invalid = [5.2, 0.0, 1e-15, 1000000.12];
         assert!(!is_valid_multinomial(&invalid, false));
     }
+
+    fn check_params<D: Parameterized + PartialEq + ::std::fmt::Debug>(params: &[f64]) {
+        let d = D::from_params(params).unwrap();
+        assert_eq!(d.params(), params);
+        assert_eq!(D::param_names().len(), params.len());
+        assert_eq!(D::from_params(&d.params()).unwrap(), d);
+        let mut longer = params.to_vec();
+        longer.push(1.0);
+        assert!(D::from_params(&longer).is_err());
+        assert!(D::from_params(&params[1..]).is_err());
+    }
+
+    #[test]
+    fn test_parameterized() {
+        use crate::distribution::{
+            Bernoulli, Beta, BetaBinomial, Binomial, Cauchy, Chi, ChiSquared, Dirac, DiscreteGaussian,
+            DiscreteLaplace, DiscreteUniform, Erlang, Exp, FisherSnedecor, Gamma, Geometric, Hypergeometric,
+            InverseGamma, Laplace, LogNormal, NegativeBinomial, Normal, Pareto, Poisson, StudentsT,
+            Triangular, Uniform, Weibull,
+        };
+
+        check_params::<Bernoulli>(&[0.3]);
+        check_params::<Beta>(&[2.0, 3.0]);
+        check_params::<BetaBinomial>(&[10.0, 2.0, 3.0]);
+        check_params::<Binomial>(&[0.3, 10.0]);
+        check_params::<Cauchy>(&[1.0, 2.0]);
+        check_params::<Chi>(&[3.0]);
+        check_params::<ChiSquared>(&[3.0]);
+        check_params::<Dirac>(&[1.5]);
+        check_params::<DiscreteGaussian>(&[-3.0, 2.0]);
+        check_params::<DiscreteLaplace>(&[-3.0, 2.0]);
+        check_params::<DiscreteUniform>(&[-3.0, 7.0]);
+        check_params::<Erlang>(&[3.0, 0.5]);
+        check_params::<Exp>(&[0.5]);
+        check_params::<FisherSnedecor>(&[3.0, 20.0]);
+        check_params::<Gamma>(&[3.0, 0.5]);
+        check_params::<Geometric>(&[0.3]);
+        check_params::<Hypergeometric>(&[50.0, 20.0, 10.0]);
+        check_params::<InverseGamma>(&[3.0, 0.5]);
+        check_params::<Laplace>(&[1.0, 2.0]);
+        check_params::<LogNormal>(&[1.0, 2.0]);
+        check_params::<NegativeBinomial>(&[2.5, 0.3]);
+        check_params::<Normal>(&[1.0, 2.0]);
+        check_params::<Pareto>(&[1.0, 2.0]);
+        check_params::<Poisson>(&[3.5]);
+        check_params::<StudentsT>(&[1.0, 2.0, 5.0]);
+        check_params::<Triangular>(&[0.0, 5.0, 1.0]);
+        check_params::<Uniform>(&[0.0, 5.0]);
+        check_params::<Weibull>(&[2.0, 3.0]);
+        // integer parameters must be integral and in range
+        assert!(Binomial::from_params(&[0.3, 10.5]).is_err());
+        assert!(Binomial::from_params(&[0.3, -1.0]).is_err());
+        assert!(Binomial::from_params(&[0.3, f64::NAN]).is_err());
+        assert!(BetaBinomial::from_params(&[10.5, 2.0, 3.0]).is_err());
+        assert!(DiscreteUniform::from_params(&[0.0, 1e300]).is_err());
+        // and the constructors still validate
+        assert!(Normal::from_params(&[0.0, -1.0]).is_err());
+    }
+
+    fn check_vector_params<D: Parameterized + PartialEq + ::std::fmt::Debug>(params: &[f64]) {
+        let d = D::from_params(params).unwrap();
+        assert_eq!(d.params(), params);
+        assert_eq!(D::from_params(&d.params()).unwrap(), d);
+        let mut longer = params.to_vec();
+        longer.push(1.0);
+        assert!(D::from_params(&longer).is_err());
+        assert!(D::from_params(&params[..params.len() - 1]).is_err());
+        let mut wider = params.to_vec();
+        wider[0] += 1.0;
+        assert!(D::from_params(&wider).is_err());
+        assert!(D::from_params(&[]).is_err());
+    }
+
+    #[test]
+    fn test_parameterized_vector() {
+        use crate::distribution::{
+            Categorical, Dirichlet, Multinomial, MultivariateLaplace, MultivariateLogNormal,
+            MultivariateNormal, MultivariateStudent,
+        };
+
+        check_vector_params::<Categorical>(&[3.0, 0.25, 0.25, 0.5]);
+        check_vector_params::<Dirichlet>(&[3.0, 1.0, 2.0, 0.5]);
+        check_vector_params::<Multinomial>(&[3.0, 0.2, 0.3, 0.5, 10.0]);
+        check_vector_params::<MultivariateNormal>(&[2.0, 1.0, -1.0, 2.0, 0.5, 0.5, 1.0]);
+        check_vector_params::<MultivariateStudent>(&[2.0, 1.0, -1.0, 2.0, 0.5, 0.5, 1.0, 4.0]);
+        check_vector_params::<MultivariateLaplace>(&[2.0, 1.0, -1.0, 2.0, 0.5, 0.5, 1.0]);
+        check_vector_params::<MultivariateLogNormal>(&[2.0, 1.0, -1.0, 2.0, 0.5, 0.5, 1.0]);
+        assert_eq!(Categorical::new(&[1.0, 1.0, 2.0]).unwrap().params(), [3.0, 0.25, 0.25, 0.5]);
+        // the dimension must be integral and the number of trials too
+        assert!(Dirichlet::from_params(&[2.5, 1.0, 2.0]).is_err());
+        assert!(Multinomial::from_params(&[2.0, 0.5, 0.5, 2.5]).is_err());
+        // and the constructors still validate
+        assert!(MultivariateNormal::from_params(&[2.0, 0.0, 0.0, 1.0, 2.0, 2.0, 1.0]).is_err());
+        assert!(MultivariateStudent::from_params(&[2.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0]).is_err());
+    }
 }
@@ -1,4 +1,5 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Gamma, Parameterized};
+use crate::fp;
 use crate::function::gamma;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -58,6 +59,22 @@ impl InverseGamma {
         }
     }
 
+    /// Constructs a new inverse gamma distribution from parameters already
+    /// known to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(shape: f64, rate: f64) -> InverseGamma {
+        debug_assert!(
+            InverseGamma::new(shape, rate).is_ok(),
+            "invalid inverse gamma parameters"
+        );
+        InverseGamma { shape, rate }
+    }
+
     /// Returns the shape (α) of the inverse gamma distribution
     ///
     /// # Examples
@@ -201,7 +218,7 @@ impl Distribution<f64> for InverseGamma {
     /// where `α` is the shape, `β` is the rate, `Γ` is the gamma function,
     /// and `ψ` is the digamma function
     fn entropy(&self) -> Option<f64> {
-        let entr = self.shape + self.rate.ln() + gamma::ln_gamma(self.shape)
+        let entr = self.shape + fp::ln(self.rate) + gamma::ln_gamma(self.shape)
             - (1.0 + self.shape) * gamma::digamma(self.shape);
         Some(entr)
     }
@@ -227,6 +244,22 @@ impl Distribution<f64> for InverseGamma {
     }
 }
 
+impl Median<f64> for InverseGamma {
+    /// Returns the median of the inverse gamma distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 1 / G^-1(1 / 2)
+    /// ```
+    ///
+    /// where `G` is the cdf of the gamma distribution with shape `α` and
+    /// rate `β`
+    fn median(&self) -> f64 {
+        1.0 / Gamma::new(self.shape, self.rate).unwrap().median()
+    }
+}
+
 impl Mode<Option<f64>> for InverseGamma {
     /// Returns the mode of the inverse gamma distribution
     ///
@@ -257,9 +290,11 @@ impl Continuous<f64, f64> for InverseGamma {
         if x <= 0.0 || x.is_infinite() {
             0.0
         } else if ulps_eq!(self.shape, 1.0) {
-            self.rate / (x * x) * (-self.rate / x).exp()
+            self.rate / (x * x) * fp::exp(-self.rate / x)
         } else {
-            self.rate.powf(self.shape) * x.powf(-self.shape - 1.0) * (-self.rate / x).exp()
+            fp::powf(self.rate, self.shape)
+                * fp::powf(x, -self.shape - 1.0)
+                * fp::exp(-self.rate / x)
                 / gamma::gamma(self.shape)
         }
     }
@@ -275,7 +310,24 @@ impl Continuous<f64, f64> for InverseGamma {
     ///
     /// where `α` is the shape, `β` is the rate, and `Γ` is the gamma function
     fn ln_pdf(&self, x: f64) -> f64 {
-        self.pdf(x).ln()
+        fp::ln(self.pdf(x))
+    }
+}
+
+impl Parameterized for InverseGamma {
+    fn param_names() -> &'static [&'static str] {
+        &["shape", "rate"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.shape, self.rate]
+    }
+
+    fn from_params(params: &[f64]) -> Result<InverseGamma> {
+        match *params {
+            [shape, rate] => InverseGamma::new(shape, rate),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
     }
 }
 
@@ -405,6 +457,13 @@ mod tests {
         test_case(1.0, 1.0, 0.5, mode);
     }
 
+    #[test]
+    fn test_median() {
+        let median = |x: InverseGamma| x.median();
+        test_almost(1.0, 1.0, 1.0 / std::f64::consts::LN_2, 1e-14, median);
+        test_almost(3.0, 2.0, 0.74792628638022429617910171, 1e-14, median);
+    }
+
     #[test]
     fn test_min_max() {
         let min = |x: InverseGamma| x.min();
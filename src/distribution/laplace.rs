@@ -1,6 +1,11 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{
+    fit, CharacteristicFunction, Continuous, ContinuousCDF, Fit, KullbackLeibler, Parameterized,
+};
+use crate::fp;
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
+use rand::distributions::Open01;
 use rand::Rng;
 use std::f64;
 
@@ -50,6 +55,22 @@ impl Laplace {
         }
     }
 
+    /// Constructs a new laplace distribution from parameters already known to
+    /// be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(location: f64, scale: f64) -> Laplace {
+        debug_assert!(
+            Laplace::new(location, scale).is_ok(),
+            "invalid laplace parameters"
+        );
+        Laplace { location, scale }
+    }
+
     /// Returns the location of the laplace distribution
     ///
     /// # Examples
@@ -81,8 +102,7 @@ impl Laplace {
 
 impl ::rand::distributions::Distribution<f64> for Laplace {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let x: f64 = rng.gen_range(-0.5..0.5);
-        self.location - self.scale * x.signum() * (1. - 2. * x.abs()).ln()
+        self.inverse_cdf(rng.sample(Open01))
     }
 }
 
@@ -98,16 +118,85 @@ impl ContinuousCDF<f64, f64> for Laplace {
     ///
     /// where `μ` is the location, `b` is the scale
     fn cdf(&self, x: f64) -> f64 {
-        let y = (-(x - self.location).abs() / self.scale).exp() / 2.;
+        let y = fp::exp(-(x - self.location).abs() / self.scale) / 2.;
         if x >= self.location {
             1. - y
         } else {
             y
         }
     }
+
+    /// Calculates the survival function for the
+    /// laplace distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 / 2) * (1 - signum(x - μ)) + signum(x - μ) * exp(-|x - μ| / b)
+    /// ```
+    ///
+    /// where `μ` is the location, `b` is the scale
+    fn sf(&self, x: f64) -> f64 {
+        let y = fp::exp(-(x - self.location).abs() / self.scale) / 2.;
+        if x >= self.location {
+            y
+        } else {
+            1. - y
+        }
+    }
+
+    /// Calculates the log cumulative distribution function for the
+    /// laplace distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if x < μ {
+    ///     (x - μ) / b - ln(2)
+    /// } else {
+    ///     ln(1 - exp(-(x - μ) / b) / 2)
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `b` is the scale
+    fn ln_cdf(&self, x: f64) -> f64 {
+        let d = (x - self.location) / self.scale;
+        if d < 0.0 {
+            d - f64::consts::LN_2
+        } else {
+            fp::ln_1p(-0.5 * fp::exp(-d))
+        }
+    }
+
+    /// Calculates the log survival function for the
+    /// laplace distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if x > μ {
+    ///     -(x - μ) / b - ln(2)
+    /// } else {
+    ///     ln(1 - exp((x - μ) / b) / 2)
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `b` is the scale
+    fn ln_sf(&self, x: f64) -> f64 {
+        let d = (self.location - x) / self.scale;
+        if d < 0.0 {
+            d - f64::consts::LN_2
+        } else {
+            fp::ln_1p(-0.5 * fp::exp(-d))
+        }
+    }
     /// Calculates the inverse cumulative distribution function for the
     /// laplace distribution at `p`
     ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
     /// # Formula
     ///
     /// if p <= 1/2
@@ -121,13 +210,13 @@ impl ContinuousCDF<f64, f64> for Laplace {
     ///
     /// where `μ` is the location, `b` is the scale
     fn inverse_cdf(&self, p: f64) -> f64 {
-        if p <= 0. || 1. <= p {
+        if !(0.0..=1.0).contains(&p) {
             panic!("p must be in [0, 1]");
         };
         if p <= 0.5 {
-            self.location + self.scale * (2. * p)
+            self.location + self.scale * fp::ln(2. * p)
         } else {
-            self.location - self.scale * (2. - 2. * p)
+            self.location - self.scale * fp::ln(2. - 2. * p)
         }
     }
 }
@@ -195,7 +284,7 @@ impl Distribution<f64> for Laplace {
     ///
     /// where `b` is the scale
     fn entropy(&self) -> Option<f64> {
-        Some((2. * self.scale).ln() + 1.)
+        Some(fp::ln(2. * self.scale) + 1.)
     }
     /// Returns the skewness of the laplace distribution
     ///
@@ -207,6 +296,57 @@ impl Distribution<f64> for Laplace {
     fn skewness(&self) -> Option<f64> {
         Some(0.)
     }
+    /// Returns the raw moment of order `k` of the laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_{j = 0}^k C(k, j) * μ^(k - j) * E[(X - μ)^j]
+    /// ```
+    ///
+    /// where `μ` is the location and `C` the binomial coefficient
+    fn moment(&self, k: u32) -> Option<f64> {
+        shift_moments(k, self.location, |j| self.central_moment(j))
+    }
+    /// Returns the central moment of order `k` of the laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if k is even {
+    ///     k! * b^k
+    /// } else {
+    ///     0
+    /// }
+    /// ```
+    ///
+    /// where `b` is the scale
+    fn central_moment(&self, k: u32) -> Option<f64> {
+        if k % 2 == 1 {
+            Some(0.0)
+        } else {
+            Some((1..=k).map(|i| i as f64 * self.scale).product())
+        }
+    }
+    /// Returns the cumulant of order `k` of the laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// κ_1 = μ, κ_k = 2 * (k - 1)! * b^k for even k, κ_k = 0 for odd k > 1
+    /// ```
+    ///
+    /// where `μ` is the location and `b` is the scale
+    fn cumulant(&self, k: u32) -> Option<f64> {
+        match k {
+            1 => Some(self.location),
+            _ if k % 2 == 1 => Some(0.0),
+            _ => {
+                let factorial: f64 = (1..k).map(|i| i as f64 * self.scale).product();
+                Some(2.0 * factorial * self.scale)
+            }
+        }
+    }
 }
 
 impl Median<f64> for Laplace {
@@ -250,7 +390,7 @@ impl Continuous<f64, f64> for Laplace {
     /// ```
     /// where `μ` is the location and `b` is the scale
     fn pdf(&self, x: f64) -> f64 {
-        (-(x - self.location).abs() / self.scale).exp() / (2. * self.scale)
+        fp::exp(-(x - self.location).abs() / self.scale) / (2. * self.scale)
     }
 
     /// Calculates the log probability density function for the laplace
@@ -264,7 +404,85 @@ impl Continuous<f64, f64> for Laplace {
     ///
     /// where `μ` is the location and `b` is the scale
     fn ln_pdf(&self, x: f64) -> f64 {
-        ((-(x - self.location).abs() / self.scale).exp() / (2. * self.scale)).ln()
+        fp::ln(fp::exp(-(x - self.location).abs() / self.scale) / (2. * self.scale))
+    }
+}
+
+impl CharacteristicFunction<f64, f64> for Laplace {
+    /// Calculates the characteristic function for the laplace
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(iμt) / (1 + b^2 t^2)
+    /// ```
+    ///
+    /// where `μ` is the location and `b` is the scale
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let v = self.scale * t;
+        Complex::from_polar(1.0 / (1.0 + v * v), self.location * t)
+    }
+}
+
+impl KullbackLeibler for Laplace {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(b_q / b_p) + |μ_p - μ_q| / b_q + (b_p / b_q) e^(-|μ_p - μ_q| / b_p) - 1
+    /// ```
+    ///
+    /// where `μ` is the location and `b` the scale
+    fn kl_divergence(&self, other: &Laplace) -> f64 {
+        let d = (self.location - other.location).abs();
+        let r = self.scale / other.scale;
+        d / other.scale + r * fp::exp(-d / self.scale) - 1.0 - fp::ln(r)
+    }
+}
+
+impl Parameterized for Laplace {
+    fn param_names() -> &'static [&'static str] {
+        &["location", "scale"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.location, self.scale]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Laplace> {
+        match *params {
+            [location, scale] => Laplace::new(location, scale),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for Laplace {
+    type Sample = f64;
+
+    /// Fits the location as the sample median, the midpoint of the two
+    /// middle observations for an even sample size, and the scale as the
+    /// mean absolute deviation from it
+    fn fit_mle(data: &[f64]) -> Result<Laplace> {
+        fit::check_sample(data, |_| true)?;
+        let mut sorted = data.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = sorted.len();
+        let location = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        };
+        let scale = fit::mean(
+            &sorted
+                .iter()
+                .map(|x| (x - location).abs())
+                .collect::<Vec<f64>>(),
+        );
+        Laplace::new(location, scale)
     }
 }
 
@@ -452,6 +670,31 @@ mod tests {
         test_is_nan(-INF, INF, ln_pdf(-5.1));
     }
 
+    #[test]
+    fn test_ln_cdf_ln_sf() {
+        let n = try_create(1.0, 2.0);
+        for &x in &[-3.0, 0.0, 1.0, 2.5, 10.0] {
+            assert_almost_eq!(n.ln_cdf(x), n.cdf(x).ln(), 1e-14);
+            assert_almost_eq!(n.ln_sf(x), n.sf(x).ln(), 1e-14);
+        }
+        assert_almost_eq!(n.ln_cdf(-2001.0), -1001.0 - 2f64.ln(), 1e-12);
+        assert_almost_eq!(n.ln_sf(2003.0), -1001.0 - 2f64.ln(), 1e-12);
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Laplace| x.inverse_cdf(arg);
+        test_case(1.0, 2.0, 1.0, inverse_cdf(0.5));
+        test_almost(1.0, 2.0, -0.8325814637483102, 1e-15, inverse_cdf(0.2));
+        test_almost(1.0, 2.0, 4.218875824868201, 1e-14, inverse_cdf(0.9));
+        test_case(1.0, 2.0, -INF, inverse_cdf(0.0));
+        test_case(1.0, 2.0, INF, inverse_cdf(1.0));
+        let n = try_create(-3.0, 0.5);
+        for &p in [0.01, 0.25, 0.5, 0.75, 0.99].iter() {
+            assert_almost_eq!(n.cdf(n.inverse_cdf(p)), p, 1e-14);
+        }
+    }
+
     #[test]
     fn test_sample() {
         use ::rand::distributions::Distribution;
@@ -492,4 +735,28 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(1.0, 2.0).cf(0.5);
+        assert_almost_eq!(phi.re, 0.4387912809451864, 1e-15);
+        assert_almost_eq!(phi.im, 0.2397127693021015, 1e-15);
+    }
+
+    #[test]
+    fn test_moment() {
+        let n = try_create(1.0, 2.0);
+        assert_eq!(n.moment(2).unwrap(), 9.0);
+        assert_eq!(n.central_moment(3).unwrap(), 0.0);
+        assert_eq!(n.central_moment(4).unwrap(), 384.0);
+    }
+
+    #[test]
+    fn test_cumulant() {
+        let n = try_create(1.0, 2.0);
+        assert_eq!(n.cumulant(1).unwrap(), 1.0);
+        assert_eq!(n.cumulant(2).unwrap(), n.variance().unwrap());
+        assert_eq!(n.cumulant(3).unwrap(), 0.0);
+        assert_eq!(n.cumulant(4).unwrap(), 192.0);
+    }
 }
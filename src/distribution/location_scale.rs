@@ -0,0 +1,409 @@
+use crate::distribution::{Continuous, ContinuousCDF};
+use crate::fp;
+use crate::function::factorial;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the location-scale family generated by a continuous
+/// distribution, i.e. the distribution of `loc + scale * X` where `X` is
+/// distributed according to the parent
+///
+/// # Remarks
+///
+/// Every quantity is derived exactly from the corresponding quantity of the
+/// parent, so shifted and scaled versions of distributions that only come
+/// in a standard form get correct densities, quantiles and moments.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ContinuousCDF, LocationScale, StudentsT};
+/// use statrs::statistics::Distribution;
+///
+/// let t = StudentsT::new(0.0, 1.0, 5.0).unwrap();
+/// let n = LocationScale::new(t, 10.0, 2.0).unwrap();
+/// assert_eq!(n.mean().unwrap(), 10.0);
+/// assert_eq!(n.cdf(10.0), 0.5);
+/// assert_eq!(n.pdf(12.0), t.pdf(1.0) / 2.0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LocationScale<D> {
+    parent: D,
+    loc: f64,
+    scale: f64,
+}
+
+impl<D> LocationScale<D> {
+    /// Constructs the distribution of `loc + scale * X` where `X` is
+    /// distributed according to `parent`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `loc` is not finite or `scale` is not finite and
+    /// positive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Laplace, LocationScale};
+    ///
+    /// let l = Laplace::new(0.0, 1.0).unwrap();
+    /// assert!(LocationScale::new(l, 1.0, 2.0).is_ok());
+    /// assert!(LocationScale::new(l, 1.0, 0.0).is_err());
+    /// assert!(LocationScale::new(l, f64::NAN, 1.0).is_err());
+    /// ```
+    pub fn new(parent: D, loc: f64, scale: f64) -> Result<LocationScale<D>> {
+        if !loc.is_finite() || !scale.is_finite() || scale <= 0.0 {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(LocationScale { parent, loc, scale })
+        }
+    }
+
+    /// Constructs a new location-scale distribution from parameters already
+    /// known to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(parent: D, loc: f64, scale: f64) -> LocationScale<D> {
+        debug_assert!(
+            loc.is_finite() && scale.is_finite() && scale > 0.0,
+            "invalid location-scale parameters"
+        );
+        LocationScale { parent, loc, scale }
+    }
+
+    /// Returns the parent distribution
+    pub fn parent(&self) -> &D {
+        &self.parent
+    }
+
+    /// Returns the location
+    pub fn loc(&self) -> f64 {
+        self.loc
+    }
+
+    /// Returns the scale
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Maps `x` to the scale of the parent
+    fn standardize(&self, x: f64) -> f64 {
+        (x - self.loc) / self.scale
+    }
+
+    /// Maps `z` from the scale of the parent
+    fn unstandardize(&self, z: f64) -> f64 {
+        self.loc + self.scale * z
+    }
+}
+
+impl<D: ::rand::distributions::Distribution<f64>> ::rand::distributions::Distribution<f64>
+    for LocationScale<D>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.unstandardize(self.parent.sample(rng))
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f64, f64> for LocationScale<D> {
+    /// Calculates the cumulative distribution function at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F((x - μ) / σ)
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent, `μ` the location and `σ` the scale
+    fn cdf(&self, x: f64) -> f64 {
+        self.parent.cdf(self.standardize(x))
+    }
+
+    /// Calculates the survival function at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// S((x - μ) / σ)
+    /// ```
+    ///
+    /// where `S` is the survival function of the parent, `μ` the location and
+    /// `σ` the scale
+    fn sf(&self, x: f64) -> f64 {
+        self.parent.sf(self.standardize(x))
+    }
+
+    /// Calculates the inverse cumulative distribution function at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + σ F^-1(p)
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent, `μ` the location and `σ` the scale
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        self.unstandardize(self.parent.inverse_cdf(p))
+    }
+}
+
+impl<D: Min<f64>> Min<f64> for LocationScale<D> {
+    /// Returns the minimum value in the domain of the distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + σ min
+    /// ```
+    ///
+    /// where `min` is the minimum of the parent
+    fn min(&self) -> f64 {
+        self.unstandardize(self.parent.min())
+    }
+}
+
+impl<D: Max<f64>> Max<f64> for LocationScale<D> {
+    /// Returns the maximum value in the domain of the distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + σ max
+    /// ```
+    ///
+    /// where `max` is the maximum of the parent
+    fn max(&self) -> f64 {
+        self.unstandardize(self.parent.max())
+    }
+}
+
+impl<D: Distribution<f64>> Distribution<f64> for LocationScale<D> {
+    /// Returns the mean of the distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + σ m
+    /// ```
+    ///
+    /// where `m` is the mean of the parent
+    fn mean(&self) -> Option<f64> {
+        Some(self.unstandardize(self.parent.mean()?))
+    }
+    /// Returns the variance of the distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// σ^2 v
+    /// ```
+    ///
+    /// where `v` is the variance of the parent
+    fn variance(&self) -> Option<f64> {
+        Some(self.scale * self.scale * self.parent.variance()?)
+    }
+    /// Returns the entropy of the distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// H + ln(σ)
+    /// ```
+    ///
+    /// where `H` is the entropy of the parent
+    fn entropy(&self) -> Option<f64> {
+        Some(self.parent.entropy()? + fp::ln(self.scale))
+    }
+    /// Returns the skewness of the distribution, which equals the skewness
+    /// of the parent
+    fn skewness(&self) -> Option<f64> {
+        self.parent.skewness()
+    }
+    /// Returns the `k`th raw moment of the distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_j binomial(k, j) μ^(k - j) σ^j m_j
+    /// ```
+    ///
+    /// for `j = 0..=k`, where `m_j` is the `j`th raw moment of the parent
+    fn moment(&self, k: u32) -> Option<f64> {
+        (0..=k).try_fold(0.0, |acc, j| {
+            let m = if j == 0 { 1.0 } else { self.parent.moment(j)? };
+            Some(
+                acc + factorial::binomial(k as u64, j as u64)
+                    * fp::powi(self.loc, (k - j) as i32)
+                    * fp::powi(self.scale, j as i32)
+                    * m,
+            )
+        })
+    }
+    /// Returns the `k`th central moment of the distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// σ^k c_k
+    /// ```
+    ///
+    /// where `c_k` is the `k`th central moment of the parent
+    fn central_moment(&self, k: u32) -> Option<f64> {
+        Some(fp::powi(self.scale, k as i32) * self.parent.central_moment(k)?)
+    }
+}
+
+impl<D: Median<f64>> Median<f64> for LocationScale<D> {
+    /// Returns the median of the distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + σ m
+    /// ```
+    ///
+    /// where `m` is the median of the parent
+    fn median(&self) -> f64 {
+        self.unstandardize(self.parent.median())
+    }
+}
+
+impl<D: Mode<Option<f64>>> Mode<Option<f64>> for LocationScale<D> {
+    /// Returns the mode of the distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ + σ m
+    /// ```
+    ///
+    /// where `m` is the mode of the parent
+    fn mode(&self) -> Option<f64> {
+        Some(self.unstandardize(self.parent.mode()?))
+    }
+}
+
+impl<D: Continuous<f64, f64>> Continuous<f64, f64> for LocationScale<D> {
+    /// Calculates the probability density function at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// f((x - μ) / σ) / σ
+    /// ```
+    ///
+    /// where `f` is the pdf of the parent, `μ` the location and `σ` the scale
+    fn pdf(&self, x: f64) -> f64 {
+        self.parent.pdf(self.standardize(x)) / self.scale
+    }
+
+    /// Calculates the log probability density function at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(f((x - μ) / σ)) - ln(σ)
+    /// ```
+    ///
+    /// where `f` is the pdf of the parent, `μ` the location and `σ` the scale
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.parent.ln_pdf(self.standardize(x)) - fp::ln(self.scale)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::{Exp, Laplace, Normal, StudentsT};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bad_create() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        assert!(LocationScale::new(n, 0.0, -1.0).is_err());
+        assert!(LocationScale::new(n, 0.0, f64::INFINITY).is_err());
+        assert!(LocationScale::new(n, f64::INFINITY, 1.0).is_err());
+        let ls = LocationScale::new(n, 1.5, 2.0).unwrap();
+        assert_eq!((ls.loc(), ls.scale()), (1.5, 2.0));
+        assert_eq!(*ls.parent(), n);
+    }
+
+    #[test]
+    fn test_matches_normal() {
+        let ls = LocationScale::new(Normal::new(0.0, 1.0).unwrap(), 3.0, 2.5).unwrap();
+        let n = Normal::new(3.0, 2.5).unwrap();
+        for &x in &[-5.0, 0.0, 3.0, 4.2, 10.0] {
+            assert_almost_eq!(ls.pdf(x), n.pdf(x), 1e-15);
+            assert_almost_eq!(ls.ln_pdf(x), n.ln_pdf(x), 1e-14);
+            assert_almost_eq!(ls.cdf(x), n.cdf(x), 1e-15);
+            assert_almost_eq!(ls.sf(x), n.sf(x), 1e-15);
+        }
+        for &p in &[0.01, 0.3, 0.5, 0.99] {
+            assert_almost_eq!(ls.inverse_cdf(p), n.inverse_cdf(p), 1e-13);
+        }
+        assert_eq!(ls.mean().unwrap(), 3.0);
+        assert_eq!(ls.variance().unwrap(), 6.25);
+        assert_almost_eq!(ls.entropy().unwrap(), n.entropy().unwrap(), 1e-15);
+        assert_eq!(ls.skewness().unwrap(), 0.0);
+        assert_eq!(ls.median(), 3.0);
+        assert_eq!(ls.mode(), Some(3.0));
+        assert_eq!((ls.min(), ls.max()), (f64::NEG_INFINITY, f64::INFINITY));
+        test::check_continuous_distribution(&ls, -20.0, 30.0);
+    }
+
+    #[test]
+    fn test_moments() {
+        // X = 1 + 2 E with E ~ Exp(1), so E[X^2] = 1 + 4 + 4 * 2 and E[X^3] = 1 + 6 + 12 * 2 + 8 * 6
+        let ls = LocationScale::new(Exp::new(1.0).unwrap(), 1.0, 2.0).unwrap();
+        assert_eq!(ls.moment(0).unwrap(), 1.0);
+        assert_eq!(ls.moment(1).unwrap(), 3.0);
+        assert_almost_eq!(ls.moment(2).unwrap(), 13.0, 1e-13);
+        assert_almost_eq!(ls.moment(3).unwrap(), 79.0, 1e-12);
+        assert_eq!(ls.variance().unwrap(), 4.0);
+        assert_almost_eq!(ls.central_moment(3).unwrap(), 16.0, 1e-13);
+        assert_eq!(ls.skewness().unwrap(), 2.0);
+        assert_eq!(ls.min(), 1.0);
+        assert_eq!(ls.inverse_cdf(0.0), 1.0);
+        test::check_continuous_distribution(&ls, 1.0, 100.0);
+    }
+
+    #[test]
+    fn test_students_t_and_laplace() {
+        let t = StudentsT::new(0.0, 1.0, 4.0).unwrap();
+        let ls = LocationScale::new(t, -2.0, 3.0).unwrap();
+        let direct = StudentsT::new(-2.0, 3.0, 4.0).unwrap();
+        for &x in &[-10.0, -2.0, 0.5, 7.0] {
+            assert_almost_eq!(ls.pdf(x), direct.pdf(x), 1e-15);
+            assert_almost_eq!(ls.cdf(x), direct.cdf(x), 1e-14);
+        }
+        assert_almost_eq!(ls.variance().unwrap(), 9.0 * 2.0, 1e-14);
+        let l = LocationScale::new(Laplace::new(0.0, 1.0).unwrap(), 5.0, 0.5).unwrap();
+        let direct = Laplace::new(5.0, 0.5).unwrap();
+        for &x in &[3.0, 5.0, 5.3, 8.0] {
+            assert_almost_eq!(l.pdf(x), direct.pdf(x), 1e-15);
+            assert_almost_eq!(l.cdf(x), direct.cdf(x), 1e-15);
+        }
+        assert_eq!(l.mode(), Some(5.0));
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let ls = LocationScale::new(Exp::new(1.0).unwrap(), -1.0, 0.5).unwrap();
+        let samples: Vec<f64> = (0..20000).map(|_| ls.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| x >= -1.0));
+        let mean = samples.iter().sum::<f64>() / 20000.0;
+        assert!((mean + 0.5).abs() < 0.02);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{fit, Continuous, ContinuousCDF, Fit, KullbackLeibler, Parameterized};
+use crate::fp;
 use crate::function::erf;
 use crate::statistics::*;
 use crate::{consts, Result, StatsError};
@@ -53,11 +54,132 @@ impl LogNormal {
             Ok(LogNormal { location, scale })
         }
     }
+
+    /// Constructs a new log-normal distribution from parameters already known
+    /// to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(location: f64, scale: f64) -> LogNormal {
+        debug_assert!(
+            LogNormal::new(location, scale).is_ok(),
+            "invalid log-normal parameters"
+        );
+        LogNormal { location, scale }
+    }
+
+    /// Constructs a new log-normal distribution with mean `mean` and
+    /// variance `variance`, solving for the location and scale of its
+    /// logarithm
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// σ^2 = ln(1 + v / m^2)
+    /// μ = ln(m) - σ^2 / 2
+    /// ```
+    ///
+    /// where `m` is the mean, `v` the variance, `μ` the location and `σ` the
+    /// scale
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mean` or `variance` is not positive and finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::LogNormal;
+    /// use statrs::statistics::Distribution;
+    ///
+    /// let n = LogNormal::from_mean_variance(10.0, 4.0).unwrap();
+    /// assert!((n.mean().unwrap() - 10.0).abs() < 1e-12);
+    /// assert!((n.variance().unwrap() - 4.0).abs() < 1e-12);
+    /// assert!((n.scale() - 1.04f64.ln().sqrt()).abs() < 1e-15);
+    /// ```
+    pub fn from_mean_variance(mean: f64, variance: f64) -> Result<LogNormal> {
+        if !mean.is_finite() || mean <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("mean"));
+        }
+        if !variance.is_finite() || variance <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("variance"));
+        }
+        let sigma2 = fp::ln_1p(variance / (mean * mean));
+        LogNormal::new(fp::ln(mean) - sigma2 / 2.0, sigma2.sqrt())
+    }
+
+    /// Returns the location (μ) of the log-normal distribution, i.e. the
+    /// mean of its logarithm
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::LogNormal;
+    ///
+    /// let n = LogNormal::new(1.0, 2.0).unwrap();
+    /// assert_eq!(n.location(), 1.0);
+    /// ```
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale (σ) of the log-normal distribution, i.e. the
+    /// standard deviation of its logarithm
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::LogNormal;
+    ///
+    /// let n = LogNormal::new(1.0, 2.0).unwrap();
+    /// assert_eq!(n.scale(), 2.0);
+    /// ```
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Fits a log-normal distribution to `data` by the method of moments,
+    /// matching the mean `exp(μ + σ²/2)` and the variance
+    /// `(exp(σ²) - 1) exp(2μ + σ²)` of the sample, taken with the divisor `n`
+    ///
+    /// # Remarks
+    ///
+    /// Unlike `fit_mle`, which fits the logs of the sample, the moment
+    /// estimates exist for samples containing zeros.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty, has a negative or non-finite
+    /// observation, or has zero variance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::LogNormal;
+    /// use statrs::statistics::Distribution;
+    ///
+    /// let n = LogNormal::fit_moments(&[0.0, 1.0, 2.0, 5.0]).unwrap();
+    /// assert!((n.mean().unwrap() - 2.0).abs() < 1e-14);
+    /// assert!((n.variance().unwrap() - 3.5).abs() < 1e-13);
+    /// ```
+    pub fn fit_moments(data: &[f64]) -> Result<LogNormal> {
+        fit::check_sample(data, |x| x >= 0.0)?;
+        let (mean, variance) = fit::mean_variance(data);
+        if variance <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let scale2 = fp::ln_1p(variance / (mean * mean));
+        LogNormal::new(fp::ln(mean) - scale2 / 2.0, scale2.sqrt())
+    }
 }
 
 impl ::rand::distributions::Distribution<f64> for LogNormal {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        super::normal::sample_unchecked(rng, self.location, self.scale).exp()
+        let ln_x = super::normal::sample_unchecked(rng, self.location, self.scale);
+        fp::exp(ln_x)
     }
 }
 
@@ -80,7 +202,54 @@ impl ContinuousCDF<f64, f64> for LogNormal {
         } else if x.is_infinite() {
             1.0
         } else {
-            0.5 * erf::erfc((self.location - x.ln()) / (self.scale * f64::consts::SQRT_2))
+            0.5 * erf::erfc((self.location - fp::ln(x)) / (self.scale * f64::consts::SQRT_2))
+        }
+    }
+
+    /// Calculates the survival function for the log-normal
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 / 2) * erfc((ln(x) - μ) / (σ * sqrt(2)))
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale, and `erfc` is the
+    /// complementary error function
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else if x.is_infinite() {
+            0.0
+        } else {
+            0.5 * erf::erfc((fp::ln(x) - self.location) / (self.scale * f64::consts::SQRT_2))
+        }
+    }
+
+    /// Calculates the log cumulative distribution function for the
+    /// log-normal distribution at `x` as the log cdf of the underlying
+    /// normal distribution at `ln(x)`
+    fn ln_cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            f64::NEG_INFINITY
+        } else if x.is_infinite() {
+            0.0
+        } else {
+            super::normal::ln_cdf_unchecked(fp::ln(x), self.location, self.scale)
+        }
+    }
+
+    /// Calculates the log survival function for the log-normal
+    /// distribution at `x` as the log survival function of the underlying
+    /// normal distribution at `ln(x)`
+    fn ln_sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else if x.is_infinite() {
+            f64::NEG_INFINITY
+        } else {
+            super::normal::ln_cdf_unchecked(-fp::ln(x), -self.location, self.scale)
         }
     }
 }
@@ -124,7 +293,7 @@ impl Distribution<f64> for LogNormal {
     ///
     /// where `μ` is the location and `σ` is the scale
     fn mean(&self) -> Option<f64> {
-        Some((self.location + self.scale * self.scale / 2.0).exp())
+        Some(fp::exp(self.location + self.scale * self.scale / 2.0))
     }
     /// Returns the variance of the log-normal distribution
     ///
@@ -137,7 +306,7 @@ impl Distribution<f64> for LogNormal {
     /// where `μ` is the location and `σ` is the scale
     fn variance(&self) -> Option<f64> {
         let sigma2 = self.scale * self.scale;
-        Some((sigma2.exp() - 1.0) * (self.location + self.location + sigma2).exp())
+        Some(fp::exp_m1(sigma2) * fp::exp(self.location + self.location + sigma2))
     }
     /// Returns the entropy of the log-normal distribution
     ///
@@ -149,7 +318,7 @@ impl Distribution<f64> for LogNormal {
     ///
     /// where `μ` is the location and `σ` is the scale
     fn entropy(&self) -> Option<f64> {
-        Some(0.5 + self.scale.ln() + self.location + consts::LN_SQRT_2PI)
+        Some(0.5 + fp::ln(self.scale) + self.location + consts::LN_SQRT_2PI)
     }
     /// Returns the skewness of the log-normal distribution
     ///
@@ -161,9 +330,24 @@ impl Distribution<f64> for LogNormal {
     ///
     /// where `μ` is the location and `σ` is the scale
     fn skewness(&self) -> Option<f64> {
-        let expsigma2 = (self.scale * self.scale).exp();
+        let expsigma2 = fp::exp(self.scale * self.scale);
         Some((expsigma2 + 2.0) * (expsigma2 - 1.0).sqrt())
     }
+    /// Returns the raw moment of order `k` of the log-normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(kμ + k^2 σ^2 / 2)
+    /// ```
+    ///
+    /// where `μ` is the location and `σ` is the scale
+    fn moment(&self, k: u32) -> Option<f64> {
+        let k = k as f64;
+        Some(fp::exp(
+            k * self.location + k * k * self.scale * self.scale / 2.0,
+        ))
+    }
 }
 
 impl Median<f64> for LogNormal {
@@ -177,7 +361,7 @@ impl Median<f64> for LogNormal {
     ///
     /// where `μ` is the location
     fn median(&self) -> f64 {
-        self.location.exp()
+        fp::exp(self.location)
     }
 }
 
@@ -192,7 +376,7 @@ impl Mode<Option<f64>> for LogNormal {
     ///
     /// where `μ` is the location and `σ` is the scale
     fn mode(&self) -> Option<f64> {
-        Some((self.location - self.scale * self.scale).exp())
+        Some(fp::exp(self.location - self.scale * self.scale))
     }
 }
 
@@ -211,8 +395,8 @@ impl Continuous<f64, f64> for LogNormal {
         if x <= 0.0 || x.is_infinite() {
             0.0
         } else {
-            let d = (x.ln() - self.location) / self.scale;
-            (-0.5 * d * d).exp() / (x * consts::SQRT_2PI * self.scale)
+            let d = (fp::ln(x) - self.location) / self.scale;
+            fp::exp(-0.5 * d * d) / (x * consts::SQRT_2PI * self.scale)
         }
     }
 
@@ -230,12 +414,60 @@ impl Continuous<f64, f64> for LogNormal {
         if x <= 0.0 || x.is_infinite() {
             f64::NEG_INFINITY
         } else {
-            let d = (x.ln() - self.location) / self.scale;
-            (-0.5 * d * d) - consts::LN_SQRT_2PI - (x * self.scale).ln()
+            let d = (fp::ln(x) - self.location) / self.scale;
+            (-0.5 * d * d) - consts::LN_SQRT_2PI - fp::ln(x * self.scale)
         }
     }
 }
 
+impl KullbackLeibler for LogNormal {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// log-normal distribution, which equals that of the underlying normal
+    /// distributions
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(σ_q / σ_p) + (σ_p^2 + (μ_p - μ_q)^2) / (2σ_q^2) - 1 / 2
+    /// ```
+    ///
+    /// where `μ` is the location and `σ` the scale
+    fn kl_divergence(&self, other: &LogNormal) -> f64 {
+        let r = self.scale / other.scale;
+        let d = (self.location - other.location) / other.scale;
+        0.5 * (r * r - 1.0 + d * d) - fp::ln(r)
+    }
+}
+
+impl Parameterized for LogNormal {
+    fn param_names() -> &'static [&'static str] {
+        &["location", "scale"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.location, self.scale]
+    }
+
+    fn from_params(params: &[f64]) -> Result<LogNormal> {
+        match *params {
+            [location, scale] => LogNormal::new(location, scale),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for LogNormal {
+    type Sample = f64;
+
+    /// Fits the normal distribution of the logs of the sample
+    fn fit_mle(data: &[f64]) -> Result<LogNormal> {
+        fit::check_sample(data, |x| x > 0.0)?;
+        let ln_data: Vec<f64> = data.iter().map(|x| fp::ln(*x)).collect();
+        let (location, variance) = fit::mean_variance(&ln_data);
+        LogNormal::new(location, variance.sqrt())
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
@@ -266,7 +498,12 @@ mod tests {
         where F: Fn(LogNormal) -> f64
     {
         let x = get_value(mean, std_dev, eval);
+        // the expectations pin the platform libm to the last bit; musl's
+        // rounding under `deterministic` can land a few ulps away
+        #[cfg(not(feature = "deterministic"))]
         assert_eq!(expected, x);
+        #[cfg(feature = "deterministic")]
+        assert_relative_eq!(expected, x, max_relative = 8.0 * f64::EPSILON);
     }
 
     fn test_almost<F>(mean: f64, std_dev: f64, expected: f64, acc: f64, eval: F)
@@ -294,6 +531,27 @@ mod tests {
         bad_create_case(1.0, -1.0);
     }
 
+    #[test]
+    fn test_from_mean_variance() {
+        for &(m, v) in [(1.0, 1.0), (10.0, 4.0), (0.01, 100.0), (1e6, 1e-6)].iter() {
+            let n = LogNormal::from_mean_variance(m, v).unwrap();
+            assert_almost_eq!(n.mean().unwrap() / m, 1.0, 1e-12);
+            assert_almost_eq!(n.variance().unwrap() / v, 1.0, 1e-9);
+        }
+        let n = LogNormal::from_mean_variance(1.0, 1.0).unwrap();
+        assert_almost_eq!(n.location(), -0.5 * 2f64.ln(), 1e-15);
+        assert_almost_eq!(n.scale(), 2f64.ln().sqrt(), 1e-15);
+        let n = LogNormal::new(0.3, 0.7).unwrap();
+        let m = LogNormal::from_mean_variance(n.mean().unwrap(), n.variance().unwrap()).unwrap();
+        assert_almost_eq!(m.location(), 0.3, 1e-14);
+        assert_almost_eq!(m.scale(), 0.7, 1e-14);
+        assert!(LogNormal::from_mean_variance(0.0, 1.0).is_err());
+        assert!(LogNormal::from_mean_variance(-1.0, 1.0).is_err());
+        assert!(LogNormal::from_mean_variance(1.0, 0.0).is_err());
+        assert!(LogNormal::from_mean_variance(f64::INFINITY, 1.0).is_err());
+        assert!(LogNormal::from_mean_variance(1.0, f64::NAN).is_err());
+    }
+
     #[test]
     fn test_mean() {
         let mean = |x: LogNormal| x.mean().unwrap();
@@ -601,4 +859,13 @@ mod tests {
         test::check_continuous_distribution(&try_create(0.0, 0.25), 0.0, 10.0);
         test::check_continuous_distribution(&try_create(0.0, 0.5), 0.0, 10.0);
     }
+
+    #[test]
+    fn test_moment() {
+        let n = try_create(0.5, 0.3);
+        assert_eq!(n.moment(0).unwrap(), 1.0);
+        assert_almost_eq!(n.moment(1).unwrap(), n.mean().unwrap(), 1e-15);
+        assert_almost_eq!(n.moment(2).unwrap(), 3.2543742028896707, 1e-15);
+        assert_almost_eq!(n.central_moment(2).unwrap(), n.variance().unwrap(), 1e-15);
+    }
 }
@@ -0,0 +1,935 @@
+use crate::consts;
+use crate::distribution::{fit, Continuous, ContinuousCDF, Discrete, DiscreteCDF, Normal};
+use crate::fp;
+use crate::function::{exponential, optimize};
+use crate::rng::Pcg64;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::{Rng, SeedableRng};
+use std::f64;
+
+/// Implements a finite [mixture
+/// distribution](https://en.wikipedia.org/wiki/Mixture_distribution) of
+/// univariate components of the same type, which draws from the `i`th
+/// component with probability `w_i`
+///
+/// # Remarks
+///
+/// Continuous components give a continuous mixture and discrete components
+/// a discrete mixture. Moments follow from the moments of the components
+/// exactly, the density is combined in log space to avoid underflow and
+/// continuous quantiles are found by Brent's method within the bracket
+/// spanned by the component quantiles.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, Mixture, Normal};
+/// use statrs::statistics::Distribution;
+///
+/// let components = vec![Normal::new(-1.0, 1.0).unwrap(), Normal::new(1.0, 1.0).unwrap()];
+/// let n = Mixture::new(&[1.0, 1.0], components).unwrap();
+/// assert_eq!(n.mean().unwrap(), 0.0);
+/// assert_eq!(n.variance().unwrap(), 2.0);
+/// assert!((n.pdf(0.0) - 0.24197072451914337).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mixture<D> {
+    weights: Vec<f64>,
+    cum_weights: Vec<f64>,
+    components: Vec<D>,
+}
+
+impl<D> Mixture<D> {
+    /// Constructs a new mixture of `components`, drawing from the `i`th
+    /// component with probability proportional to `weights[i]`
+    ///
+    /// # Note
+    ///
+    /// The elements in `weights` do not need to be normalized
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `weights` and `components` differ in length, if
+    /// any weight is negative, `NaN` or infinite, or if all weights are zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, Mixture};
+    ///
+    /// let components = vec![Exp::new(1.0).unwrap(), Exp::new(2.0).unwrap()];
+    /// assert!(Mixture::new(&[1.0, 3.0], components.clone()).is_ok());
+    /// assert!(Mixture::new(&[1.0], components.clone()).is_err());
+    /// assert!(Mixture::new(&[1.0, -1.0], components).is_err());
+    /// ```
+    pub fn new(weights: &[f64], components: Vec<D>) -> Result<Mixture<D>> {
+        if weights.len() != components.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        if !super::internal::is_valid_multinomial(weights, true)
+            || weights.iter().any(|w| w.is_infinite())
+        {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Mixture::new_unchecked(weights, components))
+    }
+
+    /// Constructs a new mixture distribution from parameters already known to
+    /// be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(weights: &[f64], components: Vec<D>) -> Mixture<D> {
+        debug_assert!(
+            weights.len() == components.len()
+                && super::internal::is_valid_multinomial(weights, true)
+                && weights.iter().all(|w| w.is_finite()),
+            "invalid mixture parameters"
+        );
+        let sum: f64 = weights.iter().sum();
+        let weights: Vec<f64> = weights.iter().map(|w| w / sum).collect();
+        let mut acc = 0.0;
+        let cum_weights = weights
+            .iter()
+            .map(|w| {
+                acc += w;
+                acc
+            })
+            .collect();
+        Mixture {
+            weights,
+            cum_weights,
+            components,
+        }
+    }
+
+    /// Returns the normalized weights of the components
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the components
+    pub fn components(&self) -> &[D] {
+        &self.components
+    }
+
+    /// Returns the index of the component selected by the uniform draw `u`
+    fn select(&self, u: f64) -> usize {
+        let draw = u * self.cum_weights[self.cum_weights.len() - 1];
+        self.cum_weights
+            .iter()
+            .position(|&c| c > draw)
+            .unwrap_or(self.cum_weights.len() - 1)
+    }
+
+    /// Returns the weighted sum of `f` over the components
+    fn weighted_sum<F: Fn(&D) -> f64>(&self, f: F) -> f64 {
+        self.weights
+            .iter()
+            .zip(self.components.iter())
+            .filter(|&(&w, _)| w > 0.0)
+            .map(|(w, d)| w * f(d))
+            .sum()
+    }
+
+    /// Collects `f` over the components with positive weight, using zero
+    /// for the others, or returns `None` if `f` does for any of them
+    fn component_stats<F: Fn(&D) -> Option<f64>>(&self, f: F) -> Option<Vec<f64>> {
+        self.weights
+            .iter()
+            .zip(self.components.iter())
+            .map(|(&w, d)| if w > 0.0 { f(d) } else { Some(0.0) })
+            .collect()
+    }
+
+    /// Returns `ln(sum_i w_i exp(f(d_i)))` computed with the log-sum-exp
+    /// trick
+    fn ln_weighted_sum<F: Fn(&D) -> f64>(&self, f: F) -> f64 {
+        let terms: Vec<f64> = self
+            .weights
+            .iter()
+            .zip(self.components.iter())
+            .filter(|&(&w, _)| w > 0.0)
+            .map(|(w, d)| fp::ln(*w) + f(d))
+            .collect();
+        exponential::ln_sum_exp(&terms)
+    }
+}
+
+impl<D: ::rand::distributions::Distribution<f64>> ::rand::distributions::Distribution<f64>
+    for Mixture<D>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let i = self.select(rng.gen::<f64>());
+        self.components[i].sample(rng)
+    }
+}
+
+impl<T: PartialOrd, D: Min<T>> Min<T> for Mixture<D> {
+    /// Returns the minimum value in the domain of the mixture, i.e. the
+    /// smallest minimum of the components with positive weight
+    fn min(&self) -> T {
+        self.weights
+            .iter()
+            .zip(self.components.iter())
+            .filter(|&(&w, _)| w > 0.0)
+            .map(|(_, d)| d.min())
+            .fold(None, |acc: Option<T>, m| match acc {
+                Some(a) if a <= m => Some(a),
+                _ => Some(m),
+            })
+            .unwrap()
+    }
+}
+
+impl<T: PartialOrd, D: Max<T>> Max<T> for Mixture<D> {
+    /// Returns the maximum value in the domain of the mixture, i.e. the
+    /// largest maximum of the components with positive weight
+    fn max(&self) -> T {
+        self.weights
+            .iter()
+            .zip(self.components.iter())
+            .filter(|&(&w, _)| w > 0.0)
+            .map(|(_, d)| d.max())
+            .fold(None, |acc: Option<T>, m| match acc {
+                Some(a) if a >= m => Some(a),
+                _ => Some(m),
+            })
+            .unwrap()
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f64, f64> for Mixture<D> {
+    /// Calculates the cumulative distribution function for the mixture at
+    /// `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_i w_i F_i(x)
+    /// ```
+    ///
+    /// where `w_i` and `F_i` are the weight and cdf of the `i`th component
+    fn cdf(&self, x: f64) -> f64 {
+        self.weighted_sum(|d| d.cdf(x))
+    }
+
+    /// Calculates the survival function for the mixture at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_i w_i S_i(x)
+    /// ```
+    ///
+    /// where `w_i` and `S_i` are the weight and survival function of the
+    /// `i`th component
+    fn sf(&self, x: f64) -> f64 {
+        self.weighted_sum(|d| d.sf(x))
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// mixture at `p` by Brent's method, using that the quantile lies
+    /// between the smallest and the largest component quantile at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == 0.0 {
+            return self.min();
+        }
+        if p == 1.0 {
+            return self.max();
+        }
+        let (low, high) = self
+            .weights
+            .iter()
+            .zip(self.components.iter())
+            .filter(|&(&w, _)| w > 0.0)
+            .map(|(_, d)| d.inverse_cdf(p))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), q| {
+                (lo.min(q), hi.max(q))
+            });
+        if low >= high || self.cdf(low) >= p {
+            return low;
+        }
+        optimize::brent_root(|x| self.cdf(x) - p, low, high, 0.0).unwrap_or(high)
+    }
+}
+
+impl<D: DiscreteCDF<u64, f64>> DiscreteCDF<u64, f64> for Mixture<D> {
+    /// Calculates the cumulative distribution function for the mixture at
+    /// `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_i w_i F_i(x)
+    /// ```
+    ///
+    /// where `w_i` and `F_i` are the weight and cdf of the `i`th component
+    fn cdf(&self, x: u64) -> f64 {
+        self.weighted_sum(|d| d.cdf(x))
+    }
+
+    /// Calculates the survival function for the mixture at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_i w_i S_i(x)
+    /// ```
+    ///
+    /// where `w_i` and `S_i` are the weight and survival function of the
+    /// `i`th component
+    fn sf(&self, x: u64) -> f64 {
+        self.weighted_sum(|d| d.sf(x))
+    }
+}
+
+impl<D: Distribution<f64>> Distribution<f64> for Mixture<D> {
+    /// Returns the mean of the mixture
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_i w_i μ_i
+    /// ```
+    ///
+    /// where `w_i` and `μ_i` are the weight and mean of the `i`th component
+    fn mean(&self) -> Option<f64> {
+        let means = self.component_stats(|d| d.mean())?;
+        Some(self.weights.iter().zip(means).map(|(w, m)| w * m).sum())
+    }
+    /// Returns the variance of the mixture by the law of total variance
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_i w_i (σ_i^2 + (μ_i - μ)^2)
+    /// ```
+    ///
+    /// where `w_i`, `μ_i` and `σ_i^2` are the weight, mean and variance of
+    /// the `i`th component and `μ` is the mean of the mixture
+    fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let means = self.component_stats(|d| d.mean())?;
+        let vars = self.component_stats(|d| d.variance())?;
+        Some(
+            self.weights
+                .iter()
+                .zip(means.iter().zip(vars))
+                .map(|(w, (m, v))| w * (v + (m - mean) * (m - mean)))
+                .sum(),
+        )
+    }
+    /// Returns the skewness of the mixture
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_i w_i (γ_i σ_i^3 + 3 σ_i^2 d_i + d_i^3) / σ^3
+    /// ```
+    ///
+    /// where `w_i`, `σ_i^2` and `γ_i` are the weight, variance and skewness
+    /// of the `i`th component, `d_i` is the difference of its mean and the
+    /// mean of the mixture and `σ^2` is the variance of the mixture
+    fn skewness(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let var = self.variance()?;
+        let means = self.component_stats(|d| d.mean())?;
+        let vars = self.component_stats(|d| d.variance())?;
+        let skews = self.component_stats(|d| d.skewness())?;
+        let third: f64 = (0..self.weights.len())
+            .map(|i| {
+                let d = means[i] - mean;
+                self.weights[i]
+                    * (skews[i] * fp::powf(vars[i], 1.5) + 3.0 * vars[i] * d + d * d * d)
+            })
+            .sum();
+        Some(third / fp::powf(var, 1.5))
+    }
+    /// Returns the `k`th raw moment of the mixture
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_i w_i E[X_i^k]
+    /// ```
+    ///
+    /// where `w_i` is the weight and `E[X_i^k]` the `k`th raw moment of the
+    /// `i`th component
+    fn moment(&self, k: u32) -> Option<f64> {
+        let moments = self.component_stats(|d| d.moment(k))?;
+        Some(self.weights.iter().zip(moments).map(|(w, m)| w * m).sum())
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Median<f64> for Mixture<D> {
+    /// Returns the median of the mixture
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(1 / 2)
+    /// ```
+    ///
+    /// where `F` is the cdf of the mixture
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl<D: Continuous<f64, f64>> Continuous<f64, f64> for Mixture<D> {
+    /// Calculates the probability density function for the mixture at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_i w_i f_i(x)
+    /// ```
+    ///
+    /// where `w_i` and `f_i` are the weight and pdf of the `i`th component
+    fn pdf(&self, x: f64) -> f64 {
+        self.weighted_sum(|d| d.pdf(x))
+    }
+
+    /// Calculates the log probability density function for the mixture at
+    /// `x` without underflowing for small component densities
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// m + ln(sum_i exp(ln(w_i) + ln(f_i(x)) - m))
+    /// ```
+    ///
+    /// where `w_i` and `f_i` are the weight and pdf of the `i`th component
+    /// and `m` is the largest of the terms `ln(w_i) + ln(f_i(x))`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.ln_weighted_sum(|d| d.ln_pdf(x))
+    }
+}
+
+impl<D: Discrete<u64, f64>> Discrete<u64, f64> for Mixture<D> {
+    /// Calculates the probability mass function for the mixture at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_i w_i p_i(x)
+    /// ```
+    ///
+    /// where `w_i` and `p_i` are the weight and pmf of the `i`th component
+    fn pmf(&self, x: u64) -> f64 {
+        self.weighted_sum(|d| d.pmf(x))
+    }
+
+    /// Calculates the log probability mass function for the mixture at `x`
+    /// without underflowing for small component masses
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// m + ln(sum_i exp(ln(w_i) + ln(p_i(x)) - m))
+    /// ```
+    ///
+    /// where `w_i` and `p_i` are the weight and pmf of the `i`th component
+    /// and `m` is the largest of the terms `ln(w_i) + ln(p_i(x))`
+    fn ln_pmf(&self, x: u64) -> f64 {
+        self.ln_weighted_sum(|d| d.ln_pmf(x))
+    }
+}
+
+/// A mixture of normal distributions, as fitted by `fit_em`
+pub type NormalMixture = Mixture<Normal>;
+
+/// The options of `NormalMixture::fit_em`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::EmOptions;
+///
+/// let options = EmOptions::default().with_max_iter(100).with_seed(7);
+/// assert_eq!(options.max_iter(), 100);
+/// assert!(options.with_tolerance(0.0).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EmOptions {
+    max_iter: usize,
+    tolerance: f64,
+    variance_floor: f64,
+    seed: u64,
+}
+
+impl Default for EmOptions {
+    /// Returns the options allowing 500 iterations with a tolerance of
+    /// `1e-10`, a variance floor of `1e-6` and the seed `0`
+    fn default() -> EmOptions {
+        EmOptions {
+            max_iter: 500,
+            tolerance: 1e-10,
+            variance_floor: 1e-6,
+            seed: 0,
+        }
+    }
+}
+
+impl EmOptions {
+    /// Sets the maximum number of iterations
+    pub fn with_max_iter(self, max_iter: usize) -> EmOptions {
+        EmOptions { max_iter, ..self }
+    }
+
+    /// Sets the tolerance on the change of the log-likelihood between
+    /// iterations, relative to `1 + |ln L|`, below which the iterations
+    /// have converged
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `tolerance` is not positive and finite
+    pub fn with_tolerance(self, tolerance: f64) -> Result<EmOptions> {
+        if !(tolerance.is_finite() && tolerance > 0.0) {
+            return Err(StatsError::ArgMustBePositive("tolerance"));
+        }
+        Ok(EmOptions { tolerance, ..self })
+    }
+
+    /// Sets the floor of the component variances as a fraction of the
+    /// sample variance, which keeps a component from collapsing onto a
+    /// single observation, where the likelihood is unbounded
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `variance_floor` is not positive and finite
+    pub fn with_variance_floor(self, variance_floor: f64) -> Result<EmOptions> {
+        if !(variance_floor.is_finite() && variance_floor > 0.0) {
+            return Err(StatsError::ArgMustBePositive("variance_floor"));
+        }
+        Ok(EmOptions {
+            variance_floor,
+            ..self
+        })
+    }
+
+    /// Sets the seed of the `Pcg64` generator drawing the initial centers
+    pub fn with_seed(self, seed: u64) -> EmOptions {
+        EmOptions { seed, ..self }
+    }
+
+    /// Returns the maximum number of iterations
+    pub fn max_iter(&self) -> usize {
+        self.max_iter
+    }
+
+    /// Returns the convergence tolerance
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    /// Returns the variance floor as a fraction of the sample variance
+    pub fn variance_floor(&self) -> f64 {
+        self.variance_floor
+    }
+
+    /// Returns the seed of the initialization
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// The result of `NormalMixture::fit_em`, the fitted mixture together with
+/// the trace of the log-likelihood
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmFit {
+    mixture: NormalMixture,
+    ln_likelihoods: Vec<f64>,
+    converged: bool,
+}
+
+impl EmFit {
+    /// Returns the fitted mixture
+    pub fn mixture(&self) -> &NormalMixture {
+        &self.mixture
+    }
+
+    /// Consumes the result, returning the fitted mixture
+    pub fn into_mixture(self) -> NormalMixture {
+        self.mixture
+    }
+
+    /// Returns the log-likelihood of the fitted mixture
+    pub fn ln_likelihood(&self) -> f64 {
+        self.ln_likelihoods[self.ln_likelihoods.len() - 1]
+    }
+
+    /// Returns the log-likelihood of the initial mixture followed by that
+    /// after every iteration, which EM never decreases except where the
+    /// variance floor binds
+    pub fn ln_likelihoods(&self) -> &[f64] {
+        &self.ln_likelihoods
+    }
+
+    /// Returns the number of iterations done
+    pub fn iterations(&self) -> usize {
+        self.ln_likelihoods.len() - 1
+    }
+
+    /// Returns whether the log-likelihood converged within the maximum
+    /// number of iterations
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+impl Mixture<Normal> {
+    /// Fits a mixture of `k` normal distributions to `data` by maximum
+    /// likelihood with the [EM
+    /// algorithm](https://en.wikipedia.org/wiki/Expectation%E2%80%93maximization_algorithm)
+    ///
+    /// # Remarks
+    ///
+    /// The initial centers are chosen by the k-means++ seeding of Arthur &
+    /// Vassilvitskii (2007), drawing every further center with probability
+    /// proportional to the squared distance to the nearest center chosen so
+    /// far, and the initial components are fitted to the observations
+    /// nearest to each center. Every iteration then computes the posterior
+    /// probabilities of the components in log space and refits the weights,
+    /// means and variances to them, until the log-likelihood changes by
+    /// less than the tolerance. The likelihood of a normal mixture is
+    /// unbounded as a component collapses onto one observation, so the
+    /// variances are kept above the variance floor, and a component left
+    /// without observations keeps its parameters with zero weight. The
+    /// likelihood has local maxima, so fits from several seeds may be
+    /// compared by their log-likelihood. The components are returned in
+    /// increasing order of their means.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `k` is zero, or if `data` has a non-finite
+    /// observation, fewer than `k` distinct values or no variance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{EmOptions, NormalMixture};
+    /// use statrs::statistics::Distribution;
+    ///
+    /// let data = [-2.1, -1.8, -2.4, -1.9, -2.2, 3.0, 3.3, 2.8, 3.1, 2.9, 3.2];
+    /// let fit = NormalMixture::fit_em(&data, 2, EmOptions::default()).unwrap();
+    /// assert!(fit.converged());
+    /// let components = fit.mixture().components();
+    /// assert!((components[0].mean().unwrap() + 2.08).abs() < 1e-6);
+    /// assert!((fit.mixture().weights()[1] - 6.0 / 11.0).abs() < 1e-6);
+    /// ```
+    pub fn fit_em(data: &[f64], k: usize, options: EmOptions) -> Result<EmFit> {
+        if k == 0 {
+            return Err(StatsError::ArgMustBePositive("k"));
+        }
+        fit::check_sample(data, |_| true)?;
+        let n = data.len();
+        let (_, sample_variance) = fit::mean_variance(data);
+        let floor = options.variance_floor * sample_variance;
+
+        // k-means++ seeding
+        let mut rng = Pcg64::seed_from_u64(options.seed);
+        let mut centers = vec![data[rng.gen_range(0..n)]];
+        let mut distances: Vec<f64> = data.iter().map(|x| fp::powi(x - centers[0], 2)).collect();
+        while centers.len() < k {
+            let total: f64 = distances.iter().sum();
+            if total <= 0.0 {
+                return Err(StatsError::BadParams);
+            }
+            let draw = rng.gen::<f64>() * total;
+            let mut acc = 0.0;
+            let next = distances
+                .iter()
+                .position(|&d| {
+                    acc += d;
+                    acc > draw
+                })
+                .unwrap_or(n - 1);
+            let center = data[next];
+            for (d, x) in distances.iter_mut().zip(data) {
+                *d = d.min(fp::powi(x - center, 2));
+            }
+            centers.push(center);
+        }
+
+        // the initial responsibilities assign every observation to its
+        // nearest center
+        let mut resp = vec![0.0; n * k];
+        for (i, x) in data.iter().enumerate() {
+            let nearest = (0..k)
+                .min_by(|&a, &b| {
+                    let (da, db) = ((x - centers[a]).abs(), (x - centers[b]).abs());
+                    da.partial_cmp(&db).unwrap()
+                })
+                .unwrap();
+            resp[i * k + nearest] = 1.0;
+        }
+        let mut params: Vec<(f64, f64, f64)> = centers
+            .iter()
+            .map(|&center| (0.0, center, sample_variance.max(floor)))
+            .collect();
+        em_maximize(data, &resp, &mut params, floor);
+
+        let mut ln_likelihoods = vec![em_expect(data, &params, &mut resp)];
+        let mut converged = false;
+        for _ in 0..options.max_iter {
+            em_maximize(data, &resp, &mut params, floor);
+            let ln_l = em_expect(data, &params, &mut resp);
+            let change = ln_l - ln_likelihoods[ln_likelihoods.len() - 1];
+            ln_likelihoods.push(ln_l);
+            if change.abs() <= options.tolerance * (1.0 + ln_l.abs()) {
+                converged = true;
+                break;
+            }
+        }
+
+        params.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        let weights: Vec<f64> = params.iter().map(|p| p.0).collect();
+        let components = params
+            .iter()
+            .map(|&(_, mean, variance)| Normal::new(mean, variance.sqrt()))
+            .collect::<Result<Vec<Normal>>>()?;
+        Ok(EmFit {
+            mixture: Mixture::new(&weights, components)?,
+            ln_likelihoods,
+            converged,
+        })
+    }
+}
+
+/// Computes the responsibilities `resp` of the components `params`, given
+/// as `(weight, mean, variance)`, for every observation in log space and
+/// returns the log-likelihood
+fn em_expect(data: &[f64], params: &[(f64, f64, f64)], resp: &mut [f64]) -> f64 {
+    let k = params.len();
+    let mut terms = vec![0.0; k];
+    let mut ln_l = 0.0;
+    for (i, &x) in data.iter().enumerate() {
+        for (t, &(w, mean, variance)) in terms.iter_mut().zip(params) {
+            *t = fp::ln(w)
+                - 0.5 * (fp::powi(x - mean, 2) / variance + fp::ln(variance))
+                - consts::LN_SQRT_2PI;
+        }
+        let ln_p = exponential::ln_sum_exp(&terms);
+        for (r, t) in resp[i * k..(i + 1) * k].iter_mut().zip(&terms) {
+            *r = fp::exp(t - ln_p);
+        }
+        ln_l += ln_p;
+    }
+    ln_l
+}
+
+/// Refits the weights, means and variances `params` to the
+/// responsibilities `resp`, keeping the variances above `floor` and the
+/// parameters of components without responsibility
+fn em_maximize(data: &[f64], resp: &[f64], params: &mut [(f64, f64, f64)], floor: f64) {
+    let k = params.len();
+    let n = data.len() as f64;
+    for (j, p) in params.iter_mut().enumerate() {
+        let mass: f64 = (0..data.len()).map(|i| resp[i * k + j]).sum();
+        if mass <= 0.0 {
+            p.0 = 0.0;
+            continue;
+        }
+        let mean = data
+            .iter()
+            .enumerate()
+            .map(|(i, x)| resp[i * k + j] * x)
+            .sum::<f64>()
+            / mass;
+        let variance = data
+            .iter()
+            .enumerate()
+            .map(|(i, x)| resp[i * k + j] * fp::powi(x - mean, 2))
+            .sum::<f64>()
+            / mass;
+        *p = (mass / n, mean, variance.max(floor));
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::{Exp, Fit, Normal, Poisson};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn two_normals() -> Mixture<Normal> {
+        let components = vec![Normal::new(-2.0, 1.0).unwrap(), Normal::new(3.0, 0.5).unwrap()];
+        Mixture::new(&[3.0, 1.0], components).unwrap()
+    }
+
+    #[test]
+    fn test_bad_create() {
+        let c = vec![Exp::new(1.0).unwrap(), Exp::new(2.0).unwrap()];
+        assert!(matches!(Mixture::new(&[1.0], c.clone()), Err(StatsError::ContainersMustBeSameLength)));
+        assert!(Mixture::new(&[0.0, 0.0], c.clone()).is_err());
+        assert!(Mixture::new(&[1.0, f64::NAN], c.clone()).is_err());
+        assert!(Mixture::new(&[1.0, f64::INFINITY], c.clone()).is_err());
+        assert!(Mixture::<Exp>::new(&[], vec![]).is_err());
+        let m = Mixture::new(&[1.0, 0.0], c).unwrap();
+        assert_eq!(m.weights(), &[1.0, 0.0]);
+        assert_eq!(m.components().len(), 2);
+    }
+
+    #[test]
+    fn test_new_unchecked() {
+        let c = vec![Exp::new(1.0).unwrap(), Exp::new(2.0).unwrap()];
+        assert_eq!(Mixture::new_unchecked(&[3.0, 1.0], c.clone()), Mixture::new(&[3.0, 1.0], c).unwrap());
+    }
+
+    #[test]
+    fn test_density() {
+        let m = two_normals();
+        let (a, b) = (m.components()[0], m.components()[1]);
+        for &x in &[-5.0, -2.0, 0.0, 1.3, 3.0, 8.0] {
+            assert_almost_eq!(m.pdf(x), 0.75 * a.pdf(x) + 0.25 * b.pdf(x), 1e-15);
+            assert_almost_eq!(m.ln_pdf(x), m.pdf(x).ln(), 1e-13);
+            assert_almost_eq!(m.cdf(x), 0.75 * a.cdf(x) + 0.25 * b.cdf(x), 1e-15);
+            assert_almost_eq!(m.cdf(x) + m.sf(x), 1.0, 1e-15);
+        }
+        // far in the tail the density underflows but its logarithm does not
+        assert_eq!(m.pdf(-60.0), 0.0);
+        assert_almost_eq!(m.ln_pdf(-60.0), 0.75f64.ln() + a.ln_pdf(-60.0), 1e-10);
+        assert_eq!((m.min(), m.max()), (f64::NEG_INFINITY, f64::INFINITY));
+        test::check_continuous_distribution(&m, -10.0, 10.0);
+    }
+
+    #[test]
+    fn test_moments() {
+        let m = two_normals();
+        // 0.75 * -2 + 0.25 * 3
+        assert_almost_eq!(m.mean().unwrap(), -0.75, 1e-15);
+        // 0.75 * (1 + 1.25^2) + 0.25 * (0.25 + 3.75^2)
+        assert_almost_eq!(m.variance().unwrap(), 5.5, 1e-14);
+        assert_almost_eq!(m.moment(2).unwrap(), 5.5 + 0.5625, 1e-14);
+        // 0.75 * (-1.25)^3 + 0.75 * 3 * (-1.25) + 0.25 * 3.75^3 + 0.25 * 3 * 0.25 * 3.75
+        let third = 0.75 * (-1.953125 - 3.75) + 0.25 * (52.734375 + 2.8125);
+        assert_almost_eq!(m.skewness().unwrap(), third / 5.5f64.powf(1.5), 1e-14);
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let m = two_normals();
+        for &p in &[1e-10, 0.01, 0.3, 0.5, 0.75, 0.76, 0.9, 0.999] {
+            let x = m.inverse_cdf(p);
+            assert_almost_eq!(m.cdf(x), p, 1e-14);
+        }
+        assert_eq!(m.inverse_cdf(0.0), f64::NEG_INFINITY);
+        assert_eq!(m.inverse_cdf(1.0), f64::INFINITY);
+        assert_eq!(m.median(), m.inverse_cdf(0.5));
+        // identical components give the component quantile
+        let e = Exp::new(2.0).unwrap();
+        let m = Mixture::new(&[1.0, 2.0], vec![e, e]).unwrap();
+        assert_almost_eq!(m.inverse_cdf(0.4), e.inverse_cdf(0.4), 1e-15);
+    }
+
+    #[test]
+    fn test_discrete() {
+        let (a, b) = (Poisson::new(1.0).unwrap(), Poisson::new(10.0).unwrap());
+        let m = Mixture::new(&[0.5, 0.5], vec![a, b]).unwrap();
+        for x in 0..20 {
+            assert_almost_eq!(m.pmf(x), 0.5 * a.pmf(x) + 0.5 * b.pmf(x), 1e-15);
+            assert_almost_eq!(m.ln_pmf(x), m.pmf(x).ln(), 1e-13);
+        }
+        assert_eq!(m.min(), 0);
+        assert_eq!(m.mean().unwrap(), 5.5);
+        // 0.5 * (1 + 4.5^2) + 0.5 * (10 + 4.5^2)
+        assert_almost_eq!(m.variance().unwrap(), 25.75, 1e-13);
+        assert_eq!(m.inverse_cdf(m.cdf(3)), 3);
+        test::check_discrete_distribution(&m, 60);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let m = two_normals();
+        let n = 40000;
+        let samples: Vec<f64> = (0..n).map(|_| m.sample(&mut rng)).collect();
+        let right = samples.iter().filter(|&&x| x > 0.5).count() as f64 / n as f64;
+        assert!((right - m.sf(0.5)).abs() < 0.01);
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        assert!((mean - m.mean().unwrap()).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_fit_em() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let truth = Mixture::new(
+            &[0.5, 0.3, 0.2],
+            vec![Normal::new(-4.0, 1.0).unwrap(), Normal::new(0.0, 0.5).unwrap(), Normal::new(5.0, 2.0).unwrap()],
+        ).unwrap();
+        let x: Vec<f64> = (0..5000).map(|_| truth.sample(&mut rng)).collect();
+        let fit = NormalMixture::fit_em(&x, 3, EmOptions::default()).unwrap();
+        assert!(fit.converged());
+        assert_eq!(fit.iterations() + 1, fit.ln_likelihoods().len());
+        // EM never decreases the likelihood
+        for w in fit.ln_likelihoods().windows(2) {
+            assert!(w[1] >= w[0] - 1e-9 * w[0].abs());
+        }
+        let ln_l: f64 = x.iter().map(|&x| fit.mixture().ln_pdf(x)).sum();
+        assert_almost_eq!(fit.ln_likelihood(), ln_l, 1e-8 * ln_l.abs());
+        for ((w, d), (tw, td)) in fit.mixture().weights().iter().zip(fit.mixture().components())
+            .zip(truth.weights().iter().zip(truth.components()))
+        {
+            assert!((w - tw).abs() < 0.03);
+            assert!((d.mean().unwrap() - td.mean().unwrap()).abs() < 0.15);
+            assert!((d.std_dev().unwrap() / td.std_dev().unwrap() - 1.0).abs() < 0.1);
+        }
+        // the fit is reproducible from its seed
+        let options = EmOptions::default().with_seed(3);
+        assert_eq!(NormalMixture::fit_em(&x, 3, options).unwrap(), NormalMixture::fit_em(&x, 3, options).unwrap());
+        // a single component is the maximum likelihood normal
+        let fit = NormalMixture::fit_em(&x, 1, EmOptions::default()).unwrap();
+        let normal = Normal::fit_mle(&x).unwrap();
+        assert_almost_eq!(fit.mixture().components()[0].mean().unwrap(), normal.mean().unwrap(), 1e-12);
+        assert_almost_eq!(fit.mixture().components()[0].std_dev().unwrap(), normal.std_dev().unwrap(), 1e-12);
+    }
+
+    #[test]
+    fn test_fit_em_variance_collapse() {
+        // a repeated observation attracts a component whose variance is
+        // held at the floor instead of collapsing to zero
+        let mut x = vec![0.3, -1.2, 0.8, 1.5, -0.4, 0.0, 2.1, -1.7, 0.6, -0.9];
+        x.extend_from_slice(&[10.0; 4]);
+        let options = EmOptions::default().with_variance_floor(1e-4).unwrap();
+        let fit = NormalMixture::fit_em(&x, 2, options).unwrap();
+        let (_, variance) = fit::mean_variance(&x);
+        let collapsed = fit.mixture().components()[1];
+        assert_eq!(collapsed.mean().unwrap(), 10.0);
+        assert_almost_eq!(collapsed.variance().unwrap(), 1e-4 * variance, 1e-12);
+        assert!(fit.ln_likelihood().is_finite());
+        assert_almost_eq!(fit.mixture().weights()[1], 4.0 / 14.0, 1e-12);
+    }
+
+    #[test]
+    fn test_fit_em_bad_input() {
+        let x = [1.0, 2.0, 2.0, 1.0];
+        assert!(NormalMixture::fit_em(&x, 0, EmOptions::default()).is_err());
+        assert!(NormalMixture::fit_em(&x, 3, EmOptions::default()).is_err());
+        assert!(NormalMixture::fit_em(&x, 2, EmOptions::default()).is_ok());
+        assert!(NormalMixture::fit_em(&[1.0, f64::NAN], 1, EmOptions::default()).is_err());
+        assert!(NormalMixture::fit_em(&[1.0, 1.0], 1, EmOptions::default()).is_err());
+        assert!(NormalMixture::fit_em(&[], 1, EmOptions::default()).is_err());
+        assert!(EmOptions::default().with_tolerance(f64::NAN).is_err());
+        assert!(EmOptions::default().with_variance_floor(-1.0).is_err());
+        let fit = NormalMixture::fit_em(&[0.1, 0.5, 3.2, 4.0, 3.9], 2, EmOptions::default().with_max_iter(0)).unwrap();
+        assert!(!fit.converged());
+        assert_eq!(fit.iterations(), 0);
+    }
+}
@@ -2,75 +2,238 @@
 //! and provides
 //! concrete implementations for a variety of distributions.
 use super::statistics::{Max, Min};
-use ::num_traits::{float::Float, Bounded, Num};
+use crate::fp;
+use crate::function::optimize;
+use ::num_traits::{cast, float::Float, Bounded, Num, NumCast};
+use num_complex::Complex;
 
 pub use self::bernoulli::Bernoulli;
-pub use self::beta::Beta;
+pub use self::batch::SampleBatch;
+pub use self::beta::{Beta, BetaBoundary, BetaSampler};
+pub use self::beta_binomial::BetaBinomial;
 pub use self::binomial::Binomial;
 pub use self::categorical::Categorical;
 pub use self::cauchy::Cauchy;
+pub use self::censored::Censored;
 pub use self::chi::Chi;
 pub use self::chi_squared::ChiSquared;
 pub use self::dirac::Dirac;
-pub use self::dirichlet::Dirichlet;
+pub use self::dirichlet::{Dirichlet, DirichletSampler};
+pub use self::discrete_gaussian::DiscreteGaussian;
+pub use self::discrete_laplace::DiscreteLaplace;
 pub use self::discrete_uniform::DiscreteUniform;
-pub use self::empirical::Empirical;
+pub(crate) use self::discretize::tanh_sinh;
+pub(crate) use self::iid_sum::fft;
+pub use self::discretize::{discretize, Discretization, DiscretizeMethod};
+pub use self::divergence::{
+    kl_divergence_discrete, kl_divergence_monte_carlo, kl_divergence_quadrature,
+};
+pub use self::elliptical::{Elliptical, RadialGenerator};
+pub use self::empirical::{Empirical, EmpiricalInterpolation};
 pub use self::erlang::Erlang;
 pub use self::exponential::Exp;
 pub use self::fisher_snedecor::FisherSnedecor;
-pub use self::gamma::Gamma;
+pub use self::fit::{fit_censored, fit_mse, Fit, FitResult, Likelihood, ParameterEstimate};
+pub use self::gamma::{Gamma, GammaSampler};
+pub use self::generalized_extreme_value::GeneralizedExtremeValue;
+pub use self::generalized_pareto::GeneralizedPareto;
 pub use self::geometric::Geometric;
+pub use self::gumbel::Gumbel;
 pub use self::hypergeometric::Hypergeometric;
+pub use self::iid_sum::{IidSum, IidSumDiscrete, IidSumExact};
 pub use self::inverse_gamma::InverseGamma;
 pub use self::laplace::Laplace;
+pub use self::location_scale::LocationScale;
 pub use self::log_normal::LogNormal;
+pub use self::mixture::{EmFit, EmOptions, Mixture, NormalMixture};
 pub use self::multinomial::Multinomial;
-pub use self::multivariate_normal::MultivariateNormal;
+pub use self::multivariate_laplace::{LaplaceGenerator, MultivariateLaplace};
+pub use self::multivariate_log_normal::MultivariateLogNormal;
+pub use self::multivariate_normal::{GaussianGenerator, MultivariateNormal};
+pub use self::multivariate_students_t::{MultivariateStudent, StudentsTGenerator};
 pub use self::negative_binomial::NegativeBinomial;
 pub use self::normal::Normal;
-pub use self::pareto::Pareto;
-pub use self::poisson::Poisson;
+pub use self::observation::Observation;
+pub use self::order_statistic::OrderStatistic;
+pub use self::pareto::{hill_estimator, hill_plot, HillEstimate, Pareto};
+pub use self::piecewise::{PiecewiseConstant, PiecewiseLinear};
+pub use self::poisson::{dispersion_test, DispersionTest, Poisson, PoissonSampler};
+pub use self::single_precision::SinglePrecision;
+pub use self::spec::{DynDistribution, ParseDistributionError};
 pub use self::students_t::StudentsT;
+pub use self::transformed::Transformed;
 pub use self::triangular::Triangular;
+pub use self::truncated::{Truncated, TruncatedDiscrete};
 pub use self::uniform::Uniform;
 pub use self::weibull::Weibull;
 
+mod batch;
 mod bernoulli;
 mod beta;
+mod beta_binomial;
 mod binomial;
 mod categorical;
 mod cauchy;
+mod censored;
 mod chi;
 mod chi_squared;
 mod dirac;
 mod dirichlet;
+mod discrete_gaussian;
+mod discrete_laplace;
 mod discrete_uniform;
+mod discretize;
+mod divergence;
+pub mod dynamic;
+mod elliptical;
 mod empirical;
 mod erlang;
 mod exponential;
 mod fisher_snedecor;
+mod fit;
 mod gamma;
+mod generalized_extreme_value;
+mod generalized_pareto;
 mod geometric;
+mod gumbel;
 mod hypergeometric;
+mod iid_sum;
 #[macro_use]
 mod internal;
 mod inverse_gamma;
 mod laplace;
+mod location_scale;
 mod log_normal;
+mod mixture;
 mod multinomial;
+mod multivariate_laplace;
+mod multivariate_log_normal;
 mod multivariate_normal;
+mod multivariate_students_t;
 mod negative_binomial;
 mod normal;
+mod observation;
+mod order_statistic;
+#[cfg(feature = "rayon")]
+pub mod parallel;
 mod pareto;
+mod piecewise;
 mod poisson;
+mod single_precision;
+mod spec;
 mod students_t;
+mod transformed;
 mod triangular;
+mod truncated;
 mod uniform;
 mod weibull;
 mod ziggurat;
 mod ziggurat_tables;
 
-use crate::Result;
+use crate::{Result, StatsError};
+
+/// Returns an error if the argument `x` of a checked evaluation is `NaN`
+fn check_argument<K: Float>(x: K) -> Result<()> {
+    if x.is_nan() {
+        Err(StatsError::ArgIntervalIncl(
+            "x",
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns an error if the probability `p` of a checked evaluation is not
+/// in `[0, 1]`
+fn check_probability<T: Float>(p: T) -> Result<()> {
+    if p >= T::zero() && p <= T::one() {
+        Ok(())
+    } else {
+        Err(StatsError::ArgIntervalIncl("p", 0.0, 1.0))
+    }
+}
+
+/// The `Parameterized` trait exposes the parameters of a distribution
+/// family as a flat list of floats, so that fitting, serialization and
+/// optimizer code can treat families uniformly
+///
+/// # Remarks
+///
+/// The parameters are those of the family's `new` constructor, in the same
+/// order. Integer parameters such as a number of trials are given as floats
+/// and `from_params` rejects values that are not integral.
+///
+/// Families with vector parameters, such as `Dirichlet` or
+/// `MultivariateNormal`, lead `params` with their dimension `k` and then
+/// list each vector in turn, matrices in the column-major order taken by
+/// their constructor. Their `param_names` name the dimension and each
+/// vector, so they are shorter than `params`. `Categorical` gives its
+/// normalized probability masses.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Gamma, Normal, Parameterized};
+///
+/// fn describe<D: Parameterized>(d: &D) -> String {
+///     D::param_names()
+///         .iter()
+///         .zip(d.params())
+///         .map(|(name, value)| format!("{}={}", name, value))
+///         .collect::<Vec<_>>()
+///         .join(", ")
+/// }
+///
+/// assert_eq!(describe(&Normal::new(1.0, 2.0).unwrap()), "mean=1, std_dev=2");
+/// let g = Gamma::from_params(&[3.0, 0.5]).unwrap();
+/// assert_eq!(g.params(), vec![3.0, 0.5]);
+/// assert!(Gamma::from_params(&[3.0]).is_err());
+/// ```
+pub trait Parameterized: Sized {
+    /// Returns the names of the parameters in the order of `params`
+    fn param_names() -> &'static [&'static str];
+
+    /// Returns the values of the parameters
+    fn params(&self) -> Vec<f64>;
+
+    /// Constructs the distribution from the values of its parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of values does not match
+    /// `param_names`, or the leading dimension for families with vector
+    /// parameters, or if the family's constructor rejects them
+    fn from_params(params: &[f64]) -> Result<Self>;
+}
+
+/// Converts the value `x` of an integer parameter given to `from_params`,
+/// failing if it is not integral or out of range
+fn integer_param<I: NumCast>(x: f64) -> Result<I> {
+    if x.fract() == 0.0 {
+        cast(x).ok_or(StatsError::BadParams)
+    } else {
+        Err(StatsError::BadParams)
+    }
+}
+
+/// Splits the values given to `from_params` of a family with vector
+/// parameters into their leading dimension `k` and the values that follow,
+/// failing unless there are `len(k)` of them
+fn dimension_param(params: &[f64], len: fn(usize) -> usize) -> Result<(usize, &[f64])> {
+    match params.split_first() {
+        Some((&k, rest)) => {
+            let k: usize = integer_param(k)?;
+            if k <= rest.len() && len(k) == rest.len() {
+                Ok((k, rest))
+            } else {
+                Err(StatsError::ContainersMustBeSameLength)
+            }
+        }
+        None => Err(StatsError::ContainersMustBeSameLength),
+    }
+}
 
 /// The `ContinuousCDF` trait is used to specify an interface for univariate
 /// distributions for which cdf float arguments are sensible.
@@ -88,12 +251,124 @@ pub trait ContinuousCDF<K: Float, T: Float>: Min<K> + Max<K> {
     /// assert_eq!(0.5, n.cdf(0.5));
     /// ```
     fn cdf(&self, x: K) -> T;
-    /// Due to issues with rounding and floating-point accuracy the default
-    /// implementation may be ill-behaved.
-    /// Specialized inverse cdfs should be used whenever possible.
-    /// Performs a binary search on the domain of `cdf` to obtain an approximation
-    /// of `F^-1(p) := inf { x | F(x) >= p }`. Needless to say, performance may
-    /// may be lacking.
+
+    /// Returns the natural logarithm of the cumulative distribution function
+    /// calculated at `x` for a given distribution. May panic depending on
+    /// the implementor.
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation evaluates `cdf(x).ln()`, which underflows
+    /// to `-inf` in the lower tail; distributions override it where a stable
+    /// form is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{ContinuousCDF, Normal};
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// assert!(n.ln_cdf(-40.0).is_finite());
+    /// assert_eq!(n.cdf(-40.0).ln(), f64::NEG_INFINITY);
+    /// ```
+    fn ln_cdf(&self, x: K) -> T {
+        fp::ln_float(self.cdf(x))
+    }
+
+    /// Returns the survival function calculated at `x` for a given
+    /// distribution, i.e. `P(X > x) = 1 - cdf(x)`. May panic depending
+    /// on the implementor.
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation evaluates `1 - cdf(x)`, which loses all
+    /// precision in the upper tail; distributions with a complemented form
+    /// of their cdf override it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{ContinuousCDF, Normal};
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// assert!(n.sf(10.0) > 0.0);
+    /// assert_eq!(1.0 - n.cdf(10.0), 0.0);
+    /// ```
+    fn sf(&self, x: K) -> T {
+        T::one() - self.cdf(x)
+    }
+
+    /// Returns the natural logarithm of the survival function calculated at
+    /// `x` for a given distribution. May panic depending on the implementor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{ContinuousCDF, Exp};
+    ///
+    /// let n = Exp::new(1.0).unwrap();
+    /// assert_eq!(n.ln_sf(800.0), -800.0);
+    /// ```
+    fn ln_sf(&self, x: K) -> T {
+        fp::ln_float(self.sf(x))
+    }
+
+    /// Returns the probability that a random variable of a given
+    /// distribution falls between `a` and `b`, i.e. `P(a < X <= b)`, which
+    /// is zero if `b <= a`. May panic depending on the implementor.
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation evaluates `cdf(b) - cdf(a)`, or
+    /// `sf(a) - sf(b)` if the interval lies in the upper half of the
+    /// distribution so that upper tail probabilities do not cancel against
+    /// one. Distributions with a closed form override it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{ContinuousCDF, Normal};
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// assert!(n.prob_between(9.0, 10.0) > 0.0);
+    /// assert_eq!(n.cdf(10.0) - n.cdf(9.0), 0.0);
+    /// ```
+    fn prob_between(&self, a: K, b: K) -> T {
+        if b <= a {
+            return T::zero();
+        }
+        let lower = self.cdf(a);
+        if lower > T::one() / (T::one() + T::one()) {
+            self.sf(a) - self.sf(b)
+        } else {
+            self.cdf(b) - lower
+        }
+    }
+
+    /// Returns the inverse of the cumulative distribution function
+    /// calculated at `p` for a given distribution, i.e.
+    /// `F^-1(p) := inf { x | F(x) >= p }`. May panic depending on the
+    /// implementor.
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation first brackets the quantile, starting from
+    /// the bounds of the support where they are finite and expanding the
+    /// bracket geometrically otherwise, and then refines it to machine
+    /// precision with Brent's method. It only relies on `cdf` being monotone,
+    /// so every distribution gets quantiles, but specialized inverse cdfs
+    /// are faster and more accurate in the tails and should be used whenever
+    /// possible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{ContinuousCDF, Gamma};
+    ///
+    /// let n = Gamma::new(3.0, 2.0).unwrap();
+    /// let x = n.inverse_cdf(0.9);
+    /// assert!((n.cdf(x) - 0.9).abs() < 1e-14);
+    /// ```
     fn inverse_cdf(&self, p: T) -> K {
         if p == T::zero() {
             return self.min();
@@ -102,25 +377,107 @@ pub trait ContinuousCDF<K: Float, T: Float>: Min<K> + Max<K> {
             return self.max();
         };
         let two = K::one() + K::one();
-        let mut high = two;
-        let mut low = -high;
-        while self.cdf(low) > p {
-            low = low + low;
+        let (mut low, mut high) = (self.min(), self.max());
+        let anchor = if low.is_finite() {
+            low
+        } else if high.is_finite() {
+            high
+        } else {
+            K::zero()
+        };
+        if !low.is_finite() {
+            let mut step = K::one();
+            low = anchor - step;
+            while low.is_finite() && self.cdf(low) >= p {
+                step = step * two;
+                low = anchor - step;
+            }
         }
-        while self.cdf(high) < p {
-            high = high + high;
+        if !high.is_finite() {
+            let mut step = K::one();
+            high = anchor + step;
+            while high.is_finite() && self.cdf(high) < p {
+                step = step * two;
+                high = anchor + step;
+            }
         }
-        let mut i = 16;
-        while i != 0 {
-            let mid = (high + low) / two;
-            if self.cdf(mid) >= p {
-                high = mid;
-            } else {
-                low = mid;
+        let target = p.to_f64().unwrap();
+        let root = optimize::brent_root(
+            |x| self.cdf(cast(x).unwrap()).to_f64().unwrap() - target,
+            low.to_f64().unwrap(),
+            high.to_f64().unwrap(),
+            0.0,
+        );
+        match root {
+            Ok(x) => cast(x).unwrap(),
+            // fall back to bisection if the cdf is not well-behaved enough
+            // for Brent's method, e.g. if it returns `NaN`
+            Err(_) => {
+                for _ in 0..128 {
+                    let mid = (high + low) / two;
+                    if self.cdf(mid) >= p {
+                        high = mid;
+                    } else {
+                        low = mid;
+                    }
+                }
+                (high + low) / two
             }
-            i -= 1;
         }
-        (high + low) / two
+    }
+    /// Returns the cumulative distribution function calculated at `x`,
+    /// or an error instead of a meaningless result if `x` is `NaN`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` is `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{ContinuousCDF, Normal};
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// assert_eq!(n.checked_cdf(0.0).unwrap(), 0.5);
+    /// assert!(n.checked_cdf(f64::NAN).is_err());
+    /// ```
+    fn checked_cdf(&self, x: K) -> Result<T> {
+        check_argument(x)?;
+        Ok(self.cdf(x))
+    }
+
+    /// Returns the survival function calculated at `x`, or an error
+    /// instead of a meaningless result if `x` is `NaN`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` is `NaN`
+    fn checked_sf(&self, x: K) -> Result<T> {
+        check_argument(x)?;
+        Ok(self.sf(x))
+    }
+
+    /// Returns the inverse of the cumulative distribution function
+    /// calculated at `p`, or an error where `inverse_cdf` would panic or
+    /// return a meaningless result
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `p` is not in `[0, 1]`, including if `p` is `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{ContinuousCDF, Exp};
+    ///
+    /// let n = Exp::new(1.0).unwrap();
+    /// assert_eq!(n.checked_inverse_cdf(0.0).unwrap(), 0.0);
+    /// assert!(n.checked_inverse_cdf(1.5).is_err());
+    /// assert!(n.checked_inverse_cdf(f64::NAN).is_err());
+    /// ```
+    fn checked_inverse_cdf(&self, p: T) -> Result<K> {
+        check_probability(p)?;
+        Ok(self.inverse_cdf(p))
     }
 }
 
@@ -140,10 +497,110 @@ pub trait DiscreteCDF<K: Bounded + Clone + Num, T: Float>: Min<K> + Max<K> {
     /// assert_eq!(0.5, n.cdf(0.5));
     /// ```
     fn cdf(&self, x: K) -> T;
+
+    /// Returns the natural logarithm of the cumulative distribution function
+    /// calculated at `x` for a given distribution. May panic depending on
+    /// the implementor.
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation evaluates `cdf(x).ln()`, which underflows
+    /// to `-inf` in the lower tail; distributions override it where a stable
+    /// form is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{DiscreteCDF, Geometric};
+    ///
+    /// let n = Geometric::new(1e-20).unwrap();
+    /// assert!((n.ln_cdf(1) - (1e-20f64).ln()).abs() < 1e-12);
+    /// ```
+    fn ln_cdf(&self, x: K) -> T {
+        fp::ln_float(self.cdf(x))
+    }
+
+    /// Returns the survival function calculated at `x` for a given
+    /// distribution, i.e. `P(X > x) = 1 - cdf(x)`. May panic depending
+    /// on the implementor.
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation evaluates `1 - cdf(x)`, which loses all
+    /// precision in the upper tail; distributions with a complemented form
+    /// of their cdf override it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{DiscreteCDF, Poisson};
+    ///
+    /// let n = Poisson::new(1.0).unwrap();
+    /// assert!(n.sf(30) > 0.0);
+    /// assert_eq!(1.0 - n.cdf(30), 0.0);
+    /// ```
+    fn sf(&self, x: K) -> T {
+        T::one() - self.cdf(x)
+    }
+
+    /// Returns the natural logarithm of the survival function calculated at
+    /// `x` for a given distribution. May panic depending on the implementor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{DiscreteCDF, Geometric};
+    ///
+    /// let n = Geometric::new(0.5).unwrap();
+    /// assert_eq!(n.ln_sf(2), 2.0 * 0.5f64.ln());
+    /// ```
+    fn ln_sf(&self, x: K) -> T {
+        fp::ln_float(self.sf(x))
+    }
+
+    /// Returns the probability that a random variable of a given
+    /// distribution falls between `a` and `b`, i.e. `P(a < X <= b)`, which
+    /// is zero if `b <= a`. May panic depending on the implementor.
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation evaluates `cdf(b) - cdf(a)`, or
+    /// `sf(a) - sf(b)` if the interval lies in the upper half of the
+    /// distribution so that upper tail probabilities do not cancel against
+    /// one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{DiscreteCDF, Poisson};
+    ///
+    /// let n = Poisson::new(1.0).unwrap();
+    /// assert!(n.prob_between(25, 30) > 0.0);
+    /// assert_eq!(n.cdf(30) - n.cdf(25), 0.0);
+    /// ```
+    fn prob_between(&self, a: K, b: K) -> T {
+        let lower = self.cdf(a.clone());
+        let p = if lower > T::one() / (T::one() + T::one()) {
+            self.sf(a) - self.sf(b)
+        } else {
+            self.cdf(b) - lower
+        };
+        p.max(T::zero())
+    }
+
     /// Due to issues with rounding and floating-point accuracy the default implementation may be ill-behaved
     /// Specialized inverse cdfs should be used whenever possible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{DiscreteCDF, NegativeBinomial};
+    ///
+    /// let n = NegativeBinomial::new(4.0, 0.5).unwrap();
+    /// let x = n.inverse_cdf(0.5);
+    /// assert!(n.cdf(x - 1) < 0.5 && n.cdf(x) >= 0.5);
+    /// ```
     fn inverse_cdf(&self, p: T) -> K {
-        // TODO: fix integer implementation
         if p == T::zero() {
             return self.min();
         };
@@ -151,20 +608,50 @@ pub trait DiscreteCDF<K: Bounded + Clone + Num, T: Float>: Min<K> + Max<K> {
             return self.max();
         };
         let two = K::one() + K::one();
-        let mut high = two.clone();
         let mut low = K::min_value();
+        if self.cdf(low.clone()) >= p {
+            return low;
+        }
+        let mut high = two.clone();
         while self.cdf(high.clone()) < p {
             high = high.clone() + high.clone();
         }
-        while high != low {
-            let mid = (high.clone() + low.clone()) / two.clone();
+        // bisect while keeping `cdf(low) < p <= cdf(high)`, computing the
+        // midpoint without overflowing
+        loop {
+            let mid = low.clone() / two.clone()
+                + high.clone() / two.clone()
+                + (low.clone() % two.clone() + high.clone() % two.clone()) / two.clone();
+            if mid == low || mid == high {
+                return high;
+            }
             if self.cdf(mid.clone()) >= p {
                 high = mid;
             } else {
                 low = mid;
             }
         }
-        high
+    }
+    /// Returns the inverse of the cumulative distribution function
+    /// calculated at `p`, or an error where `inverse_cdf` would panic or
+    /// return a meaningless result
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `p` is not in `[0, 1]`, including if `p` is `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{DiscreteCDF, Poisson};
+    ///
+    /// let n = Poisson::new(3.0).unwrap();
+    /// assert_eq!(n.checked_inverse_cdf(0.5).unwrap(), 3);
+    /// assert!(n.checked_inverse_cdf(-0.1).is_err());
+    /// ```
+    fn checked_inverse_cdf(&self, p: T) -> Result<K> {
+        check_probability(p)?;
+        Ok(self.inverse_cdf(p))
     }
 }
 
@@ -204,6 +691,44 @@ pub trait Continuous<K, T> {
     /// assert_eq!(0.0, n.ln_pdf(0.5));
     /// ```
     fn ln_pdf(&self, x: K) -> T;
+
+    /// Returns the probability density function calculated at `x`, or an
+    /// error instead of a meaningless result if `x` is `NaN`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` is `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Continuous, Uniform};
+    ///
+    /// let n = Uniform::new(0.0, 1.0).unwrap();
+    /// assert_eq!(n.checked_pdf(0.5).unwrap(), 1.0);
+    /// assert!(n.checked_pdf(f64::NAN).is_err());
+    /// ```
+    fn checked_pdf(&self, x: K) -> Result<T>
+    where
+        K: Float,
+    {
+        check_argument(x)?;
+        Ok(self.pdf(x))
+    }
+
+    /// Returns the log of the probability density function calculated at
+    /// `x`, or an error instead of a meaningless result if `x` is `NaN`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `x` is `NaN`
+    fn checked_ln_pdf(&self, x: K) -> Result<T>
+    where
+        K: Float,
+    {
+        check_argument(x)?;
+        Ok(self.ln_pdf(x))
+    }
 }
 
 /// The `Discrete` trait provides an interface for interacting with discrete
@@ -245,3 +770,47 @@ pub trait Discrete<K, T> {
     /// ```
     fn ln_pmf(&self, x: K) -> T;
 }
+
+/// The `CharacteristicFunction` trait provides an interface for evaluating
+/// the [characteristic
+/// function](https://en.wikipedia.org/wiki/Characteristic_function_(probability_theory))
+/// `E[e^(itX)]` of a univariate distribution, e.g. for Fourier inversion
+/// of densities or Fourier-based option pricing
+pub trait CharacteristicFunction<K, T> {
+    /// Returns the characteristic function calculated at `t` for a given
+    /// distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{CharacteristicFunction, Normal};
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// let phi = n.cf(2.0);
+    /// assert!((phi.re - (-2f64).exp()).abs() < 1e-15);
+    /// assert_eq!(phi.im, 0.0);
+    /// ```
+    fn cf(&self, t: K) -> Complex<T>;
+}
+
+/// The `KullbackLeibler` trait provides the closed-form [Kullback-Leibler
+/// divergence](https://en.wikipedia.org/wiki/Kullback%E2%80%93Leibler_divergence)
+/// `D_KL(P || Q) = E_P[ln(p(X) / q(X))]` between two distributions of the
+/// same family. For other pairs see `kl_divergence_quadrature`,
+/// `kl_divergence_discrete` and `kl_divergence_monte_carlo`.
+pub trait KullbackLeibler<Rhs = Self> {
+    /// Returns the Kullback-Leibler divergence of `other` from `self`,
+    /// which is infinite if `self` puts mass where `other` does not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{KullbackLeibler, Normal};
+    ///
+    /// let p = Normal::new(0.0, 1.0).unwrap();
+    /// let q = Normal::new(1.0, 1.0).unwrap();
+    /// assert_eq!(p.kl_divergence(&q), 0.5);
+    /// assert_eq!(p.kl_divergence(&p), 0.0);
+    /// ```
+    fn kl_divergence(&self, other: &Rhs) -> f64;
+}
@@ -1,4 +1,6 @@
-use crate::distribution::Discrete;
+use crate::distribution::categorical::AliasTable;
+use crate::distribution::{dimension_param, integer_param, Discrete, Parameterized};
+use crate::fp;
 use crate::function::factorial;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -25,6 +27,7 @@ use rand::Rng;
 pub struct Multinomial {
     p: Vec<f64>,
     n: u64,
+    alias: AliasTable,
 }
 
 impl Multinomial {
@@ -55,7 +58,27 @@ impl Multinomial {
         if !super::internal::is_valid_multinomial(p, true) {
             Err(StatsError::BadParams)
         } else {
-            Ok(Multinomial { p: p.to_vec(), n })
+            Ok(Multinomial::new_unchecked(p, n))
+        }
+    }
+
+    /// Constructs a new multinomial distribution from parameters already known
+    /// to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(p: &[f64], n: u64) -> Multinomial {
+        debug_assert!(
+            super::internal::is_valid_multinomial(p, true),
+            "invalid multinomial parameters"
+        );
+        Multinomial {
+            p: p.to_vec(),
+            n,
+            alias: AliasTable::new(p),
         }
     }
 
@@ -92,12 +115,9 @@ impl Multinomial {
 
 impl ::rand::distributions::Distribution<Vec<f64>> for Multinomial {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
-        let p_cdf = super::categorical::prob_mass_to_cdf(self.p());
         let mut res = vec![0.0; self.p.len()];
         for _ in 0..self.n {
-            let i = super::categorical::sample_unchecked(rng, &p_cdf);
-            let el = res.get_mut(i as usize).unwrap();
-            *el += 1.0;
+            res[self.alias.sample(rng)] += 1.0;
         }
         res
     }
@@ -196,7 +216,7 @@ impl<'a> Discrete<&'a [u64], f64> for Multinomial {
                 .p
                 .iter()
                 .zip(x.iter())
-                .fold(1.0, |acc, (pi, xi)| acc * pi.powf(*xi as f64));
+                .fold(1.0, |acc, (pi, xi)| acc * fp::powf(*pi, *xi as f64));
         val
     }
 
@@ -226,13 +246,13 @@ impl<'a> Discrete<&'a [u64], f64> for Multinomial {
         if x.iter().sum::<u64>() != self.n {
             return f64::NEG_INFINITY;
         }
-        let coeff = factorial::multinomial(self.n, x).ln();
+        let coeff = fp::ln(factorial::multinomial(self.n, x));
         let val = coeff
             + self
                 .p
                 .iter()
                 .zip(x.iter())
-                .map(|(pi, xi)| *xi as f64 * pi.ln())
+                .map(|(pi, xi)| *xi as f64 * fp::ln(*pi))
                 .fold(0.0, |acc, x| acc + x);
         val
     }
@@ -378,3 +398,21 @@ impl<'a> Discrete<&'a [u64], f64> for Multinomial {
 //         n.ln_pmf(&[1, 3]);
 //     }
 // }
+
+impl Parameterized for Multinomial {
+    fn param_names() -> &'static [&'static str] {
+        &["k", "p", "n"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        let mut params = vec![self.p.len() as f64];
+        params.extend_from_slice(&self.p);
+        params.push(self.n as f64);
+        params
+    }
+
+    fn from_params(params: &[f64]) -> Result<Multinomial> {
+        let (k, rest) = dimension_param(params, |k| k + 1)?;
+        Multinomial::new(&rest[..k], integer_param(rest[k])?)
+    }
+}
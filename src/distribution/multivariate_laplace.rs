@@ -0,0 +1,163 @@
+use crate::distribution::{dimension_param, Elliptical, Normal, Parameterized, RadialGenerator};
+use crate::fp;
+use crate::function::gamma;
+use crate::Result;
+use nalgebra::DVector;
+use rand::Rng;
+use std::f64;
+use std::f64::consts::{LN_2, PI};
+
+/// Implements an elliptical multivariate
+/// [Laplace](https://en.wikipedia.org/wiki/Multivariate_Laplace_distribution)
+/// distribution with location `μ` and scale matrix `Σ`, whose density is
+/// proportional to `exp(-sqrt(transpose(x - μ) * inv(Σ) * (x - μ)))`
+///
+/// # Remarks
+///
+/// In one dimension this reduces to the `Laplace` distribution with scale
+/// `sqrt(Σ)`. In `k` dimensions the covariance matrix is `(k + 1) * Σ`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{MultivariateLaplace, Continuous};
+/// use nalgebra::{DMatrix, DVector};
+/// use statrs::statistics::VarianceN;
+///
+/// let mvl = MultivariateLaplace::new(vec![0., 0.], vec![1., 0., 0., 1.]).unwrap();
+/// assert_eq!(mvl.variance().unwrap(), DMatrix::from_vec(2, 2, vec![3., 0., 0., 3.]));
+/// assert!((mvl.pdf(&DVector::from_vec(vec![0., 0.])) - 0.5 / std::f64::consts::PI).abs() < 1e-15);
+/// ```
+pub type MultivariateLaplace = Elliptical<LaplaceGenerator>;
+
+/// The radial generator of the elliptical multivariate Laplace
+/// distribution, whose spherical part has a radius following a gamma
+/// distribution with shape `k` and rate `1` and a uniformly distributed
+/// direction
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LaplaceGenerator;
+
+impl RadialGenerator for LaplaceGenerator {
+    fn ln_density(&self, q: f64, dim: usize) -> f64 {
+        let d = dim as f64;
+        gamma::ln_gamma(0.5 * d) - LN_2 - 0.5 * d * fp::ln(PI) - gamma::ln_gamma(d) - q.sqrt()
+    }
+
+    fn sample_spherical<R: Rng + ?Sized>(&self, dim: usize, rng: &mut R) -> DVector<f64> {
+        let d = Normal::new(0., 1.).unwrap();
+        let z = DVector::<f64>::from_distribution(dim, &d, rng);
+        let r = super::gamma::sample_unchecked(rng, dim as f64, 1.0);
+        z.scale(r / z.norm())
+    }
+
+    fn variance_scale(&self, dim: usize) -> Option<f64> {
+        Some(dim as f64 + 1.0)
+    }
+}
+
+impl Elliptical<LaplaceGenerator> {
+    /// Constructs a new elliptical multivariate Laplace distribution with
+    /// location `location` and scale matrix `scale`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given scale matrix is not
+    /// symmetric or positive-definite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::MultivariateLaplace;
+    ///
+    /// let mut result = MultivariateLaplace::new(vec![0., 0.], vec![1., 0.5, 0.5, 1.]);
+    /// assert!(result.is_ok());
+    ///
+    /// result = MultivariateLaplace::new(vec![0., 0.], vec![1., 2., 2., 1.]);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(location: Vec<f64>, scale: Vec<f64>) -> Result<Self> {
+        Elliptical::with_generator(location, scale, LaplaceGenerator)
+    }
+}
+
+impl Parameterized for Elliptical<LaplaceGenerator> {
+    fn param_names() -> &'static [&'static str] {
+        &["k", "location", "scale"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        let mut params = vec![self.dim() as f64];
+        params.extend(self.location().iter());
+        params.extend(self.scale().iter());
+        params
+    }
+
+    fn from_params(params: &[f64]) -> Result<Self> {
+        let (k, rest) = dimension_param(params, |k| k + k * k)?;
+        MultivariateLaplace::new(rest[..k].to_vec(), rest[k..].to_vec())
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::distribution::{Continuous, Laplace, MultivariateLaplace};
+    use crate::statistics::*;
+    use nalgebra::{DMatrix, DVector};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn dvec(v: Vec<f64>) -> DVector<f64> {
+        DVector::from_vec(v)
+    }
+
+    #[test]
+    fn test_moments() {
+        let mvl = MultivariateLaplace::new(vec![1., 2., 3.], vec![2., 0.5, 0., 0.5, 1., 0., 0., 0., 1.]).unwrap();
+        assert_eq!(mvl.mean().unwrap(), dvec(vec![1., 2., 3.]));
+        assert_eq!(mvl.mode(), dvec(vec![1., 2., 3.]));
+        assert_eq!(mvl.variance().unwrap(), DMatrix::from_vec(3, 3, vec![8., 2., 0., 2., 4., 0., 0., 0., 4.]));
+    }
+
+    #[test]
+    fn test_ln_pdf_univariate() {
+        let l = Laplace::new(0.5, 1.5).unwrap();
+        let mvl = MultivariateLaplace::new(vec![0.5], vec![2.25]).unwrap();
+        for &x in &[-3., -0.5, 0., 0.5, 2., 10.] {
+            assert_almost_eq!(mvl.ln_pdf(&dvec(vec![x])), l.ln_pdf(x), 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_pdf() {
+        // reference values computed from the closed form density
+        let mvl = MultivariateLaplace::new(vec![0., 0.], vec![1., 0., 0., 1.]).unwrap();
+        assert_almost_eq!(mvl.pdf(&dvec(vec![3., 4.])), 0.5 / std::f64::consts::PI * (-5f64).exp(), 1e-15);
+        let mvl = MultivariateLaplace::new(vec![0., 0., 0.], vec![4., 0., 0., 0., 4., 0., 0., 0., 4.]).unwrap();
+        assert_almost_eq!(mvl.ln_pdf(&dvec(vec![0., 0., 2.])), -(64. * std::f64::consts::PI).ln() - 1., 1e-14);
+    }
+
+    #[test]
+    fn test_sample_moments() {
+        use rand::distributions::Distribution;
+        let mvl = MultivariateLaplace::new(vec![1., -1.], vec![1., 0.5, 0.5, 2.]).unwrap();
+        let mut rng = StdRng::seed_from_u64(11);
+        let n = 100_000;
+        let mut sum = dvec(vec![0., 0.]);
+        let mut sum_sq = DMatrix::zeros(2, 2);
+        for _ in 0..n {
+            let x = mvl.sample(&mut rng);
+            let dv = &x - dvec(vec![1., -1.]);
+            sum += &x;
+            sum_sq += &dv * dv.transpose();
+        }
+        let mean = sum / n as f64;
+        let cov = sum_sq / n as f64;
+        let expected = mvl.variance().unwrap();
+        assert_almost_eq!(mean[0], 1., 0.03);
+        assert_almost_eq!(mean[1], -1., 0.03);
+        for i in 0..4 {
+            assert_almost_eq!(cov[i], expected[i], 0.12);
+        }
+    }
+}
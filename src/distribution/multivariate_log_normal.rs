@@ -0,0 +1,347 @@
+use crate::distribution::{dimension_param, Continuous, MultivariateNormal, Parameterized};
+use crate::fp;
+use crate::statistics::{Max, MeanN, Median, Min, Mode, VarianceN};
+use crate::Result;
+use nalgebra::{DMatrix, DVector};
+use rand::Rng;
+use std::f64;
+
+/// Implements the [Multivariate
+/// LogNormal](https://en.wikipedia.org/wiki/Log-normal_distribution#Multivariate_log-normal)
+/// distribution, i.e. the distribution of `exp(X)` (taken element-wise) where
+/// `X` follows a multivariate normal distribution
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{MultivariateLogNormal, Continuous};
+/// use nalgebra::DVector;
+/// use statrs::statistics::MeanN;
+///
+/// let mvln = MultivariateLogNormal::new(vec![0., 0.], vec![1., 0., 0., 1.]).unwrap();
+/// assert_eq!(mvln.mean().unwrap(), DVector::from_vec(vec![0.5f64.exp(), 0.5f64.exp()]));
+/// let pdf = mvln.pdf(&DVector::from_vec(vec![1., 1.]));
+/// assert!((pdf - 0.15915494309189535).abs() < 1e-16);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultivariateLogNormal {
+    mvn: MultivariateNormal,
+    mu: DVector<f64>,
+    cov: DMatrix<f64>,
+}
+
+impl MultivariateLogNormal {
+    /// Constructs a new multivariate log-normal distribution whose logarithm
+    /// has a mean of `mu` and covariance matrix `cov`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given covariance matrix is not
+    /// symmetric or positive-definite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::MultivariateLogNormal;
+    ///
+    /// let mut result = MultivariateLogNormal::new(vec![0., 0.], vec![1., 0.5, 0.5, 1.]);
+    /// assert!(result.is_ok());
+    ///
+    /// result = MultivariateLogNormal::new(vec![0., 0.], vec![1., 2., 2., 1.]);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(mu: Vec<f64>, cov: Vec<f64>) -> Result<Self> {
+        let mvn = MultivariateNormal::new(mu, cov)?;
+        let mu = mvn.mean().unwrap();
+        let cov = mvn.variance().unwrap();
+        Ok(MultivariateLogNormal { mvn, mu, cov })
+    }
+
+    /// Returns the multivariate normal distribution of the logarithm of
+    /// this distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{MultivariateLogNormal, MultivariateNormal};
+    ///
+    /// let mvln = MultivariateLogNormal::new(vec![1., 2.], vec![1., 0., 0., 1.]).unwrap();
+    /// let mvn = MultivariateNormal::new(vec![1., 2.], vec![1., 0., 0., 1.]).unwrap();
+    /// assert_eq!(mvln.ln_space(), &mvn);
+    /// ```
+    pub fn ln_space(&self) -> &MultivariateNormal {
+        &self.mvn
+    }
+
+    /// Returns the entropy of the multivariate log-normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 / 2) * ln(det(2 * π * e * Σ)) + Σ μ_i
+    /// ```
+    ///
+    /// where `μ` is the mean and `Σ` the covariance matrix of the logarithm
+    /// of the distribution
+    pub fn entropy(&self) -> Option<f64> {
+        self.mvn.entropy().map(|h| h + self.mu.sum())
+    }
+}
+
+impl Parameterized for MultivariateLogNormal {
+    fn param_names() -> &'static [&'static str] {
+        &["k", "mu", "cov"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        let mut params = vec![self.mu.len() as f64];
+        params.extend(self.mu.iter());
+        params.extend(self.cov.iter());
+        params
+    }
+
+    fn from_params(params: &[f64]) -> Result<Self> {
+        let (k, rest) = dimension_param(params, |k| k + k * k)?;
+        MultivariateLogNormal::new(rest[..k].to_vec(), rest[k..].to_vec())
+    }
+}
+
+impl ::rand::distributions::Distribution<DVector<f64>> for MultivariateLogNormal {
+    /// Samples from the multivariate log-normal distribution by
+    /// exponentiating a sample from the underlying multivariate normal
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DVector<f64> {
+        self.mvn.sample(rng).map(fp::exp)
+    }
+}
+
+impl Min<DVector<f64>> for MultivariateLogNormal {
+    /// Returns the minimum value in the domain of the
+    /// multivariate log-normal distribution represented by a real vector
+    fn min(&self) -> DVector<f64> {
+        DVector::zeros(self.mu.len())
+    }
+}
+
+impl Max<DVector<f64>> for MultivariateLogNormal {
+    /// Returns the maximum value in the domain of the
+    /// multivariate log-normal distribution represented by a real vector
+    fn max(&self) -> DVector<f64> {
+        DVector::from_element(self.mu.len(), f64::INFINITY)
+    }
+}
+
+impl MeanN<DVector<f64>> for MultivariateLogNormal {
+    /// Returns the mean of the multivariate log-normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(μ_i + Σ_ii / 2)
+    /// ```
+    ///
+    /// for the `i`th element where `μ` is the mean and `Σ` the covariance
+    /// matrix of the logarithm of the distribution
+    fn mean(&self) -> Option<DVector<f64>> {
+        Some(DVector::from_fn(self.mu.len(), |i, _| {
+            fp::exp(self.mu[i] + self.cov[(i, i)] / 2.0)
+        }))
+    }
+}
+
+impl VarianceN<DMatrix<f64>> for MultivariateLogNormal {
+    /// Returns the covariance matrix of the multivariate log-normal
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(μ_i + μ_j + (Σ_ii + Σ_jj) / 2) * (e^Σ_ij - 1)
+    /// ```
+    ///
+    /// for the `(i, j)`th element where `μ` is the mean and `Σ` the
+    /// covariance matrix of the logarithm of the distribution
+    fn variance(&self) -> Option<DMatrix<f64>> {
+        let mean = self.mean().unwrap();
+        let dim = self.mu.len();
+        Some(DMatrix::from_fn(dim, dim, |i, j| {
+            mean[i] * mean[j] * fp::exp_m1(self.cov[(i, j)])
+        }))
+    }
+}
+
+impl Median<DVector<f64>> for MultivariateLogNormal {
+    /// Returns the (element-wise) median of the multivariate log-normal
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^μ_i
+    /// ```
+    ///
+    /// for the `i`th element where `μ` is the mean of the logarithm of the
+    /// distribution
+    fn median(&self) -> DVector<f64> {
+        self.mu.map(fp::exp)
+    }
+}
+
+impl Mode<DVector<f64>> for MultivariateLogNormal {
+    /// Returns the mode of the multivariate log-normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(μ_i - Σ_j Σ_ij)
+    /// ```
+    ///
+    /// for the `i`th element where `μ` is the mean and `Σ` the covariance
+    /// matrix of the logarithm of the distribution
+    fn mode(&self) -> DVector<f64> {
+        let row_sums = &self.cov * DVector::from_element(self.mu.len(), 1.0);
+        (&self.mu - row_sums).map(fp::exp)
+    }
+}
+
+impl<'a> Continuous<&'a DVector<f64>, f64> for MultivariateLogNormal {
+    /// Calculates the probability density function for the multivariate
+    /// log-normal distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// f_N(ln(x)) / Π x_i
+    /// ```
+    ///
+    /// where `f_N` is the density of the multivariate normal distribution of
+    /// the logarithm of the distribution. The density is zero unless every
+    /// `x_i > 0`.
+    fn pdf(&self, x: &'a DVector<f64>) -> f64 {
+        fp::exp(self.ln_pdf(x))
+    }
+
+    /// Calculates the log probability density function for the multivariate
+    /// log-normal distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(f_N(ln(x))) - Σ ln(x_i)
+    /// ```
+    ///
+    /// where `f_N` is the density of the multivariate normal distribution of
+    /// the logarithm of the distribution and `-Σ ln(x_i)` is the log of the
+    /// Jacobian of the transformation. Returns `-INF` unless every `x_i > 0`.
+    fn ln_pdf(&self, x: &'a DVector<f64>) -> f64 {
+        if x.iter().any(|&x| x <= 0.0) {
+            return f64::NEG_INFINITY;
+        }
+        let ln_x = x.map(fp::ln);
+        self.mvn.ln_pdf(&ln_x) - ln_x.sum()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::distribution::{Continuous, MultivariateLogNormal, MultivariateNormal};
+    use crate::statistics::*;
+    use nalgebra::DVector;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn try_create(mu: Vec<f64>, cov: Vec<f64>) -> MultivariateLogNormal {
+        let mvln = MultivariateLogNormal::new(mu, cov);
+        assert!(mvln.is_ok());
+        mvln.unwrap()
+    }
+
+    macro_rules! dvec {
+        ($($x:expr),*) => (DVector::from_vec(vec![$($x),*]));
+    }
+
+    #[test]
+    fn test_bad_create() {
+        assert!(MultivariateLogNormal::new(vec![0., 0.], vec![1., 1., 0., 1.]).is_err());
+        assert!(MultivariateLogNormal::new(vec![0., 0.], vec![1., 2., 2., 1.]).is_err());
+        assert!(MultivariateLogNormal::new(vec![0., f64::NAN], vec![1., 0., 0., 1.]).is_err());
+    }
+
+    #[test]
+    fn test_mean_variance() {
+        let mvln = try_create(vec![0.5, -0.2], vec![2.0, 0.3, 0.3, 0.5]);
+        let mean = mvln.mean().unwrap();
+        assert_almost_eq!(mean[0], (1.5f64).exp(), 1e-14);
+        assert_almost_eq!(mean[1], (0.05f64).exp(), 1e-15);
+        let cov = mvln.variance().unwrap();
+        assert_almost_eq!(cov[(0, 0)], (3.0f64).exp() * (2f64.exp() - 1.0), 1e-12);
+        assert_almost_eq!(cov[(1, 1)], (0.1f64).exp() * (0.5f64.exp() - 1.0), 1e-15);
+        assert_almost_eq!(cov[(0, 1)], (1.55f64).exp() * (0.3f64.exp() - 1.0), 1e-14);
+        assert_eq!(cov[(0, 1)], cov[(1, 0)]);
+    }
+
+    #[test]
+    fn test_median_mode() {
+        let mvln = try_create(vec![0.5, -0.2], vec![2.0, 0.3, 0.3, 0.5]);
+        assert_eq!(mvln.median(), dvec![0.5f64.exp(), (-0.2f64).exp()]);
+        let mode = mvln.mode();
+        assert_almost_eq!(mode[0], (0.5f64 - 2.3).exp(), 1e-15);
+        assert_almost_eq!(mode[1], (-0.2f64 - 0.8).exp(), 1e-15);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let mvln = try_create(vec![0., 0.], vec![1., 0., 0., 1.]);
+        assert_eq!(mvln.min(), dvec![0., 0.]);
+        assert_eq!(mvln.max(), dvec![f64::INFINITY, f64::INFINITY]);
+    }
+
+    #[test]
+    fn test_entropy() {
+        let mvln = try_create(vec![1., 2.], vec![1., 0., 0., 1.]);
+        assert_almost_eq!(mvln.entropy().unwrap(), 2.8378770664093453 + 3.0, 1e-14);
+    }
+
+    #[test]
+    fn test_pdf() {
+        let mvln = try_create(vec![0., 0.], vec![1., 0., 0., 1.]);
+        // exponentiating the log density through musl lands one ulp high under `deterministic`
+        #[cfg(not(feature = "deterministic"))]
+        assert_eq!(mvln.pdf(&dvec![1., 1.]), 0.15915494309189535);
+        #[cfg(feature = "deterministic")]
+        assert_relative_eq!(mvln.pdf(&dvec![1., 1.]), 0.15915494309189535, max_relative = 2.0 * f64::EPSILON);
+        assert_eq!(mvln.pdf(&dvec![0., 1.]), 0.0);
+        assert_eq!(mvln.pdf(&dvec![1., -1.]), 0.0);
+        // the density is that of the normal on the log scale times the jacobian
+        let mvln = try_create(vec![0.5, -0.2], vec![2.0, 0.3, 0.3, 0.5]);
+        let x = dvec![2f64.exp(), 2f64.exp()];
+        assert_almost_eq!(mvln.pdf(&x), 0.0013075203140666656 * (-4f64).exp(), 1e-17);
+    }
+
+    #[test]
+    fn test_ln_pdf() {
+        let mvln = try_create(vec![0.5, -0.2], vec![2.0, 0.3, 0.3, 0.5]);
+        let x = dvec![2f64.exp(), 2f64.exp()];
+        assert_almost_eq!(mvln.ln_pdf(&x), 0.0013075203140666656f64.ln() - 4.0, 1e-13);
+        assert_eq!(mvln.ln_pdf(&dvec![0., 1.]), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mvln = try_create(vec![0.1, -0.3], vec![0.2, 0.05, 0.05, 0.1]);
+        let mvn = MultivariateNormal::new(vec![0.1, -0.3], vec![0.2, 0.05, 0.05, 0.1]).unwrap();
+        let mut r1 = StdRng::seed_from_u64(42);
+        let mut r2 = StdRng::seed_from_u64(42);
+        for _ in 0..10 {
+            let x = mvln.sample(&mut r1);
+            let y = mvn.sample(&mut r2);
+            assert_eq!(x, y.map(crate::fp::exp));
+        }
+        let n = 20_000;
+        let mean = (0..n).fold(DVector::zeros(2), |acc: DVector<f64>, _| acc + mvln.sample(&mut r1)) / n as f64;
+        let expected = mvln.mean().unwrap();
+        assert!((mean[0] - expected[0]).abs() < 0.02);
+        assert!((mean[1] - expected[1]).abs() < 0.02);
+    }
+}
@@ -1,11 +1,9 @@
-use crate::distribution::Continuous;
-use crate::distribution::Normal;
-use crate::statistics::{Max, MeanN, Min, Mode, VarianceN};
-use crate::{Result, StatsError};
-use nalgebra::{
-    base::allocator::Allocator, base::dimension::DimName, Cholesky, DefaultAllocator, Dim, DimMin,
-    LU, U1,
+use crate::distribution::{
+    dimension_param, Elliptical, KullbackLeibler, Normal, Parameterized, RadialGenerator,
 };
+use crate::fp;
+use crate::statistics::VarianceN;
+use crate::Result;
 use nalgebra::{DMatrix, DVector};
 use rand::Rng;
 use std::f64;
@@ -26,17 +24,29 @@ use std::f64::consts::{E, PI};
 /// assert_eq!(mvn.variance().unwrap(), DMatrix::from_vec(2, 2, vec![1., 0., 0., 1.]));
 /// assert_eq!(mvn.pdf(&DVector::from_vec(vec![1.,  1.])), 0.05854983152431917);
 /// ```
-#[derive(Debug, Clone, PartialEq)]
-pub struct MultivariateNormal {
-    dim: usize,
-    cov_chol_decomp: DMatrix<f64>,
-    mu: DVector<f64>,
-    cov: DMatrix<f64>,
-    precision: DMatrix<f64>,
-    pdf_const: f64,
+pub type MultivariateNormal = Elliptical<GaussianGenerator>;
+
+/// The radial generator of the multivariate normal distribution, whose
+/// spherical part is a vector of independent standard normal variables
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GaussianGenerator;
+
+impl RadialGenerator for GaussianGenerator {
+    fn ln_density(&self, q: f64, dim: usize) -> f64 {
+        -0.5 * (dim as f64 * fp::ln(2. * PI) + q)
+    }
+
+    fn sample_spherical<R: Rng + ?Sized>(&self, dim: usize, rng: &mut R) -> DVector<f64> {
+        let d = Normal::new(0., 1.).unwrap();
+        DVector::<f64>::from_distribution(dim, &d, rng)
+    }
+
+    fn variance_scale(&self, _dim: usize) -> Option<f64> {
+        Some(1.0)
+    }
 }
 
-impl MultivariateNormal {
+impl Elliptical<GaussianGenerator> {
     ///  Constructs a new multivariate normal distribution with a mean of `mean`
     /// and covariance matrix `cov`
     ///
@@ -45,40 +55,9 @@ impl MultivariateNormal {
     /// Returns an error if the given covariance matrix is not
     /// symmetric or positive-definite
     pub fn new(mean: Vec<f64>, cov: Vec<f64>) -> Result<Self> {
-        let mean = DVector::from_vec(mean);
-        let cov = DMatrix::from_vec(mean.len(), mean.len(), cov);
-        let dim = mean.len();
-        // Check that the provided covariance matrix is symmetric
-        if cov.lower_triangle() != cov.upper_triangle().transpose()
-        // Check that mean and covariance do not contain NaN
-            || mean.iter().any(|f| f.is_nan())
-            || cov.iter().any(|f| f.is_nan())
-        // Check that the dimensions match
-            || mean.nrows() != cov.nrows() || cov.nrows() != cov.ncols()
-        {
-            return Err(StatsError::BadParams);
-        }
-        let cov_det = cov.determinant();
-        let pdf_const = ((2. * PI).powi(mean.nrows() as i32) * cov_det.abs())
-            .recip()
-            .sqrt();
-        // Store the Cholesky decomposition of the covariance matrix
-        // for sampling
-        match Cholesky::new(cov.clone()) {
-            None => Err(StatsError::BadParams),
-            Some(cholesky_decomp) => {
-                let precision = cholesky_decomp.inverse();
-                Ok(MultivariateNormal {
-                    dim,
-                    cov_chol_decomp: cholesky_decomp.unpack(),
-                    mu: mean,
-                    cov,
-                    precision,
-                    pdf_const,
-                })
-            }
-        }
+        Elliptical::with_generator(mean, cov, GaussianGenerator)
     }
+
     /// Returns the entropy of the multivariate normal distribution
     ///
     /// # Formula
@@ -89,116 +68,56 @@ impl MultivariateNormal {
     ///
     /// where `Σ` is the covariance matrix and `det` is the determinant
     pub fn entropy(&self) -> Option<f64> {
-        Some(
-            0.5 * self
-                .variance()
-                .unwrap()
-                .scale(2. * PI * E)
-                .determinant()
-                .ln(),
-        )
+        Some(0.5 * fp::ln(self.variance().unwrap().scale(2. * PI * E).determinant()))
     }
 }
 
-impl ::rand::distributions::Distribution<DVector<f64>> for MultivariateNormal {
-    /// Samples from the multivariate normal distribution
+impl KullbackLeibler for Elliptical<GaussianGenerator> {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// multivariate normal distribution
     ///
-    /// # Formula
-    /// L * Z + μ
+    /// # Panics
     ///
-    /// where `L` is the Cholesky decomposition of the covariance matrix,
-    /// `Z` is a vector of normally distributed random variables, and
-    /// `μ` is the mean vector
-
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DVector<f64> {
-        let d = Normal::new(0., 1.).unwrap();
-        let z = DVector::<f64>::from_distribution(self.dim, &d, rng);
-        (&self.cov_chol_decomp * z) + &self.mu
-    }
-}
-
-impl Min<DVector<f64>> for MultivariateNormal {
-    /// Returns the minimum value in the domain of the
-    /// multivariate normal distribution represented by a real vector
-    fn min(&self) -> DVector<f64> {
-        DVector::from_vec(vec![f64::NEG_INFINITY; self.dim])
-    }
-}
-
-impl Max<DVector<f64>> for MultivariateNormal {
-    /// Returns the maximum value in the domain of the
-    /// multivariate normal distribution represented by a real vector
-    fn max(&self) -> DVector<f64> {
-        DVector::from_vec(vec![f64::INFINITY; self.dim])
-    }
-}
-
-impl MeanN<DVector<f64>> for MultivariateNormal {
-    /// Returns the mean of the normal distribution
-    ///
-    /// # Remarks
-    ///
-    /// This is the same mean used to construct the distribution
-    fn mean(&self) -> Option<DVector<f64>> {
-        let mut vec = vec![];
-        for elt in self.mu.clone().into_iter() {
-            vec.push(*elt);
-        }
-        Some(DVector::from_vec(vec))
-    }
-}
-
-impl VarianceN<DMatrix<f64>> for MultivariateNormal {
-    /// Returns the covariance matrix of the multivariate normal distribution
-    fn variance(&self) -> Option<DMatrix<f64>> {
-        Some(self.cov.clone())
-    }
-}
-
-impl Mode<DVector<f64>> for MultivariateNormal {
-    /// Returns the mode of the multivariate normal distribution
+    /// If the distributions have different dimensions
     ///
     /// # Formula
     ///
     /// ```ignore
-    /// μ
+    /// (tr(Σ_q^-1 Σ_p) + (μ_q - μ_p)^T Σ_q^-1 (μ_q - μ_p) - k + ln(det(Σ_q) / det(Σ_p))) / 2
     /// ```
     ///
-    /// where `μ` is the mean
-    fn mode(&self) -> DVector<f64> {
-        self.mu.clone()
+    /// where `μ` is the mean, `Σ` the covariance matrix and `k` the
+    /// dimension
+    fn kl_divergence(&self, other: &Self) -> f64 {
+        if self.dim() != other.dim() {
+            panic!("multivariate normal distributions must have the same dimension");
+        }
+        let chol_p = self.scale().clone().cholesky().unwrap();
+        let chol_q = other.scale().clone().cholesky().unwrap();
+        let ln_det = |l: &DMatrix<f64>| 2.0 * l.diagonal().iter().map(|x| fp::ln(*x)).sum::<f64>();
+        let trace = chol_q.solve(self.scale()).trace();
+        let diff = other.location() - self.location();
+        let mahalanobis = diff.dot(&chol_q.solve(&diff));
+        0.5 * (trace + mahalanobis - self.dim() as f64 + ln_det(&chol_q.unpack())
+            - ln_det(&chol_p.unpack()))
     }
 }
 
-impl<'a> Continuous<&'a DVector<f64>, f64> for MultivariateNormal {
-    /// Calculates the probability density function for the multivariate
-    /// normal distribution at `x`
-    ///
-    /// # Formula
-    ///
-    /// ```ignore
-    /// (2 * π) ^ (-k / 2) * det(Σ) ^ (1 / 2) * e ^ ( -(1 / 2) * transpose(x - μ) * inv(Σ) * (x - μ))
-    /// ```
-    ///
-    /// where `μ` is the mean, `inv(Σ)` is the precision matrix, `det(Σ)` is the determinant
-    /// of the covariance matrix, and `k` is the dimension of the distribution
-    fn pdf(&self, x: &'a DVector<f64>) -> f64 {
-        let dv = x - &self.mu;
-        let exp_term = -0.5
-            * *(&dv.transpose() * &self.precision * &dv)
-                .get((0, 0))
-                .unwrap();
-        self.pdf_const * exp_term.exp()
+impl Parameterized for Elliptical<GaussianGenerator> {
+    fn param_names() -> &'static [&'static str] {
+        &["k", "mean", "cov"]
     }
-    /// Calculates the log probability density function for the multivariate
-    /// normal distribution at `x`. Equivalent to pdf(x).ln().
-    fn ln_pdf(&self, x: &'a DVector<f64>) -> f64 {
-        let dv = x - &self.mu;
-        let exp_term = -0.5
-            * *(&dv.transpose() * &self.precision * &dv)
-                .get((0, 0))
-                .unwrap();
-        self.pdf_const.ln() + exp_term
+
+    fn params(&self) -> Vec<f64> {
+        let mut params = vec![self.dim() as f64];
+        params.extend(self.location().iter());
+        params.extend(self.scale().iter());
+        params
+    }
+
+    fn from_params(params: &[f64]) -> Result<Self> {
+        let (k, rest) = dimension_param(params, |k| k + k * k)?;
+        MultivariateNormal::new(rest[..k].to_vec(), rest[k..].to_vec())
     }
 }
 
@@ -353,4 +272,19 @@ mod tests  {
         test_case(vec![0., 0.], vec![f64::INFINITY, 0., 0., f64::INFINITY], f64::NEG_INFINITY, ln_pdf(dvec![10., 10.]));
         test_case(vec![0., 0.], vec![f64::INFINITY, 0., 0., f64::INFINITY], f64::NEG_INFINITY, ln_pdf(dvec![100., 100.]));
     }
+
+    #[test]
+    fn test_kl_divergence() {
+        use crate::distribution::{KullbackLeibler, Normal};
+        // independent components add up
+        let p = try_create(vec![0., 1.], vec![1., 0., 0., 4.]);
+        let q = try_create(vec![1., -1.], vec![2., 0., 0., 0.5]);
+        let expected = Normal::new(0., 1.).unwrap().kl_divergence(&Normal::new(1., 2f64.sqrt()).unwrap())
+            + Normal::new(1., 2.).unwrap().kl_divergence(&Normal::new(-1., 0.5f64.sqrt()).unwrap());
+        assert_almost_eq!(p.kl_divergence(&q), expected, 1e-14);
+        let p = try_create(vec![0., 0.], vec![1., 0.5, 0.5, 2.]);
+        let q = try_create(vec![1., -1.], vec![2., 0.3, 0.3, 1.]);
+        assert_almost_eq!(p.kl_divergence(&q), 1.2165185961715055, 1e-14);
+        assert_almost_eq!(p.kl_divergence(&p), 0.0, 1e-15);
+    }
 }
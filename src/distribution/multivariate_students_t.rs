@@ -0,0 +1,235 @@
+use crate::distribution::{dimension_param, Elliptical, Normal, Parameterized, RadialGenerator};
+use crate::fp;
+use crate::function::gamma;
+use crate::{Result, StatsError};
+use nalgebra::DVector;
+use rand::Rng;
+use std::f64;
+use std::f64::consts::PI;
+
+/// Implements the [Multivariate Student's
+/// T](https://en.wikipedia.org/wiki/Multivariate_t-distribution)
+/// distribution with location `μ`, scale matrix `Σ` and `ν` degrees of
+/// freedom
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{MultivariateStudent, Continuous};
+/// use nalgebra::{DMatrix, DVector};
+/// use statrs::statistics::{MeanN, VarianceN};
+///
+/// let mvt = MultivariateStudent::new(vec![0., 0.], vec![1., 0., 0., 1.], 4.).unwrap();
+/// assert_eq!(mvt.mean().unwrap(), DVector::from_vec(vec![0., 0.]));
+/// assert_eq!(mvt.variance().unwrap(), DMatrix::from_vec(2, 2, vec![2., 0., 0., 2.]));
+/// assert!((mvt.pdf(&DVector::from_vec(vec![0., 0.])) - 0.5 / std::f64::consts::PI).abs() < 1e-15);
+/// ```
+pub type MultivariateStudent = Elliptical<StudentsTGenerator>;
+
+/// The radial generator of the multivariate student's T distribution, whose
+/// spherical part is a standard normal vector divided by `sqrt(W / ν)` for
+/// an independent chi-squared variable `W` with `ν` degrees of freedom
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StudentsTGenerator {
+    freedom: f64,
+}
+
+impl StudentsTGenerator {
+    /// Constructs a new radial generator with `freedom` degrees of freedom
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `freedom` is `NaN` or less than or equal to `0.0`
+    pub fn new(freedom: f64) -> Result<StudentsTGenerator> {
+        if freedom.is_nan() || freedom <= 0.0 {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(StudentsTGenerator { freedom })
+        }
+    }
+
+    /// Returns the degrees of freedom of the generator
+    pub fn freedom(&self) -> f64 {
+        self.freedom
+    }
+}
+
+impl RadialGenerator for StudentsTGenerator {
+    fn ln_density(&self, q: f64, dim: usize) -> f64 {
+        let d = dim as f64;
+        let v = self.freedom;
+        if v.is_infinite() {
+            return -0.5 * (d * fp::ln(2. * PI) + q);
+        }
+        gamma::ln_gamma(0.5 * (v + d))
+            - gamma::ln_gamma(0.5 * v)
+            - 0.5 * d * fp::ln(v * PI)
+            - 0.5 * (v + d) * fp::ln_1p(q / v)
+    }
+
+    fn sample_spherical<R: Rng + ?Sized>(&self, dim: usize, rng: &mut R) -> DVector<f64> {
+        let d = Normal::new(0., 1.).unwrap();
+        let z = DVector::<f64>::from_distribution(dim, &d, rng);
+        if self.freedom.is_infinite() {
+            return z;
+        }
+        let w = super::gamma::sample_unchecked(rng, 0.5 * self.freedom, 0.5);
+        z / (w / self.freedom).sqrt()
+    }
+
+    fn variance_scale(&self, _dim: usize) -> Option<f64> {
+        if self.freedom.is_infinite() {
+            Some(1.0)
+        } else if self.freedom > 2.0 {
+            Some(self.freedom / (self.freedom - 2.0))
+        } else {
+            None
+        }
+    }
+
+    fn has_mean(&self) -> bool {
+        self.freedom > 1.0
+    }
+}
+
+impl Elliptical<StudentsTGenerator> {
+    /// Constructs a new multivariate student's T distribution with location
+    /// `location`, scale matrix `scale` and `freedom` degrees of freedom
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given scale matrix is not
+    /// symmetric or positive-definite, or if `freedom` is `NaN` or less than
+    /// or equal to `0.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::MultivariateStudent;
+    ///
+    /// let mut result = MultivariateStudent::new(vec![0., 0.], vec![1., 0., 0., 1.], 3.);
+    /// assert!(result.is_ok());
+    ///
+    /// result = MultivariateStudent::new(vec![0., 0.], vec![1., 0., 0., 1.], 0.);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(location: Vec<f64>, scale: Vec<f64>, freedom: f64) -> Result<Self> {
+        Elliptical::with_generator(location, scale, StudentsTGenerator::new(freedom)?)
+    }
+
+    /// Returns the degrees of freedom of the multivariate student's T
+    /// distribution
+    pub fn freedom(&self) -> f64 {
+        self.generator().freedom()
+    }
+}
+
+impl Parameterized for Elliptical<StudentsTGenerator> {
+    fn param_names() -> &'static [&'static str] {
+        &["k", "location", "scale", "freedom"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        let mut params = vec![self.dim() as f64];
+        params.extend(self.location().iter());
+        params.extend(self.scale().iter());
+        params.push(self.freedom());
+        params
+    }
+
+    fn from_params(params: &[f64]) -> Result<Self> {
+        let (k, rest) = dimension_param(params, |k| k + k * k + 1)?;
+        MultivariateStudent::new(
+            rest[..k].to_vec(),
+            rest[k..k + k * k].to_vec(),
+            rest[k + k * k],
+        )
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::distribution::{Continuous, MultivariateNormal, MultivariateStudent, StudentsT};
+    use crate::statistics::*;
+    use nalgebra::{DMatrix, DVector};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn dvec(v: Vec<f64>) -> DVector<f64> {
+        DVector::from_vec(v)
+    }
+
+    #[test]
+    fn test_bad_create() {
+        assert!(MultivariateStudent::new(vec![0., 0.], vec![1., 0., 0., 1.], f64::NAN).is_err());
+        assert!(MultivariateStudent::new(vec![0., 0.], vec![1., 0., 0., 1.], -1.).is_err());
+        assert!(MultivariateStudent::new(vec![0., 0.], vec![1., 2., 2., 1.], 3.).is_err());
+        assert!(MultivariateStudent::new(vec![0., 0.], vec![1., 1., 0., 1.], 3.).is_err());
+    }
+
+    #[test]
+    fn test_moments() {
+        let mvt = MultivariateStudent::new(vec![1., 2.], vec![2., 0.5, 0.5, 1.], 1.).unwrap();
+        assert!(mvt.mean().is_none());
+        assert!(mvt.variance().is_none());
+        let mvt = MultivariateStudent::new(vec![1., 2.], vec![2., 0.5, 0.5, 1.], 2.).unwrap();
+        assert_eq!(mvt.mean().unwrap(), dvec(vec![1., 2.]));
+        assert!(mvt.variance().is_none());
+        let mvt = MultivariateStudent::new(vec![1., 2.], vec![2., 0.5, 0.5, 1.], 6.).unwrap();
+        assert_eq!(mvt.variance().unwrap(), DMatrix::from_vec(2, 2, vec![3., 0.75, 0.75, 1.5]));
+        assert_eq!(mvt.mode(), dvec(vec![1., 2.]));
+    }
+
+    #[test]
+    fn test_ln_pdf_univariate() {
+        let t = StudentsT::new(0.5, 1.5, 3.).unwrap();
+        let mvt = MultivariateStudent::new(vec![0.5], vec![2.25], 3.).unwrap();
+        for &x in &[-3., -0.5, 0., 0.5, 2., 10.] {
+            assert_almost_eq!(mvt.ln_pdf(&dvec(vec![x])), t.ln_pdf(x), 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_pdf() {
+        // reference values computed from the closed form density
+        let mvt = MultivariateStudent::new(vec![0., 0.], vec![1., 0., 0., 1.], 3.).unwrap();
+        assert_almost_eq!(mvt.pdf(&dvec(vec![0., 0.])), 0.15915494309189535, 1e-15);
+        assert_almost_eq!(mvt.pdf(&dvec(vec![1., 1.])), 0.044381119972428, 1e-15);
+        let mvt = MultivariateStudent::new(vec![0.5, -0.2], vec![2.0, 0.3, 0.3, 0.5], 5.).unwrap();
+        assert_almost_eq!(mvt.ln_pdf(&dvec(vec![2., 2.])), -5.564631936040964, 1e-12);
+    }
+
+    #[test]
+    fn test_infinite_freedom() {
+        let mvt = MultivariateStudent::new(vec![0.5, -0.2], vec![2.0, 0.3, 0.3, 0.5], f64::INFINITY).unwrap();
+        let mvn = MultivariateNormal::new(vec![0.5, -0.2], vec![2.0, 0.3, 0.3, 0.5]).unwrap();
+        let x = dvec(vec![2., 2.]);
+        assert_eq!(mvt.ln_pdf(&x), mvn.ln_pdf(&x));
+        assert_eq!(mvt.variance(), mvn.variance());
+    }
+
+    #[test]
+    fn test_sample_moments() {
+        use rand::distributions::Distribution;
+        let mvt = MultivariateStudent::new(vec![1., -1.], vec![1., 0.5, 0.5, 2.], 8.).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = 100_000;
+        let mut sum = dvec(vec![0., 0.]);
+        let mut sum_sq = DMatrix::zeros(2, 2);
+        for _ in 0..n {
+            let x = mvt.sample(&mut rng);
+            let dv = &x - dvec(vec![1., -1.]);
+            sum += &x;
+            sum_sq += &dv * dv.transpose();
+        }
+        let mean = sum / n as f64;
+        let cov = sum_sq / n as f64;
+        let expected = mvt.variance().unwrap();
+        assert_almost_eq!(mean[0], 1., 0.02);
+        assert_almost_eq!(mean[1], -1., 0.02);
+        for i in 0..4 {
+            assert_almost_eq!(cov[i], expected[i], 0.06);
+        }
+    }
+}
@@ -1,7 +1,11 @@
-use crate::distribution::{self, poisson, Discrete, DiscreteCDF};
+use crate::distribution::{
+    self, fit, poisson, CharacteristicFunction, Discrete, DiscreteCDF, Fit, Parameterized,
+};
+use crate::fp;
 use crate::function::{beta, gamma};
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 use std::f64;
 
@@ -55,6 +59,52 @@ impl NegativeBinomial {
         }
     }
 
+    /// Constructs a new negative binomial distribution from parameters already
+    /// known to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(r: f64, p: f64) -> NegativeBinomial {
+        debug_assert!(
+            NegativeBinomial::new(r, p).is_ok(),
+            "invalid negative binomial parameters"
+        );
+        NegativeBinomial { r, p }
+    }
+
+    /// Constructs a new negative binomial distribution with mean `mu` and
+    /// dispersion `alpha`, the parameterization of negative binomial
+    /// regression in which the variance is `μ + αμ^2`. This corresponds to
+    /// `r = 1 / α` and `p = 1 / (1 + αμ)`, where `r` need not be an integer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mu` or `alpha` is not positive and finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NegativeBinomial;
+    /// use statrs::statistics::DiscreteDistribution;
+    ///
+    /// let n = NegativeBinomial::from_mean_dispersion(4.0, 0.5).unwrap();
+    /// assert_eq!(n.r(), 2.0);
+    /// assert_eq!(n.p(), 1.0 / 3.0);
+    /// assert!((n.variance().unwrap() - 12.0).abs() < 1e-12);
+    /// ```
+    pub fn from_mean_dispersion(mu: f64, alpha: f64) -> Result<NegativeBinomial> {
+        if !mu.is_finite() || mu <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("mu"));
+        }
+        if !alpha.is_finite() || alpha <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("alpha"));
+        }
+        NegativeBinomial::new(1.0 / alpha, 1.0 / (1.0 + alpha * mu))
+    }
+
     /// Returns the probability of success `p` of
     /// the negative binomial distribution.
     ///
@@ -84,6 +134,50 @@ impl NegativeBinomial {
     pub fn r(&self) -> f64 {
         self.r
     }
+
+    /// Returns the dispersion `α = 1 / r` of the negative binomial
+    /// distribution, as in `from_mean_dispersion`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NegativeBinomial;
+    ///
+    /// let r = NegativeBinomial::new(4.0, 0.5).unwrap();
+    /// assert_eq!(r.dispersion(), 0.25);
+    /// ```
+    pub fn dispersion(&self) -> f64 {
+        1.0 / self.r
+    }
+
+    /// Fits a negative binomial distribution to the counts `data` by the
+    /// method of moments, matching the mean `μ` and the variance `μ + αμ^2`,
+    /// which gives the dispersion `α = (var - mean) / mean^2` with the
+    /// variance taken with the divisor `n`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or is not overdispersed, i.e. its
+    /// variance does not exceed its mean
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NegativeBinomial;
+    ///
+    /// let n = NegativeBinomial::fit_moments(&[0, 0, 2, 6]).unwrap();
+    /// assert_eq!(n.dispersion(), 1.0);
+    /// assert_eq!(n.p(), 1.0 / 3.0);
+    /// ```
+    pub fn fit_moments(data: &[u64]) -> Result<NegativeBinomial> {
+        let counts: Vec<f64> = data.iter().map(|&k| k as f64).collect();
+        fit::check_sample(&counts, |_| true)?;
+        let (mean, variance) = fit::mean_variance(&counts);
+        if variance <= mean {
+            return Err(StatsError::BadParams);
+        }
+        NegativeBinomial::from_mean_dispersion(mean, (variance - mean) / (mean * mean))
+    }
 }
 
 impl ::rand::distributions::Distribution<u64> for NegativeBinomial {
@@ -178,6 +272,31 @@ impl DiscreteDistribution<f64> for NegativeBinomial {
     }
 }
 
+impl Median<f64> for NegativeBinomial {
+    /// Returns the median of the negative binomial distribution
+    ///
+    /// # Remarks
+    ///
+    /// Returns `0` if `r = 0` and `INF` if `p = 0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(1 / 2)
+    /// ```
+    ///
+    /// where `F` is the cdf
+    fn median(&self) -> f64 {
+        if self.r == 0.0 {
+            0.0
+        } else if self.p == 0.0 {
+            f64::INFINITY
+        } else {
+            self.inverse_cdf(0.5) as f64
+        }
+    }
+}
+
 impl Mode<Option<f64>> for NegativeBinomial {
     /// Returns the mode for the negative binomial distribution
     ///
@@ -208,8 +327,11 @@ impl Discrete<u64, f64> for NegativeBinomial {
     /// ```ignore
     /// (x + r - 1 choose k) * (1 - p)^x * p^r
     /// ```
+    ///
+    /// where the binomial coefficient is `Γ(x + r) / (Γ(r) x!)`, extending
+    /// the distribution to non-integer `r`
     fn pmf(&self, x: u64) -> f64 {
-        self.ln_pmf(x).exp()
+        fp::exp(self.ln_pmf(x))
     }
 
     /// Calculates the log probability mass function for the negative binomial
@@ -223,8 +345,72 @@ impl Discrete<u64, f64> for NegativeBinomial {
     fn ln_pmf(&self, x: u64) -> f64 {
         let k = x as f64;
         gamma::ln_gamma(self.r + k) - gamma::ln_gamma(self.r) - gamma::ln_gamma(k + 1.0)
-            + (self.r * self.p.ln())
-            + (k * (1.0 - self.p).ln())
+            + (self.r * fp::ln(self.p))
+            + (k * fp::ln(1.0 - self.p))
+    }
+}
+
+impl CharacteristicFunction<f64, f64> for NegativeBinomial {
+    /// Calculates the characteristic function for the negative binomial
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (p / (1 - (1 - p)e^(it)))^r
+    /// ```
+    ///
+    /// where `r` is the number of successes and `p` the success probability
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let denom = Complex::new(self.p, 0.0) - distribution::poisson::exp_m1_i(t) * (1.0 - self.p);
+        (self.p / denom).powf(self.r)
+    }
+}
+
+impl Parameterized for NegativeBinomial {
+    fn param_names() -> &'static [&'static str] {
+        &["r", "p"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.r, self.p]
+    }
+
+    fn from_params(params: &[f64]) -> Result<NegativeBinomial> {
+        match *params {
+            [r, p] => NegativeBinomial::new(r, p),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for NegativeBinomial {
+    type Sample = u64;
+
+    /// Fits the mean as the sample mean `μ` and the dispersion `α = 1 / r`
+    /// solving the profile likelihood equation
+    /// `Σ ψ(x + r) - n ψ(r) - n ln(1 + μ / r) = 0` by Newton's method in `α`
+    /// from the method of moments estimate. The estimate exists only for
+    /// overdispersed samples, whose variance with the divisor `n` exceeds
+    /// their mean; `dispersion_test` tests for overdispersion.
+    fn fit_mle(data: &[u64]) -> Result<NegativeBinomial> {
+        let start = NegativeBinomial::fit_moments(data)?;
+        let n = data.len() as f64;
+        let mean = fit::count_mean(data)?;
+        let dispersion = fit::newton_positive(
+            |alpha| {
+                let r = 1.0 / alpha;
+                let (score, slope) = data.iter().fold((0.0, 0.0), |(s, ds), &x| {
+                    let x = x as f64;
+                    (s + gamma::digamma(x + r), ds + gamma::trigamma(x + r))
+                });
+                let score = score - n * gamma::digamma(r) - n * fp::ln_1p(mean / r);
+                let slope = slope - n * gamma::trigamma(r) + n * (1.0 / r - 1.0 / (r + mean));
+                (score, -slope * r * r)
+            },
+            start.dispersion(),
+        )?;
+        NegativeBinomial::from_mean_dispersion(mean, dispersion)
     }
 }
 
@@ -233,8 +419,9 @@ impl Discrete<u64, f64> for NegativeBinomial {
 mod tests {
     use std::fmt::Debug;
     use crate::statistics::*;
-    use crate::distribution::{DiscreteCDF, Discrete, NegativeBinomial};
+    use crate::distribution::{CharacteristicFunction, DiscreteCDF, Discrete, NegativeBinomial};
     use crate::consts::ACC;
+    use crate::StatsError;
 
     fn try_create(r: f64, p: f64) -> NegativeBinomial {
         let r = NegativeBinomial::new(r, p);
@@ -303,6 +490,25 @@ mod tests {
         bad_create_case(2.0, 2.0);
     }
 
+    #[test]
+    fn test_from_mean_dispersion() {
+        for &(mu, alpha) in [(2.5, 0.4), (0.1, 3.0), (100.0, 0.01)].iter() {
+            let n = NegativeBinomial::from_mean_dispersion(mu, alpha).unwrap();
+            assert_almost_eq!(n.mean().unwrap(), mu, 1e-12);
+            assert_almost_eq!(n.variance().unwrap(), mu + alpha * mu * mu, 1e-9);
+            assert_almost_eq!(n.dispersion(), alpha, 1e-15);
+        }
+        // r = 2.5 is not an integer
+        let n = NegativeBinomial::from_mean_dispersion(2.5, 0.4).unwrap();
+        assert_almost_eq!(n.pmf(0), 0.1767766952966369, 1e-14);
+        assert_almost_eq!(n.pmf(3), 0.14501213286052236, 1e-14);
+        assert_almost_eq!(n.pmf(10), 0.004897219476953436, 1e-14);
+        assert!(matches!(NegativeBinomial::from_mean_dispersion(0.0, 1.0), Err(StatsError::ArgMustBePositive("mu"))));
+        assert!(NegativeBinomial::from_mean_dispersion(f64::INFINITY, 1.0).is_err());
+        assert!(matches!(NegativeBinomial::from_mean_dispersion(1.0, 0.0), Err(StatsError::ArgMustBePositive("alpha"))));
+        assert!(NegativeBinomial::from_mean_dispersion(1.0, f64::NAN).is_err());
+    }
+
     #[test]
     fn test_mean() {
         let mean = |x: NegativeBinomial| x.mean().unwrap();
@@ -336,6 +542,17 @@ mod tests {
         test_case(10.0, 0.01, 891.0, mode);
     }
 
+    #[test]
+    fn test_median() {
+        let median = |x: NegativeBinomial| x.median();
+        test_case(0.0, 0.5, 0.0, median);
+        test_case(1.0, 0.0, f64::INFINITY, median);
+        test_case(1.0, 1.0, 0.0, median);
+        test_case(4.0, 0.4, 5.0, median);
+        test_case(0.5, 0.1, 2.0, median);
+        test_case(20.0, 0.3, 46.0, median);
+    }
+
     #[test]
     fn test_min_max() {
         let min = |x: NegativeBinomial| x.min();
@@ -433,4 +650,14 @@ mod tests {
     //     test::check_discrete_distribution(&try_create(5.0, 0.3), 35);
     //     test::check_discrete_distribution(&try_create(10.0, 0.7), 21);
     // }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(4.0, 0.5).cf(0.5);
+        assert_almost_eq!(phi.re, -0.019785068623618035, 1e-14);
+        assert_almost_eq!(phi.im, 0.4501953514679943, 1e-14);
+        let phi = try_create(2.5, 0.3).cf(0.2);
+        assert_almost_eq!(phi.re, 0.3596624585619915, 1e-14);
+        assert_almost_eq!(phi.im, 0.616191114018191, 1e-14);
+    }
 }
@@ -1,7 +1,13 @@
-use crate::distribution::{ziggurat, Continuous, ContinuousCDF};
+use crate::critical_values;
+use crate::distribution::{
+    fit, ziggurat, CharacteristicFunction, Continuous, ContinuousCDF, Fit, FitResult,
+    KullbackLeibler, ParameterEstimate, Parameterized,
+};
+use crate::fp;
 use crate::function::erf;
 use crate::statistics::*;
 use crate::{consts, Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 use std::f64;
 
@@ -51,6 +57,99 @@ impl Normal {
             Ok(Normal { mean, std_dev })
         }
     }
+
+    /// Constructs a new normal distribution from parameters already known to be
+    /// valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(mean: f64, std_dev: f64) -> Normal {
+        debug_assert!(
+            Normal::new(mean, std_dev).is_ok(),
+            "invalid normal parameters"
+        );
+        Normal { mean, std_dev }
+    }
+
+    /// Fits a normal distribution to `data`, reporting the mean and the
+    /// variance with their standard errors and exact 95% confidence
+    /// intervals, as `fit_with_confidence` at level `0.95`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two observations, a
+    /// non-finite observation or zero variance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    ///
+    /// let fit = Normal::fit(&[4.1, 5.3, 4.8, 6.0, 5.1, 4.4]).unwrap();
+    /// let mean = fit.param("mean").unwrap();
+    /// let (lower, upper) = mean.confidence_interval();
+    /// assert!(lower < 4.95 && 4.95 < upper);
+    /// assert!(fit.param("variance").unwrap().confidence_interval().0 > 0.0);
+    /// ```
+    pub fn fit(data: &[f64]) -> Result<FitResult<Normal>> {
+        Normal::fit_with_confidence(data, 0.95)
+    }
+
+    /// Fits a normal distribution to `data`, reporting the mean and the
+    /// variance with their standard errors and exact confidence intervals
+    /// at level `confidence`
+    ///
+    /// # Remarks
+    ///
+    /// Unlike `fit_mle`, the variance is estimated with the divisor `n - 1`,
+    /// giving the unbiased sample variance `s²` and a fitted distribution
+    /// with standard deviation `s`. The interval of the mean is based on the
+    /// Student's t distribution of `(x̄ - μ) √n / s` and that of the variance
+    /// on the chi-squared distribution of `(n - 1) s² / σ²`, both with
+    /// `n - 1` degrees of freedom, so they are exact for normal samples of
+    /// any size. The standard errors are `s / √n` and `s² √(2 / (n - 1))`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `confidence` is not in `(0, 1)`, or if `data`
+    /// has fewer than two observations, a non-finite observation or zero
+    /// variance
+    pub fn fit_with_confidence(data: &[f64], confidence: f64) -> Result<FitResult<Normal>> {
+        if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+        }
+        fit::check_sample(data, |_| true)?;
+        if data.len() < 2 {
+            return Err(StatsError::BadParams);
+        }
+        let n = data.len() as f64;
+        let (mean, variance) = fit::mean_variance(data);
+        let variance = variance * n / (n - 1.0);
+        let dist = Normal::new(mean, variance.sqrt())?;
+        let alpha = (1.0 - confidence) / 2.0;
+        let mean_se = (variance / n).sqrt();
+        let t = critical_values::students_t(n - 1.0, alpha)?;
+        let mean_estimate = ParameterEstimate::new(
+            "mean",
+            mean,
+            mean_se,
+            (mean - t * mean_se, mean + t * mean_se),
+        )?;
+        let scatter = (n - 1.0) * variance;
+        let variance_estimate = ParameterEstimate::new(
+            "variance",
+            variance,
+            variance * (2.0 / (n - 1.0)).sqrt(),
+            (
+                scatter / critical_values::chi_squared(n - 1.0, alpha)?,
+                scatter / critical_values::chi_squared(n - 1.0, 1.0 - alpha)?,
+            ),
+        )?;
+        FitResult::new(dist, confidence, vec![mean_estimate, variance_estimate])
+    }
 }
 
 impl ::rand::distributions::Distribution<f64> for Normal {
@@ -74,6 +173,43 @@ impl ContinuousCDF<f64, f64> for Normal {
     fn cdf(&self, x: f64) -> f64 {
         cdf_unchecked(x, self.mean, self.std_dev)
     }
+
+    /// Calculates the survival function for the
+    /// normal distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 / 2) * erfc((x - μ) / (σ * sqrt(2)))
+    /// ```
+    ///
+    /// where `μ` is the mean, `σ` is the standard deviation, and
+    /// `erfc` is the complementary error function
+    fn sf(&self, x: f64) -> f64 {
+        0.5 * erf::erfc((x - self.mean) / (self.std_dev * f64::consts::SQRT_2))
+    }
+
+    /// Calculates the log cumulative distribution function for the
+    /// normal distribution at `x`
+    ///
+    /// # Remarks
+    ///
+    /// In the lower tail, where the cdf underflows, the logarithm is
+    /// evaluated from the asymptotic continued fraction of the Mills ratio
+    fn ln_cdf(&self, x: f64) -> f64 {
+        ln_cdf_unchecked(x, self.mean, self.std_dev)
+    }
+
+    /// Calculates the log survival function for the
+    /// normal distribution at `x`
+    ///
+    /// # Remarks
+    ///
+    /// Evaluated as the log cdf of the reflected distribution, so it stays
+    /// finite in the upper tail
+    fn ln_sf(&self, x: f64) -> f64 {
+        ln_cdf_unchecked(-x, -self.mean, self.std_dev)
+    }
     /// Calculates the inverse cumulative distribution function for the
     /// normal distribution at `x`
     ///
@@ -96,6 +232,29 @@ impl ContinuousCDF<f64, f64> for Normal {
             self.mean - (self.std_dev * f64::consts::SQRT_2 * erf::erfc_inv(2.0 * x))
         }
     }
+
+    /// Calculates the probability that a normally distributed random
+    /// variable falls between `a` and `b`
+    ///
+    /// # Remarks
+    ///
+    /// Intervals on one side of the mean are evaluated as a difference of
+    /// the complementary error function in the tail they lie in, which
+    /// keeps full relative precision far from the mean
+    fn prob_between(&self, a: f64, b: f64) -> f64 {
+        if b <= a {
+            return 0.0;
+        }
+        let za = (a - self.mean) / (self.std_dev * f64::consts::SQRT_2);
+        let zb = (b - self.mean) / (self.std_dev * f64::consts::SQRT_2);
+        if za >= 0.0 {
+            0.5 * (erf::erfc(za) - erf::erfc(zb))
+        } else if zb <= 0.0 {
+            0.5 * (erf::erfc(-zb) - erf::erfc(-za))
+        } else {
+            1.0 - 0.5 * (erf::erfc(zb) + erf::erfc(-za))
+        }
+    }
 }
 
 impl Min<f64> for Normal {
@@ -157,7 +316,7 @@ impl Distribution<f64> for Normal {
     ///
     /// where `σ` is the standard deviation
     fn entropy(&self) -> Option<f64> {
-        Some(self.std_dev.ln() + consts::LN_SQRT_2PIE)
+        Some(fp::ln(self.std_dev) + consts::LN_SQRT_2PIE)
     }
     /// Returns the skewness of the normal distribution
     ///
@@ -169,6 +328,54 @@ impl Distribution<f64> for Normal {
     fn skewness(&self) -> Option<f64> {
         Some(0.0)
     }
+    /// Returns the raw moment of order `k` of the normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sum_{j = 0}^k C(k, j) * μ^(k - j) * E[(X - μ)^j]
+    /// ```
+    ///
+    /// where `μ` is the mean and `C` the binomial coefficient
+    fn moment(&self, k: u32) -> Option<f64> {
+        shift_moments(k, self.mean, |j| self.central_moment(j))
+    }
+    /// Returns the central moment of order `k` of the normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if k is even {
+    ///     σ^k * (k - 1)!!
+    /// } else {
+    ///     0
+    /// }
+    /// ```
+    ///
+    /// where `σ` is the standard deviation
+    fn central_moment(&self, k: u32) -> Option<f64> {
+        if k % 2 == 1 {
+            return Some(0.0);
+        }
+        let double_factorial: f64 = (1..k).step_by(2).map(|i| i as f64).product();
+        Some(fp::powi(self.std_dev, k as i32) * double_factorial)
+    }
+    /// Returns the cumulant of order `k` of the normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// κ_1 = μ, κ_2 = σ^2, κ_k = 0 for k > 2
+    /// ```
+    ///
+    /// where `μ` is the mean and `σ` the standard deviation
+    fn cumulant(&self, k: u32) -> Option<f64> {
+        match k {
+            1 => Some(self.mean),
+            2 => Some(self.std_dev * self.std_dev),
+            _ => Some(0.0),
+        }
+    }
 }
 
 impl Median<f64> for Normal {
@@ -238,18 +445,43 @@ pub fn cdf_unchecked(x: f64, mean: f64, std_dev: f64) -> f64 {
     0.5 * erf::erfc((mean - x) / (std_dev * f64::consts::SQRT_2))
 }
 
+/// performs an unchecked log(cdf) calculation for a normal distribution
+/// with the given mean and standard deviation at x, using a continued
+/// fraction for the Mills ratio in the tails where the cdf underflows or
+/// `erfc` loses relative accuracy
+pub fn ln_cdf_unchecked(x: f64, mean: f64, std_dev: f64) -> f64 {
+    let z = (x - mean) / std_dev;
+    if z < -3.0 {
+        ln_lower_tail(-z)
+    } else if z > 3.0 {
+        fp::ln_1p(-fp::exp(ln_lower_tail(z)))
+    } else {
+        fp::ln(0.5 * erf::erfc(-z / f64::consts::SQRT_2))
+    }
+}
+
+/// Returns `ln(Φ(-t))` for `t > 3` from the continued fraction
+/// `Φ(-t) = φ(t) / (t + 1 / (t + 2 / (t + 3 / (t + ...))))`
+fn ln_lower_tail(t: f64) -> f64 {
+    let mut f = t;
+    for k in (1..=60).rev() {
+        f = t + k as f64 / f;
+    }
+    -0.5 * t * t - consts::LN_SQRT_2PI - fp::ln(f)
+}
+
 /// performs an unchecked pdf calculation for a normal distribution
 /// with the given mean and standard deviation at x
 pub fn pdf_unchecked(x: f64, mean: f64, std_dev: f64) -> f64 {
     let d = (x - mean) / std_dev;
-    (-0.5 * d * d).exp() / (consts::SQRT_2PI * std_dev)
+    fp::exp(-0.5 * d * d) / (consts::SQRT_2PI * std_dev)
 }
 
 /// performs an unchecked log(pdf) calculation for a normal distribution
 /// with the given mean and standard deviation at x
 pub fn ln_pdf_unchecked(x: f64, mean: f64, std_dev: f64) -> f64 {
     let d = (x - mean) / std_dev;
-    (-0.5 * d * d) - consts::LN_SQRT_2PI - std_dev.ln()
+    (-0.5 * d * d) - consts::LN_SQRT_2PI - fp::ln(std_dev)
 }
 
 /// draws a sample from a normal distribution using the Box-Muller algorithm
@@ -257,11 +489,75 @@ pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, mean: f64, std_dev: f64) -
     mean + std_dev * ziggurat::sample_std_normal(rng)
 }
 
+impl CharacteristicFunction<f64, f64> for Normal {
+    /// Calculates the characteristic function for the normal
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(iμt - σ^2 t^2 / 2)
+    /// ```
+    ///
+    /// where `μ` is the mean and `σ` the standard deviation
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let v = self.std_dev * t;
+        Complex::from_polar(fp::exp(-0.5 * v * v), self.mean * t)
+    }
+}
+
+impl KullbackLeibler for Normal {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(σ_q / σ_p) + (σ_p^2 + (μ_p - μ_q)^2) / (2σ_q^2) - 1 / 2
+    /// ```
+    ///
+    /// where `μ` is the mean and `σ` the standard deviation
+    fn kl_divergence(&self, other: &Normal) -> f64 {
+        let r = self.std_dev / other.std_dev;
+        let d = (self.mean - other.mean) / other.std_dev;
+        0.5 * (r * r - 1.0 + d * d) - fp::ln(r)
+    }
+}
+
+impl Parameterized for Normal {
+    fn param_names() -> &'static [&'static str] {
+        &["mean", "std_dev"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.mean, self.std_dev]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Normal> {
+        match *params {
+            [mean, std_dev] => Normal::new(mean, std_dev),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for Normal {
+    type Sample = f64;
+
+    /// Fits the sample mean and the standard deviation with the divisor `n`
+    fn fit_mle(data: &[f64]) -> Result<Normal> {
+        fit::check_sample(data, |_| true)?;
+        let (mean, variance) = fit::mean_variance(data);
+        Normal::new(mean, variance.sqrt())
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::*;
-    use crate::distribution::{ContinuousCDF, Continuous, Normal};
+    use crate::distribution::{CharacteristicFunction, ContinuousCDF, Continuous, Normal};
+    use num_complex::Complex;
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -444,6 +740,44 @@ mod tests {
         test_almost(5.0, 2.0, 0.993790334674, 1e-12, cdf(10.0));
     }
 
+    #[test]
+    fn test_sf() {
+        let sf = |arg: f64| move |x: Normal| x.sf(arg);
+        test_case(5.0, 2.0, 1.0, sf(f64::NEG_INFINITY));
+        test_almost(5.0, 2.0, 0.993790334674, 1e-12, sf(0.0));
+        test_case(5.0, 2.0, 0.5, sf(5.0));
+        test_almost(5.0, 2.0, 0.0000002866515718, 1e-16, sf(15.0));
+        // 1 - cdf underflows to zero this far in the tail
+        test_almost(5.0, 2.0, 3.056696706382874e-138, 1e-148, sf(55.0));
+        test_almost(5.0, 2.0, 3.056696706382874e-138f64.ln(), 1e-10, |x| x.ln_sf(55.0));
+    }
+
+    #[test]
+    fn test_prob_between() {
+        test_almost(0.0, 1.0, 0.13590512198327787, 1e-10, |x| x.prob_between(1.0, 2.0));
+        test_almost(0.0, 1.0, 0.13590512198327787, 1e-10, |x| x.prob_between(-2.0, -1.0));
+        test_almost(0.0, 1.0, 0.6826894921370859, 1e-10, |x| x.prob_between(-1.0, 1.0));
+        test_case(0.0, 1.0, 0.0, |x| x.prob_between(1.0, -1.0));
+        // cdf(10) - cdf(9) rounds to zero
+        test_almost(0.0, 1.0, 1.1285122074236006e-19, 1e-29, |x| x.prob_between(9.0, 10.0));
+        test_almost(0.0, 1.0, 1.1285122074236006e-19, 1e-29, |x| x.prob_between(-10.0, -9.0));
+    }
+
+    #[test]
+    fn test_ln_cdf() {
+        let ln_cdf = |arg: f64| move |x: Normal| x.ln_cdf(arg);
+        test_almost(5.0, 2.0, 0.30853753872598689636229538939166226011639782444542207f64.ln(), 1e-15, ln_cdf(4.0));
+        test_almost(5.0, 2.0, -15.064998393988724, 1e-12, ln_cdf(-5.0));
+        // the continued fraction agrees with the asymptotic series
+        test_almost(0.0, 1.0, -203.91715537109724, 1e-11, ln_cdf(-20.0));
+        test_almost(0.0, 1.0, -203.71670771720838, 1e-11, ln_cdf(-19.99));
+        test_almost(0.0, 1.0, -804.6084420137538, 1e-10, ln_cdf(-40.0));
+        test_almost(0.0, 2.0, -5005.524208694205, 1e-9, ln_cdf(-200.0));
+        test_almost(0.0, 1.0, -7.619853024160593e-24, 1e-36, ln_cdf(10.0));
+        test_almost(0.0, 1.0, -804.6084420137538, 1e-10, |x| x.ln_sf(40.0));
+        test_almost(5.0, 2.0, 0.5f64.ln(), 1e-15, |x| x.ln_sf(5.0));
+    }
+
     #[test]
     fn test_continuous() {
         test::check_continuous_distribution(&try_create(0.0, 1.0), -10.0, 10.0);
@@ -464,4 +798,32 @@ mod tests {
         test_almost(5.0, 2.0, 10.0, 1e-14, inverse_cdf(0.9937903346742238648330218954258077788721022530769078));
         test_case(5.0, 2.0, f64::INFINITY, inverse_cdf(1.0));
     }
+
+    #[test]
+    fn test_cf() {
+        assert_eq!(try_create(0.0, 1.0).cf(0.0), Complex::new(1.0, 0.0));
+        let phi = try_create(1.0, 2.0).cf(0.7);
+        assert_almost_eq!(phi.re, 0.28705376175764935, 1e-15);
+        assert_almost_eq!(phi.im, 0.24178204809668508, 1e-15);
+    }
+
+    #[test]
+    fn test_moment() {
+        let n = try_create(1.0, 2.0);
+        assert_eq!(n.moment(0).unwrap(), 1.0);
+        assert_eq!(n.moment(3).unwrap(), 13.0);
+        assert_eq!(n.moment(4).unwrap(), 73.0);
+        assert_eq!(n.central_moment(3).unwrap(), 0.0);
+        assert_eq!(n.central_moment(4).unwrap(), 48.0);
+        assert_eq!(n.central_moment(6).unwrap(), 960.0);
+    }
+
+    #[test]
+    fn test_cumulant() {
+        let n = try_create(1.0, 2.0);
+        assert_eq!(n.cumulant(1).unwrap(), 1.0);
+        assert_eq!(n.cumulant(2).unwrap(), 4.0);
+        assert_eq!(n.cumulant(3).unwrap(), 0.0);
+        assert_eq!(n.cumulant(6).unwrap(), 0.0);
+    }
 }
@@ -0,0 +1,185 @@
+use crate::distribution::{Continuous, ContinuousCDF, Likelihood};
+use crate::fp;
+use std::f64;
+
+/// Represents an observation of a continuous quantity that may be censored,
+/// known exactly or only to lie below, above or between given values, as
+/// is common for survival times and measurements at detection limits
+///
+/// # Remarks
+///
+/// The log-likelihood of a sample of observations under any continuous
+/// distribution is given by `Likelihood::ln_likelihood`, and maximized by
+/// `fit_censored`. Truncation, where values outside an interval are not
+/// recorded at all, is handled by evaluating the likelihood under the
+/// `Truncated` distribution, and combines freely with censoring.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Exp, Likelihood, Observation};
+///
+/// let exp = Exp::new(2.0).unwrap();
+/// let data = [Observation::Exact(0.5), Observation::RightCensored(1.0)];
+/// // ln(2 e^-1) + ln(e^-2)
+/// assert!((exp.ln_likelihood(&data) - (2f64.ln() - 3.0)).abs() < 1e-14);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Observation {
+    /// The quantity was observed exactly
+    Exact(f64),
+    /// The quantity is only known to be at most the given value
+    LeftCensored(f64),
+    /// The quantity is only known to be at least the given value
+    RightCensored(f64),
+    /// The quantity is only known to lie in the given interval, the lower
+    /// bound excluded
+    IntervalCensored(f64, f64),
+}
+
+impl Observation {
+    /// Returns the contribution of the observation to the log-likelihood
+    /// under `dist`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Exact(x)               => ln f(x)
+    /// LeftCensored(x)        => ln F(x)
+    /// RightCensored(x)       => ln S(x)
+    /// IntervalCensored(a, b) => ln(F(b) - F(a))
+    /// ```
+    ///
+    /// where `f`, `F` and `S` are the pdf, cdf and survival function of
+    /// `dist`, with `NEG_INFINITY` for an interval with `a > b`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Normal, Observation};
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// assert_eq!(Observation::LeftCensored(0.0).ln_likelihood(&n), 0.5f64.ln());
+    /// // far tails keep their precision
+    /// assert!(Observation::RightCensored(40.0).ln_likelihood(&n).is_finite());
+    /// ```
+    pub fn ln_likelihood<D>(&self, dist: &D) -> f64
+    where
+        D: Continuous<f64, f64> + ContinuousCDF<f64, f64>,
+    {
+        match *self {
+            Observation::Exact(x) => dist.ln_pdf(x),
+            Observation::LeftCensored(x) => dist.ln_cdf(x),
+            Observation::RightCensored(x) => dist.ln_sf(x),
+            Observation::IntervalCensored(a, b) => {
+                if a > b {
+                    f64::NEG_INFINITY
+                } else if b == f64::INFINITY {
+                    dist.ln_sf(a)
+                } else if a == f64::NEG_INFINITY {
+                    dist.ln_cdf(b)
+                } else if dist.cdf(a) > 0.5 {
+                    // S(a) - S(b) in the upper half, as F(b) - F(a) in the
+                    // lower half, factored to stay finite far in the tails
+                    let ln_sf_a = dist.ln_sf(a);
+                    ln_sf_a + ln_1m_exp(dist.ln_sf(b) - ln_sf_a)
+                } else {
+                    let ln_cdf_b = dist.ln_cdf(b);
+                    ln_cdf_b + ln_1m_exp(dist.ln_cdf(a) - ln_cdf_b)
+                }
+            }
+        }
+    }
+
+    /// Returns whether the observation is censored, i.e. not exact
+    pub fn is_censored(&self) -> bool {
+        !matches!(*self, Observation::Exact(_))
+    }
+
+    /// Returns whether the observation describes an event of positive
+    /// probability, i.e. exact and one-sided observations are finite and
+    /// the bounds of an interval are not `NaN` and increase
+    pub(crate) fn is_valid(&self) -> bool {
+        match *self {
+            Observation::Exact(x)
+            | Observation::LeftCensored(x)
+            | Observation::RightCensored(x) => x.is_finite(),
+            Observation::IntervalCensored(a, b) => {
+                a < b && a != f64::INFINITY && b != f64::NEG_INFINITY
+            }
+        }
+    }
+}
+
+/// Returns `ln(1 - exp(x))` for `x <= 0` without cancellation
+fn ln_1m_exp(x: f64) -> f64 {
+    if x > -f64::consts::LN_2 {
+        fp::ln(-fp::exp_m1(x))
+    } else {
+        fp::ln_1p(-fp::exp(x))
+    }
+}
+
+impl<D> Likelihood<Observation> for D
+where
+    D: Continuous<f64, f64> + ContinuousCDF<f64, f64>,
+{
+    fn ln_likelihood(&self, data: &[Observation]) -> f64 {
+        data.iter().map(|obs| obs.ln_likelihood(self)).sum()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::distribution::{ContinuousCDF, Continuous, Exp, Likelihood, Normal, Observation, Truncated};
+
+    #[test]
+    fn test_ln_likelihood() {
+        let n = Normal::new(1.0, 2.0).unwrap();
+        assert_eq!(Observation::Exact(0.5).ln_likelihood(&n), n.ln_pdf(0.5));
+        assert_eq!(Observation::LeftCensored(0.5).ln_likelihood(&n), n.ln_cdf(0.5));
+        assert_eq!(Observation::RightCensored(0.5).ln_likelihood(&n), n.ln_sf(0.5));
+        assert_almost_eq!(Observation::IntervalCensored(0.5, 2.0).ln_likelihood(&n), (n.cdf(2.0) - n.cdf(0.5)).ln(), 1e-15);
+        // one-sided intervals are the one-sided observations
+        assert_eq!(Observation::IntervalCensored(0.5, f64::INFINITY).ln_likelihood(&n), n.ln_sf(0.5));
+        assert_eq!(Observation::IntervalCensored(f64::NEG_INFINITY, 0.5).ln_likelihood(&n), n.ln_cdf(0.5));
+        assert_eq!(Observation::IntervalCensored(2.0, 0.5).ln_likelihood(&n), f64::NEG_INFINITY);
+        assert_eq!(Observation::IntervalCensored(0.5, 0.5).ln_likelihood(&n), f64::NEG_INFINITY);
+        // the far upper tail keeps its precision
+        let exp = Exp::new(1.0).unwrap();
+        assert_almost_eq!(Observation::IntervalCensored(800.0, 801.0).ln_likelihood(&exp), -800.0 + (-(-1f64).exp_m1()).ln(), 1e-12);
+
+        let data = [Observation::Exact(0.5), Observation::LeftCensored(-1.0), Observation::IntervalCensored(2.0, 3.0)];
+        let sum: f64 = data.iter().map(|obs| obs.ln_likelihood(&n)).sum();
+        assert_eq!(n.ln_likelihood(&data), sum);
+        assert_eq!(n.ln_likelihood(&[] as &[Observation]), 0.0);
+        // exact observations give the ordinary likelihood
+        assert_eq!(n.ln_likelihood(&[Observation::Exact(0.5), Observation::Exact(2.0)]), n.ln_likelihood(&[0.5, 2.0]));
+    }
+
+    #[test]
+    fn test_truncated() {
+        // a sample recorded only above 1 and censored at 3 has the likelihood
+        // of the censored observations under the truncated distribution
+        let exp = Exp::new(0.5).unwrap();
+        let truncated = Truncated::new(exp, 1.0, f64::INFINITY).unwrap();
+        let data = [Observation::Exact(1.5), Observation::RightCensored(3.0)];
+        let expected = exp.ln_pdf(1.5) + exp.ln_sf(3.0) - 2.0 * exp.ln_sf(1.0);
+        assert_almost_eq!(truncated.ln_likelihood(&data), expected, 1e-14);
+    }
+
+    #[test]
+    fn test_validity() {
+        assert!(Observation::Exact(1.0).is_valid());
+        assert!(!Observation::Exact(f64::INFINITY).is_valid());
+        assert!(!Observation::RightCensored(f64::NAN).is_valid());
+        assert!(Observation::IntervalCensored(f64::NEG_INFINITY, 1.0).is_valid());
+        assert!(!Observation::IntervalCensored(2.0, 1.0).is_valid());
+        assert!(!Observation::IntervalCensored(1.0, 1.0).is_valid());
+        assert!(!Observation::IntervalCensored(f64::NAN, 1.0).is_valid());
+        assert!(!Observation::IntervalCensored(f64::INFINITY, f64::INFINITY).is_valid());
+        assert!(!Observation::Exact(1.0).is_censored());
+        assert!(Observation::LeftCensored(1.0).is_censored());
+    }
+}
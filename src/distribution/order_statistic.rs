@@ -0,0 +1,369 @@
+use crate::distribution::discretize::tanh_sinh;
+use crate::distribution::{Beta, Continuous, ContinuousCDF};
+use crate::fp;
+use crate::function::beta;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the distribution of the `k`th smallest of `n` independent
+/// draws from a continuous parent distribution, i.e. of the `k`th order
+/// statistic `X_(k)`
+///
+/// # Remarks
+///
+/// Since `F(X_(k))` follows a `Beta(k, n - k + 1)` distribution, where `F`
+/// is the cdf of the parent, the cdf and quantiles follow from the
+/// regularized incomplete beta function and its inverse. Moments are
+/// computed by tanh-sinh quadrature over the quantiles of the order
+/// statistic and sampling draws `F^-1(U)` for a beta distributed `U`, so
+/// its cost does not grow with `n`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, Exp, OrderStatistic};
+/// use statrs::statistics::Distribution;
+///
+/// // the minimum of 4 exponential draws with rate 0.5 is exponential with
+/// // rate 2
+/// let min = OrderStatistic::new(Exp::new(0.5).unwrap(), 4, 1).unwrap();
+/// let e = Exp::new(2.0).unwrap();
+/// assert!((min.cdf(0.3) - e.cdf(0.3)).abs() < 1e-14);
+/// assert!((min.mean().unwrap() - 0.5).abs() < 1e-10);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct OrderStatistic<D> {
+    parent: D,
+    n: u64,
+    k: u64,
+}
+
+impl<D> OrderStatistic<D> {
+    /// Constructs the distribution of the `k`th smallest of `n` independent
+    /// draws from `parent`, so that `k = 1` is the minimum and `k = n` the
+    /// maximum
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `k` is zero or larger than `n`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Normal, OrderStatistic};
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// assert!(OrderStatistic::new(n, 5, 5).is_ok());
+    /// assert!(OrderStatistic::new(n, 5, 0).is_err());
+    /// assert!(OrderStatistic::new(n, 5, 6).is_err());
+    /// ```
+    pub fn new(parent: D, n: u64, k: u64) -> Result<OrderStatistic<D>> {
+        if k == 0 || k > n {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(OrderStatistic { parent, n, k })
+        }
+    }
+
+    /// Constructs a new order statistic distribution from parameters already
+    /// known to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(parent: D, n: u64, k: u64) -> OrderStatistic<D> {
+        debug_assert!(k > 0 && k <= n, "invalid order statistic parameters");
+        OrderStatistic { parent, n, k }
+    }
+
+    /// Returns the parent distribution
+    pub fn parent(&self) -> &D {
+        &self.parent
+    }
+
+    /// Returns the number of draws `n`
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Returns the rank `k` of the order statistic
+    pub fn k(&self) -> u64 {
+        self.k
+    }
+
+    /// Returns the shape parameters `(k, n - k + 1)` of the beta
+    /// distribution of `F(X_(k))`
+    fn beta_shapes(&self) -> (f64, f64) {
+        (self.k as f64, (self.n - self.k + 1) as f64)
+    }
+
+    /// Computes `E[h(X_(k))]` by tanh-sinh quadrature of
+    /// `h(F^-1(B^-1(v)))` over `(0, 1)`, where `B` is the cdf of the beta
+    /// distribution of `F(X_(k))`
+    fn expectation<H: Fn(f64) -> f64>(&self, h: H) -> f64
+    where
+        D: ContinuousCDF<f64, f64>,
+    {
+        tanh_sinh()
+            .map(|(t, t_c, w)| {
+                let x = if t <= 0.5 {
+                    self.inverse_cdf(t)
+                } else {
+                    self.upper_quantile(t_c)
+                };
+                w * h(x)
+            })
+            .sum()
+    }
+
+    /// Returns the value exceeded by the order statistic with probability
+    /// `q`
+    fn upper_quantile(&self, q: f64) -> f64
+    where
+        D: ContinuousCDF<f64, f64>,
+    {
+        let (a, b) = self.beta_shapes();
+        self.parent.inverse_cdf(1.0 - beta::inv_beta_reg(b, a, q))
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ::rand::distributions::Distribution<f64> for OrderStatistic<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let (a, b) = self.beta_shapes();
+        let u = ::rand::distributions::Distribution::sample(&Beta::new(a, b).unwrap(), rng);
+        self.parent.inverse_cdf(u)
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f64, f64> for OrderStatistic<D> {
+    /// Calculates the cumulative distribution function at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// I_(F(x))(k, n - k + 1)
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent and `I_x(a, b)` is the
+    /// regularized incomplete beta function
+    fn cdf(&self, x: f64) -> f64 {
+        let (a, b) = self.beta_shapes();
+        beta::beta_reg(a, b, self.parent.cdf(x))
+    }
+
+    /// Calculates the survival function at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// I_(S(x))(n - k + 1, k)
+    /// ```
+    ///
+    /// where `S` is the survival function of the parent and `I_x(a, b)` is
+    /// the regularized incomplete beta function
+    fn sf(&self, x: f64) -> f64 {
+        let (a, b) = self.beta_shapes();
+        beta::beta_reg(b, a, self.parent.sf(x))
+    }
+
+    /// Calculates the inverse cumulative distribution function at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(I^-1_p(k, n - k + 1))
+    /// ```
+    ///
+    /// where `F^-1` is the inverse cdf of the parent and `I^-1` is the
+    /// inverse of the regularized incomplete beta function
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let (a, b) = self.beta_shapes();
+        self.parent.inverse_cdf(beta::inv_beta_reg(a, b, p))
+    }
+}
+
+impl<D: Min<f64>> Min<f64> for OrderStatistic<D> {
+    /// Returns the minimum value in the domain of the order statistic, i.e.
+    /// the minimum of the parent
+    fn min(&self) -> f64 {
+        self.parent.min()
+    }
+}
+
+impl<D: Max<f64>> Max<f64> for OrderStatistic<D> {
+    /// Returns the maximum value in the domain of the order statistic, i.e.
+    /// the maximum of the parent
+    fn max(&self) -> f64 {
+        self.parent.max()
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Distribution<f64> for OrderStatistic<D> {
+    /// Returns the mean of the order statistic, computed numerically
+    fn mean(&self) -> Option<f64> {
+        Some(self.expectation(|x| x))
+    }
+    /// Returns the variance of the order statistic, computed numerically
+    fn variance(&self) -> Option<f64> {
+        self.central_moment(2)
+    }
+    /// Returns the skewness of the order statistic, computed numerically
+    fn skewness(&self) -> Option<f64> {
+        Some(self.central_moment(3)? / fp::powf(self.central_moment(2)?, 1.5))
+    }
+    /// Returns the `j`th raw moment of the order statistic, computed
+    /// numerically
+    fn moment(&self, j: u32) -> Option<f64> {
+        Some(self.expectation(|x| fp::powi(x, j as i32)))
+    }
+    /// Returns the `j`th central moment of the order statistic, computed
+    /// numerically
+    fn central_moment(&self, j: u32) -> Option<f64> {
+        let mean = self.mean()?;
+        Some(self.expectation(|x| fp::powi(x - mean, j as i32)))
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Median<f64> for OrderStatistic<D> {
+    /// Returns the median of the order statistic
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl<D> Continuous<f64, f64> for OrderStatistic<D>
+where
+    D: ContinuousCDF<f64, f64> + Continuous<f64, f64>,
+{
+    /// Calculates the probability density function at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F(x)^(k - 1) * S(x)^(n - k) * f(x) / B(k, n - k + 1)
+    /// ```
+    ///
+    /// where `F`, `S` and `f` are the cdf, survival function and pdf of the
+    /// parent and `B` is the beta function
+    fn pdf(&self, x: f64) -> f64 {
+        fp::exp(self.ln_pdf(x))
+    }
+
+    /// Calculates the log probability density function at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (k - 1) ln(F(x)) + (n - k) ln(S(x)) + ln(f(x)) - ln(B(k, n - k + 1))
+    /// ```
+    ///
+    /// where `F`, `S` and `f` are the cdf, survival function and pdf of the
+    /// parent and `B` is the beta function
+    fn ln_pdf(&self, x: f64) -> f64 {
+        let (a, b) = self.beta_shapes();
+        let ln_f = self.parent.ln_pdf(x);
+        if ln_f == f64::NEG_INFINITY {
+            return ln_f;
+        }
+        let lower = if a > 1.0 {
+            (a - 1.0) * self.parent.ln_cdf(x)
+        } else {
+            0.0
+        };
+        let upper = if b > 1.0 {
+            (b - 1.0) * self.parent.ln_sf(x)
+        } else {
+            0.0
+        };
+        lower + upper + ln_f - beta::ln_beta(a, b)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::{Exp, Normal, Uniform};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bad_create() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        assert!(OrderStatistic::new(n, 0, 0).is_err());
+        assert!(OrderStatistic::new(n, 3, 0).is_err());
+        assert!(OrderStatistic::new(n, 3, 4).is_err());
+        let o = OrderStatistic::new(n, 3, 2).unwrap();
+        assert_eq!((o.n(), o.k()), (3, 2));
+        assert_eq!(*o.parent(), n);
+    }
+
+    #[test]
+    fn test_uniform_is_beta() {
+        let o = OrderStatistic::new(Uniform::new(0.0, 1.0).unwrap(), 7, 3).unwrap();
+        let b = Beta::new(3.0, 5.0).unwrap();
+        for &x in &[0.01, 0.2, 0.37, 0.6, 0.95] {
+            assert_almost_eq!(o.pdf(x), b.pdf(x), 1e-13);
+            assert_almost_eq!(o.cdf(x), b.cdf(x), 1e-15);
+            assert_almost_eq!(o.sf(x), b.sf(x), 1e-15);
+        }
+        assert_almost_eq!(o.mean().unwrap(), b.mean().unwrap(), 1e-12);
+        assert_almost_eq!(o.variance().unwrap(), b.variance().unwrap(), 1e-12);
+        assert_almost_eq!(o.skewness().unwrap(), b.skewness().unwrap(), 1e-9);
+        assert_eq!((o.min(), o.max()), (0.0, 1.0));
+        test::check_continuous_distribution(&o, 0.0, 1.0);
+    }
+
+    #[test]
+    fn test_exp_extremes() {
+        let e = Exp::new(0.5).unwrap();
+        let min = OrderStatistic::new(e, 4, 1).unwrap();
+        let exact = Exp::new(2.0).unwrap();
+        for &x in &[0.01, 0.3, 1.0, 5.0] {
+            assert_almost_eq!(min.pdf(x), exact.pdf(x), 1e-13);
+            assert_almost_eq!(min.sf(x), exact.sf(x), 1e-14);
+        }
+        assert_almost_eq!(min.variance().unwrap(), 0.25, 1e-10);
+        // the maximum of n exponential draws has mean H_n / λ
+        let max = OrderStatistic::new(e, 4, 4).unwrap();
+        assert_almost_eq!(max.mean().unwrap(), 2.0 * (1.0 + 0.5 + 1.0 / 3.0 + 0.25), 1e-9);
+        assert_almost_eq!(max.cdf(3.0), (1.0 - (-1.5f64).exp()).powi(4), 1e-14);
+    }
+
+    #[test]
+    fn test_normal_max() {
+        // expected maximum of 5 standard normal draws
+        let o = OrderStatistic::new(Normal::new(0.0, 1.0).unwrap(), 5, 5).unwrap();
+        assert_almost_eq!(o.mean().unwrap(), 1.1629644736405196, 1e-9);
+        // the median is symmetric
+        let med = OrderStatistic::new(Normal::new(2.0, 1.0).unwrap(), 9, 5).unwrap();
+        assert_almost_eq!(med.median(), 2.0, 1e-12);
+        assert_almost_eq!(med.mean().unwrap(), 2.0, 1e-10);
+        assert_almost_eq!(med.skewness().unwrap(), 0.0, 1e-8);
+        for &p in &[0.001, 0.2, 0.5, 0.9] {
+            assert_almost_eq!(o.cdf(o.inverse_cdf(p)), p, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let o = OrderStatistic::new(Normal::new(0.0, 1.0).unwrap(), 10, 8).unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = 20000;
+        let below = (0..n).filter(|_| o.sample(&mut rng) <= 0.8).count() as f64 / n as f64;
+        assert!((below - o.cdf(0.8)).abs() < 0.01);
+    }
+}
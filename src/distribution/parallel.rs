@@ -0,0 +1,187 @@
+//! Provides data-parallel evaluation and sampling of distributions on the
+//! `rayon` thread pool, available with the `rayon` feature
+
+use crate::distribution::{Continuous, ContinuousCDF};
+use rand::distributions::Distribution;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+
+/// The number of values handled by a single task, which fixes the split of
+/// the work independently of the number of threads
+const CHUNK_SIZE: usize = 1 << 12;
+
+/// Evaluates the probability density function of `dist` at every point of
+/// `x` in parallel
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, Gamma};
+/// use statrs::distribution::parallel::par_pdf_slice;
+///
+/// let gamma = Gamma::new(2.0, 1.0).unwrap();
+/// let x: Vec<f64> = (1..=100000).map(|i| i as f64 / 1000.0).collect();
+/// let pdf = par_pdf_slice(&gamma, &x);
+/// assert_eq!(pdf[999], gamma.pdf(1.0));
+/// ```
+pub fn par_pdf_slice<D>(dist: &D, x: &[f64]) -> Vec<f64>
+where
+    D: Continuous<f64, f64> + Sync,
+{
+    x.par_iter()
+        .with_min_len(CHUNK_SIZE)
+        .map(|&x| dist.pdf(x))
+        .collect()
+}
+
+/// Evaluates the log of the probability density function of `dist` at
+/// every point of `x` in parallel
+pub fn par_ln_pdf_slice<D>(dist: &D, x: &[f64]) -> Vec<f64>
+where
+    D: Continuous<f64, f64> + Sync,
+{
+    x.par_iter()
+        .with_min_len(CHUNK_SIZE)
+        .map(|&x| dist.ln_pdf(x))
+        .collect()
+}
+
+/// Evaluates the cumulative distribution function of `dist` at every point
+/// of `x` in parallel
+pub fn par_cdf_slice<D>(dist: &D, x: &[f64]) -> Vec<f64>
+where
+    D: ContinuousCDF<f64, f64> + Sync,
+{
+    x.par_iter()
+        .with_min_len(CHUNK_SIZE)
+        .map(|&x| dist.cdf(x))
+        .collect()
+}
+
+/// Computes the log likelihood `Σ ln f(x_i)` of the sample `x` under `dist`
+/// in parallel, without storing the individual terms
+///
+/// # Remarks
+///
+/// The terms are summed in fixed chunks whose sums are added in order, so
+/// the result does not depend on the number of threads.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, Normal};
+/// use statrs::distribution::parallel::par_ln_likelihood;
+///
+/// let normal = Normal::new(0.0, 1.0).unwrap();
+/// let x = vec![0.5; 10000];
+/// let ln_l = par_ln_likelihood(&normal, &x);
+/// assert!((ln_l - 10000.0 * normal.ln_pdf(0.5)).abs() < 1e-8);
+/// ```
+pub fn par_ln_likelihood<D>(dist: &D, x: &[f64]) -> f64
+where
+    D: Continuous<f64, f64> + Sync,
+{
+    let sums: Vec<f64> = x
+        .par_chunks(CHUNK_SIZE)
+        .map(|chunk| chunk.iter().map(|&x| dist.ln_pdf(x)).sum())
+        .collect();
+    sums.iter().sum()
+}
+
+/// Draws `n` samples from `dist` in parallel, each chunk of samples being
+/// drawn from its own `StdRng` seeded from `rng`
+///
+/// # Remarks
+///
+/// The streams are seeded in order from `rng` and the samples are split
+/// into fixed chunks, so the samples only depend on the state of `rng`
+/// and not on the number of threads or the scheduling. They differ from
+/// the samples drawn sequentially from `rng`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Exp;
+/// use statrs::distribution::parallel::par_sample_n;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let exp = Exp::new(2.0).unwrap();
+/// let x: Vec<f64> = par_sample_n(&exp, 100000, &mut StdRng::seed_from_u64(0));
+/// let mean = x.iter().sum::<f64>() / 100000.0;
+/// assert!((mean - 0.5).abs() < 0.01);
+/// let y: Vec<f64> = par_sample_n(&exp, 100000, &mut StdRng::seed_from_u64(0));
+/// assert_eq!(x, y);
+/// ```
+pub fn par_sample_n<D, T, R>(dist: &D, n: usize, rng: &mut R) -> Vec<T>
+where
+    D: Distribution<T> + Sync,
+    T: Send,
+    R: Rng + ?Sized,
+{
+    let streams: Vec<StdRng> = (0..n.div_ceil(CHUNK_SIZE))
+        .map(|_| StdRng::from_seed(rng.gen()))
+        .collect();
+    streams
+        .into_par_iter()
+        .enumerate()
+        .flat_map_iter(|(i, mut stream)| {
+            let len = CHUNK_SIZE.min(n - i * CHUNK_SIZE);
+            (0..len)
+                .map(|_| dist.sample(&mut stream))
+                .collect::<Vec<T>>()
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Gamma, Normal, Poisson};
+
+    #[test]
+    fn test_par_eval() {
+        let gamma = Gamma::new(2.5, 1.5).unwrap();
+        let x: Vec<f64> = (0..20000).map(|i| i as f64 / 1000.0).collect();
+        let pdf = par_pdf_slice(&gamma, &x);
+        let ln_pdf = par_ln_pdf_slice(&gamma, &x);
+        let cdf = par_cdf_slice(&gamma, &x);
+        assert_eq!(pdf.len(), x.len());
+        for (i, &xi) in x.iter().enumerate() {
+            assert_eq!(pdf[i], gamma.pdf(xi));
+            assert_eq!(ln_pdf[i], gamma.ln_pdf(xi));
+            assert_eq!(cdf[i], gamma.cdf(xi));
+        }
+        assert!(par_pdf_slice(&gamma, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_par_ln_likelihood() {
+        let normal = Normal::new(1.0, 2.0).unwrap();
+        let x: Vec<f64> = (0..50000).map(|i| (i as f64).sin() * 3.0).collect();
+        let expected: f64 = x.iter().map(|&x| normal.ln_pdf(x)).sum();
+        let ln_l = par_ln_likelihood(&normal, &x);
+        assert!((ln_l - expected).abs() < 1e-9 * expected.abs());
+        // the chunked sum is reproducible
+        assert_eq!(ln_l, par_ln_likelihood(&normal, &x));
+        assert_eq!(par_ln_likelihood(&normal, &[]), 0.0);
+    }
+
+    #[test]
+    fn test_par_sample_n() {
+        let poisson = Poisson::new(3.0).unwrap();
+        for &n in &[0, 1, CHUNK_SIZE, 3 * CHUNK_SIZE + 17] {
+            let x: Vec<f64> = par_sample_n(&poisson, n, &mut StdRng::seed_from_u64(n as u64));
+            assert_eq!(x.len(), n);
+            let y: Vec<f64> = par_sample_n(&poisson, n, &mut StdRng::seed_from_u64(n as u64));
+            assert_eq!(x, y);
+        }
+        let x: Vec<f64> = par_sample_n(&poisson, 200000, &mut StdRng::seed_from_u64(1));
+        let mean = x.iter().sum::<f64>() / 200000.0;
+        assert!((mean - 3.0).abs() < 0.02);
+        // the streams of different chunks are not copies of each other
+        assert_ne!(&x[..CHUNK_SIZE], &x[CHUNK_SIZE..2 * CHUNK_SIZE]);
+    }
+}
@@ -1,7 +1,8 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::critical_values;
+use crate::distribution::{fit, Continuous, ContinuousCDF, Fit, Parameterized};
+use crate::fp;
 use crate::statistics::*;
 use crate::{Result, StatsError};
-use rand::distributions::OpenClosed01;
 use rand::Rng;
 use std::f64;
 
@@ -54,6 +55,22 @@ impl Pareto {
         }
     }
 
+    /// Constructs a new pareto distribution from parameters already known to be
+    /// valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(scale: f64, shape: f64) -> Pareto {
+        debug_assert!(
+            Pareto::new(scale, shape).is_ok(),
+            "invalid pareto parameters"
+        );
+        Pareto { scale, shape }
+    }
+
     /// Returns the scale of the Pareto distribution
     ///
     /// # Examples
@@ -86,8 +103,7 @@ impl Pareto {
 impl ::rand::distributions::Distribution<f64> for Pareto {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
         // Inverse transform sampling
-        let u: f64 = rng.sample(OpenClosed01);
-        self.scale * u.powf(-1.0 / self.shape)
+        self.inverse_cdf(rng.gen())
     }
 }
 
@@ -110,9 +126,108 @@ impl ContinuousCDF<f64, f64> for Pareto {
         if x < self.scale {
             0.0
         } else {
-            1.0 - (self.scale / x).powf(self.shape)
+            1.0 - fp::powf(self.scale / x, self.shape)
         }
     }
+
+    /// Calculates the survival function for the Pareto
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if x < x_m {
+    ///     1
+    /// } else {
+    ///     (x_m/x)^α
+    /// }
+    /// ```
+    ///
+    /// where `x_m` is the scale and `α` is the shape
+    fn sf(&self, x: f64) -> f64 {
+        if x < self.scale {
+            1.0
+        } else {
+            fp::powf(self.scale / x, self.shape)
+        }
+    }
+
+    /// Calculates the log cumulative distribution function for the Pareto
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(1 - (x_m/x)^α)
+    /// ```
+    ///
+    /// where `x_m` is the scale and `α` is the shape
+    fn ln_cdf(&self, x: f64) -> f64 {
+        if x < self.scale {
+            f64::NEG_INFINITY
+        } else {
+            fp::ln(-fp::exp_m1(self.shape * fp::ln(self.scale / x)))
+        }
+    }
+
+    /// Calculates the log survival function for the Pareto
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// α * ln(x_m/x)
+    /// ```
+    ///
+    /// where `x_m` is the scale and `α` is the shape
+    fn ln_sf(&self, x: f64) -> f64 {
+        if x < self.scale {
+            0.0
+        } else {
+            self.shape * fp::ln(self.scale / x)
+        }
+    }
+
+    /// Calculates the probability that a Pareto distributed random variable
+    /// falls between `a` and `b`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (x_m/a)^α * (1 - (a/b)^α)
+    /// ```
+    ///
+    /// where `x_m` is the scale, `α` is the shape and `a` is clamped to be
+    /// at least `x_m`
+    fn prob_between(&self, a: f64, b: f64) -> f64 {
+        let a = a.max(self.scale);
+        if b <= a {
+            0.0
+        } else {
+            -fp::powf(self.scale / a, self.shape) * fp::exp_m1(self.shape * fp::ln(a / b))
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// Pareto distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// x_m * (1 - p)^(-1 / α)
+    /// ```
+    ///
+    /// where `x_m` is the scale and `α` is the shape
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        self.scale * fp::powf(1.0 - p, -1.0 / self.shape)
+    }
 }
 
 impl Min<f64> for Pareto {
@@ -197,7 +312,7 @@ impl Distribution<f64> for Pareto {
     ///
     /// where `x_m` is the scale and `α` is the shape
     fn entropy(&self) -> Option<f64> {
-        Some(self.shape.ln() - self.scale.ln() - (1.0 / self.shape) - 1.0)
+        Some(fp::ln(self.shape) - fp::ln(self.scale) - (1.0 / self.shape) - 1.0)
     }
     /// Returns the skewness of the Pareto distribution
     ///
@@ -224,6 +339,29 @@ impl Distribution<f64> for Pareto {
             )
         }
     }
+    /// Returns the raw moment of order `k` of the Pareto distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if k >= α {
+    ///     INF
+    /// } else {
+    ///     α * x_m^k / (α - k)
+    /// }
+    /// ```
+    ///
+    /// where `x_m` is the scale and `α` is the shape
+    fn moment(&self, k: u32) -> Option<f64> {
+        let k = k as f64;
+        if k >= self.shape {
+            None
+        } else if self.shape.is_infinite() {
+            Some(fp::powf(self.scale, k))
+        } else {
+            Some(self.shape * fp::powf(self.scale, k) / (self.shape - k))
+        }
+    }
 }
 
 impl Median<f64> for Pareto {
@@ -237,7 +375,7 @@ impl Median<f64> for Pareto {
     ///
     /// where `x_m` is the scale and `α` is the shape
     fn median(&self) -> f64 {
-        self.scale * (2f64.powf(1.0 / self.shape))
+        self.scale * (fp::powf(2f64, 1.0 / self.shape))
     }
 }
 
@@ -275,7 +413,7 @@ impl Continuous<f64, f64> for Pareto {
         if x < self.scale {
             0.0
         } else {
-            (self.shape * self.scale.powf(self.shape)) / x.powf(self.shape + 1.0)
+            (self.shape * fp::powf(self.scale, self.shape)) / fp::powf(x, self.shape + 1.0)
         }
     }
 
@@ -297,18 +435,218 @@ impl Continuous<f64, f64> for Pareto {
         if x < self.scale {
             f64::NEG_INFINITY
         } else {
-            self.shape.ln() + self.shape * self.scale.ln() - (self.shape + 1.0) * x.ln()
+            fp::ln(self.shape) + self.shape * fp::ln(self.scale) - (self.shape + 1.0) * fp::ln(x)
         }
     }
 }
 
+impl Parameterized for Pareto {
+    fn param_names() -> &'static [&'static str] {
+        &["scale", "shape"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.scale, self.shape]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Pareto> {
+        match *params {
+            [scale, shape] => Pareto::new(scale, shape),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for Pareto {
+    type Sample = f64;
+
+    /// Fits the scale as the sample minimum `m` and the shape
+    /// `n / Σ ln(x / m)`
+    fn fit_mle(data: &[f64]) -> Result<Pareto> {
+        fit::check_sample(data, |x| x > 0.0)?;
+        let scale = data.iter().fold(f64::INFINITY, |m, &x| m.min(x));
+        let ln_excess = data.iter().map(|x| fp::ln(x / scale)).sum::<f64>();
+        if ln_excess <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        Pareto::new(scale, data.len() as f64 / ln_excess)
+    }
+}
+
+/// The result of `hill_estimator`, the tail index of a sample estimated
+/// from its largest observations
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HillEstimate {
+    k: usize,
+    threshold: f64,
+    shape: f64,
+}
+
+impl HillEstimate {
+    /// Returns the number `k` of upper order statistics used
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the threshold, the `k + 1`th largest observation
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Returns the estimated tail index `α`, the shape of the Pareto tail
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the asymptotic standard error `α / √k` of the tail index
+    pub fn std_error(&self) -> f64 {
+        self.shape / (self.k as f64).sqrt()
+    }
+
+    /// Returns the Pareto distribution of the observations above the
+    /// threshold
+    pub fn dist(&self) -> Pareto {
+        Pareto::new_unchecked(self.threshold, self.shape)
+    }
+
+    /// Returns the confidence interval of the tail index at level
+    /// `confidence`, which is exact when the tail above the threshold is
+    /// Pareto, since `2kα / α̂` then follows the chi-squared distribution
+    /// with `2k` degrees of freedom
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `confidence` is not in `(0, 1)`
+    pub fn confidence_interval(&self, confidence: f64) -> Result<(f64, f64)> {
+        if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+        }
+        let alpha = (1.0 - confidence) / 2.0;
+        let freedom = 2.0 * self.k as f64;
+        Ok((
+            self.shape * critical_values::chi_squared(freedom, 1.0 - alpha)? / freedom,
+            self.shape * critical_values::chi_squared(freedom, alpha)? / freedom,
+        ))
+    }
+}
+
+/// Estimates the tail index `α` of a heavy-tailed sample with the [Hill
+/// estimator](https://en.wikipedia.org/wiki/Heavy-tailed_distribution#Hill's_tail-index_estimator)
+/// from its `k` largest observations
+///
+/// # Formula
+///
+/// ```ignore
+/// 1 / α = Σ ln(x_(i) / x_(k + 1)) / k
+/// ```
+///
+/// over the `k` largest observations `x_(1) >= ... >= x_(k)`, where the
+/// `k + 1`th largest observation `x_(k + 1)` is the threshold above which
+/// the tail is taken to be Pareto
+///
+/// # Remarks
+///
+/// A small `k` gives a noisy estimate and a large `k` one biased by the
+/// body of the distribution; `hill_plot` gives the estimates for every `k`,
+/// among which a range of stable estimates indicates a suitable threshold.
+///
+/// # Errors
+///
+/// Returns an error if an observation is not positive and finite, if `k`
+/// is zero or not less than the number of observations, or if the `k`
+/// largest observations all equal the threshold
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::hill_estimator;
+///
+/// let losses = [1.2, 3.4, 1.1, 8.9, 2.2, 1.6, 25.0, 1.3, 4.1, 1.9];
+/// let hill = hill_estimator(&losses, 4).unwrap();
+/// assert_eq!(hill.threshold(), 2.2);
+/// assert!(hill.shape() > 0.0);
+/// let (lower, upper) = hill.confidence_interval(0.95).unwrap();
+/// assert!(lower < hill.shape() && hill.shape() < upper);
+/// ```
+pub fn hill_estimator(data: &[f64], k: usize) -> Result<HillEstimate> {
+    if k == 0 || k >= data.len() {
+        return Err(StatsError::ArgIntervalExcl("k", 0.0, data.len() as f64));
+    }
+    let sorted = sorted_descending(data)?;
+    let ln_top = sorted[..k].iter().map(|x| fp::ln(*x)).sum::<f64>();
+    hill_from_sum(k, ln_top, sorted[k])
+}
+
+/// Returns the Hill estimates of the tail index of `data` for every number
+/// `k` of upper order statistics from `1` to `n - 1`, in the order of `k`,
+/// skipping those for which the `k` largest observations equal the
+/// threshold. Plotted against `k`, they are the Hill plot, in which a range
+/// of stable estimates indicates a suitable threshold.
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two observations or an
+/// observation that is not positive and finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{hill_estimator, hill_plot};
+///
+/// let losses = [1.2, 3.4, 1.1, 8.9, 2.2, 1.6, 25.0, 1.3, 4.1, 1.9];
+/// let plot = hill_plot(&losses).unwrap();
+/// assert_eq!(plot.len(), 9);
+/// assert_eq!(plot[3], hill_estimator(&losses, 4).unwrap());
+/// ```
+pub fn hill_plot(data: &[f64]) -> Result<Vec<HillEstimate>> {
+    if data.len() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let sorted = sorted_descending(data)?;
+    let mut ln_top = 0.0;
+    let mut estimates = Vec::with_capacity(data.len() - 1);
+    for k in 1..data.len() {
+        ln_top += fp::ln(sorted[k - 1]);
+        if let Ok(estimate) = hill_from_sum(k, ln_top, sorted[k]) {
+            estimates.push(estimate);
+        }
+    }
+    Ok(estimates)
+}
+
+/// Returns `data` sorted in decreasing order, failing if an observation is
+/// not positive and finite
+fn sorted_descending(data: &[f64]) -> Result<Vec<f64>> {
+    fit::check_sample(data, |x| x > 0.0)?;
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    Ok(sorted)
+}
+
+/// Returns the Hill estimate from the sum `ln_top` of the logs of the `k`
+/// largest observations and the threshold
+fn hill_from_sum(k: usize, ln_top: f64, threshold: f64) -> Result<HillEstimate> {
+    let mean_excess = ln_top / k as f64 - fp::ln(threshold);
+    if mean_excess <= 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    Ok(HillEstimate {
+        k,
+        threshold,
+        shape: 1.0 / mean_excess,
+    })
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::*;
-    use crate::distribution::{ContinuousCDF, Continuous, Pareto};
+    use crate::distribution::{hill_estimator, hill_plot, ContinuousCDF, Continuous, Fit, Pareto};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     fn try_create(scale: f64, shape: f64) -> Pareto {
         let p = Pareto::new(scale, shape);
@@ -338,7 +676,12 @@ mod tests {
         where F: Fn(Pareto) -> f64
     {
         let x = get_value(scale, shape, eval);
+        // the expectations pin the platform libm to the last bit; musl's
+        // rounding under `deterministic` can land a few ulps away
+        #[cfg(not(feature = "deterministic"))]
         assert_eq!(expected, x);
+        #[cfg(feature = "deterministic")]
+        assert_relative_eq!(expected, x, max_relative = 8.0 * f64::EPSILON);
     }
 
     fn test_almost<F>(scale: f64, shape: f64, expected: f64, acc: f64, eval: F)
@@ -487,9 +830,91 @@ mod tests {
         test_case(1.0, 1.0, 1.0, cdf(f64::INFINITY));
     }
 
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Pareto| x.inverse_cdf(arg);
+        test_case(1.5, 2.0, 1.5, inverse_cdf(0.0));
+        test_almost(1.5, 2.0, 1.7928429140015907, 1e-15, inverse_cdf(0.3));
+        test_almost(1.5, 3.0, 6.962383250419166, 1e-13, inverse_cdf(0.99));
+        test_case(1.5, 2.0, f64::INFINITY, inverse_cdf(1.0));
+        let n = Pareto::new(2.0, 0.5).unwrap();
+        for &p in [0.01, 0.25, 0.5, 0.75, 0.99].iter() {
+            assert_almost_eq!(n.cdf(n.inverse_cdf(p)), p, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_prob_between() {
+        test_case(1.0, 1.0, 0.5, |x| x.prob_between(0.0, 2.0));
+        test_case(1.0, 1.0, 0.0, |x| x.prob_between(2.0, 2.0));
+        test_almost(1.0, 2.0, 7.5e-7, 1e-20, |x| x.prob_between(1e3, 2e3));
+    }
+
     #[test]
     fn test_continuous() {
         test::check_continuous_distribution(&try_create(1.0, 10.0), 1.0, 10.0);
         test::check_continuous_distribution(&try_create(0.1, 2.0), 0.1, 100.0);
     }
+
+    #[test]
+    fn test_moment() {
+        let n = try_create(1.0, 3.0);
+        assert_eq!(n.moment(2).unwrap(), 3.0);
+        assert!(n.moment(3).is_none());
+        assert!(n.central_moment(4).is_none());
+    }
+
+    const LOSSES: [f64; 10] = [1.2, 3.4, 1.1, 8.9, 2.2, 1.6, 25.0, 1.3, 4.1, 1.9];
+
+    #[test]
+    fn test_fit_mle() {
+        let n = Pareto::fit_mle(&LOSSES).unwrap();
+        assert_eq!(n.scale(), 1.1);
+        assert_almost_eq!(n.shape(), 1.049659541350043560628770811529528507298, 1e-14);
+        assert!(Pareto::fit_mle(&[2.0, 2.0]).is_err());
+        assert!(Pareto::fit_mle(&[0.0, 2.0]).is_err());
+        assert!(Pareto::fit_mle(&[]).is_err());
+    }
+
+    #[test]
+    fn test_hill_estimator() {
+        // reference values computed in arbitrary precision
+        let hill = hill_estimator(&LOSSES, 4).unwrap();
+        assert_eq!((hill.k(), hill.threshold()), (4, 2.2));
+        assert_almost_eq!(hill.shape(), 0.8186890222787676581902456171056422304482, 1e-14);
+        assert_almost_eq!(hill.std_error(), 0.4093445111393838290951228085528211152241, 1e-14);
+        let (lower, upper) = hill.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(lower, 0.2230652042874049308897039860063138116109, 1e-9);
+        assert_almost_eq!(upper, 1.794417554379578719951635880490691853222, 1e-9);
+        assert_eq!(hill.dist(), Pareto::new(2.2, hill.shape()).unwrap());
+        assert!(hill.confidence_interval(0.0).is_err());
+        assert!(hill_estimator(&LOSSES, 0).is_err());
+        assert!(hill_estimator(&LOSSES, 10).is_err());
+        assert!(hill_estimator(&[1.0, 2.0, 2.0], 1).is_err());
+        assert!(hill_estimator(&[1.0, -2.0, 3.0], 1).is_err());
+    }
+
+    #[test]
+    fn test_hill_plot() {
+        let plot = hill_plot(&LOSSES).unwrap();
+        assert_eq!(plot.len(), 9);
+        for (k, hill) in plot.iter().enumerate() {
+            let expected = hill_estimator(&LOSSES, k + 1).unwrap();
+            assert_eq!(hill.k(), k + 1);
+            assert_eq!(hill.threshold(), expected.threshold());
+            assert_almost_eq!(hill.shape(), expected.shape(), 1e-14);
+        }
+        // ties at the threshold are skipped
+        assert_eq!(hill_plot(&[3.0, 3.0, 1.0]).unwrap().iter().map(|h| h.k()).collect::<Vec<_>>(), vec![2]);
+        assert!(hill_plot(&[1.0]).is_err());
+        // the estimates are stable over the tail of a Pareto sample
+        let mut rng = StdRng::seed_from_u64(1);
+        let pareto = Pareto::new(1.0, 1.5).unwrap();
+        let x: Vec<f64> = (0..20000).map(|_| pareto.sample(&mut rng)).collect();
+        let plot = hill_plot(&x).unwrap();
+        for &k in &[500, 2000, 10000] {
+            let hill = &plot[k - 1];
+            assert!((hill.shape() - 1.5).abs() < 4.0 * hill.std_error());
+        }
+    }
 }
@@ -0,0 +1,740 @@
+use crate::distribution::{Continuous, ContinuousCDF};
+use crate::fp;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the piecewise constant distribution, whose density is
+/// constant on each of a sequence of adjacent bins; the distribution of a
+/// histogram
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ContinuousCDF, PiecewiseConstant};
+/// use statrs::statistics::Distribution;
+///
+/// let n = PiecewiseConstant::new(&[0.0, 1.0, 3.0], &[2.0, 6.0]).unwrap();
+/// assert_eq!(n.pdf(0.5), 0.25);
+/// assert_eq!(n.pdf(2.0), 0.375);
+/// assert_eq!(n.cdf(2.0), 0.625);
+/// assert_eq!(n.inverse_cdf(0.625), 2.0);
+/// assert_eq!(n.mean().unwrap(), 1.625);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiecewiseConstant {
+    edges: Vec<f64>,
+    probabilities: Vec<f64>,
+    // the cdf at the edges
+    cumulative: Vec<f64>,
+}
+
+impl PiecewiseConstant {
+    /// Constructs a new piecewise constant distribution from the histogram
+    /// with the bins `[edges[i], edges[i + 1])` holding `counts[i]`
+    /// observations. Empty bins at either end are dropped from the support.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `edges` does not have one more element than
+    /// `counts`, if the edges are not finite and strictly increasing, or
+    /// if the counts are not finite and non-negative with a positive sum
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::PiecewiseConstant;
+    ///
+    /// let mut result = PiecewiseConstant::new(&[0.0, 1.0, 2.0], &[3.0, 1.0]);
+    /// assert!(result.is_ok());
+    ///
+    /// result = PiecewiseConstant::new(&[0.0, 1.0, 1.0], &[3.0, 1.0]);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(edges: &[f64], counts: &[f64]) -> Result<PiecewiseConstant> {
+        let (first, last) = check_knots(edges, counts, counts.len() + 1)?;
+        let edges = &edges[first..=last + 1];
+        let counts = &counts[first..=last];
+        let total: f64 = counts.iter().sum();
+        let probabilities: Vec<f64> = counts.iter().map(|c| c / total).collect();
+        Ok(PiecewiseConstant {
+            edges: edges.to_vec(),
+            cumulative: cumulative(&probabilities),
+            probabilities,
+        })
+    }
+
+    /// Returns the edges of the bins
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Returns the probabilities of the bins
+    pub fn probabilities(&self) -> &[f64] {
+        &self.probabilities
+    }
+
+    /// Returns the index of the bin containing `x`, which must be within
+    /// the support
+    fn bin(&self, x: f64) -> usize {
+        let i = self.edges.partition_point(|&e| e <= x);
+        i.clamp(1, self.probabilities.len()) - 1
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for PiecewiseConstant {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        // Inverse transform sampling
+        self.inverse_cdf(rng.gen())
+    }
+}
+
+impl ContinuousCDF<f64, f64> for PiecewiseConstant {
+    /// Calculates the cumulative distribution function for the piecewise
+    /// constant distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// c_i + p_i (x - e_i) / (e_(i + 1) - e_i)
+    /// ```
+    ///
+    /// where `e_i <= x < e_(i + 1)` and `c_i` is the probability of the
+    /// bins below the `i`-th bin
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= self.min() {
+            return 0.0;
+        }
+        if x >= self.max() {
+            return 1.0;
+        }
+        let i = self.bin(x);
+        let (a, b) = (self.edges[i], self.edges[i + 1]);
+        self.cumulative[i] + self.probabilities[i] * (x - a) / (b - a)
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// piecewise constant distribution at `p`, interpolating linearly
+    /// within the bin whose cumulative probabilities enclose `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p` is not in `[0, 1]`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == 0.0 {
+            return self.min();
+        }
+        let i = self
+            .cumulative
+            .partition_point(|&c| c < p)
+            .clamp(1, self.probabilities.len())
+            - 1;
+        let (a, b) = (self.edges[i], self.edges[i + 1]);
+        let fraction = ((p - self.cumulative[i]) / self.probabilities[i]).min(1.0);
+        a + fraction * (b - a)
+    }
+}
+
+impl Min<f64> for PiecewiseConstant {
+    /// Returns the lower edge of the first non-empty bin
+    fn min(&self) -> f64 {
+        self.edges[0]
+    }
+}
+
+impl Max<f64> for PiecewiseConstant {
+    /// Returns the upper edge of the last non-empty bin
+    fn max(&self) -> f64 {
+        self.edges[self.edges.len() - 1]
+    }
+}
+
+impl Distribution<f64> for PiecewiseConstant {
+    /// Returns the mean of the piecewise constant distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ p_i (e_i + e_(i + 1)) / 2
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        Some(
+            self.probabilities
+                .iter()
+                .zip(self.edges.windows(2))
+                .map(|(p, e)| p * (e[0] + e[1]) / 2.0)
+                .sum(),
+        )
+    }
+    /// Returns the variance of the piecewise constant distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ p_i ((m_i - μ)^2 + w_i^2 / 12)
+    /// ```
+    ///
+    /// where `m_i` and `w_i` are the midpoint and width of the `i`-th bin
+    fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        Some(
+            self.probabilities
+                .iter()
+                .zip(self.edges.windows(2))
+                .map(|(p, e)| {
+                    let (d, w) = ((e[0] + e[1]) / 2.0 - mean, e[1] - e[0]);
+                    p * (d * d + w * w / 12.0)
+                })
+                .sum(),
+        )
+    }
+    /// Returns the entropy of the piecewise constant distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -Σ p_i ln(p_i / w_i)
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        Some(
+            -self
+                .probabilities
+                .iter()
+                .zip(self.edges.windows(2))
+                .filter(|&(&p, _)| p > 0.0)
+                .map(|(p, e)| p * fp::ln(p / (e[1] - e[0])))
+                .sum::<f64>(),
+        )
+    }
+    /// Returns the skewness of the piecewise constant distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ p_i ((m_i - μ)^3 + (m_i - μ) w_i^2 / 4) / σ^3
+    /// ```
+    fn skewness(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let third: f64 = self
+            .probabilities
+            .iter()
+            .zip(self.edges.windows(2))
+            .map(|(p, e)| {
+                let (d, w) = ((e[0] + e[1]) / 2.0 - mean, e[1] - e[0]);
+                p * d * (d * d + w * w / 4.0)
+            })
+            .sum();
+        Some(third / fp::powf(self.variance()?, 1.5))
+    }
+}
+
+impl Median<f64> for PiecewiseConstant {
+    /// Returns the median of the piecewise constant distribution
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Continuous<f64, f64> for PiecewiseConstant {
+    /// Calculates the probability density function for the piecewise
+    /// constant distribution at `x`, the probability of the bin containing
+    /// `x` divided by its width
+    fn pdf(&self, x: f64) -> f64 {
+        if x < self.min() || x > self.max() {
+            return 0.0;
+        }
+        let i = self.bin(x);
+        self.probabilities[i] / (self.edges[i + 1] - self.edges[i])
+    }
+
+    /// Calculates the log probability density function for the piecewise
+    /// constant distribution at `x`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        fp::ln(self.pdf(x))
+    }
+}
+
+/// Implements the piecewise linear distribution, whose density
+/// interpolates linearly between its values at a sequence of knots
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ContinuousCDF, PiecewiseLinear};
+/// use statrs::statistics::Distribution;
+///
+/// // the triangular distribution on [0, 2] with mode 1
+/// let n = PiecewiseLinear::new(&[0.0, 1.0, 2.0], &[0.0, 5.0, 0.0]).unwrap();
+/// assert_eq!(n.pdf(0.5), 0.5);
+/// assert_eq!(n.cdf(0.5), 0.125);
+/// assert_eq!(n.inverse_cdf(0.125), 0.5);
+/// assert_eq!(n.mean().unwrap(), 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiecewiseLinear {
+    knots: Vec<f64>,
+    densities: Vec<f64>,
+    // the cdf at the knots
+    cumulative: Vec<f64>,
+}
+
+impl PiecewiseLinear {
+    /// Constructs a new piecewise linear distribution whose density is
+    /// proportional to `weights[i]` at `knots[i]` and linear in between.
+    /// Segments without mass at either end are dropped from the support.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `knots` and `weights` differ in length or have
+    /// fewer than two elements, if the knots are not finite and strictly
+    /// increasing, or if the weights are not finite and non-negative with
+    /// a positive sum
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::PiecewiseLinear;
+    ///
+    /// let mut result = PiecewiseLinear::new(&[0.0, 1.0, 2.0], &[1.0, 3.0, 0.0]);
+    /// assert!(result.is_ok());
+    ///
+    /// result = PiecewiseLinear::new(&[0.0, 1.0, 2.0], &[0.0, 0.0, 0.0]);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(knots: &[f64], weights: &[f64]) -> Result<PiecewiseLinear> {
+        if weights.len() < 2 {
+            return Err(StatsError::BadParams);
+        }
+        check_knots(knots, weights, weights.len())?;
+        let masses: Vec<f64> = knots
+            .windows(2)
+            .zip(weights.windows(2))
+            .map(|(x, y)| (x[1] - x[0]) * (y[0] + y[1]) / 2.0)
+            .collect();
+        let first = masses.iter().position(|&m| m > 0.0).unwrap();
+        let last = masses.iter().rposition(|&m| m > 0.0).unwrap();
+        let total: f64 = masses.iter().sum();
+        let probabilities = &masses[first..=last]
+            .iter()
+            .map(|m| m / total)
+            .collect::<Vec<_>>();
+        Ok(PiecewiseLinear {
+            knots: knots[first..=last + 1].to_vec(),
+            densities: weights[first..=last + 1]
+                .iter()
+                .map(|w| w / total)
+                .collect(),
+            cumulative: cumulative(probabilities),
+        })
+    }
+
+    /// Constructs the piecewise linear distribution of the frequency
+    /// polygon of a histogram with the bins `[edges[i], edges[i + 1])`
+    /// holding `counts[i]` observations, whose density interpolates the
+    /// histogram density between the midpoints of the bins and is constant
+    /// from the outermost midpoints to the outermost edges
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as
+    /// `PiecewiseConstant::new`
+    pub fn from_histogram(edges: &[f64], counts: &[f64]) -> Result<PiecewiseLinear> {
+        check_knots(edges, counts, counts.len() + 1)?;
+        let n = counts.len();
+        let mut knots = Vec::with_capacity(n + 2);
+        let mut weights = Vec::with_capacity(n + 2);
+        knots.push(edges[0]);
+        weights.push(counts[0] / (edges[1] - edges[0]));
+        for (e, c) in edges.windows(2).zip(counts) {
+            knots.push((e[0] + e[1]) / 2.0);
+            weights.push(c / (e[1] - e[0]));
+        }
+        knots.push(edges[n]);
+        weights.push(weights[n]);
+        PiecewiseLinear::new(&knots, &weights)
+    }
+
+    /// Returns the knots
+    pub fn knots(&self) -> &[f64] {
+        &self.knots
+    }
+
+    /// Returns the densities at the knots
+    pub fn densities(&self) -> &[f64] {
+        &self.densities
+    }
+
+    /// Returns the index of the segment containing `x`, which must be
+    /// within the support
+    fn segment(&self, x: f64) -> usize {
+        let i = self.knots.partition_point(|&k| k <= x);
+        i.clamp(1, self.knots.len() - 1) - 1
+    }
+
+    /// Returns the integrals `∫ (x - c)^k f(x) dx` over the support for
+    /// `k = 1, 2, 3`
+    fn central_moments(&self, c: f64) -> [f64; 3] {
+        let mut moments = [0.0; 3];
+        for (x, y) in self.knots.windows(2).zip(self.densities.windows(2)) {
+            // the density is c0 + c1 s in s = x - c
+            let slope = (y[1] - y[0]) / (x[1] - x[0]);
+            let c0 = y[0] + slope * (c - x[0]);
+            let (s0, s1) = (x[0] - c, x[1] - c);
+            for (k, moment) in moments.iter_mut().enumerate() {
+                let k = k as i32 + 1;
+                *moment += c0 * (fp::powi(s1, k + 1) - fp::powi(s0, k + 1)) / f64::from(k + 1)
+                    + slope * (fp::powi(s1, k + 2) - fp::powi(s0, k + 2)) / f64::from(k + 2);
+            }
+        }
+        moments
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for PiecewiseLinear {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        // Inverse transform sampling
+        self.inverse_cdf(rng.gen())
+    }
+}
+
+impl ContinuousCDF<f64, f64> for PiecewiseLinear {
+    /// Calculates the cumulative distribution function for the piecewise
+    /// linear distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// c_i + (x - k_i) (f_i + f(x)) / 2
+    /// ```
+    ///
+    /// where `k_i <= x < k_(i + 1)`, `f_i` is the density at `k_i` and
+    /// `c_i` is the probability below `k_i`
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= self.min() {
+            return 0.0;
+        }
+        if x >= self.max() {
+            return 1.0;
+        }
+        let i = self.segment(x);
+        (self.cumulative[i] + (x - self.knots[i]) * (self.densities[i] + self.pdf(x)) / 2.0)
+            .min(1.0)
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// piecewise linear distribution at `p`, solving the quadratic
+    /// equation for the cdf within the segment whose cumulative
+    /// probabilities enclose `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p` is not in `[0, 1]`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == 0.0 {
+            return self.min();
+        }
+        let i = self
+            .cumulative
+            .partition_point(|&c| c < p)
+            .clamp(1, self.knots.len() - 1)
+            - 1;
+        let (a, b) = (self.knots[i], self.knots[i + 1]);
+        let (fa, fb) = (self.densities[i], self.densities[i + 1]);
+        // solves slope t^2 / 2 + fa t = area for the offset t from a, in
+        // the form which is stable when the slope is small
+        let area = p - self.cumulative[i];
+        let slope = (fb - fa) / (b - a);
+        let t = 2.0 * area / (fa + (fa * fa + 2.0 * slope * area).max(0.0).sqrt());
+        (a + t).min(b)
+    }
+}
+
+impl Min<f64> for PiecewiseLinear {
+    /// Returns the first knot of the support
+    fn min(&self) -> f64 {
+        self.knots[0]
+    }
+}
+
+impl Max<f64> for PiecewiseLinear {
+    /// Returns the last knot of the support
+    fn max(&self) -> f64 {
+        self.knots[self.knots.len() - 1]
+    }
+}
+
+impl Distribution<f64> for PiecewiseLinear {
+    /// Returns the mean of the piecewise linear distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ w_i (k_i (f_i + f_(i + 1)) / 2 + w_i (f_i + 2 f_(i + 1)) / 6)
+    /// ```
+    ///
+    /// where `w_i = k_(i + 1) - k_i`
+    fn mean(&self) -> Option<f64> {
+        Some(
+            self.knots
+                .windows(2)
+                .zip(self.densities.windows(2))
+                .map(|(x, y)| {
+                    let w = x[1] - x[0];
+                    w * (x[0] * (y[0] + y[1]) / 2.0 + w * (y[0] + 2.0 * y[1]) / 6.0)
+                })
+                .sum(),
+        )
+    }
+    /// Returns the variance of the piecewise linear distribution,
+    /// integrating the squared deviation from the mean exactly over each
+    /// segment
+    fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        Some(self.central_moments(mean)[1])
+    }
+    /// Returns the entropy of the piecewise linear distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -Σ w_i (g(f_(i + 1)) - g(f_i)) / (f_(i + 1) - f_i)
+    /// ```
+    ///
+    /// where `g(f) = f^2 ln(f) / 2 - f^2 / 4`, or `w_i f_i ln(f_i)` where
+    /// the density is constant
+    fn entropy(&self) -> Option<f64> {
+        let g = |f: f64| {
+            if f > 0.0 {
+                f * f * (2.0 * fp::ln(f) - 1.0) / 4.0
+            } else {
+                0.0
+            }
+        };
+        let h = |f: f64| if f > 0.0 { f * fp::ln(f) } else { 0.0 };
+        Some(
+            -self
+                .knots
+                .windows(2)
+                .zip(self.densities.windows(2))
+                .map(|(x, y)| {
+                    let (w, d) = (x[1] - x[0], y[1] - y[0]);
+                    if d.abs() <= 1e-6 * y[0].max(y[1]) {
+                        // the midpoint rule, with an error of the order of d^2
+                        w * h((y[0] + y[1]) / 2.0)
+                    } else {
+                        w * (g(y[1]) - g(y[0])) / d
+                    }
+                })
+                .sum::<f64>(),
+        )
+    }
+    /// Returns the skewness of the piecewise linear distribution
+    fn skewness(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let moments = self.central_moments(mean);
+        Some(moments[2] / fp::powf(moments[1], 1.5))
+    }
+}
+
+impl Median<f64> for PiecewiseLinear {
+    /// Returns the median of the piecewise linear distribution
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Continuous<f64, f64> for PiecewiseLinear {
+    /// Calculates the probability density function for the piecewise
+    /// linear distribution at `x`, interpolating linearly between the
+    /// densities at the enclosing knots
+    fn pdf(&self, x: f64) -> f64 {
+        if x < self.min() || x > self.max() {
+            return 0.0;
+        }
+        let i = self.segment(x);
+        let (a, b) = (self.knots[i], self.knots[i + 1]);
+        let t = (x - a) / (b - a);
+        self.densities[i] + t * (self.densities[i + 1] - self.densities[i])
+    }
+
+    /// Calculates the log probability density function for the piecewise
+    /// linear distribution at `x`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        fp::ln(self.pdf(x))
+    }
+}
+
+/// Checks that `knots` has `len` finite and strictly increasing elements
+/// and that the weights are finite and non-negative with a positive sum,
+/// returning the indices of the first and last positive weights
+fn check_knots(knots: &[f64], weights: &[f64], len: usize) -> Result<(usize, usize)> {
+    if knots.len() != len || knots.len() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    if knots.iter().any(|k| !k.is_finite()) || knots.windows(2).any(|k| k[0] >= k[1]) {
+        return Err(StatsError::BadParams);
+    }
+    if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+        return Err(StatsError::BadParams);
+    }
+    match (
+        weights.iter().position(|&w| w > 0.0),
+        weights.iter().rposition(|&w| w > 0.0),
+    ) {
+        (Some(first), Some(last)) => Ok((first, last)),
+        _ => Err(StatsError::BadParams),
+    }
+}
+
+/// Returns the cumulative sums of `probabilities` starting from zero, with
+/// the last replaced by one
+fn cumulative(probabilities: &[f64]) -> Vec<f64> {
+    let mut cumulative = Vec::with_capacity(probabilities.len() + 1);
+    let mut sum = 0.0;
+    cumulative.push(sum);
+    for p in probabilities {
+        sum += p;
+        cumulative.push(sum);
+    }
+    cumulative[probabilities.len()] = 1.0;
+    cumulative
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::statistics::*;
+    use crate::distribution::{Continuous, ContinuousCDF, PiecewiseConstant, PiecewiseLinear, Triangular, Uniform};
+    use crate::distribution::internal::*;
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_constant() {
+        let n = PiecewiseConstant::new(&[-1.0, 0.0, 1.0, 3.0, 4.0, 5.0], &[0.0, 1.0, 2.0, 1.0, 0.0]).unwrap();
+        // the empty bins at the ends are dropped
+        assert_eq!(n.edges(), &[0.0, 1.0, 3.0, 4.0]);
+        assert_eq!(n.probabilities(), &[0.25, 0.5, 0.25]);
+        assert_eq!((n.min(), n.max()), (0.0, 4.0));
+        assert_eq!(n.pdf(-0.5), 0.0);
+        assert_eq!(n.pdf(0.5), 0.25);
+        assert_eq!(n.pdf(1.0), 0.25);
+        assert_eq!(n.pdf(4.0), 0.25);
+        assert_eq!(n.ln_pdf(4.5), f64::NEG_INFINITY);
+        assert_eq!(n.cdf(0.5), 0.125);
+        assert_eq!(n.cdf(2.0), 0.5);
+        assert_eq!(n.cdf(3.5), 0.875);
+        for &p in &[0.0, 0.1, 0.25, 0.5, 0.8, 1.0] {
+            assert_almost_eq!(n.cdf(n.inverse_cdf(p)), p, 1e-15);
+        }
+        assert_eq!(n.median(), 2.0);
+        // the moments of the mixture of uniforms on the bins
+        assert_eq!(n.mean().unwrap(), 2.0);
+        assert_almost_eq!(n.variance().unwrap(), 0.25 * (2.25 + 1.0 / 12.0) * 2.0 + 0.5 / 3.0, 1e-15);
+        assert_almost_eq!(n.skewness().unwrap(), 0.0, 1e-15);
+        assert_almost_eq!(n.entropy().unwrap(), -(0.5 * 0.25f64.ln() + 0.5 * 0.25f64.ln()), 1e-15);
+        test::check_continuous_distribution(&n, 0.0, 4.0);
+        // a single bin is the uniform distribution
+        let n = PiecewiseConstant::new(&[2.0, 5.0], &[7.0]).unwrap();
+        let u = Uniform::new(2.0, 5.0).unwrap();
+        for &x in &[2.5, 3.0, 4.9] {
+            assert_almost_eq!(n.cdf(x), u.cdf(x), 1e-15);
+            assert_almost_eq!(n.pdf(x), u.pdf(x), 1e-15);
+        }
+        assert_almost_eq!(n.variance().unwrap(), u.variance().unwrap(), 1e-15);
+        assert_almost_eq!(n.entropy().unwrap(), u.entropy().unwrap(), 1e-15);
+    }
+
+    #[test]
+    fn test_constant_skewness() {
+        // bins [0, 1) and [1, 3) with probabilities 1/4 and 3/4
+        let n = PiecewiseConstant::new(&[0.0, 1.0, 3.0], &[1.0, 3.0]).unwrap();
+        let mean = 0.25 * 0.5 + 0.75 * 2.0;
+        let raw = |k: i32| 0.25 / f64::from(k + 1) + 0.75 * (3f64.powi(k + 1) - 1.0) / (2.0 * f64::from(k + 1));
+        let variance = raw(2) - mean * mean;
+        let third = raw(3) - 3.0 * mean * raw(2) + 2.0 * mean.powi(3);
+        assert_almost_eq!(n.mean().unwrap(), mean, 1e-15);
+        assert_almost_eq!(n.variance().unwrap(), variance, 1e-14);
+        assert_almost_eq!(n.skewness().unwrap(), third / variance.powf(1.5), 1e-13);
+    }
+
+    #[test]
+    fn test_bad_create() {
+        assert!(PiecewiseConstant::new(&[0.0, 1.0], &[1.0, 1.0]).is_err());
+        assert!(PiecewiseConstant::new(&[0.0], &[]).is_err());
+        assert!(PiecewiseConstant::new(&[1.0, 0.0], &[1.0]).is_err());
+        assert!(PiecewiseConstant::new(&[0.0, f64::INFINITY], &[1.0]).is_err());
+        assert!(PiecewiseConstant::new(&[0.0, 1.0, 2.0], &[1.0, -1.0]).is_err());
+        assert!(PiecewiseConstant::new(&[0.0, 1.0, 2.0], &[1.0, f64::NAN]).is_err());
+        assert!(PiecewiseConstant::new(&[0.0, 1.0, 2.0], &[0.0, 0.0]).is_err());
+        assert!(PiecewiseLinear::new(&[0.0], &[1.0]).is_err());
+        assert!(PiecewiseLinear::new(&[0.0, 1.0], &[1.0]).is_err());
+        assert!(PiecewiseLinear::new(&[0.0, 0.0], &[1.0, 1.0]).is_err());
+        assert!(PiecewiseLinear::new(&[0.0, 1.0], &[0.0, 0.0]).is_err());
+        assert!(PiecewiseLinear::from_histogram(&[0.0, 1.0], &[1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_linear() {
+        // the triangular distribution on [-1, 3] with mode 2, padded with
+        // segments without mass
+        let n = PiecewiseLinear::new(&[-2.0, -1.0, 2.0, 3.0, 4.0], &[0.0, 0.0, 1.0, 0.0, 0.0]).unwrap();
+        let t = Triangular::new(-1.0, 3.0, 2.0).unwrap();
+        assert_eq!(n.knots(), &[-1.0, 2.0, 3.0]);
+        assert_eq!((n.min(), n.max()), (-1.0, 3.0));
+        for &x in &[-1.5, -1.0, 0.0, 1.3, 2.0, 2.5, 3.0, 3.5] {
+            assert_almost_eq!(n.pdf(x), t.pdf(x), 1e-15);
+            assert_almost_eq!(n.cdf(x), t.cdf(x), 1e-15);
+        }
+        for &p in &[0.0, 0.01, 0.3, 0.75, 0.9, 1.0] {
+            assert_almost_eq!(n.inverse_cdf(p), t.inverse_cdf(p), 1e-14);
+        }
+        assert_almost_eq!(n.median(), t.median(), 1e-14);
+        assert_almost_eq!(n.mean().unwrap(), t.mean().unwrap(), 1e-15);
+        assert_almost_eq!(n.variance().unwrap(), t.variance().unwrap(), 1e-14);
+        assert_almost_eq!(n.skewness().unwrap(), t.skewness().unwrap(), 1e-13);
+        assert_almost_eq!(n.entropy().unwrap(), t.entropy().unwrap(), 1e-14);
+        test::check_continuous_distribution(&n, -1.0, 3.0);
+        // a constant density is the uniform distribution, whose entropy
+        // takes the midpoint rule
+        let n = PiecewiseLinear::new(&[1.0, 2.0, 5.0], &[3.0, 3.0, 3.0]).unwrap();
+        let u = Uniform::new(1.0, 5.0).unwrap();
+        assert_almost_eq!(n.inverse_cdf(0.3), u.inverse_cdf(0.3), 1e-15);
+        assert_almost_eq!(n.variance().unwrap(), u.variance().unwrap(), 1e-14);
+        assert_almost_eq!(n.entropy().unwrap(), u.entropy().unwrap(), 1e-15);
+    }
+
+    #[test]
+    fn test_from_histogram() {
+        let n = PiecewiseLinear::from_histogram(&[0.0, 1.0, 3.0], &[2.0, 2.0]).unwrap();
+        assert_eq!(n.knots(), &[0.0, 0.5, 2.0, 3.0]);
+        // the histogram densities 2 and 1 at the midpoints, normalized by
+        // the area 1 + 2.25 + 1
+        let area = 4.25;
+        assert_almost_eq!(n.pdf(0.25), 2.0 / area, 1e-15);
+        assert_almost_eq!(n.pdf(1.25), 1.5 / area, 1e-15);
+        assert_almost_eq!(n.pdf(2.5), 1.0 / area, 1e-15);
+        assert_almost_eq!(n.cdf(0.5), 1.0 / area, 1e-15);
+        test::check_continuous_distribution(&n, 0.0, 3.0);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let n = PiecewiseConstant::new(&[0.0, 1.0, 3.0, 4.0], &[1.0, 2.0, 1.0]).unwrap();
+        let x: Vec<f64> = (0..20000).map(|_| n.sample(&mut rng)).collect();
+        assert!(x.iter().all(|&x| (0.0..=4.0).contains(&x)));
+        assert!((x.iter().filter(|&&x| x < 1.0).count() as f64 / 20000.0 - 0.25).abs() < 0.01);
+        let n = PiecewiseLinear::new(&[0.0, 1.0, 2.0], &[0.0, 1.0, 0.0]).unwrap();
+        let x: Vec<f64> = (0..20000).map(|_| n.sample(&mut rng)).collect();
+        assert!((x.iter().filter(|&&x| x < 0.5).count() as f64 / 20000.0 - 0.125).abs() < 0.01);
+    }
+}
@@ -1,7 +1,12 @@
-use crate::distribution::{Discrete, DiscreteCDF};
-use crate::function::{factorial, gamma};
+use crate::distribution::{
+    fit, CharacteristicFunction, Discrete, DiscreteCDF, Fit, KullbackLeibler, Parameterized,
+    SampleBatch,
+};
+use crate::fp;
+use crate::function::{erf, factorial, gamma};
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 use std::f64;
 use std::u64;
@@ -52,6 +57,19 @@ impl Poisson {
         }
     }
 
+    /// Constructs a new poisson distribution from parameters already known to
+    /// be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(lambda: f64) -> Poisson {
+        debug_assert!(Poisson::new(lambda).is_ok(), "invalid poisson parameters");
+        Poisson { lambda }
+    }
+
     /// Returns the rate (λ) of the poisson distribution
     ///
     /// # Examples
@@ -69,10 +87,9 @@ impl Poisson {
 
 impl ::rand::distributions::Distribution<f64> for Poisson {
     /// Generates one sample from the Poisson distribution either by
-    /// Knuth's method if lambda < 30.0 or Rejection method PA by
-    /// A. C. Atkinson from the Journal of the Royal Statistical Society
-    /// Series C (Applied Statistics) Vol. 28 No. 1. (1979) pp. 29 - 35
-    /// otherwise
+    /// Knuth's method if lambda < 10.0 or the transformed rejection method
+    /// PTRS by W. Hörmann from Insurance: Mathematics and Economics Vol. 12
+    /// No. 1 (1993) pp. 39 - 45 otherwise
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
         sample_unchecked(rng, self.lambda)
     }
@@ -92,6 +109,20 @@ impl DiscreteCDF<u64, f64> for Poisson {
     fn cdf(&self, x: u64) -> f64 {
         1.0 - gamma::gamma_lr(x as f64 + 1.0, self.lambda)
     }
+
+    /// Calculates the survival function for the poisson
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// P(x + 1, λ)
+    /// ```
+    ///
+    /// where `λ` is the rate and `P` is the lower regularized gamma function
+    fn sf(&self, x: u64) -> f64 {
+        gamma::gamma_lr(x as f64 + 1.0, self.lambda)
+    }
 }
 
 impl Min<u64> for Poisson {
@@ -158,7 +189,7 @@ impl Distribution<f64> for Poisson {
     /// where `λ` is the rate
     fn entropy(&self) -> Option<f64> {
         Some(
-            0.5 * (2.0 * f64::consts::PI * f64::consts::E * self.lambda).ln()
+            0.5 * fp::ln(2.0 * f64::consts::PI * f64::consts::E * self.lambda)
                 - 1.0 / (12.0 * self.lambda)
                 - 1.0 / (24.0 * self.lambda * self.lambda)
                 - 19.0 / (360.0 * self.lambda * self.lambda * self.lambda),
@@ -176,6 +207,22 @@ impl Distribution<f64> for Poisson {
     fn skewness(&self) -> Option<f64> {
         Some(1.0 / self.lambda.sqrt())
     }
+    /// Returns the cumulant of order `k` of the poisson distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// κ_k = λ for k > 0
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn cumulant(&self, k: u32) -> Option<f64> {
+        if k == 0 {
+            Some(0.0)
+        } else {
+            Some(self.lambda)
+        }
+    }
 }
 
 impl Median<f64> for Poisson {
@@ -220,7 +267,7 @@ impl Discrete<u64, f64> for Poisson {
     ///
     /// where `λ` is the rate
     fn pmf(&self, x: u64) -> f64 {
-        (-self.lambda + x as f64 * self.lambda.ln() - factorial::ln_factorial(x as u64)).exp()
+        fp::exp(-self.lambda + x as f64 * fp::ln(self.lambda) - factorial::ln_factorial(x as u64))
     }
 
     /// Calculates the log probability mass function for the poisson
@@ -235,56 +282,257 @@ impl Discrete<u64, f64> for Poisson {
     ///
     /// where `λ` is the rate
     fn ln_pmf(&self, x: u64) -> f64 {
-        -self.lambda + x as f64 * self.lambda.ln() - factorial::ln_factorial(x as u64)
+        -self.lambda + x as f64 * fp::ln(self.lambda) - factorial::ln_factorial(x as u64)
     }
 }
 /// Generates one sample from the Poisson distribution either by
-/// Knuth's method if lambda < 30.0 or Rejection method PA by
-/// A. C. Atkinson from the Journal of the Royal Statistical Society
-/// Series C (Applied Statistics) Vol. 28 No. 1. (1979) pp. 29 - 35
-/// otherwise
+/// Knuth's method if lambda < 10.0 or the transformed rejection method
+/// PTRS by W. Hörmann from Insurance: Mathematics and Economics Vol. 12
+/// No. 1 (1993) pp. 39 - 45 otherwise
 pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, lambda: f64) -> f64 {
-    if lambda < 30.0 {
-        let limit = (-lambda).exp();
-        let mut count = 0.0;
-        let mut product: f64 = rng.gen();
-        while product >= limit {
-            count += 1.0;
-            product *= rng.gen::<f64>();
+    ::rand::distributions::Distribution::sample(&PoissonSampler::new(lambda), rng)
+}
+
+/// A sampler for the poisson distribution which computes the constants of
+/// the multiplication or transformed rejection method once, for drawing
+/// many samples
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PoissonSampler(PoissonMethod);
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum PoissonMethod {
+    Multiplication {
+        limit: f64,
+    },
+    TransformedRejection {
+        lambda: f64,
+        ln_lambda: f64,
+        a: f64,
+        b: f64,
+        ln_inv_alpha: f64,
+        v_r: f64,
+    },
+}
+
+impl PoissonSampler {
+    pub(crate) fn new(lambda: f64) -> PoissonSampler {
+        if lambda < 10.0 {
+            PoissonSampler(PoissonMethod::Multiplication {
+                limit: fp::exp(-lambda),
+            })
+        } else {
+            let b = 0.931 + 2.53 * lambda.sqrt();
+            PoissonSampler(PoissonMethod::TransformedRejection {
+                lambda,
+                ln_lambda: fp::ln(lambda),
+                a: -0.059 + 0.02483 * b,
+                b,
+                ln_inv_alpha: fp::ln(1.1239 + 1.1328 / (b - 3.4)),
+                v_r: 0.9277 - 3.6224 / (b - 2.0),
+            })
         }
-        count
-    } else {
-        let c = 0.767 - 3.36 / lambda;
-        let beta = f64::consts::PI / (3.0 * lambda).sqrt();
-        let alpha = beta * lambda;
-        let k = c.ln() - lambda - beta.ln();
-
-        loop {
-            let u: f64 = rng.gen();
-            let x = (alpha - ((1.0 - u) / u).ln()) / beta;
-            let n = (x + 0.5).floor();
-            if n < 0.0 {
-                continue;
-            }
+    }
+}
 
-            let v: f64 = rng.gen();
-            let y = alpha - beta * x;
-            let temp = 1.0 + y.exp();
-            let lhs = y + (v / (temp * temp)).ln();
-            let rhs = k + n * lambda.ln() - factorial::ln_factorial(n as u64);
-            if lhs <= rhs {
-                return n;
+impl ::rand::distributions::Distribution<f64> for PoissonSampler {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self.0 {
+            PoissonMethod::Multiplication { limit } => {
+                let mut count = 0.0;
+                let mut product: f64 = rng.gen();
+                while product >= limit {
+                    count += 1.0;
+                    product *= rng.gen::<f64>();
+                }
+                count
             }
+            PoissonMethod::TransformedRejection {
+                lambda,
+                ln_lambda,
+                a,
+                b,
+                ln_inv_alpha,
+                v_r,
+            } => loop {
+                let u = rng.gen::<f64>() - 0.5;
+                let v: f64 = rng.gen();
+                let us = 0.5 - u.abs();
+                let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+                // immediate acceptance in the centre of the hat
+                if us >= 0.07 && v <= v_r {
+                    return k;
+                }
+                if k < 0.0 || (us < 0.013 && v > us) {
+                    continue;
+                }
+                let lhs = fp::ln(v) + ln_inv_alpha - fp::ln(a / (us * us) + b);
+                let rhs = -lambda + k * ln_lambda - factorial::ln_factorial(k as u64);
+                if lhs <= rhs {
+                    return k;
+                }
+            },
         }
     }
 }
 
+impl SampleBatch<f64> for Poisson {
+    type Sampler = PoissonSampler;
+
+    fn sampler(&self) -> PoissonSampler {
+        PoissonSampler::new(self.lambda)
+    }
+}
+
+impl CharacteristicFunction<f64, f64> for Poisson {
+    /// Calculates the characteristic function for the poisson
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(λ(e^(it) - 1))
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn cf(&self, t: f64) -> Complex<f64> {
+        (exp_m1_i(t) * self.lambda).exp()
+    }
+}
+
+/// Computes `e^(it) - 1` without cancellation for small `t`
+pub(crate) fn exp_m1_i(t: f64) -> Complex<f64> {
+    let s = fp::sin(0.5 * t);
+    Complex::new(-2.0 * s * s, fp::sin(t))
+}
+
+impl KullbackLeibler for Poisson {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// poisson distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// λ_p ln(λ_p / λ_q) + λ_q - λ_p
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn kl_divergence(&self, other: &Poisson) -> f64 {
+        let r = other.lambda / self.lambda;
+        self.lambda * (r - 1.0 - fp::ln(r))
+    }
+}
+
+impl Parameterized for Poisson {
+    fn param_names() -> &'static [&'static str] {
+        &["lambda"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.lambda]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Poisson> {
+        match *params {
+            [lambda] => Poisson::new(lambda),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for Poisson {
+    type Sample = u64;
+
+    /// Fits the rate as the sample mean, which must be positive
+    fn fit_mle(data: &[u64]) -> Result<Poisson> {
+        Poisson::new(fit::count_mean(data)?)
+    }
+}
+
+/// The result of `dispersion_test`, the score test of a Poisson model of
+/// counts against the overdispersed negative binomial model
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DispersionTest {
+    statistic: f64,
+    p_value: f64,
+}
+
+impl DispersionTest {
+    /// Returns the score statistic, which is asymptotically standard
+    /// normal for Poisson counts and large for overdispersed counts
+    pub fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    /// Returns the one-sided p-value of the null hypothesis that the counts
+    /// are Poisson, against the alternative that they are negative binomial
+    pub fn p_value(&self) -> f64 {
+        self.p_value
+    }
+}
+
+/// Tests whether the counts `data` are overdispersed relative to a Poisson
+/// distribution with the score test of Dean & Lawless (1989) for the
+/// dispersion `α` of a negative binomial model with variance `μ + αμ^2`
+///
+/// # Formula
+///
+/// ```ignore
+/// T = Σ ((x - μ)^2 - x) / (μ √(2n))
+/// ```
+///
+/// where `μ` is the sample mean, the Poisson maximum likelihood estimate.
+/// Under the Poisson null hypothesis `α = 0`, `T` is asymptotically
+/// standard normal, and the p-value is its upper tail probability.
+///
+/// # Remarks
+///
+/// A small p-value favours the negative binomial model, to be fitted with
+/// `NegativeBinomial::fit_mle`, and a large one the Poisson model, to be
+/// fitted with `Poisson::fit_mle`. The score test only requires the fit
+/// under the null hypothesis, so it exists for underdispersed samples as
+/// well, for which the statistic is negative.
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two observations or only
+/// zeros
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::dispersion_test;
+///
+/// let claims = [0, 0, 3, 0, 1, 7, 0, 0, 2, 0, 5, 0, 0, 1, 9, 0];
+/// assert!(dispersion_test(&claims).unwrap().p_value() < 0.01);
+/// let arrivals = [3, 4, 2, 5, 3, 3, 4, 1, 3, 4, 2, 5];
+/// assert!(dispersion_test(&arrivals).unwrap().p_value() > 0.5);
+/// ```
+pub fn dispersion_test(data: &[u64]) -> Result<DispersionTest> {
+    let mean = fit::count_mean(data)?;
+    if data.len() < 2 || mean == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let n = data.len() as f64;
+    let score = data
+        .iter()
+        .map(|&x| {
+            let x = x as f64;
+            (x - mean) * (x - mean) - x
+        })
+        .sum::<f64>();
+    let statistic = score / (mean * (2.0 * n).sqrt());
+    Ok(DispersionTest {
+        statistic,
+        p_value: 0.5 * erf::erfc(statistic / f64::consts::SQRT_2),
+    })
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use std::fmt::Debug;
     use crate::statistics::*;
-    use crate::distribution::{DiscreteCDF, Discrete, Poisson};
+    use crate::distribution::{CharacteristicFunction, DiscreteCDF, Discrete, Poisson};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -443,9 +691,62 @@ mod tests {
         test_almost(10.8, 0.9961800769608090000000, 1e-15, cdf(20));
     }
 
+    #[test]
+    fn test_sf() {
+        let sf = |arg: u64| move |x: Poisson| x.sf(arg);
+        test_almost(1.5, 1.0 - 0.5578254003710750000000, 1e-15, sf(1));
+        test_almost(1.5, 0.004455980775247849, 1e-16, sf(5));
+        test_almost(1.5, 2.3372210700347163e-17, 1e-29, sf(20));
+    }
+
     #[test]
     fn test_discrete() {
         test::check_discrete_distribution(&try_create(0.3), 10);
         test::check_discrete_distribution(&try_create(4.5), 30);
     }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(4.0).cf(0.3);
+        assert_almost_eq!(phi.re, 0.31699383001719655, 1e-14);
+        assert_almost_eq!(phi.im, 0.7739973203225409, 1e-14);
+    }
+
+    #[test]
+    fn test_sample() {
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(1);
+        // both sides of the switch to the transformed rejection method and
+        // rates far beyond the reach of the multiplication method
+        for &lambda in &[9.5, 10.0, 37.2, 1e4, 1e9] {
+            let n = try_create(lambda);
+            let x: Vec<f64> = (0..40000).map(|_| n.sample(&mut rng)).collect();
+            assert!(x.iter().all(|&x| x >= 0.0 && x.fract() == 0.0));
+            let mean = x.iter().sum::<f64>() / 40000.0;
+            let var = x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 39999.0;
+            assert!((mean - lambda).abs() < 4.0 * (lambda / 40000.0).sqrt());
+            assert!((var / lambda - 1.0).abs() < 0.03);
+        }
+        // the frequencies match the pmf
+        let n = try_create(12.5);
+        let mut counts = [0.0f64; 40];
+        for _ in 0..100000 {
+            counts[(n.sample(&mut rng) as usize).min(39)] += 1.0;
+        }
+        for (k, &c) in counts.iter().enumerate().take(30) {
+            let expected = 100000.0 * n.pmf(k as u64);
+            assert!((c - expected).abs() < 5.0 * expected.sqrt() + 5.0);
+        }
+    }
+
+    #[test]
+    fn test_cumulant() {
+        let n = try_create(4.0);
+        assert_eq!(n.cumulant(0).unwrap(), 0.0);
+        assert_eq!(n.cumulant(1).unwrap(), 4.0);
+        assert_eq!(n.cumulant(5).unwrap(), 4.0);
+    }
 }
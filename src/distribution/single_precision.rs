@@ -0,0 +1,209 @@
+use crate::distribution::{Continuous, ContinuousCDF};
+use crate::statistics::*;
+use rand::Rng;
+
+/// Provides single precision evaluation and sampling of a continuous
+/// distribution, implementing `Continuous<f32, f32>`,
+/// `ContinuousCDF<f32, f32>`, the statistics traits over `f32` and
+/// `rand`'s `Distribution<f32>` for any distribution implementing their
+/// `f64` counterparts
+///
+/// # Remarks
+///
+/// Every quantity is computed by the wrapped distribution in double
+/// precision and rounded to the nearest `f32`, so the results are as
+/// accurate as single precision allows, and samples are the rounded
+/// samples of the wrapped distribution. The `f32` implementations are not
+/// added to the distributions themselves since float literals and sampled
+/// values would then no longer infer to `f64`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ContinuousCDF, Normal, SinglePrecision};
+/// use rand::distributions::Distribution;
+///
+/// let n = SinglePrecision::new(Normal::new(0.0, 1.0).unwrap());
+/// let x: f32 = n.sample(&mut rand::thread_rng());
+/// assert!(n.pdf(x) > 0.0);
+/// assert_eq!(n.cdf(0.0f32), 0.5f32);
+/// assert!((n.inverse_cdf(0.975f32) - 1.959964f32).abs() < 1e-6);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SinglePrecision<D> {
+    inner: D,
+}
+
+impl<D> SinglePrecision<D> {
+    /// Constructs a single precision view of `inner`
+    pub fn new(inner: D) -> SinglePrecision<D> {
+        SinglePrecision { inner }
+    }
+
+    /// Returns the wrapped distribution
+    pub fn inner(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns the wrapped distribution, consuming the wrapper
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl<D> From<D> for SinglePrecision<D> {
+    fn from(inner: D) -> SinglePrecision<D> {
+        SinglePrecision::new(inner)
+    }
+}
+
+impl<D: ::rand::distributions::Distribution<f64>> ::rand::distributions::Distribution<f32>
+    for SinglePrecision<D>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        self.inner.sample(rng) as f32
+    }
+}
+
+impl<D: Continuous<f64, f64>> Continuous<f32, f32> for SinglePrecision<D> {
+    fn pdf(&self, x: f32) -> f32 {
+        self.inner.pdf(x as f64) as f32
+    }
+
+    fn ln_pdf(&self, x: f32) -> f32 {
+        self.inner.ln_pdf(x as f64) as f32
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f32, f32> for SinglePrecision<D> {
+    fn cdf(&self, x: f32) -> f32 {
+        self.inner.cdf(x as f64) as f32
+    }
+
+    fn ln_cdf(&self, x: f32) -> f32 {
+        self.inner.ln_cdf(x as f64) as f32
+    }
+
+    fn sf(&self, x: f32) -> f32 {
+        self.inner.sf(x as f64) as f32
+    }
+
+    fn ln_sf(&self, x: f32) -> f32 {
+        self.inner.ln_sf(x as f64) as f32
+    }
+
+    fn prob_between(&self, a: f32, b: f32) -> f32 {
+        self.inner.prob_between(a as f64, b as f64) as f32
+    }
+
+    fn inverse_cdf(&self, p: f32) -> f32 {
+        self.inner.inverse_cdf(p as f64) as f32
+    }
+}
+
+impl<D: Min<f64>> Min<f32> for SinglePrecision<D> {
+    fn min(&self) -> f32 {
+        self.inner.min() as f32
+    }
+}
+
+impl<D: Max<f64>> Max<f32> for SinglePrecision<D> {
+    fn max(&self) -> f32 {
+        self.inner.max() as f32
+    }
+}
+
+impl<D: Distribution<f64>> Distribution<f32> for SinglePrecision<D> {
+    fn mean(&self) -> Option<f32> {
+        self.inner.mean().map(|x| x as f32)
+    }
+
+    fn variance(&self) -> Option<f32> {
+        self.inner.variance().map(|x| x as f32)
+    }
+
+    fn std_dev(&self) -> Option<f32> {
+        self.inner.std_dev().map(|x| x as f32)
+    }
+
+    fn entropy(&self) -> Option<f32> {
+        self.inner.entropy().map(|x| x as f32)
+    }
+
+    fn skewness(&self) -> Option<f32> {
+        self.inner.skewness().map(|x| x as f32)
+    }
+}
+
+impl<D: Median<f64>> Median<f32> for SinglePrecision<D> {
+    fn median(&self) -> f32 {
+        self.inner.median() as f32
+    }
+}
+
+impl<D: Mode<Option<f64>>> Mode<Option<f32>> for SinglePrecision<D> {
+    fn mode(&self) -> Option<f32> {
+        self.inner.mode().map(|x| x as f32)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::{Beta, Exp, Gamma, Normal, StudentsT};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn check_rounded<D>(dist: D, xs: &[f64])
+    where
+        D: Continuous<f64, f64> + ContinuousCDF<f64, f64> + Copy,
+    {
+        let single = SinglePrecision::new(dist);
+        for &x in xs {
+            let xf = x as f32;
+            assert_eq!(single.pdf(xf), dist.pdf(xf as f64) as f32);
+            assert_eq!(single.ln_pdf(xf), dist.ln_pdf(xf as f64) as f32);
+            assert_eq!(single.cdf(xf), dist.cdf(xf as f64) as f32);
+            assert_eq!(single.sf(xf), dist.sf(xf as f64) as f32);
+        }
+        for &p in &[0.01f32, 0.25, 0.5, 0.9] {
+            assert_eq!(single.inverse_cdf(p), dist.inverse_cdf(p as f64) as f32);
+        }
+    }
+
+    #[test]
+    fn test_rounded() {
+        check_rounded(Normal::new(1.0, 2.0).unwrap(), &[-3.0, 0.0, 1.5, 7.0]);
+        check_rounded(Gamma::new(2.5, 0.5).unwrap(), &[0.1, 1.0, 4.0, 20.0]);
+        check_rounded(Beta::new(2.0, 3.0).unwrap(), &[0.05, 0.3, 0.9]);
+        check_rounded(StudentsT::new(0.0, 1.0, 4.0).unwrap(), &[-10.0, 0.2, 3.0]);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let n = SinglePrecision::new(Gamma::new(3.0, 2.0).unwrap());
+        assert_eq!(n.mean().unwrap(), 1.5f32);
+        assert_eq!(n.variance().unwrap(), 0.75f32);
+        assert_eq!(n.min(), 0.0f32);
+        assert_eq!(n.max(), f32::INFINITY);
+        assert_eq!(n.mode(), Some(1.0f32));
+        assert_eq!(n.inner(), &Gamma::new(3.0, 2.0).unwrap());
+        let e: SinglePrecision<Exp> = Exp::new(2.0).unwrap().into();
+        assert_eq!(e.median(), (2f64.ln() / 2.0) as f32);
+        assert_eq!(e.into_inner(), Exp::new(2.0).unwrap());
+    }
+
+    #[test]
+    fn test_sample() {
+        let dist = Normal::new(3.0, 0.5).unwrap();
+        let single = SinglePrecision::new(dist);
+        let mut a = StdRng::seed_from_u64(1);
+        let mut b = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let x: f32 = ::rand::distributions::Distribution::sample(&single, &mut a);
+            let y: f64 = ::rand::distributions::Distribution::sample(&dist, &mut b);
+            assert_eq!(x, y as f32);
+        }
+    }
+}
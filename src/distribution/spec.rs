@@ -0,0 +1,414 @@
+use crate::distribution::dynamic::{DynContinuous, DynDiscrete};
+use crate::distribution::{
+    Bernoulli, Beta, Binomial, Cauchy, Chi, ChiSquared, Discrete, DiscreteCDF, DiscreteGaussian,
+    DiscreteLaplace, DiscreteUniform, Erlang, Exp, FisherSnedecor, Gamma, Geometric,
+    Hypergeometric, InverseGamma, Laplace, LogNormal, NegativeBinomial, Normal, Parameterized,
+    Pareto, Poisson, StudentsT, Triangular, Uniform, Weibull,
+};
+use crate::StatsError;
+use num_traits::AsPrimitive;
+use rand::distributions::Distribution;
+use rand::RngCore;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// A univariate distribution whose family is chosen at runtime, as parsed
+/// from a spec string such as `"Normal(0, 1)"` or `"Beta(alpha=2, beta=5)"`
+///
+/// # Remarks
+///
+/// A spec is the name of a distribution followed by its parameters in
+/// parentheses, in the order of its constructor. Parameters may also be
+/// given by name, after any positional ones, using the names reported by
+/// `Parameterized::param_names`. Family names are case-insensitive and
+/// `Exponential`, `Student`/`StudentT` and `Beta(alpha, beta)` are accepted
+/// as aliases. The parameters are validated by the constructor of the
+/// family.
+///
+/// Discrete families over non-negative integers are widened to `i64`, so
+/// that all discrete families share one representation.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::DynDistribution;
+///
+/// let d: DynDistribution = "Triangular(0, 10, 3)".parse().unwrap();
+/// assert_eq!(d.as_continuous().unwrap().cdf(3.0), 0.3);
+///
+/// let d: DynDistribution = "Binomial(p = 0.5, n = 4)".parse().unwrap();
+/// assert_eq!(d.as_discrete().unwrap().pmf(2), 0.375);
+///
+/// let err = "Normal(0)".parse::<DynDistribution>().unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "Normal takes 2 parameters (mean, std_dev), found 1"
+/// );
+/// ```
+pub enum DynDistribution {
+    /// A continuous distribution
+    Continuous(Box<dyn DynContinuous + Send + Sync>),
+    /// A discrete distribution
+    Discrete(Box<dyn DynDiscrete<i64> + Send + Sync>),
+}
+
+impl DynDistribution {
+    /// Returns the distribution if it is continuous
+    pub fn as_continuous(&self) -> Option<&(dyn DynContinuous + Send + Sync)> {
+        match self {
+            DynDistribution::Continuous(d) => Some(d.as_ref()),
+            DynDistribution::Discrete(_) => None,
+        }
+    }
+
+    /// Returns the distribution if it is discrete
+    pub fn as_discrete(&self) -> Option<&(dyn DynDiscrete<i64> + Send + Sync)> {
+        match self {
+            DynDistribution::Continuous(_) => None,
+            DynDistribution::Discrete(d) => Some(d.as_ref()),
+        }
+    }
+
+    /// Draws a sample from the distribution using `rng`
+    pub fn sample(&self, rng: &mut dyn RngCore) -> f64 {
+        match self {
+            DynDistribution::Continuous(d) => d.sample(rng),
+            DynDistribution::Discrete(d) => d.sample(rng),
+        }
+    }
+}
+
+impl fmt::Debug for DynDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DynDistribution::Continuous(_) => write!(f, "DynDistribution::Continuous(..)"),
+            DynDistribution::Discrete(_) => write!(f, "DynDistribution::Discrete(..)"),
+        }
+    }
+}
+
+/// Enumeration of possible errors when parsing a `DynDistribution`
+#[derive(Debug)]
+pub enum ParseDistributionError {
+    /// The spec is not of the form `Name(arg, ...)`
+    Syntax(String),
+    /// The distribution family is not known
+    UnknownFamily(String),
+    /// A parameter value is not a number
+    InvalidNumber(&'static str, String),
+    /// A parameter name is not a parameter of the family
+    UnknownParam(&'static str, String, &'static [&'static str]),
+    /// A parameter was given more than once
+    DuplicateParam(&'static str, &'static str),
+    /// A positional parameter followed a named one
+    PositionalAfterNamed(&'static str),
+    /// The number of parameters is wrong
+    ParamCount(&'static str, &'static [&'static str], usize),
+    /// The parameters were rejected by the constructor of the family
+    InvalidParams(&'static str, StatsError),
+}
+
+impl Error for ParseDistributionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseDistributionError::InvalidParams(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseDistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseDistributionError::Syntax(s) => {
+                write!(f, "Invalid distribution `{}`, expected `Name(arg, ...)`", s)
+            }
+            ParseDistributionError::UnknownFamily(s) => write!(f, "Unknown distribution `{}`", s),
+            ParseDistributionError::InvalidNumber(family, s) => {
+                write!(f, "Invalid number `{}` in {} parameters", s, family)
+            }
+            ParseDistributionError::UnknownParam(family, s, names) => write!(
+                f,
+                "{} has no parameter `{}`, expected one of {}",
+                family,
+                s,
+                names.join(", ")
+            ),
+            ParseDistributionError::DuplicateParam(family, s) => {
+                write!(f, "Parameter `{}` of {} given more than once", s, family)
+            }
+            ParseDistributionError::PositionalAfterNamed(family) => write!(
+                f,
+                "Positional parameters of {} must precede named ones",
+                family
+            ),
+            ParseDistributionError::ParamCount(family, names, found) => write!(
+                f,
+                "{} takes {} parameters ({}), found {}",
+                family,
+                names.len(),
+                names.join(", "),
+                found
+            ),
+            ParseDistributionError::InvalidParams(family, e) => {
+                write!(f, "Invalid {} parameters: {}", family, e)
+            }
+        }
+    }
+}
+
+impl FromStr for DynDistribution {
+    type Err = ParseDistributionError;
+
+    fn from_str(s: &str) -> Result<DynDistribution, ParseDistributionError> {
+        let syntax = || ParseDistributionError::Syntax(s.trim().to_string());
+        let (name, rest) = s.trim().split_once('(').ok_or_else(syntax)?;
+        let args = rest.strip_suffix(')').ok_or_else(syntax)?;
+        let name = name.trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(syntax());
+        }
+        let args: Vec<&str> = if args.trim().is_empty() {
+            Vec::new()
+        } else {
+            args.split(',').map(str::trim).collect()
+        };
+        if args.iter().any(|a| a.is_empty()) {
+            return Err(syntax());
+        }
+        match name.to_ascii_lowercase().as_str() {
+            "bernoulli" => discrete::<Bernoulli, f64>("Bernoulli", &args),
+            "beta" => continuous::<Beta>("Beta", &args),
+            "binomial" => discrete::<Binomial, f64>("Binomial", &args),
+            "cauchy" => continuous::<Cauchy>("Cauchy", &args),
+            "chi" => continuous::<Chi>("Chi", &args),
+            "chisquared" => continuous::<ChiSquared>("ChiSquared", &args),
+            "discretegaussian" => signed::<DiscreteGaussian>("DiscreteGaussian", &args),
+            "discretelaplace" => signed::<DiscreteLaplace>("DiscreteLaplace", &args),
+            "discreteuniform" => signed::<DiscreteUniform>("DiscreteUniform", &args),
+            "erlang" => continuous::<Erlang>("Erlang", &args),
+            "exp" | "exponential" => continuous::<Exp>("Exp", &args),
+            "fishersnedecor" => continuous::<FisherSnedecor>("FisherSnedecor", &args),
+            "gamma" => continuous::<Gamma>("Gamma", &args),
+            "geometric" => discrete::<Geometric, f64>("Geometric", &args),
+            "hypergeometric" => discrete::<Hypergeometric, f64>("Hypergeometric", &args),
+            "inversegamma" => continuous::<InverseGamma>("InverseGamma", &args),
+            "laplace" => continuous::<Laplace>("Laplace", &args),
+            "lognormal" => continuous::<LogNormal>("LogNormal", &args),
+            "negativebinomial" => discrete::<NegativeBinomial, u64>("NegativeBinomial", &args),
+            "normal" => continuous::<Normal>("Normal", &args),
+            "pareto" => continuous::<Pareto>("Pareto", &args),
+            "poisson" => discrete::<Poisson, f64>("Poisson", &args),
+            "student" | "studentt" | "studentst" => continuous::<StudentsT>("StudentsT", &args),
+            "triangular" => continuous::<Triangular>("Triangular", &args),
+            "uniform" => continuous::<Uniform>("Uniform", &args),
+            "weibull" => continuous::<Weibull>("Weibull", &args),
+            _ => Err(ParseDistributionError::UnknownFamily(name.to_string())),
+        }
+    }
+}
+
+/// Returns the canonical name of the parameter `key` of `family`, accepting
+/// the conventional names of the shape parameters of the beta distribution
+fn canonical_name(family: &str, key: &str) -> String {
+    match (family, key) {
+        ("Beta", "alpha") | ("Beta", "a") => "shape_a".to_string(),
+        ("Beta", "beta") | ("Beta", "b") => "shape_b".to_string(),
+        _ => key.to_string(),
+    }
+}
+
+/// Binds positional and named `args` to the parameters `names` of `family`
+fn bind(
+    family: &'static str,
+    names: &'static [&'static str],
+    args: &[&str],
+) -> Result<Vec<f64>, ParseDistributionError> {
+    let mut params = vec![None; names.len()];
+    let mut named = false;
+    for (i, arg) in args.iter().enumerate() {
+        let (index, value) = match arg.split_once('=') {
+            Some((key, value)) => {
+                named = true;
+                let key = canonical_name(family, key.trim());
+                let index = names.iter().position(|&n| n == key).ok_or_else(|| {
+                    ParseDistributionError::UnknownParam(family, key.clone(), names)
+                })?;
+                (index, value.trim())
+            }
+            None if named => return Err(ParseDistributionError::PositionalAfterNamed(family)),
+            None if i < names.len() => (i, *arg),
+            None => {
+                return Err(ParseDistributionError::ParamCount(
+                    family,
+                    names,
+                    args.len(),
+                ))
+            }
+        };
+        let value = value
+            .parse::<f64>()
+            .map_err(|_| ParseDistributionError::InvalidNumber(family, value.to_string()))?;
+        if params[index].replace(value).is_some() {
+            return Err(ParseDistributionError::DuplicateParam(family, names[index]));
+        }
+    }
+    params
+        .into_iter()
+        .collect::<Option<Vec<f64>>>()
+        .ok_or(ParseDistributionError::ParamCount(
+            family,
+            names,
+            args.len(),
+        ))
+}
+
+fn build<D: Parameterized>(
+    family: &'static str,
+    args: &[&str],
+) -> Result<D, ParseDistributionError> {
+    let params = bind(family, D::param_names(), args)?;
+    D::from_params(&params).map_err(|e| ParseDistributionError::InvalidParams(family, e))
+}
+
+fn continuous<D>(
+    family: &'static str,
+    args: &[&str],
+) -> Result<DynDistribution, ParseDistributionError>
+where
+    D: Parameterized + DynContinuous + Send + Sync + 'static,
+{
+    let d = build::<D>(family, args)?;
+    Ok(DynDistribution::Continuous(Box::new(d)))
+}
+
+fn signed<D>(family: &'static str, args: &[&str]) -> Result<DynDistribution, ParseDistributionError>
+where
+    D: Parameterized + DynDiscrete<i64> + Send + Sync + 'static,
+{
+    let d = build::<D>(family, args)?;
+    Ok(DynDistribution::Discrete(Box::new(d)))
+}
+
+fn discrete<D, S>(
+    family: &'static str,
+    args: &[&str],
+) -> Result<DynDistribution, ParseDistributionError>
+where
+    D: Parameterized + Discrete<u64, f64> + DiscreteCDF<u64, f64> + Distribution<S>,
+    D: Send + Sync + 'static,
+    S: AsPrimitive<f64>,
+{
+    let d = build::<D>(family, args)?;
+    Ok(DynDistribution::Discrete(Box::new(NonNegative(
+        d,
+        PhantomData,
+    ))))
+}
+
+/// Widens a distribution over non-negative integers, sampling values of
+/// type `S`, to one over `i64`
+struct NonNegative<D, S>(D, PhantomData<fn() -> S>);
+
+impl<D, S> DynDiscrete<i64> for NonNegative<D, S>
+where
+    D: Discrete<u64, f64> + DiscreteCDF<u64, f64> + Distribution<S>,
+    S: AsPrimitive<f64>,
+{
+    fn pmf(&self, x: i64) -> f64 {
+        u64::try_from(x).map_or(0.0, |x| self.0.pmf(x))
+    }
+
+    fn ln_pmf(&self, x: i64) -> f64 {
+        u64::try_from(x).map_or(f64::NEG_INFINITY, |x| self.0.ln_pmf(x))
+    }
+
+    fn cdf(&self, x: i64) -> f64 {
+        u64::try_from(x).map_or(0.0, |x| self.0.cdf(x))
+    }
+
+    fn sf(&self, x: i64) -> f64 {
+        u64::try_from(x).map_or(1.0, |x| self.0.sf(x))
+    }
+
+    fn inverse_cdf(&self, p: f64) -> i64 {
+        i64::try_from(self.0.inverse_cdf(p)).unwrap_or(i64::MAX)
+    }
+
+    fn sample(&self, rng: &mut dyn RngCore) -> f64 {
+        self.0.sample(rng).as_()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::{Continuous, ContinuousCDF};
+
+    fn parse(s: &str) -> DynDistribution {
+        s.parse().unwrap()
+    }
+
+    fn parse_err(s: &str) -> String {
+        s.parse::<DynDistribution>().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse_continuous() {
+        let d = parse("Normal(0, 1)");
+        let d = d.as_continuous().unwrap();
+        assert_eq!(d.pdf(0.5), Continuous::pdf(&Normal::new(0.0, 1.0).unwrap(), 0.5));
+        let beta = Beta::new(2.0, 5.0).unwrap();
+        for s in &["Beta(2, 5)", "Beta(alpha=2, beta=5)", "beta( beta = 5 , alpha = 2 )", "Beta(2, shape_b=5)"] {
+            assert_eq!(parse(s).as_continuous().unwrap().cdf(0.3), ContinuousCDF::cdf(&beta, 0.3));
+        }
+        let t = parse("Triangular(0, 10, 3)");
+        assert_eq!(t.as_continuous().unwrap().inverse_cdf(0.3), 3.0);
+        assert!(t.as_discrete().is_none());
+        assert_eq!(parse("Exponential(2)").as_continuous().unwrap().sf(1.0), (-2.0f64).exp());
+        assert_eq!(parse("StudentT(0, 1, inf)").as_continuous().unwrap().cdf(0.0), 0.5);
+        assert_eq!(parse("Uniform(-1e3, 1E3)").as_continuous().unwrap().cdf(0.0), 0.5);
+    }
+
+    #[test]
+    fn test_parse_discrete() {
+        let d = parse("Poisson(3)");
+        let d = d.as_discrete().unwrap();
+        let p = Poisson::new(3.0).unwrap();
+        assert_eq!(d.pmf(2), Discrete::pmf(&p, 2));
+        assert_eq!(d.pmf(-1), 0.0);
+        assert_eq!(d.ln_pmf(-1), f64::NEG_INFINITY);
+        assert_eq!(d.cdf(-1), 0.0);
+        assert_eq!(d.sf(-1), 1.0);
+        assert_eq!(d.inverse_cdf(0.5), 3);
+        let u = parse("DiscreteUniform(-3, 3)");
+        assert_eq!(u.as_discrete().unwrap().cdf(-1), 3.0 / 7.0);
+        assert!(u.as_continuous().is_none());
+        assert_eq!(parse("Hypergeometric(draws=2, population=10, successes=5)").as_discrete().unwrap().pmf(1), 5.0 / 9.0);
+        let mut rng = rand::thread_rng();
+        assert!((0.0..=4.0).contains(&parse("Binomial(0.5, 4)").sample(&mut rng)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_err("Normal 0, 1"), "Invalid distribution `Normal 0, 1`, expected `Name(arg, ...)`");
+        assert_eq!(parse_err("Normal(0, 1"), "Invalid distribution `Normal(0, 1`, expected `Name(arg, ...)`");
+        assert_eq!(parse_err("(0, 1)"), "Invalid distribution `(0, 1)`, expected `Name(arg, ...)`");
+        assert_eq!(parse_err("Normal(0,, 1)"), "Invalid distribution `Normal(0,, 1)`, expected `Name(arg, ...)`");
+        assert_eq!(parse_err("Gaussian(0, 1)"), "Unknown distribution `Gaussian`");
+        assert_eq!(parse_err("Normal(0, one)"), "Invalid number `one` in Normal parameters");
+        assert_eq!(parse_err("Normal(0, sigma=1)"), "Normal has no parameter `sigma`, expected one of mean, std_dev");
+        assert_eq!(parse_err("Normal(0, mean=1)"), "Parameter `mean` of Normal given more than once");
+        assert_eq!(parse_err("Normal(mean=0, 1)"), "Positional parameters of Normal must precede named ones");
+        assert_eq!(parse_err("Normal(0, 1, 2)"), "Normal takes 2 parameters (mean, std_dev), found 3");
+        assert_eq!(parse_err("Normal()"), "Normal takes 2 parameters (mean, std_dev), found 0");
+        assert_eq!(parse_err("Normal(0, -1)"), "Invalid Normal parameters: Bad distribution parameters");
+        assert_eq!(parse_err("Binomial(0.5, 2.5)"), "Invalid Binomial parameters: Bad distribution parameters");
+        let err = "Normal(0, -1)".parse::<DynDistribution>().unwrap_err();
+        assert!(err.source().is_some());
+    }
+}
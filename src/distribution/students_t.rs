@@ -1,4 +1,5 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
+use crate::fp;
 use crate::function::{beta, gamma};
 use crate::is_zero;
 use crate::statistics::*;
@@ -61,6 +62,26 @@ impl StudentsT {
         }
     }
 
+    /// Constructs a new student's t distribution from parameters already known
+    /// to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(location: f64, scale: f64, freedom: f64) -> StudentsT {
+        debug_assert!(
+            StudentsT::new(location, scale, freedom).is_ok(),
+            "invalid student's t parameters"
+        );
+        StudentsT {
+            location,
+            scale,
+            freedom,
+        }
+    }
+
     /// Returns the location of the student's t-distribution
     ///
     /// # Examples
@@ -264,10 +285,10 @@ impl Distribution<f64> for StudentsT {
         // generalised Student's T is related to normal Student's T by `Y = μ + σ X`
         // where `X` is distributed as Student's T, plugging into the definition
         // of entropy shows scaling affects the entropy by an additive constant `- ln σ`
-        let shift = -self.scale.ln();
+        let shift = -fp::ln(self.scale);
         let result = (self.freedom + 1.0) / 2.0
             * (gamma::digamma((self.freedom + 1.0) / 2.0) - gamma::digamma(self.freedom / 2.0))
-            + (self.freedom.sqrt() * beta::beta(self.freedom / 2.0, 0.5)).ln();
+            + fp::ln(self.freedom.sqrt() * beta::beta(self.freedom / 2.0, 0.5));
         Some(result + shift)
     }
     /// Returns the skewness of the student's t-distribution
@@ -342,9 +363,10 @@ impl Continuous<f64, f64> for StudentsT {
             super::normal::pdf_unchecked(x, self.location, self.scale)
         } else {
             let d = (x - self.location) / self.scale;
-            (gamma::ln_gamma((self.freedom + 1.0) / 2.0) - gamma::ln_gamma(self.freedom / 2.0))
-                .exp()
-                * (1.0 + d * d / self.freedom).powf(-0.5 * (self.freedom + 1.0))
+            fp::exp(
+                gamma::ln_gamma((self.freedom + 1.0) / 2.0) - gamma::ln_gamma(self.freedom / 2.0),
+            )
+                * fp::powf(1.0 + d * d / self.freedom, -0.5 * (self.freedom + 1.0))
                 / (self.freedom * f64::consts::PI).sqrt()
                 / self.scale
         }
@@ -372,10 +394,27 @@ impl Continuous<f64, f64> for StudentsT {
         } else {
             let d = (x - self.location) / self.scale;
             gamma::ln_gamma((self.freedom + 1.0) / 2.0)
-                - 0.5 * ((self.freedom + 1.0) * (1.0 + d * d / self.freedom).ln())
+                - 0.5 * ((self.freedom + 1.0) * fp::ln(1.0 + d * d / self.freedom))
                 - gamma::ln_gamma(self.freedom / 2.0)
-                - 0.5 * (self.freedom * f64::consts::PI).ln()
-                - self.scale.ln()
+                - 0.5 * fp::ln(self.freedom * f64::consts::PI)
+                - fp::ln(self.scale)
+        }
+    }
+}
+
+impl Parameterized for StudentsT {
+    fn param_names() -> &'static [&'static str] {
+        &["location", "scale", "freedom"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.location, self.scale, self.freedom]
+    }
+
+    fn from_params(params: &[f64]) -> Result<StudentsT> {
+        match *params {
+            [location, scale, freedom] => StudentsT::new(location, scale, freedom),
+            _ => Err(StatsError::ContainersMustBeSameLength),
         }
     }
 }
@@ -0,0 +1,376 @@
+use crate::distribution::discretize::tanh_sinh;
+use crate::distribution::{Continuous, ContinuousCDF};
+use crate::fp;
+use crate::statistics::*;
+use rand::Rng;
+use std::f64;
+
+/// Implements the distribution of `g(X)` for a strictly monotone,
+/// differentiable transform `g` and a continuous random variable `X`
+/// distributed according to the parent
+///
+/// The transform is given by the closures `forward` computing `g(x)`,
+/// `inverse` computing `g^-1(y)` and `ln_jacobian` computing
+/// `ln |d g^-1(y) / dy|`, the log absolute derivative of the inverse, which
+/// is needed for the density.
+///
+/// # Remarks
+///
+/// The cdf, quantiles and median follow exactly from the parent, while
+/// moments are computed by tanh-sinh quadrature of `g(F^-1(u))` over
+/// `(0, 1)`. The closures must handle the bounds of the support of the
+/// parent, which may be infinite, and the density is only meaningful on the
+/// image of `g`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ContinuousCDF, LogNormal, Normal, Transformed};
+///
+/// // the exponential of a normal variable is log-normal
+/// let n = Normal::new(0.5, 0.8).unwrap();
+/// let t = Transformed::increasing(n, f64::exp, f64::ln, |y: f64| -y.ln());
+/// let ln = LogNormal::new(0.5, 0.8).unwrap();
+/// assert!((t.pdf(2.0) - ln.pdf(2.0)).abs() < 1e-15);
+/// assert!((t.cdf(2.0) - ln.cdf(2.0)).abs() < 1e-15);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Transformed<D, F, G, J> {
+    parent: D,
+    forward: F,
+    inverse: G,
+    ln_jacobian: J,
+    increasing: bool,
+}
+
+impl<D, F, G, J> Transformed<D, F, G, J>
+where
+    F: Fn(f64) -> f64,
+    G: Fn(f64) -> f64,
+    J: Fn(f64) -> f64,
+{
+    /// Constructs the distribution of `forward(X)` for a strictly
+    /// increasing transform `forward` with inverse `inverse` and log
+    /// absolute derivative of the inverse `ln_jacobian`, where `X` is
+    /// distributed according to `parent`
+    pub fn increasing(parent: D, forward: F, inverse: G, ln_jacobian: J) -> Self {
+        Transformed {
+            parent,
+            forward,
+            inverse,
+            ln_jacobian,
+            increasing: true,
+        }
+    }
+
+    /// Constructs the distribution of `forward(X)` for a strictly
+    /// decreasing transform `forward` with inverse `inverse` and log
+    /// absolute derivative of the inverse `ln_jacobian`, where `X` is
+    /// distributed according to `parent`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{ContinuousCDF, Exp, Transformed};
+    /// use statrs::statistics::Min;
+    ///
+    /// // the reciprocal of an exponential variable
+    /// let e = Exp::new(1.0).unwrap();
+    /// let t = Transformed::decreasing(e, |x: f64| 1.0 / x, |y: f64| 1.0 / y, |y: f64| -2.0 * y.ln());
+    /// assert!((t.cdf(2.0) - e.sf(0.5)).abs() < 1e-15);
+    /// assert_eq!(t.min(), 0.0);
+    /// ```
+    pub fn decreasing(parent: D, forward: F, inverse: G, ln_jacobian: J) -> Self {
+        Transformed {
+            parent,
+            forward,
+            inverse,
+            ln_jacobian,
+            increasing: false,
+        }
+    }
+
+    /// Returns the parent distribution
+    pub fn parent(&self) -> &D {
+        &self.parent
+    }
+
+    /// Returns `true` if the transform is increasing and `false` if it is
+    /// decreasing
+    pub fn is_increasing(&self) -> bool {
+        self.increasing
+    }
+
+    /// Computes `E[h(Y)]` by tanh-sinh quadrature of `h(g(F^-1(u)))` over
+    /// `(0, 1)`
+    fn expectation<H: Fn(f64) -> f64>(&self, h: H) -> f64
+    where
+        D: ContinuousCDF<f64, f64>,
+    {
+        tanh_sinh()
+            .map(|(t, t_c, w)| {
+                let x = if t <= 0.5 {
+                    self.parent.inverse_cdf(t)
+                } else {
+                    self.parent.inverse_cdf(1.0 - t_c)
+                };
+                w * h((self.forward)(x))
+            })
+            .sum()
+    }
+}
+
+impl<D, F, G, J> ::rand::distributions::Distribution<f64> for Transformed<D, F, G, J>
+where
+    D: ::rand::distributions::Distribution<f64>,
+    F: Fn(f64) -> f64,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        (self.forward)(self.parent.sample(rng))
+    }
+}
+
+impl<D, F, G, J> ContinuousCDF<f64, f64> for Transformed<D, F, G, J>
+where
+    D: ContinuousCDF<f64, f64>,
+    F: Fn(f64) -> f64,
+    G: Fn(f64) -> f64,
+    J: Fn(f64) -> f64,
+{
+    /// Calculates the cumulative distribution function at `y`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F(g^-1(y))    if g is increasing
+    /// S(g^-1(y))    if g is decreasing
+    /// ```
+    ///
+    /// where `F` and `S` are the cdf and survival function of the parent
+    fn cdf(&self, y: f64) -> f64 {
+        let x = (self.inverse)(y);
+        if self.increasing {
+            self.parent.cdf(x)
+        } else {
+            self.parent.sf(x)
+        }
+    }
+
+    /// Calculates the survival function at `y`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// S(g^-1(y))    if g is increasing
+    /// F(g^-1(y))    if g is decreasing
+    /// ```
+    ///
+    /// where `F` and `S` are the cdf and survival function of the parent
+    fn sf(&self, y: f64) -> f64 {
+        let x = (self.inverse)(y);
+        if self.increasing {
+            self.parent.sf(x)
+        } else {
+            self.parent.cdf(x)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// g(F^-1(p))        if g is increasing
+    /// g(F^-1(1 - p))    if g is decreasing
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if self.increasing {
+            (self.forward)(self.parent.inverse_cdf(p))
+        } else {
+            (self.forward)(self.parent.inverse_cdf(1.0 - p))
+        }
+    }
+}
+
+impl<D, F, G, J> Min<f64> for Transformed<D, F, G, J>
+where
+    D: Min<f64> + Max<f64>,
+    F: Fn(f64) -> f64,
+{
+    /// Returns the minimum value in the domain of the distribution, i.e.
+    /// the image of the minimum of the parent if the transform is increasing
+    /// and of its maximum otherwise
+    fn min(&self) -> f64 {
+        if self.increasing {
+            (self.forward)(self.parent.min())
+        } else {
+            (self.forward)(self.parent.max())
+        }
+    }
+}
+
+impl<D, F, G, J> Max<f64> for Transformed<D, F, G, J>
+where
+    D: Min<f64> + Max<f64>,
+    F: Fn(f64) -> f64,
+{
+    /// Returns the maximum value in the domain of the distribution, i.e.
+    /// the image of the maximum of the parent if the transform is increasing
+    /// and of its minimum otherwise
+    fn max(&self) -> f64 {
+        if self.increasing {
+            (self.forward)(self.parent.max())
+        } else {
+            (self.forward)(self.parent.min())
+        }
+    }
+}
+
+impl<D, F, G, J> Distribution<f64> for Transformed<D, F, G, J>
+where
+    D: ContinuousCDF<f64, f64> + ::rand::distributions::Distribution<f64>,
+    F: Fn(f64) -> f64,
+    G: Fn(f64) -> f64,
+    J: Fn(f64) -> f64,
+{
+    /// Returns the mean of the distribution, computed numerically
+    fn mean(&self) -> Option<f64> {
+        Some(self.expectation(|y| y))
+    }
+    /// Returns the variance of the distribution, computed numerically
+    fn variance(&self) -> Option<f64> {
+        self.central_moment(2)
+    }
+    /// Returns the skewness of the distribution, computed numerically
+    fn skewness(&self) -> Option<f64> {
+        Some(self.central_moment(3)? / fp::powf(self.central_moment(2)?, 1.5))
+    }
+    /// Returns the `k`th raw moment of the distribution, computed
+    /// numerically
+    fn moment(&self, k: u32) -> Option<f64> {
+        Some(self.expectation(|y| fp::powi(y, k as i32)))
+    }
+    /// Returns the `k`th central moment of the distribution, computed
+    /// numerically
+    fn central_moment(&self, k: u32) -> Option<f64> {
+        let mean = self.mean()?;
+        Some(self.expectation(|y| fp::powi(y - mean, k as i32)))
+    }
+}
+
+impl<D, F, G, J> Median<f64> for Transformed<D, F, G, J>
+where
+    D: Median<f64>,
+    F: Fn(f64) -> f64,
+{
+    /// Returns the median of the distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// g(m)
+    /// ```
+    ///
+    /// where `m` is the median of the parent
+    fn median(&self) -> f64 {
+        (self.forward)(self.parent.median())
+    }
+}
+
+impl<D, F, G, J> Continuous<f64, f64> for Transformed<D, F, G, J>
+where
+    D: Continuous<f64, f64>,
+    G: Fn(f64) -> f64,
+    J: Fn(f64) -> f64,
+{
+    /// Calculates the probability density function at `y`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// f(g^-1(y)) |d g^-1(y) / dy|
+    /// ```
+    ///
+    /// where `f` is the pdf of the parent
+    fn pdf(&self, y: f64) -> f64 {
+        fp::exp(self.ln_pdf(y))
+    }
+
+    /// Calculates the log probability density function at `y`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(f(g^-1(y))) + ln |d g^-1(y) / dy|
+    /// ```
+    ///
+    /// where `f` is the pdf of the parent
+    fn ln_pdf(&self, y: f64) -> f64 {
+        self.parent.ln_pdf((self.inverse)(y)) + (self.ln_jacobian)(y)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::{Exp, LogNormal, Normal, Uniform};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_increasing() {
+        let n = Normal::new(0.5, 0.8).unwrap();
+        let t = Transformed::increasing(n, f64::exp, f64::ln, |y: f64| -y.ln());
+        let ln = LogNormal::new(0.5, 0.8).unwrap();
+        assert!(t.is_increasing());
+        for &y in &[0.1, 1.0, 1.65, 4.0, 20.0] {
+            assert_almost_eq!(t.pdf(y), ln.pdf(y), 1e-14);
+            assert_almost_eq!(t.ln_pdf(y), ln.ln_pdf(y), 1e-14);
+            assert_almost_eq!(t.cdf(y), ln.cdf(y), 1e-14);
+            assert_almost_eq!(t.sf(y), ln.sf(y), 1e-14);
+        }
+        for &p in &[0.01, 0.5, 0.9] {
+            assert_almost_eq!(t.inverse_cdf(p), ln.inverse_cdf(p), 1e-10);
+        }
+        assert_eq!((t.min(), t.max()), (0.0, f64::INFINITY));
+        assert_almost_eq!(t.median(), 0.5f64.exp(), 1e-15);
+        assert_almost_eq!(t.mean().unwrap(), ln.mean().unwrap(), 1e-11);
+        assert_almost_eq!(t.variance().unwrap(), ln.variance().unwrap(), 1e-8);
+        assert_almost_eq!(t.skewness().unwrap(), ln.skewness().unwrap(), 1e-6);
+    }
+
+    #[test]
+    fn test_decreasing() {
+        // -ln(U) for U ~ Uniform(0, 1) is Exp(1)
+        let u = Uniform::new(0.0, 1.0).unwrap();
+        let t = Transformed::decreasing(u, |x: f64| -x.ln(), |y: f64| (-y).exp(), |y: f64| -y);
+        let e = Exp::new(1.0).unwrap();
+        assert!(!t.is_increasing());
+        for &y in &[0.0, 0.3, 1.0, 5.0] {
+            assert_almost_eq!(t.pdf(y), e.pdf(y), 1e-15);
+            assert_almost_eq!(t.cdf(y), e.cdf(y), 1e-15);
+            assert_almost_eq!(t.sf(y), e.sf(y), 1e-15);
+        }
+        assert_almost_eq!(t.inverse_cdf(0.25), e.inverse_cdf(0.25), 1e-15);
+        assert_eq!((t.min(), t.max()), (0.0, f64::INFINITY));
+        assert_almost_eq!(t.median(), 2f64.ln(), 1e-15);
+        assert_almost_eq!(t.mean().unwrap(), 1.0, 1e-12);
+        assert_almost_eq!(t.variance().unwrap(), 1.0, 1e-11);
+        assert_almost_eq!(t.moment(3).unwrap(), 6.0, 1e-9);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let t = Transformed::increasing(n, |x: f64| x * x * x, f64::cbrt, |y: f64| -(3f64.ln()) - 2.0 / 3.0 * y.abs().ln());
+        let samples: Vec<f64> = (0..20000).map(|_| t.sample(&mut rng)).collect();
+        let below = samples.iter().filter(|&&y| y <= 0.125).count() as f64 / 20000.0;
+        assert!((below - t.cdf(0.125)).abs() < 0.01);
+        assert_almost_eq!(t.pdf(8.0), n.pdf(2.0) / 12.0, 1e-15);
+    }
+}
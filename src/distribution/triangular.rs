@@ -1,6 +1,8 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{CharacteristicFunction, Continuous, ContinuousCDF, Parameterized};
+use crate::fp;
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::Rng;
 use std::f64;
 
@@ -57,11 +59,28 @@ impl Triangular {
         }
         Ok(Triangular { min, max, mode })
     }
+
+    /// Constructs a new triangular distribution from parameters already known
+    /// to be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(min: f64, max: f64, mode: f64) -> Triangular {
+        debug_assert!(
+            Triangular::new(min, max, mode).is_ok(),
+            "invalid triangular parameters"
+        );
+        Triangular { min, max, mode }
+    }
 }
 
 impl ::rand::distributions::Distribution<f64> for Triangular {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        sample_unchecked(rng, self.min, self.max, self.mode)
+        // Inverse transform sampling
+        self.inverse_cdf(rng.gen())
     }
 }
 
@@ -97,6 +116,106 @@ impl ContinuousCDF<f64, f64> for Triangular {
             1.0
         }
     }
+
+    /// Calculates the survival function for the triangular distribution at
+    /// `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if x <= min {
+    ///     1
+    /// } else if min < x <= mode {
+    ///     1 - (x - min)^2 / ((max - min) * (mode - min))
+    /// } else if mode < x < max {
+    ///     (max - x)^2 / ((max - min) * (max - mode))
+    /// } else {
+    ///     0
+    /// }
+    /// ```
+    fn sf(&self, x: f64) -> f64 {
+        let a = self.min;
+        let b = self.max;
+        let c = self.mode;
+        if x <= a {
+            1.0
+        } else if x <= c {
+            1.0 - (x - a) * (x - a) / ((b - a) * (c - a))
+        } else if x < b {
+            (b - x) * (b - x) / ((b - a) * (b - c))
+        } else {
+            0.0
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// triangular distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if p < (mode - min) / (max - min) {
+    ///     min + sqrt(p * (max - min) * (mode - min))
+    /// } else {
+    ///     max - sqrt((1 - p) * (max - min) * (max - mode))
+    /// }
+    /// ```
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let a = self.min;
+        let b = self.max;
+        let c = self.mode;
+        if p < (c - a) / (b - a) {
+            a + (p * (b - a) * (c - a)).sqrt()
+        } else {
+            b - ((1.0 - p) * (b - a) * (b - c)).sqrt()
+        }
+    }
+
+    /// Calculates the probability that a triangular distributed random
+    /// variable falls between `x` and `y`
+    ///
+    /// # Remarks
+    ///
+    /// The interval is split at the mode and the probability of each part
+    /// is evaluated from the factored difference of squares, so narrow
+    /// intervals do not suffer from cancellation
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if x < y <= mode {
+    ///     (y - x) * (x + y - 2 * min) / ((max - min) * (mode - min))
+    /// } else if mode <= x < y {
+    ///     (y - x) * (2 * max - x - y) / ((max - min) * (max - mode))
+    /// }
+    /// ```
+    fn prob_between(&self, x: f64, y: f64) -> f64 {
+        let a = self.min;
+        let b = self.max;
+        let c = self.mode;
+        let x = x.max(a);
+        let y = y.min(b);
+        if y <= x {
+            return 0.0;
+        }
+        let mut p = 0.0;
+        let (lx, ly) = (x, y.min(c));
+        if lx < ly {
+            p += (ly - lx) * (lx + ly - 2.0 * a) / ((b - a) * (c - a));
+        }
+        let (ux, uy) = (x.max(c), y);
+        if ux < uy {
+            p += (uy - ux) * (2.0 * b - ux - uy) / ((b - a) * (b - c));
+        }
+        p
+    }
 }
 
 impl Min<f64> for Triangular {
@@ -155,7 +274,7 @@ impl Distribution<f64> for Triangular {
     /// 1 / 2 + ln((max - min) / 2)
     /// ```
     fn entropy(&self) -> Option<f64> {
-        Some(0.5 + ((self.max - self.min) / 2.0).ln())
+        Some(0.5 + fp::ln((self.max - self.min) / 2.0))
     }
     /// Returns the skewness of the triangular distribution
     ///
@@ -172,7 +291,7 @@ impl Distribution<f64> for Triangular {
         let b = self.max;
         let c = self.mode;
         let q = f64::consts::SQRT_2 * (a + b - 2.0 * c) * (2.0 * a - b - c) * (a - 2.0 * b + c);
-        let d = 5.0 * (a * a + b * b + c * c - a * b - a * c - b * c).powf(3.0 / 2.0);
+        let d = 5.0 * fp::powf(a * a + b * b + c * c - a * b - a * c - b * c, 3.0 / 2.0);
         Some(q / d)
     }
 }
@@ -263,16 +382,67 @@ impl Continuous<f64, f64> for Triangular {
     /// } )
     /// ```
     fn ln_pdf(&self, x: f64) -> f64 {
-        self.pdf(x).ln()
+        fp::ln(self.pdf(x))
+    }
+}
+
+impl CharacteristicFunction<f64, f64> for Triangular {
+    /// Calculates the characteristic function for the triangular
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -2((b - c)e^(iat) - (b - a)e^(ict) + (c - a)e^(ibt)) / ((b - a)(c - a)(b - c)t^2)
+    /// ```
+    ///
+    /// where `a` is the min, `b` is the max and `c` is the mode. The formula is
+    /// evaluated in a rearranged form that does not cancel for small `t`
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let width = self.max - self.min;
+        let mode = self.mode - self.min;
+        // reflect the distribution to keep the mode in the lower half
+        let (shift, t, mode) = if 2.0 * mode <= width {
+            (self.min * t, t, mode)
+        } else {
+            (self.max * t, -t, width - mode)
+        };
+        let z = Complex::new(0.0, t);
+        let phi = (width * exp_rem(z * width) - mode * exp_rem(z * mode)) * 2.0 / (width - mode);
+        Complex::from_polar(1.0, shift) * phi
     }
 }
 
-fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, min: f64, max: f64, mode: f64) -> f64 {
-    let f: f64 = rng.gen();
-    if f < (mode - min) / (max - min) {
-        min + (f * (max - min) * (mode - min)).sqrt()
+/// Computes `(e^z - 1 - z) / z^2` without cancellation for small `z`
+fn exp_rem(z: Complex<f64>) -> Complex<f64> {
+    if z.norm() < 1.0 {
+        // Taylor series sum_k z^k / (k + 2)!
+        let mut term = Complex::new(0.5, 0.0);
+        let mut sum = term;
+        for k in 1..20 {
+            term = term * z / (k + 2) as f64;
+            sum += term;
+        }
+        sum
     } else {
-        max - ((1.0 - f) * (max - min) * (max - mode)).sqrt()
+        (z.exp() - 1.0 - z) / (z * z)
+    }
+}
+
+impl Parameterized for Triangular {
+    fn param_names() -> &'static [&'static str] {
+        &["min", "max", "mode"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.min, self.max, self.mode]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Triangular> {
+        match *params {
+            [min, max, mode] => Triangular::new(min, max, mode),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
     }
 }
 
@@ -281,7 +451,7 @@ fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, min: f64, max: f64, mode: f64)
 mod tests {
     use std::fmt::Debug;
     use crate::statistics::*;
-    use crate::distribution::{ContinuousCDF, Continuous, Triangular};
+    use crate::distribution::{CharacteristicFunction, ContinuousCDF, Continuous, Triangular};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -460,6 +630,51 @@ mod tests {
         test_case(-5.0, -3.0, -4.0, 0.875, cdf(-3.5));
     }
 
+    #[test]
+    fn test_sf() {
+        let sf = |arg: f64| move |x: Triangular| x.sf(arg);
+        test_case(0.0, 1.0, 0.5, 0.875, sf(0.25));
+        test_case(0.0, 1.0, 0.5, 0.5, sf(0.5));
+        test_case(0.0, 1.0, 0.5, 0.125, sf(0.75));
+        test_case(0.0, 1.0, 0.5, 1.0, sf(-1.0));
+        test_case(0.0, 1.0, 0.5, 0.0, sf(1.5));
+        test_almost(-5.0, 8.0, -3.5, 0.107023411371237458194, 1e-16, sf(4.0));
+        // the upper tail does not cancel against one
+        test_case(0.0, 1.0, 0.5, 2f64.powi(-59), sf(1.0 - 2f64.powi(-30)));
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Triangular| x.inverse_cdf(arg);
+        test_case(0.0, 1.0, 0.5, 0.0, inverse_cdf(0.0));
+        test_case(0.0, 1.0, 0.5, 0.25, inverse_cdf(0.125));
+        test_case(0.0, 1.0, 0.5, 0.5, inverse_cdf(0.5));
+        test_case(0.0, 1.0, 0.5, 0.75, inverse_cdf(0.875));
+        test_case(0.0, 1.0, 0.5, 1.0, inverse_cdf(1.0));
+        test_almost(-5.0, 8.0, -3.5, -4.0, 1e-15, inverse_cdf(0.05128205128205128205128));
+        test_almost(-5.0, 8.0, -3.5, 4.0, 1e-14, inverse_cdf(0.892976588628762541806));
+        test_case(5.0, 25.0, 25.0, 25.0, inverse_cdf(1.0));
+        test_case(1.0, 2.0, 1.0, 1.0, inverse_cdf(0.0));
+        let n = try_create(-5.0, 8.0, -3.5);
+        assert_almost_eq!(n.inverse_cdf(0.5), n.median(), 1e-14);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_cdf_out_of_range() {
+        try_create(0.0, 1.0, 0.5).inverse_cdf(1.5);
+    }
+
+    #[test]
+    fn test_prob_between() {
+        test_case(0.0, 1.0, 0.5, 0.75, |x| x.prob_between(0.25, 0.75));
+        test_case(0.0, 1.0, 0.5, 0.375, |x| x.prob_between(0.25, 0.5));
+        test_case(0.0, 1.0, 0.5, 1.0, |x| x.prob_between(-1.0, 2.0));
+        test_case(0.0, 1.0, 0.5, 0.0, |x| x.prob_between(0.75, 0.25));
+        test_case(0.0, 1.0, 0.5, 0.0, |x| x.prob_between(1.5, 2.0));
+        test_almost(0.0, 1.0, 0.5, 4.000000328961483e-11, 1e-24, |x| x.prob_between(0.9, 0.9 + 1e-10));
+    }
+
     #[test]
     fn test_cdf_lower_bound() {
         let cdf = |arg: f64| move |x: Triangular| x.cdf(arg);
@@ -477,4 +692,24 @@ mod tests {
         test::check_continuous_distribution(&try_create(-5.0, 5.0, 0.0), -5.0, 5.0);
         test::check_continuous_distribution(&try_create(-15.0, -2.0, -3.0), -15.0, -2.0);
     }
+
+    #[test]
+    fn test_cf() {
+        let phi = try_create(0.0, 1.0, 0.5).cf(2.0);
+        assert_almost_eq!(phi.re, 0.49675144828342194, 1e-14);
+        assert_almost_eq!(phi.im, 0.7736445427901113, 1e-14);
+        let phi = try_create(-5.0, 8.0, -3.5).cf(0.3);
+        assert_almost_eq!(phi.re, 0.6699899791131659, 1e-14);
+        assert_almost_eq!(phi.im, -0.08111030423409599, 1e-14);
+        let phi = try_create(-5.0, 8.0, 6.0).cf(0.3);
+        assert_almost_eq!(phi.re, 0.38976140144585575, 1e-14);
+        assert_almost_eq!(phi.im, 0.5613763938276602, 1e-14);
+        let phi = try_create(0.0, 1.0, 0.25).cf(40.0);
+        assert_almost_eq!(phi.re, -0.009482246757755913, 1e-14);
+        assert_almost_eq!(phi.im, -0.004868662673394713, 1e-14);
+        // the closed form cancels catastrophically for small t
+        let phi = try_create(1.0, 2.0, 2.0).cf(1e-4);
+        assert_almost_eq!(phi.re, 0.9999999858333333, 1e-15);
+        assert_almost_eq!(phi.im, 1.6666666585e-4, 1e-16);
+    }
 }
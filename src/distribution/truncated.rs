@@ -0,0 +1,835 @@
+use crate::distribution::discretize::tanh_sinh;
+use crate::distribution::{Continuous, ContinuousCDF, Discrete, DiscreteCDF};
+use crate::fp;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use num_traits::PrimInt;
+use rand::Rng;
+use std::f64;
+
+/// Implements the [truncated
+/// distribution](https://en.wikipedia.org/wiki/Truncated_distribution) of a
+/// continuous distribution restricted to the interval `[lower, upper]`
+///
+/// # Remarks
+///
+/// The cdf and pdf are those of the parent distribution renormalized by the
+/// probability of the interval, and quantiles are computed from the inverse
+/// cdf of the parent. Since the parent's quantile is evaluated at
+/// `F(lower) + p * (F(upper) - F(lower))`, quantiles lose accuracy if the
+/// interval lies far in the upper tail of the parent. Moments and the
+/// entropy are computed numerically by tanh-sinh quadrature of the quantile
+/// function, so they are only meaningful if they exist.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, Normal, Truncated};
+/// use statrs::statistics::Distribution;
+///
+/// // the half-normal distribution
+/// let n = Truncated::new(Normal::new(0.0, 1.0).unwrap(), 0.0, f64::INFINITY).unwrap();
+/// assert!((n.mean().unwrap() - (2.0 / std::f64::consts::PI).sqrt()).abs() < 1e-12);
+/// assert!((n.cdf(1.0) - 0.6826894921370859).abs() < 1e-10);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Truncated<D> {
+    parent: D,
+    lower: f64,
+    upper: f64,
+    cdf_lower: f64,
+    sf_upper: f64,
+    mass: f64,
+}
+
+impl<D: ContinuousCDF<f64, f64>> Truncated<D> {
+    /// Constructs the distribution of `parent` truncated to the interval
+    /// `[lower, upper]`, either bound of which may be infinite
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lower` or `upper` are `NaN`, if `lower >= upper`
+    /// or if the interval has zero probability under `parent`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, Truncated};
+    ///
+    /// let exp = Exp::new(1.0).unwrap();
+    /// assert!(Truncated::new(exp, 1.0, 2.0).is_ok());
+    /// assert!(Truncated::new(exp, 2.0, 1.0).is_err());
+    /// assert!(Truncated::new(exp, -2.0, -1.0).is_err());
+    /// ```
+    pub fn new(parent: D, lower: f64, upper: f64) -> Result<Truncated<D>> {
+        if lower.is_nan() || upper.is_nan() || lower >= upper {
+            return Err(StatsError::BadParams);
+        }
+        let mass = parent.prob_between(lower, upper);
+        if mass.is_nan() || mass <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Truncated {
+            cdf_lower: parent.cdf(lower),
+            sf_upper: parent.sf(upper),
+            parent,
+            lower,
+            upper,
+            mass,
+        })
+    }
+
+    /// Returns the parent distribution
+    pub fn parent(&self) -> &D {
+        &self.parent
+    }
+
+    /// Returns the lower truncation bound
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Returns the upper truncation bound
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// Returns the quantile at `t`, or at `1 - t_c` if `t > 0.5`, computing
+    /// the parent's probability from the nearer end of the interval
+    fn quantile(&self, t: f64, t_c: f64) -> f64 {
+        let p = if t <= 0.5 {
+            self.cdf_lower + t * self.mass
+        } else {
+            1.0 - self.sf_upper - t_c * self.mass
+        };
+        self.parent.inverse_cdf(p).max(self.min()).min(self.max())
+    }
+
+    /// Computes `E[f(X)]` by tanh-sinh quadrature of `f(F^-1(u))` over `u`
+    /// in `[0, 1]`
+    fn expectation<F: Fn(f64) -> f64>(&self, f: F) -> f64 {
+        tanh_sinh()
+            .map(|(t, t_c, w)| w * f(self.quantile(t, t_c)))
+            .sum()
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ::rand::distributions::Distribution<f64> for Truncated<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.inverse_cdf(rng.gen::<f64>())
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f64, f64> for Truncated<D> {
+    /// Calculates the cumulative distribution function for the truncated
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (F(x) - F(a)) / (F(b) - F(a))
+    /// ```
+    ///
+    /// for `a <= x <= b`, where `F` is the cdf of the parent and `a` and `b`
+    /// are the lower and upper bounds
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= self.lower {
+            0.0
+        } else if x >= self.upper {
+            1.0
+        } else {
+            (self.parent.prob_between(self.lower, x) / self.mass).min(1.0)
+        }
+    }
+
+    /// Calculates the survival function for the truncated distribution at
+    /// `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (S(x) - S(b)) / (F(b) - F(a))
+    /// ```
+    ///
+    /// for `a <= x <= b`, where `F` and `S` are the cdf and survival
+    /// function of the parent and `a` and `b` are the lower and upper bounds
+    fn sf(&self, x: f64) -> f64 {
+        if x <= self.lower {
+            1.0
+        } else if x >= self.upper {
+            0.0
+        } else {
+            (self.parent.prob_between(x, self.upper) / self.mass).min(1.0)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// truncated distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(F(a) + p * (F(b) - F(a)))
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent and `a` and `b` are the lower and
+    /// upper bounds
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == 0.0 {
+            self.min()
+        } else if p == 1.0 {
+            self.max()
+        } else {
+            self.quantile(p, 1.0 - p)
+        }
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Min<f64> for Truncated<D> {
+    /// Returns the minimum value in the domain of the truncated
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// max(a, min)
+    /// ```
+    ///
+    /// where `a` is the lower bound and `min` the minimum of the parent
+    fn min(&self) -> f64 {
+        self.lower.max(self.parent.min())
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Max<f64> for Truncated<D> {
+    /// Returns the maximum value in the domain of the truncated
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// min(b, max)
+    /// ```
+    ///
+    /// where `b` is the upper bound and `max` the maximum of the parent
+    fn max(&self) -> f64 {
+        self.upper.min(self.parent.max())
+    }
+}
+
+impl<D> Distribution<f64> for Truncated<D>
+where
+    D: ContinuousCDF<f64, f64> + Continuous<f64, f64>,
+{
+    /// Returns the mean of the truncated distribution, computed numerically
+    fn mean(&self) -> Option<f64> {
+        Some(self.expectation(|x| x))
+    }
+    /// Returns the variance of the truncated distribution, computed
+    /// numerically
+    fn variance(&self) -> Option<f64> {
+        self.central_moment(2)
+    }
+    /// Returns the entropy of the truncated distribution, computed
+    /// numerically
+    fn entropy(&self) -> Option<f64> {
+        Some(-self.expectation(|x| self.ln_pdf(x)))
+    }
+    /// Returns the skewness of the truncated distribution, computed
+    /// numerically
+    fn skewness(&self) -> Option<f64> {
+        Some(self.central_moment(3)? / fp::powf(self.central_moment(2)?, 1.5))
+    }
+    /// Returns the `k`th raw moment of the truncated distribution, computed
+    /// numerically
+    fn moment(&self, k: u32) -> Option<f64> {
+        Some(self.expectation(|x| fp::powi(x, k as i32)))
+    }
+    /// Returns the `k`th central moment of the truncated distribution,
+    /// computed numerically
+    fn central_moment(&self, k: u32) -> Option<f64> {
+        let mean = self.mean()?;
+        Some(self.expectation(|x| fp::powi(x - mean, k as i32)))
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Median<f64> for Truncated<D> {
+    /// Returns the median of the truncated distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(F(a) + (F(b) - F(a)) / 2)
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent and `a` and `b` are the lower and
+    /// upper bounds
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl<D> Continuous<f64, f64> for Truncated<D>
+where
+    D: ContinuousCDF<f64, f64> + Continuous<f64, f64>,
+{
+    /// Calculates the probability density function for the truncated
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// f(x) / (F(b) - F(a))
+    /// ```
+    ///
+    /// for `a <= x <= b` and zero otherwise, where `f` and `F` are the pdf
+    /// and cdf of the parent and `a` and `b` are the lower and upper bounds
+    fn pdf(&self, x: f64) -> f64 {
+        if x < self.lower || x > self.upper {
+            0.0
+        } else {
+            self.parent.pdf(x) / self.mass
+        }
+    }
+
+    /// Calculates the log probability density function for the truncated
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(f(x)) - ln(F(b) - F(a))
+    /// ```
+    ///
+    /// for `a <= x <= b` and `-INF` otherwise, where `f` and `F` are the pdf
+    /// and cdf of the parent and `a` and `b` are the lower and upper bounds
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x < self.lower || x > self.upper {
+            f64::NEG_INFINITY
+        } else {
+            self.parent.ln_pdf(x) - fp::ln(self.mass)
+        }
+    }
+}
+
+/// Implements the [truncated
+/// distribution](https://en.wikipedia.org/wiki/Truncated_distribution) of a
+/// discrete distribution restricted to the integers in `[lower, upper]`
+///
+/// # Remarks
+///
+/// The cdf and pmf are those of the parent distribution renormalized by the
+/// probability of the interval, and quantiles are computed from the inverse
+/// cdf of the parent. Moments and the entropy are computed by summing over
+/// the support outwards from the median until the remaining probability in
+/// either direction is negligible.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Discrete, Poisson, TruncatedDiscrete};
+/// use statrs::statistics::Distribution;
+///
+/// // the zero-truncated poisson distribution
+/// let n = TruncatedDiscrete::new(Poisson::new(2.0).unwrap(), 1, u64::MAX).unwrap();
+/// assert_eq!(n.pmf(0), 0.0);
+/// assert!((n.mean().unwrap() - 2.0 / (1.0 - (-2f64).exp())).abs() < 1e-12);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TruncatedDiscrete<D, K> {
+    parent: D,
+    lower: K,
+    upper: K,
+    cdf_below: f64,
+    sf_upper: f64,
+    mass: f64,
+}
+
+impl<D, K> TruncatedDiscrete<D, K>
+where
+    D: DiscreteCDF<K, f64>,
+    K: PrimInt,
+{
+    /// Constructs the distribution of `parent` truncated to the integers in
+    /// `[lower, upper]`, where `K::min_value()` and `K::max_value()` leave
+    /// the respective side untruncated
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lower > upper` or if the interval has zero
+    /// probability under `parent`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Binomial, TruncatedDiscrete};
+    ///
+    /// let b = Binomial::new(0.5, 10).unwrap();
+    /// assert!(TruncatedDiscrete::new(b, 2, 8).is_ok());
+    /// assert!(TruncatedDiscrete::new(b, 8, 2).is_err());
+    /// assert!(TruncatedDiscrete::new(b, 11, 20).is_err());
+    /// ```
+    pub fn new(parent: D, lower: K, upper: K) -> Result<TruncatedDiscrete<D, K>> {
+        if lower > upper {
+            return Err(StatsError::BadParams);
+        }
+        let cdf_below = if lower == K::min_value() {
+            0.0
+        } else {
+            parent.cdf(lower - K::one())
+        };
+        let cdf_upper = parent.cdf(upper);
+        let sf_upper = parent.sf(upper);
+        // compute the mass from the nearer tail
+        let mass = if cdf_below > 0.5 {
+            let sf_below = if lower == K::min_value() {
+                1.0
+            } else {
+                parent.sf(lower - K::one())
+            };
+            sf_below - sf_upper
+        } else {
+            cdf_upper - cdf_below
+        };
+        if mass.is_nan() || mass <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        Ok(TruncatedDiscrete {
+            parent,
+            lower,
+            upper,
+            cdf_below,
+            sf_upper,
+            mass,
+        })
+    }
+
+    /// Returns the parent distribution
+    pub fn parent(&self) -> &D {
+        &self.parent
+    }
+
+    /// Returns the lower truncation bound
+    pub fn lower(&self) -> K {
+        self.lower
+    }
+
+    /// Returns the upper truncation bound
+    pub fn upper(&self) -> K {
+        self.upper
+    }
+
+    /// Computes `E[f(X)]` by summing over the support outwards from the
+    /// median
+    fn expectation<F: Fn(K) -> f64>(&self, f: F) -> f64
+    where
+        D: Discrete<K, f64>,
+    {
+        let eps = f64::EPSILON * f64::EPSILON;
+        let term = |x: K| match self.pmf(x) {
+            p if p > 0.0 => p * f(x),
+            _ => 0.0,
+        };
+        let median = self.inverse_cdf(0.5);
+        let mut total = 0.0;
+        let mut x = median;
+        loop {
+            let term = term(x);
+            total += term;
+            if x >= self.max() || (self.sf(x) <= eps && term.abs() <= eps * total.abs()) {
+                break;
+            }
+            x = x + K::one();
+        }
+        let mut x = median;
+        while x > self.min() {
+            x = x - K::one();
+            let term = term(x);
+            total += term;
+            if x > self.min() && self.cdf(x - K::one()) <= eps && term.abs() <= eps * total.abs() {
+                break;
+            }
+        }
+        total
+    }
+}
+
+impl<D, K> ::rand::distributions::Distribution<f64> for TruncatedDiscrete<D, K>
+where
+    D: DiscreteCDF<K, f64>,
+    K: PrimInt,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.inverse_cdf(rng.gen::<f64>()).to_f64().unwrap()
+    }
+}
+
+impl<D, K> DiscreteCDF<K, f64> for TruncatedDiscrete<D, K>
+where
+    D: DiscreteCDF<K, f64>,
+    K: PrimInt,
+{
+    /// Calculates the cumulative distribution function for the truncated
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (F(x) - F(a - 1)) / (F(b) - F(a - 1))
+    /// ```
+    ///
+    /// for `a <= x <= b`, where `F` is the cdf of the parent and `a` and `b`
+    /// are the lower and upper bounds
+    fn cdf(&self, x: K) -> f64 {
+        if x < self.lower {
+            0.0
+        } else if x >= self.upper {
+            1.0
+        } else {
+            ((self.parent.cdf(x) - self.cdf_below) / self.mass).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Calculates the survival function for the truncated distribution at
+    /// `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (S(x) - S(b)) / (F(b) - F(a - 1))
+    /// ```
+    ///
+    /// for `a <= x <= b`, where `F` and `S` are the cdf and survival
+    /// function of the parent and `a` and `b` are the lower and upper bounds
+    fn sf(&self, x: K) -> f64 {
+        if x < self.lower {
+            1.0
+        } else if x >= self.upper {
+            0.0
+        } else {
+            ((self.parent.sf(x) - self.sf_upper) / self.mass).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// truncated distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(F(a - 1) + p * (F(b) - F(a - 1)))
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent and `a` and `b` are the lower and
+    /// upper bounds
+    fn inverse_cdf(&self, p: f64) -> K {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == 0.0 {
+            self.min()
+        } else if p == 1.0 {
+            self.max()
+        } else {
+            let x = self.parent.inverse_cdf(self.cdf_below + p * self.mass);
+            x.clamp(self.min(), self.max())
+        }
+    }
+}
+
+impl<D, K> Min<K> for TruncatedDiscrete<D, K>
+where
+    D: DiscreteCDF<K, f64>,
+    K: PrimInt,
+{
+    /// Returns the minimum value in the domain of the truncated
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// max(a, min)
+    /// ```
+    ///
+    /// where `a` is the lower bound and `min` the minimum of the parent
+    fn min(&self) -> K {
+        self.lower.max(self.parent.min())
+    }
+}
+
+impl<D, K> Max<K> for TruncatedDiscrete<D, K>
+where
+    D: DiscreteCDF<K, f64>,
+    K: PrimInt,
+{
+    /// Returns the maximum value in the domain of the truncated
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// min(b, max)
+    /// ```
+    ///
+    /// where `b` is the upper bound and `max` the maximum of the parent
+    fn max(&self) -> K {
+        self.upper.min(self.parent.max())
+    }
+}
+
+impl<D, K> Distribution<f64> for TruncatedDiscrete<D, K>
+where
+    D: DiscreteCDF<K, f64> + Discrete<K, f64>,
+    K: PrimInt,
+{
+    /// Returns the mean of the truncated distribution, computed by
+    /// summation
+    fn mean(&self) -> Option<f64> {
+        Some(self.expectation(|x| x.to_f64().unwrap()))
+    }
+    /// Returns the variance of the truncated distribution, computed by
+    /// summation
+    fn variance(&self) -> Option<f64> {
+        self.central_moment(2)
+    }
+    /// Returns the entropy of the truncated distribution, computed by
+    /// summation
+    fn entropy(&self) -> Option<f64> {
+        Some(-self.expectation(|x| self.ln_pmf(x)))
+    }
+    /// Returns the skewness of the truncated distribution, computed by
+    /// summation
+    fn skewness(&self) -> Option<f64> {
+        Some(self.central_moment(3)? / fp::powf(self.central_moment(2)?, 1.5))
+    }
+    /// Returns the `k`th raw moment of the truncated distribution, computed
+    /// by summation
+    fn moment(&self, k: u32) -> Option<f64> {
+        Some(self.expectation(|x| fp::powi(x.to_f64().unwrap(), k as i32)))
+    }
+    /// Returns the `k`th central moment of the truncated distribution,
+    /// computed by summation
+    fn central_moment(&self, k: u32) -> Option<f64> {
+        let mean = self.mean()?;
+        Some(self.expectation(|x| fp::powi(x.to_f64().unwrap() - mean, k as i32)))
+    }
+}
+
+impl<D, K> Median<f64> for TruncatedDiscrete<D, K>
+where
+    D: DiscreteCDF<K, f64>,
+    K: PrimInt,
+{
+    /// Returns the median of the truncated distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// F^-1(F(a - 1) + (F(b) - F(a - 1)) / 2)
+    /// ```
+    ///
+    /// where `F` is the cdf of the parent and `a` and `b` are the lower and
+    /// upper bounds
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5).to_f64().unwrap()
+    }
+}
+
+impl<D, K> Discrete<K, f64> for TruncatedDiscrete<D, K>
+where
+    D: DiscreteCDF<K, f64> + Discrete<K, f64>,
+    K: PrimInt,
+{
+    /// Calculates the probability mass function for the truncated
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// p(x) / (F(b) - F(a - 1))
+    /// ```
+    ///
+    /// for `a <= x <= b` and zero otherwise, where `p` and `F` are the pmf
+    /// and cdf of the parent and `a` and `b` are the lower and upper bounds
+    fn pmf(&self, x: K) -> f64 {
+        if x < self.lower || x > self.upper {
+            0.0
+        } else {
+            self.parent.pmf(x) / self.mass
+        }
+    }
+
+    /// Calculates the log probability mass function for the truncated
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(p(x)) - ln(F(b) - F(a - 1))
+    /// ```
+    ///
+    /// for `a <= x <= b` and `-INF` otherwise, where `p` and `F` are the pmf
+    /// and cdf of the parent and `a` and `b` are the lower and upper bounds
+    fn ln_pmf(&self, x: K) -> f64 {
+        if x < self.lower || x > self.upper {
+            f64::NEG_INFINITY
+        } else {
+            self.parent.ln_pmf(x) - fp::ln(self.mass)
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::{Binomial, DiscreteUniform, Exp, Normal, Poisson, Uniform};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn half_normal() -> Truncated<Normal> {
+        Truncated::new(Normal::new(0.0, 1.0).unwrap(), 0.0, f64::INFINITY).unwrap()
+    }
+
+    #[test]
+    fn test_bad_create() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        assert!(Truncated::new(n, f64::NAN, 1.0).is_err());
+        assert!(Truncated::new(n, 0.0, f64::NAN).is_err());
+        assert!(Truncated::new(n, 1.0, 1.0).is_err());
+        assert!(Truncated::new(n, 50.0, 60.0).is_err());
+        let u = Uniform::new(0.0, 1.0).unwrap();
+        assert!(Truncated::new(u, 2.0, 3.0).is_err());
+        let p = Poisson::new(1.0).unwrap();
+        assert!(TruncatedDiscrete::new(p, 3, 2).is_err());
+        assert!(TruncatedDiscrete::new(Binomial::new(0.5, 5).unwrap(), 6, 10).is_err());
+    }
+
+    #[test]
+    fn test_half_normal() {
+        let n = half_normal();
+        assert_eq!(n.min(), 0.0);
+        assert_eq!(n.max(), f64::INFINITY);
+        assert_almost_eq!(n.mean().unwrap(), (2.0 / f64::consts::PI).sqrt(), 1e-12);
+        assert_almost_eq!(n.variance().unwrap(), 0.36338022763241865692446494651, 1e-12);
+        assert_almost_eq!(n.skewness().unwrap(), 0.995271746431156042444139754242, 1e-11);
+        assert_almost_eq!(n.entropy().unwrap(), 0.725791352644727432363097614947, 1e-12);
+        assert_almost_eq!(n.median(), 0.6744897501960817, 1e-14);
+        assert_almost_eq!(n.cdf(1.0), 0.6826894921370859, 1e-10);
+        assert_almost_eq!(n.sf(1.0), 1.0 - 0.6826894921370859, 1e-10);
+        assert_almost_eq!(n.pdf(1.0), 0.48394144903828673, 1e-15);
+        assert_eq!(n.pdf(-1.0), 0.0);
+        assert_eq!(n.ln_pdf(-1.0), f64::NEG_INFINITY);
+        assert_eq!(n.cdf(-1.0), 0.0);
+        assert_eq!(n.sf(-1.0), 1.0);
+    }
+
+    #[test]
+    fn test_interval() {
+        let n = Truncated::new(Exp::new(1.0).unwrap(), 1.0, 2.0).unwrap();
+        assert_eq!(n.lower(), 1.0);
+        assert_eq!(n.upper(), 2.0);
+        assert_almost_eq!(n.mean().unwrap(), 1.41802329313067357561499799489, 1e-13);
+        assert_almost_eq!(n.variance().unwrap(), 0.0793264057922076810545864772835, 1e-13);
+        assert_almost_eq!(n.skewness().unwrap(), 0.344872927054998930037734434827, 1e-12);
+        assert_almost_eq!(n.entropy().unwrap(), -0.0406518522564083154066456501764, 1e-12);
+        assert_almost_eq!(n.cdf(1.5), 0.622459331201854564638900565746, 1e-15);
+        assert_almost_eq!(n.median(), 1.37988549304172247536823662649, 1e-14);
+        assert_eq!(n.inverse_cdf(0.0), 1.0);
+        assert_eq!(n.inverse_cdf(1.0), 2.0);
+        for &p in &[0.01, 0.3, 0.7, 0.99] {
+            assert_almost_eq!(n.cdf(n.inverse_cdf(p)), p, 1e-14);
+        }
+        // truncating a uniform distribution gives a uniform distribution
+        let u = Truncated::new(Uniform::new(0.0, 10.0).unwrap(), 2.0, 4.0).unwrap();
+        assert_almost_eq!(u.mean().unwrap(), 3.0, 1e-13);
+        assert_almost_eq!(u.variance().unwrap(), 1.0 / 3.0, 1e-13);
+        assert_almost_eq!(u.entropy().unwrap(), f64::consts::LN_2, 1e-13);
+        assert_almost_eq!(u.moment(2).unwrap(), 28.0 / 3.0, 1e-12);
+        assert_almost_eq!(u.pdf(3.0), 0.5, 1e-15);
+    }
+
+    #[test]
+    fn test_continuous() {
+        let n = Truncated::new(Normal::new(1.0, 2.0).unwrap(), -1.0, 4.0).unwrap();
+        test::check_continuous_distribution(&n, -1.0, 4.0);
+        test::check_continuous_distribution(&half_normal(), 0.0, 5.0);
+    }
+
+    #[test]
+    fn test_discrete() {
+        let n = TruncatedDiscrete::new(Poisson::new(5.0).unwrap(), 2, 12).unwrap();
+        test::check_discrete_distribution(&n, 12);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = Truncated::new(Normal::new(0.0, 1.0).unwrap(), -0.5, 1.5).unwrap();
+        let samples: Vec<f64> = (0..10000).map(|_| n.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| (-0.5..=1.5).contains(&x)));
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!((mean - n.mean().unwrap()).abs() < 0.02);
+        let d = TruncatedDiscrete::new(Poisson::new(3.0).unwrap(), 2, 5).unwrap();
+        for _ in 0..1000 {
+            let x = d.sample(&mut rng);
+            assert!((2.0..=5.0).contains(&x) && x.fract() == 0.0);
+        }
+    }
+
+    #[test]
+    fn test_zero_truncated_poisson() {
+        let n = TruncatedDiscrete::new(Poisson::new(2.0).unwrap(), 1, u64::MAX).unwrap();
+        assert_eq!(n.min(), 1);
+        assert_eq!(n.max(), u64::MAX);
+        assert_eq!(n.pmf(0), 0.0);
+        assert_eq!(n.ln_pmf(0), f64::NEG_INFINITY);
+        assert_almost_eq!(n.pmf(1), 0.313035285499331303636161246931, 1e-15);
+        assert_almost_eq!(n.mean().unwrap(), 2.31303528549933130363616124693, 1e-13);
+        assert_almost_eq!(n.variance().unwrap(), 1.5889736245330208372281724578, 1e-13);
+        assert_almost_eq!(n.skewness().unwrap(), 1.01962813071422371417464967604, 1e-12);
+        assert_almost_eq!(n.entropy().unwrap(), 1.51327811315800167271309920056, 1e-13);
+    }
+
+    #[test]
+    fn test_discrete_interval() {
+        let n = TruncatedDiscrete::new(Binomial::new(0.5, 10).unwrap(), 2, 8).unwrap();
+        assert_eq!((n.lower(), n.upper()), (2, 8));
+        assert_eq!(n.cdf(1), 0.0);
+        assert_almost_eq!(n.cdf(2), 0.044910179640718562874251497006, 1e-14);
+        assert_almost_eq!(n.cdf(4), 0.374251497005988023952095808383, 1e-14);
+        assert_almost_eq!(n.sf(4), 1.0 - 0.374251497005988023952095808383, 1e-14);
+        assert_eq!(n.cdf(8), 1.0);
+        assert_eq!(n.sf(8), 0.0);
+        assert_almost_eq!(n.mean().unwrap(), 5.0, 1e-14);
+        assert_almost_eq!(n.variance().unwrap(), 2.18562874251497005988023952096, 1e-14);
+        assert_almost_eq!(n.skewness().unwrap(), 0.0, 1e-14);
+        assert_almost_eq!(n.entropy().unwrap(), 1.78919546198289859519946790597, 1e-14);
+        assert_eq!(n.median(), 5.0);
+        assert_eq!(n.inverse_cdf(0.0), 2);
+        assert_eq!(n.inverse_cdf(0.04), 2);
+        assert_eq!(n.inverse_cdf(0.05), 3);
+        assert_eq!(n.inverse_cdf(1.0), 8);
+        let total: f64 = (0..=10).map(|x| n.pmf(x)).sum();
+        assert_almost_eq!(total, 1.0, 1e-15);
+    }
+
+    #[test]
+    fn test_signed_support() {
+        let n = TruncatedDiscrete::new(DiscreteUniform::new(-5, 5).unwrap(), -2, 10).unwrap();
+        assert_eq!(n.min(), -2);
+        assert_eq!(n.max(), 5);
+        assert_almost_eq!(n.pmf(0), 0.125, 1e-15);
+        assert_eq!(n.pmf(-3), 0.0);
+        assert_almost_eq!(n.cdf(0), 0.375, 1e-15);
+        assert_eq!(n.inverse_cdf(0.5), 1);
+        assert_almost_eq!(n.mean().unwrap(), 1.5, 1e-14);
+        assert_almost_eq!(n.variance().unwrap(), 5.25, 1e-14);
+        assert_almost_eq!(n.entropy().unwrap(), 8f64.ln(), 1e-14);
+    }
+}
@@ -1,6 +1,10 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{
+    fit, CharacteristicFunction, Continuous, ContinuousCDF, Fit, KullbackLeibler, Parameterized,
+};
+use crate::fp;
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use num_complex::Complex;
 use rand::distributions::Uniform as RandUniform;
 use rand::Rng;
 use std::f64;
@@ -52,6 +56,51 @@ impl Uniform {
             Ok(Uniform { min, max })
         }
     }
+
+    /// Constructs a new uniform distribution from parameters already known to
+    /// be valid, skipping the validation done by `new`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` would return an error for the
+    /// parameters. In release builds invalid parameters are not detected
+    /// and the distribution's methods may return meaningless values.
+    pub fn new_unchecked(min: f64, max: f64) -> Uniform {
+        debug_assert!(Uniform::new(min, max).is_ok(), "invalid uniform parameters");
+        Uniform { min, max }
+    }
+
+    /// Fits a uniform distribution to `data` by the method of moments, with
+    /// the bounds `mean ∓ √3 sd`, the standard deviation taken with the
+    /// divisor `n`
+    ///
+    /// # Remarks
+    ///
+    /// Unlike the maximum likelihood bounds of `fit_mle`, the sample
+    /// minimum and maximum, the moment estimates are not determined by the
+    /// extreme observations alone and so are less sensitive to outliers,
+    /// but need not contain the whole sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or has a non-finite observation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Uniform;
+    /// use statrs::statistics::{Max, Min};
+    ///
+    /// let n = Uniform::fit_moments(&[1.0, 3.0]).unwrap();
+    /// assert!((n.min() - (2.0 - 3f64.sqrt())).abs() < 1e-15);
+    /// assert!((n.max() - (2.0 + 3f64.sqrt())).abs() < 1e-15);
+    /// ```
+    pub fn fit_moments(data: &[f64]) -> Result<Uniform> {
+        fit::check_sample(data, |_| true)?;
+        let (mean, variance) = fit::mean_variance(data);
+        let half_width = (3.0 * variance).sqrt();
+        Uniform::new(mean - half_width, mean + half_width)
+    }
 }
 
 impl ::rand::distributions::Distribution<f64> for Uniform {
@@ -80,6 +129,43 @@ impl ContinuousCDF<f64, f64> for Uniform {
             (x - self.min) / (self.max - self.min)
         }
     }
+
+    /// Calculates the probability that a uniformly distributed random
+    /// variable falls between `a` and `b`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (min(b, max) - max(a, min)) / (max - min)
+    /// ```
+    fn prob_between(&self, a: f64, b: f64) -> f64 {
+        let low = a.max(self.min);
+        let high = b.min(self.max);
+        if high <= low {
+            0.0
+        } else {
+            (high - low) / (self.max - self.min)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// uniform distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// min + p * (max - min)
+    /// ```
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        self.min + p * (self.max - self.min)
+    }
 }
 
 impl Min<f64> for Uniform {
@@ -123,7 +209,7 @@ impl Distribution<f64> for Uniform {
     /// ln(max - min)
     /// ```
     fn entropy(&self) -> Option<f64> {
-        Some((self.max - self.min).ln())
+        Some(fp::ln(self.max - self.min))
     }
     /// Returns the skewness for the continuous uniform distribution
     ///
@@ -135,6 +221,38 @@ impl Distribution<f64> for Uniform {
     fn skewness(&self) -> Option<f64> {
         Some(0.0)
     }
+    /// Returns the raw moment of order `k` of the uniform distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (max^(k + 1) - min^(k + 1)) / ((k + 1) * (max - min))
+    /// ```
+    fn moment(&self, k: u32) -> Option<f64> {
+        // sum_{j = 0}^k min^j * max^(k - j) / (k + 1) avoids cancellation
+        let sum: f64 = (0..=k)
+            .map(|j| fp::powi(self.min, j as i32) * fp::powi(self.max, (k - j) as i32))
+            .sum();
+        Some(sum / (k + 1) as f64)
+    }
+    /// Returns the central moment of order `k` of the uniform distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if k is even {
+    ///     ((max - min) / 2)^k / (k + 1)
+    /// } else {
+    ///     0
+    /// }
+    /// ```
+    fn central_moment(&self, k: u32) -> Option<f64> {
+        if k % 2 == 1 {
+            Some(0.0)
+        } else {
+            Some(fp::powi((self.max - self.min) / 2.0, k as i32) / (k + 1) as f64)
+        }
+    }
 }
 
 impl Median<f64> for Uniform {
@@ -206,16 +324,81 @@ impl Continuous<f64, f64> for Uniform {
         if x < self.min || x > self.max {
             f64::NEG_INFINITY
         } else {
-            -(self.max - self.min).ln()
+            -fp::ln(self.max - self.min)
+        }
+    }
+}
+
+impl CharacteristicFunction<f64, f64> for Uniform {
+    /// Calculates the characteristic function for the continuous uniform
+    /// distribution at `t`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// e^(it(min + max) / 2) * sin(t(max - min) / 2) / (t(max - min) / 2)
+    /// ```
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let x = 0.5 * t * (self.max - self.min);
+        let sinc = if x == 0.0 { 1.0 } else { fp::sin(x) / x };
+        Complex::from_polar(sinc, 0.5 * t * (self.min + self.max))
+    }
+}
+
+impl KullbackLeibler for Uniform {
+    /// Calculates the Kullback-Leibler divergence of `other` from the
+    /// uniform distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln((max_q - min_q) / (max_p - min_p))    if [min_p, max_p] ⊆ [min_q, max_q]
+    /// ∞                                        otherwise
+    /// ```
+    fn kl_divergence(&self, other: &Uniform) -> f64 {
+        if self.min < other.min || self.max > other.max {
+            f64::INFINITY
+        } else {
+            fp::ln((other.max - other.min) / (self.max - self.min))
         }
     }
 }
 
+impl Parameterized for Uniform {
+    fn param_names() -> &'static [&'static str] {
+        &["min", "max"]
+    }
+
+    fn params(&self) -> Vec<f64> {
+        vec![self.min, self.max]
+    }
+
+    fn from_params(params: &[f64]) -> Result<Uniform> {
+        match *params {
+            [min, max] => Uniform::new(min, max),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl Fit for Uniform {
+    type Sample = f64;
+
+    /// Fits the bounds as the sample minimum and maximum
+    fn fit_mle(data: &[f64]) -> Result<Uniform> {
+        fit::check_sample(data, |_| true)?;
+        let min = data.iter().fold(f64::INFINITY, |m, &x| m.min(x));
+        let max = data.iter().fold(f64::NEG_INFINITY, |m, &x| m.max(x));
+        Uniform::new(min, max)
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::*;
-    use crate::distribution::{ContinuousCDF, Continuous, Uniform};
+    use crate::distribution::{CharacteristicFunction, ContinuousCDF, Continuous, Uniform};
+    use num_complex::Complex;
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -395,6 +578,26 @@ mod tests {
         test_case(0.0, f64::INFINITY, 1.0, cdf(f64::INFINITY));
     }
 
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Uniform| x.inverse_cdf(arg);
+        test_case(0.0, 1.0, 0.3, inverse_cdf(0.3));
+        test_case(-2.0, 6.0, -2.0, inverse_cdf(0.0));
+        test_case(-2.0, 6.0, 0.0, inverse_cdf(0.25));
+        test_case(-2.0, 6.0, 6.0, inverse_cdf(1.0));
+    }
+
+    #[test]
+    fn test_prob_between() {
+        test_case(-1.0, 1.0, 0.25, |x| x.prob_between(0.25, 0.75));
+        test_case(-1.0, 1.0, 0.5, |x| x.prob_between(-5.0, 0.0));
+        test_case(-1.0, 1.0, 1.0, |x| x.prob_between(-5.0, 5.0));
+        test_case(-1.0, 1.0, 0.0, |x| x.prob_between(0.75, 0.25));
+        test_case(-1.0, 1.0, 0.0, |x| x.prob_between(2.0, 3.0));
+        // cdf(b) - cdf(a) loses most digits this close to the upper bound
+        test_almost(0.0, 1e10, 1e-10, 1e-24, |x| x.prob_between(1e10 - 1.0, 1e10));
+    }
+
     #[test]
     fn test_cdf_lower_bound() {
         let cdf = |arg: f64| move |x: Uniform| x.cdf(arg);
@@ -435,4 +638,22 @@ mod tests {
             .all(|v| (min <= v) && (v < max))
         );
     }
+
+    #[test]
+    fn test_cf() {
+        assert_eq!(try_create(-1.0, 3.0).cf(0.0), Complex::new(1.0, 0.0));
+        let phi = try_create(-1.0, 3.0).cf(0.9);
+        assert_almost_eq!(phi.re, 0.3363074416281426, 1e-15);
+        assert_almost_eq!(phi.im, 0.4238005861910349, 1e-15);
+    }
+
+    #[test]
+    fn test_moment() {
+        let n = try_create(-1.0, 3.0);
+        assert_almost_eq!(n.moment(2).unwrap(), 7.0 / 3.0, 1e-15);
+        assert_eq!(n.moment(3).unwrap(), 5.0);
+        assert_eq!(n.central_moment(2).unwrap(), n.variance().unwrap());
+        assert_eq!(n.central_moment(3).unwrap(), 0.0);
+        assert_eq!(n.central_moment(4).unwrap(), 3.2);
+    }
 }
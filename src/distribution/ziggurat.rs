@@ -1,11 +1,12 @@
 use super::ziggurat_tables;
+use crate::fp;
 use rand::distributions::Open01;
 use rand::Rng;
 
 pub fn sample_std_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
     #[inline]
     fn pdf(x: f64) -> f64 {
-        (-x * x / 2.0).exp()
+        fp::exp(-x * x / 2.0)
     }
 
     #[inline]
@@ -16,8 +17,8 @@ pub fn sample_std_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
             let x_: f64 = rng.sample(Open01);
             let y_: f64 = rng.sample(Open01);
 
-            x = x_.ln() / ziggurat_tables::ZIG_NORM_R;
-            y = y_.ln();
+            x = fp::ln(x_) / ziggurat_tables::ZIG_NORM_R;
+            y = fp::ln(y_);
         }
         if u < 0.0 {
             x - ziggurat_tables::ZIG_NORM_R
@@ -39,12 +40,12 @@ pub fn sample_std_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
 pub fn sample_exp_1<R: Rng + ?Sized>(rng: &mut R) -> f64 {
     #[inline]
     fn pdf(x: f64) -> f64 {
-        (-x).exp()
+        fp::exp(-x)
     }
 
     #[inline]
     fn zero_case<R: Rng + ?Sized>(rng: &mut R, _u: f64) -> f64 {
-        ziggurat_tables::ZIG_EXP_R - rng.gen::<f64>().ln()
+        ziggurat_tables::ZIG_EXP_R - fp::ln(rng.gen::<f64>())
     }
 
     ziggurat(
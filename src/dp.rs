@@ -0,0 +1,280 @@
+//! Provides noise mechanisms for [differential
+//! privacy](https://en.wikipedia.org/wiki/Differential_privacy), returning
+//! statrs distributions whose scale is calibrated to the sensitivity of a
+//! query and the desired privacy budget. Adding a sample of the returned
+//! distribution to the query result releases it privately.
+
+use crate::distribution::{ContinuousCDF, DiscreteGaussian, DiscreteLaplace, Laplace, Normal};
+use crate::fp;
+use crate::function::optimize;
+use crate::{Result, StatsError};
+
+/// Returns the noise distribution of the Laplace mechanism, which is
+/// `ε`-differentially private for a query with L1 sensitivity `sensitivity`
+///
+/// # Formula
+///
+/// ```ignore
+/// Laplace(0, Δ / ε)
+/// ```
+///
+/// where `Δ` is the sensitivity and `ε` the privacy budget
+///
+/// # Errors
+///
+/// Returns an error if `sensitivity` or `epsilon` is `NaN`, infinite or not
+/// positive
+///
+/// # Examples
+///
+/// ```
+/// use statrs::dp;
+///
+/// let noise = dp::laplace_noise(2.0, 0.5).unwrap();
+/// assert_eq!(noise.scale(), 4.0);
+/// ```
+pub fn laplace_noise(sensitivity: f64, epsilon: f64) -> Result<Laplace> {
+    check_positive("sensitivity", sensitivity)?;
+    check_positive("epsilon", epsilon)?;
+    Laplace::new(0.0, sensitivity / epsilon)
+}
+
+/// Returns the noise distribution of the classical Gaussian mechanism,
+/// which is `(ε, δ)`-differentially private for a query with L2 sensitivity
+/// `sensitivity` when `ε < 1` (Dwork & Roth, 2014, Theorem A.1)
+///
+/// # Formula
+///
+/// ```ignore
+/// N(0, σ^2), σ = Δ * sqrt(2 * ln(1.25 / δ)) / ε
+/// ```
+///
+/// where `Δ` is the sensitivity
+///
+/// # Errors
+///
+/// Returns an error if `sensitivity` is `NaN`, infinite or not positive, if
+/// `epsilon` is not in `(0, 1)` or if `delta` is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::dp;
+/// use statrs::statistics::Distribution;
+///
+/// let noise = dp::gaussian_noise(1.0, 0.5, 1e-5).unwrap();
+/// assert!((noise.std_dev().unwrap() - 9.68961).abs() < 1e-5);
+/// ```
+pub fn gaussian_noise(sensitivity: f64, epsilon: f64, delta: f64) -> Result<Normal> {
+    check_positive("sensitivity", sensitivity)?;
+    check_unit("epsilon", epsilon)?;
+    check_unit("delta", delta)?;
+    let sigma = sensitivity * (2.0 * fp::ln(1.25 / delta)).sqrt() / epsilon;
+    Normal::new(0.0, sigma)
+}
+
+/// Returns the smallest standard deviation `σ` for which the Gaussian
+/// mechanism is `(ε, δ)`-differentially private for a query with L2
+/// sensitivity `sensitivity`, as characterized by the analytic Gaussian
+/// mechanism of Balle & Wang (2018). Unlike the classical calibration this
+/// is exact and valid for any `ε > 0`.
+///
+/// # Formula
+///
+/// `σ` is the root of
+///
+/// ```ignore
+/// Φ(Δ / (2σ) - εσ / Δ) - e^ε * Φ(-Δ / (2σ) - εσ / Δ) = δ
+/// ```
+///
+/// where `Δ` is the sensitivity and `Φ` the standard normal cdf
+///
+/// # Errors
+///
+/// Returns an error if `sensitivity` or `epsilon` is `NaN`, infinite or
+/// not positive, if `delta` is not in `(0, 1)`, or if the root could not be
+/// found
+///
+/// # Examples
+///
+/// ```
+/// use statrs::dp;
+/// use statrs::statistics::Distribution;
+///
+/// let sigma = dp::analytic_gaussian_sigma(1.0, 0.5, 1e-5).unwrap();
+/// assert!(sigma < dp::gaussian_noise(1.0, 0.5, 1e-5).unwrap().std_dev().unwrap());
+/// ```
+pub fn analytic_gaussian_sigma(sensitivity: f64, epsilon: f64, delta: f64) -> Result<f64> {
+    check_positive("sensitivity", sensitivity)?;
+    check_positive("epsilon", epsilon)?;
+    check_unit("delta", delta)?;
+    // privacy loss of the mechanism with σ = Δ * e^s, decreasing in s
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let excess = |s: f64| {
+        let x = fp::exp(s);
+        let a = 0.5 / x;
+        let b = epsilon * x;
+        normal.cdf(a - b) - fp::exp(epsilon) * normal.cdf(-a - b) - delta
+    };
+    let mut lower = -1.0;
+    while excess(lower) <= 0.0 {
+        lower -= 1.0;
+        if lower < -50.0 {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+    }
+    let mut upper = 0.0;
+    while excess(upper) > 0.0 {
+        upper += 1.0;
+        if upper > 50.0 {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+    }
+    let s = optimize::brent_root(excess, lower, upper, 1e-12)?;
+    Ok(sensitivity * fp::exp(s))
+}
+
+/// Returns the noise distribution of the analytic Gaussian mechanism, i.e.
+/// a centred normal distribution with the standard deviation returned by
+/// `analytic_gaussian_sigma`
+///
+/// # Errors
+///
+/// Returns an error under the same conditions as `analytic_gaussian_sigma`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::dp;
+/// use statrs::statistics::Distribution;
+///
+/// let noise = dp::analytic_gaussian_noise(1.0, 1.0, 1e-5).unwrap();
+/// assert_eq!(noise.mean().unwrap(), 0.0);
+/// ```
+pub fn analytic_gaussian_noise(sensitivity: f64, epsilon: f64, delta: f64) -> Result<Normal> {
+    Normal::new(0.0, analytic_gaussian_sigma(sensitivity, epsilon, delta)?)
+}
+
+/// Returns the noise distribution of the discrete Gaussian mechanism for
+/// integer-valued queries, which satisfies `ρ`-zero-concentrated
+/// differential privacy for a query with L2 sensitivity `sensitivity`
+/// (Canonne, Kamath & Steinke, 2020)
+///
+/// # Formula
+///
+/// ```ignore
+/// N_Z(0, σ^2), σ = Δ / sqrt(2ρ)
+/// ```
+///
+/// where `Δ` is the sensitivity and `N_Z` the discrete Gaussian
+/// distribution
+///
+/// # Errors
+///
+/// Returns an error if `sensitivity` or `rho` is `NaN`, infinite or not
+/// positive
+///
+/// # Examples
+///
+/// ```
+/// use statrs::dp;
+///
+/// let noise = dp::discrete_gaussian_noise(1.0, 0.5).unwrap();
+/// assert_eq!(noise.sigma(), 1.0);
+/// ```
+pub fn discrete_gaussian_noise(sensitivity: f64, rho: f64) -> Result<DiscreteGaussian> {
+    check_positive("sensitivity", sensitivity)?;
+    check_positive("rho", rho)?;
+    DiscreteGaussian::new(0, sensitivity / (2.0 * rho).sqrt())
+}
+
+/// Returns the noise distribution of the discrete Laplace (geometric)
+/// mechanism for integer-valued queries, which is `ε`-differentially
+/// private for a query with L1 sensitivity `sensitivity`
+///
+/// # Formula
+///
+/// ```ignore
+/// DiscreteLaplace(0, Δ / ε)
+/// ```
+///
+/// where `Δ` is the sensitivity
+///
+/// # Errors
+///
+/// Returns an error if `sensitivity` or `epsilon` is `NaN`, infinite or not
+/// positive
+///
+/// # Examples
+///
+/// ```
+/// use statrs::dp;
+///
+/// let noise = dp::discrete_laplace_noise(1.0, 0.25).unwrap();
+/// assert_eq!(noise.b(), 4.0);
+/// ```
+pub fn discrete_laplace_noise(sensitivity: f64, epsilon: f64) -> Result<DiscreteLaplace> {
+    check_positive("sensitivity", sensitivity)?;
+    check_positive("epsilon", epsilon)?;
+    DiscreteLaplace::new(0, sensitivity / epsilon)
+}
+
+fn check_positive(name: &'static str, x: f64) -> Result<()> {
+    if !x.is_finite() || x <= 0.0 {
+        Err(StatsError::ArgMustBePositive(name))
+    } else {
+        Ok(())
+    }
+}
+
+fn check_unit(name: &'static str, x: f64) -> Result<()> {
+    if x.is_nan() || x <= 0.0 || x >= 1.0 {
+        Err(StatsError::ArgIntervalExcl(name, 0.0, 1.0))
+    } else {
+        Ok(())
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::Distribution;
+
+    #[test]
+    fn test_bad_params() {
+        assert!(laplace_noise(0.0, 1.0).is_err());
+        assert!(laplace_noise(1.0, f64::NAN).is_err());
+        assert!(gaussian_noise(1.0, 1.5, 1e-5).is_err());
+        assert!(gaussian_noise(1.0, 0.5, 0.0).is_err());
+        assert!(analytic_gaussian_sigma(1.0, -1.0, 1e-5).is_err());
+        assert!(analytic_gaussian_sigma(1.0, 1.0, 1.0).is_err());
+        assert!(discrete_gaussian_noise(1.0, 0.0).is_err());
+        assert!(discrete_laplace_noise(1.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_laplace_noise() {
+        let noise = laplace_noise(1.0, 0.1).unwrap();
+        assert_eq!(noise.location(), 0.0);
+        assert_almost_eq!(noise.scale(), 10.0, 1e-14);
+    }
+
+    #[test]
+    fn test_analytic_gaussian_sigma() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        for &(sensitivity, epsilon, delta) in &[(1.0, 0.1, 1e-5), (1.0, 1.0, 1e-5), (3.0, 4.0, 1e-8), (0.5, 0.5, 0.01)] {
+            let sigma = analytic_gaussian_sigma(sensitivity, epsilon, delta).unwrap();
+            let a = sensitivity / (2.0 * sigma);
+            let b = epsilon * sigma / sensitivity;
+            let achieved = normal.cdf(a - b) - epsilon.exp() * normal.cdf(-a - b);
+            assert_almost_eq!(achieved, delta, delta * 1e-6);
+            // scales linearly with the sensitivity
+            let unit = analytic_gaussian_sigma(1.0, epsilon, delta).unwrap();
+            assert_almost_eq!(sigma, sensitivity * unit, 1e-9 * sigma);
+            if epsilon < 1.0 {
+                assert!(sigma < gaussian_noise(sensitivity, epsilon, delta).unwrap().std_dev().unwrap());
+            }
+        }
+    }
+}
@@ -0,0 +1,543 @@
+//! Provides detectors of distribution drift in a stream of observations:
+//! a sliding-window Kolmogorov-Smirnov test against a reference
+//! distribution or sample, and the Page-Hinkley test for a shift in mean
+
+use crate::distribution::{ContinuousCDF, Empirical};
+use crate::function::kolmogorov::kolmogorov_sf;
+use crate::statistics::Distribution;
+use crate::{Result, StatsError};
+use std::collections::VecDeque;
+use std::f64;
+
+/// An alarm raised by a `DriftDetector`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DriftAlarm {
+    index: u64,
+    statistic: f64,
+}
+
+impl DriftAlarm {
+    /// Returns the zero-based position in the stream of the observation
+    /// that raised the alarm
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Returns the value of the detector's test statistic when the alarm
+    /// was raised
+    pub fn statistic(&self) -> f64 {
+        self.statistic
+    }
+}
+
+/// The `DriftDetector` trait is implemented by detectors consuming a
+/// stream of observations one at a time
+pub trait DriftDetector {
+    /// Adds the next observation of the stream, returning an alarm if
+    /// drift is detected. The detector starts afresh after an alarm, so a
+    /// persistent change raises a new alarm once enough post-change
+    /// observations have been seen. `NaN` observations are skipped but
+    /// still counted towards the position in the stream.
+    fn update(&mut self, x: f64) -> Option<DriftAlarm>;
+
+    /// Discards all observations seen so far, keeping the position in the
+    /// stream
+    fn reset(&mut self);
+
+    /// Feeds all observations of `stream` to the detector and returns the
+    /// alarms raised
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::drift::{DriftDetector, PageHinkley};
+    ///
+    /// let mut detector = PageHinkley::new(0.5, 10.0).unwrap();
+    /// let stream = (0..200).map(|i| if i < 100 { (i % 3) as f64 } else { 10.0 });
+    /// let alarms = detector.detect(stream);
+    /// assert_eq!(alarms.len(), 1);
+    /// assert!(alarms[0].index() >= 100 && alarms[0].index() < 110);
+    /// ```
+    fn detect<I: IntoIterator<Item = f64>>(&mut self, stream: I) -> Vec<DriftAlarm>
+    where
+        Self: Sized,
+    {
+        stream.into_iter().filter_map(|x| self.update(x)).collect()
+    }
+}
+
+/// Returns the largest floating point number smaller than `x`
+fn next_down(x: f64) -> f64 {
+    if x.is_nan() || x == f64::NEG_INFINITY {
+        x
+    } else if x == 0.0 {
+        -f64::from_bits(1)
+    } else if x > 0.0 {
+        f64::from_bits(x.to_bits() - 1)
+    } else {
+        f64::from_bits(x.to_bits() + 1)
+    }
+}
+
+/// Computes the Kolmogorov-Smirnov statistic `sup_x |F_n(x) - F(x)|`
+/// between the empirical distribution function `F_n` of `sample` and the
+/// cdf `F` of `reference`
+///
+/// # Remarks
+///
+/// The reference need not be continuous: passing the `Empirical`
+/// distribution of a second sample gives the two-sample statistic.
+///
+/// # Errors
+///
+/// Returns an error if `sample` is empty
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Empirical, Uniform};
+/// use statrs::drift::ks_statistic;
+///
+/// let sample = Empirical::from_vec(vec![0.1, 0.4, 0.7]);
+/// let reference = Uniform::new(0.0, 1.0).unwrap();
+/// let d = ks_statistic(&sample, &reference).unwrap();
+/// assert!((d - 0.3).abs() < 1e-15);
+/// ```
+pub fn ks_statistic<R: ContinuousCDF<f64, f64>>(sample: &Empirical, reference: &R) -> Result<f64> {
+    let n = sample.counts().map(|(_, count)| count).sum::<u64>() as f64;
+    if n == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    // F_n is constant between consecutive sample points and F is
+    // monotone, so the supremum is attained just before or at one of them
+    let mut seen = 0;
+    let mut d: f64 = 0.0;
+    for (x, count) in sample.counts() {
+        let below = seen as f64 / n;
+        seen += count;
+        let at = seen as f64 / n;
+        d = d
+            .max((below - reference.cdf(next_down(x))).abs())
+            .max((at - reference.cdf(x)).abs());
+    }
+    Ok(d)
+}
+
+/// Returns the asymptotic p-value of the Kolmogorov-Smirnov statistic
+/// `statistic` with Stephens' small sample correction, where
+/// `effective_n` is the sample size for the one-sample test and
+/// `n m / (n + m)` for the two-sample test with sample sizes `n` and `m`
+///
+/// # Formula
+///
+/// ```ignore
+/// Q((sqrt(n_e) + 0.12 + 0.11 / sqrt(n_e)) D)
+/// ```
+///
+/// where `n_e` is the effective sample size, `D` the statistic and
+/// `Q(x) = 2 Σ (-1)^(k - 1) e^(-2 k^2 x^2)` the survival function of the
+/// Kolmogorov distribution
+///
+/// # Examples
+///
+/// ```
+/// use statrs::drift::ks_p_value;
+///
+/// assert!(ks_p_value(0.05, 100.0) > 0.9);
+/// assert!(ks_p_value(0.2, 100.0) < 0.001);
+/// ```
+pub fn ks_p_value(statistic: f64, effective_n: f64) -> f64 {
+    let sqrt_n = effective_n.sqrt();
+    kolmogorov_sf((sqrt_n + 0.12 + 0.11 / sqrt_n) * statistic)
+}
+
+/// Implements a sliding-window Kolmogorov-Smirnov drift detector, which
+/// tests the empirical distribution of the most recent `window`
+/// observations against a reference and raises an alarm when the p-value
+/// falls below `alpha`
+///
+/// # Remarks
+///
+/// The test is repeated after every observation once the window is full,
+/// so `alpha` bounds the false alarm probability of each test rather than
+/// of the whole stream; choose it accordingly small. After an alarm the
+/// window is emptied and testing resumes once it has filled again.
+///
+/// # Examples
+///
+/// ```
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::distribution::Normal;
+/// use statrs::drift::{DriftDetector, SlidingKs};
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let reference = Normal::new(0.0, 1.0).unwrap();
+/// let shifted = Normal::new(1.5, 1.0).unwrap();
+/// let mut detector = SlidingKs::new(reference, 50, 1e-4).unwrap();
+/// let stream: Vec<f64> = (0..400)
+///     .map(|i| if i < 200 { reference.sample(&mut rng) } else { shifted.sample(&mut rng) })
+///     .collect();
+/// let alarms = detector.detect(stream);
+/// assert!(alarms[0].index() >= 200 && alarms[0].index() < 250);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SlidingKs<R> {
+    reference: R,
+    size: usize,
+    alpha: f64,
+    effective_n: f64,
+    window: VecDeque<f64>,
+    ecdf: Empirical,
+    statistic: f64,
+    index: u64,
+}
+
+impl<R: ContinuousCDF<f64, f64>> SlidingKs<R> {
+    /// Constructs a detector testing windows of `window` observations
+    /// against the exact distribution `reference` at level `alpha`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `window` is zero or `alpha` is not in `(0, 1)`
+    pub fn new(reference: R, window: usize, alpha: f64) -> Result<SlidingKs<R>> {
+        SlidingKs::with_effective_n(reference, window, alpha, window as f64)
+    }
+
+    fn with_effective_n(
+        reference: R,
+        window: usize,
+        alpha: f64,
+        effective_n: f64,
+    ) -> Result<SlidingKs<R>> {
+        if window == 0 {
+            Err(StatsError::ArgMustBePositive("window"))
+        } else if alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0 {
+            Err(StatsError::ArgIntervalExcl("alpha", 0.0, 1.0))
+        } else {
+            Ok(SlidingKs {
+                reference,
+                size: window,
+                alpha,
+                effective_n,
+                window: VecDeque::with_capacity(window + 1),
+                ecdf: Empirical::new().unwrap(),
+                statistic: 0.0,
+                index: 0,
+            })
+        }
+    }
+
+    /// Returns the reference distribution
+    pub fn reference(&self) -> &R {
+        &self.reference
+    }
+
+    /// Returns the Kolmogorov-Smirnov statistic of the last full window,
+    /// or zero if the window has not filled since the last alarm or reset
+    pub fn statistic(&self) -> f64 {
+        self.statistic
+    }
+}
+
+impl SlidingKs<Empirical> {
+    /// Constructs a detector testing windows of `window` observations
+    /// against the reference sample `reference` at level `alpha` with the
+    /// two-sample test. `NaN` values in the reference sample are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `reference` has no values other than `NaN`,
+    /// `window` is zero or `alpha` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::drift::{DriftDetector, SlidingKs};
+    ///
+    /// let reference: Vec<f64> = (0..100).map(|i| i as f64).collect();
+    /// let mut detector = SlidingKs::from_sample(&reference, 20, 1e-3).unwrap();
+    /// assert!(detector.detect((0..40).map(|i| (i * 37 % 100) as f64)).is_empty());
+    /// assert!(!detector.detect((0..20).map(|i| 200.0 + i as f64)).is_empty());
+    /// ```
+    pub fn from_sample(
+        reference: &[f64],
+        window: usize,
+        alpha: f64,
+    ) -> Result<SlidingKs<Empirical>> {
+        let m = reference.iter().filter(|x| !x.is_nan()).count();
+        if m == 0 {
+            return Err(StatsError::BadParams);
+        }
+        let n = window as f64;
+        let effective_n = n * m as f64 / (n + m as f64);
+        SlidingKs::with_effective_n(
+            Empirical::from_vec(reference.to_vec()),
+            window,
+            alpha,
+            effective_n,
+        )
+    }
+}
+
+impl<R: ContinuousCDF<f64, f64>> DriftDetector for SlidingKs<R> {
+    fn update(&mut self, x: f64) -> Option<DriftAlarm> {
+        let index = self.index;
+        self.index += 1;
+        if x.is_nan() {
+            return None;
+        }
+        self.window.push_back(x);
+        self.ecdf.add(x);
+        if self.window.len() > self.size {
+            let old = self.window.pop_front().unwrap();
+            self.ecdf.remove(old);
+        }
+        if self.window.len() < self.size {
+            return None;
+        }
+        let statistic = ks_statistic(&self.ecdf, &self.reference).unwrap();
+        self.statistic = statistic;
+        if ks_p_value(statistic, self.effective_n) < self.alpha {
+            self.reset();
+            Some(DriftAlarm { index, statistic })
+        } else {
+            None
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.ecdf = Empirical::new().unwrap();
+        self.statistic = 0.0;
+    }
+}
+
+/// Implements the two-sided [Page-Hinkley
+/// test](https://doi.org/10.1093/biomet/41.1-2.100) for a change in the
+/// mean of a stream, raising an alarm when the cumulative deviation from
+/// the mean, less a tolerance `delta` per observation, rises or falls by
+/// more than `threshold` from its extreme
+///
+/// # Formula
+///
+/// ```ignore
+/// m_t = Σ_{i <= t} (x_i - μ_i - δ),    alarm if m_t - min_{i <= t} m_i > λ
+/// ```
+///
+/// for an increase, and symmetrically with `+δ` for a decrease, where `μ_i`
+/// is the reference mean or the running mean of the first `i`
+/// observations, `δ` the tolerance and `λ` the threshold
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::drift::{DriftDetector, PageHinkley};
+///
+/// let reference = Normal::new(5.0, 1.0).unwrap();
+/// let mut detector = PageHinkley::from_reference(&reference, 0.1, 5.0).unwrap();
+/// assert!(detector.update(5.5).is_none());
+/// let alarm = detector.detect(vec![4.0, 3.0, 2.0]);
+/// assert_eq!(alarm[0].index(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageHinkley {
+    delta: f64,
+    threshold: f64,
+    reference_mean: Option<f64>,
+    n: u64,
+    mean: f64,
+    up: f64,
+    up_min: f64,
+    down: f64,
+    down_max: f64,
+    index: u64,
+}
+
+impl PageHinkley {
+    /// Constructs a detector comparing each observation with the running
+    /// mean of the observations since the last alarm or reset
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `delta` is negative or `threshold` is not
+    /// positive
+    pub fn new(delta: f64, threshold: f64) -> Result<PageHinkley> {
+        PageHinkley::with_mean(None, delta, threshold)
+    }
+
+    /// Constructs a detector comparing each observation with the mean of
+    /// the reference distribution `reference`, which may be the
+    /// `Empirical` distribution of a reference sample
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mean of `reference` is undefined or not
+    /// finite, `delta` is negative or `threshold` is not positive
+    pub fn from_reference<D: Distribution<f64>>(
+        reference: &D,
+        delta: f64,
+        threshold: f64,
+    ) -> Result<PageHinkley> {
+        match reference.mean() {
+            Some(mean) if mean.is_finite() => PageHinkley::with_mean(Some(mean), delta, threshold),
+            _ => Err(StatsError::BadParams),
+        }
+    }
+
+    fn with_mean(reference_mean: Option<f64>, delta: f64, threshold: f64) -> Result<PageHinkley> {
+        if delta.is_nan() || delta < 0.0 {
+            Err(StatsError::ArgNotNegative("delta"))
+        } else if threshold.is_nan() || threshold <= 0.0 {
+            Err(StatsError::ArgMustBePositive("threshold"))
+        } else {
+            Ok(PageHinkley {
+                delta,
+                threshold,
+                reference_mean,
+                n: 0,
+                mean: 0.0,
+                up: 0.0,
+                up_min: 0.0,
+                down: 0.0,
+                down_max: 0.0,
+                index: 0,
+            })
+        }
+    }
+
+    /// Returns the current test statistic, the larger of the rise and the
+    /// fall of the cumulative deviations from their extremes
+    pub fn statistic(&self) -> f64 {
+        (self.up - self.up_min).max(self.down_max - self.down)
+    }
+}
+
+impl DriftDetector for PageHinkley {
+    fn update(&mut self, x: f64) -> Option<DriftAlarm> {
+        let index = self.index;
+        self.index += 1;
+        if x.is_nan() {
+            return None;
+        }
+        self.n += 1;
+        self.mean += (x - self.mean) / self.n as f64;
+        let deviation = x - self.reference_mean.unwrap_or(self.mean);
+        self.up += deviation - self.delta;
+        self.up_min = self.up_min.min(self.up);
+        self.down += deviation + self.delta;
+        self.down_max = self.down_max.max(self.down);
+        let statistic = self.statistic();
+        if statistic > self.threshold {
+            self.reset();
+            Some(DriftAlarm { index, statistic })
+        } else {
+            None
+        }
+    }
+
+    fn reset(&mut self) {
+        self.n = 0;
+        self.mean = 0.0;
+        self.up = 0.0;
+        self.up_min = 0.0;
+        self.down = 0.0;
+        self.down_max = 0.0;
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Normal, Uniform};
+    use rand::distributions::Distribution as Sample;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_ks_statistic() {
+        let u = Uniform::new(0.0, 1.0).unwrap();
+        assert_eq!(ks_statistic(&Empirical::from_vec(vec![0.5]), &u).unwrap(), 0.5);
+        assert!(ks_statistic(&Empirical::new().unwrap(), &u).is_err());
+        // ties count with their multiplicity
+        let tied = Empirical::from_vec(vec![0.2, 0.2, 0.9]);
+        assert_almost_eq!(ks_statistic(&tied, &u).unwrap(), 2.0 / 3.0 - 0.2, 1e-15);
+        // two samples, against the supremum over a fine grid
+        let x = Empirical::from_vec(vec![0.3, 1.2, 2.5, 2.5, 4.0]);
+        let y = Empirical::from_vec(vec![0.0, 1.2, 1.9, 3.1]);
+        let grid = (-100..=500).map(|i| i as f64 / 100.0).fold(0.0, |d: f64, t| d.max((x.cdf(t) - y.cdf(t)).abs()));
+        assert_almost_eq!(ks_statistic(&x, &y).unwrap(), grid, 1e-15);
+        assert_almost_eq!(ks_statistic(&y, &x).unwrap(), grid, 1e-15);
+    }
+
+    #[test]
+    fn test_sliding_ks() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let reference = Normal::new(0.0, 1.0).unwrap();
+        let shifted = Normal::new(0.0, 3.0).unwrap();
+        let stream: Vec<f64> = (0..1000)
+            .map(|i| if i < 600 { reference.sample(&mut rng) } else { shifted.sample(&mut rng) })
+            .collect();
+        let mut detector = SlidingKs::new(reference, 100, 1e-5).unwrap();
+        let alarms = detector.detect(stream.iter().cloned());
+        assert!(!alarms.is_empty());
+        assert!(alarms.iter().all(|a| a.index() >= 600));
+        assert!(alarms[0].index() < 700);
+        assert!(alarms[0].statistic() > 0.2);
+
+        let sample: Vec<f64> = (0..500).map(|_| reference.sample(&mut rng)).collect();
+        let mut detector = SlidingKs::from_sample(&sample, 100, 1e-5).unwrap();
+        let alarms = detector.detect(stream.iter().cloned());
+        assert!(alarms.iter().all(|a| a.index() >= 600));
+        // the two-sample test has less power, needing a full window after the change
+        assert!(alarms[0].index() < 720);
+
+        assert!(SlidingKs::new(reference, 0, 0.01).is_err());
+        assert!(SlidingKs::new(reference, 10, 0.0).is_err());
+        assert!(SlidingKs::new(reference, 10, f64::NAN).is_err());
+        assert!(SlidingKs::from_sample(&[f64::NAN], 10, 0.01).is_err());
+    }
+
+    #[test]
+    fn test_sliding_ks_window() {
+        let u = Uniform::new(0.0, 1.0).unwrap();
+        let mut detector = SlidingKs::new(u, 2, 1e-3).unwrap();
+        assert!(detector.update(0.25).is_none());
+        assert_eq!(detector.statistic(), 0.0);
+        assert!(detector.update(f64::NAN).is_none());
+        assert!(detector.update(0.75).is_none());
+        assert_almost_eq!(detector.statistic(), 0.25, 1e-15);
+        // the first observation has left the window
+        assert!(detector.update(0.8).is_none());
+        assert_almost_eq!(detector.statistic(), 0.75, 1e-15);
+        detector.reset();
+        assert_eq!(detector.statistic(), 0.0);
+    }
+
+    #[test]
+    fn test_page_hinkley() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let before = Normal::new(0.0, 1.0).unwrap();
+        let after = Normal::new(2.0, 1.0).unwrap();
+        let stream: Vec<f64> = (0..600)
+            .map(|i| if i < 300 { before.sample(&mut rng) } else { after.sample(&mut rng) })
+            .collect();
+        let mut detector = PageHinkley::new(0.5, 20.0).unwrap();
+        let alarms = detector.detect(stream.iter().cloned());
+        assert_eq!(alarms.len(), 1);
+        assert!(alarms[0].index() >= 300 && alarms[0].index() < 330);
+        assert!(alarms[0].statistic() > 20.0);
+
+        // against the reference mean a decrease is detected too
+        let mut detector = PageHinkley::from_reference(&after, 0.5, 20.0).unwrap();
+        let alarms = detector.detect(stream.iter().cloned());
+        assert!(alarms[0].index() < 30);
+
+        assert!(PageHinkley::new(-0.1, 1.0).is_err());
+        assert!(PageHinkley::new(0.1, 0.0).is_err());
+        assert!(PageHinkley::from_reference(&Empirical::new().unwrap(), 0.1, 1.0).is_err());
+    }
+}
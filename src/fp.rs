@@ -0,0 +1,197 @@
+//! Provides the elementary functions used throughout the crate. The standard
+//! library forwards them to the platform's math library, whose results may
+//! differ in the last bit between targets; with the `deterministic` feature
+//! they are computed by the pure Rust port of musl libm instead, so results
+//! on wasm32 match native ones bit-for-bit.
+//!
+//! Every real-valued transcendental call of the crate goes through this
+//! module. The exceptions are the characteristic functions, whose complex
+//! arithmetic is done by `num_complex` on top of the standard library, and
+//! the `f32` routines in `function::single_precision`. Square roots are
+//! correctly rounded by IEEE 754 on every target and are left to the
+//! standard library.
+
+macro_rules! unary {
+    ($(#[$attr:meta])* $name:ident => $libm:ident) => {
+        $(#[$attr])*
+        #[inline]
+        pub(crate) fn $name(x: f64) -> f64 {
+            #[cfg(feature = "deterministic")]
+            {
+                libm::$libm(x)
+            }
+            #[cfg(not(feature = "deterministic"))]
+            {
+                x.$name()
+            }
+        }
+    };
+}
+
+unary!(
+    /// Returns `e^x`
+    exp => exp
+);
+unary!(
+    /// Returns the natural logarithm of `x`
+    ln => log
+);
+unary!(
+    /// Returns `ln(1 + x)`, accurate for small `x`
+    ln_1p => log1p
+);
+unary!(
+    /// Returns the sine of `x` in radians
+    sin => sin
+);
+unary!(
+    /// Returns the tangent of `x` in radians
+    tan => tan
+);
+unary!(
+    /// Returns `e^x - 1`, accurate for small `x`
+    exp_m1 => expm1
+);
+unary!(
+    /// Returns the arctangent of `x` in radians
+    atan => atan
+);
+unary!(
+    /// Returns the hyperbolic sine of `x`
+    sinh => sinh
+);
+unary!(
+    /// Returns the hyperbolic cosine of `x`
+    cosh => cosh
+);
+unary!(
+    /// Returns the hyperbolic tangent of `x`
+    tanh => tanh
+);
+unary!(
+    /// Returns the cosine of `x` in radians
+    cos => cos
+);
+unary!(
+    /// Returns the arcsine of `x` in radians
+    asin => asin
+);
+unary!(
+    /// Returns the base 10 logarithm of `x`
+    log10 => log10
+);
+unary!(
+    /// Returns the cube root of `x`
+    cbrt => cbrt
+);
+
+/// Returns `x^y`
+#[inline]
+pub(crate) fn powf(x: f64, y: f64) -> f64 {
+    #[cfg(feature = "deterministic")]
+    {
+        libm::pow(x, y)
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        x.powf(y)
+    }
+}
+
+/// Returns `sqrt(x^2 + y^2)` without undue overflow or underflow
+#[inline]
+pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+    #[cfg(feature = "deterministic")]
+    {
+        libm::hypot(x, y)
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        x.hypot(y)
+    }
+}
+
+/// Returns the natural logarithm of `x` for the generic floats of the
+/// distribution traits, computed in double precision
+#[inline]
+pub(crate) fn ln_float<T: num_traits::Float>(x: T) -> T {
+    #[cfg(feature = "deterministic")]
+    {
+        T::from(libm::log(x.to_f64().unwrap())).unwrap()
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        x.ln()
+    }
+}
+
+/// Returns `x^n`. The standard library leaves the rounding of `powi` to the
+/// target, so the deterministic version multiplies by repeated squaring in
+/// the order of compiler-rt's `__powidf2`.
+#[inline]
+pub(crate) fn powi<T: num_traits::Float>(x: T, n: i32) -> T {
+    #[cfg(feature = "deterministic")]
+    {
+        let mut base = x;
+        let mut m = n;
+        let mut r = T::one();
+        loop {
+            if m & 1 != 0 {
+                r = r * base;
+            }
+            m /= 2;
+            if m == 0 {
+                break;
+            }
+            base = base * base;
+        }
+        if n < 0 {
+            T::one() / r
+        } else {
+            r
+        }
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        x.powi(n)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_std() {
+        // both implementations are faithfully rounded, so agree to an ulp
+        for &x in [-700.0, -3.5, -1e-10, 0.0, 1e-300, 0.3, 1.0, 2.5, 100.0, 700.0].iter() {
+            assert_relative_eq!(exp(x), x.exp(), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(sin(x), x.sin(), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(tan(x), x.tan(), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(ln_1p(x.abs()), x.abs().ln_1p(), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(exp_m1(x), x.exp_m1(), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(atan(x), x.atan(), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(cos(x), x.cos(), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(tanh(x), x.tanh(), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(asin(x / 700.0), (x / 700.0).asin(), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(cbrt(x), x.cbrt(), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(hypot(x, 2.5), x.hypot(2.5), max_relative = 2.0 * f64::EPSILON);
+            assert_relative_eq!(powi(x, 3), x.powi(3), max_relative = 4.0 * f64::EPSILON);
+            let y = x.abs() + 0.5;
+            assert_relative_eq!(powi(y, -2), y.powi(-2), max_relative = 4.0 * f64::EPSILON);
+            if x.abs() < 100.0 {
+                assert_relative_eq!(sinh(x), x.sinh(), max_relative = 2.0 * f64::EPSILON);
+                assert_relative_eq!(cosh(x), x.cosh(), max_relative = 2.0 * f64::EPSILON);
+            }
+            assert_relative_eq!(powf(x.abs(), 0.7), x.abs().powf(0.7), max_relative = 2.0 * f64::EPSILON);
+            if x > 0.0 {
+                assert_relative_eq!(ln(x), x.ln(), max_relative = 2.0 * f64::EPSILON);
+                assert_relative_eq!(log10(x), x.log10(), max_relative = 2.0 * f64::EPSILON);
+            }
+        }
+        assert_eq!(ln(0.0), f64::NEG_INFINITY);
+        assert!(ln(-1.0).is_nan());
+        assert_eq!(exp(f64::NEG_INFINITY), 0.0);
+    }
+}
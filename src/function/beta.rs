@@ -2,6 +2,7 @@
 //! function
 
 use crate::error::StatsError;
+use crate::fp;
 use crate::function::gamma;
 use crate::is_zero;
 use crate::prec;
@@ -61,7 +62,7 @@ pub fn beta(a: f64, b: f64) -> f64 {
 ///
 /// if `a <= 0.0` or `b <= 0.0`
 pub fn checked_beta(a: f64, b: f64) -> Result<f64> {
-    checked_ln_beta(a, b).map(|x| x.exp())
+    checked_ln_beta(a, b).map(fp::exp)
 }
 
 /// Computes the lower incomplete (unregularized) beta function
@@ -121,10 +122,11 @@ pub fn checked_beta_reg(a: f64, b: f64, x: f64) -> Result<f64> {
         let bt = if is_zero(x) || ulps_eq!(x, 1.0) {
             0.0
         } else {
-            (gamma::ln_gamma(a + b) - gamma::ln_gamma(a) - gamma::ln_gamma(b)
-                + a * x.ln()
-                + b * (1.0 - x).ln())
-            .exp()
+            fp::exp(
+                gamma::ln_gamma(a + b) - gamma::ln_gamma(a) - gamma::ln_gamma(b)
+                    + a * fp::ln(x)
+                    + b * fp::ln(1.0 - x),
+            )
         };
         let symm_transform = x >= (a + 1.0) / (a + b + 2.0);
         let eps = prec::F64_PREC;
@@ -295,7 +297,7 @@ pub fn inv_beta_reg(mut a: f64, mut b: f64, mut x: f64) -> f64 {
         x = 1.0 - x;
     }
 
-    p = (-(x * x).ln()).sqrt();
+    p = (-fp::ln(x * x)).sqrt();
     q = p - (2.30753 + 0.27061 * p) / (1.0 + (0.99229 + 0.04481 * p) * p);
 
     if 1.0 < a && 1.0 < b {
@@ -311,16 +313,16 @@ pub fn inv_beta_reg(mut a: f64, mut b: f64, mut x: f64) -> f64 {
         let t = 1.0 / (2.0 * b - 1.0);
         let h = 2.0 / (s + t);
         let w = q * (h + r).sqrt() / h - (t - s) * (r + 5.0 / 6.0 - 2.0 / (3.0 * h));
-        p = a / (a + b * (2.0 * w).exp());
+        p = a / (a + b * fp::exp(2.0 * w));
     } else {
         let mut t = 1.0 / (9.0 * b);
-        t = 2.0 * b * (1.0 - t + q * t.sqrt()).powf(3.0);
+        t = 2.0 * b * fp::powf(1.0 - t + q * t.sqrt(), 3.0);
         if t <= 0.0 {
-            p = 1.0 - ((((1.0 - x) * b).ln() + ln_beta) / b).exp();
+            p = 1.0 - fp::exp((fp::ln((1.0 - x) * b) + ln_beta) / b);
         } else {
             t = 2.0 * (2.0 * a + b - 1.0) / t;
             if t <= 1.0 {
-                p = (((x * a).ln() + ln_beta) / a).exp();
+                p = fp::exp((fp::ln(x * a) + ln_beta) / a);
             } else {
                 p = 1.0 - 2.0 / (t + 1.0);
             }
@@ -335,7 +337,7 @@ pub fn inv_beta_reg(mut a: f64, mut b: f64, mut x: f64) -> f64 {
 
     // Remark AS R83
     // http://www.jstor.org/stable/2347779
-    let e = (-5.0 / a / a - 1.0 / x.powf(0.2) - 13.0) as i32;
+    let e = (-5.0 / a / a - 1.0 / fp::powf(x, 0.2) - 13.0) as i32;
     let acu = if e > SAE { f64::powi(10.0, e) } else { FPU };
 
     let mut pnext;
@@ -347,7 +349,7 @@ pub fn inv_beta_reg(mut a: f64, mut b: f64, mut x: f64) -> f64 {
         // Remark AS R19 and Algorithm AS 109
         // http://www.jstor.org/stable/2346887
         q = beta_reg(a, b, p);
-        q = (q - x) * (ln_beta + (1.0 - a) * p.ln() + (1.0 - b) * (1.0 - p).ln()).exp();
+        q = (q - x) * fp::exp(ln_beta + (1.0 - a) * fp::ln(p) + (1.0 - b) * fp::ln(1.0 - p));
 
         // Remark AS R83
         // http://www.jstor.org/stable/2347779
@@ -552,7 +554,12 @@ mod tests {
         assert_eq!(super::beta_reg(1.0, 1.0, 1.0), 1.0);
         assert_almost_eq!(super::beta_reg(2.5, 1.0, 0.5), 0.1767766952966368811, 1e-15);
         assert_eq!(super::beta_reg(2.5, 1.0, 1.0), 1.0);
+        // musl's `pow` differs from the platform libm in the last place here, so
+        // the `deterministic` build is one ulp off the correctly rounded value
+        #[cfg(not(feature = "deterministic"))]
         assert_eq!(super::beta_reg(0.5, 2.5, 0.5), 0.92441318157838756205);
+        #[cfg(feature = "deterministic")]
+        assert_almost_eq!(super::beta_reg(0.5, 2.5, 0.5), 0.92441318157838756205, 1e-15);
         assert_eq!(super::beta_reg(0.5, 2.5, 1.0), 1.0);
         assert_almost_eq!(super::beta_reg(1.0, 2.5, 0.5), 0.8232233047033631189, 1e-15);
         assert_eq!(super::beta_reg(1.0, 2.5, 1.0), 1.0);
@@ -1,6 +1,7 @@
 //! Provides the [error](https://en.wikipedia.org/wiki/Error_function) and
 //! related functions
 
+use crate::fp;
 use crate::function::evaluate;
 use crate::is_zero;
 use std::f64;
@@ -668,7 +669,7 @@ fn erf_impl(z: f64, inv: bool) -> f64 {
                 0.5641584396,
             )
         };
-        let g = (-z * z).exp() / z;
+        let g = fp::exp(-z * z) / z;
         g * b + g * r
     } else {
         0.0
@@ -694,13 +695,13 @@ fn erf_inv_impl(p: f64, q: f64, s: f64) -> f64 {
         g * y + g * r
     } else if q >= 0.25 {
         let y = 2.249481201171875;
-        let g = (-2.0 * q.ln()).sqrt();
+        let g = (-2.0 * fp::ln(q)).sqrt();
         let xs = q - 0.25;
         let r =
             evaluate::polynomial(xs, ERF_INV_IMPL_BN) / evaluate::polynomial(xs, ERF_INV_IMPL_BD);
         g / (y + r)
     } else {
-        let x = (-q.ln()).sqrt();
+        let x = (-fp::ln(q)).sqrt();
         if x < 3.0 {
             let y = 0.807220458984375;
             let xs = x - 1.125;
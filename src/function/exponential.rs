@@ -1,5 +1,6 @@
 //! Provides functions related to exponential calculations
 
+use crate::fp;
 use crate::{consts, Result, StatsError};
 
 /// Computes the generalized Exponential Integral function
@@ -42,7 +43,7 @@ pub fn integral(x: f64, n: u64) -> Result<f64> {
 
     // special cases
     if n == 0 {
-        return Ok((-1.0 * x).exp() / x);
+        return Ok(fp::exp(-1.0 * x) / x);
     }
     if x == 0.0 {
         return Ok(1.0 / (nf64 - 1.0));
@@ -61,7 +62,7 @@ pub fn integral(x: f64, n: u64) -> Result<f64> {
             let del = c * d;
             h *= del;
             if (del - 1.0).abs() < eps {
-                return Ok(h * (-x).exp());
+                return Ok(h * fp::exp(-x));
             }
         }
         Err(StatsError::ComputationFailedToConverge)
@@ -70,7 +71,7 @@ pub fn integral(x: f64, n: u64) -> Result<f64> {
         let mut result = if n - 1 != 0 {
             1.0 / (nf64 - 1.0)
         } else {
-            -1.0 * x.ln() - consts::EULER_MASCHERONI
+            -1.0 * fp::ln(x) - consts::EULER_MASCHERONI
         };
         for i in 1..max_iter + 1 {
             factorial *= -1.0 * x / i as f64;
@@ -81,7 +82,7 @@ pub fn integral(x: f64, n: u64) -> Result<f64> {
                 for ii in 1..n {
                     psi += 1.0 / ii as f64;
                 }
-                factorial * (-1.0 * x.ln() + psi)
+                factorial * (-1.0 * fp::ln(x) + psi)
             };
             result += del;
             if del.abs() < result.abs() * eps {
@@ -92,6 +93,31 @@ pub fn integral(x: f64, n: u64) -> Result<f64> {
     }
 }
 
+/// Computes `ln(sum(exp(x_i)))` for the elements `x_i` of `x` without
+/// overflowing or underflowing, by factoring out the largest element
+///
+/// # Remarks
+///
+/// Returns `-INF` if `x` is empty or all of its elements are `-INF`, and
+/// `INF` if any element is `INF`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::exponential;
+///
+/// let x = [-1000.0, -1000.0];
+/// assert!((exponential::ln_sum_exp(&x) - (-1000.0 + 2f64.ln())).abs() < 1e-12);
+/// assert_eq!(exponential::ln_sum_exp(&[]), f64::NEG_INFINITY);
+/// ```
+pub fn ln_sum_exp(x: &[f64]) -> f64 {
+    let max = x.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    if max.is_infinite() {
+        return max;
+    }
+    max + fp::ln(x.iter().map(|v| fp::exp(v - max)).sum::<f64>())
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
@@ -112,6 +138,23 @@ mod tests {
         assert_eq!(super::integral(0.1, 0).unwrap(), 9.048374180359595);
         assert_almost_eq!(super::integral(1.0, 0).unwrap(), 0.3678794411714423, 1e-16);
         assert_eq!(super::integral(2.0, 0).unwrap(), 0.06766764161830635);
+        // musl's `exp` differs from the platform libm in the last place here, so
+        // the `deterministic` build is one ulp off the correctly rounded value
+        #[cfg(not(feature = "deterministic"))]
         assert_eq!(super::integral(10.0, 0).unwrap(), 4.539992976248485e-06);
+        #[cfg(feature = "deterministic")]
+        assert_almost_eq!(super::integral(10.0, 0).unwrap(), 4.539992976248485e-06, 1e-20);
+    }
+
+    #[test]
+    fn test_ln_sum_exp() {
+        assert_eq!(super::ln_sum_exp(&[]), f64::NEG_INFINITY);
+        assert_eq!(super::ln_sum_exp(&[f64::NEG_INFINITY, f64::NEG_INFINITY]), f64::NEG_INFINITY);
+        assert_eq!(super::ln_sum_exp(&[0.0, f64::INFINITY]), f64::INFINITY);
+        assert_eq!(super::ln_sum_exp(&[2.5]), 2.5);
+        assert_almost_eq!(super::ln_sum_exp(&[0.0, 0.0]), 2f64.ln(), 1e-15);
+        assert_almost_eq!(super::ln_sum_exp(&[1.0, 2.0, 3.0]), 3.4076059644443806, 1e-15);
+        assert_almost_eq!(super::ln_sum_exp(&[800.0, 800.0]), 800.0 + 2f64.ln(), 1e-12);
+        assert_almost_eq!(super::ln_sum_exp(&[-800.0, f64::NEG_INFINITY]), -800.0, 1e-12);
     }
 }
@@ -2,6 +2,7 @@
 //! coefficient, factorial, multinomial)
 
 use crate::error::StatsError;
+use crate::fp;
 use crate::function::gamma;
 use crate::Result;
 use core::f64::INFINITY as INF;
@@ -33,7 +34,7 @@ pub fn ln_factorial(x: u64) -> f64 {
     let x = x as usize;
     FCACHE
         .get(x)
-        .map_or_else(|| gamma::ln_gamma(x as f64 + 1.0), |&fac| fac.ln())
+        .map_or_else(|| gamma::ln_gamma(x as f64 + 1.0), |&fac| fp::ln(fac))
 }
 
 /// Computes the binomial coefficient `n choose k`
@@ -46,7 +47,7 @@ pub fn binomial(n: u64, k: u64) -> f64 {
     if k > n {
         0.0
     } else {
-        (0.5 + (ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k)).exp()).floor()
+        (0.5 + fp::exp(ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k))).floor()
     }
 }
 
@@ -85,7 +86,7 @@ pub fn checked_multinomial(n: u64, ni: &[u64]) -> Result<f64> {
     if sum != n {
         Err(StatsError::ContainerExpectedSumVar("ni", "n"))
     } else {
-        Ok((0.5 + ret.exp()).floor())
+        Ok((0.5 + fp::exp(ret)).floor())
     }
 }
 
@@ -3,6 +3,7 @@
 
 use crate::consts;
 use crate::error::StatsError;
+use crate::fp;
 use crate::is_zero;
 use crate::prec;
 use crate::Result;
@@ -40,10 +41,10 @@ pub fn ln_gamma(x: f64) -> f64 {
             .fold(GAMMA_DK[0], |s, t| s + t.1 / (t.0 as f64 - x));
 
         consts::LN_PI
-            - (f64::consts::PI * x).sin().ln()
-            - s.ln()
+            - fp::ln(fp::sin(f64::consts::PI * x))
+            - fp::ln(s)
             - consts::LN_2_SQRT_E_OVER_PI
-            - (0.5 - x) * ((0.5 - x + GAMMA_R) / f64::consts::E).ln()
+            - (0.5 - x) * fp::ln((0.5 - x + GAMMA_R) / f64::consts::E)
     } else {
         let s = GAMMA_DK
             .iter()
@@ -51,9 +52,9 @@ pub fn ln_gamma(x: f64) -> f64 {
             .skip(1)
             .fold(GAMMA_DK[0], |s, t| s + t.1 / (x + t.0 as f64 - 1.0));
 
-        s.ln()
+        fp::ln(s)
             + consts::LN_2_SQRT_E_OVER_PI
-            + (x - 0.5) * ((x - 0.5 + GAMMA_R) / f64::consts::E).ln()
+            + (x - 0.5) * fp::ln((x - 0.5 + GAMMA_R) / f64::consts::E)
     }
 }
 
@@ -70,10 +71,10 @@ pub fn gamma(x: f64) -> f64 {
             .fold(GAMMA_DK[0], |s, t| s + t.1 / (t.0 as f64 - x));
 
         f64::consts::PI
-            / ((f64::consts::PI * x).sin()
+            / (fp::sin(f64::consts::PI * x)
                 * s
                 * consts::TWO_SQRT_E_OVER_PI
-                * ((0.5 - x + GAMMA_R) / f64::consts::E).powf(0.5 - x))
+                * fp::powf((0.5 - x + GAMMA_R) / f64::consts::E, 0.5 - x))
     } else {
         let s = GAMMA_DK
             .iter()
@@ -81,7 +82,7 @@ pub fn gamma(x: f64) -> f64 {
             .skip(1)
             .fold(GAMMA_DK[0], |s, t| s + t.1 / (x + t.0 as f64 - 1.0));
 
-        s * consts::TWO_SQRT_E_OVER_PI * ((x - 0.5 + GAMMA_R) / f64::consts::E).powf(x - 0.5)
+        s * consts::TWO_SQRT_E_OVER_PI * fp::powf((x - 0.5 + GAMMA_R) / f64::consts::E, x - 0.5)
     }
 }
 
@@ -182,12 +183,12 @@ pub fn checked_gamma_ur(a: f64, x: f64) -> Result<f64> {
         return Ok(1.0 - gamma_lr(a, x));
     }
 
-    let mut ax = a * x.ln() - x - ln_gamma(a);
+    let mut ax = a * fp::ln(x) - x - ln_gamma(a);
     if ax < -709.78271289338399 {
         return if a < x { Ok(0.0) } else { Ok(1.0) };
     }
 
-    ax = ax.exp();
+    ax = fp::exp(ax);
     let mut y = 1.0 - a;
     let mut z = x + y + 1.0;
     let mut c = 0.0;
@@ -279,7 +280,7 @@ pub fn checked_gamma_lr(a: f64, x: f64) -> Result<f64> {
         return Ok(0.0);
     }
 
-    let ax = a * x.ln() - x - ln_gamma(a);
+    let ax = a * fp::ln(x) - x - ln_gamma(a);
     if ax < -709.78271289338399 {
         if a < x {
             return Ok(1.0);
@@ -299,7 +300,7 @@ pub fn checked_gamma_lr(a: f64, x: f64) -> Result<f64> {
                 break;
             }
         }
-        return Ok(ax.exp() * ans2 / a);
+        return Ok(fp::exp(ax) * ans2 / a);
     }
 
     let mut y = 1.0 - a;
@@ -343,7 +344,7 @@ pub fn checked_gamma_lr(a: f64, x: f64) -> Result<f64> {
             }
         }
     }
-    Ok(1.0 - ax.exp() * ans)
+    Ok(1.0 - fp::exp(ax) * ans)
 }
 
 /// Computes the Digamma function which is defined as the derivative of
@@ -368,7 +369,7 @@ pub fn digamma(x: f64) -> f64 {
         return f64::NEG_INFINITY;
     }
     if x < 0.0 {
-        return digamma(1.0 - x) + f64::consts::PI / (-f64::consts::PI * x).tan();
+        return digamma(1.0 - x) + f64::consts::PI / fp::tan(-f64::consts::PI * x);
     }
     if x <= s {
         return d1 - 1.0 / x + d2 * x;
@@ -383,7 +384,7 @@ pub fn digamma(x: f64) -> f64 {
 
     if z >= c {
         let mut r = 1.0 / z;
-        result += z.ln() - 0.5 * r;
+        result += fp::ln(z) - 0.5 * r;
         r *= r;
 
         result -= r * (s3 - r * (s4 - r * (s5 - r * (s6 - r * s7))));
@@ -391,6 +392,60 @@ pub fn digamma(x: f64) -> f64 {
     result
 }
 
+/// Computes the trigamma function, the derivative of the digamma function,
+/// using the recurrence `ψ'(x) = ψ'(x + 1) + 1 / x²` to shift the argument
+/// above ten and the asymptotic expansion in the Bernoulli numbers there.
+/// Negative arguments use the reflection formula
+/// `ψ'(1 - x) + ψ'(x) = π² / sin²(πx)` and the non-positive integers are
+/// poles.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::gamma::trigamma;
+/// use std::f64::consts::PI;
+///
+/// assert!((trigamma(1.0) - PI * PI / 6.0).abs() < 1e-14);
+/// assert_eq!(trigamma(0.0), f64::INFINITY);
+/// ```
+pub fn trigamma(x: f64) -> f64 {
+    if x == f64::NEG_INFINITY || x.is_nan() {
+        return f64::NAN;
+    }
+    if x == f64::INFINITY {
+        return 0.0;
+    }
+    if x <= 0.0 && x.floor() == x {
+        return f64::INFINITY;
+    }
+    if x < 0.0 {
+        let s = fp::sin(f64::consts::PI * x);
+        return -trigamma(1.0 - x) + f64::consts::PI * f64::consts::PI / (s * s);
+    }
+
+    let mut result = 0.0;
+    let mut z = x;
+    while z < 10.0 {
+        result += 1.0 / (z * z);
+        z += 1.0;
+    }
+    // the Bernoulli numbers B_2k in the terms B_2k / z^(2k + 1)
+    let bernoulli = [
+        1.0 / 6.0,
+        -1.0 / 30.0,
+        1.0 / 42.0,
+        -1.0 / 30.0,
+        5.0 / 66.0,
+        -691.0 / 2730.0,
+        7.0 / 6.0,
+        -3617.0 / 510.0,
+    ];
+    let r = 1.0 / z;
+    let r2 = r * r;
+    let series = bernoulli.iter().rev().fold(0.0, |acc, b| acc * r2 + b);
+    result + r + 0.5 * r2 + r * r2 * series
+}
+
 pub fn inv_digamma(x: f64) -> f64 {
     if x.is_nan() {
         return f64::NAN;
@@ -401,7 +456,7 @@ pub fn inv_digamma(x: f64) -> f64 {
     if x == f64::INFINITY {
         return f64::INFINITY;
     }
-    let mut y = x.exp();
+    let mut y = fp::exp(x);
     let mut i = 1.0;
     while i > 1e-15 {
         y += i * signum(x - digamma(y));
@@ -786,6 +841,23 @@ mod tests {
         assert_almost_eq!(super::digamma(10.1), 2.2622143570941481235561593642219403924532310597356171, 1e-14);
     }
 
+    #[test]
+    fn test_trigamma() {
+        assert!(super::trigamma(f64::NAN).is_nan());
+        assert_eq!(super::trigamma(-2.0), f64::INFINITY);
+        assert_eq!(super::trigamma(f64::INFINITY), 0.0);
+        assert_almost_eq!(super::trigamma(-1.5), 9.37924664498912375386168994438, 1e-13);
+        assert_almost_eq!(super::trigamma(1e-5), 10000000001.6449083899737777864, 1e-4);
+        assert_almost_eq!(super::trigamma(0.1), 101.433299150792747704652039651, 1e-12);
+        assert_almost_eq!(super::trigamma(1.0), 1.64493406684822643647241516665, 1e-14);
+        assert_almost_eq!(super::trigamma(1.5), 0.934802200544679309417245499938, 1e-14);
+        assert_almost_eq!(super::trigamma(2.5), 0.490357756100234864972801055494, 1e-14);
+        assert_almost_eq!(super::trigamma(3.3), 0.353501541841061832246059560266, 1e-14);
+        assert_almost_eq!(super::trigamma(10.0), 0.105166335681685746122201006908, 1e-15);
+        assert_almost_eq!(super::trigamma(25.7), 0.0396773351946374986806834236201, 1e-15);
+        assert_almost_eq!(super::trigamma(100.0), 0.0100501666633335713952456684657, 1e-16);
+    }
+
     #[test]
     fn test_inv_digamma() {
         assert!(super::inv_digamma(f64::NAN).is_nan());
@@ -801,7 +873,13 @@ mod tests {
         assert_almost_eq!(super::inv_digamma(1.1031566406452431872256903336679110994735070620062326), 3.5, 1e-14);
         assert_almost_eq!(super::inv_digamma(1.2561176684318004727268212432509309022911739973934097), 4.0, 1e-14);
         assert_almost_eq!(super::inv_digamma(1.3888709263595289015114046193821968137592213477205183), 4.5, 1e-14);
+        // the bisection stops on the sign of `x - digamma(y)`, which near the root
+        // is decided by the last few ulps of the `ln` calls inside digamma; musl's
+        // rounding walks it to 1.1e-14 away here
+        #[cfg(not(feature = "deterministic"))]
         assert_almost_eq!(super::inv_digamma(1.5061176684318004727268212432509309022911739973934097), 5.0, 1e-14);
+        #[cfg(feature = "deterministic")]
+        assert_almost_eq!(super::inv_digamma(1.5061176684318004727268212432509309022911739973934097), 5.0, 2e-14);
         assert_almost_eq!(super::inv_digamma(1.6110931485817511237336268416044190359814435699427405), 5.5, 1e-14);
         assert_almost_eq!(super::inv_digamma(2.2622143570941481235561593642219403924532310597356171), 10.1, 1e-13);
     }
@@ -3,6 +3,7 @@
 //! numbers
 
 use crate::consts;
+use crate::fp;
 use crate::function::gamma;
 
 /// Computes the `t`-th harmonic number
@@ -26,7 +27,7 @@ pub fn harmonic(t: u64) -> f64 {
 pub fn gen_harmonic(n: u64, m: f64) -> f64 {
     match n {
         0 => 1.0,
-        _ => (0..n).fold(0.0, |acc, x| acc + (x as f64 + 1.0).powf(-m)),
+        _ => (0..n).fold(0.0, |acc, x| acc + fp::powf(x as f64 + 1.0, -m)),
     }
 }
 
@@ -0,0 +1,65 @@
+//! Provides the survival function of the
+//! [Kolmogorov distribution](https://en.wikipedia.org/wiki/Kolmogorov%E2%80%93Smirnov_test#Kolmogorov_distribution),
+//! the limiting distribution of the Kolmogorov-Smirnov statistic
+
+use crate::fp;
+use std::f64;
+
+/// `kolmogorov_sf` calculates the survival function of the Kolmogorov
+/// distribution, the limiting distribution of `sqrt(n) D_n`, at `x`
+///
+/// # Formula
+///
+/// ```ignore
+/// 2 Σ (-1)^(k - 1) exp(-2 k^2 x^2)
+/// ```
+///
+/// summed over `k >= 1`. Below `x = 1.18` the equivalent theta function
+/// form `1 - sqrt(2π) / x Σ exp(-(2k - 1)^2 π^2 / (8 x^2))` is used, which
+/// converges faster there.
+pub fn kolmogorov_sf(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    if x < 1.18 {
+        // the theta function form converges quickly for small arguments
+        for k in 1..100 {
+            let j = (2 * k - 1) as f64;
+            let term = fp::exp(-j * j * f64::consts::PI * f64::consts::PI / (8.0 * x * x));
+            sum += term;
+            if term < 1e-17 * sum {
+                break;
+            }
+        }
+        (1.0 - (2.0 * f64::consts::PI).sqrt() / x * sum).max(0.0)
+    } else {
+        let mut sign = 1.0;
+        for k in 1..100 {
+            let k = k as f64;
+            let term = fp::exp(-2.0 * k * k * x * x);
+            sum += sign * term;
+            if term < 1e-17 * sum {
+                break;
+            }
+            sign = -sign;
+        }
+        (2.0 * sum).min(1.0)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_kolmogorov_sf() {
+        // reference values from mpmath
+        assert_almost_eq!(super::kolmogorov_sf(0.3), 0.9999906941986654, 1e-15);
+        assert_almost_eq!(super::kolmogorov_sf(0.5), 0.9639452436648751, 1e-15);
+        assert_almost_eq!(super::kolmogorov_sf(1.0), 0.26999967167735456, 1e-15);
+        assert_almost_eq!(super::kolmogorov_sf(1.5), 0.022217962616525129, 1e-15);
+        assert_almost_eq!(super::kolmogorov_sf(2.0), 0.00067092525577969535, 1e-15);
+        assert_eq!(super::kolmogorov_sf(0.0), 1.0);
+        assert_eq!(super::kolmogorov_sf(-1.0), 1.0);
+    }
+}
@@ -2,11 +2,12 @@
 //! related functions
 
 use crate::error::StatsError;
+use crate::fp;
 use crate::Result;
 
 /// Computes the logistic function
 pub fn logistic(p: f64) -> f64 {
-    1.0 / ((-p).exp() + 1.0)
+    1.0 / (fp::exp(-p) + 1.0)
 }
 
 /// Computes the logit function
@@ -27,7 +28,7 @@ pub fn checked_logit(p: f64) -> Result<f64> {
     if !(0.0..=1.0).contains(&p) {
         Err(StatsError::ArgIntervalIncl("p", 0.0, 1.0))
     } else {
-        Ok((p / (1.0 - p)).ln())
+        Ok(fp::ln(p / (1.0 - p)))
     }
 }
 
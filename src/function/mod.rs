@@ -8,4 +8,6 @@ pub mod exponential;
 pub mod factorial;
 pub mod gamma;
 pub mod harmonic;
+pub mod kolmogorov;
 pub mod logistic;
+pub mod optimize;
@@ -0,0 +1,409 @@
+//! Provides root finding and minimization routines used to solve equations
+//! that have no closed form solution (e.g. likelihood equations)
+
+use crate::{Result, StatsError};
+
+const GOLDEN: f64 = 1.618_033_988_749_895;
+const CGOLD: f64 = 0.381_966_011_250_105_1;
+const MAX_ITER: usize = 500;
+
+/// Finds a root of `f` within the bracket `[a, b]` using Brent's method,
+/// combining bisection, the secant method and inverse quadratic
+/// interpolation.
+///
+/// # Errors
+///
+/// Returns an error if `f(a)` and `f(b)` do not have opposite signs (or one
+/// of them is zero), if either is `NaN`, or if the method fails to converge
+/// to the absolute tolerance `tol` within 500 iterations
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::optimize;
+///
+/// let root = optimize::brent_root(|x| x * x - 2.0, 0.0, 2.0, 1e-14).unwrap();
+/// assert!((root - 2f64.sqrt()).abs() < 1e-14);
+/// ```
+pub fn brent_root<F>(mut f: F, a: f64, b: f64, tol: f64) -> Result<f64>
+where
+    F: FnMut(f64) -> f64,
+{
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa.is_nan() || fb.is_nan() || fa * fb > 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    if fa == 0.0 {
+        return Ok(a);
+    }
+    let (mut c, mut fc) = (b, fb);
+    let mut d = b - a;
+    let mut e = d;
+    for _ in 0..MAX_ITER {
+        if fb * fc > 0.0 {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol1 = 2.0 * f64::EPSILON * b.abs() + 0.5 * tol;
+        let xm = 0.5 * (c - b);
+        if xm.abs() <= tol1 || fb == 0.0 {
+            return Ok(b);
+        }
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * xm * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * xm * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            if 2.0 * p < (3.0 * xm * q - (tol1 * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = xm;
+                e = d;
+            }
+        } else {
+            d = xm;
+            e = d;
+        }
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol1 { d } else { tol1.copysign(xm) };
+        fb = f(b);
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// Finds a local minimum of `f` using Brent's method, starting from the
+/// initial points `a` and `b`. A bracketing triple is first searched for by
+/// golden-section expansion downhill from `a` and `b`, so the minimum is not
+/// required to lie between the initial points.
+///
+/// Returns the location of the minimum together with the value of `f`
+/// there.
+///
+/// # Errors
+///
+/// Returns an error if `a == b`, if `f` evaluates to `NaN` during the
+/// search, or if no bracket or minimum to the relative tolerance `tol` is
+/// found within 500 iterations (e.g. because `f` is unbounded below)
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::optimize;
+///
+/// let (x, fx) = optimize::brent_minimize(|x| (x - 3.0) * (x - 3.0) + 1.0, 0.0, 1.0, 1e-10).unwrap();
+/// assert!((x - 3.0).abs() < 1e-8);
+/// assert!((fx - 1.0).abs() < 1e-15);
+/// ```
+pub fn brent_minimize<F>(mut f: F, a: f64, b: f64, tol: f64) -> Result<(f64, f64)>
+where
+    F: FnMut(f64) -> f64,
+{
+    if a == b || a.is_nan() || b.is_nan() {
+        return Err(StatsError::BadParams);
+    }
+    let (lo, mid, hi) = bracket_minimum(&mut f, a, b)?;
+    let (mut a, mut b) = if lo < hi { (lo, hi) } else { (hi, lo) };
+    let mut x = mid;
+    let mut fx = f(x);
+    let (mut w, mut v) = (x, x);
+    let (mut fw, mut fv) = (fx, fx);
+    let mut d: f64 = 0.0;
+    let mut e: f64 = 0.0;
+    for _ in 0..MAX_ITER {
+        let xm = 0.5 * (a + b);
+        let tol1 = tol * x.abs() + 1e-12;
+        let tol2 = 2.0 * tol1;
+        if (x - xm).abs() <= tol2 - 0.5 * (b - a) {
+            return Ok((x, fx));
+        }
+        if e.abs() > tol1 {
+            let r = (x - w) * (fx - fv);
+            let mut q = (x - v) * (fx - fw);
+            let mut p = (x - v) * q - (x - w) * r;
+            q = 2.0 * (q - r);
+            if q > 0.0 {
+                p = -p;
+            }
+            q = q.abs();
+            let etemp = e;
+            e = d;
+            if p.abs() >= (0.5 * q * etemp).abs() || p <= q * (a - x) || p >= q * (b - x) {
+                e = if x >= xm { a - x } else { b - x };
+                d = CGOLD * e;
+            } else {
+                d = p / q;
+                let u = x + d;
+                if u - a < tol2 || b - u < tol2 {
+                    d = tol1.copysign(xm - x);
+                }
+            }
+        } else {
+            e = if x >= xm { a - x } else { b - x };
+            d = CGOLD * e;
+        }
+        let u = if d.abs() >= tol1 {
+            x + d
+        } else {
+            x + tol1.copysign(d)
+        };
+        let fu = f(u);
+        if fu.is_nan() {
+            return Err(StatsError::BadParams);
+        }
+        if fu <= fx {
+            if u >= x {
+                a = x;
+            } else {
+                b = x;
+            }
+            v = w;
+            w = x;
+            x = u;
+            fv = fw;
+            fw = fx;
+            fx = fu;
+        } else {
+            if u < x {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                v = w;
+                w = u;
+                fv = fw;
+                fw = fu;
+            } else if fu <= fv || v == x || v == w {
+                v = u;
+                fv = fu;
+            }
+        }
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// Searches downhill from `a` and `b` for points `(a, b, c)` such that `b`
+/// lies between `a` and `c` and `f(b)` is no larger than `f(a)` and `f(c)`
+fn bracket_minimum<F>(f: &mut F, a: f64, b: f64) -> Result<(f64, f64, f64)>
+where
+    F: FnMut(f64) -> f64,
+{
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fb > fa {
+        std::mem::swap(&mut a, &mut b);
+        std::mem::swap(&mut fa, &mut fb);
+    }
+    let mut c = b + GOLDEN * (b - a);
+    let mut fc = f(c);
+    for _ in 0..MAX_ITER {
+        if fa.is_nan() || fb.is_nan() || fc.is_nan() {
+            return Err(StatsError::BadParams);
+        }
+        if fb <= fc {
+            return Ok((a, b, c));
+        }
+        a = b;
+        fa = fb;
+        b = c;
+        fb = fc;
+        c = b + GOLDEN * (b - a);
+        fc = f(c);
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// Finds a local minimum of the function `f` of several variables with the
+/// Nelder-Mead simplex method, starting from the simplex spanned by
+/// `start` and the points moving each coordinate of `start` by 5%.
+/// Evaluations of `f` returning `NaN` or `INF`, e.g. outside its domain,
+/// are treated as infinitely bad, so `f` may return `INF` to constrain the
+/// search.
+///
+/// Returns the location of the minimum together with the value of `f`
+/// there. The method needs no derivatives and tolerates kinks, but may
+/// stall before reaching a minimum, so callers restart it from its result
+/// when accuracy matters.
+///
+/// # Errors
+///
+/// Returns an error if `start` is empty or not finite, if `f(start)` is
+/// not finite, or if the values of `f` on the simplex do not agree to
+/// within `tol (1 + |f|)` within `500 n` iterations for `n` variables
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::optimize;
+///
+/// let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+/// let (x, fx) = optimize::nelder_mead(rosenbrock, &[-1.2, 1.0], 1e-15).unwrap();
+/// assert!((x[0] - 1.0).abs() < 1e-5 && (x[1] - 1.0).abs() < 1e-5);
+/// assert!(fx < 1e-10);
+/// ```
+pub fn nelder_mead<F>(mut f: F, start: &[f64], tol: f64) -> Result<(Vec<f64>, f64)>
+where
+    F: FnMut(&[f64]) -> f64,
+{
+    let n = start.len();
+    if n == 0 || start.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::BadParams);
+    }
+    let mut eval = |x: &[f64]| {
+        let fx = f(x);
+        if fx.is_nan() {
+            f64::INFINITY
+        } else {
+            fx
+        }
+    };
+    let mut simplex = vec![start.to_vec()];
+    for i in 0..n {
+        let mut x = start.to_vec();
+        x[i] = if x[i] != 0.0 { 1.05 * x[i] } else { 0.00025 };
+        simplex.push(x);
+    }
+    let mut values: Vec<f64> = simplex.iter().map(|x| eval(x)).collect();
+    if !values[0].is_finite() {
+        return Err(StatsError::BadParams);
+    }
+    // the point `centroid + t (worst - centroid)`
+    let along = |centroid: &[f64], worst: &[f64], t: f64| -> Vec<f64> {
+        centroid
+            .iter()
+            .zip(worst)
+            .map(|(c, w)| c + t * (w - c))
+            .collect()
+    };
+    for _ in 0..MAX_ITER * n {
+        let mut order: Vec<usize> = (0..=n).collect();
+        order.sort_by(|&i, &j| values[i].partial_cmp(&values[j]).unwrap());
+        simplex = order.iter().map(|&i| simplex[i].clone()).collect();
+        values = order.iter().map(|&i| values[i]).collect();
+        let (best, worst) = (values[0], values[n]);
+        if worst - best <= tol * (1.0 + best.abs()) {
+            return Ok((simplex.swap_remove(0), best));
+        }
+        let mut centroid = vec![0.0; n];
+        for x in &simplex[..n] {
+            for (c, xi) in centroid.iter_mut().zip(x) {
+                *c += xi / n as f64;
+            }
+        }
+        let reflected = along(&centroid, &simplex[n], -1.0);
+        let fr = eval(&reflected);
+        if fr < best {
+            let expanded = along(&centroid, &simplex[n], -2.0);
+            let fe = eval(&expanded);
+            if fe < fr {
+                simplex[n] = expanded;
+                values[n] = fe;
+            } else {
+                simplex[n] = reflected;
+                values[n] = fr;
+            }
+            continue;
+        }
+        if fr < values[n - 1] {
+            simplex[n] = reflected;
+            values[n] = fr;
+            continue;
+        }
+        let (contracted, fc) = if fr < worst {
+            let x = along(&centroid, &simplex[n], -0.5);
+            let fx = eval(&x);
+            (x, if fx <= fr { fx } else { f64::INFINITY })
+        } else {
+            let x = along(&centroid, &simplex[n], 0.5);
+            let fx = eval(&x);
+            (x, if fx < worst { fx } else { f64::INFINITY })
+        };
+        if fc.is_finite() {
+            simplex[n] = contracted;
+            values[n] = fc;
+            continue;
+        }
+        // shrinks the simplex towards the best point
+        for i in 1..=n {
+            let x = along(&simplex[0], &simplex[i], 0.5);
+            values[i] = eval(&x);
+            simplex[i] = x;
+        }
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_brent_root() {
+        assert_almost_eq!(brent_root(|x| x.cos() - x, 0.0, 1.0, 1e-15).unwrap(), 0.7390851332151607, 1e-15);
+        assert_almost_eq!(brent_root(|x| x * x * x, -1.0, 2.0, 1e-12).unwrap(), 0.0, 1e-4);
+        assert_eq!(brent_root(|x| x - 1.0, 1.0, 2.0, 1e-12).unwrap(), 1.0);
+        assert_eq!(brent_root(|x| x - 2.0, 1.0, 2.0, 1e-12).unwrap(), 2.0);
+        assert!(brent_root(|x| x * x + 1.0, -1.0, 1.0, 1e-12).is_err());
+        assert!(brent_root(|_| f64::NAN, -1.0, 1.0, 1e-12).is_err());
+    }
+
+    #[test]
+    fn test_brent_minimize() {
+        let (x, fx) = brent_minimize(|x| x.cosh(), 5.0, 6.0, 1e-10).unwrap();
+        assert_almost_eq!(x, 0.0, 1e-8);
+        assert_almost_eq!(fx, 1.0, 1e-15);
+        let (x, _) = brent_minimize(|x| x.ln() * x, 0.1, 0.2, 1e-10).unwrap();
+        assert_almost_eq!(x, (-1f64).exp(), 1e-8);
+        assert!(brent_minimize(|x| x, 0.0, 1.0, 1e-10).is_err());
+        assert!(brent_minimize(|x| x * x, 1.0, 1.0, 1e-10).is_err());
+    }
+
+    #[test]
+    fn test_nelder_mead() {
+        let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let (x, fx) = nelder_mead(rosenbrock, &[-1.2, 1.0], 1e-15).unwrap();
+        assert_almost_eq!(x[0], 1.0, 1e-6);
+        assert_almost_eq!(x[1], 1.0, 1e-6);
+        assert!(fx < 1e-12);
+        let quadratic = |x: &[f64]| (x[0] - 1.0).powi(2) + 2.0 * (x[1] + 2.0).powi(2) + 3.0 * x[2].powi(2) + 5.0;
+        let (x, fx) = nelder_mead(quadratic, &[0.0, 0.0, 0.0], 1e-15).unwrap();
+        assert_almost_eq!(x[0], 1.0, 1e-6);
+        assert_almost_eq!(x[1], -2.0, 1e-6);
+        assert_almost_eq!(x[2], 0.0, 1e-6);
+        assert_almost_eq!(fx, 5.0, 1e-14);
+        // a constraint by infinite values outside the domain
+        let (x, _) = nelder_mead(|x: &[f64]| if x[0] > 0.0 { x[0] - x[0].ln() } else { f64::INFINITY }, &[3.0], 1e-15).unwrap();
+        assert_almost_eq!(x[0], 1.0, 1e-6);
+        let (x, _) = nelder_mead(|x: &[f64]| if x[0] > 0.0 { x[0] } else { f64::NAN }, &[3.0], 1e-12).unwrap();
+        assert!(x[0] > 0.0 && x[0] < 1e-6);
+        assert!(nelder_mead(|x: &[f64]| x[0], &[], 1e-10).is_err());
+        assert!(nelder_mead(|x: &[f64]| x[0], &[f64::NAN], 1e-10).is_err());
+        assert!(nelder_mead(|_: &[f64]| f64::INFINITY, &[1.0], 1e-10).is_err());
+        assert!(nelder_mead(|x: &[f64]| x[0], &[1.0], 1e-10).is_err());
+    }
+}
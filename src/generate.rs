@@ -1,6 +1,7 @@
 //! Provides utility functions for generating data sequences
 
 use crate::euclid::Modulus;
+use crate::fp;
 use std::f64::consts;
 /// Generates a base 10 log spaced vector of the given length between the
 /// specified decade exponents (inclusive). Equivalent to MATLAB logspace
@@ -16,13 +17,13 @@ use std::f64::consts;
 pub fn log_spaced(length: usize, start_exp: f64, stop_exp: f64) -> Vec<f64> {
     match length {
         0 => Vec::new(),
-        1 => vec![10f64.powf(stop_exp)],
+        1 => vec![fp::powf(10f64, stop_exp)],
         _ => {
             let step = (stop_exp - start_exp) / (length - 1) as f64;
             let mut vec = (0..length)
-                .map(|x| 10f64.powf(start_exp + (x as f64) * step))
+                .map(|x| fp::powf(10f64, start_exp + (x as f64) * step))
                 .collect::<Vec<f64>>();
-            vec[length - 1] = 10f64.powf(stop_exp);
+            vec[length - 1] = fp::powf(10f64, stop_exp);
             vec
         }
     }
@@ -163,7 +164,7 @@ impl Iterator for InfiniteSinusoidal {
     type Item = f64;
 
     fn next(&mut self) -> Option<f64> {
-        let x = self.mean + self.amplitude * (self.phase + self.i as f64 * self.step).sin();
+        let x = self.mean + self.amplitude * fp::sin(self.phase + self.i as f64 * self.step);
         self.i += 1;
         if self.i == 1000 {
             self.i = 0;
@@ -0,0 +1,568 @@
+//! Provides hidden Markov models with a finite number of hidden states and
+//! univariate emission distributions. All recursions are carried out in log
+//! space, so long observation sequences do not underflow.
+
+use crate::distribution::{Continuous, Normal};
+use crate::fp;
+use crate::function::exponential::ln_sum_exp;
+use crate::{Result, StatsError};
+use nalgebra::DMatrix;
+use rand::distributions::Distribution;
+use rand::Rng;
+use std::f64;
+
+/// Represents a [hidden Markov
+/// model](https://en.wikipedia.org/wiki/Hidden_Markov_model) whose hidden
+/// state starts in state `i` with probability `initial[i]`, moves from state
+/// `i` to state `j` with probability `transition[(i, j)]` and emits an
+/// observation drawn from `emissions[i]` while in state `i`
+///
+/// # Examples
+///
+/// ```
+/// use nalgebra::DMatrix;
+/// use statrs::distribution::Normal;
+/// use statrs::hmm::HiddenMarkov;
+///
+/// let transition = DMatrix::from_row_slice(2, 2, &[0.9, 0.1, 0.2, 0.8]);
+/// let emissions = vec![Normal::new(0.0, 1.0).unwrap(), Normal::new(5.0, 1.0).unwrap()];
+/// let hmm = HiddenMarkov::new(&[0.5, 0.5], transition, emissions).unwrap();
+/// let states = hmm.viterbi(&[0.1, -0.3, 5.2, 4.9, 0.2]);
+/// assert_eq!(states, vec![0, 0, 1, 1, 0]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HiddenMarkov<D> {
+    initial: Vec<f64>,
+    transition: DMatrix<f64>,
+    emissions: Vec<D>,
+}
+
+impl<D> HiddenMarkov<D> {
+    /// Constructs a new hidden Markov model from the initial state
+    /// probabilities, the row-stochastic transition matrix and the emission
+    /// distribution of every state
+    ///
+    /// # Note
+    ///
+    /// `initial` and the rows of `transition` do not need to be normalized
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `emissions` is empty, if `initial` does not have
+    /// one element per state, if `transition` is not square with one row per
+    /// state, or if `initial` or any row of `transition` contains negative,
+    /// `NaN` or infinite elements or only zeros
+    pub fn new(
+        initial: &[f64],
+        transition: DMatrix<f64>,
+        emissions: Vec<D>,
+    ) -> Result<HiddenMarkov<D>> {
+        let n = emissions.len();
+        if n == 0 || transition.nrows() != n || transition.ncols() != n {
+            return Err(StatsError::BadParams);
+        }
+        if initial.len() != n {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        let initial = normalize(initial).ok_or(StatsError::BadParams)?;
+        let mut transition = transition;
+        for i in 0..n {
+            let row: Vec<f64> = transition.row(i).iter().cloned().collect();
+            let row = normalize(&row).ok_or(StatsError::BadParams)?;
+            for (j, p) in row.into_iter().enumerate() {
+                transition[(i, j)] = p;
+            }
+        }
+        Ok(HiddenMarkov {
+            initial,
+            transition,
+            emissions,
+        })
+    }
+
+    /// Returns the normalized initial state probabilities
+    pub fn initial(&self) -> &[f64] {
+        &self.initial
+    }
+
+    /// Returns the normalized transition matrix, whose entry `(i, j)` is the
+    /// probability of moving from state `i` to state `j`
+    pub fn transition(&self) -> &DMatrix<f64> {
+        &self.transition
+    }
+
+    /// Returns the emission distribution of every state
+    pub fn emissions(&self) -> &[D] {
+        &self.emissions
+    }
+
+    /// Returns the number of hidden states
+    pub fn num_states(&self) -> usize {
+        self.emissions.len()
+    }
+
+    /// Draws a sequence of `n` hidden states together with the observations
+    /// they emit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalgebra::DMatrix;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use statrs::distribution::Normal;
+    /// use statrs::hmm::HiddenMarkov;
+    ///
+    /// let transition = DMatrix::from_row_slice(2, 2, &[0.9, 0.1, 0.2, 0.8]);
+    /// let emissions = vec![Normal::new(0.0, 1.0).unwrap(), Normal::new(5.0, 1.0).unwrap()];
+    /// let hmm = HiddenMarkov::new(&[0.5, 0.5], transition, emissions).unwrap();
+    /// let (states, observations) = hmm.sample_n(100, &mut StdRng::seed_from_u64(0));
+    /// assert_eq!(states.len(), 100);
+    /// assert_eq!(observations.len(), 100);
+    /// ```
+    pub fn sample_n<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> (Vec<usize>, Vec<f64>)
+    where
+        D: Distribution<f64>,
+    {
+        let mut states = Vec::with_capacity(n);
+        let mut observations = Vec::with_capacity(n);
+        for t in 0..n {
+            let state = if t == 0 {
+                draw(self.initial.iter().cloned(), rng)
+            } else {
+                draw(self.transition.row(states[t - 1]).iter().cloned(), rng)
+            };
+            states.push(state);
+            observations.push(self.emissions[state].sample(rng));
+        }
+        (states, observations)
+    }
+}
+
+impl<D: Continuous<f64, f64>> HiddenMarkov<D> {
+    /// Returns the log-likelihood of `observations` under the model, summing
+    /// over all hidden state sequences with the forward algorithm. The
+    /// log-likelihood of an empty sequence is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalgebra::DMatrix;
+    /// use statrs::distribution::{Continuous, Normal};
+    /// use statrs::hmm::HiddenMarkov;
+    ///
+    /// // with a single state the observations are independent
+    /// let n = Normal::new(1.0, 2.0).unwrap();
+    /// let hmm = HiddenMarkov::new(&[1.0], DMatrix::from_element(1, 1, 1.0), vec![n]).unwrap();
+    /// let ll = hmm.ln_likelihood(&[0.5, 2.0]);
+    /// assert!((ll - n.ln_pdf(0.5) - n.ln_pdf(2.0)).abs() < 1e-14);
+    /// ```
+    pub fn ln_likelihood(&self, observations: &[f64]) -> f64 {
+        if observations.is_empty() {
+            return 0.0;
+        }
+        let ln_e = self.ln_emissions(observations);
+        ln_sum_exp(&self.forward(&ln_e)[observations.len() - 1])
+    }
+
+    /// Returns the posterior probabilities of the hidden states given
+    /// `observations`, computed with the forward-backward algorithm, as a
+    /// matrix with one row per observation and one column per state
+    ///
+    /// # Remarks
+    ///
+    /// The rows are filled with `NaN` if the observations are impossible
+    /// under the model.
+    pub fn posterior(&self, observations: &[f64]) -> DMatrix<f64> {
+        let n = self.num_states();
+        if observations.is_empty() {
+            return DMatrix::zeros(0, n);
+        }
+        let ln_e = self.ln_emissions(observations);
+        let alpha = self.forward(&ln_e);
+        let beta = self.backward(&ln_e);
+        let ll = ln_sum_exp(&alpha[observations.len() - 1]);
+        DMatrix::from_fn(observations.len(), n, |t, i| {
+            fp::exp(alpha[t][i] + beta[t][i] - ll)
+        })
+    }
+
+    /// Returns the most likely sequence of hidden states given
+    /// `observations`, computed with the Viterbi algorithm. Ties are broken
+    /// in favour of the lower state.
+    pub fn viterbi(&self, observations: &[f64]) -> Vec<usize> {
+        if observations.is_empty() {
+            return Vec::new();
+        }
+        let n = self.num_states();
+        let ln_a = self.transition.map(fp::ln);
+        let ln_e = self.ln_emissions(observations);
+        let mut delta: Vec<f64> = (0..n)
+            .map(|i| fp::ln(self.initial[i]) + ln_e[0][i])
+            .collect();
+        let mut back = Vec::with_capacity(observations.len());
+        for e in &ln_e[1..] {
+            let mut next = vec![f64::NEG_INFINITY; n];
+            let mut from = vec![0; n];
+            for j in 0..n {
+                for i in 0..n {
+                    let v = delta[i] + ln_a[(i, j)];
+                    if v > next[j] {
+                        next[j] = v;
+                        from[j] = i;
+                    }
+                }
+                next[j] += e[j];
+            }
+            back.push(from);
+            delta = next;
+        }
+        let mut state = argmax(&delta);
+        let mut path = vec![state; observations.len()];
+        for (t, from) in back.iter().enumerate().rev() {
+            state = from[state];
+            path[t] = state;
+        }
+        path
+    }
+
+    /// Returns the log emission densities with one row per observation
+    fn ln_emissions(&self, observations: &[f64]) -> Vec<Vec<f64>> {
+        observations
+            .iter()
+            .map(|&x| self.emissions.iter().map(|d| d.ln_pdf(x)).collect())
+            .collect()
+    }
+
+    /// Returns the log forward variables `ln P(x_0..=x_t, s_t = i)`
+    fn forward(&self, ln_e: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let n = self.num_states();
+        let ln_a = self.transition.map(fp::ln);
+        let mut alpha: Vec<Vec<f64>> = Vec::with_capacity(ln_e.len());
+        alpha.push(
+            (0..n)
+                .map(|i| fp::ln(self.initial[i]) + ln_e[0][i])
+                .collect(),
+        );
+        let mut terms = vec![0.0; n];
+        for e in &ln_e[1..] {
+            let prev = &alpha[alpha.len() - 1];
+            let next = (0..n)
+                .map(|j| {
+                    for i in 0..n {
+                        terms[i] = prev[i] + ln_a[(i, j)];
+                    }
+                    ln_sum_exp(&terms) + e[j]
+                })
+                .collect();
+            alpha.push(next);
+        }
+        alpha
+    }
+
+    /// Returns the log backward variables `ln P(x_t+1.. | s_t = i)`
+    fn backward(&self, ln_e: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let n = self.num_states();
+        let ln_a = self.transition.map(fp::ln);
+        let mut beta = vec![vec![0.0; n]; ln_e.len()];
+        let mut terms = vec![0.0; n];
+        for t in (0..ln_e.len() - 1).rev() {
+            for i in 0..n {
+                for j in 0..n {
+                    terms[j] = ln_a[(i, j)] + ln_e[t + 1][j] + beta[t + 1][j];
+                }
+                beta[t][i] = ln_sum_exp(&terms);
+            }
+        }
+        beta
+    }
+}
+
+impl HiddenMarkov<Normal> {
+    /// Fits the model to `observations` with the Baum–Welch algorithm,
+    /// starting from `self` and iterating until the log-likelihood improves
+    /// by less than `tol` or `max_iter` iterations have been made
+    ///
+    /// # Remarks
+    ///
+    /// Baum–Welch is an expectation-maximization algorithm, so it increases
+    /// the likelihood in every iteration but only converges to a local
+    /// maximum that depends on the starting model. Parameters of states or
+    /// transitions that have no posterior weight are left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two observations, if the
+    /// observations are impossible under the current model, or if a state
+    /// collapses onto a single observation so that its variance vanishes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalgebra::DMatrix;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use statrs::distribution::Normal;
+    /// use statrs::hmm::HiddenMarkov;
+    ///
+    /// let transition = DMatrix::from_row_slice(2, 2, &[0.95, 0.05, 0.1, 0.9]);
+    /// let emissions = vec![Normal::new(0.0, 1.0).unwrap(), Normal::new(4.0, 1.0).unwrap()];
+    /// let truth = HiddenMarkov::new(&[0.5, 0.5], transition, emissions).unwrap();
+    /// let (_, x) = truth.sample_n(2000, &mut StdRng::seed_from_u64(0));
+    ///
+    /// let start = HiddenMarkov::new(
+    ///     &[0.5, 0.5],
+    ///     DMatrix::from_element(2, 2, 0.5),
+    ///     vec![Normal::new(-1.0, 2.0).unwrap(), Normal::new(1.0, 2.0).unwrap()],
+    /// )
+    /// .unwrap();
+    /// let fitted = start.fit(&x, 200, 1e-8).unwrap();
+    /// assert!(fitted.ln_likelihood(&x) > start.ln_likelihood(&x));
+    /// assert!((fitted.transition()[(0, 0)] - 0.95).abs() < 0.05);
+    /// ```
+    pub fn fit(
+        &self,
+        observations: &[f64],
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<HiddenMarkov<Normal>> {
+        if observations.len() < 2 {
+            return Err(StatsError::BadParams);
+        }
+        let mut model = self.clone();
+        let mut prev = f64::NEG_INFINITY;
+        for _ in 0..max_iter {
+            let (ll, next) = model.baum_welch_step(observations)?;
+            model = next;
+            if ll - prev < tol {
+                break;
+            }
+            prev = ll;
+        }
+        Ok(model)
+    }
+
+    /// Performs one Baum–Welch update and returns the log-likelihood of the
+    /// observations under `self` together with the updated model
+    fn baum_welch_step(&self, observations: &[f64]) -> Result<(f64, HiddenMarkov<Normal>)> {
+        let n = self.num_states();
+        let len = observations.len();
+        let ln_a = self.transition.map(fp::ln);
+        let ln_e = self.ln_emissions(observations);
+        let alpha = self.forward(&ln_e);
+        let beta = self.backward(&ln_e);
+        let ll = ln_sum_exp(&alpha[len - 1]);
+        if !ll.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+
+        let gamma = DMatrix::from_fn(len, n, |t, i| fp::exp(alpha[t][i] + beta[t][i] - ll));
+        let mut xi = DMatrix::zeros(n, n);
+        for t in 0..len - 1 {
+            for i in 0..n {
+                for j in 0..n {
+                    xi[(i, j)] +=
+                        fp::exp(alpha[t][i] + ln_a[(i, j)] + ln_e[t + 1][j] + beta[t + 1][j] - ll);
+                }
+            }
+        }
+
+        let initial: Vec<f64> = gamma.row(0).iter().cloned().collect();
+        let mut transition = self.transition.clone();
+        for i in 0..n {
+            let total: f64 = xi.row(i).sum();
+            if total > 0.0 {
+                for j in 0..n {
+                    transition[(i, j)] = xi[(i, j)] / total;
+                }
+            }
+        }
+        let mut emissions = self.emissions.clone();
+        for (i, emission) in emissions.iter_mut().enumerate() {
+            let weight: f64 = gamma.column(i).sum();
+            if weight > 0.0 {
+                let mean = gamma
+                    .column(i)
+                    .iter()
+                    .zip(observations)
+                    .map(|(g, x)| g * x)
+                    .sum::<f64>()
+                    / weight;
+                let var = gamma
+                    .column(i)
+                    .iter()
+                    .zip(observations)
+                    .map(|(g, x)| g * (x - mean) * (x - mean))
+                    .sum::<f64>()
+                    / weight;
+                *emission = Normal::new(mean, var.sqrt())?;
+            }
+        }
+        Ok((ll, HiddenMarkov::new(&initial, transition, emissions)?))
+    }
+}
+
+/// Returns `p` divided by its sum, or `None` if `p` contains negative, `NaN`
+/// or infinite elements or sums to zero
+fn normalize(p: &[f64]) -> Option<Vec<f64>> {
+    if p.iter().any(|x| !x.is_finite() || *x < 0.0) {
+        return None;
+    }
+    let sum: f64 = p.iter().sum();
+    if sum > 0.0 {
+        Some(p.iter().map(|x| x / sum).collect())
+    } else {
+        None
+    }
+}
+
+/// Draws an index with probability proportional to the given weights
+fn draw<I: Iterator<Item = f64>, R: Rng + ?Sized>(weights: I, rng: &mut R) -> usize {
+    let mut u = rng.gen::<f64>();
+    let mut last = 0;
+    for (i, w) in weights.enumerate() {
+        if w > 0.0 {
+            last = i;
+            u -= w;
+            if u < 0.0 {
+                return i;
+            }
+        }
+    }
+    last
+}
+
+/// Returns the index of the first largest element
+fn argmax(x: &[f64]) -> usize {
+    let mut best = 0;
+    for (i, &v) in x.iter().enumerate() {
+        if v > x[best] {
+            best = i;
+        }
+    }
+    best
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::Distribution as StatsDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn model() -> HiddenMarkov<Normal> {
+        let transition = DMatrix::from_row_slice(2, 2, &[0.7, 0.3, 0.4, 0.6]);
+        let emissions = vec![Normal::new(0.0, 1.0).unwrap(), Normal::new(2.0, 1.5).unwrap()];
+        HiddenMarkov::new(&[0.6, 0.4], transition, emissions).unwrap()
+    }
+
+    /// Returns the joint log probability of every state sequence, indexed by
+    /// the sequence's binary digits
+    fn path_ln_probs(hmm: &HiddenMarkov<Normal>, x: &[f64]) -> Vec<(Vec<usize>, f64)> {
+        (0..1usize << x.len())
+            .map(|code| {
+                let path: Vec<usize> = (0..x.len()).map(|t| (code >> t) & 1).collect();
+                let mut lp = hmm.initial()[path[0]].ln() + hmm.emissions()[path[0]].ln_pdf(x[0]);
+                for t in 1..x.len() {
+                    lp += hmm.transition()[(path[t - 1], path[t])].ln()
+                        + hmm.emissions()[path[t]].ln_pdf(x[t]);
+                }
+                (path, lp)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bad_create() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let t = DMatrix::from_element(2, 2, 0.5);
+        assert!(HiddenMarkov::<Normal>::new(&[], DMatrix::zeros(0, 0), vec![]).is_err());
+        assert!(matches!(HiddenMarkov::new(&[1.0], t.clone(), vec![n, n]), Err(StatsError::ContainersMustBeSameLength)));
+        assert!(HiddenMarkov::new(&[1.0, 1.0], DMatrix::from_element(2, 3, 0.5), vec![n, n]).is_err());
+        assert!(HiddenMarkov::new(&[1.0, -1.0], t.clone(), vec![n, n]).is_err());
+        assert!(HiddenMarkov::new(&[0.0, 0.0], t.clone(), vec![n, n]).is_err());
+        assert!(HiddenMarkov::new(&[1.0, f64::NAN], t, vec![n, n]).is_err());
+        let bad_row = DMatrix::from_row_slice(2, 2, &[1.0, 0.0, 0.0, 0.0]);
+        assert!(HiddenMarkov::new(&[1.0, 1.0], bad_row, vec![n, n]).is_err());
+        // rows and initial probabilities are normalized
+        let h = HiddenMarkov::new(&[1.0, 3.0], DMatrix::from_row_slice(2, 2, &[1.0, 1.0, 2.0, 6.0]), vec![n, n]).unwrap();
+        assert_eq!(h.initial(), &[0.25, 0.75]);
+        assert_eq!(h.transition()[(1, 1)], 0.75);
+        assert_eq!(h.num_states(), 2);
+    }
+
+    #[test]
+    fn test_ln_likelihood() {
+        let hmm = model();
+        let x = [0.3, 1.9, -0.4, 2.5, 3.1];
+        let paths: Vec<f64> = path_ln_probs(&hmm, &x).into_iter().map(|(_, lp)| lp).collect();
+        assert_almost_eq!(hmm.ln_likelihood(&x), ln_sum_exp(&paths), 1e-12);
+        assert_eq!(hmm.ln_likelihood(&[]), 0.0);
+        // long sequences do not underflow
+        let long: Vec<f64> = (0..5000).map(|i| (i % 7) as f64 * 0.5).collect();
+        assert!(hmm.ln_likelihood(&long).is_finite());
+    }
+
+    #[test]
+    fn test_posterior() {
+        let hmm = model();
+        let x = [0.3, 1.9, -0.4, 2.5];
+        let paths = path_ln_probs(&hmm, &x);
+        let ll = hmm.ln_likelihood(&x);
+        let post = hmm.posterior(&x);
+        assert_eq!(post.shape(), (4, 2));
+        for t in 0..4 {
+            assert_almost_eq!(post.row(t).sum(), 1.0, 1e-14);
+            let in_one: f64 = paths.iter().filter(|(p, _)| p[t] == 1).map(|(_, lp)| (lp - ll).exp()).sum();
+            assert_almost_eq!(post[(t, 1)], in_one, 1e-13);
+        }
+        assert_eq!(hmm.posterior(&[]).shape(), (0, 2));
+    }
+
+    #[test]
+    fn test_viterbi() {
+        let hmm = model();
+        let x = [0.3, 1.9, -0.4, 2.5, 3.1, 0.0];
+        let best = path_ln_probs(&hmm, &x)
+            .into_iter()
+            .fold((vec![], f64::NEG_INFINITY), |acc, (p, lp)| if lp > acc.1 { (p, lp) } else { acc });
+        assert_eq!(hmm.viterbi(&x), best.0);
+        assert!(hmm.viterbi(&[]).is_empty());
+        assert_eq!(hmm.viterbi(&[5.0]), vec![1]);
+    }
+
+    #[test]
+    fn test_sample_n() {
+        let hmm = model();
+        let mut rng = StdRng::seed_from_u64(0);
+        let (states, x) = hmm.sample_n(20000, &mut rng);
+        let stays = states.windows(2).filter(|w| w[0] == 0).count() as f64;
+        let stays_in_zero = states.windows(2).filter(|w| w[0] == 0 && w[1] == 0).count() as f64;
+        assert!((stays_in_zero / stays - 0.7).abs() < 0.02);
+        let ones: Vec<f64> = states.iter().zip(&x).filter(|(&s, _)| s == 1).map(|(_, &v)| v).collect();
+        let mean = ones.iter().sum::<f64>() / ones.len() as f64;
+        assert!((mean - 2.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_fit() {
+        let transition = DMatrix::from_row_slice(2, 2, &[0.9, 0.1, 0.2, 0.8]);
+        let emissions = vec![Normal::new(-1.0, 0.5).unwrap(), Normal::new(3.0, 1.0).unwrap()];
+        let truth = HiddenMarkov::new(&[0.5, 0.5], transition, emissions).unwrap();
+        let (_, x) = truth.sample_n(5000, &mut StdRng::seed_from_u64(1));
+        let start = model();
+        let fitted = start.fit(&x, 500, 1e-10).unwrap();
+        assert!(fitted.ln_likelihood(&x) > start.ln_likelihood(&x));
+        assert!(fitted.ln_likelihood(&x) >= truth.ln_likelihood(&x) - 1.0);
+        assert!((fitted.transition()[(0, 0)] - 0.9).abs() < 0.03);
+        assert!((fitted.transition()[(1, 1)] - 0.8).abs() < 0.03);
+        assert!((fitted.emissions()[0].mean().unwrap() + 1.0).abs() < 0.05);
+        assert!((fitted.emissions()[1].mean().unwrap() - 3.0).abs() < 0.1);
+        assert!((fitted.emissions()[0].std_dev().unwrap() - 0.5).abs() < 0.05);
+        // a further iteration does not decrease the likelihood
+        let again = fitted.fit(&x, 1, 0.0).unwrap();
+        assert!(again.ln_likelihood(&x) >= fitted.ln_likelihood(&x) - 1e-9);
+        assert!(start.fit(&[1.0], 10, 1e-8).is_err());
+    }
+}
@@ -0,0 +1,613 @@
+//! Provides kernel density estimation of univariate samples, with the
+//! bandwidth chosen by Silverman's rule of thumb or the plug-in method of
+//! Sheather & Jones and fast evaluation on grids by the Fourier transform
+
+use crate::distribution::{self, Beta, Continuous, ContinuousCDF, Normal};
+use crate::fp;
+use crate::function::{erf, optimize};
+use crate::statistics::{Data, Max, Min, OrderStatistics};
+use crate::{consts, Result, StatsError};
+use num_complex::Complex;
+use rand::Rng;
+use std::f64;
+
+/// The number of bins over which the pairwise distances are counted for
+/// the density functionals of the Sheather-Jones bandwidth
+const SJ_BINS: usize = 1000;
+
+/// The distance in bandwidths beyond which the Gaussian kernel is
+/// neglected on grids, where it has fallen below `1e-14`
+const GAUSSIAN_CUTOFF: f64 = 8.0;
+
+/// The largest number of grid steps spanned by the kernel for which
+/// `pdf_grid` convolves by the Fourier transform rather than summing the
+/// kernels directly
+const MAX_REACH: usize = 1 << 20;
+
+/// The kernel of a kernel density estimate, a symmetric density which is
+/// scaled by the bandwidth and centered at every observation
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Kernel {
+    /// The standard normal density, whose scale the bandwidth is the
+    /// standard deviation of
+    Gaussian,
+    /// The Epanechnikov kernel `3 (1 - u²) / 4` on `[-1, 1]`, which
+    /// minimizes the asymptotic mean integrated squared error
+    Epanechnikov,
+    /// The biweight kernel `15 (1 - u²)² / 16` on `[-1, 1]`, which unlike
+    /// the Epanechnikov kernel is smooth at the ends of its support
+    Biweight,
+}
+
+impl Kernel {
+    /// Returns the density of the kernel at `u`
+    pub fn pdf(&self, u: f64) -> f64 {
+        match *self {
+            Kernel::Gaussian => fp::exp(-0.5 * u * u) / consts::SQRT_2PI,
+            _ if u.abs() >= 1.0 => 0.0,
+            Kernel::Epanechnikov => 0.75 * (1.0 - u * u),
+            Kernel::Biweight => 0.9375 * (1.0 - u * u) * (1.0 - u * u),
+        }
+    }
+
+    /// Returns the cumulative distribution function of the kernel at `u`
+    pub fn cdf(&self, u: f64) -> f64 {
+        match *self {
+            Kernel::Gaussian => 0.5 * erf::erfc(-u / f64::consts::SQRT_2),
+            _ if u <= -1.0 => 0.0,
+            _ if u >= 1.0 => 1.0,
+            Kernel::Epanechnikov => 0.25 * (2.0 + 3.0 * u - u * u * u),
+            Kernel::Biweight => {
+                let u2 = u * u;
+                0.5 + 0.9375 * u * (1.0 - u2 * (2.0 / 3.0 - u2 / 5.0))
+            }
+        }
+    }
+
+    /// Returns the variance `∫ u² K(u) du` of the kernel
+    pub fn variance(&self) -> f64 {
+        match *self {
+            Kernel::Gaussian => 1.0,
+            Kernel::Epanechnikov => 0.2,
+            Kernel::Biweight => 1.0 / 7.0,
+        }
+    }
+
+    /// Returns the roughness `∫ K(u)² du` of the kernel
+    pub fn roughness(&self) -> f64 {
+        match *self {
+            Kernel::Gaussian => 0.5 / f64::consts::PI.sqrt(),
+            Kernel::Epanechnikov => 0.6,
+            Kernel::Biweight => 5.0 / 7.0,
+        }
+    }
+
+    /// Returns the radius of the support of the kernel, or of the region
+    /// outside which the Gaussian kernel is neglected
+    fn radius(&self) -> f64 {
+        match *self {
+            Kernel::Gaussian => GAUSSIAN_CUTOFF,
+            _ => 1.0,
+        }
+    }
+
+    /// Returns the canonical scale `(R(K) / μ₂(K)²)^(1/5)` of the kernel;
+    /// bandwidths proportional to the canonical scales give the same
+    /// amount of smoothing with different kernels
+    fn canonical_scale(&self) -> f64 {
+        fp::powf(self.roughness() / (self.variance() * self.variance()), 0.2)
+    }
+
+    /// Draws a sample from the kernel
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match *self {
+            Kernel::Gaussian => {
+                let normal = Normal::new_unchecked(0.0, 1.0);
+                ::rand::distributions::Distribution::sample(&normal, rng)
+            }
+            Kernel::Epanechnikov => {
+                // of three uniforms on [-1, 1], the second if the third is the
+                // largest in magnitude and the third otherwise (Devroye, 1986)
+                let mut u = [0.0f64; 3];
+                for x in u.iter_mut() {
+                    *x = rng.gen_range(-1.0..=1.0);
+                }
+                if u[2].abs() >= u[1].abs() && u[2].abs() >= u[0].abs() {
+                    u[1]
+                } else {
+                    u[2]
+                }
+            }
+            Kernel::Biweight => {
+                let beta = Beta::new_unchecked(3.0, 3.0);
+                2.0 * ::rand::distributions::Distribution::sample(&beta, rng) - 1.0
+            }
+        }
+    }
+}
+
+/// Selects the bandwidth of a kernel density estimate
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Bandwidth {
+    /// Silverman's rule of thumb `0.9 min(s, IQR / 1.34) n^(-1/5)` for the
+    /// Gaussian kernel, which is near optimal for unimodal samples but
+    /// oversmooths multimodal ones
+    Silverman,
+    /// The solve-the-equation plug-in bandwidth of Sheather & Jones (1991),
+    /// which estimates the curvature of the density from the sample and so
+    /// adapts to multimodal samples. The density functionals are computed
+    /// from the pairwise distances counted in 1000 bins, as in R's
+    /// `bw.SJ`.
+    SheatherJones,
+    /// The given bandwidth, the scale of the kernel
+    Fixed(f64),
+}
+
+/// Implements a [kernel density
+/// estimate](https://en.wikipedia.org/wiki/Kernel_density_estimation) of
+/// a univariate sample, the continuous distribution
+///
+/// ```ignore
+/// f(x) = Σ K((x - x_i) / h) / (n h)
+/// ```
+///
+/// of an observation drawn uniformly from the sample plus the kernel `K`
+/// scaled by the bandwidth `h`
+///
+/// # Remarks
+///
+/// Bandwidths selected by a rule are computed for the Gaussian kernel and
+/// converted to other kernels by the ratio of their canonical scales, so
+/// that all kernels smooth the sample equally. The density and cdf sum the
+/// kernels of all observations, or of those within the support of compact
+/// kernels, while `pdf_grid` evaluates the density at many points at once
+/// by convolving the linearly binned sample with the kernel by the fast
+/// Fourier transform.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ContinuousCDF};
+/// use statrs::kde::{Bandwidth, Kernel, KernelDensity};
+///
+/// let data = [-2.1, -1.3, -0.4, 0.2, 0.5, 1.1, 1.8, 2.9];
+/// let kde = KernelDensity::fit(&data).unwrap().with_kernel(Kernel::Epanechnikov);
+/// assert!(kde.pdf(0.0) > kde.pdf(3.0));
+/// assert!((kde.cdf(10.0) - 1.0).abs() < 1e-15);
+/// let grid = kde.pdf_grid(-4.0, 4.0, 81).unwrap();
+/// assert!((grid[40] - kde.pdf(0.0)).abs() < 1e-3);
+/// let kde = kde.with_bandwidth(Bandwidth::SheatherJones).unwrap();
+/// assert!(kde.bandwidth() > 0.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct KernelDensity {
+    data: Vec<f64>,
+    kernel: Kernel,
+    bandwidth: f64,
+}
+
+impl KernelDensity {
+    /// Fits the kernel density estimate of `data` with the Gaussian kernel
+    /// and Silverman's bandwidth
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two observations, a
+    /// non-finite observation or no spread
+    pub fn fit(data: &[f64]) -> Result<KernelDensity> {
+        if data.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        let mut data = data.to_vec();
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let bandwidth = gaussian_bandwidth(&data, Bandwidth::Silverman)?;
+        Ok(KernelDensity {
+            data,
+            kernel: Kernel::Gaussian,
+            bandwidth,
+        })
+    }
+
+    /// Replaces the kernel, converting the bandwidth to the same amount of
+    /// smoothing with the new kernel
+    pub fn with_kernel(self, kernel: Kernel) -> KernelDensity {
+        let bandwidth = self.bandwidth * kernel.canonical_scale() / self.kernel.canonical_scale();
+        KernelDensity {
+            kernel,
+            bandwidth,
+            ..self
+        }
+    }
+
+    /// Replaces the bandwidth by the one selected by `bandwidth`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a fixed bandwidth is not positive and finite,
+    /// or if the Sheather-Jones equation has no solution
+    pub fn with_bandwidth(self, bandwidth: Bandwidth) -> Result<KernelDensity> {
+        let bandwidth = match bandwidth {
+            Bandwidth::Fixed(h) => {
+                if !(h.is_finite() && h > 0.0) {
+                    return Err(StatsError::ArgMustBePositive("bandwidth"));
+                }
+                h
+            }
+            rule => {
+                gaussian_bandwidth(&self.data, rule)? * self.kernel.canonical_scale()
+                    / Kernel::Gaussian.canonical_scale()
+            }
+        };
+        Ok(KernelDensity { bandwidth, ..self })
+    }
+
+    /// Returns the sorted sample
+    pub fn data(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// Returns the kernel
+    pub fn kernel(&self) -> Kernel {
+        self.kernel
+    }
+
+    /// Returns the bandwidth, the scale of the kernel
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Returns the observations whose kernels are not neglected at `x`
+    fn window(&self, x: f64) -> &[f64] {
+        match self.kernel {
+            Kernel::Gaussian => &self.data,
+            _ => {
+                let lower = self.data.partition_point(|&y| y <= x - self.bandwidth);
+                let upper = self.data.partition_point(|&y| y < x + self.bandwidth);
+                &self.data[lower..upper]
+            }
+        }
+    }
+
+    /// Evaluates the density at the `points` equally spaced points from
+    /// `lower` to `upper`, in time `O(n + m log m)` for the `m` grid points
+    /// spanned by the sample and the kernel
+    ///
+    /// # Remarks
+    ///
+    /// Every observation is split between its two neighbouring grid points
+    /// in proportion to its proximity, and the binned sample is convolved
+    /// with the kernel by the fast Fourier transform. The error of the
+    /// binning is of the order of the square of the grid spacing relative
+    /// to the bandwidth. Observations farther than the support of the
+    /// kernel from the grid do not contribute. When the bandwidth spans
+    /// more than `2^20` grid steps, the kernels are summed directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `points` is less than two or if `lower` and
+    /// `upper` are not finite with `lower < upper`
+    pub fn pdf_grid(&self, lower: f64, upper: f64, points: usize) -> Result<Vec<f64>> {
+        if points < 2 || !(lower.is_finite() && upper.is_finite() && lower < upper) {
+            return Err(StatsError::BadParams);
+        }
+        let step = (upper - lower) / (points - 1) as f64;
+        let reach = (self.kernel.radius() * self.bandwidth / step).ceil();
+        if reach > MAX_REACH as f64 {
+            return Ok((0..points)
+                .map(|i| self.pdf(lower + i as f64 * step))
+                .collect());
+        }
+        let reach = reach as usize;
+        let len = points + 2 * reach;
+        let size = len.next_power_of_two();
+        let origin = lower - reach as f64 * step;
+        let mut bins = vec![Complex::new(0.0, 0.0); size];
+        for &x in &self.data {
+            let position = (x - origin) / step;
+            if position < 0.0 || position > (len - 1) as f64 {
+                continue;
+            }
+            let i = (position.floor() as usize).min(len - 2);
+            let fraction = position - i as f64;
+            bins[i].re += 1.0 - fraction;
+            bins[i + 1].re += fraction;
+        }
+        let scale = 1.0 / (self.data.len() as f64 * self.bandwidth);
+        let mut kernel = vec![Complex::new(0.0, 0.0); size];
+        for l in 0..=reach {
+            let k = scale * self.kernel.pdf(l as f64 * step / self.bandwidth);
+            kernel[l].re = k;
+            if l > 0 {
+                kernel[size - l].re = k;
+            }
+        }
+        distribution::fft(&mut bins, false);
+        distribution::fft(&mut kernel, false);
+        for (b, k) in bins.iter_mut().zip(&kernel) {
+            *b *= k;
+        }
+        distribution::fft(&mut bins, true);
+        Ok(bins[reach..reach + points]
+            .iter()
+            .map(|c| c.re.max(0.0))
+            .collect())
+    }
+}
+
+/// Returns the bandwidth selected by `rule` for the Gaussian kernel and the
+/// sorted sample `data`
+fn gaussian_bandwidth(data: &[f64], rule: Bandwidth) -> Result<f64> {
+    let n = data.len() as f64;
+    if data.len() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let mean = data.iter().sum::<f64>() / n;
+    let std_dev = (data.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0)).sqrt();
+    let iqr = Data::new(data.to_vec()).interquartile_range();
+    if std_dev <= 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    match rule {
+        Bandwidth::Silverman => {
+            let spread = if iqr > 0.0 {
+                std_dev.min(iqr / 1.34)
+            } else {
+                std_dev
+            };
+            Ok(0.9 * spread * fp::powf(n, -0.2))
+        }
+        Bandwidth::SheatherJones => {
+            let spread = if iqr > 0.0 {
+                std_dev.min(iqr / 1.349)
+            } else {
+                std_dev
+            };
+            sheather_jones(data, spread)
+        }
+        Bandwidth::Fixed(h) => Ok(h),
+    }
+}
+
+/// Solves the Sheather-Jones equation `h = (R(φ) / (n ψ₄(g(h))))^(1/5)`
+/// for the Gaussian kernel, where the pilot bandwidth `g(h)` is
+/// proportional to `h^(5/7)` with the constant estimated from the density
+/// functionals `ψ₄` and `ψ₆` at rule of thumb bandwidths
+fn sheather_jones(data: &[f64], spread: f64) -> Result<f64> {
+    let n = data.len();
+    let nf = n as f64;
+    let min = data[0];
+    let width = (data[n - 1] - min) * 1.01 / SJ_BINS as f64;
+    let mut counts = vec![0.0; SJ_BINS];
+    for &x in data {
+        counts[(((x - min) / width) as usize).min(SJ_BINS - 1)] += 1.0;
+    }
+    // pairs[k] counts the pairs of distinct observations k bins apart
+    let mut pairs = vec![0.0; SJ_BINS];
+    for (i, &ci) in counts.iter().enumerate().filter(|&(_, &c)| c > 0.0) {
+        pairs[0] += ci * (ci - 1.0) / 2.0;
+        for (k, &cj) in counts[i + 1..].iter().enumerate() {
+            pairs[k + 1] += ci * cj;
+        }
+    }
+    // the estimate of ψ_r at bandwidth h from the derivative of order r of
+    // the normal density, a polynomial in δ = (distance / h)², including
+    // the n terms of each observation with itself
+    let functional = |h: f64, order: i32, poly: &dyn Fn(f64) -> f64| {
+        let mut sum = 0.0;
+        for (k, &p) in pairs.iter().enumerate() {
+            let delta = fp::powi(k as f64 * width / h, 2);
+            if delta >= 1000.0 {
+                break;
+            }
+            sum += p * fp::exp(-delta / 2.0) * poly(delta);
+        }
+        (2.0 * sum + nf * poly(0.0)) / (nf * (nf - 1.0) * fp::powi(h, order + 1) * consts::SQRT_2PI)
+    };
+    let psi4 = |h: f64| functional(h, 4, &|d| d * d - 6.0 * d + 3.0);
+    let psi6 = |h: f64| functional(h, 6, &|d| d * d * d - 15.0 * d * d + 45.0 * d - 15.0);
+    let a = 1.24 * spread * fp::powf(nf, -1.0 / 7.0);
+    let b = 1.23 * spread * fp::powf(nf, -1.0 / 9.0);
+    let t = -psi6(b);
+    let ratio = psi4(a) / t;
+    if !(ratio.is_finite() && ratio > 0.0) {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+    let alpha = 1.357 * fp::powf(ratio, 1.0 / 7.0);
+    let c = 1.0 / (2.0 * f64::consts::PI.sqrt() * nf);
+    let equation = |h: f64| fp::powf(c / psi4(alpha * fp::powf(h, 5.0 / 7.0)), 0.2) - h;
+    let h_max = 1.144 * spread * fp::powf(nf, -0.2);
+    let (mut lower, mut upper) = (0.1 * h_max, h_max);
+    let mut widenings = 0;
+    while equation(lower) * equation(upper) > 0.0 {
+        if widenings == 100 {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+        if widenings % 2 == 0 {
+            upper *= 1.2;
+        } else {
+            lower /= 1.2;
+        }
+        widenings += 1;
+    }
+    optimize::brent_root(equation, lower, upper, 1e-10 * lower)
+}
+
+impl Min<f64> for KernelDensity {
+    /// Returns the smallest observation less the support of the kernel,
+    /// or `-INF` for the Gaussian kernel
+    fn min(&self) -> f64 {
+        match self.kernel {
+            Kernel::Gaussian => f64::NEG_INFINITY,
+            _ => self.data[0] - self.bandwidth,
+        }
+    }
+}
+
+impl Max<f64> for KernelDensity {
+    /// Returns the largest observation plus the support of the kernel, or
+    /// `INF` for the Gaussian kernel
+    fn max(&self) -> f64 {
+        match self.kernel {
+            Kernel::Gaussian => f64::INFINITY,
+            _ => self.data[self.data.len() - 1] + self.bandwidth,
+        }
+    }
+}
+
+impl ContinuousCDF<f64, f64> for KernelDensity {
+    /// Calculates the cumulative distribution function of the kernel
+    /// density estimate at `x`, the mean of the kernel cdfs
+    fn cdf(&self, x: f64) -> f64 {
+        let window = self.window(x);
+        let below = match self.kernel {
+            Kernel::Gaussian => 0.0,
+            _ => self.data.partition_point(|&y| y <= x - self.bandwidth) as f64,
+        };
+        let sum = window
+            .iter()
+            .map(|y| self.kernel.cdf((x - y) / self.bandwidth))
+            .sum::<f64>();
+        ((below + sum) / self.data.len() as f64).min(1.0)
+    }
+}
+
+impl Continuous<f64, f64> for KernelDensity {
+    /// Calculates the kernel density estimate at `x`
+    fn pdf(&self, x: f64) -> f64 {
+        self.window(x)
+            .iter()
+            .map(|y| self.kernel.pdf((x - y) / self.bandwidth))
+            .sum::<f64>()
+            / (self.data.len() as f64 * self.bandwidth)
+    }
+
+    /// Calculates the log of the kernel density estimate at `x`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        fp::ln(self.pdf(x))
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for KernelDensity {
+    /// Draws an observation uniformly from the sample and adds a draw of
+    /// the kernel scaled by the bandwidth
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let x = self.data[rng.gen_range(0..self.data.len())];
+        x + self.bandwidth * self.kernel.sample(rng)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const DATA: [f64; 10] = [0.63, 1.47, 2.19, 0.31, 3.85, 1.02, 0.88, 2.61, 1.73, 0.55];
+
+    #[test]
+    fn test_kernels() {
+        for &kernel in &[Kernel::Gaussian, Kernel::Epanechnikov, Kernel::Biweight] {
+            // the kernels are densities with the stated moments
+            let step = 1e-3;
+            let grid: Vec<f64> = (-9000..=9000).map(|i| i as f64 * step).collect();
+            let mass: f64 = grid.iter().map(|&u| kernel.pdf(u)).sum::<f64>() * step;
+            let variance: f64 = grid.iter().map(|&u| u * u * kernel.pdf(u)).sum::<f64>() * step;
+            let roughness: f64 = grid.iter().map(|&u| kernel.pdf(u).powi(2)).sum::<f64>() * step;
+            assert_almost_eq!(mass, 1.0, 1e-6);
+            assert_almost_eq!(variance, kernel.variance(), 1e-6);
+            assert_almost_eq!(roughness, kernel.roughness(), 1e-6);
+            for &u in &[-1.5, -0.7, 0.0, 0.3, 0.99] {
+                let integral: f64 = grid.iter().filter(|&&v| v < u).map(|&v| kernel.pdf(v)).sum::<f64>() * step;
+                assert_almost_eq!(kernel.cdf(u), integral, 2e-3);
+            }
+            // and their samples follow them
+            let mut rng = StdRng::seed_from_u64(1);
+            let x: Vec<f64> = (0..20000).map(|_| kernel.sample(&mut rng)).collect();
+            let mean = x.iter().sum::<f64>() / 20000.0;
+            let var = x.iter().map(|x| x * x).sum::<f64>() / 20000.0;
+            assert!(mean.abs() < 0.02 && (var / kernel.variance() - 1.0).abs() < 0.03);
+            assert!(x.iter().all(|u| kernel == Kernel::Gaussian || u.abs() <= 1.0));
+        }
+        assert_almost_eq!(Kernel::Gaussian.canonical_scale(), 0.77638835640902, 1e-9);
+        assert_almost_eq!(Kernel::Epanechnikov.canonical_scale(), 15f64.powf(0.2), 1e-14);
+    }
+
+    #[test]
+    fn test_bandwidths() {
+        let kde = KernelDensity::fit(&DATA).unwrap();
+        let mut data = Data::new(DATA.to_vec());
+        let iqr = data.interquartile_range();
+        let sd = 1.0473318480787261 * (10.0f64 / 9.0).sqrt();
+        assert_almost_eq!(kde.bandwidth(), 0.9 * sd.min(iqr / 1.34) * 10f64.powf(-0.2), 1e-14);
+        // the same smoothing with another kernel has the canonical ratio
+        let epanechnikov = kde.clone().with_kernel(Kernel::Epanechnikov);
+        assert_almost_eq!(epanechnikov.bandwidth() / kde.bandwidth(), 15f64.powf(0.2) / 0.77638835640902, 1e-9);
+        let gaussian = epanechnikov.with_kernel(Kernel::Gaussian);
+        assert_almost_eq!(gaussian.bandwidth(), kde.bandwidth(), 1e-14);
+        let fixed = kde.clone().with_bandwidth(Bandwidth::Fixed(0.5)).unwrap();
+        assert_eq!(fixed.bandwidth(), 0.5);
+        assert!(kde.clone().with_bandwidth(Bandwidth::Fixed(0.0)).is_err());
+        assert!(kde.clone().with_bandwidth(Bandwidth::Fixed(f64::NAN)).is_err());
+        // the Sheather-Jones bandwidth is near the optimum for normal
+        // samples and much smaller than Silverman's for bimodal ones
+        let mut rng = StdRng::seed_from_u64(2);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let x: Vec<f64> = (0..2000).map(|_| normal.sample(&mut rng)).collect();
+        let sj = KernelDensity::fit(&x).unwrap().with_bandwidth(Bandwidth::SheatherJones).unwrap();
+        assert!((sj.bandwidth() / (1.06 * 2000f64.powf(-0.2)) - 1.0).abs() < 0.15);
+        let x: Vec<f64> = (0..2000).map(|i| normal.sample(&mut rng) * 0.5 + if i % 2 == 0 { -3.0 } else { 3.0 }).collect();
+        let silverman = KernelDensity::fit(&x).unwrap();
+        let sj = silverman.clone().with_bandwidth(Bandwidth::SheatherJones).unwrap();
+        assert!(sj.bandwidth() < 0.5 * silverman.bandwidth());
+        assert!(KernelDensity::fit(&[1.0]).is_err());
+        assert!(KernelDensity::fit(&[1.0, 1.0]).is_err());
+        assert!(KernelDensity::fit(&[1.0, f64::INFINITY]).is_err());
+    }
+
+    #[test]
+    fn test_density() {
+        for &kernel in &[Kernel::Gaussian, Kernel::Epanechnikov, Kernel::Biweight] {
+            let kde = KernelDensity::fit(&DATA).unwrap().with_kernel(kernel);
+            let h = kde.bandwidth();
+            let expected = |x: f64| DATA.iter().map(|y| kernel.pdf((x - y) / h)).sum::<f64>() / (10.0 * h);
+            for &x in &[-1.0, 0.31, 1.0, 2.5, 4.2] {
+                assert_almost_eq!(kde.pdf(x), expected(x), 1e-15);
+                assert_almost_eq!(kde.ln_pdf(x), expected(x).ln(), 1e-12);
+                let cdf = DATA.iter().map(|y| kernel.cdf((x - y) / h)).sum::<f64>() / 10.0;
+                assert_almost_eq!(kde.cdf(x), cdf, 1e-15);
+            }
+            assert_almost_eq!(kde.cdf(kde.inverse_cdf(0.3)), 0.3, 1e-9);
+            // the grid evaluation agrees with the direct sums
+            let grid = kde.pdf_grid(-2.0, 6.0, 801).unwrap();
+            for (i, &g) in grid.iter().enumerate() {
+                assert!((g - kde.pdf(-2.0 + i as f64 * 0.01)).abs() < 1e-3);
+            }
+            let mass = grid.iter().sum::<f64>() * 0.01;
+            assert!((mass - 1.0).abs() < 1e-3);
+        }
+        let kde = KernelDensity::fit(&DATA).unwrap().with_kernel(Kernel::Biweight);
+        assert_eq!(kde.pdf(-10.0), 0.0);
+        assert_eq!(kde.cdf(10.0), 1.0);
+        assert_eq!(kde.min(), 0.31 - kde.bandwidth());
+        // a bandwidth spanning the whole grid is summed directly
+        let kde = KernelDensity::fit(&DATA).unwrap().with_bandwidth(Bandwidth::Fixed(1e3)).unwrap();
+        let grid = kde.pdf_grid(0.0, 1e-6, 3).unwrap();
+        assert_almost_eq!(grid[1], kde.pdf(5e-7), 1e-15);
+        assert!(kde.pdf_grid(1.0, 1.0, 10).is_err());
+        assert!(kde.pdf_grid(0.0, 1.0, 1).is_err());
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let kde = KernelDensity::fit(&DATA).unwrap().with_kernel(Kernel::Epanechnikov);
+        let x: Vec<f64> = (0..20000).map(|_| kde.sample(&mut rng)).collect();
+        assert!(x.iter().all(|&x| x >= kde.min() && x <= kde.max()));
+        for &q in &[0.5, 1.5, 3.0] {
+            let fraction = x.iter().filter(|&&x| x <= q).count() as f64 / 20000.0;
+            assert!((fraction - kde.cdf(q)).abs() < 0.015);
+        }
+    }
+}
@@ -50,16 +50,37 @@ macro_rules! assert_almost_eq {
     };
 }
 
+pub mod bootstrap;
+pub mod certified;
+pub mod conjugate;
 pub mod consts;
+pub mod copula;
+pub mod critical_values;
+pub mod cross_validation;
+pub mod distance;
 #[macro_use]
 pub mod distribution;
+pub mod dp;
+pub mod drift;
 pub mod euclid;
 pub mod function;
 pub mod generate;
+pub mod hmm;
+pub mod kde;
+pub mod markov;
+pub mod model_selection;
 pub mod prec;
+pub mod quadrature;
+pub mod rng;
+pub mod sampler;
+pub mod sensitivity;
 pub mod statistics;
+pub mod stats_tests;
+pub mod survival;
+pub mod transform;
 
 mod error;
+mod fp;
 
 // function to silence clippy on the special case when comparing to zero.
 #[inline(always)]
@@ -0,0 +1,521 @@
+//! Provides finite, time-homogeneous Markov chains together with their
+//! stationary distributions and total variation mixing diagnostics
+
+use crate::distribution::Categorical;
+use crate::fp;
+use crate::{Result, StatsError};
+use nalgebra::{DMatrix, DVector};
+use rand::distributions::Distribution;
+use rand::Rng;
+use std::f64;
+
+/// The number of doublings after which `MarkovChain::mixing_time` gives up
+const MAX_DOUBLINGS: u32 = 48;
+
+/// Represents a [Markov chain](https://en.wikipedia.org/wiki/Markov_chain)
+/// on the states `0..n` that moves from state `i` to state `j` with
+/// probability `transition[(i, j)]`
+///
+/// # Examples
+///
+/// ```
+/// use nalgebra::DMatrix;
+/// use statrs::markov::MarkovChain;
+///
+/// let chain = MarkovChain::new(DMatrix::from_row_slice(2, 2, &[0.9, 0.1, 0.5, 0.5])).unwrap();
+/// let pi = chain.stationary().unwrap();
+/// assert!((pi[0] - 5.0 / 6.0).abs() < 1e-14);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkovChain {
+    transition: DMatrix<f64>,
+    rows: Vec<Categorical>,
+}
+
+impl MarkovChain {
+    /// Constructs a new Markov chain from its row-stochastic transition
+    /// matrix
+    ///
+    /// # Note
+    ///
+    /// The rows of `transition` do not need to be normalized
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `transition` is empty or not square, or if any
+    /// of its rows contains negative, `NaN` or infinite elements or only
+    /// zeros
+    pub fn new(transition: DMatrix<f64>) -> Result<MarkovChain> {
+        let n = transition.nrows();
+        if n == 0 || transition.ncols() != n || transition.iter().any(|p| p.is_infinite()) {
+            return Err(StatsError::BadParams);
+        }
+        let mut transition = transition;
+        let mut rows = Vec::with_capacity(n);
+        for i in 0..n {
+            let row: Vec<f64> = transition.row(i).iter().cloned().collect();
+            let categorical = Categorical::new(&row)?;
+            let sum: f64 = row.iter().sum();
+            for j in 0..n {
+                transition[(i, j)] /= sum;
+            }
+            rows.push(categorical);
+        }
+        Ok(MarkovChain { transition, rows })
+    }
+
+    /// Returns the normalized transition matrix
+    pub fn transition(&self) -> &DMatrix<f64> {
+        &self.transition
+    }
+
+    /// Returns the number of states
+    pub fn num_states(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the stationary distribution `π` of the chain, i.e. the
+    /// probability vector satisfying `π P = π`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain has no unique stationary distribution,
+    /// which happens if it has more than one closed communicating class
+    pub fn stationary(&self) -> Result<Vec<f64>> {
+        let n = self.num_states();
+        // replace one of the redundant balance equations by the
+        // normalization constraint
+        let mut a = self.transition.transpose() - DMatrix::identity(n, n);
+        a.row_mut(n - 1).fill(1.0);
+        let mut b = DVector::zeros(n);
+        b[n - 1] = 1.0;
+        let pi = a
+            .lu()
+            .solve(&b)
+            .ok_or(StatsError::SpecialCase("no unique stationary distribution"))?;
+        if pi.iter().any(|p| !p.is_finite() || *p < -1e-10) {
+            return Err(StatsError::SpecialCase("no unique stationary distribution"));
+        }
+        let residual = (pi.transpose() * &self.transition - pi.transpose()).amax();
+        if residual > 1e-10 {
+            return Err(StatsError::SpecialCase("no unique stationary distribution"));
+        }
+        Ok(pi.iter().map(|p| p.max(0.0)).collect())
+    }
+
+    /// Returns the `n`-step transition matrix `P^n`, computed by repeated
+    /// squaring
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalgebra::DMatrix;
+    /// use statrs::markov::MarkovChain;
+    ///
+    /// let chain = MarkovChain::new(DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0])).unwrap();
+    /// assert_eq!(chain.n_step(2), DMatrix::identity(2, 2));
+    /// ```
+    pub fn n_step(&self, n: u64) -> DMatrix<f64> {
+        let size = self.num_states();
+        let mut result = DMatrix::identity(size, size);
+        let mut base = self.transition.clone();
+        let mut n = n;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = &result * &base;
+            }
+            n >>= 1;
+            if n > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// Returns the distribution of the state after `n` steps when the chain
+    /// starts in a state drawn from `initial`, i.e. `initial P^n`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `initial` does not have one element per state or
+    /// is not a probability vector up to normalization
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalgebra::DMatrix;
+    /// use statrs::markov::MarkovChain;
+    ///
+    /// let chain = MarkovChain::new(DMatrix::from_row_slice(2, 2, &[0.9, 0.1, 0.5, 0.5])).unwrap();
+    /// let dist = chain.distribution_after(&[1.0, 0.0], 1).unwrap();
+    /// assert_eq!(dist, vec![0.9, 0.1]);
+    /// ```
+    pub fn distribution_after(&self, initial: &[f64], n: u64) -> Result<Vec<f64>> {
+        if initial.len() != self.num_states() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        if initial.iter().any(|p| !p.is_finite() || *p < 0.0) {
+            return Err(StatsError::BadParams);
+        }
+        let sum: f64 = initial.iter().sum();
+        if sum <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let initial = DVector::from_iterator(initial.len(), initial.iter().map(|p| p / sum));
+        Ok((initial.transpose() * self.n_step(n))
+            .iter()
+            .cloned()
+            .collect())
+    }
+
+    /// Returns the worst-case total variation distance to stationarity
+    /// after `n` steps
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// d(n) = max_i 1/2 sum_j |P^n(i, j) - π_j|
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain has no unique stationary distribution
+    pub fn distance_to_stationarity(&self, n: u64) -> Result<f64> {
+        let pi = self.stationary()?;
+        Ok(tv_distance(&self.n_step(n), &pi))
+    }
+
+    /// Returns the mixing time `t_mix(ε)`, the smallest number of steps `n`
+    /// with `d(n) <= ε`, where `d` is the worst-case total variation
+    /// distance to stationarity
+    ///
+    /// # Remarks
+    ///
+    /// Since `d` is non-increasing, the mixing time is bracketed by doubling
+    /// `n` and then found by bisection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `eps` is not in `(0, 1)`, if the chain has no
+    /// unique stationary distribution, or if it does not mix within `2^48`
+    /// steps, e.g. because it is periodic
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalgebra::DMatrix;
+    /// use statrs::markov::MarkovChain;
+    ///
+    /// let chain = MarkovChain::new(DMatrix::from_row_slice(2, 2, &[0.5, 0.5, 0.5, 0.5])).unwrap();
+    /// assert_eq!(chain.mixing_time(0.25).unwrap(), 1);
+    /// ```
+    pub fn mixing_time(&self, eps: f64) -> Result<u64> {
+        if !(eps > 0.0 && eps < 1.0) {
+            return Err(StatsError::ArgIntervalExcl("eps", 0.0, 1.0));
+        }
+        let pi = self.stationary()?;
+        if tv_distance(&DMatrix::identity(pi.len(), pi.len()), &pi) <= eps {
+            return Ok(0);
+        }
+        let mut high = 1;
+        let mut power = self.transition.clone();
+        let mut doublings = 0;
+        while tv_distance(&power, &pi) > eps {
+            if doublings == MAX_DOUBLINGS {
+                return Err(StatsError::ComputationFailedToConverge);
+            }
+            power = &power * &power;
+            high *= 2;
+            doublings += 1;
+        }
+        let mut low = high / 2;
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if tv_distance(&self.n_step(mid), &pi) <= eps {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        Ok(high)
+    }
+
+    /// Returns `true` if the chain satisfies detailed balance
+    /// `π_i P(i, j) = π_j P(j, i)` with respect to its stationary
+    /// distribution
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain has no unique stationary distribution
+    pub fn is_reversible(&self) -> Result<bool> {
+        let pi = self.stationary()?;
+        let n = self.num_states();
+        Ok((0..n).all(|i| {
+            (0..n).all(|j| {
+                (pi[i] * self.transition[(i, j)] - pi[j] * self.transition[(j, i)]).abs() <= 1e-10
+            })
+        }))
+    }
+
+    /// Returns the relaxation time `1 / γ*` of a reversible chain, where
+    /// `γ* = 1 - max |λ|` is the absolute spectral gap and the maximum is
+    /// over all eigenvalues of the transition matrix except the leading
+    /// eigenvalue one. The relaxation time is infinite for periodic chains.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain has no unique stationary distribution,
+    /// is not reversible or has states of zero stationary probability
+    pub fn relaxation_time(&self) -> Result<f64> {
+        let pi = self.stationary()?;
+        if !self.is_reversible()? || pi.iter().any(|&p| p <= 0.0) {
+            return Err(StatsError::BadParams);
+        }
+        let n = self.num_states();
+        // D^(1/2) P D^(-1/2) is symmetric for reversible chains and has the
+        // eigenvalues of P
+        let s = DMatrix::from_fn(n, n, |i, j| {
+            let (a, b) = (
+                pi[i].sqrt() * self.transition[(i, j)] / pi[j].sqrt(),
+                pi[j].sqrt() * self.transition[(j, i)] / pi[i].sqrt(),
+            );
+            0.5 * (a + b)
+        });
+        let mut eigenvalues: Vec<f64> = s.symmetric_eigenvalues().iter().cloned().collect();
+        eigenvalues.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        let lambda = eigenvalues[1..]
+            .iter()
+            .fold(0.0, |acc: f64, l| acc.max(l.abs()));
+        let gap = 1.0 - lambda;
+        if gap <= 1e-12 {
+            Ok(f64::INFINITY)
+        } else {
+            Ok(1.0 / gap)
+        }
+    }
+
+    /// Returns the spectral lower and upper bounds on the mixing time
+    /// `t_mix(ε)` of a reversible chain
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (t_rel - 1) ln(1 / (2ε)) <= t_mix(ε) <= t_rel ln(1 / (ε π_min))
+    /// ```
+    ///
+    /// where `t_rel` is the relaxation time and `π_min` the smallest
+    /// stationary probability, see Levin, Peres and Wilmer, "Markov Chains
+    /// and Mixing Times", theorems 12.4 and 12.5. The lower bound is clamped
+    /// at zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `eps` is not in `(0, 1)` or the relaxation time
+    /// is not defined, see `relaxation_time`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalgebra::DMatrix;
+    /// use statrs::markov::MarkovChain;
+    ///
+    /// let chain = MarkovChain::new(DMatrix::from_row_slice(2, 2, &[0.9, 0.1, 0.1, 0.9])).unwrap();
+    /// let (low, high) = chain.mixing_time_bounds(0.25).unwrap();
+    /// let t = chain.mixing_time(0.25).unwrap() as f64;
+    /// assert!(low <= t && t <= high);
+    /// ```
+    pub fn mixing_time_bounds(&self, eps: f64) -> Result<(f64, f64)> {
+        if !(eps > 0.0 && eps < 1.0) {
+            return Err(StatsError::ArgIntervalExcl("eps", 0.0, 1.0));
+        }
+        let t_rel = self.relaxation_time()?;
+        let pi_min = self.stationary()?.into_iter().fold(f64::INFINITY, f64::min);
+        let lower = ((t_rel - 1.0) * fp::ln(1.0 / (2.0 * eps))).max(0.0);
+        let upper = t_rel * fp::ln(1.0 / (eps * pi_min));
+        Ok((lower, upper))
+    }
+
+    /// Simulates `steps` transitions of the chain starting in `start` and
+    /// returns the `steps + 1` visited states including `start`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `start` is not a state of the chain
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nalgebra::DMatrix;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use statrs::markov::MarkovChain;
+    ///
+    /// let chain = MarkovChain::new(DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0])).unwrap();
+    /// let path = chain.sample_path(0, 4, &mut StdRng::seed_from_u64(0)).unwrap();
+    /// assert_eq!(path, vec![0, 1, 0, 1, 0]);
+    /// ```
+    pub fn sample_path<R: Rng + ?Sized>(
+        &self,
+        start: usize,
+        steps: usize,
+        rng: &mut R,
+    ) -> Result<Vec<usize>> {
+        if start >= self.num_states() {
+            return Err(StatsError::BadParams);
+        }
+        let mut path = Vec::with_capacity(steps + 1);
+        path.push(start);
+        let mut state = start;
+        for _ in 0..steps {
+            state = self.rows[state].sample(rng) as usize;
+            path.push(state);
+        }
+        Ok(path)
+    }
+}
+
+/// Returns the largest total variation distance between a row of `power`
+/// and `pi`
+fn tv_distance(power: &DMatrix<f64>, pi: &[f64]) -> f64 {
+    (0..power.nrows())
+        .map(|i| {
+            0.5 * pi
+                .iter()
+                .enumerate()
+                .map(|(j, p)| (power[(i, j)] - p).abs())
+                .sum::<f64>()
+        })
+        .fold(0.0, f64::max)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn chain(n: usize, p: &[f64]) -> MarkovChain {
+        MarkovChain::new(DMatrix::from_row_slice(n, n, p)).unwrap()
+    }
+
+    /// Returns the lazy random walk on a cycle of `n` states
+    fn lazy_cycle(n: usize) -> MarkovChain {
+        let p = DMatrix::from_fn(n, n, |i, j| {
+            if i == j {
+                0.5
+            } else if (i + 1) % n == j || (j + 1) % n == i {
+                0.25
+            } else {
+                0.0
+            }
+        });
+        MarkovChain::new(p).unwrap()
+    }
+
+    #[test]
+    fn test_bad_create() {
+        assert!(MarkovChain::new(DMatrix::zeros(0, 0)).is_err());
+        assert!(MarkovChain::new(DMatrix::from_element(2, 3, 1.0)).is_err());
+        assert!(MarkovChain::new(DMatrix::from_row_slice(2, 2, &[1.0, -1.0, 0.5, 0.5])).is_err());
+        assert!(MarkovChain::new(DMatrix::from_row_slice(2, 2, &[0.0, 0.0, 0.5, 0.5])).is_err());
+        assert!(MarkovChain::new(DMatrix::from_row_slice(2, 2, &[f64::NAN, 1.0, 0.5, 0.5])).is_err());
+        assert!(MarkovChain::new(DMatrix::from_row_slice(2, 2, &[f64::INFINITY, 1.0, 0.5, 0.5])).is_err());
+        let c = chain(2, &[1.0, 3.0, 2.0, 2.0]);
+        assert_eq!(c.transition()[(0, 1)], 0.75);
+        assert_eq!(c.num_states(), 2);
+    }
+
+    #[test]
+    fn test_stationary() {
+        let c = chain(3, &[0.5, 0.3, 0.2, 0.2, 0.6, 0.2, 0.1, 0.4, 0.5]);
+        let pi = c.stationary().unwrap();
+        assert_almost_eq!(pi.iter().sum::<f64>(), 1.0, 1e-14);
+        let after = c.distribution_after(&pi, 1).unwrap();
+        for (a, b) in after.iter().zip(&pi) {
+            assert_almost_eq!(*a, *b, 1e-14);
+        }
+        // pi = (12, 23, 14) / 49
+        assert_almost_eq!(pi[0], 12.0 / 49.0, 1e-14);
+        assert_almost_eq!(pi[1], 23.0 / 49.0, 1e-14);
+        // an absorbing state attracts all mass
+        let c = chain(2, &[1.0, 0.0, 0.3, 0.7]);
+        let pi = c.stationary().unwrap();
+        assert_almost_eq!(pi[0], 1.0, 1e-15);
+        assert_almost_eq!(pi[1], 0.0, 1e-15);
+        // two closed classes
+        let c = chain(2, &[1.0, 0.0, 0.0, 1.0]);
+        assert!(c.stationary().is_err());
+        assert!(c.mixing_time(0.25).is_err());
+    }
+
+    #[test]
+    fn test_n_step() {
+        let c = chain(3, &[0.5, 0.3, 0.2, 0.2, 0.6, 0.2, 0.1, 0.4, 0.5]);
+        assert_eq!(c.n_step(0), DMatrix::identity(3, 3));
+        assert_eq!(c.n_step(1), *c.transition());
+        let mut direct = DMatrix::identity(3, 3);
+        for _ in 0..13 {
+            direct = &direct * c.transition();
+        }
+        assert!((c.n_step(13) - direct).amax() < 1e-15);
+        let dist = c.distribution_after(&[2.0, 0.0, 0.0], 2).unwrap();
+        assert_almost_eq!(dist[0], 0.5 * 0.5 + 0.3 * 0.2 + 0.2 * 0.1, 1e-15);
+        assert!(c.distribution_after(&[1.0, 0.0], 1).is_err());
+        assert!(c.distribution_after(&[0.0, 0.0, 0.0], 1).is_err());
+        assert!(c.distribution_after(&[1.0, -1.0, 1.0], 1).is_err());
+    }
+
+    #[test]
+    fn test_mixing_time() {
+        // d(n) = |1 - 2p|^n / 2 for the symmetric two-state chain
+        let c = chain(2, &[0.9, 0.1, 0.1, 0.9]);
+        assert_almost_eq!(c.distance_to_stationarity(3).unwrap(), 0.5 * 0.8f64.powi(3), 1e-15);
+        // 0.5 * 0.8^n <= 0.01 first for n = 18
+        assert_eq!(c.mixing_time(0.01).unwrap(), 18);
+        assert_eq!(c.mixing_time(0.5).unwrap(), 0);
+        assert!(c.mixing_time(0.0).is_err());
+        assert!(c.mixing_time(1.0).is_err());
+        // periodic chains do not mix
+        let c = chain(2, &[0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(c.stationary().unwrap(), vec![0.5, 0.5]);
+        assert!(matches!(c.mixing_time(0.25), Err(StatsError::ComputationFailedToConverge)));
+        assert_eq!(c.relaxation_time().unwrap(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_spectral_bounds() {
+        let c = chain(2, &[0.9, 0.1, 0.1, 0.9]);
+        assert!(c.is_reversible().unwrap());
+        // the second eigenvalue is 0.8
+        assert_almost_eq!(c.relaxation_time().unwrap(), 5.0, 1e-12);
+        for &n in &[5, 10, 20] {
+            let c = lazy_cycle(n);
+            let pi = c.stationary().unwrap();
+            assert!(pi.iter().all(|&p| (p - 1.0 / n as f64).abs() < 1e-14));
+            let t = c.mixing_time(0.25).unwrap() as f64;
+            let (low, high) = c.mixing_time_bounds(0.25).unwrap();
+            assert!(low <= t && t <= high);
+        }
+        // a cyclic rotation with laziness is not reversible
+        let c = chain(3, &[0.5, 0.5, 0.0, 0.0, 0.5, 0.5, 0.5, 0.0, 0.5]);
+        assert!(!c.is_reversible().unwrap());
+        assert!(c.relaxation_time().is_err());
+        assert!(c.mixing_time_bounds(0.25).is_err());
+    }
+
+    #[test]
+    fn test_sample_path() {
+        let c = chain(3, &[0.5, 0.3, 0.2, 0.2, 0.6, 0.2, 0.1, 0.4, 0.5]);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(c.sample_path(3, 10, &mut rng).is_err());
+        assert_eq!(c.sample_path(1, 0, &mut rng).unwrap(), vec![1]);
+        let path = c.sample_path(0, 100000, &mut rng).unwrap();
+        assert_eq!(path.len(), 100001);
+        let pi = c.stationary().unwrap();
+        for (state, p) in pi.iter().enumerate() {
+            let freq = path.iter().filter(|&&s| s == state).count() as f64 / path.len() as f64;
+            assert!((freq - p).abs() < 0.01);
+        }
+    }
+}
@@ -0,0 +1,424 @@
+//! Provides the comparison of candidate distribution families fitted to the
+//! same data by information criteria and goodness of fit, answering which of
+//! several families describes a sample best
+
+use crate::distribution::dynamic::DynContinuous;
+use crate::distribution::{Continuous, ContinuousCDF, Fit, Parameterized};
+use crate::fp;
+use crate::{Result, StatsError};
+use rand::distributions::Distribution;
+use std::f64;
+use std::fmt;
+
+/// Fits a member of a candidate family to a sample
+type FitFn = dyn Fn(&[f64]) -> Result<Box<dyn DynContinuous>>;
+
+/// A distribution family to be fitted to the data by `compare`, made of a
+/// name, the number of free parameters and the procedure fitting a member
+/// of the family to a sample
+pub struct Candidate {
+    name: &'static str,
+    num_params: usize,
+    fit: Box<FitFn>,
+}
+
+impl Candidate {
+    /// Constructs a candidate named `name` with `num_params` free
+    /// parameters fitted by `fit`, e.g. a family with some parameters held
+    /// fixed or an estimator other than the maximum likelihood
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    /// use statrs::model_selection::Candidate;
+    ///
+    /// let gev = Candidate::new("gev", 3, GeneralizedExtremeValue::fit_l_moments);
+    /// assert_eq!(gev.name(), "gev");
+    /// assert_eq!(gev.num_params(), 3);
+    /// ```
+    pub fn new<D, F>(name: &'static str, num_params: usize, fit: F) -> Candidate
+    where
+        D: Continuous<f64, f64> + ContinuousCDF<f64, f64> + Distribution<f64> + 'static,
+        F: Fn(&[f64]) -> Result<D> + 'static,
+    {
+        Candidate {
+            name,
+            num_params,
+            fit: Box::new(move |data| {
+                let dist = fit(data)?;
+                Ok(Box::new(dist) as Box<dyn DynContinuous>)
+            }),
+        }
+    }
+
+    /// Constructs a candidate named `name` fitted by `Fit::fit_mle`, whose
+    /// free parameters are those of `Parameterized::params`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Gamma;
+    /// use statrs::model_selection::Candidate;
+    ///
+    /// let gamma = Candidate::mle::<Gamma>("gamma");
+    /// assert_eq!(gamma.num_params(), 2);
+    /// ```
+    pub fn mle<D>(name: &'static str) -> Candidate
+    where
+        D: Fit<Sample = f64>
+            + Parameterized
+            + Continuous<f64, f64>
+            + ContinuousCDF<f64, f64>
+            + Distribution<f64>
+            + 'static,
+    {
+        Candidate::new(name, D::param_names().len(), D::fit_mle)
+    }
+
+    /// Returns the name of the candidate
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the number of free parameters of the candidate
+    pub fn num_params(&self) -> usize {
+        self.num_params
+    }
+}
+
+impl fmt::Debug for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Candidate")
+            .field("name", &self.name)
+            .field("num_params", &self.num_params)
+            .finish()
+    }
+}
+
+/// A candidate fitted by `compare` together with its log-likelihood,
+/// information criteria and Kolmogorov-Smirnov statistic
+pub struct ModelFit {
+    name: &'static str,
+    num_params: usize,
+    dist: Box<dyn DynContinuous>,
+    ln_likelihood: f64,
+    aic: f64,
+    aicc: f64,
+    bic: f64,
+    ks_statistic: f64,
+    delta_aic: f64,
+}
+
+impl ModelFit {
+    /// Returns the name of the candidate
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the number of free parameters `k` of the candidate
+    pub fn num_params(&self) -> usize {
+        self.num_params
+    }
+
+    /// Returns the fitted distribution
+    pub fn dist(&self) -> &dyn DynContinuous {
+        self.dist.as_ref()
+    }
+
+    /// Returns the log-likelihood `ln L` of the data under the fitted
+    /// distribution
+    pub fn ln_likelihood(&self) -> f64 {
+        self.ln_likelihood
+    }
+
+    /// Returns Akaike's information criterion `2k - 2 ln L`
+    pub fn aic(&self) -> f64 {
+        self.aic
+    }
+
+    /// Returns the small sample corrected Akaike information criterion
+    /// `AIC + 2k(k + 1) / (n - k - 1)`, which is infinite if `n <= k + 1`
+    pub fn aicc(&self) -> f64 {
+        self.aicc
+    }
+
+    /// Returns the Bayesian information criterion `k ln n - 2 ln L`
+    pub fn bic(&self) -> f64 {
+        self.bic
+    }
+
+    /// Returns the Kolmogorov-Smirnov statistic `sup_x |F_n(x) - F(x)|`
+    /// between the empirical distribution function of the data and the
+    /// cdf of the fitted distribution
+    ///
+    /// # Remarks
+    ///
+    /// The statistic measures the fit on a common scale for all candidates,
+    /// but since the parameters are estimated from the same data its usual
+    /// p-values are too large
+    pub fn ks_statistic(&self) -> f64 {
+        self.ks_statistic
+    }
+
+    /// Returns the difference between the AIC of the candidate and the
+    /// smallest AIC among the fitted candidates
+    pub fn delta_aic(&self) -> f64 {
+        self.delta_aic
+    }
+}
+
+impl fmt::Debug for ModelFit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ModelFit")
+            .field("name", &self.name)
+            .field("num_params", &self.num_params)
+            .field("ln_likelihood", &self.ln_likelihood)
+            .field("aic", &self.aic)
+            .field("aicc", &self.aicc)
+            .field("bic", &self.bic)
+            .field("ks_statistic", &self.ks_statistic)
+            .field("delta_aic", &self.delta_aic)
+            .finish()
+    }
+}
+
+/// The report returned by `compare`, holding the candidates that could be
+/// fitted ranked by increasing AIC and the errors of those that could not
+#[derive(Debug)]
+pub struct Comparison {
+    ranked: Vec<ModelFit>,
+    failures: Vec<(&'static str, StatsError)>,
+}
+
+impl Comparison {
+    /// Returns the fitted candidates ranked by increasing AIC, ties keeping
+    /// the order of the candidates
+    pub fn ranked(&self) -> &[ModelFit] {
+        &self.ranked
+    }
+
+    /// Returns the candidate with the smallest AIC
+    pub fn best(&self) -> &ModelFit {
+        &self.ranked[0]
+    }
+
+    /// Returns the fitted candidate named `name`
+    pub fn get(&self, name: &str) -> Option<&ModelFit> {
+        self.ranked.iter().find(|fit| fit.name == name)
+    }
+
+    /// Returns the names of the candidates that could not be fitted to the
+    /// data together with the errors of their fits, e.g. for data outside
+    /// the support of the family
+    pub fn failures(&self) -> &[(&'static str, StatsError)] {
+        &self.failures
+    }
+}
+
+/// Fits each of `candidates` to `data` and ranks the fits by Akaike's
+/// information criterion, reporting for each the log-likelihood, AIC, AICc,
+/// BIC and Kolmogorov-Smirnov statistic
+///
+/// # Formula
+///
+/// ```ignore
+/// AIC  = 2k - 2 ln L
+/// AICc = AIC + 2k(k + 1) / (n - k - 1)
+/// BIC  = k ln n - 2 ln L
+/// ```
+///
+/// where `n` is the number of observations, `k` the number of free
+/// parameters of the candidate and `L` the likelihood of the data under
+/// the fitted distribution
+///
+/// # Remarks
+///
+/// Information criteria only compare candidates fitted to the same data
+/// and are meaningful as differences: as a rule of thumb, candidates
+/// within 2 of the smallest AIC are well supported by the data and those
+/// more than 10 above it are not. BIC penalizes parameters more heavily
+/// than AIC once `n > 7` and AICc should be preferred over AIC when `n / k`
+/// is small. Candidates that fail to fit, e.g. a family on the positive
+/// reals for data with negative values, are reported in
+/// `Comparison::failures` instead of failing the comparison.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or has a value that is not finite,
+/// or if none of `candidates` can be fitted to it
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Exp, Gamma, LogNormal, Normal};
+/// use statrs::model_selection::{compare, Candidate};
+///
+/// let data = [0.31, 2.16, 0.47, 1.12, 0.08, 0.92, 3.87, 0.64, 0.23, 1.55];
+/// let candidates = [
+///     Candidate::mle::<Normal>("normal"),
+///     Candidate::mle::<Exp>("exponential"),
+///     Candidate::mle::<Gamma>("gamma"),
+///     Candidate::mle::<LogNormal>("log-normal"),
+/// ];
+/// let comparison = compare(&data, &candidates).unwrap();
+/// assert_eq!(comparison.ranked().len(), 4);
+/// // the skewed sample is poorly described by a normal
+/// assert_eq!(comparison.ranked()[3].name(), "normal");
+/// assert_eq!(comparison.best().delta_aic(), 0.0);
+/// ```
+pub fn compare(data: &[f64], candidates: &[Candidate]) -> Result<Comparison> {
+    if data.is_empty() || data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::BadParams);
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = data.len() as f64;
+
+    let mut ranked = Vec::with_capacity(candidates.len());
+    let mut failures = Vec::new();
+    for candidate in candidates {
+        let dist = match (candidate.fit)(data) {
+            Ok(dist) => dist,
+            Err(err) => {
+                failures.push((candidate.name, err));
+                continue;
+            }
+        };
+        let ln_likelihood: f64 = data.iter().map(|&x| dist.ln_pdf(x)).sum();
+        let k = candidate.num_params as f64;
+        let aic = 2.0 * k - 2.0 * ln_likelihood;
+        let aicc = if n > k + 1.0 {
+            aic + 2.0 * k * (k + 1.0) / (n - k - 1.0)
+        } else {
+            f64::INFINITY
+        };
+        let ks_statistic = ks_statistic(&sorted, dist.as_ref());
+        ranked.push(ModelFit {
+            name: candidate.name,
+            num_params: candidate.num_params,
+            dist,
+            ln_likelihood,
+            aic,
+            aicc,
+            bic: k * fp::ln(n) - 2.0 * ln_likelihood,
+            ks_statistic,
+            delta_aic: 0.0,
+        });
+    }
+    if ranked.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+
+    // a `NaN` criterion ranks last
+    ranked.sort_by(|a, b| match (a.aic.is_nan(), b.aic.is_nan()) {
+        (false, false) => a.aic.partial_cmp(&b.aic).unwrap(),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
+    });
+    let min_aic = ranked[0].aic;
+    for fit in &mut ranked {
+        fit.delta_aic = fit.aic - min_aic;
+    }
+    Ok(Comparison { ranked, failures })
+}
+
+/// Returns the Kolmogorov-Smirnov statistic between the empirical
+/// distribution function of the sorted sample `sorted` and the cdf of the
+/// continuous distribution `dist`
+fn ks_statistic(sorted: &[f64], dist: &dyn DynContinuous) -> f64 {
+    let n = sorted.len() as f64;
+    sorted.iter().enumerate().fold(0.0, |d: f64, (i, &x)| {
+        let cdf = dist.cdf(x);
+        d.max((i + 1) as f64 / n - cdf).max(cdf - i as f64 / n)
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::{compare, Candidate};
+    use crate::distribution::{ContinuousCDF, Empirical, Exp, Fit, Gamma, GeneralizedExtremeValue, Likelihood, LogNormal, Normal, Uniform};
+    use crate::drift;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn candidates() -> Vec<Candidate> {
+        vec![
+            Candidate::mle::<Normal>("normal"),
+            Candidate::mle::<Exp>("exponential"),
+            Candidate::mle::<Gamma>("gamma"),
+            Candidate::mle::<LogNormal>("log-normal"),
+        ]
+    }
+
+    #[test]
+    fn test_compare() {
+        let data = [0.31, 2.16, 0.47, 1.12, 0.08, 0.92, 3.87, 0.64, 0.23, 1.55];
+        let comparison = compare(&data, &candidates()).unwrap();
+        assert!(comparison.failures().is_empty());
+        let aics: Vec<f64> = comparison.ranked().iter().map(|fit| fit.aic()).collect();
+        assert!(aics.windows(2).all(|w| w[0] <= w[1]));
+
+        let gamma = comparison.get("gamma").unwrap();
+        let fitted = Gamma::fit_mle(&data).unwrap();
+        let ln_likelihood = fitted.ln_likelihood(&data);
+        assert_almost_eq!(gamma.ln_likelihood(), ln_likelihood, 1e-12);
+        assert_almost_eq!(gamma.aic(), 4.0 - 2.0 * ln_likelihood, 1e-12);
+        assert_almost_eq!(gamma.aicc(), 4.0 - 2.0 * ln_likelihood + 12.0 / 7.0, 1e-12);
+        assert_almost_eq!(gamma.bic(), 2.0 * 10f64.ln() - 2.0 * ln_likelihood, 1e-12);
+        assert_eq!(gamma.num_params(), 2);
+        assert_almost_eq!(gamma.dist().cdf(1.0), fitted.cdf(1.0), 1e-15);
+        assert_almost_eq!(gamma.delta_aic(), gamma.aic() - comparison.best().aic(), 1e-15);
+
+        let exp = comparison.get("exponential").unwrap();
+        let ks = drift::ks_statistic(&Empirical::from_vec(data.to_vec()), &Exp::fit_mle(&data).unwrap()).unwrap();
+        assert_almost_eq!(exp.ks_statistic(), ks, 1e-15);
+        assert!(comparison.get("weibull").is_none());
+    }
+
+    #[test]
+    fn test_compare_recovers_family() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let gamma = Gamma::new(3.0, 2.0).unwrap();
+        let data: Vec<f64> = (0..2000).map(|_| gamma.sample(&mut rng)).collect();
+        let comparison = compare(&data, &candidates()).unwrap();
+        assert_eq!(comparison.best().name(), "gamma");
+        assert!(comparison.get("exponential").unwrap().delta_aic() > 10.0);
+        assert!(comparison.best().ks_statistic() < 0.03);
+    }
+
+    #[test]
+    fn test_compare_failures() {
+        // the positive families cannot be fitted to negative data
+        let data = [-0.5, 0.3, 1.2, -1.1, 0.8, 0.1];
+        let mut candidates = candidates();
+        candidates.push(Candidate::new("gev", 3, GeneralizedExtremeValue::fit_l_moments));
+        let comparison = compare(&data, &candidates).unwrap();
+        let failed: Vec<&str> = comparison.failures().iter().map(|(name, _)| *name).collect();
+        assert_eq!(failed, ["exponential", "gamma", "log-normal"]);
+        assert_eq!(comparison.ranked().len(), 2);
+        // with n = k + 1 the corrected criterion is undefined
+        let small = compare(&data[..4], &[Candidate::new("gev", 3, GeneralizedExtremeValue::fit_l_moments)]);
+        assert_eq!(small.unwrap().best().aicc(), f64::INFINITY);
+
+        assert!(compare(&[], &candidates).is_err());
+        assert!(compare(&[1.0, f64::NAN], &candidates).is_err());
+        assert!(compare(&data, &candidates[1..3]).is_err());
+        assert!(compare(&data, &[]).is_err());
+    }
+
+    #[test]
+    fn test_compare_infinite_likelihood() {
+        // a fit whose support excludes some of the data ranks last
+        let data = [0.1, 0.4, 0.5, 0.9];
+        let candidates = [
+            Candidate::new("narrow", 2, |_: &[f64]| Uniform::new(0.0, 0.6)),
+            Candidate::mle::<Uniform>("uniform"),
+        ];
+        let comparison = compare(&data, &candidates).unwrap();
+        assert_eq!(comparison.best().name(), "uniform");
+        assert_eq!(comparison.ranked()[1].aic(), f64::INFINITY);
+        assert_eq!(comparison.ranked()[1].delta_aic(), f64::INFINITY);
+    }
+}
@@ -0,0 +1,504 @@
+//! Provides [Gaussian quadrature](https://en.wikipedia.org/wiki/Gaussian_quadrature)
+//! rules for the weight functions of the classical orthogonal polynomials,
+//! keyed to the statrs distributions whose densities they are. The rules
+//! allow the moments of a model output to be computed from the
+//! distributions of its inputs, as in non-intrusive polynomial chaos
+//! expansions.
+
+use crate::distribution::{Beta, Exp, Gamma, LogNormal, Normal, Uniform};
+use crate::fp;
+use crate::statistics::{Distribution, Max, Min};
+use crate::{Result, StatsError};
+use nalgebra::DMatrix;
+
+/// Represents an `n`-point Gaussian quadrature rule whose weights sum to one,
+/// so that `expectation` approximates the expected value of a function of a
+/// random variable. The rule is exact for polynomials of degree up to
+/// `2n - 1`.
+///
+/// # Remarks
+///
+/// Nodes and weights are computed with the Golub-Welsch algorithm from the
+/// eigendecomposition of the Jacobi matrix of the three-term recurrence of
+/// the orthogonal polynomials. The nodes are sorted in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::quadrature::GaussRule;
+///
+/// // probabilists' Gauss-Hermite rule for the standard normal distribution
+/// let rule = GaussRule::hermite(3).unwrap();
+/// assert!((rule.expectation(|x| x * x * x * x) - 3.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaussRule {
+    nodes: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl GaussRule {
+    /// Constructs the `n`-point Gauss-Hermite rule for the standard normal
+    /// distribution, i.e. for the weight function `e^(-x^2 / 2) / sqrt(2π)`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is zero
+    pub fn hermite(n: usize) -> Result<GaussRule> {
+        GaussRule::golub_welsch(n, |_| 0.0, |k| (k as f64).sqrt())
+    }
+
+    /// Constructs the `n`-point Gauss-Legendre rule for the uniform
+    /// distribution on `[-1, 1]`, i.e. for the weight function `1 / 2`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is zero
+    pub fn legendre(n: usize) -> Result<GaussRule> {
+        GaussRule::golub_welsch(
+            n,
+            |_| 0.0,
+            |k| {
+                let k = k as f64;
+                k / (4.0 * k * k - 1.0).sqrt()
+            },
+        )
+    }
+
+    /// Constructs the `n`-point generalized Gauss-Laguerre rule for the
+    /// weight function `x^α * e^(-x) / Γ(α + 1)` on `[0, ∞)`, i.e. for the
+    /// gamma distribution with shape `α + 1` and unit rate
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is zero or if `alpha` is `NaN`, infinite or
+    /// not greater than `-1`
+    pub fn laguerre(n: usize, alpha: f64) -> Result<GaussRule> {
+        check_exponent("alpha", alpha)?;
+        GaussRule::golub_welsch(
+            n,
+            |k| 2.0 * k as f64 + alpha + 1.0,
+            |k| (k as f64 * (k as f64 + alpha)).sqrt(),
+        )
+    }
+
+    /// Constructs the `n`-point Gauss-Jacobi rule for the weight function
+    /// proportional to `(1 - x)^α * (1 + x)^β` on `[-1, 1]`, i.e. for a beta
+    /// distribution with shapes `β + 1` and `α + 1` rescaled to `[-1, 1]`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is zero or if `alpha` or `beta` is `NaN`,
+    /// infinite or not greater than `-1`
+    pub fn jacobi(n: usize, alpha: f64, beta: f64) -> Result<GaussRule> {
+        check_exponent("alpha", alpha)?;
+        check_exponent("beta", beta)?;
+        let s = alpha + beta;
+        let diagonal = |k: usize| {
+            if k == 0 {
+                (beta - alpha) / (s + 2.0)
+            } else {
+                let t = 2.0 * k as f64 + s;
+                (beta * beta - alpha * alpha) / (t * (t + 2.0))
+            }
+        };
+        let off_diagonal = |k: usize| {
+            let k = k as f64;
+            let t = 2.0 * k + s;
+            if k == 1.0 {
+                // k + α + β cancels against 2k + α + β - 1
+                (4.0 * (1.0 + alpha) * (1.0 + beta) / (t * t * (t + 1.0))).sqrt()
+            } else {
+                (4.0 * k * (k + alpha) * (k + beta) * (k + s) / (t * t * (t + 1.0) * (t - 1.0)))
+                    .sqrt()
+            }
+        };
+        GaussRule::golub_welsch(n, diagonal, off_diagonal)
+    }
+
+    /// Computes the rule from the recurrence coefficients of the
+    /// orthonormal polynomials, where `diagonal(k)` is the `k`th diagonal
+    /// entry of the Jacobi matrix for `k` in `0..n` and `off_diagonal(k)`
+    /// the entry between rows `k - 1` and `k` for `k` in `1..n`
+    pub(crate) fn golub_welsch<A, B>(n: usize, diagonal: A, off_diagonal: B) -> Result<GaussRule>
+    where
+        A: Fn(usize) -> f64,
+        B: Fn(usize) -> f64,
+    {
+        if n == 0 {
+            return Err(StatsError::BadParams);
+        }
+        let mut jacobi = DMatrix::zeros(n, n);
+        for k in 0..n {
+            jacobi[(k, k)] = diagonal(k);
+            if k > 0 {
+                let b = off_diagonal(k);
+                jacobi[(k - 1, k)] = b;
+                jacobi[(k, k - 1)] = b;
+            }
+        }
+        let eigen = jacobi.symmetric_eigen();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| {
+            eigen.eigenvalues[a]
+                .partial_cmp(&eigen.eigenvalues[b])
+                .unwrap()
+        });
+        let nodes = order.iter().map(|&i| eigen.eigenvalues[i]).collect();
+        let mut weights: Vec<f64> = order
+            .iter()
+            .map(|&i| eigen.eigenvectors[(0, i)] * eigen.eigenvectors[(0, i)])
+            .collect();
+        let total: f64 = weights.iter().sum();
+        for w in &mut weights {
+            *w /= total;
+        }
+        Ok(GaussRule { nodes, weights })
+    }
+
+    /// Returns the rule with every node `x` replaced by `f(x)`, which keeps
+    /// the weights and so yields a rule for the transformed random variable
+    pub fn map<F>(&self, f: F) -> GaussRule
+    where
+        F: Fn(f64) -> f64,
+    {
+        GaussRule {
+            nodes: self.nodes.iter().map(|&x| f(x)).collect(),
+            weights: self.weights.clone(),
+        }
+    }
+
+    /// Returns the nodes of the rule in increasing order
+    pub fn nodes(&self) -> &[f64] {
+        &self.nodes
+    }
+
+    /// Returns the weights of the rule, which sum to one
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the number of nodes of the rule
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the rule has no nodes, which can not happen for
+    /// rules constructed by this module
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Approximates the expected value of `f(X)` by the weighted sum of `f`
+    /// over the nodes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Uniform;
+    /// use statrs::quadrature::GaussQuadrature;
+    ///
+    /// let rule = Uniform::new(0.0, 2.0).unwrap().gauss_rule(5).unwrap();
+    /// assert!((rule.expectation(|x| x.exp()) - (2f64.exp() - 1.0) / 2.0).abs() < 1e-8);
+    /// ```
+    pub fn expectation<F>(&self, mut f: F) -> f64
+    where
+        F: FnMut(f64) -> f64,
+    {
+        self.nodes
+            .iter()
+            .zip(&self.weights)
+            .map(|(&x, &w)| w * f(x))
+            .sum()
+    }
+}
+
+/// The `GaussQuadrature` trait provides the Gaussian quadrature rule
+/// associated with a distribution, i.e. the rule whose weight function is
+/// the density of the distribution
+pub trait GaussQuadrature {
+    /// Returns the `n`-point Gaussian quadrature rule for the distribution
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    /// use statrs::quadrature::GaussQuadrature;
+    ///
+    /// let rule = Normal::new(1.0, 2.0).unwrap().gauss_rule(4).unwrap();
+    /// assert!((rule.expectation(|x| x * x) - 5.0).abs() < 1e-12);
+    /// ```
+    fn gauss_rule(&self, n: usize) -> Result<GaussRule>;
+}
+
+impl GaussQuadrature for Normal {
+    /// Returns the Gauss-Hermite rule scaled by the standard deviation and
+    /// shifted by the mean
+    fn gauss_rule(&self, n: usize) -> Result<GaussRule> {
+        let mean = self.mean().unwrap();
+        let std_dev = self.std_dev().unwrap();
+        Ok(GaussRule::hermite(n)?.map(|x| mean + std_dev * x))
+    }
+}
+
+impl GaussQuadrature for LogNormal {
+    /// Returns the exponential of the Gauss-Hermite rule for the normal
+    /// distribution of the logarithm. The rule is not exact for polynomials
+    /// but converges quickly for smooth functions
+    fn gauss_rule(&self, n: usize) -> Result<GaussRule> {
+        let (location, scale) = (self.location(), self.scale());
+        Ok(GaussRule::hermite(n)?.map(|x| fp::exp(location + scale * x)))
+    }
+}
+
+impl GaussQuadrature for Uniform {
+    /// Returns the Gauss-Legendre rule mapped to `[min, max]`
+    fn gauss_rule(&self, n: usize) -> Result<GaussRule> {
+        let half_width = (self.max() - self.min()) / 2.0;
+        let centre = self.min() + half_width;
+        Ok(GaussRule::legendre(n)?.map(|x| centre + half_width * x))
+    }
+}
+
+impl GaussQuadrature for Gamma {
+    /// Returns the generalized Gauss-Laguerre rule with `α = shape - 1`
+    /// divided by the rate
+    fn gauss_rule(&self, n: usize) -> Result<GaussRule> {
+        let rate = self.rate();
+        Ok(GaussRule::laguerre(n, self.shape() - 1.0)?.map(|x| x / rate))
+    }
+}
+
+impl GaussQuadrature for Exp {
+    /// Returns the Gauss-Laguerre rule divided by the rate
+    fn gauss_rule(&self, n: usize) -> Result<GaussRule> {
+        let rate = self.rate();
+        Ok(GaussRule::laguerre(n, 0.0)?.map(|x| x / rate))
+    }
+}
+
+impl GaussQuadrature for Beta {
+    /// Returns the Gauss-Jacobi rule with `α = shape_b - 1` and
+    /// `β = shape_a - 1` mapped to `[0, 1]`
+    fn gauss_rule(&self, n: usize) -> Result<GaussRule> {
+        let rule = GaussRule::jacobi(n, self.shape_b() - 1.0, self.shape_a() - 1.0)?;
+        Ok(rule.map(|x| (1.0 + x) / 2.0))
+    }
+}
+
+/// Represents the moments of the output of a model whose inputs are
+/// independent random variables, as computed by `propagate`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Moments {
+    mean: f64,
+    variance: f64,
+    skewness: f64,
+}
+
+impl Moments {
+    /// Returns the mean of the model output
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the variance of the model output
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+
+    /// Returns the standard deviation of the model output
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    /// Returns the skewness of the model output, which is `NaN` if its
+    /// variance is zero
+    pub fn skewness(&self) -> f64 {
+        self.skewness
+    }
+}
+
+/// Propagates independent inputs with the quadrature rules `rules` through
+/// `model` by evaluating it on the tensor product grid of the rules, and
+/// returns the mean, variance and skewness of the output
+///
+/// # Remarks
+///
+/// The model is evaluated at the product of the lengths of the rules many
+/// points. The moments are exact if the `k`th power of the model is a
+/// polynomial of degree at most `2n - 1` in every input, where `n` is the
+/// length of the corresponding rule and `k` the order of the moment.
+///
+/// # Errors
+///
+/// Returns an error if `rules` is empty
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Normal, Uniform};
+/// use statrs::quadrature::{self, GaussQuadrature};
+///
+/// let x = Normal::new(1.0, 2.0).unwrap().gauss_rule(3).unwrap();
+/// let y = Uniform::new(0.0, 1.0).unwrap().gauss_rule(3).unwrap();
+/// let moments = quadrature::propagate(&[x, y], |v| v[0] * v[1]).unwrap();
+/// assert!((moments.mean() - 0.5).abs() < 1e-12);
+/// ```
+pub fn propagate<F>(rules: &[GaussRule], mut model: F) -> Result<Moments>
+where
+    F: FnMut(&[f64]) -> f64,
+{
+    if rules.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    let mut index = vec![0; rules.len()];
+    let mut point: Vec<f64> = rules.iter().map(|r| r.nodes[0]).collect();
+    let mut outputs = Vec::new();
+    loop {
+        let weight: f64 = rules
+            .iter()
+            .zip(&index)
+            .map(|(r, &i)| r.weights[i])
+            .product();
+        outputs.push((weight, model(&point)));
+        // advance the multi-index like an odometer
+        let mut dim = 0;
+        loop {
+            if dim == rules.len() {
+                return Ok(central_moments(&outputs));
+            }
+            index[dim] += 1;
+            if index[dim] < rules[dim].len() {
+                point[dim] = rules[dim].nodes[index[dim]];
+                break;
+            }
+            index[dim] = 0;
+            point[dim] = rules[dim].nodes[0];
+            dim += 1;
+        }
+    }
+}
+
+fn central_moments(outputs: &[(f64, f64)]) -> Moments {
+    let mean: f64 = outputs.iter().map(|&(w, y)| w * y).sum();
+    let (variance, third) = outputs.iter().fold((0.0, 0.0), |(m2, m3), &(w, y)| {
+        let d = y - mean;
+        (m2 + w * d * d, m3 + w * d * d * d)
+    });
+    Moments {
+        mean,
+        variance,
+        skewness: third / (variance * variance.sqrt()),
+    }
+}
+
+fn check_exponent(name: &'static str, x: f64) -> Result<()> {
+    if !x.is_finite() || x <= -1.0 {
+        Err(StatsError::ArgGt(name, -1.0))
+    } else {
+        Ok(())
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::gamma::gamma;
+
+    #[test]
+    fn test_bad_params() {
+        assert!(GaussRule::hermite(0).is_err());
+        assert!(GaussRule::laguerre(3, -1.0).is_err());
+        assert!(GaussRule::jacobi(3, 0.5, f64::NAN).is_err());
+        assert!(propagate(&[], |_| 0.0).is_err());
+    }
+
+    #[test]
+    fn test_known_rules() {
+        let rule = GaussRule::hermite(3).unwrap();
+        let sqrt3 = 3f64.sqrt();
+        for (x, e) in rule.nodes().iter().zip(&[-sqrt3, 0.0, sqrt3]) {
+            assert_almost_eq!(*x, *e, 1e-14);
+        }
+        for (w, e) in rule.weights().iter().zip(&[1.0 / 6.0, 2.0 / 3.0, 1.0 / 6.0]) {
+            assert_almost_eq!(*w, *e, 1e-14);
+        }
+
+        let rule = GaussRule::legendre(2).unwrap();
+        assert_almost_eq!(rule.nodes()[1], 1.0 / sqrt3, 1e-14);
+        assert_almost_eq!(rule.weights()[0], 0.5, 1e-14);
+
+        let rule = GaussRule::laguerre(2, 0.0).unwrap();
+        let sqrt2 = 2f64.sqrt();
+        assert_almost_eq!(rule.nodes()[0], 2.0 - sqrt2, 1e-14);
+        assert_almost_eq!(rule.nodes()[1], 2.0 + sqrt2, 1e-14);
+        assert_almost_eq!(rule.weights()[0], (2.0 + sqrt2) / 4.0, 1e-14);
+
+        // Chebyshev nodes of the first kind with equal weights
+        let rule = GaussRule::jacobi(4, -0.5, -0.5).unwrap();
+        for (k, x) in rule.nodes().iter().enumerate() {
+            let e = -((2 * k + 1) as f64 * std::f64::consts::PI / 8.0).cos();
+            assert_almost_eq!(*x, e, 1e-14);
+            assert_almost_eq!(rule.weights()[k], 0.25, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_polynomial_exactness() {
+        let gamma_dist = Gamma::new(3.5, 2.0).unwrap();
+        let rule = gamma_dist.gauss_rule(4).unwrap();
+        for k in 0..8 {
+            let expected = gamma(3.5 + k as f64) / gamma(3.5) / 2f64.powi(k);
+            assert_almost_eq!(rule.expectation(|x| x.powi(k)), expected, 1e-12 * expected);
+        }
+
+        let beta_dist = Beta::new(2.0, 5.0).unwrap();
+        let rule = beta_dist.gauss_rule(3).unwrap();
+        let mut expected = 1.0;
+        for k in 0..6 {
+            assert_almost_eq!(rule.expectation(|x| x.powi(k)), expected, 1e-14);
+            expected *= (2.0 + k as f64) / (7.0 + k as f64);
+        }
+
+        let rule = Exp::new(0.5).unwrap().gauss_rule(2).unwrap();
+        assert_almost_eq!(rule.expectation(|x| x * x * x), 48.0, 1e-12);
+
+        let rule = Uniform::new(-1.0, 3.0).unwrap().gauss_rule(2).unwrap();
+        assert_almost_eq!(rule.expectation(|x| x * x * x), 5.0, 1e-13);
+    }
+
+    #[test]
+    fn test_log_normal() {
+        let rule = LogNormal::new(0.5, 0.3).unwrap().gauss_rule(20).unwrap();
+        assert_almost_eq!(rule.expectation(|x| x), (0.5f64 + 0.045).exp(), 1e-13);
+        assert_almost_eq!(rule.expectation(|x| x * x), (1.0f64 + 0.18).exp(), 1e-12);
+    }
+
+    #[test]
+    fn test_propagate() {
+        let x = Normal::new(1.0, 2.0).unwrap().gauss_rule(3).unwrap();
+        let y = Uniform::new(0.0, 1.0).unwrap().gauss_rule(2).unwrap();
+        let moments = propagate(&[x.clone(), y.clone()], |v| v[0] + v[1]).unwrap();
+        assert_almost_eq!(moments.mean(), 1.5, 1e-14);
+        assert_almost_eq!(moments.variance(), 4.0 + 1.0 / 12.0, 1e-13);
+        assert_almost_eq!(moments.skewness(), 0.0, 1e-13);
+
+        // X^2 with X ~ N(0, 1) is chi-squared with skewness sqrt(8)
+        let z = GaussRule::hermite(4).unwrap();
+        let moments = propagate(&[z], |v| v[0] * v[0]).unwrap();
+        assert_almost_eq!(moments.mean(), 1.0, 1e-14);
+        assert_almost_eq!(moments.variance(), 2.0, 1e-13);
+        assert_almost_eq!(moments.skewness(), 8f64.sqrt(), 1e-12);
+
+        let e = Exp::new(1.0).unwrap().gauss_rule(3).unwrap();
+        let moments = propagate(&[x, y, e], |v| v[0] * v[1] * v[2]).unwrap();
+        assert_almost_eq!(moments.mean(), 0.5, 1e-13);
+        // E[X^2] E[Y^2] E[Z^2] - E[XYZ]^2
+        assert_almost_eq!(moments.variance(), 5.0 / 3.0 * 2.0 - 0.25, 1e-12);
+    }
+}
@@ -0,0 +1,297 @@
+//! Provides a random number generator pinned by the crate, for simulations
+//! whose results must be reproducible bit-for-bit across platforms and
+//! versions of `statrs`.
+//!
+//! The samplers of `statrs` draw from any `rand::Rng`, but the generators
+//! of `rand` such as `StdRng` may change between its releases. `Pcg64` is
+//! implemented here and its output sequence is part of the stability
+//! guarantees of the crate. The `deterministic` feature computes the
+//! elementary functions with the pure Rust port of musl libm, which removes
+//! the platform math library as a source of differences between targets.
+//!
+//! # Algorithms
+//!
+//! The following samplers use only integer and IEEE 754 arithmetic, the
+//! elementary functions covered by the `deterministic` feature and the
+//! conversions of random bits to uniforms of `rand` 0.8, so with the feature
+//! enabled they draw the same samples from a `Pcg64` on every target:
+//!
+//! - uniform: `Uniform::new_inclusive` of `rand`
+//! - normal and log-normal: the ZIGNOR ziggurat of Doornik (2005)
+//! - exponential: the ziggurat of Marsaglia & Tsang (2000)
+//! - gamma, chi-squared and Erlang: Marsaglia & Tsang (2000), boosted by
+//!   `U^(1 / α)` for shapes `α < 1`
+//! - beta and dirichlet: normalized gamma samples
+//! - poisson: multiplication of uniforms below a rate of 10, PTRS of
+//!   Hörmann (1993) otherwise
+//!
+//! Parallel simulations stay reproducible by giving every worker its own
+//! stream, e.g. `Pcg64::from_stream(seed, worker_id)`, instead of sharing a
+//! generator.
+
+use rand::distributions::Distribution;
+use rand::{Error, RngCore, SeedableRng};
+
+/// The multiplier of the 128 bit linear congruential generator
+const MULTIPLIER: u128 = 0x2360_ed05_1fc6_5da4_4385_df64_9fcc_f645;
+
+/// Implements the 64 bit output PCG generator `pcg64` of O'Neill (2014),
+/// the XSL RR output permutation of a 128 bit linear congruential
+/// generator, with `2^127` selectable streams
+///
+/// # Remarks
+///
+/// The sequence of `Pcg64::new(state, stream)` is that of the reference
+/// implementation and of `Lcg128Xsl64` of the `rand_pcg` crate. `next_u32`
+/// returns the low 32 bits of `next_u64`, and `fill_bytes` writes the
+/// outputs of `next_u64` in little-endian order. The generator is fast and
+/// statistically strong but not cryptographically secure.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::rng::Pcg64;
+/// use rand::distributions::Distribution;
+///
+/// let n = Normal::new(0.0, 1.0).unwrap();
+/// let mut rng = Pcg64::from_stream(42, 0);
+/// let x = n.sample(&mut rng);
+/// // the same seed and stream always yield the same sample
+/// assert_eq!(x, n.sample(&mut Pcg64::from_stream(42, 0)));
+/// assert_ne!(x, n.sample(&mut Pcg64::from_stream(42, 1)));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Pcg64 {
+    state: u128,
+    increment: u128,
+}
+
+impl Pcg64 {
+    /// Constructs the generator with initial state `state` on stream
+    /// `stream`, of which only the low 127 bits are used
+    pub fn new(state: u128, stream: u128) -> Pcg64 {
+        Pcg64::from_state_increment(state, (stream << 1) | 1)
+    }
+
+    /// Constructs the generator for stream `stream_id` of the 64 bit
+    /// `seed`, whose state is made of the first two outputs of SplitMix64
+    /// seeded with `seed`
+    ///
+    /// # Remarks
+    ///
+    /// Generators of different streams share no state, so they can be
+    /// handed to parallel workers and the results only depend on `seed` and
+    /// the assignment of stream ids.
+    pub fn from_stream(seed: u64, stream_id: u64) -> Pcg64 {
+        let mut splitmix = seed;
+        let high = split_mix_64(&mut splitmix) as u128;
+        let low = split_mix_64(&mut splitmix) as u128;
+        Pcg64::new(high << 64 | low, stream_id as u128)
+    }
+
+    fn from_state_increment(state: u128, increment: u128) -> Pcg64 {
+        let mut rng = Pcg64 {
+            state: state.wrapping_add(increment),
+            increment,
+        };
+        rng.step();
+        rng
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl RngCore for Pcg64 {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.step();
+        let rotation = (self.state >> 122) as u32;
+        let xsl = (self.state >> 64) as u64 ^ self.state as u64;
+        xsl.rotate_right(rotation)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for Pcg64 {
+    /// The little-endian state followed by the little-endian increment, whose
+    /// lowest bit is forced to one
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: [u8; 32]) -> Pcg64 {
+        let mut state = [0u8; 16];
+        let mut increment = [0u8; 16];
+        state.copy_from_slice(&seed[..16]);
+        increment.copy_from_slice(&seed[16..]);
+        Pcg64::from_state_increment(
+            u128::from_le_bytes(state),
+            u128::from_le_bytes(increment) | 1,
+        )
+    }
+
+    /// Constructs the generator for stream zero of `seed`, which unlike the
+    /// default implementation of `rand` is pinned by the crate
+    fn seed_from_u64(seed: u64) -> Pcg64 {
+        Pcg64::from_stream(seed, 0)
+    }
+}
+
+/// Advances the SplitMix64 generator of Steele et al. (2014) and returns its
+/// output
+fn split_mix_64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Draws a single sample from `dist` using the generator for stream
+/// `stream_id` of `seed`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Gamma;
+/// use statrs::rng::sample_with_stream;
+///
+/// let g = Gamma::new(2.0, 1.0).unwrap();
+/// let x: f64 = sample_with_stream(&g, 7, 3);
+/// assert_eq!(x, sample_with_stream(&g, 7, 3));
+/// ```
+pub fn sample_with_stream<D, T>(dist: &D, seed: u64, stream_id: u64) -> T
+where
+    D: Distribution<T>,
+{
+    dist.sample(&mut Pcg64::from_stream(seed, stream_id))
+}
+
+/// Draws `n` samples from `dist` using the generator for stream `stream_id`
+/// of `seed`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Poisson;
+/// use statrs::rng::sample_n_with_stream;
+///
+/// // every worker of a parallel simulation draws from its own stream
+/// let p = Poisson::new(50.0).unwrap();
+/// let chunks: Vec<Vec<f64>> = (0..4).map(|id| sample_n_with_stream(&p, 100, 2024, id)).collect();
+/// assert_eq!(chunks[2], sample_n_with_stream(&p, 100, 2024, 2));
+/// ```
+pub fn sample_n_with_stream<D, T>(dist: &D, n: usize, seed: u64, stream_id: u64) -> Vec<T>
+where
+    D: Distribution<T>,
+{
+    let mut rng = Pcg64::from_stream(seed, stream_id);
+    (0..n).map(|_| dist.sample(&mut rng)).collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Beta, Exp, Gamma, Normal, Poisson, Uniform};
+    use rand::Rng;
+
+    #[test]
+    fn test_reference_sequence() {
+        // the outputs of the reference pcg64 for state 42 on stream 54
+        let mut rng = Pcg64::new(42, 54);
+        let expected = [
+            0x86b1da1d72062b68, 0x1304aa46c9853d39, 0xa3670e9e0dd50358,
+            0xf9090e529a7dae00, 0xc85b9fd837996f2c, 0x606121f8e3919196,
+        ];
+        for &e in &expected {
+            assert_eq!(rng.next_u64(), e);
+        }
+    }
+
+    #[test]
+    fn test_seed_and_bytes() {
+        let mut seed = [0u8; 32];
+        seed[..16].copy_from_slice(&42u128.to_le_bytes());
+        seed[16..].copy_from_slice(&(54u128 << 1 | 1).to_le_bytes());
+        assert_eq!(Pcg64::from_seed(seed), Pcg64::new(42, 54));
+        assert_eq!(Pcg64::seed_from_u64(9), Pcg64::from_stream(9, 0));
+
+        let mut a = Pcg64::from_stream(1, 2);
+        let mut b = a;
+        let mut bytes = [0u8; 13];
+        a.fill_bytes(&mut bytes);
+        let first = b.next_u64().to_le_bytes();
+        let second = b.next_u64().to_le_bytes();
+        assert_eq!(&bytes[..8], &first);
+        assert_eq!(&bytes[8..], &second[..5]);
+        assert_eq!(a.next_u32(), b.next_u64() as u32);
+    }
+
+    #[test]
+    fn test_streams() {
+        let seeds: Vec<u64> = (0..4).map(|id| Pcg64::from_stream(5, id).next_u64()).collect();
+        for i in 0..4 {
+            for j in 0..i {
+                assert_ne!(seeds[i], seeds[j]);
+            }
+        }
+        assert_ne!(Pcg64::from_stream(5, 0).next_u64(), Pcg64::from_stream(6, 0).next_u64());
+        // the uniforms of a stream look uniform
+        let mut rng = Pcg64::from_stream(3, 7);
+        let mean = (0..100000).map(|_| rng.gen::<f64>()).sum::<f64>() / 100000.0;
+        assert!((mean - 0.5).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_sample_with_stream() {
+        let n = Normal::new(1.0, 2.0).unwrap();
+        let x: Vec<f64> = sample_n_with_stream(&n, 10, 11, 0);
+        assert_eq!(x, sample_n_with_stream(&n, 10, 11, 0));
+        assert_eq!(x[0], sample_with_stream(&n, 11, 0));
+        assert_ne!(x, sample_n_with_stream(&n, 10, 11, 1));
+        let x: Vec<f64> = sample_n_with_stream(&Poisson::new(40.0).unwrap(), 5, 11, 0);
+        assert!(x.iter().all(|&x| x.fract() == 0.0));
+    }
+
+    #[cfg(feature = "deterministic")]
+    #[test]
+    fn test_pinned_samples() {
+        // samples that must stay the same across targets and releases
+        const UNIFORM: [f64; 3] = [-0.30444544565025744, 0.2765682473949085, -0.2728011045060479];
+        const NORMAL: [f64; 3] = [-1.6536857022879647, -0.14455249568052064, -1.226601657160691];
+        const EXP: [f64; 3] = [0.8758438663969642, 0.22046748084834664, 0.5767212869756401];
+        const GAMMA: [f64; 3] = [0.02734506339996688, 0.01034895953992335, 0.3034379909378285];
+        const BETA: [f64; 3] = [0.9896754659065131, 0.981881556958528, 0.998396835024972];
+        const POISSON: [f64; 3] = [114.0, 114.0, 117.0];
+        let cases: Vec<(Vec<f64>, [f64; 3])> = vec![
+            (sample_n_with_stream(&Uniform::new(-1.0, 2.0).unwrap(), 3, 1, 0), UNIFORM),
+            (sample_n_with_stream(&Normal::new(0.0, 1.0).unwrap(), 3, 1, 0), NORMAL),
+            (sample_n_with_stream(&Exp::new(1.5).unwrap(), 3, 1, 0), EXP),
+            (sample_n_with_stream(&Gamma::new(0.5, 2.0).unwrap(), 3, 1, 0), GAMMA),
+            (sample_n_with_stream(&Beta::new(2.0, 0.3).unwrap(), 3, 1, 0), BETA),
+            (sample_n_with_stream(&Poisson::new(123.0).unwrap(), 3, 1, 0), POISSON),
+        ];
+        for (samples, expected) in cases {
+            assert_eq!(samples, expected.to_vec());
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use crate::fp;
+use crate::{Result, StatsError};
+
+/// Computes the effective sample size of one or more Markov chains sampling
+/// the same target, i.e. the number of independent draws that would
+/// estimate the mean with the same precision as the `m * n` correlated
+/// draws of `m` chains of length `n`
+///
+/// # Formula
+///
+/// ```ignore
+/// ess = m * n / τ,    τ = -1 + 2 * Σ_k (ρ_{2k} + ρ_{2k+1})
+/// ```
+///
+/// where `ρ_t` is the autocorrelation at lag `t` combined across chains as
+/// in Vehtari et al. (2021),
+///
+/// ```ignore
+/// ρ_t = 1 - (W - mean_j(c_{j,t})) / var⁺,    var⁺ = (n - 1) / n * W + B / n
+/// ```
+///
+/// with `c_{j,t}` the autocovariance of chain `j` at lag `t`, `W` the mean of
+/// the within-chain variances and `B / n` the variance of the chain means.
+/// The sum is truncated by Geyer's initial monotone sequence estimator: it
+/// stops at the first pair `ρ_{2k} + ρ_{2k+1}` that is not positive and
+/// the pairs are made non-increasing.
+///
+/// # Remarks
+///
+/// The chains are used as given. Split chains, e.g. halves of every chain,
+/// make the estimate sensitive to non-stationarity as well. As in Stan the
+/// result is capped at `m * n * log10(m * n)` for antithetic chains.
+///
+/// # Errors
+///
+/// Returns an error if `chains` is empty, if the chains do not all have the
+/// same length of at least four draws, if any draw is not finite or if all
+/// chains are constant
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler::ess;
+///
+/// let chain = [0.3, -1.2, 0.8, 1.5, -0.4, 0.1, -0.9, 2.0];
+/// let n_eff = ess(&[chain]).unwrap();
+/// assert!(n_eff > 0.0);
+/// ```
+pub fn ess<T: AsRef<[f64]>>(chains: &[T]) -> Result<f64> {
+    let n = check_chains(chains, 4)?;
+    let m = chains.len();
+    let means: Vec<f64> = chains.iter().map(|c| mean(c.as_ref())).collect();
+    let (w, var_plus) = variance_components(chains, &means)?;
+    let rho = |t: usize| {
+        let acov = chains
+            .iter()
+            .zip(means.iter())
+            .map(|(c, &mu)| autocovariance(c.as_ref(), mu, t))
+            .sum::<f64>()
+            / m as f64;
+        1.0 - (w - acov) / var_plus
+    };
+
+    let mut sum = 0.0;
+    let mut last_pair = f64::INFINITY;
+    let mut t = 0;
+    while t + 1 < n {
+        let pair = rho(t) + rho(t + 1);
+        if pair <= 0.0 {
+            break;
+        }
+        last_pair = pair.min(last_pair);
+        sum += last_pair;
+        t += 2;
+    }
+    let total = (m * n) as f64;
+    let tau = (2.0 * sum - 1.0).max(1.0 / fp::log10(total));
+    Ok(total / tau)
+}
+
+/// Computes the split potential scale reduction factor `R̂` of one or more
+/// Markov chains sampling the same target. Values close to one
+/// indicate convergence; `R̂ > 1.01` is commonly taken as a sign that the
+/// chains have not yet mixed.
+///
+/// # Formula
+///
+/// ```ignore
+/// R̂ = sqrt(var⁺ / W),    var⁺ = (n - 1) / n * W + B / n
+/// ```
+///
+/// where every chain is split into halves of length `n`, dropping the
+/// middle draw of chains of odd length, `W` is the mean of the within-half
+/// variances and `B / n` is the variance of the half means (Gelman et al.,
+/// Bayesian Data Analysis, 3rd ed., section 11.4).
+///
+/// # Errors
+///
+/// Returns an error if `chains` is empty, if the chains do not all have the
+/// same length of at least four draws, if any draw is not finite or if all
+/// halves are constant
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler::split_rhat;
+///
+/// let r = split_rhat(&[[1.0, 2.0, 3.0, 4.0], [2.0, 3.0, 4.0, 5.0]]).unwrap();
+/// assert!((r - 1.957890).abs() < 1e-6);
+/// ```
+pub fn split_rhat<T: AsRef<[f64]>>(chains: &[T]) -> Result<f64> {
+    let n = check_chains(chains, 4)?;
+    let half = n / 2;
+    let halves: Vec<&[f64]> = chains
+        .iter()
+        .flat_map(|c| {
+            let c = c.as_ref();
+            vec![&c[..half], &c[n - half..]]
+        })
+        .collect();
+    let means: Vec<f64> = halves.iter().map(|h| mean(h)).collect();
+    let (w, var_plus) = variance_components(&halves, &means)?;
+    Ok((var_plus / w).sqrt())
+}
+
+/// Checks that `chains` is non-empty and that all chains have the same
+/// length of at least `min_len` finite draws, returning that length
+fn check_chains<T: AsRef<[f64]>>(chains: &[T], min_len: usize) -> Result<usize> {
+    let n = match chains.first() {
+        Some(first) if first.as_ref().len() >= min_len => first.as_ref().len(),
+        _ => return Err(StatsError::BadParams),
+    };
+    if chains.iter().any(|c| c.as_ref().len() != n) {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if chains
+        .iter()
+        .any(|c| c.as_ref().iter().any(|x| !x.is_finite()))
+    {
+        return Err(StatsError::BadParams);
+    }
+    Ok(n)
+}
+
+/// Returns the mean within-chain variance `W` and the pooled variance
+/// estimate `var⁺` of chains with the given means
+fn variance_components<T: AsRef<[f64]>>(chains: &[T], means: &[f64]) -> Result<(f64, f64)> {
+    let m = chains.len() as f64;
+    let n = chains[0].as_ref().len() as f64;
+    let w = chains
+        .iter()
+        .zip(means.iter())
+        .map(|(c, &mu)| autocovariance(c.as_ref(), mu, 0) * n / (n - 1.0))
+        .sum::<f64>()
+        / m;
+    if w <= 0.0 {
+        return Err(StatsError::SpecialCase("chains have zero variance"));
+    }
+    let between = if means.len() > 1 {
+        let grand = mean(means);
+        means.iter().map(|mu| fp::powi(mu - grand, 2)).sum::<f64>() / (m - 1.0)
+    } else {
+        0.0
+    };
+    Ok((w, (n - 1.0) / n * w + between))
+}
+
+/// Returns the biased autocovariance of `x` with mean `mu` at lag `t`
+fn autocovariance(x: &[f64], mu: f64, t: usize) -> f64 {
+    x.iter()
+        .zip(x[t..].iter())
+        .map(|(a, b)| (a - mu) * (b - mu))
+        .sum::<f64>()
+        / x.len() as f64
+}
+
+fn mean(x: &[f64]) -> f64 {
+    x.iter().sum::<f64>() / x.len() as f64
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn ar1(phi: f64, n: usize, rng: &mut StdRng) -> Vec<f64> {
+        let noise = Normal::new(0.0, 1.0).unwrap();
+        let mut x = noise.sample(rng) / (1.0 - phi * phi).sqrt();
+        (0..n)
+            .map(|_| {
+                x = phi * x + noise.sample(rng);
+                x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_rhat_exact() {
+        // halves [1, 2], [3, 4], [2, 3], [4, 5]: W = 1/2, B/n = 5/3
+        let r = split_rhat(&[[1.0, 2.0, 3.0, 4.0], [2.0, 3.0, 4.0, 5.0]]).unwrap();
+        assert_almost_eq!(r, (23.0f64 / 6.0).sqrt(), 1e-15);
+        // the middle draw of odd chains is dropped
+        let odd = split_rhat(&[[1.0, 2.0, 100.0, 3.0, 4.0]]).unwrap();
+        let even = split_rhat(&[[1.0, 2.0, 3.0, 4.0]]).unwrap();
+        assert_almost_eq!(odd, even, 1e-15);
+    }
+
+    #[test]
+    fn test_converged() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let chains: Vec<Vec<f64>> = (0..4).map(|_| ar1(0.0, 2000, &mut rng)).collect();
+        let r = split_rhat(&chains).unwrap();
+        assert!(r < 1.01, "{}", r);
+        let n_eff = ess(&chains).unwrap();
+        assert!((n_eff / 8000.0 - 1.0).abs() < 0.15, "{}", n_eff);
+    }
+
+    #[test]
+    fn test_ess_autocorrelated() {
+        // the integrated autocorrelation time of an AR(1) process is
+        // (1 + φ) / (1 - φ) = 19
+        let mut rng = StdRng::seed_from_u64(1);
+        let chains: Vec<Vec<f64>> = (0..4).map(|_| ar1(0.9, 5000, &mut rng)).collect();
+        let n_eff = ess(&chains).unwrap();
+        assert!((n_eff / (20000.0 / 19.0) - 1.0).abs() < 0.2, "{}", n_eff);
+        assert!(split_rhat(&chains).unwrap() < 1.01);
+    }
+
+    #[test]
+    fn test_not_mixed() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut chains: Vec<Vec<f64>> = (0..4).map(|_| ar1(0.0, 500, &mut rng)).collect();
+        for x in chains[0].iter_mut() {
+            *x += 3.0;
+        }
+        assert!(split_rhat(&chains).unwrap() > 1.2);
+        // a trend within a single chain is caught by splitting
+        let trend: Vec<f64> = ar1(0.0, 1000, &mut rng)
+            .iter()
+            .enumerate()
+            .map(|(i, x)| x + i as f64 / 100.0)
+            .collect();
+        assert!(split_rhat(&[trend]).unwrap() > 1.5);
+    }
+
+    #[test]
+    fn test_antithetic_cap() {
+        let chain: Vec<f64> = (0..100).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+        let n_eff = ess(&[chain]).unwrap();
+        assert!(n_eff > 100.0);
+        assert!(n_eff <= 100.0 * 2.0 + 1e-9);
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let empty: [[f64; 4]; 0] = [];
+        assert!(ess(&empty).is_err());
+        assert!(split_rhat(&[[1.0, 2.0, 3.0]]).is_err());
+        assert!(matches!(
+            ess(&[vec![1.0, 2.0, 3.0, 4.0], vec![1.0, 2.0, 3.0, 4.0, 5.0]]),
+            Err(StatsError::ContainersMustBeSameLength)
+        ));
+        assert!(split_rhat(&[[1.0, 2.0, f64::NAN, 4.0]]).is_err());
+        assert!(ess(&[[1.0; 8], [1.0; 8]]).is_err());
+    }
+}
@@ -0,0 +1,320 @@
+use crate::distribution::Beta;
+use crate::{Result, StatsError};
+use rand::distributions::Distribution;
+use rand::Rng;
+
+/// Draws the first `n` weights of the stick-breaking construction of a
+/// Dirichlet process with concentration `alpha`, i.e. of the GEM(`alpha`)
+/// distribution, truncated so that the last weight takes the remainder of
+/// the stick and the weights sum to one
+///
+/// # Formula
+///
+/// ```ignore
+/// w_k = v_k * prod_{j < k} (1 - v_j)
+/// ```
+///
+/// where `v_k ~ Beta(1, α)` for `k < n` and `v_n = 1`
+///
+/// # Errors
+///
+/// Returns an error if `alpha` is not finite and positive or `n` is zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let weights = sampler::stick_breaking(2.0, 50, &mut rng).unwrap();
+/// assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+/// ```
+pub fn stick_breaking<R: Rng + ?Sized>(alpha: f64, n: usize, rng: &mut R) -> Result<Vec<f64>> {
+    check_alpha(alpha)?;
+    if n == 0 {
+        return Err(StatsError::BadParams);
+    }
+    let beta = Beta::new(1.0, alpha)?;
+    let mut remaining = 1.0;
+    let mut weights = Vec::with_capacity(n);
+    for _ in 1..n {
+        let w = remaining * beta.sample(rng);
+        weights.push(w);
+        remaining -= w;
+    }
+    weights.push(remaining.max(0.0));
+    Ok(weights)
+}
+
+/// Draws the table assignments of `n` customers in the Chinese restaurant
+/// process with concentration `alpha`, which is the partition induced by
+/// `n` draws from a Dirichlet process. Tables are numbered in the order in
+/// which they are opened, so the first customer sits at table `0`.
+///
+/// # Remarks
+///
+/// Customer `i` (counting from zero) joins an occupied table with
+/// probability proportional to the number of its customers and opens a new
+/// table with probability `α / (α + i)`.
+///
+/// # Errors
+///
+/// Returns an error if `alpha` is not finite and positive
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let tables = sampler::chinese_restaurant(1.0, 100, &mut rng).unwrap();
+/// assert_eq!(tables[0], 0);
+/// assert!(tables.iter().enumerate().all(|(i, &t)| t <= i));
+/// ```
+pub fn chinese_restaurant<R: Rng + ?Sized>(
+    alpha: f64,
+    n: usize,
+    rng: &mut R,
+) -> Result<Vec<usize>> {
+    check_alpha(alpha)?;
+    let mut counts: Vec<usize> = Vec::new();
+    let mut tables = Vec::with_capacity(n);
+    for i in 0..n {
+        let table = seat(&counts, alpha, i, rng);
+        if table == counts.len() {
+            counts.push(0);
+        }
+        counts[table] += 1;
+        tables.push(table);
+    }
+    Ok(tables)
+}
+
+/// Returns the table of customer `i` given the number of customers at
+/// every occupied table, where `counts.len()` denotes a new table
+fn seat<R: Rng + ?Sized>(counts: &[usize], alpha: f64, i: usize, rng: &mut R) -> usize {
+    let mut u = rng.gen::<f64>() * (alpha + i as f64);
+    for (table, &count) in counts.iter().enumerate() {
+        u -= count as f64;
+        if u < 0.0 {
+            return table;
+        }
+    }
+    counts.len()
+}
+
+fn check_alpha(alpha: f64) -> Result<()> {
+    if alpha.is_finite() && alpha > 0.0 {
+        Ok(())
+    } else {
+        Err(StatsError::ArgMustBePositive("alpha"))
+    }
+}
+
+/// Represents a Dirichlet process mixture model, in which every observation
+/// is drawn from a component distribution built by `kernel` from parameters
+/// that are drawn from a Dirichlet process with concentration `alpha` and
+/// base distribution `base`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::sampler::DirichletProcessMixture;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// // unit variance gaussian clusters with widely spread means
+/// let base = Normal::new(0.0, 10.0).unwrap();
+/// let dpm = DirichletProcessMixture::new(1.0, base, |mu: &f64| Normal::new(*mu, 1.0).unwrap())
+///     .unwrap();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let sample = dpm.sample_n(200, &mut rng);
+/// assert_eq!(sample.observations().len(), 200);
+/// assert!(sample.parameters().len() < 20);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DirichletProcessMixture<B, K> {
+    alpha: f64,
+    base: B,
+    kernel: K,
+}
+
+impl<B, K> DirichletProcessMixture<B, K> {
+    /// Constructs a new Dirichlet process mixture with concentration
+    /// `alpha`, base distribution `base` of the component parameters and
+    /// `kernel` mapping parameters to component distributions
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `alpha` is not finite and positive
+    pub fn new(alpha: f64, base: B, kernel: K) -> Result<DirichletProcessMixture<B, K>> {
+        check_alpha(alpha)?;
+        Ok(DirichletProcessMixture {
+            alpha,
+            base,
+            kernel,
+        })
+    }
+
+    /// Returns the concentration of the Dirichlet process
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Returns the base distribution of the component parameters
+    pub fn base(&self) -> &B {
+        &self.base
+    }
+
+    /// Draws `n` observations from the mixture by seating them in a Chinese
+    /// restaurant process, drawing the parameters of every new cluster from
+    /// the base distribution and every observation from the component of
+    /// its cluster
+    pub fn sample_n<P, D, T, R>(&self, n: usize, rng: &mut R) -> MixtureSample<P, T>
+    where
+        B: Distribution<P>,
+        K: Fn(&P) -> D,
+        D: Distribution<T>,
+        R: Rng + ?Sized,
+    {
+        let mut counts: Vec<usize> = Vec::new();
+        let mut components = Vec::new();
+        let mut parameters = Vec::new();
+        let mut assignments = Vec::with_capacity(n);
+        let mut observations = Vec::with_capacity(n);
+        for i in 0..n {
+            let cluster = seat(&counts, self.alpha, i, rng);
+            if cluster == counts.len() {
+                let p = self.base.sample(rng);
+                components.push((self.kernel)(&p));
+                parameters.push(p);
+                counts.push(0);
+            }
+            counts[cluster] += 1;
+            assignments.push(cluster);
+            observations.push(components[cluster].sample(rng));
+        }
+        MixtureSample {
+            assignments,
+            parameters,
+            observations,
+        }
+    }
+}
+
+/// Represents a sample drawn by `DirichletProcessMixture::sample_n`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MixtureSample<P, T> {
+    assignments: Vec<usize>,
+    parameters: Vec<P>,
+    observations: Vec<T>,
+}
+
+impl<P, T> MixtureSample<P, T> {
+    /// Returns the cluster of every observation, numbered in the order in
+    /// which the clusters were opened
+    pub fn assignments(&self) -> &[usize] {
+        &self.assignments
+    }
+
+    /// Returns the component parameters of every cluster
+    pub fn parameters(&self) -> &[P] {
+        &self.parameters
+    }
+
+    /// Returns the observations
+    pub fn observations(&self) -> &[T] {
+        &self.observations
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Dirac, Normal};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bad_params() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(stick_breaking(0.0, 5, &mut rng).is_err());
+        assert!(stick_breaking(f64::INFINITY, 5, &mut rng).is_err());
+        assert!(stick_breaking(1.0, 0, &mut rng).is_err());
+        assert!(chinese_restaurant(-1.0, 5, &mut rng).is_err());
+        assert!(chinese_restaurant(f64::NAN, 5, &mut rng).is_err());
+        let base = Normal::new(0.0, 1.0).unwrap();
+        assert!(DirichletProcessMixture::new(0.0, base, |mu: &f64| Dirac::new(*mu).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_stick_breaking() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(stick_breaking(1.0, 1, &mut rng).unwrap(), vec![1.0]);
+        // the first weight is Beta(1, α) distributed with mean 1 / (1 + α)
+        let alpha = 3.0;
+        let mut first = 0.0;
+        for _ in 0..20000 {
+            let w = stick_breaking(alpha, 10, &mut rng).unwrap();
+            assert_eq!(w.len(), 10);
+            assert!(w.iter().all(|&w| w >= 0.0));
+            assert_almost_eq!(w.iter().sum::<f64>(), 1.0, 1e-14);
+            first += w[0];
+        }
+        assert!((first / 20000.0 - 0.25).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_chinese_restaurant() {
+        let mut rng = StdRng::seed_from_u64(2);
+        assert!(chinese_restaurant(1.0, 0, &mut rng).unwrap().is_empty());
+        // the expected number of tables is sum_i α / (α + i)
+        let (alpha, n) = (2.0, 100);
+        let expected: f64 = (0..n).map(|i| alpha / (alpha + i as f64)).sum();
+        let reps = 2000;
+        let mut tables = 0;
+        for _ in 0..reps {
+            let t = chinese_restaurant(alpha, n, &mut rng).unwrap();
+            let opened = t.iter().max().unwrap() + 1;
+            // tables are opened in order
+            let mut next = 0;
+            for &x in &t {
+                assert!(x <= next);
+                if x == next {
+                    next += 1;
+                }
+            }
+            tables += opened;
+        }
+        assert!((tables as f64 / reps as f64 - expected).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_mixture() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let base = Normal::new(0.0, 100.0).unwrap();
+        let dpm = DirichletProcessMixture::new(0.5, base, |mu: &f64| Dirac::new(*mu).unwrap()).unwrap();
+        assert_eq!(dpm.alpha(), 0.5);
+        let sample = dpm.sample_n(500, &mut rng);
+        assert_eq!(sample.assignments().len(), 500);
+        assert_eq!(sample.parameters().len(), sample.assignments().iter().max().unwrap() + 1);
+        for (&c, &x) in sample.assignments().iter().zip(sample.observations()) {
+            assert_eq!(x, sample.parameters()[c]);
+        }
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let base = Normal::new(0.0, 10.0).unwrap();
+        let dpm = DirichletProcessMixture::new(1.0, base, |mu: &f64| Normal::new(*mu, 1.0).unwrap()).unwrap();
+        let a = dpm.sample_n(50, &mut StdRng::seed_from_u64(4));
+        let b = dpm.sample_n(50, &mut StdRng::seed_from_u64(4));
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,607 @@
+use crate::distribution::{Beta, Gamma, InverseGamma, Normal};
+use crate::fp;
+use crate::sampler::ess;
+use crate::sampler::metropolis::RngRef;
+use crate::statistics::{Data, Distribution, OrderStatistics};
+use crate::{Result, StatsError};
+use rand::{Rng, RngCore};
+use std::fmt;
+
+/// A draw of one coordinate from its full conditional given the state
+type Draw = Box<dyn Fn(&[f64], &mut dyn RngCore) -> f64 + Send + Sync>;
+
+/// The full conditional distribution of one coordinate of a `Gibbs`
+/// sampler, drawing a new value of the coordinate given the whole state
+///
+/// # Remarks
+///
+/// Besides arbitrary samplers given to `new`, the associated functions
+/// build the conditionals of standard conjugate pairs from observed data.
+/// The data are reduced to sufficient statistics on construction. For a
+/// coordinate without a conjugate conditional, a step of a `Slice` sampler
+/// of the log target as a function of that coordinate keeps the target
+/// invariant as well.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Beta;
+/// use statrs::sampler::Conditional;
+///
+/// // a fixed conditional ignoring the state
+/// let uniform = Conditional::new(0, |_, rng| rand::Rng::gen::<f64>(rng));
+/// assert_eq!(uniform.index(), 0);
+/// // the posterior of a success probability after 7 successes in 10 trials
+/// let p = Conditional::binomial_probability(1, Beta::new(1.0, 1.0).unwrap(), &[7], &[10]);
+/// let x = p.draw(&[0.5, 0.5], &mut rand::thread_rng());
+/// assert!(x > 0.0 && x < 1.0);
+/// ```
+pub struct Conditional {
+    index: usize,
+    draw: Draw,
+}
+
+impl Conditional {
+    /// Constructs the conditional of the coordinate `index` drawn by
+    /// `draw` given the current state, in which the coordinate still has
+    /// its previous value
+    pub fn new<D>(index: usize, draw: D) -> Conditional
+    where
+        D: Fn(&[f64], &mut dyn RngCore) -> f64 + Send + Sync + 'static,
+    {
+        Conditional {
+            index,
+            draw: Box::new(draw),
+        }
+    }
+
+    /// Constructs the conditional of the mean `μ` at `index` of normal
+    /// `data` with the variance `σ²` at `variance_index` and the normal
+    /// prior `prior` on `μ`, which is normal with precision
+    /// `1 / s0² + n / σ²` and mean `(m0 / s0² + Σ x_i / σ²)` over that
+    /// precision
+    ///
+    /// # Remarks
+    ///
+    /// Together with `normal_variance` this gives the Gibbs sampler of the
+    /// normal model with independent normal and inverse gamma priors. The
+    /// variance in the state must be positive.
+    pub fn normal_mean(
+        index: usize,
+        prior: Normal,
+        variance_index: usize,
+        data: &[f64],
+    ) -> Conditional {
+        let n = data.len() as f64;
+        let sum: f64 = data.iter().sum();
+        let m0 = prior.mean().unwrap();
+        let prior_precision = 1.0 / prior.variance().unwrap();
+        Conditional::new(index, move |state, rng| {
+            let precision = prior_precision + n / state[variance_index];
+            let mean = (m0 * prior_precision + sum / state[variance_index]) / precision;
+            let normal = Normal::new_unchecked(mean, precision.sqrt().recip());
+            ::rand::distributions::Distribution::sample(&normal, rng)
+        })
+    }
+
+    /// Constructs the conditional of the variance `σ²` at `index` of normal
+    /// `data` with the mean `μ` at `mean_index` and the inverse gamma prior
+    /// `prior` on `σ²`, which is inverse gamma with shape `a + n / 2` and
+    /// rate `b + Σ (x_i - μ)² / 2`
+    pub fn normal_variance(
+        index: usize,
+        prior: InverseGamma,
+        mean_index: usize,
+        data: &[f64],
+    ) -> Conditional {
+        let n = data.len() as f64;
+        let (mean, scatter) = mean_scatter(data);
+        let shape = prior.shape() + n / 2.0;
+        let rate = prior.rate();
+        Conditional::new(index, move |state, rng| {
+            let deviation = scatter + n * fp::powi(mean - state[mean_index], 2);
+            let gamma = Gamma::new_unchecked(shape, rate + deviation / 2.0);
+            1.0 / ::rand::distributions::Distribution::sample(&gamma, rng)
+        })
+    }
+
+    /// Constructs the conditional of the success probability `p` at `index`
+    /// of binomial data with `successes[i]` successes in `trials[i]` trials
+    /// and the beta prior `prior` on `p`, which is beta with shapes
+    /// `α + Σ k_i` and `β + Σ (n_i - k_i)`
+    ///
+    /// # Panics
+    ///
+    /// If `successes` and `trials` differ in length or any count of
+    /// successes exceeds its trials
+    pub fn binomial_probability(
+        index: usize,
+        prior: Beta,
+        successes: &[u64],
+        trials: &[u64],
+    ) -> Conditional {
+        assert_eq!(successes.len(), trials.len());
+        assert!(successes.iter().zip(trials).all(|(k, n)| k <= n));
+        let k: u64 = successes.iter().sum();
+        let n: u64 = trials.iter().sum();
+        let beta =
+            Beta::new_unchecked(prior.shape_a() + k as f64, prior.shape_b() + (n - k) as f64);
+        Conditional::new(index, move |_, rng| {
+            ::rand::distributions::Distribution::sample(&beta, rng)
+        })
+    }
+
+    /// Constructs the conditional of the rate `λ` at `index` of poisson
+    /// `counts` and the gamma prior `prior` on `λ`, which is gamma with
+    /// shape `a + Σ k_i` and rate `b + n`
+    pub fn poisson_rate(index: usize, prior: Gamma, counts: &[u64]) -> Conditional {
+        let k: u64 = counts.iter().sum();
+        let gamma =
+            Gamma::new_unchecked(prior.shape() + k as f64, prior.rate() + counts.len() as f64);
+        Conditional::new(index, move |_, rng| {
+            ::rand::distributions::Distribution::sample(&gamma, rng)
+        })
+    }
+
+    /// Returns the index of the coordinate drawn by the conditional
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Draws a new value of the coordinate given `state`
+    pub fn draw<R: Rng + ?Sized>(&self, state: &[f64], rng: &mut R) -> f64 {
+        let mut rng = RngRef(rng);
+        (self.draw)(state, &mut rng)
+    }
+}
+
+impl fmt::Debug for Conditional {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Conditional")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// Implements a [Gibbs sampler](https://en.wikipedia.org/wiki/Gibbs_sampling)
+/// cycling through the full conditionals of the coordinates of a state in
+/// `R^d`
+///
+/// Each step replaces every coordinate in turn, in the order in which the
+/// conditionals were added, by a draw from its conditional given the
+/// current values of all other coordinates.
+///
+/// # Remarks
+///
+/// Coordinates without a conditional are kept fixed, and a coordinate may
+/// have several conditionals, e.g. updates by different samplers. `states`
+/// first runs the burn-in, then yields every `thin`th state, while `trace`
+/// collects draws into a `Trace` for summaries.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{InverseGamma, Normal};
+/// use statrs::sampler::{Conditional, Gibbs};
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// // the mean and variance of normal data under independent priors
+/// let data = [9.8, 10.4, 10.1, 9.5, 10.9, 10.2, 9.9, 10.6];
+/// let mut sampler = Gibbs::new(vec![0.0, 1.0])
+///     .unwrap()
+///     .with_conditional(Conditional::normal_mean(0, Normal::new(0.0, 100.0).unwrap(), 1, &data))
+///     .unwrap()
+///     .with_conditional(Conditional::normal_variance(1, InverseGamma::new(1.0, 1.0).unwrap(), 0, &data))
+///     .unwrap()
+///     .with_burn_in(500);
+/// let trace = sampler.trace(5000, &mut StdRng::seed_from_u64(0));
+/// let mean = trace.summary(0).unwrap();
+/// assert!((mean.mean() - 10.175).abs() < 0.05);
+/// assert!(mean.lower() < 10.175 && mean.upper() > 10.175);
+/// ```
+pub struct Gibbs {
+    state: Vec<f64>,
+    conditionals: Vec<Conditional>,
+    burn_in: usize,
+    thin: usize,
+}
+
+impl Gibbs {
+    /// Constructs a Gibbs sampler starting from `initial` without any
+    /// conditionals, burn-in or thinning
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `initial` is empty or has a non-finite entry
+    pub fn new(initial: Vec<f64>) -> Result<Gibbs> {
+        if initial.is_empty() || initial.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Gibbs {
+            state: initial,
+            conditionals: Vec::new(),
+            burn_in: 0,
+            thin: 1,
+        })
+    }
+
+    /// Returns the sampler with `conditional` added to the end of the cycle
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index of `conditional` is out of bounds for
+    /// the state
+    pub fn with_conditional(mut self, conditional: Conditional) -> Result<Gibbs> {
+        if conditional.index >= self.state.len() {
+            return Err(StatsError::ArgLt("index", self.state.len() as f64));
+        }
+        self.conditionals.push(conditional);
+        Ok(self)
+    }
+
+    /// Returns the sampler running `burn_in` steps before yielding states
+    pub fn with_burn_in(self, burn_in: usize) -> Gibbs {
+        Gibbs { burn_in, ..self }
+    }
+
+    /// Returns the sampler yielding only every `thin`th state
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `thin` is zero
+    pub fn with_thinning(self, thin: usize) -> Result<Gibbs> {
+        if thin == 0 {
+            return Err(StatsError::ArgMustBePositive("thin"));
+        }
+        Ok(Gibbs { thin, ..self })
+    }
+
+    /// Returns the current state of the chain
+    pub fn state(&self) -> &[f64] {
+        &self.state
+    }
+
+    /// Returns the conditionals in the order of the cycle
+    pub fn conditionals(&self) -> &[Conditional] {
+        &self.conditionals
+    }
+
+    /// Returns the number of burn-in steps still to be run
+    pub fn burn_in(&self) -> usize {
+        self.burn_in
+    }
+
+    /// Returns the thinning interval
+    pub fn thin(&self) -> usize {
+        self.thin
+    }
+
+    /// Runs a single cycle through the conditionals
+    pub fn step<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        for conditional in &self.conditionals {
+            let x = conditional.draw(&self.state, rng);
+            self.state[conditional.index] = x;
+        }
+    }
+
+    /// Returns an infinite iterator over the states of the chain drawn
+    /// using `rng`, running the remaining burn-in first
+    pub fn states<R: Rng>(&mut self, rng: R) -> GibbsStates<'_, R> {
+        GibbsStates { sampler: self, rng }
+    }
+
+    /// Collects the next `n` states of the chain drawn using `rng`, running
+    /// the remaining burn-in first
+    pub fn trace<R: Rng>(&mut self, n: usize, rng: R) -> Trace {
+        Trace {
+            states: self.states(rng).take(n).collect(),
+        }
+    }
+}
+
+impl fmt::Debug for Gibbs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Gibbs")
+            .field("state", &self.state)
+            .field("conditionals", &self.conditionals)
+            .field("burn_in", &self.burn_in)
+            .field("thin", &self.thin)
+            .finish()
+    }
+}
+
+/// An infinite iterator over the states of a `Gibbs` chain, created by
+/// `Gibbs::states`
+#[derive(Debug)]
+pub struct GibbsStates<'a, R> {
+    sampler: &'a mut Gibbs,
+    rng: R,
+}
+
+impl<'a, R: Rng> Iterator for GibbsStates<'a, R> {
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Vec<f64>> {
+        while self.sampler.burn_in > 0 {
+            self.sampler.step(&mut self.rng);
+            self.sampler.burn_in -= 1;
+        }
+        for _ in 0..self.sampler.thin {
+            self.sampler.step(&mut self.rng);
+        }
+        Some(self.sampler.state.clone())
+    }
+}
+
+/// The states of a Markov chain in `R^d`, e.g. collected by `Gibbs::trace`
+/// or from the states of any other sampler, with summaries of the
+/// marginal of every coordinate
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler::Trace;
+///
+/// let trace = Trace::new(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![2.0, 3.0]]).unwrap();
+/// assert_eq!(trace.len(), 3);
+/// assert_eq!(trace.dim(), 2);
+/// assert_eq!(trace.coordinate(1), vec![2.0, 4.0, 3.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    states: Vec<Vec<f64>>,
+}
+
+impl Trace {
+    /// Constructs a trace of the chain with the given `states`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `states` is empty, if the states are empty or
+    /// differ in dimension
+    pub fn new(states: Vec<Vec<f64>>) -> Result<Trace> {
+        match states.first() {
+            Some(first) if !first.is_empty() => {
+                if states.iter().any(|x| x.len() != first.len()) {
+                    return Err(StatsError::BadParams);
+                }
+            }
+            _ => return Err(StatsError::BadParams),
+        }
+        Ok(Trace { states })
+    }
+
+    /// Returns the number of states
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Returns `true` if the trace has no states
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Returns the dimension of the states, which is zero for an empty
+    /// trace
+    pub fn dim(&self) -> usize {
+        self.states.first().map_or(0, |x| x.len())
+    }
+
+    /// Returns the states in the order of the chain
+    pub fn states(&self) -> &[Vec<f64>] {
+        &self.states
+    }
+
+    /// Returns the draws of the coordinate `i` in the order of the chain
+    ///
+    /// # Panics
+    ///
+    /// If `i` is not less than the dimension
+    pub fn coordinate(&self, i: usize) -> Vec<f64> {
+        self.states.iter().map(|x| x[i]).collect()
+    }
+
+    /// Returns the summary of the draws of the coordinate `i`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `i` is not less than the dimension, or if the
+    /// effective sample size of the draws is undefined, i.e. for fewer than
+    /// four draws, non-finite or constant draws
+    pub fn summary(&self, i: usize) -> Result<TraceSummary> {
+        if i >= self.dim() {
+            return Err(StatsError::ArgLt("i", self.dim() as f64));
+        }
+        let x = self.coordinate(i);
+        let ess = ess(&[&x[..]])?;
+        let mut data = Data::new(x);
+        Ok(TraceSummary {
+            mean: data.mean().unwrap(),
+            std_dev: data.std_dev().unwrap(),
+            lower: data.quantile(0.025),
+            median: data.median(),
+            upper: data.quantile(0.975),
+            ess,
+        })
+    }
+
+    /// Returns the summaries of all coordinates
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the summary of any coordinate is undefined
+    pub fn summaries(&self) -> Result<Vec<TraceSummary>> {
+        (0..self.dim()).map(|i| self.summary(i)).collect()
+    }
+}
+
+/// The summary of the draws of one coordinate of a `Trace`, with the
+/// equal-tailed 95% interval of the draws and their effective sample size
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TraceSummary {
+    mean: f64,
+    std_dev: f64,
+    lower: f64,
+    median: f64,
+    upper: f64,
+    ess: f64,
+}
+
+impl TraceSummary {
+    /// Returns the mean of the draws
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the sample standard deviation of the draws
+    pub fn std_dev(&self) -> f64 {
+        self.std_dev
+    }
+
+    /// Returns the 2.5% quantile of the draws
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Returns the median of the draws
+    pub fn median(&self) -> f64 {
+        self.median
+    }
+
+    /// Returns the 97.5% quantile of the draws
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// Returns the effective sample size of the draws
+    pub fn ess(&self) -> f64 {
+        self.ess
+    }
+
+    /// Returns the Monte Carlo standard error of the mean, `σ / √ess`
+    pub fn mcse(&self) -> f64 {
+        self.std_dev / self.ess.sqrt()
+    }
+}
+
+/// Returns the mean of `x` and the sum of squared deviations from it
+fn mean_scatter(x: &[f64]) -> (f64, f64) {
+    if x.is_empty() {
+        return (0.0, 0.0);
+    }
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    (mean, x.iter().map(|x| fp::powi(x - mean, 2)).sum())
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sampler::Slice;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_gibbs_normal_inverse_gamma() {
+        let data: Vec<f64> = (0..50).map(|i| 3.0 + 2.0 * ((i as f64) * 0.7).sin()).collect();
+        let n = data.len() as f64;
+        let xbar = data.iter().sum::<f64>() / n;
+        let s2 = data.iter().map(|x| (x - xbar).powi(2)).sum::<f64>() / (n - 1.0);
+        let mut sampler = Gibbs::new(vec![0.0, 1.0])
+            .unwrap()
+            .with_conditional(Conditional::normal_mean(0, Normal::new(0.0, 1000.0).unwrap(), 1, &data))
+            .unwrap()
+            .with_conditional(Conditional::normal_variance(1, InverseGamma::new(0.01, 0.01).unwrap(), 0, &data))
+            .unwrap()
+            .with_burn_in(200);
+        let trace = sampler.trace(20000, StdRng::seed_from_u64(1));
+        assert_eq!(trace.len(), 20000);
+        assert_eq!(trace.dim(), 2);
+        let summaries = trace.summaries().unwrap();
+        // with vague priors the posterior is close to the classical one, in
+        // which μ has standard deviation s / √n
+        assert!((summaries[0].mean() - xbar).abs() < 0.01);
+        assert!((summaries[0].std_dev() / (s2 / n).sqrt() - 1.0).abs() < 0.05);
+        assert!((summaries[1].mean() / s2 - 1.0).abs() < 0.1);
+        assert!(summaries[0].lower() < xbar && summaries[0].upper() > xbar);
+        assert!(summaries[1].lower() < summaries[1].median() && summaries[1].median() < summaries[1].upper());
+        assert!(summaries[0].ess() > 5000.0);
+        assert!(summaries[0].mcse() < 0.01);
+        assert_eq!(sampler.burn_in(), 0);
+    }
+
+    #[test]
+    fn test_gibbs_beta_binomial_poisson() {
+        let mut sampler = Gibbs::new(vec![0.5, 1.0])
+            .unwrap()
+            .with_conditional(Conditional::binomial_probability(0, Beta::new(2.0, 3.0).unwrap(), &[3, 5, 4], &[10, 10, 10]))
+            .unwrap()
+            .with_conditional(Conditional::poisson_rate(1, Gamma::new(1.0, 1.0).unwrap(), &[2, 4, 3, 5]))
+            .unwrap()
+            .with_thinning(2)
+            .unwrap();
+        let trace = sampler.trace(20000, StdRng::seed_from_u64(2));
+        // Beta(14, 21) and Gamma(15, 5)
+        let p = trace.summary(0).unwrap();
+        assert!((p.mean() - 0.4).abs() < 0.005);
+        assert!((p.std_dev() - (14.0 * 21.0 / (35.0f64.powi(2) * 36.0)).sqrt()).abs() < 0.005);
+        let lambda = trace.summary(1).unwrap();
+        assert!((lambda.mean() - 3.0).abs() < 0.02);
+        assert!((lambda.std_dev() - 15f64.sqrt() / 5.0).abs() < 0.02);
+        assert_eq!(sampler.thin(), 2);
+        assert_eq!(sampler.conditionals().len(), 2);
+        assert_eq!(sampler.conditionals()[1].index(), 1);
+    }
+
+    #[test]
+    fn test_gibbs_custom_conditionals() {
+        // a bivariate normal with correlation 0.5 whose first coordinate is
+        // drawn exactly and whose second is updated by a slice sampler
+        let rho = 0.5;
+        let sd = f64::sqrt(1.0 - rho * rho);
+        let mut sampler = Gibbs::new(vec![5.0, -5.0])
+            .unwrap()
+            .with_conditional(Conditional::new(0, move |x, rng| {
+                ::rand::distributions::Distribution::sample(&Normal::new(rho * x[1], sd).unwrap(), rng)
+            }))
+            .unwrap()
+            .with_conditional(Conditional::new(1, move |x, rng| {
+                let x0 = x[0];
+                Slice::new(move |y| -(y - rho * x0).powi(2) / (2.0 * sd * sd), 1.0).unwrap().step(x[1], rng)
+            }))
+            .unwrap()
+            .with_burn_in(100);
+        let trace = sampler.trace(20000, StdRng::seed_from_u64(3));
+        let (x, y) = (trace.coordinate(0), trace.coordinate(1));
+        let cov = x.iter().zip(&y).map(|(x, y)| x * y).sum::<f64>() / 20000.0;
+        assert!((cov - rho).abs() < 0.05);
+        let summary = trace.summary(1).unwrap();
+        assert!(summary.mean().abs() < 0.05);
+        assert!((summary.std_dev() - 1.0).abs() < 0.05);
+        // a coordinate without a conditional stays fixed
+        let mut fixed = Gibbs::new(vec![1.0, 2.0]).unwrap()
+            .with_conditional(Conditional::new(0, |x, _| x[0] + x[1]))
+            .unwrap();
+        fixed.step(&mut StdRng::seed_from_u64(4));
+        fixed.step(&mut StdRng::seed_from_u64(4));
+        assert_eq!(fixed.state(), &[5.0, 2.0]);
+    }
+
+    #[test]
+    fn test_gibbs_bad_params() {
+        assert!(Gibbs::new(vec![]).is_err());
+        assert!(Gibbs::new(vec![f64::NAN]).is_err());
+        let sampler = Gibbs::new(vec![0.0]).unwrap();
+        assert!(sampler.with_conditional(Conditional::new(1, |_, _| 0.0)).is_err());
+        assert!(Gibbs::new(vec![0.0]).unwrap().with_thinning(0).is_err());
+        assert!(Trace::new(vec![]).is_err());
+        assert!(Trace::new(vec![vec![]]).is_err());
+        assert!(Trace::new(vec![vec![1.0], vec![1.0, 2.0]]).is_err());
+        let trace = Trace::new(vec![vec![1.0]; 10]).unwrap();
+        assert!(trace.summary(1).is_err());
+        // the effective sample size of a constant chain is undefined
+        assert!(trace.summary(0).is_err());
+        assert!(!trace.is_empty());
+    }
+}
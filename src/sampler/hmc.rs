@@ -0,0 +1,499 @@
+use crate::distribution::Normal;
+use crate::fp;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::fmt;
+
+/// The default number of leapfrog steps
+const DEFAULT_STEPS: usize = 16;
+
+/// The default acceptance probability targeted by the step size adaptation
+const DEFAULT_TARGET_ACCEPTANCE: f64 = 0.8;
+
+/// The error in the Hamiltonian beyond which a trajectory is counted as
+/// divergent
+const DIVERGENCE_THRESHOLD: f64 = 1000.0;
+
+/// Implements [Hamiltonian Monte
+/// Carlo](https://en.wikipedia.org/wiki/Hamiltonian_Monte_Carlo) for
+/// sampling from a differentiable target density on `R^d` known up to a
+/// constant, given the gradient of its log
+///
+/// Each step draws a standard normal momentum `p`, follows the dynamics of
+/// the Hamiltonian `H(x, p) = -ln f(x) + |p|² / 2` from the current state
+/// `x` by leapfrog steps of size `ε` and accepts the end point with
+/// probability `min(1, exp(H(x, p) - H(x', p')))`.
+///
+/// # Remarks
+///
+/// As a lightweight alternative to the no-U-turn sampler the number of
+/// leapfrog steps of every trajectory is drawn uniformly from
+/// `1..=steps`, which avoids the periodic trajectories a fixed length can
+/// fall into without building trajectory trees. The mass matrix is the
+/// identity, so coordinates should be of similar scale.
+///
+/// `states` first runs the burn-in, then yields every `thin`th state of the
+/// chain. During the burn-in the step size is tuned by the dual averaging
+/// of Hoffman & Gelman (2014) towards an acceptance probability of 0.8,
+/// and is fixed to the averaged step size afterwards so that the chain has
+/// the target as its stationary distribution. Trajectories whose
+/// Hamiltonian grows by more than 1000, or becomes undefined, are counted
+/// as divergences and rejected, which usually points to a step size too
+/// large for regions of high curvature. The acceptance rate and the
+/// divergences only count the steps after the burn-in.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, Normal};
+/// use statrs::sampler::Hamiltonian;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// // the posterior of the mean and log standard deviation of normal data
+/// // under a flat prior
+/// let data = [4.1, 5.3, 3.8, 6.0, 4.9, 5.5, 4.4, 5.1];
+/// let ln_posterior = |x: &[f64]| {
+///     let normal = Normal::new(x[0], x[1].exp()).unwrap();
+///     data.iter().map(|&d| normal.ln_pdf(d)).sum::<f64>()
+/// };
+/// let gradient = |x: &[f64]| {
+///     let precision = (-2.0 * x[1]).exp();
+///     vec![
+///         data.iter().map(|d| (d - x[0]) * precision).sum::<f64>(),
+///         data.iter().map(|d| (d - x[0]).powi(2) * precision - 1.0).sum::<f64>(),
+///     ]
+/// };
+/// let mut sampler = Hamiltonian::new(ln_posterior, gradient, vec![0.0, 0.0])
+///     .unwrap()
+///     .with_burn_in(1000);
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let mean = sampler.states(&mut rng).take(4000).map(|x| x[0]).sum::<f64>() / 4000.0;
+/// assert!((mean - 4.8875).abs() < 0.1);
+/// assert_eq!(sampler.divergences(), 0);
+/// ```
+pub struct Hamiltonian<F, G> {
+    target_ln_pdf: F,
+    gradient: G,
+    state: Vec<f64>,
+    ln_f: f64,
+    grad: Vec<f64>,
+    step_size: f64,
+    steps: usize,
+    target_acceptance: f64,
+    burn_in: usize,
+    thin: usize,
+    proposals: u64,
+    accepted: u64,
+    divergences: u64,
+}
+
+impl<F, G> Hamiltonian<F, G>
+where
+    F: Fn(&[f64]) -> f64,
+    G: Fn(&[f64]) -> Vec<f64>,
+{
+    /// Constructs a Hamiltonian Monte Carlo sampler for the target with log
+    /// density `target_ln_pdf`, known up to an additive constant, and its
+    /// gradient `gradient`, starting from `initial`, with a step size of
+    /// 0.1, at most 16 leapfrog steps, no burn-in and no thinning
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `initial` is empty or has a non-finite entry, if
+    /// the log target is not finite at `initial` or if the gradient there
+    /// has a non-finite entry or the wrong length
+    pub fn new(target_ln_pdf: F, gradient: G, initial: Vec<f64>) -> Result<Hamiltonian<F, G>> {
+        if initial.is_empty() || initial.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        let ln_f = target_ln_pdf(&initial);
+        let grad = gradient(&initial);
+        if !ln_f.is_finite() || grad.len() != initial.len() || grad.iter().any(|g| !g.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Hamiltonian {
+            target_ln_pdf,
+            gradient,
+            state: initial,
+            ln_f,
+            grad,
+            step_size: 0.1,
+            steps: DEFAULT_STEPS,
+            target_acceptance: DEFAULT_TARGET_ACCEPTANCE,
+            burn_in: 0,
+            thin: 1,
+            proposals: 0,
+            accepted: 0,
+            divergences: 0,
+        })
+    }
+
+    /// Returns the sampler with the initial step size of the leapfrog
+    /// integrator set to `step_size`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `step_size` is not finite and positive
+    pub fn with_step_size(self, step_size: f64) -> Result<Hamiltonian<F, G>> {
+        if !(step_size.is_finite() && step_size > 0.0) {
+            return Err(StatsError::ArgMustBePositive("step_size"));
+        }
+        Ok(Hamiltonian { step_size, ..self })
+    }
+
+    /// Returns the sampler taking at most `steps` leapfrog steps per
+    /// trajectory
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `steps` is zero
+    pub fn with_steps(self, steps: usize) -> Result<Hamiltonian<F, G>> {
+        if steps == 0 {
+            return Err(StatsError::ArgMustBePositive("steps"));
+        }
+        Ok(Hamiltonian { steps, ..self })
+    }
+
+    /// Returns the sampler adapting the step size towards the acceptance
+    /// probability `target_acceptance` during the burn-in
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `target_acceptance` is not in `(0, 1)`
+    pub fn with_target_acceptance(self, target_acceptance: f64) -> Result<Hamiltonian<F, G>> {
+        if !(target_acceptance > 0.0 && target_acceptance < 1.0) {
+            return Err(StatsError::ArgIntervalExcl("target_acceptance", 0.0, 1.0));
+        }
+        Ok(Hamiltonian {
+            target_acceptance,
+            ..self
+        })
+    }
+
+    /// Returns the sampler running `burn_in` steps, which adapt the step
+    /// size, before yielding states
+    pub fn with_burn_in(self, burn_in: usize) -> Hamiltonian<F, G> {
+        Hamiltonian { burn_in, ..self }
+    }
+
+    /// Returns the sampler yielding only every `thin`th state
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `thin` is zero
+    pub fn with_thinning(self, thin: usize) -> Result<Hamiltonian<F, G>> {
+        if thin == 0 {
+            return Err(StatsError::ArgMustBePositive("thin"));
+        }
+        Ok(Hamiltonian { thin, ..self })
+    }
+
+    /// Returns the current state of the chain
+    pub fn state(&self) -> &[f64] {
+        &self.state
+    }
+
+    /// Returns the log target at the current state
+    pub fn ln_target(&self) -> f64 {
+        self.ln_f
+    }
+
+    /// Returns the step size of the leapfrog integrator
+    pub fn step_size(&self) -> f64 {
+        self.step_size
+    }
+
+    /// Returns the maximum number of leapfrog steps per trajectory
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns the acceptance probability targeted by the step size
+    /// adaptation
+    pub fn target_acceptance(&self) -> f64 {
+        self.target_acceptance
+    }
+
+    /// Returns the number of burn-in steps still to be run
+    pub fn burn_in(&self) -> usize {
+        self.burn_in
+    }
+
+    /// Returns the thinning interval
+    pub fn thin(&self) -> usize {
+        self.thin
+    }
+
+    /// Returns the number of trajectories drawn after the burn-in
+    pub fn proposals(&self) -> u64 {
+        self.proposals
+    }
+
+    /// Returns the number of trajectories accepted after the burn-in
+    pub fn accepted(&self) -> u64 {
+        self.accepted
+    }
+
+    /// Returns the number of divergent trajectories after the burn-in
+    pub fn divergences(&self) -> u64 {
+        self.divergences
+    }
+
+    /// Returns the fraction of trajectories accepted after the burn-in, or
+    /// `None` if no trajectory has been drawn
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        if self.proposals == 0 {
+            None
+        } else {
+            Some(self.accepted as f64 / self.proposals as f64)
+        }
+    }
+
+    /// Returns an infinite iterator over the states of the chain drawn
+    /// using `rng`, running the remaining burn-in first
+    pub fn states<R: Rng>(&mut self, rng: R) -> HamiltonianStates<'_, F, G, R> {
+        HamiltonianStates { sampler: self, rng }
+    }
+
+    /// Runs a single step of the chain and returns the probability with
+    /// which the end of the trajectory was accepted
+    pub fn step<R: Rng + ?Sized>(&mut self, rng: &mut R) -> f64 {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let mut p: Vec<f64> = self
+            .state
+            .iter()
+            .map(|_| ::rand::distributions::Distribution::sample(&normal, rng))
+            .collect();
+        let h = kinetic(&p) - self.ln_f;
+        let n = rng.gen_range(1..=self.steps);
+        let eps = self.step_size;
+        let mut x = self.state.clone();
+        let mut grad = self.grad.clone();
+        let mut ln_f = self.ln_f;
+        for _ in 0..n {
+            half_step(&mut p, &grad, eps);
+            x.iter_mut().zip(&p).for_each(|(x, p)| *x += eps * p);
+            ln_f = (self.target_ln_pdf)(&x);
+            if !ln_f.is_finite() {
+                break;
+            }
+            grad = (self.gradient)(&x);
+            half_step(&mut p, &grad, eps);
+            if grad.iter().any(|g| !g.is_finite()) {
+                break;
+            }
+        }
+        let ln_ratio = h - (kinetic(&p) - ln_f);
+        self.proposals += 1;
+        if ln_ratio.is_nan() || ln_ratio < -DIVERGENCE_THRESHOLD || ln_f == f64::INFINITY {
+            self.divergences += 1;
+            return 0.0;
+        }
+        let accept = fp::exp(ln_ratio.min(0.0));
+        if rng.gen::<f64>() < accept {
+            self.state = x;
+            self.ln_f = ln_f;
+            self.grad = grad;
+            self.accepted += 1;
+        }
+        accept
+    }
+
+    /// Runs the remaining burn-in, adapting the step size by dual averaging
+    fn run_burn_in<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let (gamma, t0, kappa) = (0.05, 10.0, 0.75);
+        let mu = fp::ln(10.0 * self.step_size);
+        let mut h_bar = 0.0;
+        let mut ln_eps_bar = 0.0;
+        let mut t = 1.0;
+        while self.burn_in > 0 {
+            let accept = self.step(rng);
+            h_bar += (self.target_acceptance - accept - h_bar) / (t + t0);
+            let ln_eps = mu - f64::sqrt(t) / gamma * h_bar;
+            let eta = fp::powf(t, -kappa);
+            ln_eps_bar = eta * ln_eps + (1.0 - eta) * ln_eps_bar;
+            self.step_size = fp::exp(ln_eps);
+            t += 1.0;
+            self.burn_in -= 1;
+        }
+        if t > 1.0 {
+            self.step_size = fp::exp(ln_eps_bar);
+        }
+        self.proposals = 0;
+        self.accepted = 0;
+        self.divergences = 0;
+    }
+}
+
+impl<F, G> fmt::Debug for Hamiltonian<F, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hamiltonian")
+            .field("state", &self.state)
+            .field("ln_f", &self.ln_f)
+            .field("step_size", &self.step_size)
+            .field("steps", &self.steps)
+            .field("target_acceptance", &self.target_acceptance)
+            .field("burn_in", &self.burn_in)
+            .field("thin", &self.thin)
+            .field("proposals", &self.proposals)
+            .field("accepted", &self.accepted)
+            .field("divergences", &self.divergences)
+            .finish()
+    }
+}
+
+/// Returns the kinetic energy `|p|² / 2` of the momentum `p`
+fn kinetic(p: &[f64]) -> f64 {
+    0.5 * p.iter().map(|p| p * p).sum::<f64>()
+}
+
+/// Moves the momentum `p` by half a leapfrog step along the gradient
+fn half_step(p: &mut [f64], grad: &[f64], eps: f64) {
+    p.iter_mut()
+        .zip(grad)
+        .for_each(|(p, g)| *p += 0.5 * eps * g);
+}
+
+/// An infinite iterator over the states of a `Hamiltonian` chain, created
+/// by `Hamiltonian::states`
+#[derive(Debug)]
+pub struct HamiltonianStates<'a, F, G, R> {
+    sampler: &'a mut Hamiltonian<F, G>,
+    rng: R,
+}
+
+impl<'a, F, G, R> Iterator for HamiltonianStates<'a, F, G, R>
+where
+    F: Fn(&[f64]) -> f64,
+    G: Fn(&[f64]) -> Vec<f64>,
+    R: Rng,
+{
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Vec<f64>> {
+        if self.sampler.burn_in > 0 {
+            self.sampler.run_burn_in(&mut self.rng);
+        }
+        for _ in 0..self.sampler.thin {
+            self.sampler.step(&mut self.rng);
+        }
+        Some(self.sampler.state.clone())
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Continuous, Gamma};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn moments(x: &[f64]) -> (f64, f64) {
+        let n = x.len() as f64;
+        let mean = x.iter().sum::<f64>() / n;
+        (mean, x.iter().map(|xi| (xi - mean).powi(2)).sum::<f64>() / (n - 1.0))
+    }
+
+    #[test]
+    fn test_hamiltonian_bivariate() {
+        // a bivariate normal with unit variances and correlation 0.9
+        let rho: f64 = 0.9;
+        let c = 1.0 / (1.0 - rho * rho);
+        let ln_target = |x: &[f64]| -0.5 * c * (x[0] * x[0] - 2.0 * rho * x[0] * x[1] + x[1] * x[1]);
+        let gradient = |x: &[f64]| vec![-c * (x[0] - rho * x[1]), -c * (x[1] - rho * x[0])];
+        let mut sampler = Hamiltonian::new(ln_target, gradient, vec![3.0, -3.0]).unwrap().with_burn_in(1000);
+        let mut rng = StdRng::seed_from_u64(1);
+        let x: Vec<Vec<f64>> = sampler.states(&mut rng).take(10000).collect();
+        let (m0, v0) = moments(&x.iter().map(|x| x[0]).collect::<Vec<_>>());
+        let (m1, v1) = moments(&x.iter().map(|x| x[1]).collect::<Vec<_>>());
+        let cov = x.iter().map(|x| (x[0] - m0) * (x[1] - m1)).sum::<f64>() / 9999.0;
+        assert!(m0.abs() < 0.1 && m1.abs() < 0.1);
+        assert!((v0 - 1.0).abs() < 0.1 && (v1 - 1.0).abs() < 0.1);
+        assert!((cov - rho).abs() < 0.1);
+        assert_eq!(sampler.proposals(), 10000);
+        assert_eq!(sampler.divergences(), 0);
+    }
+
+    #[test]
+    fn test_hamiltonian_step_size_adaptation() {
+        // a narrow normal needs a much smaller step than the default
+        let sigma = 0.01;
+        let ln_target = |x: &[f64]| -0.5 * (x[0] / sigma).powi(2);
+        let gradient = |x: &[f64]| vec![-x[0] / (sigma * sigma)];
+        let mut sampler = Hamiltonian::new(ln_target, gradient, vec![0.0])
+            .unwrap()
+            .with_steps(8)
+            .unwrap()
+            .with_burn_in(2000);
+        let mut rng = StdRng::seed_from_u64(2);
+        let x: Vec<f64> = sampler.states(&mut rng).take(5000).map(|x| x[0]).collect();
+        let (mean, var) = moments(&x);
+        assert!(mean.abs() < 0.1 * sigma);
+        assert!((var / (sigma * sigma) - 1.0).abs() < 0.1);
+        assert!(sampler.step_size() < 2.0 * sigma && sampler.step_size() > 0.1 * sigma);
+        assert!((sampler.acceptance_rate().unwrap() - 0.8).abs() < 0.1);
+        assert_eq!(sampler.burn_in(), 0);
+    }
+
+    #[test]
+    fn test_hamiltonian_divergences() {
+        // a step far too large for the target without any burn-in diverges
+        // and never moves the chain
+        let ln_target = |x: &[f64]| -0.5 * x[0] * x[0];
+        let gradient = |x: &[f64]| vec![-x[0]];
+        let mut sampler = Hamiltonian::new(ln_target, gradient, vec![1.0])
+            .unwrap()
+            .with_step_size(100.0)
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..100 {
+            assert_eq!(sampler.step(&mut rng), 0.0);
+        }
+        assert_eq!(sampler.divergences(), 100);
+        assert_eq!(sampler.acceptance_rate(), Some(0.0));
+        assert_eq!(sampler.state(), &[1.0]);
+    }
+
+    #[test]
+    fn test_hamiltonian_bounded_support() {
+        // a gamma target rejecting trajectories leaving its support
+        let gamma = Gamma::new(4.0, 2.0).unwrap();
+        let ln_target = |x: &[f64]| if x[0] > 0.0 { gamma.ln_pdf(x[0]) } else { f64::NEG_INFINITY };
+        let gradient = |x: &[f64]| vec![3.0 / x[0] - 2.0];
+        let mut sampler = Hamiltonian::new(ln_target, gradient, vec![1.0])
+            .unwrap()
+            .with_burn_in(1000)
+            .with_thinning(2)
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(4);
+        let x: Vec<f64> = sampler.states(&mut rng).take(10000).map(|x| x[0]).collect();
+        let (mean, var) = moments(&x);
+        assert!(x.iter().all(|&x| x > 0.0));
+        assert!((mean - 2.0).abs() < 0.05);
+        assert!((var - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_hamiltonian_bad_params() {
+        let ln_target = |x: &[f64]| -0.5 * x[0] * x[0];
+        let gradient = |x: &[f64]| vec![-x[0]];
+        assert!(Hamiltonian::new(ln_target, gradient, vec![]).is_err());
+        assert!(Hamiltonian::new(ln_target, gradient, vec![f64::NAN]).is_err());
+        assert!(Hamiltonian::new(ln_target, |_: &[f64]| vec![0.0, 0.0], vec![0.0]).is_err());
+        assert!(Hamiltonian::new(ln_target, |_: &[f64]| vec![f64::NAN], vec![0.0]).is_err());
+        assert!(Hamiltonian::new(|_: &[f64]| f64::NAN, gradient, vec![0.0]).is_err());
+        let sampler = Hamiltonian::new(ln_target, gradient, vec![0.0]).unwrap();
+        assert_eq!(sampler.steps(), 16);
+        assert_eq!(sampler.thin(), 1);
+        assert_eq!(sampler.target_acceptance(), 0.8);
+        assert!(sampler.with_steps(0).is_err());
+        let sampler = Hamiltonian::new(ln_target, gradient, vec![0.0]).unwrap();
+        assert!(sampler.with_step_size(-1.0).is_err());
+        let sampler = Hamiltonian::new(ln_target, gradient, vec![0.0]).unwrap();
+        assert!(sampler.with_thinning(0).is_err());
+        let sampler = Hamiltonian::new(ln_target, gradient, vec![0.0]).unwrap();
+        assert!(sampler.with_target_acceptance(1.0).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::distribution::{ContinuousCDF, DiscreteCDF};
+use rand::distributions::Open01;
+use rand::Rng;
+
+/// Implements [inverse transform
+/// sampling](https://en.wikipedia.org/wiki/Inverse_transform_sampling) of a
+/// univariate distribution, drawing `F⁻¹(u)` for a uniform `u`
+///
+/// # Remarks
+///
+/// Any distribution implementing `ContinuousCDF` is sampled as `f64`, and
+/// any distribution implementing `DiscreteCDF` over `u64` or `i64` as that
+/// integer type, so distributions without a dedicated sampler, e.g. new or
+/// user-defined ones, can be sampled through their `inverse_cdf`. The
+/// samples are exact up to the accuracy of `inverse_cdf`, whose default
+/// implementation finds the root of the cdf numerically, so dedicated
+/// samplers are usually much faster. The uniforms are drawn from the open
+/// interval `(0, 1)` so that unbounded distributions never yield infinite
+/// samples.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ContinuousCDF};
+/// use statrs::sampler::InverseTransform;
+/// use statrs::statistics::{Max, Min};
+/// use rand::distributions::Distribution;
+///
+/// // a user-defined distribution on [0, 1] with cdf x²
+/// struct Square;
+///
+/// impl Min<f64> for Square {
+///     fn min(&self) -> f64 { 0.0 }
+/// }
+///
+/// impl Max<f64> for Square {
+///     fn max(&self) -> f64 { 1.0 }
+/// }
+///
+/// impl ContinuousCDF<f64, f64> for Square {
+///     fn cdf(&self, x: f64) -> f64 { x.max(0.0).min(1.0).powi(2) }
+/// }
+///
+/// let sampler = InverseTransform::new(Square);
+/// let mut rng = rand::thread_rng();
+/// let mean = (0..10000).map(|_| sampler.sample(&mut rng)).sum::<f64>() / 10000.0;
+/// assert!((mean - 2.0 / 3.0).abs() < 0.02);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct InverseTransform<D> {
+    dist: D,
+}
+
+impl<D> InverseTransform<D> {
+    /// Constructs an inverse transform sampler of `dist`
+    pub fn new(dist: D) -> InverseTransform<D> {
+        InverseTransform { dist }
+    }
+
+    /// Returns the wrapped distribution
+    pub fn dist(&self) -> &D {
+        &self.dist
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ::rand::distributions::Distribution<f64> for InverseTransform<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.dist.inverse_cdf(rng.sample(Open01))
+    }
+}
+
+impl<D: DiscreteCDF<u64, f64>> ::rand::distributions::Distribution<u64> for InverseTransform<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        self.dist.inverse_cdf(rng.sample(Open01))
+    }
+}
+
+impl<D: DiscreteCDF<i64, f64>> ::rand::distributions::Distribution<i64> for InverseTransform<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> i64 {
+        self.dist.inverse_cdf(rng.sample(Open01))
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Binomial, Continuous, DiscreteGaussian, Gamma, Normal};
+    use crate::statistics::{Max, Min};
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Kolmogorov-Smirnov distance between a sample and a cdf
+    fn ks_distance<D: ContinuousCDF<f64, f64>>(mut x: Vec<f64>, dist: &D) -> f64 {
+        x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = x.len() as f64;
+        x.iter().enumerate().fold(0.0, |d, (i, &xi)| {
+            let f = dist.cdf(xi);
+            d.max((f - i as f64 / n).abs()).max(((i + 1) as f64 / n - f).abs())
+        })
+    }
+
+    // a user-defined logistic distribution relying on the default inverse
+    // of its cdf
+    struct Logistic;
+
+    impl Min<f64> for Logistic {
+        fn min(&self) -> f64 { f64::NEG_INFINITY }
+    }
+
+    impl Max<f64> for Logistic {
+        fn max(&self) -> f64 { f64::INFINITY }
+    }
+
+    impl ContinuousCDF<f64, f64> for Logistic {
+        fn cdf(&self, x: f64) -> f64 { 1.0 / (1.0 + (-x).exp()) }
+    }
+
+    #[test]
+    fn test_inverse_transform_continuous() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let gamma = Gamma::new(3.0, 2.0).unwrap();
+        let x: Vec<f64> = (0..4000).map(|_| InverseTransform::new(gamma).sample(&mut rng)).collect();
+        assert!(ks_distance(x, &gamma) < 0.03);
+        let sampler = InverseTransform::new(Logistic);
+        let x: Vec<f64> = (0..4000).map(|_| sampler.sample(&mut rng)).collect();
+        assert!(x.iter().all(|x| x.is_finite()));
+        assert!(ks_distance(x, &Logistic) < 0.03);
+        // the sampler draws exactly the quantile of the uniform
+        let normal = Normal::new(1.0, 2.0).unwrap();
+        let u: f64 = StdRng::seed_from_u64(2).sample(Open01);
+        let x: f64 = InverseTransform::new(normal).sample(&mut StdRng::seed_from_u64(2));
+        assert_eq!(x, normal.inverse_cdf(u));
+        assert_eq!(InverseTransform::new(normal).dist().pdf(1.0), normal.pdf(1.0));
+    }
+
+    #[test]
+    fn test_inverse_transform_discrete() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let binomial = Binomial::new(0.3, 10).unwrap();
+        let sampler = InverseTransform::new(binomial);
+        let mut counts = [0.0f64; 11];
+        for _ in 0..20000 {
+            let k: u64 = sampler.sample(&mut rng);
+            counts[k as usize] += 1.0;
+        }
+        for (k, &c) in counts.iter().enumerate() {
+            let expected = 20000.0 * crate::distribution::Discrete::pmf(&binomial, k as u64);
+            assert!((c - expected).abs() < 5.0 * expected.sqrt() + 5.0);
+        }
+        let sampler = InverseTransform::new(DiscreteGaussian::new(-3, 1.0).unwrap());
+        let mean = (0..20000).map(|_| Distribution::<i64>::sample(&sampler, &mut rng) as f64).sum::<f64>() / 20000.0;
+        assert!((mean + 3.0).abs() < 0.05);
+    }
+}
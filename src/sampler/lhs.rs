@@ -0,0 +1,308 @@
+use crate::distribution::ContinuousCDF;
+use crate::{Result, StatsError};
+use nalgebra::DMatrix;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Implements [Latin hypercube
+/// sampling](https://en.wikipedia.org/wiki/Latin_hypercube_sampling) of `n`
+/// points in the unit hypercube `[0, 1]^d`.
+///
+/// Every axis is split into `n` equally sized strata and each stratum of
+/// each axis contains exactly one point. Samples are returned as `n x d`
+/// matrices with one point per row and can be mapped through marginal
+/// inverse cdfs with `map_marginals`.
+///
+/// # Remarks
+///
+/// By default every point is placed uniformly at random within its cell.
+/// A `centered` design places it at the centre of the cell instead. With
+/// `decorrelated` the strata of the different axes are paired using the
+/// ranked Gram-Schmidt procedure of Owen (1994), which greatly reduces the
+/// spurious correlations between the columns of the design.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler::LatinHypercube;
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let lhs = LatinHypercube::new(10, 3).unwrap().centered();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let points = lhs.sample(&mut rng);
+/// assert_eq!(points.shape(), (10, 3));
+/// let mut column: Vec<f64> = points.column(0).iter().cloned().collect();
+/// column.sort_by(|a, b| a.partial_cmp(b).unwrap());
+/// assert_eq!(column[0], 0.05);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LatinHypercube {
+    n: usize,
+    d: usize,
+    centered: bool,
+    decorrelated: bool,
+}
+
+impl LatinHypercube {
+    /// Constructs a Latin hypercube design of `n` points in `d` dimensions
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` or `d` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::sampler::LatinHypercube;
+    ///
+    /// let mut result = LatinHypercube::new(10, 2);
+    /// assert!(result.is_ok());
+    ///
+    /// result = LatinHypercube::new(0, 2);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(n: usize, d: usize) -> Result<LatinHypercube> {
+        if n == 0 || d == 0 {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(LatinHypercube {
+                n,
+                d,
+                centered: false,
+                decorrelated: false,
+            })
+        }
+    }
+
+    /// Returns the design with every point placed at the centre of its cell
+    pub fn centered(self) -> LatinHypercube {
+        LatinHypercube {
+            centered: true,
+            ..self
+        }
+    }
+
+    /// Returns the design with the correlations between its columns reduced
+    /// by ranked Gram-Schmidt orthogonalization
+    pub fn decorrelated(self) -> LatinHypercube {
+        LatinHypercube {
+            decorrelated: true,
+            ..self
+        }
+    }
+
+    /// Returns the number of points `n`
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the dimension `d`
+    pub fn d(&self) -> usize {
+        self.d
+    }
+}
+
+impl ::rand::distributions::Distribution<DMatrix<f64>> for LatinHypercube {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DMatrix<f64> {
+        let mut strata: Vec<Vec<usize>> = (0..self.d)
+            .map(|_| {
+                let mut perm: Vec<usize> = (0..self.n).collect();
+                perm.shuffle(rng);
+                perm
+            })
+            .collect();
+        if self.decorrelated && self.n > 2 {
+            for _ in 0..2 {
+                for j in 1..self.d {
+                    strata[j] = gram_schmidt_ranks(&strata, j, 0..j);
+                }
+                for j in (0..self.d - 1).rev() {
+                    strata[j] = gram_schmidt_ranks(&strata, j, j + 1..self.d);
+                }
+            }
+        }
+        let n = self.n as f64;
+        DMatrix::from_fn(self.n, self.d, |i, j| {
+            let offset = if self.centered { 0.5 } else { rng.gen::<f64>() };
+            (strata[j][i] as f64 + offset) / n
+        })
+    }
+}
+
+/// Returns the ranks of the residuals of the centred ranks of column `j`
+/// after regressing them on the centred ranks of the columns in `others`
+fn gram_schmidt_ranks<I>(strata: &[Vec<usize>], j: usize, others: I) -> Vec<usize>
+where
+    I: Iterator<Item = usize>,
+{
+    let n = strata[j].len();
+    let centre = (n as f64 - 1.0) / 2.0;
+    let scores = |col: &[usize]| -> Vec<f64> { col.iter().map(|&r| r as f64 - centre).collect() };
+    let mut residual = scores(&strata[j]);
+    for k in others {
+        let s = scores(&strata[k]);
+        let ss: f64 = s.iter().map(|x| x * x).sum();
+        let beta = residual.iter().zip(&s).map(|(r, x)| r * x).sum::<f64>() / ss;
+        for (r, x) in residual.iter_mut().zip(&s) {
+            *r -= beta * x;
+        }
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| residual[a].partial_cmp(&residual[b]).unwrap());
+    let mut ranks = vec![0; n];
+    for (rank, &i) in order.iter().enumerate() {
+        ranks[i] = rank;
+    }
+    ranks
+}
+
+/// Samples a Latin hypercube design of `n` points in `d` dimensions with
+/// every point placed uniformly at random within its cell, see
+/// `LatinHypercube`
+///
+/// # Errors
+///
+/// Returns an error if `n` or `d` is zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let points = sampler::latin_hypercube(5, 2, &mut rng).unwrap();
+/// assert!(points.iter().all(|&x| 0.0 <= x && x < 1.0));
+/// ```
+pub fn latin_hypercube<R: Rng + ?Sized>(n: usize, d: usize, rng: &mut R) -> Result<DMatrix<f64>> {
+    use rand::distributions::Distribution;
+    Ok(LatinHypercube::new(n, d)?.sample(rng))
+}
+
+/// Maps every column of a sample `points` of the unit hypercube through the
+/// inverse cdf of the corresponding distribution in `marginals`, e.g. to
+/// turn a Latin hypercube design into a stratified sample of a product
+/// distribution
+///
+/// # Errors
+///
+/// Returns an error if the number of marginals differs from the number of
+/// columns of `points`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, Exp, Normal};
+/// use statrs::sampler;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let points = sampler::latin_hypercube(20, 2, &mut rng).unwrap();
+/// let normal = Normal::new(0.0, 1.0).unwrap();
+/// let exp = Exp::new(2.0).unwrap();
+/// let sample = sampler::map_marginals(&points, &[&normal, &exp]).unwrap();
+/// assert!(sample.column(1).iter().all(|&x| x >= 0.0));
+/// ```
+pub fn map_marginals(
+    points: &DMatrix<f64>,
+    marginals: &[&dyn ContinuousCDF<f64, f64>],
+) -> Result<DMatrix<f64>> {
+    if marginals.len() != points.ncols() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    Ok(DMatrix::from_fn(points.nrows(), points.ncols(), |i, j| {
+        marginals[j].inverse_cdf(points[(i, j)])
+    }))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Exp, Uniform};
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn max_abs_correlation(points: &DMatrix<f64>) -> f64 {
+        let n = points.nrows() as f64;
+        let mut max = 0.0f64;
+        for a in 0..points.ncols() {
+            for b in 0..a {
+                let (x, y) = (points.column(a), points.column(b));
+                let (mx, my) = (x.sum() / n, y.sum() / n);
+                let sxy: f64 = x.iter().zip(y.iter()).map(|(u, v)| (u - mx) * (v - my)).sum();
+                let sxx: f64 = x.iter().map(|u| (u - mx) * (u - mx)).sum();
+                let syy: f64 = y.iter().map(|v| (v - my) * (v - my)).sum();
+                max = max.max((sxy / (sxx * syy).sqrt()).abs());
+            }
+        }
+        max
+    }
+
+    #[test]
+    fn test_bad_create() {
+        assert!(LatinHypercube::new(0, 1).is_err());
+        assert!(LatinHypercube::new(1, 0).is_err());
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(latin_hypercube(0, 3, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_one_point_per_stratum() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for lhs in &[LatinHypercube::new(17, 4).unwrap(), LatinHypercube::new(17, 4).unwrap().decorrelated()] {
+            let points = lhs.sample(&mut rng);
+            for column in points.column_iter() {
+                let mut strata: Vec<usize> = column.iter().map(|x| (x * 17.0).floor() as usize).collect();
+                strata.sort_unstable();
+                assert_eq!(strata, (0..17).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_centered() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let points = LatinHypercube::new(8, 3).unwrap().centered().sample(&mut rng);
+        for column in points.column_iter() {
+            let mut values: Vec<f64> = column.iter().cloned().collect();
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for (k, x) in values.iter().enumerate() {
+                assert_eq!(*x, (k as f64 + 0.5) / 8.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decorrelated() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let lhs = LatinHypercube::new(50, 5).unwrap().centered();
+        let mut plain = 0.0;
+        let mut reduced = 0.0;
+        for _ in 0..20 {
+            plain += max_abs_correlation(&lhs.sample(&mut rng));
+            reduced += max_abs_correlation(&lhs.decorrelated().sample(&mut rng));
+        }
+        assert!(reduced < 0.25 * plain);
+        assert!(reduced / 20.0 < 0.05);
+    }
+
+    #[test]
+    fn test_map_marginals() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let points = LatinHypercube::new(4, 2).unwrap().centered().sample(&mut rng);
+        let uniform = Uniform::new(-1.0, 1.0).unwrap();
+        let exp = Exp::new(1.0).unwrap();
+        let sample = map_marginals(&points, &[&uniform, &exp]).unwrap();
+        for i in 0..4 {
+            assert_almost_eq!(sample[(i, 0)], 2.0 * points[(i, 0)] - 1.0, 1e-12);
+            assert_almost_eq!(sample[(i, 1)], -(1.0 - points[(i, 1)]).ln(), 1e-12);
+        }
+        assert!(map_marginals(&points, &[&uniform]).is_err());
+    }
+}
@@ -0,0 +1,554 @@
+use crate::distribution::{Beta, Normal};
+use crate::{Result, StatsError};
+use nalgebra::{Cholesky, DMatrix, DVector};
+use rand::distributions::Distribution;
+use rand::Rng;
+
+/// Samples an `n x n` orthogonal matrix uniformly, i.e. from the Haar
+/// measure on the orthogonal group `O(n)`
+///
+/// # Remarks
+///
+/// The matrix is the `Q` factor of the QR decomposition of a matrix with
+/// independent standard normal entries. The columns of `Q` are multiplied
+/// by the signs of the diagonal of `R`, which makes the decomposition unique
+/// and the result exactly Haar distributed (Mezzadri, 2007).
+///
+/// # Errors
+///
+/// Returns an error if `n` is zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use nalgebra::DMatrix;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let q = sampler::random_orthogonal(3, &mut rng).unwrap();
+/// assert!((q.transpose() * &q - DMatrix::identity(3, 3)).amax() < 1e-12);
+/// ```
+pub fn random_orthogonal<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Result<DMatrix<f64>> {
+    random_projection(n, n, rng)
+}
+
+/// Samples an `n x n` rotation matrix uniformly, i.e. from the Haar measure
+/// on the special orthogonal group `SO(n)` of orthogonal matrices with
+/// determinant one
+///
+/// # Remarks
+///
+/// A Haar distributed orthogonal matrix with determinant `-1` is turned
+/// into a rotation by negating its first column, which preserves the
+/// uniform distribution.
+///
+/// # Errors
+///
+/// Returns an error if `n` is zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let q = sampler::random_rotation(3, &mut rng).unwrap();
+/// assert!((q.determinant() - 1.0).abs() < 1e-12);
+/// ```
+pub fn random_rotation<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Result<DMatrix<f64>> {
+    let mut q = random_orthogonal(n, rng)?;
+    if q.determinant() < 0.0 {
+        q.column_mut(0).neg_mut();
+    }
+    Ok(q)
+}
+
+/// Samples an `n x k` matrix with orthonormal columns uniformly, i.e. from
+/// the Haar measure on the Stiefel manifold, e.g. to project `n`-dimensional
+/// data onto a random `k`-dimensional subspace
+///
+/// # Remarks
+///
+/// The columns are the first `k` columns of a Haar distributed orthogonal
+/// matrix, obtained from the thin QR decomposition of an `n x k` matrix with
+/// independent standard normal entries and the same sign correction as in
+/// `random_orthogonal`.
+///
+/// # Errors
+///
+/// Returns an error if `k` is zero or greater than `n`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use nalgebra::DMatrix;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let p = sampler::random_projection(5, 2, &mut rng).unwrap();
+/// assert_eq!(p.shape(), (5, 2));
+/// assert!((p.transpose() * &p - DMatrix::identity(2, 2)).amax() < 1e-12);
+/// ```
+pub fn random_projection<R: Rng + ?Sized>(n: usize, k: usize, rng: &mut R) -> Result<DMatrix<f64>> {
+    if k == 0 || k > n {
+        return Err(StatsError::BadParams);
+    }
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let qr = DMatrix::<f64>::from_distribution(n, k, &normal, rng).qr();
+    let r = qr.r();
+    let mut q = qr.q();
+    for j in 0..k {
+        if r[(j, j)] < 0.0 {
+            q.column_mut(j).neg_mut();
+        }
+    }
+    Ok(q)
+}
+
+/// Samples an `n x n` correlation matrix from the LKJ distribution with
+/// concentration `eta` using the onion method (Lewandowski, Kurowicka &
+/// Joe, 2009)
+///
+/// The density of the LKJ distribution is proportional to `det(C)^(eta - 1)`,
+/// so `eta` controls the spread of the eigenvalues: `eta = 1` is uniform
+/// over all correlation matrices, larger values concentrate the mass around
+/// the identity and values in `(0, 1)` favour nearly singular matrices with
+/// strong correlations.
+///
+/// # Remarks
+///
+/// Each off-diagonal element is marginally distributed as `2X - 1` with
+/// `X ~ Beta(eta - 1 + n / 2, eta - 1 + n / 2)`.
+///
+/// # Errors
+///
+/// Returns an error if `n` is zero or if `eta` is `NaN`, infinite or not
+/// positive
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let c = sampler::random_correlation_onion(4, 2.0, &mut rng).unwrap();
+/// assert_eq!(c.diagonal().iter().sum::<f64>(), 4.0);
+/// assert_eq!(c, c.transpose());
+/// ```
+pub fn random_correlation_onion<R: Rng + ?Sized>(
+    n: usize,
+    eta: f64,
+    rng: &mut R,
+) -> Result<DMatrix<f64>> {
+    if n == 0 {
+        return Err(StatsError::BadParams);
+    }
+    if !eta.is_finite() || eta <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("eta"));
+    }
+    let mut corr = DMatrix::<f64>::identity(n, n);
+    if n == 1 {
+        return Ok(corr);
+    }
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let mut beta = eta + 0.5 * (n as f64 - 2.0);
+    let r = 2.0 * Beta::new(beta, beta)?.sample(rng) - 1.0;
+    corr[(0, 1)] = r;
+    corr[(1, 0)] = r;
+    for k in 2..n {
+        beta -= 0.5;
+        let y = Beta::new(0.5 * k as f64, beta)?.sample(rng);
+        // uniform direction on the unit sphere in R^k
+        let mut w = DVector::<f64>::from_distribution(k, &normal, rng);
+        w *= y.sqrt() / w.norm();
+        let chol = Cholesky::new(corr.slice((0, 0), (k, k)).into_owned())
+            .ok_or(StatsError::ComputationFailedToConverge)?;
+        let z = chol.l() * w;
+        for i in 0..k {
+            corr[(i, k)] = z[i];
+            corr[(k, i)] = z[i];
+        }
+    }
+    Ok(corr)
+}
+
+/// Specifies how `random_correlation` constructs a random correlation matrix
+#[derive(Debug, Clone, PartialEq)]
+pub enum CorrelationMethod {
+    /// LKJ distribution with concentration `eta`, sampled with the onion
+    /// method, see `random_correlation_onion`
+    Onion { eta: f64 },
+    /// LKJ distribution with concentration `eta`, sampled with the C-vine
+    /// method, see `random_correlation_vine`
+    Vine { eta: f64 },
+    /// A correlation matrix with the given eigenvalues and random
+    /// eigenvectors, see `random_correlation_with_eigenvalues`
+    Eigenvalues(Vec<f64>),
+}
+
+/// Samples a `d x d` correlation matrix using the construction given by
+/// `method`
+///
+/// # Errors
+///
+/// Returns an error if `d` is zero, if the parameters of `method` are
+/// invalid or if `method` specifies a number of eigenvalues other than `d`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler::{self, CorrelationMethod};
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let method = CorrelationMethod::Eigenvalues(vec![2.5, 0.3, 0.2]);
+/// let c = sampler::random_correlation(3, &method, &mut rng).unwrap();
+/// assert!((c.trace() - 3.0).abs() < 1e-12);
+/// ```
+pub fn random_correlation<R: Rng + ?Sized>(
+    d: usize,
+    method: &CorrelationMethod,
+    rng: &mut R,
+) -> Result<DMatrix<f64>> {
+    match *method {
+        CorrelationMethod::Onion { eta } => random_correlation_onion(d, eta, rng),
+        CorrelationMethod::Vine { eta } => random_correlation_vine(d, eta, rng),
+        CorrelationMethod::Eigenvalues(ref eigenvalues) => {
+            if eigenvalues.len() != d {
+                return Err(StatsError::BadParams);
+            }
+            random_correlation_with_eigenvalues(eigenvalues, rng)
+        }
+    }
+}
+
+/// Samples an `n x n` correlation matrix from the LKJ distribution with
+/// concentration `eta` using the C-vine method (Lewandowski, Kurowicka &
+/// Joe, 2009)
+///
+/// # Remarks
+///
+/// The partial correlations of the vine are drawn independently from
+/// scaled beta distributions and converted into correlations by the
+/// recursion for partial correlations. The result has the same distribution
+/// as `random_correlation_onion`.
+///
+/// # Errors
+///
+/// Returns an error if `n` is zero or if `eta` is `NaN`, infinite or not
+/// positive
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let c = sampler::random_correlation_vine(4, 1.0, &mut rng).unwrap();
+/// assert_eq!(c.diagonal().iter().sum::<f64>(), 4.0);
+/// assert_eq!(c, c.transpose());
+/// ```
+pub fn random_correlation_vine<R: Rng + ?Sized>(
+    n: usize,
+    eta: f64,
+    rng: &mut R,
+) -> Result<DMatrix<f64>> {
+    if n == 0 {
+        return Err(StatsError::BadParams);
+    }
+    if !eta.is_finite() || eta <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("eta"));
+    }
+    let mut partial = DMatrix::<f64>::zeros(n, n);
+    let mut corr = DMatrix::<f64>::identity(n, n);
+    let mut beta = eta + 0.5 * (n as f64 - 1.0);
+    for k in 0..n - 1 {
+        beta -= 0.5;
+        let dist = Beta::new(beta, beta)?;
+        for i in k + 1..n {
+            partial[(k, i)] = 2.0 * dist.sample(rng) - 1.0;
+            let mut r = partial[(k, i)];
+            for l in (0..k).rev() {
+                let (pli, plk) = (partial[(l, i)], partial[(l, k)]);
+                r = r * ((1.0 - pli * pli) * (1.0 - plk * plk)).sqrt() + pli * plk;
+            }
+            corr[(k, i)] = r;
+            corr[(i, k)] = r;
+        }
+    }
+    Ok(corr)
+}
+
+/// Samples a correlation matrix with the given eigenvalues and random
+/// eigenvectors using the algorithm of Bendel & Mickey (1978) as refined by
+/// Davies & Higham (2000)
+///
+/// The eigenvalues are rescaled to sum to their number `n`, the trace of
+/// every `n x n` correlation matrix, so only their relative spread matters.
+///
+/// # Remarks
+///
+/// The matrix `Q diag(λ) Q^T` with a Haar distributed orthogonal `Q` is
+/// transformed into a correlation matrix by a sequence of Givens rotations
+/// that set its diagonal to one while preserving the eigenvalues.
+///
+/// # Errors
+///
+/// Returns an error if `eigenvalues` is empty, if any eigenvalue is `NaN`,
+/// infinite or negative, or if all eigenvalues are zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let c = sampler::random_correlation_with_eigenvalues(&[3.0, 1.0], &mut rng).unwrap();
+/// // a 2 x 2 correlation matrix has eigenvalues 1 ± |ρ|
+/// assert!((c[(0, 1)].abs() - 0.5).abs() < 1e-12);
+/// ```
+pub fn random_correlation_with_eigenvalues<R: Rng + ?Sized>(
+    eigenvalues: &[f64],
+    rng: &mut R,
+) -> Result<DMatrix<f64>> {
+    let n = eigenvalues.len();
+    if n == 0 || eigenvalues.iter().any(|&x| !x.is_finite() || x < 0.0) {
+        return Err(StatsError::BadParams);
+    }
+    let sum: f64 = eigenvalues.iter().sum();
+    if sum <= 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let q = random_orthogonal(n, rng)?;
+    let lambda = DVector::from_iterator(n, eigenvalues.iter().map(|&x| x * n as f64 / sum));
+    let mut a = &q * DMatrix::from_diagonal(&lambda) * q.transpose();
+    for i in 0..n - 1 {
+        let di = a[(i, i)] - 1.0;
+        if di == 0.0 {
+            continue;
+        }
+        // the remaining diagonal deviations sum to zero, so one of them has
+        // the opposite sign unless all of them vanish up to rounding
+        let j = match (i + 1..n).find(|&j| (a[(j, j)] - 1.0) * di < 0.0) {
+            Some(j) => j,
+            None => break,
+        };
+        let dj = a[(j, j)] - 1.0;
+        let aij = a[(i, j)];
+        let root = (aij * aij - di * dj).sqrt();
+        let t = di / (aij + if aij < 0.0 { -root } else { root });
+        let c = 1.0 / (1.0 + t * t).sqrt();
+        let s = c * t;
+        for k in 0..n {
+            let (x, y) = (a[(k, i)], a[(k, j)]);
+            a[(k, i)] = c * x - s * y;
+            a[(k, j)] = s * x + c * y;
+        }
+        for k in 0..n {
+            let (x, y) = (a[(i, k)], a[(j, k)]);
+            a[(i, k)] = c * x - s * y;
+            a[(j, k)] = s * x + c * y;
+        }
+    }
+    for i in 0..n {
+        a[(i, i)] = 1.0;
+        for j in 0..i {
+            let mean = 0.5 * (a[(i, j)] + a[(j, i)]);
+            a[(i, j)] = mean;
+            a[(j, i)] = mean;
+        }
+    }
+    Ok(a)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bad_params() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(random_orthogonal(0, &mut rng).is_err());
+        assert!(random_projection(0, 0, &mut rng).is_err());
+        assert!(random_correlation_onion(0, 1.0, &mut rng).is_err());
+        assert!(random_correlation_onion(3, 0.0, &mut rng).is_err());
+        assert!(random_correlation_onion(3, f64::NAN, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_random_orthogonal() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for &n in &[1, 2, 5, 10] {
+            let q = random_orthogonal(n, &mut rng).unwrap();
+            assert!((q.transpose() * &q - DMatrix::identity(n, n)).amax() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_random_orthogonal_haar_trace() {
+        // the trace of a Haar orthogonal matrix has mean 0 and second moment 1
+        let mut rng = StdRng::seed_from_u64(2);
+        let count = 20_000;
+        let traces: Vec<f64> = (0..count).map(|_| random_orthogonal(4, &mut rng).unwrap().trace()).collect();
+        let mean = traces.iter().sum::<f64>() / count as f64;
+        let second = traces.iter().map(|t| t * t).sum::<f64>() / count as f64;
+        assert_almost_eq!(mean, 0.0, 0.03);
+        assert_almost_eq!(second, 1.0, 0.05);
+    }
+
+    #[test]
+    fn test_random_rotation() {
+        let mut rng = StdRng::seed_from_u64(9);
+        assert!(random_rotation(0, &mut rng).is_err());
+        for &n in &[1, 2, 3, 8] {
+            for _ in 0..10 {
+                let q = random_rotation(n, &mut rng).unwrap();
+                assert!((q.transpose() * &q - DMatrix::identity(n, n)).amax() < 1e-12);
+                assert_almost_eq!(q.determinant(), 1.0, 1e-12);
+            }
+        }
+        // a uniform rotation of the plane has a uniformly distributed angle
+        let count = 20_000;
+        let angles: Vec<f64> = (0..count).map(|_| {
+            let q = random_rotation(2, &mut rng).unwrap();
+            q[(1, 0)].atan2(q[(0, 0)])
+        }).collect();
+        let mean = angles.iter().sum::<f64>() / count as f64;
+        let var = angles.iter().map(|x| x * x).sum::<f64>() / count as f64;
+        assert_almost_eq!(mean, 0.0, 0.05);
+        assert_almost_eq!(var, std::f64::consts::PI * std::f64::consts::PI / 3.0, 0.05);
+    }
+
+    #[test]
+    fn test_random_projection() {
+        let mut rng = StdRng::seed_from_u64(10);
+        assert!(random_projection(3, 0, &mut rng).is_err());
+        assert!(random_projection(3, 4, &mut rng).is_err());
+        let (n, k) = (6, 2);
+        let count = 20_000;
+        let mut second = DMatrix::<f64>::zeros(n, k);
+        for _ in 0..count {
+            let p = random_projection(n, k, &mut rng).unwrap();
+            assert!((p.transpose() * &p - DMatrix::identity(k, k)).amax() < 1e-12);
+            second += p.component_mul(&p);
+        }
+        // every entry of a Haar orthogonal matrix has second moment 1 / n
+        for x in (second / count as f64).iter() {
+            assert_almost_eq!(*x, 1.0 / n as f64, 0.01);
+        }
+    }
+
+    #[test]
+    fn test_random_correlation_onion() {
+        let mut rng = StdRng::seed_from_u64(3);
+        assert_eq!(random_correlation_onion(1, 1.0, &mut rng).unwrap(), DMatrix::identity(1, 1));
+        for &n in &[2, 3, 6] {
+            for &eta in &[0.5, 1.0, 4.0] {
+                let c = random_correlation_onion(n, eta, &mut rng).unwrap();
+                assert_eq!(c, c.transpose());
+                assert!(c.diagonal().iter().all(|&x| x == 1.0));
+                assert!(Cholesky::new(c).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_correlation_vine() {
+        let mut rng = StdRng::seed_from_u64(5);
+        assert_eq!(random_correlation_vine(1, 1.0, &mut rng).unwrap(), DMatrix::identity(1, 1));
+        assert!(random_correlation_vine(0, 1.0, &mut rng).is_err());
+        assert!(random_correlation_vine(3, -1.0, &mut rng).is_err());
+        for &n in &[2, 3, 6] {
+            for &eta in &[0.5, 1.0, 4.0] {
+                let c = random_correlation_vine(n, eta, &mut rng).unwrap();
+                assert_eq!(c, c.transpose());
+                assert!(c.diagonal().iter().all(|&x| x == 1.0));
+                assert!(Cholesky::new(c).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_correlation_vine_marginal() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let count = 20_000;
+        let (n, eta) = (4, 0.5);
+        let a = eta - 1.0 + 0.5 * n as f64;
+        for &(i, j) in &[(0, 1), (1, 3), (2, 3)] {
+            let r: Vec<f64> = (0..count).map(|_| random_correlation_vine(n, eta, &mut rng).unwrap()[(i, j)]).collect();
+            let mean = r.iter().sum::<f64>() / count as f64;
+            let var = r.iter().map(|x| x * x).sum::<f64>() / count as f64;
+            assert_almost_eq!(mean, 0.0, 0.015);
+            assert_almost_eq!(var, 1.0 / (2.0 * a + 1.0), 0.01);
+        }
+    }
+
+    #[test]
+    fn test_random_correlation_with_eigenvalues() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert!(random_correlation_with_eigenvalues(&[], &mut rng).is_err());
+        assert!(random_correlation_with_eigenvalues(&[1.0, -0.5], &mut rng).is_err());
+        assert!(random_correlation_with_eigenvalues(&[0.0, 0.0], &mut rng).is_err());
+        for eigenvalues in &[vec![1.0], vec![3.0, 1.0], vec![4.0, 0.5, 0.3, 0.2, 0.0], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]] {
+            let n = eigenvalues.len();
+            let c = random_correlation_with_eigenvalues(eigenvalues, &mut rng).unwrap();
+            assert_eq!(c, c.transpose());
+            assert!(c.diagonal().iter().all(|&x| x == 1.0));
+            let mut actual: Vec<f64> = c.symmetric_eigenvalues().iter().cloned().collect();
+            actual.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let sum: f64 = eigenvalues.iter().sum();
+            let mut expected: Vec<f64> = eigenvalues.iter().map(|x| x * n as f64 / sum).collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for (x, y) in actual.iter().zip(expected.iter()) {
+                assert_almost_eq!(*x, *y, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_correlation() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let methods = [
+            CorrelationMethod::Onion { eta: 2.0 },
+            CorrelationMethod::Vine { eta: 2.0 },
+            CorrelationMethod::Eigenvalues(vec![1.5, 1.0, 0.5]),
+        ];
+        for method in methods.iter() {
+            let c = random_correlation(3, method, &mut rng).unwrap();
+            assert_eq!(c.shape(), (3, 3));
+            assert!(random_correlation(0, method, &mut rng).is_err());
+        }
+        assert!(random_correlation(2, &methods[2], &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_random_correlation_onion_marginal() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let count = 20_000;
+        let (n, eta) = (4, 2.0);
+        let a = eta - 1.0 + 0.5 * n as f64;
+        for &(i, j) in &[(0, 1), (1, 3), (2, 3)] {
+            let r: Vec<f64> = (0..count).map(|_| random_correlation_onion(n, eta, &mut rng).unwrap()[(i, j)]).collect();
+            let mean = r.iter().sum::<f64>() / count as f64;
+            let var = r.iter().map(|x| x * x).sum::<f64>() / count as f64;
+            assert_almost_eq!(mean, 0.0, 0.01);
+            assert_almost_eq!(var, 1.0 / (2.0 * a + 1.0), 0.01);
+        }
+    }
+}
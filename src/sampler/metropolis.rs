@@ -0,0 +1,600 @@
+use crate::distribution::Normal;
+use crate::fp;
+use crate::{Result, StatsError};
+use nalgebra::{DMatrix, DVector};
+use rand::{Rng, RngCore};
+use std::fmt;
+
+/// The number of burn-in steps between updates of the Cholesky factor of
+/// the adapted covariance
+const COVARIANCE_INTERVAL: usize = 50;
+
+/// A proposal drawing a candidate from the current state together with the
+/// log Hastings correction `ln q(x | y) - ln q(y | x)`
+type Proposal = Box<dyn Fn(&[f64], &mut dyn RngCore) -> (Vec<f64>, f64) + Send + Sync>;
+
+/// Implements the [Metropolis–Hastings
+/// algorithm](https://en.wikipedia.org/wiki/Metropolis%E2%80%93Hastings_algorithm)
+/// for sampling from a target density on `R^d` known up to a constant
+///
+/// A candidate `y` drawn from the proposal at the current state `x` is
+/// accepted with probability
+///
+/// ```ignore
+/// min(1, f(y) q(x | y) / (f(x) q(y | x)))
+/// ```
+///
+/// where `f` is the target density and `q` the density of the proposal, and
+/// otherwise the chain stays at `x`. By default the proposal is a random
+/// walk `y = x + s z` with independent standard normal `z` whose scale `s`
+/// is adapted during the burn-in.
+///
+/// # Remarks
+///
+/// `states` first runs the burn-in, then yields every `thin`th state of the
+/// chain. The burn-in is run only once, so further calls to `states`
+/// continue the same chain. With the default proposal the log scale is
+/// moved by a Robbins–Monro step towards an acceptance rate of 0.44 in one
+/// dimension and 0.234 otherwise during the burn-in, and is kept fixed
+/// afterwards so that the chain has the target as its stationary
+/// distribution. The acceptance rate only counts the steps after the
+/// burn-in.
+///
+/// With `with_adaptive_covariance` the random walk is shaped by the
+/// covariance of the states visited so far as in the adaptive Metropolis
+/// algorithm of Haario, Saksman & Tamminen (2001), i.e. `y = x + s L z`
+/// where `L L^T` is the empirical covariance plus a small ridge, updated
+/// every 50 steps of the burn-in once more than `2d` states have been
+/// seen, and `s` starts at `2.38 / √d`. Unlike in the original algorithm
+/// the covariance is frozen with the scale after the burn-in.
+///
+/// Use `ess` and `split_rhat` to check the convergence of the chains.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, Gamma};
+/// use statrs::sampler::MetropolisHastings;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let gamma = Gamma::new(3.0, 2.0).unwrap();
+/// let mut sampler = MetropolisHastings::new(|x: &[f64]| gamma.ln_pdf(x[0]), vec![1.0])
+///     .unwrap()
+///     .with_burn_in(1000)
+///     .with_thinning(5)
+///     .unwrap();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let mean = sampler.states(&mut rng).take(4000).map(|x| x[0]).sum::<f64>() / 4000.0;
+/// assert!((mean - 1.5).abs() < 0.1);
+/// assert!(sampler.acceptance_rate().unwrap() > 0.3);
+/// ```
+pub struct MetropolisHastings<F> {
+    target_ln_pdf: F,
+    state: Vec<f64>,
+    ln_f: f64,
+    proposal: Option<Proposal>,
+    scale: f64,
+    covariance: Option<CovarianceAdaptation>,
+    burn_in: usize,
+    thin: usize,
+    proposals: u64,
+    accepted: u64,
+}
+
+impl<F> MetropolisHastings<F>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    /// Constructs a Metropolis–Hastings sampler for the target with log
+    /// density `target_ln_pdf`, known up to an additive constant, starting
+    /// from `initial`, with a random walk proposal of scale one, no burn-in
+    /// and no thinning
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `initial` is empty or has a non-finite entry, or
+    /// if the log target is not finite at `initial`
+    pub fn new(target_ln_pdf: F, initial: Vec<f64>) -> Result<MetropolisHastings<F>> {
+        if initial.is_empty() || initial.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        let ln_f = target_ln_pdf(&initial);
+        if !ln_f.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        Ok(MetropolisHastings {
+            target_ln_pdf,
+            state: initial,
+            ln_f,
+            proposal: None,
+            scale: 1.0,
+            covariance: None,
+            burn_in: 0,
+            thin: 1,
+            proposals: 0,
+            accepted: 0,
+        })
+    }
+
+    /// Returns the sampler with the initial scale of the random walk
+    /// proposal set to `scale`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `scale` is not finite and positive
+    pub fn with_scale(self, scale: f64) -> Result<MetropolisHastings<F>> {
+        if !(scale.is_finite() && scale > 0.0) {
+            return Err(StatsError::ArgMustBePositive("scale"));
+        }
+        Ok(MetropolisHastings { scale, ..self })
+    }
+
+    /// Returns the sampler with `burn_in` steps of the chain discarded before
+    /// the first state is yielded
+    pub fn with_burn_in(self, burn_in: usize) -> MetropolisHastings<F> {
+        MetropolisHastings { burn_in, ..self }
+    }
+
+    /// Returns the sampler yielding only every `thin`th state of the chain
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `thin` is zero
+    pub fn with_thinning(self, thin: usize) -> Result<MetropolisHastings<F>> {
+        if thin == 0 {
+            return Err(StatsError::ArgMustBePositive("thin"));
+        }
+        Ok(MetropolisHastings { thin, ..self })
+    }
+
+    /// Returns the sampler with the random walk proposal shaped by the
+    /// covariance of the chain adapted during the burn-in, which suits
+    /// targets with correlated or differently scaled coordinates
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::sampler::MetropolisHastings;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// // a normal with standard deviations 100 and 0.01
+    /// let ln_target = |x: &[f64]| -0.5 * ((x[0] / 100.0).powi(2) + (x[1] / 0.01).powi(2));
+    /// let mut sampler = MetropolisHastings::new(ln_target, vec![0.0, 0.0])
+    ///     .unwrap()
+    ///     .with_adaptive_covariance()
+    ///     .with_burn_in(5000);
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let x: Vec<Vec<f64>> = sampler.states(&mut rng).take(5000).collect();
+    /// let var = x.iter().map(|x| x[0] * x[0]).sum::<f64>() / 5000.0;
+    /// assert!(var > 5000.0 && var < 20000.0);
+    /// let factor = sampler.covariance_factor().unwrap();
+    /// assert!(factor[(0, 0)] > 1000.0 * factor[(1, 1)]);
+    /// ```
+    pub fn with_adaptive_covariance(self) -> MetropolisHastings<F> {
+        let d = self.state.len();
+        MetropolisHastings {
+            covariance: Some(CovarianceAdaptation::new(d)),
+            ..self
+        }
+    }
+
+    /// Returns the sampler with a custom proposal, which draws a candidate
+    /// from the current state and returns it together with the log
+    /// Hastings correction `ln q(x | y) - ln q(y | x)` for the current state
+    /// `x` and the candidate `y`, zero for symmetric proposals. The scale is
+    /// not adapted and the covariance not used for custom proposals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::sampler::MetropolisHastings;
+    /// use rand::Rng;
+    ///
+    /// // a symmetric uniform random walk
+    /// let sampler = MetropolisHastings::new(|x: &[f64]| -x[0].abs(), vec![0.0])
+    ///     .unwrap()
+    ///     .with_proposal(|x, rng| (vec![x[0] + rng.gen_range(-1.0..1.0)], 0.0));
+    /// ```
+    pub fn with_proposal<P>(self, proposal: P) -> MetropolisHastings<F>
+    where
+        P: Fn(&[f64], &mut dyn RngCore) -> (Vec<f64>, f64) + Send + Sync + 'static,
+    {
+        MetropolisHastings {
+            proposal: Some(Box::new(proposal)),
+            ..self
+        }
+    }
+
+    /// Returns the current state of the chain
+    pub fn state(&self) -> &[f64] {
+        &self.state
+    }
+
+    /// Returns the log target at the current state of the chain
+    pub fn ln_target(&self) -> f64 {
+        self.ln_f
+    }
+
+    /// Returns the scale of the random walk proposal
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the lower triangular factor `L` shaping the random walk
+    /// proposal, or `None` if the covariance is not adapted
+    pub fn covariance_factor(&self) -> Option<&DMatrix<f64>> {
+        self.covariance.as_ref().map(|c| &c.factor)
+    }
+
+    /// Returns the number of burn-in steps still to be run
+    pub fn burn_in(&self) -> usize {
+        self.burn_in
+    }
+
+    /// Returns the thinning interval
+    pub fn thin(&self) -> usize {
+        self.thin
+    }
+
+    /// Returns the number of proposals drawn after the burn-in
+    pub fn proposals(&self) -> u64 {
+        self.proposals
+    }
+
+    /// Returns the number of proposals accepted after the burn-in
+    pub fn accepted(&self) -> u64 {
+        self.accepted
+    }
+
+    /// Returns the fraction of proposals accepted after the burn-in, or
+    /// `None` if no proposal has been drawn
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        if self.proposals == 0 {
+            None
+        } else {
+            Some(self.accepted as f64 / self.proposals as f64)
+        }
+    }
+
+    /// Returns an infinite iterator over the states of the chain drawn
+    /// using `rng`, running the remaining burn-in first
+    pub fn states<R: Rng>(&mut self, rng: R) -> MetropolisStates<'_, F, R> {
+        MetropolisStates { sampler: self, rng }
+    }
+
+    /// Runs a single step of the chain and returns the probability with
+    /// which the candidate was accepted
+    pub fn step<R: Rng + ?Sized>(&mut self, rng: &mut R) -> f64 {
+        let (candidate, ln_correction) = match &self.proposal {
+            Some(proposal) => {
+                let mut rng = RngRef(rng);
+                proposal(&self.state, &mut rng)
+            }
+            None => {
+                let normal = Normal::new(0.0, self.scale).unwrap();
+                let mut step = self
+                    .state
+                    .iter()
+                    .map(|_| ::rand::distributions::Distribution::sample(&normal, rng))
+                    .collect::<Vec<f64>>();
+                if let Some(covariance) = &self.covariance {
+                    let shaped = &covariance.factor * DVector::from_vec(step);
+                    step = shaped.data.into();
+                }
+                let candidate = self.state.iter().zip(step).map(|(x, s)| x + s).collect();
+                (candidate, 0.0)
+            }
+        };
+        let ln_f = (self.target_ln_pdf)(&candidate);
+        let ln_ratio = ln_f - self.ln_f + ln_correction;
+        let accept = if ln_ratio.is_nan() {
+            0.0
+        } else {
+            fp::exp(ln_ratio.min(0.0))
+        };
+        self.proposals += 1;
+        if rng.gen::<f64>() < accept {
+            self.state = candidate;
+            self.ln_f = ln_f;
+            self.accepted += 1;
+        }
+        accept
+    }
+
+    /// Runs the remaining burn-in, adapting the scale and covariance of the
+    /// random walk proposal
+    fn run_burn_in<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let d = self.state.len();
+        let target = if d == 1 { 0.44 } else { 0.234 };
+        let adapt = self.proposal.is_none();
+        let mut ln_scale = fp::ln(self.scale);
+        let mut t = 1.0;
+        while self.burn_in > 0 {
+            let accept = self.step(rng);
+            if adapt {
+                ln_scale += (accept - target) / fp::powf(t, 0.6);
+                self.scale = fp::exp(ln_scale);
+                t += 1.0;
+                if let Some(covariance) = &mut self.covariance {
+                    // the scale adapted to the identity is restarted at the
+                    // optimum for normal targets with the first estimate
+                    if covariance.update(&self.state) && covariance.refactor() {
+                        ln_scale = fp::ln(2.38 / (d as f64).sqrt());
+                        self.scale = fp::exp(ln_scale);
+                    }
+                }
+            }
+            self.burn_in -= 1;
+        }
+        self.proposals = 0;
+        self.accepted = 0;
+    }
+}
+
+impl<F> fmt::Debug for MetropolisHastings<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetropolisHastings")
+            .field("state", &self.state)
+            .field("ln_f", &self.ln_f)
+            .field("custom_proposal", &self.proposal.is_some())
+            .field("scale", &self.scale)
+            .field("covariance", &self.covariance)
+            .field("burn_in", &self.burn_in)
+            .field("thin", &self.thin)
+            .field("proposals", &self.proposals)
+            .field("accepted", &self.accepted)
+            .finish()
+    }
+}
+
+/// The running covariance of the states of a chain, estimated by Welford's
+/// algorithm, and the Cholesky factor of the last estimate
+#[derive(Debug, Clone)]
+struct CovarianceAdaptation {
+    count: f64,
+    since_refactor: usize,
+    mean: DVector<f64>,
+    scatter: DMatrix<f64>,
+    factor: DMatrix<f64>,
+    estimated: bool,
+}
+
+impl CovarianceAdaptation {
+    fn new(d: usize) -> CovarianceAdaptation {
+        CovarianceAdaptation {
+            count: 0.0,
+            since_refactor: 0,
+            mean: DVector::zeros(d),
+            scatter: DMatrix::zeros(d, d),
+            factor: DMatrix::identity(d, d),
+            estimated: false,
+        }
+    }
+
+    /// Adds the state `x` to the estimate, returning `true` if the factor
+    /// is due to be updated
+    fn update(&mut self, x: &[f64]) -> bool {
+        let d = x.len();
+        let x = DVector::from_column_slice(x);
+        self.count += 1.0;
+        let delta = &x - &self.mean;
+        self.mean += &delta / self.count;
+        self.scatter += &delta * (x - &self.mean).transpose();
+        self.since_refactor += 1;
+        self.since_refactor >= COVARIANCE_INTERVAL && self.count > (2 * d) as f64
+    }
+
+    /// Refactors the current estimate with a ridge keeping it positive
+    /// definite, returning `true` if it is the first estimate
+    fn refactor(&mut self) -> bool {
+        let d = self.mean.len();
+        self.since_refactor = 0;
+        let mut covariance = &self.scatter / (self.count - 1.0);
+        let ridge = 1e-10 * covariance.diagonal().amax() + f64::MIN_POSITIVE;
+        for i in 0..d {
+            covariance[(i, i)] += ridge;
+        }
+        match covariance.cholesky() {
+            Some(cholesky) => {
+                self.factor = cholesky.unpack();
+                let first = !self.estimated;
+                self.estimated = true;
+                first
+            }
+            None => false,
+        }
+    }
+}
+
+/// An infinite iterator over the states of a `MetropolisHastings` chain,
+/// created by `MetropolisHastings::states`
+#[derive(Debug)]
+pub struct MetropolisStates<'a, F, R> {
+    sampler: &'a mut MetropolisHastings<F>,
+    rng: R,
+}
+
+impl<'a, F, R> Iterator for MetropolisStates<'a, F, R>
+where
+    F: Fn(&[f64]) -> f64,
+    R: Rng,
+{
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Vec<f64>> {
+        if self.sampler.burn_in > 0 {
+            self.sampler.run_burn_in(&mut self.rng);
+        }
+        for _ in 0..self.sampler.thin {
+            self.sampler.step(&mut self.rng);
+        }
+        Some(self.sampler.state.clone())
+    }
+}
+
+/// Passes a possibly unsized rng on to a proposal as a trait object
+pub(super) struct RngRef<'a, R: ?Sized>(pub(super) &'a mut R);
+
+impl<'a, R: RngCore + ?Sized> RngCore for RngRef<'a, R> {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> ::std::result::Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Continuous, Exp, Normal};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn moments(x: &[f64]) -> (f64, f64) {
+        let n = x.len() as f64;
+        let mean = x.iter().sum::<f64>() / n;
+        (mean, x.iter().map(|xi| (xi - mean).powi(2)).sum::<f64>() / (n - 1.0))
+    }
+
+    #[test]
+    fn test_metropolis_normal() {
+        let normal = Normal::new(2.0, 3.0).unwrap();
+        let mut sampler = MetropolisHastings::new(|x: &[f64]| normal.ln_pdf(x[0]), vec![0.0])
+            .unwrap()
+            .with_scale(0.1)
+            .unwrap()
+            .with_burn_in(2000)
+            .with_thinning(4)
+            .unwrap();
+        assert_eq!(sampler.acceptance_rate(), None);
+        let mut rng = StdRng::seed_from_u64(1);
+        let x: Vec<f64> = sampler.states(&mut rng).take(10000).map(|x| x[0]).collect();
+        let (mean, var) = moments(&x);
+        assert!((mean - 2.0).abs() < 0.15);
+        assert!((var - 9.0).abs() < 0.6);
+        // the scale adapts from 0.1 towards the optimum of about 2.4 σ
+        assert!(sampler.scale() > 3.0 && sampler.scale() < 12.0);
+        assert!((sampler.acceptance_rate().unwrap() - 0.44).abs() < 0.05);
+        assert_eq!(sampler.burn_in(), 0);
+        assert_eq!(sampler.proposals(), 40000);
+    }
+
+    #[test]
+    fn test_metropolis_bivariate() {
+        // a bivariate normal with unit variances and correlation 0.8
+        let rho: f64 = 0.8;
+        let ln_target = |x: &[f64]| -(x[0] * x[0] - 2.0 * rho * x[0] * x[1] + x[1] * x[1]) / (2.0 * (1.0 - rho * rho));
+        let mut sampler = MetropolisHastings::new(ln_target, vec![3.0, -3.0]).unwrap().with_burn_in(5000);
+        let mut rng = StdRng::seed_from_u64(2);
+        let x: Vec<Vec<f64>> = sampler.states(&mut rng).take(40000).collect();
+        let (m0, v0) = moments(&x.iter().map(|x| x[0]).collect::<Vec<_>>());
+        let (m1, v1) = moments(&x.iter().map(|x| x[1]).collect::<Vec<_>>());
+        let cov = x.iter().map(|x| (x[0] - m0) * (x[1] - m1)).sum::<f64>() / 39999.0;
+        assert!(m0.abs() < 0.1 && m1.abs() < 0.1);
+        assert!((v0 - 1.0).abs() < 0.1 && (v1 - 1.0).abs() < 0.1);
+        assert!((cov - rho).abs() < 0.1);
+        assert!((sampler.acceptance_rate().unwrap() - 0.234).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_metropolis_custom_proposal() {
+        // an exponential target from an independent exponential proposal
+        // with a different rate, which needs the Hastings correction
+        let target = Exp::new(1.0).unwrap();
+        let proposal = Exp::new(0.5).unwrap();
+        let mut sampler = MetropolisHastings::new(|x: &[f64]| target.ln_pdf(x[0]), vec![1.0])
+            .unwrap()
+            .with_proposal(move |x, rng| {
+                let y = ::rand::distributions::Distribution::sample(&proposal, rng);
+                (vec![y], proposal.ln_pdf(x[0]) - proposal.ln_pdf(y))
+            })
+            .with_burn_in(100);
+        let mut rng = StdRng::seed_from_u64(3);
+        let x: Vec<f64> = sampler.states(&mut rng).take(20000).map(|x| x[0]).collect();
+        let (mean, var) = moments(&x);
+        assert!((mean - 1.0).abs() < 0.05);
+        assert!((var - 1.0).abs() < 0.1);
+        // custom proposals are not adapted
+        assert_eq!(sampler.scale(), 1.0);
+        assert!(x.iter().all(|&x| x > 0.0));
+    }
+
+    #[test]
+    fn test_metropolis_step_and_bounds() {
+        // the chain never leaves the support of the target
+        let ln_target = |x: &[f64]| if x[0] > 0.0 && x[0] < 1.0 { 0.0 } else { f64::NEG_INFINITY };
+        let mut sampler = MetropolisHastings::new(ln_target, vec![0.5]).unwrap();
+        let mut rng = StdRng::seed_from_u64(4);
+        for _ in 0..1000 {
+            let accept = sampler.step(&mut rng);
+            assert!((0.0..=1.0).contains(&accept));
+            assert!(sampler.state()[0] > 0.0 && sampler.state()[0] < 1.0);
+            assert_eq!(sampler.ln_target(), 0.0);
+        }
+        assert_eq!(sampler.proposals(), 1000);
+    }
+
+    #[test]
+    fn test_metropolis_adaptive_covariance() {
+        // a correlated normal whose coordinates differ in scale by 100,
+        // where an isotropic walk barely moves along the wide direction
+        let (s0, s1, rho) = (10.0, 0.1, 0.9);
+        let ln_target = |x: &[f64]| {
+            let (z0, z1) = (x[0] / s0, x[1] / s1);
+            -(z0 * z0 - 2.0 * rho * z0 * z1 + z1 * z1) / (2.0 * (1.0 - rho * rho))
+        };
+        let mut sampler = MetropolisHastings::new(ln_target, vec![0.0, 0.0])
+            .unwrap()
+            .with_adaptive_covariance()
+            .with_burn_in(10000);
+        assert_eq!(sampler.covariance_factor().unwrap(), &DMatrix::identity(2, 2));
+        let mut rng = StdRng::seed_from_u64(5);
+        let x: Vec<Vec<f64>> = sampler.states(&mut rng).take(20000).collect();
+        let (m0, v0) = moments(&x.iter().map(|x| x[0]).collect::<Vec<_>>());
+        let (_, v1) = moments(&x.iter().map(|x| x[1]).collect::<Vec<_>>());
+        assert!(m0.abs() < 1.0);
+        assert!((v0 / (s0 * s0) - 1.0).abs() < 0.15);
+        assert!((v1 / (s1 * s1) - 1.0).abs() < 0.15);
+        // the factor recovers the shape of the target
+        let factor = sampler.covariance_factor().unwrap().clone();
+        let covariance = &factor * factor.transpose();
+        assert!((covariance[(0, 0)] / (s0 * s0) - 1.0).abs() < 0.3);
+        assert!((covariance[(0, 1)] / covariance[(0, 0)].sqrt() / covariance[(1, 1)].sqrt() - rho).abs() < 0.05);
+        assert!((sampler.acceptance_rate().unwrap() - 0.234).abs() < 0.05);
+        // the isotropic walk mixes far worse over the same number of steps
+        let mut isotropic = MetropolisHastings::new(ln_target, vec![0.0, 0.0]).unwrap().with_burn_in(10000);
+        let y: Vec<f64> = isotropic.states(&mut rng).take(20000).map(|x| x[0]).collect();
+        let lag = |x: &[f64]| x.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum::<f64>();
+        assert!(lag(&x.iter().map(|x| x[0]).collect::<Vec<_>>()) > 10.0 * lag(&y));
+        // the covariance is frozen after the burn-in
+        assert_eq!(sampler.covariance_factor().unwrap(), &factor);
+    }
+
+    #[test]
+    fn test_metropolis_bad_params() {
+        let ln_target = |x: &[f64]| -x[0].abs();
+        assert!(MetropolisHastings::new(ln_target, vec![]).is_err());
+        assert!(MetropolisHastings::new(ln_target, vec![f64::NAN]).is_err());
+        assert!(MetropolisHastings::new(|_: &[f64]| f64::NEG_INFINITY, vec![0.0]).is_err());
+        let sampler = MetropolisHastings::new(ln_target, vec![0.0]).unwrap();
+        assert_eq!(sampler.thin(), 1);
+        assert!(sampler.with_thinning(0).is_err());
+        let sampler = MetropolisHastings::new(ln_target, vec![0.0]).unwrap();
+        assert!(sampler.with_scale(0.0).is_err());
+        let sampler = MetropolisHastings::new(ln_target, vec![0.0]).unwrap();
+        assert!(sampler.with_scale(f64::INFINITY).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+//! Provides samplers for random structures that are not covered by a single
+//! distribution, such as random orthogonal and correlation matrices used to
+//! stress-test multivariate code, and space-filling designs such as Latin
+//! hypercube samples, as well as the reduction of large weighted samples to
+//! a few representative scenarios, the resampling of weighted particles and
+//! draws from Dirichlet processes, along
+//! with convergence diagnostics for the output of Markov chain samplers,
+//! antithetic and stratified samplers for variance reduction, inverse
+//! transform sampling of any distribution with a cdf, and rejection,
+//! Metropolis–Hastings, Hamiltonian Monte Carlo, slice and Gibbs sampling
+//! from custom densities
+pub use self::diagnostics::{ess, split_rhat};
+pub use self::dirichlet_process::{
+    chinese_restaurant, stick_breaking, DirichletProcessMixture, MixtureSample,
+};
+pub use self::gibbs::{Conditional, Gibbs, GibbsStates, Trace, TraceSummary};
+pub use self::hmc::{Hamiltonian, HamiltonianStates};
+pub use self::inverse_transform::InverseTransform;
+pub use self::lhs::{latin_hypercube, map_marginals, LatinHypercube};
+pub use self::matrix::{
+    random_correlation, random_correlation_onion, random_correlation_vine,
+    random_correlation_with_eigenvalues, random_orthogonal, random_projection, random_rotation,
+    CorrelationMethod,
+};
+pub use self::metropolis::{MetropolisHastings, MetropolisStates};
+pub use self::rejection::Rejection;
+pub use self::resampling::{
+    effective_particles, multinomial_resample, residual_resample, stratified_resample,
+    systematic_resample, weighted_reservoir,
+};
+pub use self::scenario::{reduce_scenarios, ReducedScenarios};
+pub use self::slice::{Slice, SliceStates};
+pub use self::variance_reduction::{Antithetic, Stratified};
+
+mod diagnostics;
+mod dirichlet_process;
+mod gibbs;
+mod hmc;
+mod inverse_transform;
+mod lhs;
+mod matrix;
+mod metropolis;
+mod rejection;
+mod resampling;
+mod scenario;
+mod slice;
+mod variance_reduction;
@@ -0,0 +1,287 @@
+use crate::distribution::Continuous;
+use crate::fp;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// The maximum number of target evaluations kept for the adaptive squeeze
+const MAX_SQUEEZE_POINTS: usize = 256;
+
+/// Implements [rejection
+/// sampling](https://en.wikipedia.org/wiki/Rejection_sampling) from a
+/// target density known up to a constant, using a proposal distribution
+/// whose density scaled by `exp(ln_envelope)` bounds the target everywhere
+///
+/// A proposal `x` is accepted with probability
+/// `f(x) / (exp(ln_envelope) q(x))`, where `f` is the target density and
+/// `q` the density of the proposal. The sampler counts proposals, accepted
+/// proposals and evaluations of the target, so that the acceptance rate of
+/// a given envelope can be checked.
+///
+/// # Remarks
+///
+/// If the target is normalized the expected acceptance rate is
+/// `exp(-ln_envelope)`. A target exceeding the envelope yields samples from
+/// the wrong distribution, and `envelope_violations` counts how often an
+/// evaluation of the target was found above the envelope.
+///
+/// With `with_adaptive_squeeze` the sampler remembers up to 256 evaluations
+/// of the target and accepts proposals lying below the chords between them
+/// without evaluating the target again. The chords are a lower bound only
+/// if the log target is concave, so the option must only be used for
+/// log-concave targets.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Cauchy;
+/// use statrs::sampler::Rejection;
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// // a standard normal from a standard cauchy proposal, with the envelope
+/// // constant sup f(x) / q(x) = sqrt(2π / e)
+/// let ln_target = |x: f64| -0.5 * x * x - 0.5 * (2.0 * std::f64::consts::PI).ln();
+/// let ln_envelope = 0.5 * (2.0 * std::f64::consts::PI / std::f64::consts::E).ln();
+/// let cauchy = Cauchy::new(0.0, 1.0).unwrap();
+/// let sampler = Rejection::new(ln_target, cauchy, ln_envelope).unwrap();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let mean = (0..10000).map(|_| sampler.sample(&mut rng)).sum::<f64>() / 10000.0;
+/// assert!(mean.abs() < 0.05);
+/// assert!((sampler.acceptance_rate().unwrap() - (-ln_envelope).exp()).abs() < 0.02);
+/// ```
+#[derive(Debug)]
+pub struct Rejection<F, Q> {
+    target_ln_pdf: F,
+    proposal: Q,
+    ln_envelope: f64,
+    squeeze: Option<Mutex<Vec<(f64, f64)>>>,
+    proposals: AtomicU64,
+    accepted: AtomicU64,
+    evaluations: AtomicU64,
+    violations: AtomicU64,
+}
+
+impl<F, Q> Rejection<F, Q>
+where
+    F: Fn(f64) -> f64,
+    Q: Continuous<f64, f64> + ::rand::distributions::Distribution<f64>,
+{
+    /// Constructs a rejection sampler for the target with log density
+    /// `target_ln_pdf`, known up to an additive constant, from the
+    /// `proposal` distribution, where `ln_envelope` is the log of a
+    /// constant `M` with `f(x) <= M q(x)` for all `x`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `ln_envelope` is not finite
+    pub fn new(target_ln_pdf: F, proposal: Q, ln_envelope: f64) -> Result<Rejection<F, Q>> {
+        if !ln_envelope.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Rejection {
+            target_ln_pdf,
+            proposal,
+            ln_envelope,
+            squeeze: None,
+            proposals: AtomicU64::new(0),
+            accepted: AtomicU64::new(0),
+            evaluations: AtomicU64::new(0),
+            violations: AtomicU64::new(0),
+        })
+    }
+
+    /// Returns the sampler with an adaptive squeeze built from the chords
+    /// between evaluations of the log target, which is only valid for
+    /// log-concave targets
+    pub fn with_adaptive_squeeze(self) -> Rejection<F, Q> {
+        Rejection {
+            squeeze: Some(Mutex::new(Vec::new())),
+            ..self
+        }
+    }
+
+    /// Returns the proposal distribution
+    pub fn proposal(&self) -> &Q {
+        &self.proposal
+    }
+
+    /// Returns the log of the envelope constant
+    pub fn ln_envelope(&self) -> f64 {
+        self.ln_envelope
+    }
+
+    /// Returns the number of proposals drawn so far
+    pub fn proposals(&self) -> u64 {
+        self.proposals.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of proposals accepted so far
+    pub fn accepted(&self) -> u64 {
+        self.accepted.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of evaluations of the target so far, which is
+    /// smaller than the number of proposals when the squeeze accepts some
+    /// of them
+    pub fn target_evaluations(&self) -> u64 {
+        self.evaluations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of evaluations of the target which exceeded the
+    /// envelope, a sign that `ln_envelope` is too small
+    pub fn envelope_violations(&self) -> u64 {
+        self.violations.load(Ordering::Relaxed)
+    }
+
+    /// Returns the fraction of proposals accepted so far, or `None` if no
+    /// proposal has been drawn
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        let proposals = self.proposals();
+        if proposals == 0 {
+            None
+        } else {
+            Some(self.accepted() as f64 / proposals as f64)
+        }
+    }
+
+    /// Resets the counts of proposals, acceptances, target evaluations and
+    /// envelope violations, keeping the squeeze
+    pub fn reset_counts(&self) {
+        self.proposals.store(0, Ordering::Relaxed);
+        self.accepted.store(0, Ordering::Relaxed);
+        self.evaluations.store(0, Ordering::Relaxed);
+        self.violations.store(0, Ordering::Relaxed);
+    }
+
+    /// Returns the squeeze, the linear interpolation of the remembered
+    /// evaluations of the log target, at `x` if `x` lies between two of
+    /// them
+    fn squeeze_at(&self, x: f64) -> Option<f64> {
+        let points = self.squeeze.as_ref()?.lock().unwrap();
+        let i = points.partition_point(|&(xi, _)| xi <= x);
+        if i == 0 || i == points.len() {
+            return None;
+        }
+        let ((x0, y0), (x1, y1)) = (points[i - 1], points[i]);
+        Some(y0 + (y1 - y0) * (x - x0) / (x1 - x0))
+    }
+
+    /// Remembers the evaluation `ln_f` of the log target at `x` for the
+    /// squeeze
+    fn remember(&self, x: f64, ln_f: f64) {
+        if let Some(squeeze) = &self.squeeze {
+            let mut points = squeeze.lock().unwrap();
+            if points.len() < MAX_SQUEEZE_POINTS && x.is_finite() && ln_f.is_finite() {
+                let i = points.partition_point(|&(xi, _)| xi < x);
+                if !matches!(points.get(i), Some(&(xi, _)) if xi == x) {
+                    points.insert(i, (x, ln_f));
+                }
+            }
+        }
+    }
+}
+
+impl<F, Q> ::rand::distributions::Distribution<f64> for Rejection<F, Q>
+where
+    F: Fn(f64) -> f64,
+    Q: Continuous<f64, f64> + ::rand::distributions::Distribution<f64>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        loop {
+            let x = self.proposal.sample(rng);
+            let ln_u = fp::ln(rng.gen::<f64>());
+            let ln_bound = self.ln_envelope + self.proposal.ln_pdf(x);
+            self.proposals.fetch_add(1, Ordering::Relaxed);
+            if let Some(ln_s) = self.squeeze_at(x) {
+                if ln_u + ln_bound <= ln_s {
+                    self.accepted.fetch_add(1, Ordering::Relaxed);
+                    return x;
+                }
+            }
+            let ln_f = (self.target_ln_pdf)(x);
+            self.evaluations.fetch_add(1, Ordering::Relaxed);
+            if ln_f > ln_bound {
+                self.violations.fetch_add(1, Ordering::Relaxed);
+            }
+            self.remember(x, ln_f);
+            if ln_u + ln_bound < ln_f {
+                self.accepted.fetch_add(1, Ordering::Relaxed);
+                return x;
+            }
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Beta, Cauchy, ContinuousCDF, Normal, Uniform};
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Kolmogorov-Smirnov distance between a sample and a cdf
+    fn ks_distance<D: ContinuousCDF<f64, f64>>(mut x: Vec<f64>, dist: &D) -> f64 {
+        x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = x.len() as f64;
+        x.iter().enumerate().fold(0.0, |d, (i, &xi)| {
+            let f = dist.cdf(xi);
+            d.max((f - i as f64 / n).abs()).max(((i + 1) as f64 / n - f).abs())
+        })
+    }
+
+    #[test]
+    fn test_rejection_beta() {
+        // an unnormalized beta(2, 3) density from a uniform proposal, with
+        // the envelope at its mode 1/3
+        let ln_target = |x: f64| x.ln() + 2.0 * (1.0 - x).ln();
+        let ln_envelope = ln_target(1.0 / 3.0);
+        let sampler = Rejection::new(ln_target, Uniform::new(0.0, 1.0).unwrap(), ln_envelope).unwrap();
+        assert_eq!(sampler.acceptance_rate(), None);
+        let mut rng = StdRng::seed_from_u64(1);
+        let x: Vec<f64> = (0..4000).map(|_| sampler.sample(&mut rng)).collect();
+        assert!(ks_distance(x, &Beta::new(2.0, 3.0).unwrap()) < 0.03);
+        // the normalizing constant is B(2, 3) = 1/12, so the acceptance
+        // rate is 1/12 / (4/27)
+        assert!((sampler.acceptance_rate().unwrap() - 27.0 / 48.0).abs() < 0.02);
+        assert_eq!(sampler.accepted(), 4000);
+        assert_eq!(sampler.target_evaluations(), sampler.proposals());
+        assert_eq!(sampler.envelope_violations(), 0);
+        sampler.reset_counts();
+        assert_eq!(sampler.proposals(), 0);
+    }
+
+    #[test]
+    fn test_rejection_envelope_violation() {
+        let ln_target = |x: f64| -0.5 * x * x;
+        let sampler = Rejection::new(ln_target, Cauchy::new(0.0, 1.0).unwrap(), -1.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..100 {
+            sampler.sample(&mut rng);
+        }
+        assert!(sampler.envelope_violations() > 0);
+        assert!(Rejection::new(ln_target, Cauchy::new(0.0, 1.0).unwrap(), f64::NAN).is_err());
+        assert!(Rejection::new(ln_target, Cauchy::new(0.0, 1.0).unwrap(), f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_rejection_adaptive_squeeze() {
+        let ln_target = |x: f64| -0.5 * x * x - 0.5 * (2.0 * std::f64::consts::PI).ln();
+        let ln_envelope = 0.5 * (2.0 * std::f64::consts::PI / std::f64::consts::E).ln();
+        let sampler = Rejection::new(ln_target, Cauchy::new(0.0, 1.0).unwrap(), ln_envelope)
+            .unwrap()
+            .with_adaptive_squeeze();
+        let mut rng = StdRng::seed_from_u64(3);
+        let x: Vec<f64> = (0..4000).map(|_| sampler.sample(&mut rng)).collect();
+        assert!(ks_distance(x, &Normal::new(0.0, 1.0).unwrap()) < 0.03);
+        // once the squeeze has filled up most proposals are accepted without
+        // evaluating the target
+        let rejected = sampler.proposals() - sampler.accepted();
+        assert!(sampler.target_evaluations() < rejected + sampler.accepted() / 10);
+        assert_eq!(sampler.envelope_violations(), 0);
+    }
+}
@@ -0,0 +1,422 @@
+use crate::distribution::Exp;
+use crate::fp;
+use crate::{Result, StatsError};
+use rand::distributions::{Distribution, Open01};
+use rand::Rng;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// Draws `n` indices of `weights` independently with probabilities
+/// proportional to the weights, as in the bootstrap particle filter
+///
+/// # Remarks
+///
+/// The weights need not be normalized and particles of weight zero are
+/// never selected. The indices are returned in ascending order, i.e. as
+/// the copies of every selected particle. The `n` sorted uniforms are drawn
+/// from normalized exponential spacings, so the cost is linear in `n` and
+/// the number of weights.
+///
+/// # Errors
+///
+/// Returns an error if `weights` is empty, has a negative or non-finite
+/// entry or sums to zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let indices = sampler::multinomial_resample(&[0.1, 0.0, 0.6, 0.3], 10, &mut rng).unwrap();
+/// assert_eq!(indices.len(), 10);
+/// assert!(indices.windows(2).all(|w| w[0] <= w[1]));
+/// assert!(!indices.contains(&1));
+/// ```
+pub fn multinomial_resample<R: Rng + ?Sized>(
+    weights: &[f64],
+    n: usize,
+    rng: &mut R,
+) -> Result<Vec<usize>> {
+    let cumulative = cumulative_weights(weights)?;
+    let exp = Exp::new(1.0).unwrap();
+    let spacings: Vec<f64> = (0..=n).map(|_| exp.sample(rng)).collect();
+    let total: f64 = spacings.iter().sum();
+    let mut u = 0.0;
+    let positions = spacings[..n].iter().map(|e| {
+        u += e;
+        u / total
+    });
+    Ok(select(&cumulative, positions))
+}
+
+/// Draws `n` indices of `weights` by stratified resampling, placing one
+/// uniform in each of the `n` strata `[i / n, (i + 1) / n)` of the
+/// cumulative weights
+///
+/// # Remarks
+///
+/// Every particle is selected `n w_i` times in expectation, as with
+/// `multinomial_resample`, but the counts vary less. The indices are
+/// returned in ascending order.
+///
+/// # Errors
+///
+/// Returns an error if `weights` is empty, has a negative or non-finite
+/// entry or sums to zero
+pub fn stratified_resample<R: Rng + ?Sized>(
+    weights: &[f64],
+    n: usize,
+    rng: &mut R,
+) -> Result<Vec<usize>> {
+    let cumulative = cumulative_weights(weights)?;
+    let positions = (0..n).map(|i| (i as f64 + rng.gen::<f64>()) / n as f64);
+    Ok(select(&cumulative, positions))
+}
+
+/// Draws `n` indices of `weights` by systematic resampling, placing the
+/// points `(i + u) / n` for a single uniform `u` on the cumulative weights
+///
+/// # Remarks
+///
+/// Every particle is selected either `⌊n w_i⌋` or `⌈n w_i⌉` times, where
+/// `w_i` are the normalized weights, with `n w_i` copies in expectation.
+/// This is the cheapest scheme and usually has the lowest variance, but
+/// the counts of different particles are not independent and depend on
+/// the order of the weights. The indices are returned in ascending order.
+///
+/// # Errors
+///
+/// Returns an error if `weights` is empty, has a negative or non-finite
+/// entry or sums to zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+///
+/// let weights = [0.5, 0.25, 0.125, 0.125];
+/// let indices = sampler::systematic_resample(&weights, 8, &mut rand::thread_rng()).unwrap();
+/// assert_eq!(indices, vec![0, 0, 0, 0, 1, 1, 2, 3]);
+/// ```
+pub fn systematic_resample<R: Rng + ?Sized>(
+    weights: &[f64],
+    n: usize,
+    rng: &mut R,
+) -> Result<Vec<usize>> {
+    let cumulative = cumulative_weights(weights)?;
+    let u: f64 = rng.gen();
+    let positions = (0..n).map(|i| (i as f64 + u) / n as f64);
+    Ok(select(&cumulative, positions))
+}
+
+/// Draws `n` indices of `weights` by residual resampling, selecting every
+/// particle `⌊n w_i⌋` times deterministically and drawing the remaining
+/// indices by `multinomial_resample` from the residual weights
+/// `n w_i - ⌊n w_i⌋`
+///
+/// # Remarks
+///
+/// The indices are returned in ascending order.
+///
+/// # Errors
+///
+/// Returns an error if `weights` is empty, has a negative or non-finite
+/// entry or sums to zero
+pub fn residual_resample<R: Rng + ?Sized>(
+    weights: &[f64],
+    n: usize,
+    rng: &mut R,
+) -> Result<Vec<usize>> {
+    let cumulative = cumulative_weights(weights)?;
+    let total = cumulative[cumulative.len() - 1];
+    let mut counts = Vec::with_capacity(weights.len());
+    let mut residuals = Vec::with_capacity(weights.len());
+    for &w in weights {
+        let expected = n as f64 * w / total;
+        let copies = expected.floor();
+        counts.push(copies as usize);
+        residuals.push(expected - copies);
+    }
+    let remaining = n - counts.iter().sum::<usize>().min(n);
+    if remaining > 0 {
+        for i in multinomial_resample(&residuals, remaining, rng)? {
+            counts[i] += 1;
+        }
+    }
+    let mut indices = Vec::with_capacity(n);
+    for (i, &c) in counts.iter().enumerate() {
+        indices.resize(indices.len() + c, i);
+    }
+    Ok(indices)
+}
+
+/// Computes the effective number of particles `(Σ w_i)² / Σ w_i²` of a
+/// weighted sample, which lies between one and the number of weights and is
+/// commonly compared to half the number of particles to decide when to
+/// resample
+///
+/// # Errors
+///
+/// Returns an error if `weights` is empty, has a negative or non-finite
+/// entry or sums to zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+///
+/// assert_eq!(sampler::effective_particles(&[1.0, 1.0, 1.0, 1.0]).unwrap(), 4.0);
+/// assert_eq!(sampler::effective_particles(&[0.0, 3.0, 0.0]).unwrap(), 1.0);
+/// ```
+pub fn effective_particles(weights: &[f64]) -> Result<f64> {
+    let cumulative = cumulative_weights(weights)?;
+    let total = cumulative[cumulative.len() - 1];
+    Ok(1.0 / weights.iter().map(|w| fp::powi(w / total, 2)).sum::<f64>())
+}
+
+/// Draws a sample of at most `k` items without replacement from a stream
+/// of `(item, weight)` pairs in a single pass, by the weighted reservoir
+/// algorithm A-ES of Efraimidis & Spirakis (2006)
+///
+/// # Remarks
+///
+/// Every item is given the key `u^(1 / w)` for a uniform `u`, and the `k`
+/// items with the largest keys are kept, which selects items successively
+/// with probabilities proportional to the weights of the items not yet
+/// selected. The keys are compared on the log scale so that small weights
+/// do not underflow. Items of weight zero are never selected, so fewer
+/// than `k` items are returned if fewer have a positive weight. The items
+/// are returned in descending order of their keys, i.e. in the order of
+/// the successive selection.
+///
+/// # Errors
+///
+/// Returns an error if any weight is negative or not finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let stream = (0..1000).map(|i| (i, if i % 2 == 0 { 1.0 } else { 0.0 }));
+/// let sample = sampler::weighted_reservoir(stream, 10, &mut rng).unwrap();
+/// assert_eq!(sample.len(), 10);
+/// assert!(sample.iter().all(|i| i % 2 == 0));
+/// ```
+pub fn weighted_reservoir<T, I, R>(items: I, k: usize, rng: &mut R) -> Result<Vec<T>>
+where
+    I: IntoIterator<Item = (T, f64)>,
+    R: Rng + ?Sized,
+{
+    let mut reservoir: BinaryHeap<Reverse<Keyed<T>>> = BinaryHeap::with_capacity(k + 1);
+    for (item, w) in items {
+        if !(w.is_finite() && w >= 0.0) {
+            return Err(StatsError::ArgNotNegative("weight"));
+        }
+        if w == 0.0 || k == 0 {
+            continue;
+        }
+        let u: f64 = rng.sample(Open01);
+        let key = fp::ln(u) / w;
+        if reservoir.len() < k {
+            reservoir.push(Reverse(Keyed { key, item }));
+        } else if key > reservoir.peek().unwrap().0.key {
+            reservoir.pop();
+            reservoir.push(Reverse(Keyed { key, item }));
+        }
+    }
+    Ok(reservoir
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(keyed)| keyed.item)
+        .collect())
+}
+
+/// An item of a weighted reservoir ordered by its log key
+struct Keyed<T> {
+    key: f64,
+    item: T,
+}
+
+impl<T> PartialEq for Keyed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T> Eq for Keyed<T> {}
+
+impl<T> PartialOrd for Keyed<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Keyed<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.total_cmp(&other.key)
+    }
+}
+
+/// Checks `weights` and returns their cumulative sums
+fn cumulative_weights(weights: &[f64]) -> Result<Vec<f64>> {
+    if weights.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    let mut sum = 0.0;
+    let mut cumulative = Vec::with_capacity(weights.len());
+    for &w in weights {
+        if !(w.is_finite() && w >= 0.0) {
+            return Err(StatsError::ArgNotNegative("weight"));
+        }
+        sum += w;
+        cumulative.push(sum);
+    }
+    if !(sum > 0.0 && sum.is_finite()) {
+        return Err(StatsError::BadParams);
+    }
+    Ok(cumulative)
+}
+
+/// Returns the indices at which the ascending `positions` in `[0, 1]` fall
+/// on the normalized `cumulative` weights
+fn select<I: Iterator<Item = f64>>(cumulative: &[f64], positions: I) -> Vec<usize> {
+    let total = cumulative[cumulative.len() - 1];
+    // rounding may put a position at the total, which must not select the
+    // trailing particles of weight zero
+    let last = cumulative.iter().position(|&c| c == total).unwrap();
+    let mut j = 0;
+    positions
+        .map(|u| {
+            let u = u * total;
+            while j < last && cumulative[j] <= u {
+                j += 1;
+            }
+            j
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    type Resample = fn(&[f64], usize, &mut StdRng) -> Result<Vec<usize>>;
+
+    fn counts(indices: &[usize], m: usize) -> Vec<usize> {
+        let mut counts = vec![0; m];
+        indices.iter().for_each(|&i| counts[i] += 1);
+        counts
+    }
+
+    #[test]
+    fn test_resample_unbiased() {
+        let weights = [0.05, 0.0, 0.3, 0.15, 0.4, 0.1];
+        let schemes: [Resample; 4] = [multinomial_resample, stratified_resample, systematic_resample, residual_resample];
+        for (s, &resample) in schemes.iter().enumerate() {
+            let mut rng = StdRng::seed_from_u64(s as u64);
+            let mut totals = vec![0.0; weights.len()];
+            for _ in 0..2000 {
+                let indices = resample(&weights, 7, &mut rng).unwrap();
+                assert_eq!(indices.len(), 7);
+                assert!(indices.windows(2).all(|w| w[0] <= w[1]));
+                for (t, c) in totals.iter_mut().zip(counts(&indices, weights.len())) {
+                    *t += c as f64;
+                }
+            }
+            for (t, w) in totals.iter().zip(weights.iter()) {
+                assert!((t / 2000.0 - 7.0 * w).abs() < 0.1, "scheme {}", s);
+            }
+            assert_eq!(totals[1], 0.0);
+        }
+    }
+
+    #[test]
+    fn test_resample_counts() {
+        // systematic counts are the floor or ceiling of n w_i and residual
+        // counts at least the floor
+        let weights = [3.0, 1.0, 0.5, 2.5, 0.0, 3.0];
+        let total: f64 = weights.iter().sum();
+        let mut rng = StdRng::seed_from_u64(10);
+        for &n in &[1, 5, 10, 33] {
+            for _ in 0..200 {
+                let systematic = counts(&systematic_resample(&weights, n, &mut rng).unwrap(), 6);
+                let residual = counts(&residual_resample(&weights, n, &mut rng).unwrap(), 6);
+                for i in 0..6 {
+                    let expected = n as f64 * weights[i] / total;
+                    assert!(systematic[i] as f64 >= expected.floor() && systematic[i] as f64 <= expected.ceil());
+                    assert!(residual[i] as f64 >= expected.floor());
+                }
+                assert_eq!(residual.iter().sum::<usize>(), n);
+            }
+        }
+        // a single particle with all the weight is always selected
+        let mut rng = StdRng::seed_from_u64(11);
+        assert_eq!(multinomial_resample(&[0.0, 0.0, 1.0, 0.0], 4, &mut rng).unwrap(), vec![2; 4]);
+        assert_eq!(stratified_resample(&[0.0, 1e-300, 0.0], 3, &mut rng).unwrap(), vec![1; 3]);
+        assert!(systematic_resample(&[1.0], 0, &mut rng).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_effective_particles() {
+        assert_eq!(effective_particles(&[2.0]).unwrap(), 1.0);
+        assert!((effective_particles(&[1.0, 1.0, 2.0]).unwrap() - 16.0 / 6.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_weighted_reservoir() {
+        // the first selected item is drawn with probability proportional
+        // to its weight
+        let weights = [1.0, 2.0, 3.0, 4.0];
+        let mut rng = StdRng::seed_from_u64(12);
+        let mut first = [0.0; 4];
+        let mut included = [0.0f64; 4];
+        for _ in 0..20000 {
+            let sample = weighted_reservoir(weights.iter().cloned().enumerate(), 2, &mut rng).unwrap();
+            assert_eq!(sample.len(), 2);
+            assert_ne!(sample[0], sample[1]);
+            first[sample[0]] += 1.0;
+            sample.iter().for_each(|&i| included[i] += 1.0);
+        }
+        for i in 0..4 {
+            assert!((first[i] / 20000.0 - weights[i] / 10.0).abs() < 0.015);
+        }
+        // P(item 0 is included) = 0.1 + Σ_{j != 0} w_j / 10 * w_0 / (10 - w_j)
+        let p0 = 0.1 + 0.2 / 8.0 + 0.3 / 7.0 + 0.4 / 6.0;
+        assert!((included[0] / 20000.0 - p0).abs() < 0.015);
+        // tiny weights keep their relative probabilities
+        let mut tiny = 0.0f64;
+        for _ in 0..20000 {
+            let sample = weighted_reservoir(vec![("a", 1e-300), ("b", 3e-300)], 1, &mut rng).unwrap();
+            if sample[0] == "a" {
+                tiny += 1.0;
+            }
+        }
+        assert!((tiny / 20000.0 - 0.25).abs() < 0.015);
+        let sample = weighted_reservoir(vec![(1, 0.0), (2, 1.0)], 5, &mut rng).unwrap();
+        assert_eq!(sample, vec![2]);
+        assert!(weighted_reservoir(vec![(1, 1.0)], 0, &mut rng).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resample_bad_params() {
+        let mut rng = StdRng::seed_from_u64(13);
+        assert!(multinomial_resample(&[], 1, &mut rng).is_err());
+        assert!(stratified_resample(&[0.0, 0.0], 1, &mut rng).is_err());
+        assert!(systematic_resample(&[1.0, -1.0], 1, &mut rng).is_err());
+        assert!(residual_resample(&[1.0, f64::NAN], 1, &mut rng).is_err());
+        assert!(residual_resample(&[f64::MAX, f64::MAX], 1, &mut rng).is_err());
+        assert!(effective_particles(&[]).is_err());
+        assert!(weighted_reservoir(vec![(1, 1.0), (2, -1.0)], 1, &mut rng).is_err());
+        assert!(weighted_reservoir(vec![(1, f64::INFINITY)], 1, &mut rng).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{Result, StatsError};
+use nalgebra::DMatrix;
+
+/// Represents a weighted subset of scenarios selected by `reduce_scenarios`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReducedScenarios {
+    indices: Vec<usize>,
+    points: DMatrix<f64>,
+    weights: Vec<f64>,
+    distance: f64,
+}
+
+impl ReducedScenarios {
+    /// Returns the row indices of the selected scenarios in the original
+    /// sample, in the order in which they were selected
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Returns the selected scenarios with one scenario per row
+    pub fn points(&self) -> &DMatrix<f64> {
+        &self.points
+    }
+
+    /// Returns the weights of the selected scenarios, which sum to one
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the Kantorovich (Wasserstein-1) distance between the original
+    /// and the reduced weighted sample
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+}
+
+/// Reduces the weighted sample `points`, with one scenario per row, to `k`
+/// representative weighted scenarios by the forward selection algorithm of
+/// Heitsch & Römisch (2003)
+///
+/// # Remarks
+///
+/// Scenarios are selected one at a time, each time adding the scenario that
+/// minimizes the Kantorovich distance, with Euclidean ground distance,
+/// between the original sample and the selected scenarios. The weight of
+/// every scenario that is not selected is then moved to the nearest selected
+/// scenario, which is the optimal redistribution for the selected set. The
+/// algorithm needs `O(k n^2)` distance evaluations for a sample of `n`
+/// scenarios.
+///
+/// # Errors
+///
+/// Returns an error if the number of weights differs from the number of
+/// scenarios, if any weight is negative or `NaN` or all weights are zero, or
+/// if `k` is zero or greater than the number of scenarios
+///
+/// # Examples
+///
+/// ```
+/// use statrs::sampler;
+/// use nalgebra::DMatrix;
+///
+/// let points = DMatrix::from_column_slice(4, 1, &[0.0, 0.1, 10.0, 10.1]);
+/// let reduced = sampler::reduce_scenarios(&points, &[1.0; 4], 2).unwrap();
+/// assert_eq!(reduced.weights(), &[0.5, 0.5]);
+/// assert!((reduced.distance() - 0.05).abs() < 1e-12);
+/// ```
+pub fn reduce_scenarios(
+    points: &DMatrix<f64>,
+    weights: &[f64],
+    k: usize,
+) -> Result<ReducedScenarios> {
+    let n = points.nrows();
+    if weights.len() != n {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if k == 0 || k > n {
+        return Err(StatsError::BadParams);
+    }
+    if weights.iter().any(|w| w.is_nan() || *w < 0.0) {
+        return Err(StatsError::BadParams);
+    }
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 || !total.is_finite() {
+        return Err(StatsError::BadParams);
+    }
+    let weights: Vec<f64> = weights.iter().map(|w| w / total).collect();
+    let distance = |i: usize, j: usize| (points.row(i) - points.row(j)).norm();
+
+    // distance from every scenario to the nearest selected scenario
+    let mut nearest = vec![f64::INFINITY; n];
+    let mut selected = vec![false; n];
+    let mut indices = Vec::with_capacity(k);
+    for _ in 0..k {
+        let mut best: Option<(f64, usize)> = None;
+        for u in (0..n).filter(|&u| !selected[u]) {
+            let cost: f64 = (0..n)
+                .filter(|&i| weights[i] > 0.0)
+                .map(|i| weights[i] * nearest[i].min(distance(i, u)))
+                .sum();
+            match best {
+                Some((c, _)) if c <= cost => {}
+                _ => best = Some((cost, u)),
+            }
+        }
+        let u = best.unwrap().1;
+        selected[u] = true;
+        indices.push(u);
+        for (i, d) in nearest.iter_mut().enumerate() {
+            *d = d.min(distance(i, u));
+        }
+    }
+
+    // move the weight of every scenario to its nearest selected scenario
+    let mut reduced_weights = vec![0.0; k];
+    let mut kantorovich = 0.0;
+    for (i, &w) in weights.iter().enumerate() {
+        let (slot, d) = indices
+            .iter()
+            .enumerate()
+            .map(|(slot, &j)| (slot, distance(i, j)))
+            .fold((0, f64::INFINITY), |a, b| if b.1 < a.1 { b } else { a });
+        reduced_weights[slot] += w;
+        kantorovich += w * d;
+    }
+    let reduced_points = DMatrix::from_fn(k, points.ncols(), |r, c| points[(indices[r], c)]);
+    Ok(ReducedScenarios {
+        indices,
+        points: reduced_points,
+        weights: reduced_weights,
+        distance: kantorovich,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::distribution::Normal;
+
+    #[test]
+    fn test_bad_params() {
+        let points = DMatrix::from_column_slice(3, 1, &[0.0, 1.0, 2.0]);
+        assert!(reduce_scenarios(&points, &[1.0, 1.0], 1).is_err());
+        assert!(reduce_scenarios(&points, &[1.0; 3], 0).is_err());
+        assert!(reduce_scenarios(&points, &[1.0; 3], 4).is_err());
+        assert!(reduce_scenarios(&points, &[1.0, -1.0, 1.0], 1).is_err());
+        assert!(reduce_scenarios(&points, &[0.0; 3], 1).is_err());
+    }
+
+    #[test]
+    fn test_clusters() {
+        let points = DMatrix::from_row_slice(6, 2, &[
+            0.0, 0.0,
+            0.1, 0.0,
+            0.0, 0.1,
+            5.0, 5.0,
+            5.1, 5.0,
+            9.0, 0.0,
+        ]);
+        let weights = [1.0, 1.0, 1.0, 2.0, 2.0, 3.0];
+        let reduced = reduce_scenarios(&points, &weights, 3).unwrap();
+        let mut indices = reduced.indices().to_vec();
+        indices.sort_unstable();
+        assert_eq!(indices.len(), 3);
+        assert!(indices[0] < 3 && (indices[1] == 3 || indices[1] == 4) && indices[2] == 5);
+        let mut w = reduced.weights().to_vec();
+        w.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_almost_eq!(w[0], 0.3, 1e-15);
+        assert_almost_eq!(w[1], 0.3, 1e-15);
+        assert_almost_eq!(w[2], 0.4, 1e-15);
+        assert_eq!(reduced.points().nrows(), 3);
+        // the first cluster is best represented by its corner point
+        assert!(reduced.indices().contains(&0));
+        assert_almost_eq!(reduced.distance(), 0.04, 1e-15);
+    }
+
+    #[test]
+    fn test_full_and_single() {
+        let points = DMatrix::from_column_slice(4, 1, &[3.0, 1.0, 4.0, 1.5]);
+        let reduced = reduce_scenarios(&points, &[1.0; 4], 4).unwrap();
+        assert_eq!(reduced.distance(), 0.0);
+        assert!(reduced.weights().iter().all(|&w| w == 0.25));
+        // a single scenario is placed at the weighted median
+        let reduced = reduce_scenarios(&points, &[1.0, 1.0, 1.0, 3.0], 1).unwrap();
+        assert_eq!(reduced.indices(), &[3]);
+        assert_eq!(reduced.weights(), &[1.0]);
+    }
+
+    #[test]
+    fn test_distance_decreases() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let points = DMatrix::from_fn(200, 2, |_, _| normal.sample(&mut rng));
+        let weights = vec![1.0; 200];
+        let mut last = f64::INFINITY;
+        for &k in &[1, 5, 20, 50] {
+            let reduced = reduce_scenarios(&points, &weights, k).unwrap();
+            assert!(reduced.distance() < last);
+            last = reduced.distance();
+        }
+    }
+}
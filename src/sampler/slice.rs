@@ -0,0 +1,281 @@
+use crate::fp;
+use crate::{Result, StatsError};
+use rand::distributions::Open01;
+use rand::Rng;
+
+/// The default maximum number of steps taken to step out a slice
+const DEFAULT_MAX_STEPS: usize = 100;
+
+/// Implements univariate [slice
+/// sampling](https://en.wikipedia.org/wiki/Slice_sampling) from a target
+/// density known up to a constant, with the stepping-out and shrinkage
+/// procedures of Neal (2003)
+///
+/// Each step draws a level `ln y = ln f(x) - e` for a standard exponential
+/// `e`, places an interval of length `width` randomly around the current
+/// state `x`, extends it by steps of `width` until both ends lie outside the
+/// slice `{x : ln f(x) > ln y}` and then draws uniformly from the interval,
+/// shrinking it towards `x` after every draw outside the slice.
+///
+/// # Remarks
+///
+/// The chain has the target as its stationary distribution for any `width`,
+/// which only affects the number of evaluations of the target per step, so
+/// the sampler needs no tuning. At most `max_steps` steps of `width` are
+/// taken to step out, 100 by default, which bounds the cost for improper or
+/// very heavy tailed targets.
+///
+/// `step` does not keep any state, so the sampler can also update a single
+/// coordinate of a multivariate target, e.g. within a Gibbs sampler, by
+/// slicing the target as a function of that coordinate.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, Gamma};
+/// use statrs::sampler::Slice;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let gamma = Gamma::new(3.0, 2.0).unwrap();
+/// let sampler = Slice::new(|x| gamma.ln_pdf(x), 1.0).unwrap();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let mean = sampler.states(1.0, &mut rng).unwrap().take(10000).sum::<f64>() / 10000.0;
+/// assert!((mean - 1.5).abs() < 0.05);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Slice<F> {
+    target_ln_pdf: F,
+    width: f64,
+    max_steps: usize,
+}
+
+impl<F> Slice<F>
+where
+    F: Fn(f64) -> f64,
+{
+    /// Constructs a slice sampler for the target with log density
+    /// `target_ln_pdf`, known up to an additive constant, stepping out by
+    /// `width`, ideally of the order of the width of the target
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `width` is not finite and positive
+    pub fn new(target_ln_pdf: F, width: f64) -> Result<Slice<F>> {
+        if !(width.is_finite() && width > 0.0) {
+            return Err(StatsError::ArgMustBePositive("width"));
+        }
+        Ok(Slice {
+            target_ln_pdf,
+            width,
+            max_steps: DEFAULT_MAX_STEPS,
+        })
+    }
+
+    /// Returns the sampler taking at most `max_steps` steps of `width` to
+    /// step out a slice, where zero keeps the initial interval
+    pub fn with_max_steps(self, max_steps: usize) -> Slice<F> {
+        Slice { max_steps, ..self }
+    }
+
+    /// Returns the width of the steps
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    /// Returns the maximum number of steps taken to step out a slice
+    pub fn max_steps(&self) -> usize {
+        self.max_steps
+    }
+
+    /// Runs a single step of the chain from `x`, at which the log target
+    /// should be finite, and returns the next state
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::sampler::Slice;
+    ///
+    /// // one coordinate of a bivariate normal with correlation 0.5 updated
+    /// // given the other, as within a Gibbs sampler
+    /// let (mut x, y) = (0.0, 2.0);
+    /// let conditional = |x: f64| -(x * x - x * y + y * y) / 1.5;
+    /// x = Slice::new(conditional, 1.0).unwrap().step(x, &mut rand::thread_rng());
+    /// assert!(x.is_finite());
+    /// ```
+    pub fn step<R: Rng + ?Sized>(&self, x: f64, rng: &mut R) -> f64 {
+        self.advance(x, (self.target_ln_pdf)(x), rng).0
+    }
+
+    /// Returns an infinite iterator over the states of the chain started at
+    /// `initial` drawn using `rng`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log target is not finite at `initial`
+    pub fn states<R: Rng>(&self, initial: f64, rng: R) -> Result<SliceStates<'_, F, R>> {
+        let ln_f = (self.target_ln_pdf)(initial);
+        if !(initial.is_finite() && ln_f.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        Ok(SliceStates {
+            sampler: self,
+            x: initial,
+            ln_f,
+            rng,
+        })
+    }
+
+    /// Runs a single step of the chain from `x` with log target `ln_f`,
+    /// returning the next state and the log target there
+    fn advance<R: Rng + ?Sized>(&self, x: f64, ln_f: f64, rng: &mut R) -> (f64, f64) {
+        let u: f64 = rng.sample(Open01);
+        let ln_y = ln_f + fp::ln(u);
+        // step out
+        let mut left = x - self.width * rng.gen::<f64>();
+        let mut right = left + self.width;
+        let mut j = (self.max_steps as f64 * rng.gen::<f64>()) as usize;
+        let mut k = self.max_steps.saturating_sub(1).saturating_sub(j);
+        while j > 0 && (self.target_ln_pdf)(left) > ln_y {
+            left -= self.width;
+            j -= 1;
+        }
+        while k > 0 && (self.target_ln_pdf)(right) > ln_y {
+            right += self.width;
+            k -= 1;
+        }
+        // shrink
+        loop {
+            let x1 = left + (right - left) * rng.gen::<f64>();
+            let ln_f1 = (self.target_ln_pdf)(x1);
+            if ln_f1 > ln_y {
+                return (x1, ln_f1);
+            }
+            if x1 < x {
+                left = x1;
+            } else if x1 > x {
+                right = x1;
+            } else {
+                // the interval has shrunk to the current state
+                return (x, ln_f);
+            }
+        }
+    }
+}
+
+/// An infinite iterator over the states of a `Slice` chain, created by
+/// `Slice::states`
+#[derive(Debug)]
+pub struct SliceStates<'a, F, R> {
+    sampler: &'a Slice<F>,
+    x: f64,
+    ln_f: f64,
+    rng: R,
+}
+
+impl<'a, F, R> Iterator for SliceStates<'a, F, R>
+where
+    F: Fn(f64) -> f64,
+    R: Rng,
+{
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        let (x, ln_f) = self.sampler.advance(self.x, self.ln_f, &mut self.rng);
+        self.x = x;
+        self.ln_f = ln_f;
+        Some(x)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Beta, Continuous, ContinuousCDF, Normal};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::cell::Cell;
+
+    // Kolmogorov-Smirnov distance between a sample and a cdf
+    fn ks_distance<D: ContinuousCDF<f64, f64>>(mut x: Vec<f64>, dist: &D) -> f64 {
+        x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = x.len() as f64;
+        x.iter().enumerate().fold(0.0, |d, (i, &xi)| {
+            let f = dist.cdf(xi);
+            d.max((f - i as f64 / n).abs()).max(((i + 1) as f64 / n - f).abs())
+        })
+    }
+
+    #[test]
+    fn test_slice_normal() {
+        let normal = Normal::new(5.0, 2.0).unwrap();
+        // a width far too small still samples correctly
+        for &width in &[0.1, 2.0, 50.0] {
+            let sampler = Slice::new(|x| normal.ln_pdf(x), width).unwrap();
+            let mut rng = StdRng::seed_from_u64(1);
+            let x: Vec<f64> = sampler.states(0.0, &mut rng).unwrap().skip(100).step_by(5).take(4000).collect();
+            assert!(ks_distance(x, &normal) < 0.04);
+        }
+    }
+
+    #[test]
+    fn test_slice_bounded_support() {
+        let beta = Beta::new(2.0, 5.0).unwrap();
+        let sampler = Slice::new(|x| if x > 0.0 && x < 1.0 { beta.ln_pdf(x) } else { f64::NEG_INFINITY }, 0.5).unwrap();
+        let mut rng = StdRng::seed_from_u64(2);
+        let x: Vec<f64> = sampler.states(0.5, &mut rng).unwrap().step_by(3).take(4000).collect();
+        assert!(x.iter().all(|&x| x > 0.0 && x < 1.0));
+        assert!(ks_distance(x, &beta) < 0.04);
+    }
+
+    #[test]
+    fn test_slice_gibbs() {
+        // a bivariate normal with unit variances and correlation 0.8 sampled
+        // by updating one coordinate at a time
+        let rho = 0.8;
+        let ln_target = |x: f64, y: f64| -(x * x - 2.0 * rho * x * y + y * y) / (2.0 * (1.0 - rho * rho));
+        let mut rng = StdRng::seed_from_u64(3);
+        let (mut x, mut y) = (3.0, -3.0);
+        let (mut sxy, mut sxx) = (0.0, 0.0);
+        for i in 0..20000 {
+            x = Slice::new(|x| ln_target(x, y), 1.0).unwrap().step(x, &mut rng);
+            y = Slice::new(|y| ln_target(x, y), 1.0).unwrap().step(y, &mut rng);
+            if i >= 1000 {
+                sxy += x * y;
+                sxx += x * x;
+            }
+        }
+        assert!((sxy / 19000.0 - rho).abs() < 0.1);
+        assert!((sxx / 19000.0 - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_slice_max_steps() {
+        // without stepping out the chain still moves within the initial
+        // interval and keeps the target invariant
+        let evaluations = Cell::new(0);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let sampler = Slice::new(|x| { evaluations.set(evaluations.get() + 1); normal.ln_pdf(x) }, 1.0)
+            .unwrap()
+            .with_max_steps(0);
+        assert_eq!(sampler.max_steps(), 0);
+        assert_eq!(sampler.width(), 1.0);
+        let mut rng = StdRng::seed_from_u64(4);
+        let x: Vec<f64> = sampler.states(0.0, &mut rng).unwrap().take(20000).collect();
+        assert!(ks_distance(x, &normal) < 0.05);
+        // every step evaluates the target at least once in the shrinkage
+        assert!(evaluations.get() > 20000);
+    }
+
+    #[test]
+    fn test_slice_bad_params() {
+        let ln_target = |x: f64| -x.abs();
+        assert!(Slice::new(ln_target, 0.0).is_err());
+        assert!(Slice::new(ln_target, -1.0).is_err());
+        assert!(Slice::new(ln_target, f64::NAN).is_err());
+        let sampler = Slice::new(|x: f64| if x > 0.0 { 0.0 } else { f64::NEG_INFINITY }, 1.0).unwrap();
+        assert!(sampler.states(-1.0, rand::thread_rng()).is_err());
+        assert!(sampler.states(f64::INFINITY, rand::thread_rng()).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+use crate::distribution::ContinuousCDF;
+use crate::{Result, StatsError};
+use rand::distributions::Open01;
+use rand::Rng;
+
+/// Implements [antithetic
+/// sampling](https://en.wikipedia.org/wiki/Antithetic_variates) of a
+/// univariate distribution, drawing pairs `(F⁻¹(u), F⁻¹(1 - u))` for a
+/// uniform `u` so that the two halves of each pair are negatively
+/// correlated
+///
+/// # Remarks
+///
+/// Averaging a monotone function over antithetic pairs has a lower variance
+/// than averaging it over independent draws. Any distribution implementing
+/// `ContinuousCDF` can be wrapped, so the quality of the samples is that of
+/// its `inverse_cdf`. The uniforms are drawn from the open interval
+/// `(0, 1)` so that unbounded distributions never yield infinite samples.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::sampler::Antithetic;
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let sampler = Antithetic::new(Normal::new(1.0, 2.0).unwrap());
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let (x, y) = sampler.sample(&mut rng);
+/// assert!((x + y - 2.0).abs() < 1e-8);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Antithetic<D> {
+    dist: D,
+}
+
+impl<D: ContinuousCDF<f64, f64>> Antithetic<D> {
+    /// Constructs an antithetic sampler of `dist`
+    pub fn new(dist: D) -> Antithetic<D> {
+        Antithetic { dist }
+    }
+
+    /// Returns the wrapped distribution
+    pub fn dist(&self) -> &D {
+        &self.dist
+    }
+
+    /// Draws `n` samples made up of `n / 2` antithetic pairs, the two halves
+    /// of the `i`th pair being at positions `2i` and `2i + 1`, followed by
+    /// a single independent draw if `n` is odd
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Exp;
+    /// use statrs::sampler::Antithetic;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let sampler = Antithetic::new(Exp::new(1.0).unwrap());
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let x = sampler.sample_n(1000, &mut rng);
+    /// let mean = x.iter().sum::<f64>() / 1000.0;
+    /// assert!((mean - 1.0).abs() < 0.05);
+    /// ```
+    pub fn sample_n<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Vec<f64> {
+        let mut samples = Vec::with_capacity(n);
+        while samples.len() + 1 < n {
+            let u: f64 = rng.sample(Open01);
+            samples.push(self.dist.inverse_cdf(u));
+            samples.push(self.dist.inverse_cdf(1.0 - u));
+        }
+        if samples.len() < n {
+            samples.push(self.dist.inverse_cdf(rng.sample(Open01)));
+        }
+        samples
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ::rand::distributions::Distribution<(f64, f64)> for Antithetic<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (f64, f64) {
+        let u: f64 = rng.sample(Open01);
+        (self.dist.inverse_cdf(u), self.dist.inverse_cdf(1.0 - u))
+    }
+}
+
+/// Implements [stratified
+/// sampling](https://en.wikipedia.org/wiki/Stratified_sampling) of a
+/// univariate distribution, splitting the unit interval into `n` equally
+/// likely strata and drawing one sample `F⁻¹((i + u_i) / n)` from each
+///
+/// # Remarks
+///
+/// Each sample is a vector of `n` values ordered by stratum, so it is
+/// sorted for any distribution. The mean of a stratified sample has a
+/// variance no greater than that of `n` independent draws. Any distribution
+/// implementing `ContinuousCDF` can be wrapped, and the uniforms are kept
+/// inside the open interval `(0, 1)`. For stratification of several
+/// dimensions at once see `LatinHypercube`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, Gamma};
+/// use statrs::sampler::Stratified;
+/// use rand::distributions::Distribution;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let gamma = Gamma::new(2.0, 1.0).unwrap();
+/// let sampler = Stratified::new(gamma, 4).unwrap();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let x = sampler.sample(&mut rng);
+/// assert!(gamma.cdf(x[0]) < 0.25 && gamma.cdf(x[3]) > 0.75);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Stratified<D> {
+    dist: D,
+    strata: usize,
+}
+
+impl<D: ContinuousCDF<f64, f64>> Stratified<D> {
+    /// Constructs a stratified sampler of `dist` with `strata` strata
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `strata` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    /// use statrs::sampler::Stratified;
+    ///
+    /// let normal = Normal::new(0.0, 1.0).unwrap();
+    /// assert!(Stratified::new(normal, 10).is_ok());
+    /// assert!(Stratified::new(normal, 0).is_err());
+    /// ```
+    pub fn new(dist: D, strata: usize) -> Result<Stratified<D>> {
+        if strata == 0 {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(Stratified { dist, strata })
+        }
+    }
+
+    /// Returns the wrapped distribution
+    pub fn dist(&self) -> &D {
+        &self.dist
+    }
+
+    /// Returns the number of strata
+    pub fn strata(&self) -> usize {
+        self.strata
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ::rand::distributions::Distribution<Vec<f64>> for Stratified<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let n = self.strata as f64;
+        // (i + u) / n can round up to one in the top stratum
+        let upper = 1.0 - f64::EPSILON / 2.0;
+        (0..self.strata)
+            .map(|i| {
+                let u: f64 = rng.sample(Open01);
+                self.dist.inverse_cdf(((i as f64 + u) / n).min(upper))
+            })
+            .collect()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Exp, Normal, Uniform};
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_antithetic_pairs() {
+        let sampler = Antithetic::new(Uniform::new(0.0, 1.0).unwrap());
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let (x, y) = sampler.sample(&mut rng);
+            assert!(x > 0.0 && x < 1.0);
+            assert_almost_eq!(x + y, 1.0, 1e-15);
+        }
+        let x = sampler.sample_n(7, &mut rng);
+        assert_eq!(x.len(), 7);
+        for i in 0..3 {
+            assert_almost_eq!(x[2 * i] + x[2 * i + 1], 1.0, 1e-15);
+        }
+        assert!(sampler.sample_n(0, &mut rng).is_empty());
+        assert_eq!(sampler.sample_n(1, &mut rng).len(), 1);
+    }
+
+    #[test]
+    fn test_antithetic_variance_reduction() {
+        // the mean of an exponential estimated from antithetic pairs varies
+        // less than from independent draws of the same size
+        let exp = Exp::new(1.0).unwrap();
+        let sampler = Antithetic::new(exp);
+        let mut rng = StdRng::seed_from_u64(2);
+        let (reps, n) = (400, 50);
+        let spread = |means: Vec<f64>| {
+            let m = means.iter().sum::<f64>() / means.len() as f64;
+            means.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (means.len() - 1) as f64
+        };
+        let antithetic: Vec<f64> = (0..reps).map(|_| sampler.sample_n(n, &mut rng).iter().sum::<f64>() / n as f64).collect();
+        let independent: Vec<f64> = (0..reps).map(|_| (0..n).map(|_| exp.sample(&mut rng)).sum::<f64>() / n as f64).collect();
+        assert!(spread(antithetic) < 0.5 * spread(independent));
+    }
+
+    #[test]
+    fn test_stratified() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let sampler = Stratified::new(normal, 8).unwrap();
+        assert_eq!(sampler.strata(), 8);
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..100 {
+            let x = sampler.sample(&mut rng);
+            assert_eq!(x.len(), 8);
+            for (i, &xi) in x.iter().enumerate() {
+                let p = normal.cdf(xi);
+                assert!(p >= i as f64 / 8.0 - 1e-12 && p <= (i + 1) as f64 / 8.0 + 1e-12);
+                assert!(xi.is_finite());
+            }
+        }
+        // a single stratum is plain inverse transform sampling
+        let x = Stratified::new(normal, 1).unwrap().sample(&mut rng);
+        assert_eq!(x.len(), 1);
+        assert!(Stratified::new(normal, 0).is_err());
+    }
+}
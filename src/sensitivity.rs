@@ -0,0 +1,358 @@
+//! Provides variance-based global sensitivity analysis, estimating the
+//! [Sobol' indices](https://en.wikipedia.org/wiki/Variance-based_sensitivity_analysis)
+//! of a model whose inputs are independent random variables described by
+//! statrs distributions
+
+use crate::distribution::ContinuousCDF;
+use crate::fp;
+use crate::sampler;
+use crate::statistics::{Data, OrderStatistics};
+use crate::{Result, StatsError};
+use nalgebra::DMatrix;
+use rand::Rng;
+
+/// Implements the sampling design of Saltelli (2002) for estimating the
+/// first-order and total-effect Sobol' indices of a model with `d` inputs.
+///
+/// Two independent `n x d` samples `A` and `B` of the inputs are drawn, and
+/// for every input `i` the matrix `AB_i` is formed from `A` with its `i`th
+/// column taken from `B`. The model has to be evaluated at all `n * (d + 2)`
+/// rows of `points`, which are stored in the order `A`, `B`, `AB_1`, ...,
+/// `AB_d`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Uniform;
+/// use statrs::sensitivity::SaltelliSample;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let u = Uniform::new(0.0, 1.0).unwrap();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let sample = SaltelliSample::new(2000, &[&u, &u], &mut rng).unwrap();
+/// // the second input does not affect the model
+/// let outputs = sample.evaluate(|x| x[0]);
+/// let indices = sample.analyze(&outputs).unwrap();
+/// assert!((indices.first_order()[0] - 1.0).abs() < 0.05);
+/// assert!(indices.total_effect()[1].abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaltelliSample {
+    n: usize,
+    d: usize,
+    points: DMatrix<f64>,
+}
+
+impl SaltelliSample {
+    /// Draws a Saltelli design of `n` base samples for independent inputs
+    /// with the distributions `marginals`, which are sampled by inversion
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is less than two or if `marginals` is empty
+    pub fn new<R: Rng + ?Sized>(
+        n: usize,
+        marginals: &[&dyn ContinuousCDF<f64, f64>],
+        rng: &mut R,
+    ) -> Result<SaltelliSample> {
+        let d = marginals.len();
+        if n < 2 || d == 0 {
+            return Err(StatsError::BadParams);
+        }
+        let uniform = DMatrix::from_fn(n, 2 * d, |_, _| rng.gen::<f64>());
+        let a = sampler::map_marginals(&uniform.columns(0, d).into_owned(), marginals)?;
+        let b = sampler::map_marginals(&uniform.columns(d, d).into_owned(), marginals)?;
+        let mut points = DMatrix::zeros(n * (d + 2), d);
+        points.rows_mut(0, n).copy_from(&a);
+        points.rows_mut(n, n).copy_from(&b);
+        for i in 0..d {
+            let mut ab = a.clone();
+            ab.column_mut(i).copy_from(&b.column(i));
+            points.rows_mut(n * (i + 2), n).copy_from(&ab);
+        }
+        Ok(SaltelliSample { n, d, points })
+    }
+
+    /// Returns the number of base samples `n`
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the number of model inputs `d`
+    pub fn dim(&self) -> usize {
+        self.d
+    }
+
+    /// Returns the `n * (d + 2)` input points at which the model has to be
+    /// evaluated, one per row
+    pub fn points(&self) -> &DMatrix<f64> {
+        &self.points
+    }
+
+    /// Evaluates `model` at every row of `points`, returning the outputs in
+    /// the order expected by `analyze`
+    pub fn evaluate<F>(&self, mut model: F) -> Vec<f64>
+    where
+        F: FnMut(&[f64]) -> f64,
+    {
+        let mut x = vec![0.0; self.d];
+        self.points
+            .row_iter()
+            .map(|row| {
+                for (xi, r) in x.iter_mut().zip(row.iter()) {
+                    *xi = *r;
+                }
+                model(&x)
+            })
+            .collect()
+    }
+
+    /// Estimates the Sobol' indices from the model `outputs` at `points`
+    ///
+    /// # Remarks
+    ///
+    /// The first-order indices use the estimator of Saltelli et al. (2010)
+    /// and the total-effect indices the estimator of Jansen (1999), both
+    /// normalized by the variance of the outputs at `A` and `B`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of outputs differs from the number of
+    /// points or if the outputs at `A` and `B` have zero variance
+    pub fn analyze(&self, outputs: &[f64]) -> Result<SobolIndices> {
+        self.check_outputs(outputs)?;
+        let rows: Vec<usize> = (0..self.n).collect();
+        let (first_order, total_effect) = self.estimate(outputs, &rows)?;
+        Ok(SobolIndices {
+            first_order,
+            total_effect,
+            first_order_interval: None,
+            total_effect_interval: None,
+        })
+    }
+
+    /// Estimates the Sobol' indices from the model `outputs` at `points`
+    /// together with percentile bootstrap confidence intervals at level
+    /// `confidence`, resampling the rows of the design `resamples` times
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as `analyze`, if
+    /// `resamples` is zero or if `confidence` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    /// use statrs::sensitivity::SaltelliSample;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let sample = SaltelliSample::new(1000, &[&n, &n], &mut rng).unwrap();
+    /// let outputs = sample.evaluate(|x| x[0] + 2.0 * x[1]);
+    /// let indices = sample.analyze_bootstrap(&outputs, 200, 0.95, &mut rng).unwrap();
+    /// let (low, high) = indices.total_effect_interval().unwrap()[1];
+    /// assert!(low < 0.8 && 0.8 < high);
+    /// ```
+    pub fn analyze_bootstrap<R: Rng + ?Sized>(
+        &self,
+        outputs: &[f64],
+        resamples: usize,
+        confidence: f64,
+        rng: &mut R,
+    ) -> Result<SobolIndices> {
+        if resamples == 0 {
+            return Err(StatsError::BadParams);
+        }
+        if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+        }
+        let mut indices = self.analyze(outputs)?;
+        let mut first: Vec<Vec<f64>> = vec![Vec::with_capacity(resamples); self.d];
+        let mut total: Vec<Vec<f64>> = vec![Vec::with_capacity(resamples); self.d];
+        let mut rows = vec![0; self.n];
+        for _ in 0..resamples {
+            for r in rows.iter_mut() {
+                *r = rng.gen_range(0..self.n);
+            }
+            // a resample with constant outputs carries no information
+            if let Ok((s, st)) = self.estimate(outputs, &rows) {
+                for i in 0..self.d {
+                    first[i].push(s[i]);
+                    total[i].push(st[i]);
+                }
+            }
+        }
+        let alpha = 0.5 * (1.0 - confidence);
+        let interval = |values: Vec<f64>| {
+            let mut data = Data::new(values);
+            (data.quantile(alpha), data.quantile(1.0 - alpha))
+        };
+        indices.first_order_interval = Some(first.into_iter().map(interval).collect());
+        indices.total_effect_interval = Some(total.into_iter().map(interval).collect());
+        Ok(indices)
+    }
+
+    fn check_outputs(&self, outputs: &[f64]) -> Result<()> {
+        if outputs.len() != self.points.nrows() {
+            Err(StatsError::ContainersMustBeSameLength)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the first-order and total-effect estimates using the base
+    /// sample rows `rows`
+    fn estimate(&self, outputs: &[f64], rows: &[usize]) -> Result<(Vec<f64>, Vec<f64>)> {
+        let n = self.n;
+        let m = rows.len() as f64;
+        let fa = |j: usize| outputs[j];
+        let fb = |j: usize| outputs[n + j];
+        let mean = rows.iter().map(|&j| fa(j) + fb(j)).sum::<f64>() / (2.0 * m);
+        let var = rows
+            .iter()
+            .map(|&j| fp::powi(fa(j) - mean, 2) + fp::powi(fb(j) - mean, 2))
+            .sum::<f64>()
+            / (2.0 * m - 1.0);
+        if var <= 0.0 || !var.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        let mut first_order = Vec::with_capacity(self.d);
+        let mut total_effect = Vec::with_capacity(self.d);
+        for i in 0..self.d {
+            let fab = |j: usize| outputs[n * (i + 2) + j];
+            let s = rows.iter().map(|&j| fb(j) * (fab(j) - fa(j))).sum::<f64>() / m;
+            let st = rows
+                .iter()
+                .map(|&j| fp::powi(fa(j) - fab(j), 2))
+                .sum::<f64>()
+                / (2.0 * m);
+            first_order.push(s / var);
+            total_effect.push(st / var);
+        }
+        Ok((first_order, total_effect))
+    }
+}
+
+/// The first-order and total-effect Sobol' indices of every model input,
+/// optionally with bootstrap confidence intervals, as estimated by
+/// `SaltelliSample::analyze` or `SaltelliSample::analyze_bootstrap`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SobolIndices {
+    first_order: Vec<f64>,
+    total_effect: Vec<f64>,
+    first_order_interval: Option<Vec<(f64, f64)>>,
+    total_effect_interval: Option<Vec<(f64, f64)>>,
+}
+
+impl SobolIndices {
+    /// Returns the first-order indices, i.e. the fraction of the output
+    /// variance explained by each input alone
+    pub fn first_order(&self) -> &[f64] {
+        &self.first_order
+    }
+
+    /// Returns the total-effect indices, i.e. the fraction of the output
+    /// variance explained by each input including all its interactions
+    pub fn total_effect(&self) -> &[f64] {
+        &self.total_effect
+    }
+
+    /// Returns the bootstrap confidence intervals of the first-order
+    /// indices, if they were computed
+    pub fn first_order_interval(&self) -> Option<&[(f64, f64)]> {
+        self.first_order_interval.as_deref()
+    }
+
+    /// Returns the bootstrap confidence intervals of the total-effect
+    /// indices, if they were computed
+    pub fn total_effect_interval(&self) -> Option<&[(f64, f64)]> {
+        self.total_effect_interval.as_deref()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Normal, Uniform};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::f64::consts::PI;
+
+    fn ishigami(x: &[f64]) -> f64 {
+        x[0].sin() + 7.0 * x[1].sin().powi(2) + 0.1 * x[2].powi(4) * x[0].sin()
+    }
+
+    #[test]
+    fn test_bad_params() {
+        let u = Uniform::new(0.0, 1.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(SaltelliSample::new(1, &[&u], &mut rng).is_err());
+        assert!(SaltelliSample::new(10, &[], &mut rng).is_err());
+        let sample = SaltelliSample::new(10, &[&u, &u], &mut rng).unwrap();
+        assert_eq!(sample.points().shape(), (40, 2));
+        assert!(sample.analyze(&[0.0; 39]).is_err());
+        assert!(sample.analyze(&[1.0; 40]).is_err());
+        let outputs = sample.evaluate(|x| x[0]);
+        assert!(sample.analyze_bootstrap(&outputs, 0, 0.9, &mut rng).is_err());
+        assert!(sample.analyze_bootstrap(&outputs, 10, 1.0, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_design() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        let sample = SaltelliSample::new(5, &[&n, &n, &n], &mut rng).unwrap();
+        let p = sample.points();
+        for i in 0..3 {
+            for j in 0..5 {
+                for k in 0..3 {
+                    let expected = if k == i { p[(5 + j, k)] } else { p[(j, k)] };
+                    assert_eq!(p[(5 * (i + 2) + j, k)], expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ishigami() {
+        let u = Uniform::new(-PI, PI).unwrap();
+        let mut rng = StdRng::seed_from_u64(2);
+        let sample = SaltelliSample::new(20_000, &[&u, &u, &u], &mut rng).unwrap();
+        let indices = sample.analyze(&sample.evaluate(ishigami)).unwrap();
+        // analytical values for a = 7 and b = 0.1
+        let (a, b) = (7.0, 0.1);
+        let pi4 = PI.powi(4);
+        let v1 = 0.5 * (1.0 + b * pi4 / 5.0).powi(2);
+        let v2 = a * a / 8.0;
+        let v13 = b * b * pi4 * pi4 * (1.0 / 18.0 - 1.0 / 50.0);
+        let v = v1 + v2 + v13;
+        let first = [v1 / v, v2 / v, 0.0];
+        let total = [(v1 + v13) / v, v2 / v, v13 / v];
+        for i in 0..3 {
+            assert_almost_eq!(indices.first_order()[i], first[i], 0.03);
+            assert_almost_eq!(indices.total_effect()[i], total[i], 0.03);
+        }
+        assert!(indices.first_order_interval().is_none());
+    }
+
+    #[test]
+    fn test_bootstrap_interval() {
+        let u = Uniform::new(-PI, PI).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let sample = SaltelliSample::new(2000, &[&u, &u, &u], &mut rng).unwrap();
+        let outputs = sample.evaluate(ishigami);
+        let indices = sample.analyze_bootstrap(&outputs, 300, 0.9, &mut rng).unwrap();
+        let first = indices.first_order_interval().unwrap();
+        let total = indices.total_effect_interval().unwrap();
+        for i in 0..3 {
+            assert!(first[i].0 <= indices.first_order()[i] && indices.first_order()[i] <= first[i].1);
+            assert!(total[i].0 <= indices.total_effect()[i] && indices.total_effect()[i] <= total[i].1);
+            assert!(total[i].1 - total[i].0 < 0.2);
+        }
+    }
+}
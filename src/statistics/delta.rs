@@ -0,0 +1,266 @@
+use crate::distribution::{ContinuousCDF, Normal};
+use crate::fp;
+use crate::{Result, StatsError};
+use nalgebra::{DMatrix, DVector};
+
+/// Represents estimated parameters together with their estimated
+/// covariance, as produced by fitting a model to data
+///
+/// # Examples
+///
+/// ```
+/// use nalgebra::{DMatrix, DVector};
+/// use statrs::statistics::ParameterFit;
+///
+/// let fit = ParameterFit::new(
+///     DVector::from_vec(vec![1.0, 2.0]),
+///     DMatrix::from_row_slice(2, 2, &[0.04, 0.0, 0.0, 0.09]),
+/// )
+/// .unwrap();
+/// assert_eq!(fit.std_errors().as_slice(), &[0.2, 0.3]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterFit {
+    estimates: DVector<f64>,
+    covariance: DMatrix<f64>,
+}
+
+impl ParameterFit {
+    /// Constructs a fit from the parameter `estimates` and their estimated
+    /// `covariance`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `estimates` is empty, if `covariance` is not a
+    /// square matrix of matching dimension, if any entry is not finite, or if
+    /// `covariance` is not symmetric with a non-negative diagonal
+    pub fn new(estimates: DVector<f64>, covariance: DMatrix<f64>) -> Result<ParameterFit> {
+        let n = estimates.len();
+        if n == 0 || covariance.nrows() != n || covariance.ncols() != n {
+            return Err(StatsError::BadParams);
+        }
+        if estimates
+            .iter()
+            .chain(covariance.iter())
+            .any(|x| !x.is_finite())
+        {
+            return Err(StatsError::BadParams);
+        }
+        for i in 0..n {
+            if covariance[(i, i)] < 0.0 {
+                return Err(StatsError::BadParams);
+            }
+            for j in 0..i {
+                let (a, b) = (covariance[(i, j)], covariance[(j, i)]);
+                if (a - b).abs() > 1e-12 * (1.0 + a.abs().max(b.abs())) {
+                    return Err(StatsError::BadParams);
+                }
+            }
+        }
+        Ok(ParameterFit {
+            estimates,
+            covariance,
+        })
+    }
+
+    /// Returns the parameter estimates
+    pub fn estimates(&self) -> &DVector<f64> {
+        &self.estimates
+    }
+
+    /// Returns the estimated covariance of the parameter estimates
+    pub fn covariance(&self) -> &DMatrix<f64> {
+        &self.covariance
+    }
+
+    /// Returns the standard errors of the parameter estimates, the square
+    /// roots of the diagonal of the covariance
+    pub fn std_errors(&self) -> DVector<f64> {
+        self.covariance.diagonal().map(f64::sqrt)
+    }
+}
+
+/// The result of `delta_method`, a derived quantity together with its
+/// standard error propagated from the parameter covariance
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeltaEstimate {
+    estimate: f64,
+    std_error: f64,
+    gradient: DVector<f64>,
+}
+
+impl DeltaEstimate {
+    /// Returns the derived quantity evaluated at the parameter estimates
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the delta method standard error of the derived quantity
+    pub fn std_error(&self) -> f64 {
+        self.std_error
+    }
+
+    /// Returns the numerical gradient of the transform at the parameter
+    /// estimates
+    pub fn gradient(&self) -> &DVector<f64> {
+        &self.gradient
+    }
+
+    /// Returns the Wald confidence interval of the derived quantity at level
+    /// `confidence`, based on its asymptotic normality
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `confidence` is not in `(0, 1)`
+    pub fn confidence_interval(&self, confidence: f64) -> Result<(f64, f64)> {
+        if confidence <= 0.0 || confidence >= 1.0 || confidence.is_nan() {
+            return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+        }
+        let z = Normal::new(0.0, 1.0)
+            .unwrap()
+            .inverse_cdf(0.5 + 0.5 * confidence);
+        Ok((
+            self.estimate - z * self.std_error,
+            self.estimate + z * self.std_error,
+        ))
+    }
+}
+
+/// Propagates the parameter covariance of `fit` through `transform` with the
+/// [delta method](https://en.wikipedia.org/wiki/Delta_method), giving the
+/// standard error of a derived quantity such as a quantile or an exceedance
+/// probability of the fitted distribution
+///
+/// # Formula
+///
+/// ```ignore
+/// Var(g(θ)) ≈ ∇g(θ)' Σ ∇g(θ)
+/// ```
+///
+/// where `θ` are the estimates, `Σ` their covariance and `∇g` the gradient
+/// of the transform, approximated by central differences with a step of
+/// `ε^(1/3) max(|θ_i|, 1)` in each parameter.
+///
+/// # Remarks
+///
+/// The transform is only evaluated in a small neighbourhood of the
+/// estimates, but it must be finite there, so estimates on the boundary of
+/// the parameter space should be reparameterized first, e.g. a scale by its
+/// logarithm.
+///
+/// # Errors
+///
+/// Returns an error if the transform is not finite at or near the estimates
+///
+/// # Examples
+///
+/// ```
+/// use nalgebra::{DMatrix, DVector};
+/// use statrs::distribution::{ContinuousCDF, Normal};
+/// use statrs::statistics::{delta_method, ParameterFit};
+///
+/// // the mean and standard deviation of a normal fitted to 100 points
+/// let fit = ParameterFit::new(
+///     DVector::from_vec(vec![10.0, 2.0]),
+///     DMatrix::from_row_slice(2, 2, &[0.04, 0.0, 0.0, 0.02]),
+/// )
+/// .unwrap();
+/// let q = delta_method(&fit, |p| Normal::new(p[0], p[1]).unwrap().inverse_cdf(0.99)).unwrap();
+/// let (lower, upper) = q.confidence_interval(0.95).unwrap();
+/// assert!(lower < q.estimate() && q.estimate() < upper);
+/// ```
+pub fn delta_method<F>(fit: &ParameterFit, transform: F) -> Result<DeltaEstimate>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let mut theta = fit.estimates.as_slice().to_vec();
+    let estimate = transform(&theta);
+    if !estimate.is_finite() {
+        return Err(StatsError::BadParams);
+    }
+    let mut gradient = DVector::zeros(theta.len());
+    for i in 0..theta.len() {
+        let x = theta[i];
+        let h = fp::cbrt(f64::EPSILON) * x.abs().max(1.0);
+        theta[i] = x + h;
+        let upper = transform(&theta);
+        theta[i] = x - h;
+        let lower = transform(&theta);
+        theta[i] = x;
+        if !upper.is_finite() || !lower.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        gradient[i] = (upper - lower) / (2.0 * h);
+    }
+    let variance = (gradient.transpose() * &fit.covariance * &gradient)[(0, 0)];
+    Ok(DeltaEstimate {
+        estimate,
+        std_error: variance.max(0.0).sqrt(),
+        gradient,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{ContinuousCDF, Exp};
+
+    fn fit(estimates: &[f64], covariance: &[f64]) -> ParameterFit {
+        let n = estimates.len();
+        ParameterFit::new(
+            DVector::from_column_slice(estimates),
+            DMatrix::from_row_slice(n, n, covariance),
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_parameter_fit() {
+        let f = fit(&[1.0, 2.0], &[4.0, 1.0, 1.0, 9.0]);
+        assert_eq!(f.std_errors().as_slice(), &[2.0, 3.0]);
+        let v = DVector::from_vec(vec![1.0, 2.0]);
+        assert!(ParameterFit::new(DVector::zeros(0), DMatrix::zeros(0, 0)).is_err());
+        assert!(ParameterFit::new(v.clone(), DMatrix::identity(3, 3)).is_err());
+        assert!(ParameterFit::new(v.clone(), DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.0, 1.0])).is_err());
+        assert!(ParameterFit::new(v.clone(), DMatrix::from_row_slice(2, 2, &[-1.0, 0.0, 0.0, 1.0])).is_err());
+        assert!(ParameterFit::new(DVector::from_vec(vec![f64::NAN, 1.0]), DMatrix::identity(2, 2)).is_err());
+    }
+
+    #[test]
+    fn test_delta_method_linear() {
+        // linear transforms are propagated exactly
+        let f = fit(&[1.0, 2.0], &[4.0, 1.0, 1.0, 9.0]);
+        let d = delta_method(&f, |p| 3.0 * p[0] - p[1]).unwrap();
+        assert_almost_eq!(d.estimate(), 1.0, 1e-15);
+        assert_almost_eq!(d.gradient()[0], 3.0, 1e-9);
+        assert_almost_eq!(d.gradient()[1], -1.0, 1e-9);
+        // 9 * 4 + 9 - 2 * 3 * 1
+        assert_almost_eq!(d.std_error(), 39.0f64.sqrt(), 1e-8);
+        let (lower, upper) = d.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(upper - d.estimate(), 1.959963984540054 * 39.0f64.sqrt(), 1e-7);
+        assert_almost_eq!(d.estimate() - lower, upper - d.estimate(), 1e-12);
+        assert!(d.confidence_interval(1.0).is_err());
+        assert!(d.confidence_interval(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_delta_method_exponential_quantile() {
+        // the p quantile of an exponential is -ln(1 - p) / λ, so its standard
+        // error is -ln(1 - p) / λ^2 times that of λ
+        let (rate, se) = (0.5, 0.05);
+        let f = fit(&[rate], &[se * se]);
+        let d = delta_method(&f, |p| Exp::new(p[0]).unwrap().inverse_cdf(0.9)).unwrap();
+        assert_almost_eq!(d.estimate(), 10.0f64.ln() / rate, 1e-12);
+        assert_almost_eq!(d.std_error(), 10.0f64.ln() / (rate * rate) * se, 1e-8);
+        // and the exceedance probability exp(-λ x) has gradient -x exp(-λ x)
+        let d = delta_method(&f, |p| Exp::new(p[0]).unwrap().sf(3.0)).unwrap();
+        assert_almost_eq!(d.std_error(), 3.0 * (-1.5f64).exp() * se, 1e-9);
+    }
+
+    #[test]
+    fn test_delta_method_not_finite() {
+        let f = fit(&[0.0], &[1.0]);
+        assert!(delta_method(&f, |p| p[0].ln()).is_err());
+        assert!(delta_method(&f, |p| if p[0] == 0.0 { 1.0 } else { f64::NAN }).is_err());
+    }
+}
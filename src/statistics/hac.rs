@@ -0,0 +1,292 @@
+use crate::fp;
+use crate::{Result, StatsError};
+use nalgebra::{DMatrix, DVector};
+
+/// Returns the automatic lag truncation `floor(4 * (n / 100)^(2 / 9))` of
+/// Newey and West (1994) for a series of length `n`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::newey_west_lags;
+///
+/// assert_eq!(newey_west_lags(100), 4);
+/// assert_eq!(newey_west_lags(1000), 6);
+/// ```
+pub fn newey_west_lags(n: usize) -> usize {
+    (4.0 * fp::powf(n as f64 / 100.0, 2.0 / 9.0)).floor() as usize
+}
+
+/// Estimates the long-run variance `σ^2_LR = Σ_l γ_l` of a stationary
+/// series, i.e. `n` times the variance of its sample mean, with the
+/// Newey-West estimator using Bartlett weights up to lag `lags`
+///
+/// # Formula
+///
+/// ```ignore
+/// σ^2_LR = γ_0 + 2 * Σ_{l = 1}^{L} (1 - l / (L + 1)) * γ_l
+/// ```
+///
+/// where `γ_l = 1/n * Σ_t (x_t - x̄)(x_{t-l} - x̄)` is the sample
+/// autocovariance at lag `l`. The weights guarantee a non-negative result.
+///
+/// # Errors
+///
+/// Returns an error if `x` has fewer than two elements, if `lags` is not
+/// smaller than the length of `x` or if any element is not finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::long_run_variance;
+///
+/// let x = [1.0, 2.0, 4.0, 3.0, 5.0];
+/// // without lags this is the biased sample variance
+/// assert!((long_run_variance(&x, 0).unwrap() - 2.0).abs() < 1e-15);
+/// assert!((long_run_variance(&x, 1).unwrap() - 2.2).abs() < 1e-15);
+/// ```
+pub fn long_run_variance(x: &[f64], lags: usize) -> Result<f64> {
+    if x.len() < 2 || lags >= x.len() || x.iter().any(|v| !v.is_finite()) {
+        return Err(StatsError::BadParams);
+    }
+    let n = x.len() as f64;
+    let mean = x.iter().sum::<f64>() / n;
+    let centered: Vec<f64> = x.iter().map(|v| v - mean).collect();
+    let gamma = |l: usize| {
+        centered
+            .iter()
+            .zip(centered[l..].iter())
+            .map(|(a, b)| a * b)
+            .sum::<f64>()
+            / n
+    };
+    Ok((1..=lags).fold(gamma(0), |acc, l| acc + 2.0 * bartlett(l, lags) * gamma(l)))
+}
+
+/// Returns the Newey-West standard error of the mean of the series `x`,
+/// i.e. `sqrt(σ^2_LR / n)` where `σ^2_LR` is the long-run variance estimated
+/// by `long_run_variance` with Bartlett weights up to lag `lags`
+///
+/// # Errors
+///
+/// Returns an error if `x` has fewer than two elements, if `lags` is not
+/// smaller than the length of `x` or if any element is not finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::newey_west_se;
+///
+/// let x = [1.0, 2.0, 4.0, 3.0, 5.0];
+/// assert!((newey_west_se(&x, 1).unwrap() - 0.44f64.sqrt()).abs() < 1e-15);
+/// ```
+pub fn newey_west_se(x: &[f64], lags: usize) -> Result<f64> {
+    Ok((long_run_variance(x, lags)? / x.len() as f64).sqrt())
+}
+
+/// Represents an ordinary least squares fit `y = X β + u` together with the
+/// heteroskedasticity and autocorrelation consistent (HAC) covariance of the
+/// coefficients estimated by Newey and West (1987)
+///
+/// # Formula
+///
+/// ```ignore
+/// Cov(β) = (X'X)^-1 S (X'X)^-1
+/// S = Σ_t u_t^2 x_t x_t' + Σ_{l = 1}^{L} w_l Σ_{t > l} u_t u_{t-l} (x_t x_{t-l}' + x_{t-l} x_t')
+/// ```
+///
+/// where `x_t` is the `t`th row of `X`, `u_t` the `t`th residual and
+/// `w_l = 1 - l / (L + 1)` the Bartlett weights. With `L = 0` this is the
+/// White heteroskedasticity-robust covariance. No small-sample degrees of
+/// freedom correction is applied.
+///
+/// # Examples
+///
+/// ```
+/// use nalgebra::DMatrix;
+/// use statrs::statistics::HacRegression;
+///
+/// // intercept and slope
+/// let x = DMatrix::from_row_slice(5, 2, &[1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0, 1.0, 4.0]);
+/// let y = [1.1, 2.9, 5.2, 6.8, 9.1];
+/// let fit = HacRegression::new(&x, &y, 1).unwrap();
+/// assert!((fit.coefficients()[1] - 1.99).abs() < 1e-12);
+/// assert_eq!(fit.std_errors().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HacRegression {
+    coefficients: DVector<f64>,
+    covariance: DMatrix<f64>,
+    residuals: DVector<f64>,
+    lags: usize,
+}
+
+impl HacRegression {
+    /// Fits `y` on the columns of the design matrix `x` by ordinary least
+    /// squares and estimates the Newey-West covariance of the coefficients
+    /// with Bartlett weights up to lag `lags`. An intercept has to be
+    /// included as a column of ones in `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of rows of `x` and the length of `y`
+    /// differ, if there are not more observations than columns, if `lags`
+    /// is not smaller than the number of observations, if any value is not
+    /// finite or if the columns of `x` are linearly dependent
+    pub fn new(x: &DMatrix<f64>, y: &[f64], lags: usize) -> Result<HacRegression> {
+        let (n, k) = x.shape();
+        if n != y.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        if k == 0 || n <= k || lags >= n || x.iter().chain(y.iter()).any(|v| !v.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        let y = DVector::from_column_slice(y);
+        let xtx_inv = (x.transpose() * x)
+            .cholesky()
+            .ok_or(StatsError::SpecialCase(
+                "design matrix columns are linearly dependent",
+            ))?
+            .inverse();
+        let coefficients = &xtx_inv * x.transpose() * &y;
+        let residuals = &y - x * &coefficients;
+
+        // scores x_t u_t as rows
+        let mut scores = x.clone();
+        for (mut row, u) in scores.row_iter_mut().zip(residuals.iter()) {
+            row *= *u;
+        }
+        let mut meat = scores.transpose() * &scores;
+        for l in 1..=lags {
+            let lagged = scores.rows(0, n - l).transpose() * scores.rows(l, n - l);
+            meat += (&lagged + lagged.transpose()) * bartlett(l, lags);
+        }
+        let covariance = &xtx_inv * meat * &xtx_inv;
+        Ok(HacRegression {
+            coefficients,
+            covariance,
+            residuals,
+            lags,
+        })
+    }
+
+    /// Returns the least squares coefficients `β`
+    pub fn coefficients(&self) -> &DVector<f64> {
+        &self.coefficients
+    }
+
+    /// Returns the HAC covariance matrix of the coefficients
+    pub fn covariance(&self) -> &DMatrix<f64> {
+        &self.covariance
+    }
+
+    /// Returns the HAC standard errors of the coefficients, i.e. the square
+    /// roots of the diagonal of the covariance matrix
+    pub fn std_errors(&self) -> DVector<f64> {
+        self.covariance.diagonal().map(f64::sqrt)
+    }
+
+    /// Returns the residuals `y - X β`
+    pub fn residuals(&self) -> &DVector<f64> {
+        &self.residuals
+    }
+
+    /// Returns the lag truncation of the covariance estimate
+    pub fn lags(&self) -> usize {
+        self.lags
+    }
+}
+
+/// Returns the Bartlett weight `1 - l / (L + 1)` of lag `l`
+fn bartlett(l: usize, lags: usize) -> f64 {
+    1.0 - l as f64 / (lags + 1) as f64
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_newey_west_lags() {
+        assert_eq!(newey_west_lags(0), 0);
+        assert_eq!(newey_west_lags(50), 3);
+        assert_eq!(newey_west_lags(100), 4);
+        assert_eq!(newey_west_lags(1000), 6);
+    }
+
+    #[test]
+    fn test_long_run_variance() {
+        let x = [1.0, 2.0, 4.0, 3.0, 5.0];
+        assert_almost_eq!(long_run_variance(&x, 0).unwrap(), 2.0, 1e-15);
+        // γ_1 = 0.2, γ_2 = 0, γ_3 = -0.4
+        assert_almost_eq!(long_run_variance(&x, 2).unwrap(), 2.0 + 2.0 * 2.0 / 3.0 * 0.2, 1e-15);
+        assert_almost_eq!(long_run_variance(&x, 3).unwrap(), 2.0 + 2.0 * (0.75 * 0.2 - 0.25 * 0.4), 1e-15);
+        assert_almost_eq!(newey_west_se(&x, 0).unwrap(), 0.4f64.sqrt(), 1e-15);
+    }
+
+    #[test]
+    fn test_long_run_variance_ar1() {
+        // the long-run variance of x_t = φ x_{t-1} + e_t is 1 / (1 - φ)^2
+        let mut rng = StdRng::seed_from_u64(0);
+        let noise = Normal::new(0.0, 1.0).unwrap();
+        let mut x = 0.0;
+        let series: Vec<f64> = (0..100_000)
+            .map(|_| {
+                x = 0.5 * x + noise.sample(&mut rng);
+                x
+            })
+            .collect();
+        let lrv = long_run_variance(&series, 50).unwrap();
+        assert!((lrv - 4.0).abs() < 0.3, "{}", lrv);
+        // ignoring the autocorrelation understates it
+        assert!(long_run_variance(&series, 0).unwrap() < 1.5);
+    }
+
+    #[test]
+    fn test_regression_on_constant_matches_mean() {
+        let y = [1.0, 2.0, 4.0, 3.0, 5.0];
+        let fit = HacRegression::new(&DMatrix::from_element(5, 1, 1.0), &y, 2).unwrap();
+        assert_almost_eq!(fit.coefficients()[0], 3.0, 1e-15);
+        assert_almost_eq!(fit.std_errors()[0], newey_west_se(&y, 2).unwrap(), 1e-15);
+        assert_eq!(fit.lags(), 2);
+    }
+
+    #[test]
+    fn test_regression() {
+        let x = DMatrix::from_row_slice(5, 2, &[1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0, 1.0, 4.0]);
+        let y = [1.1, 2.9, 5.2, 6.8, 9.1];
+        let fit = HacRegression::new(&x, &y, 1).unwrap();
+        assert_almost_eq!(fit.coefficients()[0], 1.04, 1e-12);
+        assert_almost_eq!(fit.coefficients()[1], 1.99, 1e-12);
+        let residuals = [0.06, -0.13, 0.18, -0.21, 0.1];
+        for (r, e) in fit.residuals().iter().zip(residuals.iter()) {
+            assert_almost_eq!(*r, *e, 1e-12);
+        }
+        // reference values computed with exact rational arithmetic
+        let cov = fit.covariance();
+        assert_almost_eq!(cov[(0, 0)], 0.001952, 1e-12);
+        assert_almost_eq!(cov[(0, 1)], -0.000896, 1e-12);
+        assert_almost_eq!(cov[(1, 0)], cov[(0, 1)], 1e-15);
+        assert_almost_eq!(cov[(1, 1)], 0.000578, 1e-12);
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let x = DMatrix::from_element(5, 1, 1.0);
+        assert!(long_run_variance(&[1.0], 0).is_err());
+        assert!(long_run_variance(&[1.0, 2.0], 2).is_err());
+        assert!(long_run_variance(&[1.0, f64::NAN], 0).is_err());
+        assert!(matches!(
+            HacRegression::new(&x, &[1.0; 4], 0),
+            Err(StatsError::ContainersMustBeSameLength)
+        ));
+        assert!(HacRegression::new(&x, &[1.0; 5], 5).is_err());
+        assert!(HacRegression::new(&DMatrix::from_element(5, 2, 1.0), &[1.0; 5], 0).is_err());
+        assert!(HacRegression::new(&DMatrix::from_element(2, 2, 1.0), &[1.0; 2], 0).is_err());
+    }
+}
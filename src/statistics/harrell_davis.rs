@@ -0,0 +1,328 @@
+use crate::distribution::{ContinuousCDF, Normal};
+use crate::fp;
+use crate::function::beta;
+use crate::{Result, StatsError};
+
+/// Estimates the `p` quantile of the population `data` was drawn from with
+/// the Harrell-Davis estimator, a weighted average of all order statistics
+/// that is smoother and usually more efficient than a single order
+/// statistic
+///
+/// # Formula
+///
+/// ```ignore
+/// Σ_i (I_(i / n)(a, b) - I_((i - 1) / n)(a, b)) x_(i),    a = p (n + 1), b = (1 - p)(n + 1)
+/// ```
+///
+/// where `x_(i)` is the `i`th smallest value and `I_x(a, b)` is the
+/// regularized incomplete beta function
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or contains `NaN`, or if `p` is not
+/// in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::harrell_davis;
+///
+/// let data = [2.0, 4.0, 1.0, 3.0, 5.0];
+/// // symmetric data has its median in the middle
+/// assert!((harrell_davis(&data, 0.5).unwrap() - 3.0).abs() < 1e-14);
+/// ```
+pub fn harrell_davis(data: &[f64], p: f64) -> Result<f64> {
+    let sorted = sorted(data, 1)?;
+    check_p(p)?;
+    Ok(weighted_sum(&hd_weights(sorted.len(), p), &sorted))
+}
+
+/// Returns the jackknife standard error of the Harrell-Davis estimate of
+/// the `p` quantile of `data`
+///
+/// # Formula
+///
+/// ```ignore
+/// sqrt((n - 1) / n * Σ_i (θ_(-i) - θ_(·))^2)
+/// ```
+///
+/// where `θ_(-i)` is the estimate with the `i`th value left out and `θ_(·)`
+/// is the mean of these estimates
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two values or contains `NaN`,
+/// or if `p` is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::harrell_davis_se;
+///
+/// let se = harrell_davis_se(&[2.0, 4.0, 1.0, 3.0, 5.0], 0.5).unwrap();
+/// assert!(se > 0.0);
+/// ```
+pub fn harrell_davis_se(data: &[f64], p: f64) -> Result<f64> {
+    let sorted = sorted(data, 2)?;
+    check_p(p)?;
+    Ok(jackknife_se(&sorted, p))
+}
+
+/// The comparison of a quantile of two samples returned by
+/// `quantile_difference` and `shift_function`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct QuantileDifference {
+    quantile: f64,
+    estimate: f64,
+    std_error: f64,
+    confidence_interval: (f64, f64),
+}
+
+impl QuantileDifference {
+    /// Returns the quantile `p` that is compared
+    pub fn quantile(&self) -> f64 {
+        self.quantile
+    }
+
+    /// Returns the estimated difference `Q_x(p) - Q_y(p)` of the quantiles
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the standard error of the estimated difference
+    pub fn std_error(&self) -> f64 {
+        self.std_error
+    }
+
+    /// Returns the confidence interval of the difference
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        self.confidence_interval
+    }
+}
+
+/// Compares the `p` quantiles of the independent samples `x` and `y` by the
+/// difference of their Harrell-Davis estimates, with a normal approximation
+/// confidence interval at level `confidence` based on the jackknife standard
+/// errors
+///
+/// # Errors
+///
+/// Returns an error if either sample has fewer than two values or contains
+/// `NaN`, or if `p` or `confidence` is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::quantile_difference;
+///
+/// let x = [1.2, 3.4, 2.2, 5.1, 4.4, 2.9, 3.8, 1.7];
+/// let y = [0.3, 1.1, 2.0, 0.8, 1.6, 2.4, 0.9, 1.3];
+/// let diff = quantile_difference(&x, &y, 0.5, 0.95).unwrap();
+/// let (lower, upper) = diff.confidence_interval();
+/// assert!(lower > 0.0 && upper > diff.estimate());
+/// ```
+pub fn quantile_difference(
+    x: &[f64],
+    y: &[f64],
+    p: f64,
+    confidence: f64,
+) -> Result<QuantileDifference> {
+    if confidence <= 0.0 || confidence >= 1.0 || confidence.is_nan() {
+        return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+    }
+    let (x, y) = (sorted(x, 2)?, sorted(y, 2)?);
+    check_p(p)?;
+    let z = Normal::new(0.0, 1.0)
+        .unwrap()
+        .inverse_cdf(0.5 + 0.5 * confidence);
+    Ok(compare(&x, &y, p, z))
+}
+
+/// Computes the shift function of Doksum and Sievers in the form of Wilcox
+/// (1995), comparing the deciles of the independent samples `x` and `y` by
+/// the differences of their Harrell-Davis estimates
+///
+/// The confidence intervals hold simultaneously for all nine deciles with a
+/// probability of about 95%, using the critical value `80.1 / m^2 + 2.73`
+/// where `m` is the size of the smaller sample, with jackknife standard
+/// errors in place of the bootstrap standard errors of the original
+/// procedure. A constant shift function indicates that the samples differ
+/// by a location shift only.
+///
+/// # Errors
+///
+/// Returns an error if either sample has fewer than two values or contains
+/// `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::shift_function;
+///
+/// let x: Vec<f64> = (0..50).map(|i| (i as f64 * 0.37).sin() * 2.0).collect();
+/// let y: Vec<f64> = x.iter().map(|v| v - 1.0).collect();
+/// let shift = shift_function(&x, &y).unwrap();
+/// assert_eq!(shift.len(), 9);
+/// assert!(shift.iter().all(|d| (d.estimate() - 1.0).abs() < 1e-12));
+/// ```
+pub fn shift_function(x: &[f64], y: &[f64]) -> Result<Vec<QuantileDifference>> {
+    let (x, y) = (sorted(x, 2)?, sorted(y, 2)?);
+    let m = x.len().min(y.len()) as f64;
+    let critical = 80.1 / (m * m) + 2.73;
+    Ok((1..10)
+        .map(|d| compare(&x, &y, d as f64 / 10.0, critical))
+        .collect())
+}
+
+/// Compares the `p` quantiles of the sorted samples `x` and `y` with an
+/// interval of `critical` standard errors around the difference
+fn compare(x: &[f64], y: &[f64], p: f64, critical: f64) -> QuantileDifference {
+    let estimate =
+        weighted_sum(&hd_weights(x.len(), p), x) - weighted_sum(&hd_weights(y.len(), p), y);
+    let std_error = fp::hypot(jackknife_se(x, p), jackknife_se(y, p));
+    QuantileDifference {
+        quantile: p,
+        estimate,
+        std_error,
+        confidence_interval: (
+            estimate - critical * std_error,
+            estimate + critical * std_error,
+        ),
+    }
+}
+
+/// Returns the Harrell-Davis weights of the order statistics of a sample of
+/// size `n` for the `p` quantile
+fn hd_weights(n: usize, p: f64) -> Vec<f64> {
+    let a = p * (n + 1) as f64;
+    let b = (1.0 - p) * (n + 1) as f64;
+    let mut below = 0.0;
+    (1..=n)
+        .map(|i| {
+            let cdf = if i == n {
+                1.0
+            } else {
+                beta::beta_reg(a, b, i as f64 / n as f64)
+            };
+            let w = cdf - below;
+            below = cdf;
+            w
+        })
+        .collect()
+}
+
+/// Returns the jackknife standard error of the Harrell-Davis estimate of
+/// the `p` quantile of the sorted sample `x`, using that leaving out the
+/// `i`th order statistic shifts the weights of the larger ones down by one
+fn jackknife_se(x: &[f64], p: f64) -> f64 {
+    let n = x.len();
+    let w = hd_weights(n - 1, p);
+    // lower[i] = Σ_{j < i} w_j x_j and upper[i] = Σ_{j > i} w_{j - 1} x_j
+    let mut lower = vec![0.0; n];
+    for i in 1..n {
+        lower[i] = lower[i - 1] + w[i - 1] * x[i - 1];
+    }
+    let mut upper = vec![0.0; n];
+    for i in (0..n - 1).rev() {
+        upper[i] = upper[i + 1] + w[i] * x[i + 1];
+    }
+    let estimates: Vec<f64> = lower.iter().zip(upper.iter()).map(|(l, u)| l + u).collect();
+    let mean = estimates.iter().sum::<f64>() / n as f64;
+    let ss: f64 = estimates.iter().map(|e| fp::powi(e - mean, 2)).sum();
+    ((n - 1) as f64 / n as f64 * ss).sqrt()
+}
+
+fn weighted_sum(w: &[f64], x: &[f64]) -> f64 {
+    w.iter().zip(x.iter()).map(|(w, x)| w * x).sum()
+}
+
+fn check_p(p: f64) -> Result<()> {
+    if p > 0.0 && p < 1.0 {
+        Ok(())
+    } else {
+        Err(StatsError::ArgIntervalExcl("p", 0.0, 1.0))
+    }
+}
+
+/// Returns a sorted copy of `data` after checking that it has at least
+/// `min_len` values and no `NaN`
+fn sorted(data: &[f64], min_len: usize) -> Result<Vec<f64>> {
+    if data.len() < min_len || data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(sorted)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: [f64; 10] = [3.1, 0.4, 2.2, 7.9, 1.5, 4.8, 2.6, 0.9, 5.5, 3.3];
+    const HD_MEDIAN: f64 = 2.8715037780594104;
+    const HD_MEDIAN_SE: f64 = 0.6672463243442293;
+    const HD_DECILE: f64 = 0.7014420110465036;
+    const HD_DECILE_SE: f64 = 0.4924451164235693;
+
+    #[test]
+    fn test_harrell_davis() {
+        // reference values computed with mpmath
+        assert_almost_eq!(harrell_davis(&DATA, 0.5).unwrap(), HD_MEDIAN, 1e-13);
+        assert_almost_eq!(harrell_davis(&DATA, 0.1).unwrap(), HD_DECILE, 1e-13);
+        assert_eq!(harrell_davis(&[4.2], 0.3).unwrap(), 4.2);
+        // extreme quantiles approach the sample minimum and maximum
+        let low = harrell_davis(&DATA, 1e-3).unwrap();
+        assert!(low > 0.4 && low < 0.41);
+        let high = harrell_davis(&DATA, 1.0 - 1e-3).unwrap();
+        assert!(high < 7.9 && high > 7.8);
+    }
+
+    #[test]
+    fn test_harrell_davis_se() {
+        // brute-force jackknife computed with mpmath
+        assert_almost_eq!(harrell_davis_se(&DATA, 0.5).unwrap(), HD_MEDIAN_SE, 1e-13);
+        assert_almost_eq!(harrell_davis_se(&DATA, 0.1).unwrap(), HD_DECILE_SE, 1e-13);
+        assert_eq!(harrell_davis_se(&[1.0, 1.0, 1.0], 0.5).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_quantile_difference() {
+        let y: Vec<f64> = DATA.iter().map(|x| x + 2.0).collect();
+        let d = quantile_difference(&y, &DATA, 0.5, 0.95).unwrap();
+        assert_eq!(d.quantile(), 0.5);
+        assert_almost_eq!(d.estimate(), 2.0, 1e-13);
+        assert_almost_eq!(d.std_error(), 2f64.sqrt() * HD_MEDIAN_SE, 1e-13);
+        let (lower, upper) = d.confidence_interval();
+        assert_almost_eq!(upper - d.estimate(), 1.959963984540054 * d.std_error(), 1e-9);
+        assert_almost_eq!(d.estimate() - lower, upper - d.estimate(), 1e-12);
+    }
+
+    #[test]
+    fn test_shift_function() {
+        // a scale change gives a shift function increasing across deciles
+        let y: Vec<f64> = DATA.iter().map(|x| 3.0 * x).collect();
+        let shift = shift_function(&y, &DATA).unwrap();
+        assert_eq!(shift.len(), 9);
+        for (i, d) in shift.iter().enumerate() {
+            assert_almost_eq!(d.quantile(), (i + 1) as f64 / 10.0, 1e-15);
+            assert_almost_eq!(d.estimate(), 2.0 * harrell_davis(&DATA, d.quantile()).unwrap(), 1e-12);
+            let (lower, upper) = d.confidence_interval();
+            assert_almost_eq!(upper - lower, 2.0 * (80.1 / 100.0 + 2.73) * d.std_error(), 1e-12);
+        }
+        assert!(shift.windows(2).all(|w| w[0].estimate() < w[1].estimate()));
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(harrell_davis(&[], 0.5).is_err());
+        assert!(harrell_davis(&DATA, 0.0).is_err());
+        assert!(harrell_davis(&DATA, 1.0).is_err());
+        assert!(harrell_davis(&[1.0, f64::NAN], 0.5).is_err());
+        assert!(harrell_davis_se(&[1.0], 0.5).is_err());
+        assert!(quantile_difference(&DATA, &DATA, 0.5, 1.0).is_err());
+        assert!(quantile_difference(&DATA, &[1.0], 0.5, 0.9).is_err());
+        assert!(shift_function(&[1.0], &DATA).is_err());
+    }
+}
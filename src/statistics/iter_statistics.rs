@@ -1,4 +1,5 @@
 use crate::error::StatsError;
+use crate::fp;
 use crate::statistics::*;
 use std::borrow::Borrow;
 use std::f64;
@@ -87,10 +88,10 @@ where
         let mut sum = 0.0;
         for x in self {
             i += 1.0;
-            sum += x.borrow().ln();
+            sum += fp::ln(*x.borrow());
         }
         if i > 0.0 {
-            (sum / i).exp()
+            fp::exp(sum / i)
         } else {
             f64::NAN
         }
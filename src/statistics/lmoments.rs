@@ -0,0 +1,169 @@
+use crate::{Result, StatsError};
+
+/// The first four sample L-moments of a data set returned by `l_moments`,
+/// linear combinations of the order statistics that summarize location,
+/// scale and shape like the ordinary moments but are far less sensitive to
+/// outliers and exist whenever the mean does
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LMoments {
+    l1: f64,
+    l2: f64,
+    l3: f64,
+    l4: f64,
+}
+
+impl LMoments {
+    /// Returns the first L-moment `λ_1`, the mean
+    pub fn l1(&self) -> f64 {
+        self.l1
+    }
+
+    /// Returns the second L-moment `λ_2`, half the expected absolute
+    /// difference of two observations
+    pub fn l2(&self) -> f64 {
+        self.l2
+    }
+
+    /// Returns the third L-moment `λ_3`
+    pub fn l3(&self) -> f64 {
+        self.l3
+    }
+
+    /// Returns the fourth L-moment `λ_4`
+    pub fn l4(&self) -> f64 {
+        self.l4
+    }
+
+    /// Returns the L-coefficient of variation `τ = λ_2 / λ_1`
+    pub fn l_cv(&self) -> f64 {
+        self.l2 / self.l1
+    }
+
+    /// Returns the L-skewness `τ_3 = λ_3 / λ_2`, which lies in `(-1, 1)`
+    pub fn l_skewness(&self) -> f64 {
+        self.l3 / self.l2
+    }
+
+    /// Returns the L-kurtosis `τ_4 = λ_4 / λ_2`, which lies in
+    /// `[(5 τ_3^2 - 1) / 4, 1)`
+    pub fn l_kurtosis(&self) -> f64 {
+        self.l4 / self.l2
+    }
+}
+
+/// Computes the first four sample L-moments of `data` from the unbiased
+/// estimators of the probability weighted moments of Hosking (1990)
+///
+/// # Formula
+///
+/// ```ignore
+/// b_r = (1 / n) Σ_j ((j - 1)(j - 2)...(j - r)) / ((n - 1)(n - 2)...(n - r)) x_(j)
+///
+/// λ_1 = b_0
+/// λ_2 = 2 b_1 - b_0
+/// λ_3 = 6 b_2 - 6 b_1 + b_0
+/// λ_4 = 20 b_3 - 30 b_2 + 12 b_1 - b_0
+/// ```
+///
+/// where `x_(j)` is the `j`th smallest value
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than four values, contains
+/// non-finite values, or if all its values are equal
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::l_moments;
+///
+/// let l = l_moments(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+/// assert_eq!(l.l1(), 3.0);
+/// assert!((l.l2() - 1.0).abs() < 1e-15);
+/// // symmetric data has no L-skewness
+/// assert!(l.l_skewness().abs() < 1e-15);
+/// ```
+pub fn l_moments(data: &[f64]) -> Result<LMoments> {
+    if data.len() < 4 {
+        return Err(StatsError::BadParams);
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::BadParams);
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let n = sorted.len() as f64;
+    let mut b = [0.0; 4];
+    for (i, &x) in sorted.iter().enumerate() {
+        let j = i as f64;
+        let w1 = j / (n - 1.0);
+        let w2 = w1 * (j - 1.0) / (n - 2.0);
+        let w3 = w2 * (j - 2.0) / (n - 3.0);
+        b[0] += x;
+        b[1] += w1 * x;
+        b[2] += w2 * x;
+        b[3] += w3 * x;
+    }
+    for b in b.iter_mut() {
+        *b /= n;
+    }
+
+    let l2 = 2.0 * b[1] - b[0];
+    if l2 <= 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    Ok(LMoments {
+        l1: b[0],
+        l2,
+        l3: 6.0 * b[2] - 6.0 * b[1] + b[0],
+        l4: 20.0 * b[3] - 30.0 * b[2] + 12.0 * b[1] - b[0],
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l_moments() {
+        // exact values from the definition as averages over all subsets of
+        // the data of the combinations of their order statistics
+        let data = [3.1, 0.4, 2.2, 7.9, 1.5, 4.8, 2.6, 0.9, 5.5, 3.3];
+        let l = l_moments(&data).unwrap();
+        assert!((l.l1() - 3.22).abs() < 1e-14);
+        assert!((l.l2() - 4.0 / 3.0).abs() < 1e-14);
+        assert!((l.l3() - 17.0 / 60.0).abs() < 1e-14);
+        assert!((l.l4() - 19.0 / 84.0).abs() < 1e-14);
+        assert!((l.l_cv() - 4.0 / 3.0 / 3.22).abs() < 1e-14);
+        assert!((l.l_skewness() - 17.0 / 80.0).abs() < 1e-14);
+        assert!((l.l_kurtosis() - 19.0 / 112.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_l_moments_invariance() {
+        // an affine map of the data moves λ_1 and scales the higher
+        // L-moments, leaving their ratios unchanged
+        let data = [3.1, 0.4, 2.2, 7.9, 1.5, 4.8, 2.6, 0.9, 5.5, 3.3];
+        let shifted: Vec<f64> = data.iter().map(|x| 2.0 * x - 5.0).collect();
+        let (l, m) = (l_moments(&data).unwrap(), l_moments(&shifted).unwrap());
+        assert!((m.l1() - (2.0 * l.l1() - 5.0)).abs() < 1e-13);
+        assert!((m.l2() - 2.0 * l.l2()).abs() < 1e-13);
+        assert!((m.l_skewness() - l.l_skewness()).abs() < 1e-13);
+        assert!((m.l_kurtosis() - l.l_kurtosis()).abs() < 1e-13);
+        // reflecting the data flips the sign of the L-skewness
+        let reflected: Vec<f64> = data.iter().map(|x| -x).collect();
+        let r = l_moments(&reflected).unwrap();
+        assert!((r.l_skewness() + l.l_skewness()).abs() < 1e-13);
+        assert!((r.l_kurtosis() - l.l_kurtosis()).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_l_moments_errors() {
+        assert!(l_moments(&[1.0, 2.0, 3.0]).is_err());
+        assert!(l_moments(&[1.0, 2.0, 3.0, f64::NAN]).is_err());
+        assert!(l_moments(&[1.0, 2.0, 3.0, f64::INFINITY]).is_err());
+        assert!(l_moments(&[2.0; 5]).is_err());
+    }
+}
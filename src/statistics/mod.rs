@@ -1,12 +1,27 @@
-//! Provides traits for statistical computation
+//! Provides traits for statistical computation, along with
+//! autocorrelation-consistent (Newey-West) standard errors, Harrell-Davis
+//! quantile estimation and comparison, sample L-moments, and delta method
+//! propagation of parameter uncertainty
 
+pub use self::delta::{delta_method, DeltaEstimate, ParameterFit};
+pub use self::hac::{long_run_variance, newey_west_lags, newey_west_se, HacRegression};
+pub use self::harrell_davis::{
+    harrell_davis, harrell_davis_se, quantile_difference, shift_function, QuantileDifference,
+};
 pub use self::iter_statistics::*;
+pub use self::lmoments::{l_moments, LMoments};
+pub use self::moments::{cumulants_to_moments, moments_to_cumulants};
 pub use self::order_statistics::*;
 pub use self::slice_statistics::*;
 pub use self::statistics::*;
 pub use self::traits::*;
 
+mod delta;
+mod hac;
+mod harrell_davis;
 mod iter_statistics;
+mod lmoments;
+mod moments;
 mod order_statistics;
 // TODO: fix later
 mod slice_statistics;
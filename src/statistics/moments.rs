@@ -0,0 +1,116 @@
+use ::num_traits::float::Float;
+
+/// Converts the raw moments `m_1, ..., m_n` of a distribution to its
+/// cumulants `κ_1, ..., κ_n`, returning `None` if a binomial coefficient
+/// can not be represented in `T`
+///
+/// # Remarks
+///
+/// Since the cumulants of order two and higher are invariant under shifts,
+/// passing central moments (with `m_1 = 0`) yields the same higher order
+/// cumulants with less cancellation.
+///
+/// # Formula
+///
+/// ```ignore
+/// κ_n = m_n - sum_{j = 1}^{n - 1} C(n - 1, j - 1) * κ_j * m_(n - j)
+/// ```
+///
+/// where `C` is the binomial coefficient
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::moments_to_cumulants;
+///
+/// // raw moments of the standard normal distribution
+/// let cumulants = moments_to_cumulants(&[0.0, 1.0, 0.0, 3.0]).unwrap();
+/// assert_eq!(cumulants, vec![0.0, 1.0, 0.0, 0.0]);
+/// ```
+pub fn moments_to_cumulants<T: Float>(moments: &[T]) -> Option<Vec<T>> {
+    let mut cumulants: Vec<T> = Vec::with_capacity(moments.len());
+    for n in 1..=moments.len() {
+        let mut kappa = moments[n - 1];
+        let mut binomial = T::one();
+        for j in 1..n {
+            kappa = kappa - binomial * cumulants[j - 1] * moments[n - j - 1];
+            binomial = binomial * T::from(n - j)? / T::from(j)?;
+        }
+        cumulants.push(kappa);
+    }
+    Some(cumulants)
+}
+
+/// Converts the cumulants `κ_1, ..., κ_n` of a distribution to its raw
+/// moments `m_1, ..., m_n`, returning `None` if a binomial coefficient can
+/// not be represented in `T`
+///
+/// # Formula
+///
+/// ```ignore
+/// m_n = sum_{j = 1}^n C(n - 1, j - 1) * κ_j * m_(n - j)
+/// ```
+///
+/// where `m_0 = 1` and `C` is the binomial coefficient
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::cumulants_to_moments;
+///
+/// // every cumulant of the poisson distribution equals its rate
+/// let moments = cumulants_to_moments(&[2.0, 2.0, 2.0]).unwrap();
+/// assert_eq!(moments, vec![2.0, 6.0, 22.0]);
+/// ```
+pub fn cumulants_to_moments<T: Float>(cumulants: &[T]) -> Option<Vec<T>> {
+    let mut moments: Vec<T> = Vec::with_capacity(cumulants.len());
+    for n in 1..=cumulants.len() {
+        let mut m = T::zero();
+        let mut binomial = T::one();
+        for j in 1..=n {
+            let lower = if n == j { T::one() } else { moments[n - j - 1] };
+            m = m + binomial * cumulants[j - 1] * lower;
+            binomial = binomial * T::from(n - j)? / T::from(j)?;
+        }
+        moments.push(m);
+    }
+    Some(moments)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        assert!(moments_to_cumulants::<f64>(&[]).unwrap().is_empty());
+        assert!(cumulants_to_moments::<f64>(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_normal() {
+        // raw moments of N(1, 4)
+        let moments = [1.0, 5.0, 13.0, 73.0, 281.0, 1741.0];
+        let cumulants = moments_to_cumulants(&moments).unwrap();
+        for (k, e) in cumulants.iter().zip(&[1.0, 4.0, 0.0, 0.0, 0.0, 0.0]) {
+            assert_almost_eq!(*k, *e, 1e-12);
+        }
+        let back = cumulants_to_moments(&cumulants).unwrap();
+        for (m, e) in back.iter().zip(&moments) {
+            assert_almost_eq!(*m, *e, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cumulants = [0.5, 2.0, -1.5, 3.0, 0.25];
+        let moments = cumulants_to_moments(&cumulants).unwrap();
+        // m_3 = κ_3 + 3κ_2κ_1 + κ_1^3
+        assert_almost_eq!(moments[2], -1.5 + 3.0 + 0.125, 1e-14);
+        let back = moments_to_cumulants(&moments).unwrap();
+        for (k, e) in back.iter().zip(&cumulants) {
+            assert_almost_eq!(*k, *e, 1e-13);
+        }
+    }
+}
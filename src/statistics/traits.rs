@@ -1,3 +1,6 @@
+use super::moments_to_cumulants;
+use crate::distribution::KullbackLeibler;
+use crate::fp;
 use ::nalgebra::{
     base::allocator::Allocator, base::dimension::DimName, DefaultAllocator, Dim, DimMin, U1,
 };
@@ -150,6 +153,45 @@ pub trait Distribution<T: Float>: ::rand::distributions::Distribution<T> {
     fn entropy(&self) -> Option<T> {
         None
     }
+    /// Returns the entropy in units of logarithms to base `base`, e.g. in
+    /// bits for a base of two, if it exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Distribution;
+    /// use statrs::distribution::Uniform;
+    ///
+    /// let n = Uniform::new(0.0, 8.0).unwrap();
+    /// assert!((n.entropy_base(2.0).unwrap() - 3.0).abs() < 1e-15);
+    /// ```
+    fn entropy_base(&self, base: T) -> Option<T> {
+        self.entropy().map(|h| h / fp::ln_float(base))
+    }
+    /// Returns the cross-entropy `H(P, Q) = -E_P[ln(q(X))]` of `other`
+    /// relative to `self`, if it exists, from the entropy and the closed
+    /// form Kullback-Leibler divergence as `H(P) + D_KL(P || Q)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Distribution;
+    /// use statrs::distribution::Normal;
+    ///
+    /// let p = Normal::new(0.0, 1.0).unwrap();
+    /// let q = Normal::new(1.0, 1.0).unwrap();
+    /// // the expected negative log density of q under p
+    /// let expected = 0.5 * (2.0 * std::f64::consts::PI).ln() + 1.0;
+    /// assert!((p.cross_entropy(&q).unwrap() - expected).abs() < 1e-15);
+    /// assert_eq!(p.cross_entropy(&p), p.entropy());
+    /// ```
+    fn cross_entropy(&self, other: &Self) -> Option<T>
+    where
+        Self: KullbackLeibler + Sized,
+    {
+        let kl = T::from(self.kl_divergence(other))?;
+        self.entropy().map(|h| h + kl)
+    }
     /// Returns the skewness, if it exists.
     ///
     /// # Examples
@@ -164,6 +206,107 @@ pub trait Distribution<T: Float>: ::rand::distributions::Distribution<T> {
     fn skewness(&self) -> Option<T> {
         None
     }
+    /// Returns the raw moment `E[X^k]` of order `k`, if it exists.
+    /// The default implementation returns an estimation
+    /// based on random samples. This is a crude estimate
+    /// for when no further information is known about the
+    /// distribution. More accurate statements about the
+    /// moments can and should be given by overriding the
+    /// default implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Distribution;
+    /// use statrs::distribution::Uniform;
+    ///
+    /// let n = Uniform::new(0.0, 1.0).unwrap();
+    /// assert_eq!(0.25, n.moment(3).unwrap());
+    /// ```
+    fn moment(&self, k: u32) -> Option<T> {
+        if k == 0 {
+            return Some(T::one());
+        }
+        // TODO: Does not need cryptographic rng
+        let mut rng = ::rand::rngs::OsRng;
+        let mut moment = T::zero();
+        let mut steps = T::zero();
+        for _ in 0..STEPS {
+            steps = steps + T::one();
+            moment = moment + fp::powi(Self::sample(self, &mut rng), k as i32);
+        }
+        Some(moment / steps)
+    }
+    /// Returns the central moment `E[(X - E[X])^k]` of order `k`, if it
+    /// exists.
+    /// The default implementation expands the central moment in
+    /// the raw moments returned by `moment`, which can lose
+    /// precision if the mean is large compared to the spread of
+    /// the distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Distribution;
+    /// use statrs::distribution::Uniform;
+    ///
+    /// let n = Uniform::new(0.0, 1.0).unwrap();
+    /// assert_eq!(1.0 / 80.0, n.central_moment(4).unwrap());
+    /// ```
+    fn central_moment(&self, k: u32) -> Option<T> {
+        match k {
+            0 => Some(T::one()),
+            1 => self.mean().map(|_| T::zero()),
+            2 => self.variance(),
+            _ => shift_moments(k, -self.mean()?, |j| self.moment(j)),
+        }
+    }
+    /// Returns the cumulant of order `k`, if it exists.
+    /// The default implementation converts the central moments
+    /// returned by `central_moment` to cumulants, see
+    /// `moments_to_cumulants`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Distribution;
+    /// use statrs::distribution::Uniform;
+    ///
+    /// let n = Uniform::new(0.0, 1.0).unwrap();
+    /// assert_eq!(0.5, n.cumulant(1).unwrap());
+    /// assert!((n.cumulant(4).unwrap() + 1.0 / 120.0).abs() < 1e-15);
+    /// ```
+    fn cumulant(&self, k: u32) -> Option<T> {
+        match k {
+            0 => Some(T::zero()),
+            1 => self.mean(),
+            2 => self.variance(),
+            _ => {
+                let mut central = Vec::with_capacity(k as usize);
+                for j in 1..=k {
+                    central.push(self.central_moment(j)?);
+                }
+                moments_to_cumulants(&central)?.pop()
+            }
+        }
+    }
+}
+
+/// Returns `E[(Y + shift)^k]` from the raw moments `moment(j) = E[Y^j]` for
+/// `j` in `0..=k` by binomial expansion, or `None` if any of them does not
+/// exist
+pub(crate) fn shift_moments<T, F>(k: u32, shift: T, mut moment: F) -> Option<T>
+where
+    T: Float,
+    F: FnMut(u32) -> Option<T>,
+{
+    let mut sum = T::zero();
+    let mut binomial = T::one();
+    for j in 0..=k {
+        sum = sum + binomial * moment(j)? * fp::powi(shift, (k - j) as i32);
+        binomial = binomial * T::from(k - j)? / T::from(j + 1)?;
+    }
+    Some(sum)
 }
 
 /// The `Mean` trait implements the calculation of a mean.
@@ -0,0 +1,307 @@
+use crate::distribution::{ChiSquared, ContinuousCDF};
+use crate::{Result, StatsError};
+use nalgebra::DMatrix;
+use std::f64;
+
+/// The result of a Pearson chi-square test returned by `chi_square_gof`
+/// and `chi_square_independence`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChiSquareTest {
+    statistic: f64,
+    df: f64,
+    expected: DMatrix<f64>,
+    residuals: DMatrix<f64>,
+    standardized_residuals: DMatrix<f64>,
+}
+
+impl ChiSquareTest {
+    /// Returns the test statistic `Σ (O - E)^2 / E` over the cells, with
+    /// `|O - E|` reduced by `0.5` if Yates' correction was requested
+    pub fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    /// Returns the degrees of freedom of the chi-squared distribution of
+    /// the statistic under the null hypothesis
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+
+    /// Returns the p-value of the null hypothesis, the probability that the
+    /// statistic is at least as large under it
+    pub fn p_value(&self) -> f64 {
+        ChiSquared::new(self.df).unwrap().sf(self.statistic)
+    }
+
+    /// Returns the expected counts `E` under the null hypothesis, a column
+    /// for the goodness of fit test
+    pub fn expected(&self) -> &DMatrix<f64> {
+        &self.expected
+    }
+
+    /// Returns the Pearson residuals `(O - E) / sqrt(E)`, whose squares sum
+    /// to the uncorrected statistic
+    pub fn residuals(&self) -> &DMatrix<f64> {
+        &self.residuals
+    }
+
+    /// Returns the standardized residuals, the Pearson residuals divided by
+    /// their standard errors so that they are approximately standard normal
+    /// under the null hypothesis, which locates the cells departing from it
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// goodness of fit: (O_i - E_i) / sqrt(E_i (1 - E_i / n))
+    /// independence:    (O_ij - E_ij) / sqrt(E_ij (1 - r_i / n) (1 - c_j / n))
+    /// ```
+    ///
+    /// where `n` is the total count and `r_i` and `c_j` are the row and
+    /// column totals
+    pub fn standardized_residuals(&self) -> &DMatrix<f64> {
+        &self.standardized_residuals
+    }
+}
+
+/// Tests whether the counts `observed` of the categories of a variable
+/// follow the expected frequencies `expected` with Pearson's chi-square
+/// goodness of fit test, optionally with Yates' continuity correction
+///
+/// # Remarks
+///
+/// `expected` is rescaled to the total of `observed`, so the probabilities
+/// of the categories can be passed as well as expected counts. The
+/// statistic has `k - 1` degrees of freedom for `k` categories. The
+/// chi-squared approximation is poor if some expected counts are below
+/// about 5, and Yates' correction, which makes the test conservative, is
+/// usually only applied for two categories.
+///
+/// # Errors
+///
+/// Returns an error if `observed` and `expected` have different lengths or
+/// fewer than two categories, if a count is negative or not finite, if all
+/// counts are zero, or if an expected frequency is not positive and finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::chi_square_gof;
+///
+/// // the rolls of a die
+/// let observed = [22.0, 17.0, 20.0, 26.0, 22.0, 13.0];
+/// let test = chi_square_gof(&observed, &[1.0 / 6.0; 6], false).unwrap();
+/// assert_eq!(test.df(), 5.0);
+/// assert!((test.expected()[0] - 20.0).abs() < 1e-12);
+/// // the die is not shown to be unfair
+/// assert!(test.p_value() > 0.05);
+/// ```
+pub fn chi_square_gof(observed: &[f64], expected: &[f64], yates: bool) -> Result<ChiSquareTest> {
+    if observed.len() != expected.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if observed.len() < 2 || expected.iter().any(|e| !(e.is_finite() && *e > 0.0)) {
+        return Err(StatsError::BadParams);
+    }
+    let n = check_counts(observed)?;
+    let scale = n / expected.iter().sum::<f64>();
+
+    let k = observed.len();
+    let expected = DMatrix::from_iterator(k, 1, expected.iter().map(|e| e * scale));
+    let observed = DMatrix::from_column_slice(k, 1, observed);
+    let standard_errors = expected.map(|e| (e * (1.0 - e / n)).sqrt());
+    let df = (k - 1) as f64;
+    Ok(pearson(&observed, expected, standard_errors, df, yates))
+}
+
+/// Tests whether the row and column variables of the contingency table
+/// `table` of counts are independent with Pearson's chi-square test,
+/// optionally with Yates' continuity correction
+///
+/// # Remarks
+///
+/// The expected counts under independence are `E_ij = r_i c_j / n`, where
+/// `r_i` and `c_j` are the row and column totals and `n` the total count,
+/// and the statistic has `(r - 1)(c - 1)` degrees of freedom for an
+/// `r × c` table. Yates' correction is usually only applied to `2 × 2`
+/// tables.
+///
+/// # Errors
+///
+/// Returns an error if `table` has fewer than two rows or columns, if a
+/// count is negative or not finite, or if a row or column total is zero
+///
+/// # Examples
+///
+/// ```
+/// use nalgebra::DMatrix;
+/// use statrs::stats_tests::chi_square_independence;
+///
+/// // treatment against outcome
+/// let table = DMatrix::from_row_slice(2, 2, &[30.0, 10.0, 18.0, 22.0]);
+/// let test = chi_square_independence(&table, true).unwrap();
+/// assert_eq!(test.df(), 1.0);
+/// assert!(test.p_value() < 0.05);
+/// // the treated recover more often than expected under independence
+/// assert!(test.standardized_residuals()[(0, 0)] > 2.0);
+/// ```
+pub fn chi_square_independence(table: &DMatrix<f64>, yates: bool) -> Result<ChiSquareTest> {
+    let (rows, cols) = table.shape();
+    if rows < 2 || cols < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let n = check_counts(table.as_slice())?;
+    let row_totals: Vec<f64> = table.row_iter().map(|row| row.sum()).collect();
+    let col_totals: Vec<f64> = table.column_iter().map(|col| col.sum()).collect();
+    if row_totals
+        .iter()
+        .chain(&col_totals)
+        .any(|&total| total == 0.0)
+    {
+        return Err(StatsError::BadParams);
+    }
+
+    let expected = DMatrix::from_fn(rows, cols, |i, j| row_totals[i] * col_totals[j] / n);
+    let standard_errors = DMatrix::from_fn(rows, cols, |i, j| {
+        (expected[(i, j)] * (1.0 - row_totals[i] / n) * (1.0 - col_totals[j] / n)).sqrt()
+    });
+    let df = ((rows - 1) * (cols - 1)) as f64;
+    Ok(pearson(table, expected, standard_errors, df, yates))
+}
+
+/// Returns the total of `counts`, failing if a count is negative or not
+/// finite or if all counts are zero
+fn check_counts(counts: &[f64]) -> Result<f64> {
+    if counts.iter().any(|x| !(x.is_finite() && *x >= 0.0)) {
+        return Err(StatsError::BadParams);
+    }
+    let n: f64 = counts.iter().sum();
+    if n == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    Ok(n)
+}
+
+/// Assembles the Pearson test of the counts `observed` against `expected`,
+/// where `standard_errors` are the standard errors of `O - E` under the
+/// null hypothesis
+fn pearson(
+    observed: &DMatrix<f64>,
+    expected: DMatrix<f64>,
+    standard_errors: DMatrix<f64>,
+    df: f64,
+    yates: bool,
+) -> ChiSquareTest {
+    let correction = if yates { 0.5 } else { 0.0 };
+    let statistic = observed
+        .iter()
+        .zip(expected.iter())
+        .map(|(o, e)| {
+            let d = ((o - e).abs() - correction).max(0.0);
+            d * d / e
+        })
+        .sum();
+    let residuals = observed.zip_map(&expected, |o, e| (o - e) / e.sqrt());
+    let standardized_residuals = observed
+        .zip_map(&expected, |o, e| o - e)
+        .zip_map(&standard_errors, |d, se| d / se);
+    ChiSquareTest {
+        statistic,
+        df,
+        expected,
+        residuals,
+        standardized_residuals,
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_all_almost_eq(actual: &DMatrix<f64>, expected: &[f64], prec: f64) {
+        assert_eq!(actual.len(), expected.len());
+        for (&a, &e) in actual.iter().zip(expected) {
+            assert_almost_eq!(a, e, prec);
+        }
+    }
+
+    // reference values from mpmath
+
+    #[test]
+    fn test_gof() {
+        let observed = [22.0, 17.0, 20.0, 26.0, 22.0, 13.0];
+        let test = chi_square_gof(&observed, &[1.0; 6], false).unwrap();
+        assert_almost_eq!(test.statistic(), 5.1, 1e-14);
+        assert_eq!(test.df(), 5.0);
+        assert_almost_eq!(test.p_value(), 0.40379845710420816, 1e-14);
+        assert_eq!(test.expected().shape(), (6, 1));
+        assert_all_almost_eq(test.residuals(), &[0.44721359549995794, -0.67082039324993691, 0.0, 1.3416407864998738, 0.44721359549995794, -1.5652475842498528], 1e-15);
+        assert_all_almost_eq(test.standardized_residuals(), &[0.48989794855663562, -0.73484692283495343, 0.0, 1.4696938456699069, 0.48989794855663562, -1.7146428199482247], 1e-15);
+        // expected counts and probabilities give the same test
+        let counts = chi_square_gof(&observed, &[20.0; 6], false).unwrap();
+        assert_eq!(counts, test);
+
+        let test = chi_square_gof(&[18.0, 55.0, 27.0], &[0.25, 0.5, 0.25], false).unwrap();
+        assert_almost_eq!(test.statistic(), 2.62, 1e-14);
+        assert_almost_eq!(test.p_value(), 0.26982005638468685, 1e-14);
+        assert_all_almost_eq(test.expected(), &[25.0, 50.0, 25.0], 1e-14);
+        assert_all_almost_eq(test.standardized_residuals(), &[-1.6165807537309521, 1.0, 0.46188021535170061], 1e-15);
+        let ss: f64 = test.residuals().iter().map(|r| r * r).sum();
+        assert_almost_eq!(ss, test.statistic(), 1e-14);
+    }
+
+    #[test]
+    fn test_gof_yates() {
+        let test = chi_square_gof(&[43.0, 57.0], &[0.5, 0.5], true).unwrap();
+        assert_almost_eq!(test.statistic(), 1.69, 1e-14);
+        assert_almost_eq!(test.p_value(), 0.19360096917122067, 1e-14);
+        // the correction does not change the residuals
+        assert_all_almost_eq(test.standardized_residuals(), &[-1.4, 1.4], 1e-15);
+        // and never overshoots the difference
+        let test = chi_square_gof(&[50.2, 49.8], &[0.5, 0.5], true).unwrap();
+        assert_eq!(test.statistic(), 0.0);
+        assert_eq!(test.p_value(), 1.0);
+    }
+
+    #[test]
+    fn test_independence() {
+        let table = DMatrix::from_row_slice(2, 2, &[30.0, 10.0, 18.0, 22.0]);
+        let test = chi_square_independence(&table, false).unwrap();
+        assert_almost_eq!(test.statistic(), 7.5, 1e-14);
+        assert_eq!(test.df(), 1.0);
+        assert_almost_eq!(test.p_value(), 0.0061698993205441622, 1e-15);
+        assert_all_almost_eq(test.expected(), &[24.0, 24.0, 16.0, 16.0], 1e-14);
+        assert_almost_eq!(test.residuals()[(0, 1)], -1.5, 1e-15);
+        assert_all_almost_eq(test.standardized_residuals(), &[2.7386127875258306, -2.7386127875258306, -2.7386127875258306, 2.7386127875258306], 1e-14);
+        let test = chi_square_independence(&table, true).unwrap();
+        assert_almost_eq!(test.statistic(), 6.3020833333333333, 1e-14);
+        assert_almost_eq!(test.p_value(), 0.012059616177490231, 1e-14);
+
+        let table = DMatrix::from_row_slice(3, 3, &[12.0, 5.0, 9.0, 7.0, 15.0, 4.0, 3.0, 8.0, 16.0]);
+        let test = chi_square_independence(&table, false).unwrap();
+        assert_almost_eq!(test.statistic(), 18.661508989095196, 1e-13);
+        assert_eq!(test.df(), 4.0);
+        assert_almost_eq!(test.p_value(), 0.00091587636517064885, 1e-15);
+        // matrices are stored by column
+        assert_all_almost_eq(&test.residuals().transpose(), &[1.7687900432854692, -1.3885611118455714, -0.17618512422692901, -0.089380347931978499, 1.9056229072475259, -1.7946298700324397, -1.6480160917727256, -0.50739623667824419, 1.933974082341337], 1e-14);
+        assert_all_almost_eq(&test.standardized_residuals().transpose(), &[2.5423071187733408, -2.1099349528453193, -0.27037927010445366, -0.12846764695929116, 2.8956164367877205, -2.7540958210639698, -2.391384663093435, -0.7783726601190851, 2.9963398276409729], 1e-14);
+        // the test is symmetric in rows and columns
+        let transposed = chi_square_independence(&table.transpose(), false).unwrap();
+        assert_almost_eq!(transposed.statistic(), test.statistic(), 1e-13);
+    }
+
+    #[test]
+    fn test_bad_params() {
+        assert!(chi_square_gof(&[1.0, 2.0], &[1.0], false).is_err());
+        assert!(chi_square_gof(&[1.0], &[1.0], false).is_err());
+        assert!(chi_square_gof(&[1.0, -2.0], &[1.0, 1.0], false).is_err());
+        assert!(chi_square_gof(&[1.0, f64::NAN], &[1.0, 1.0], false).is_err());
+        assert!(chi_square_gof(&[0.0, 0.0], &[1.0, 1.0], false).is_err());
+        assert!(chi_square_gof(&[1.0, 2.0], &[1.0, 0.0], false).is_err());
+        assert!(chi_square_gof(&[1.0, 2.0], &[1.0, f64::INFINITY], false).is_err());
+        assert!(chi_square_independence(&DMatrix::from_row_slice(1, 3, &[1.0, 2.0, 3.0]), false).is_err());
+        assert!(chi_square_independence(&DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 0.0, 0.0]), false).is_err());
+        assert!(chi_square_independence(&DMatrix::from_row_slice(2, 2, &[1.0, 0.0, 3.0, 0.0]), false).is_err());
+        assert!(chi_square_independence(&DMatrix::from_row_slice(2, 2, &[1.0, -1.0, 3.0, 2.0]), false).is_err());
+    }
+}
@@ -0,0 +1,8 @@
+//! Provides classical hypothesis tests of the means of populations and of
+//! the distribution of categorical variables, returning the test statistic
+//! together with its p-values and related estimates
+pub use self::chi_square::{chi_square_gof, chi_square_independence, ChiSquareTest};
+pub use self::t_test::{one_sample_t_test, paired_t_test, two_sample_t_test, welch_t_test, TTest};
+
+mod chi_square;
+mod t_test;
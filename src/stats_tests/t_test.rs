@@ -0,0 +1,394 @@
+use crate::distribution::{ContinuousCDF, StudentsT};
+use crate::function::beta;
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The result of a Student's t-test returned by `one_sample_t_test`,
+/// `two_sample_t_test`, `welch_t_test` and `paired_t_test`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TTest {
+    estimate: f64,
+    std_error: f64,
+    df: f64,
+    effect_size: f64,
+}
+
+impl TTest {
+    /// Returns the estimated mean difference, i.e. the mean minus the
+    /// hypothesized mean for the one-sample test, the difference of the
+    /// means for the two-sample tests and the mean of the differences for
+    /// the paired test
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the standard error of the estimate
+    pub fn std_error(&self) -> f64 {
+        self.std_error
+    }
+
+    /// Returns the test statistic `t = estimate / std_error`
+    pub fn statistic(&self) -> f64 {
+        self.estimate / self.std_error
+    }
+
+    /// Returns the degrees of freedom of the Student's t-distribution of
+    /// the statistic under the null hypothesis, which are fractional for
+    /// Welch's test
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+
+    /// Returns the two-sided p-value of the null hypothesis that the mean
+    /// difference is zero
+    pub fn p_value(&self) -> f64 {
+        self.upper_tail(self.statistic().abs()).min(0.5) * 2.0
+    }
+
+    /// Returns the one-sided p-value of the null hypothesis against the
+    /// alternative that the mean difference is less than zero
+    pub fn p_value_less(&self) -> f64 {
+        self.upper_tail(-self.statistic())
+    }
+
+    /// Returns the one-sided p-value of the null hypothesis against the
+    /// alternative that the mean difference is greater than zero
+    pub fn p_value_greater(&self) -> f64 {
+        self.upper_tail(self.statistic())
+    }
+
+    /// Returns the standardized effect size, Cohen's `d`, the estimate
+    /// divided by the standard deviation described by each test
+    pub fn effect_size(&self) -> f64 {
+        self.effect_size
+    }
+
+    /// Returns the two-sided confidence interval of the mean difference at
+    /// level `confidence`
+    ///
+    /// ```ignore
+    /// estimate ± t_(df)(1 - α / 2) std_error
+    /// ```
+    ///
+    /// where `α = 1 - confidence`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `confidence` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::stats_tests::one_sample_t_test;
+    ///
+    /// let test = one_sample_t_test(&[9.8, 10.2, 10.4, 9.9, 10.1, 10.6], 10.0).unwrap();
+    /// let (lower, upper) = test.confidence_interval(0.95).unwrap();
+    /// assert!(lower < 0.0 && 0.0 < upper);
+    /// assert!((lower + upper - 2.0 * test.estimate()).abs() < 1e-14);
+    /// ```
+    pub fn confidence_interval(&self, confidence: f64) -> Result<(f64, f64)> {
+        if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+        }
+        let t = StudentsT::new(0.0, 1.0, self.df)?.inverse_cdf(0.5 + 0.5 * confidence);
+        let half_width = t * self.std_error;
+        Ok((self.estimate - half_width, self.estimate + half_width))
+    }
+
+    /// Returns the probability that the Student's t-distribution of the
+    /// statistic exceeds `t`, computed from the regularized incomplete beta
+    /// function to keep the precision of small p-values
+    fn upper_tail(&self, t: f64) -> f64 {
+        let tail = 0.5 * beta::beta_reg(0.5 * self.df, 0.5, self.df / (self.df + t * t));
+        if t >= 0.0 {
+            tail
+        } else {
+            1.0 - tail
+        }
+    }
+}
+
+/// Tests whether the mean of the population of `data` equals `mu` with
+/// Student's one-sample t-test
+///
+/// # Formula
+///
+/// ```ignore
+/// t = (x̄ - μ) / (s / sqrt(n))
+/// d = (x̄ - μ) / s
+/// ```
+///
+/// where `x̄` and `s` are the mean and the sample standard deviation of the
+/// `n` values of `data`, with `n - 1` degrees of freedom
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two values, if a value or
+/// `mu` is not finite, or if all values are equal
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::one_sample_t_test;
+///
+/// let data = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 4.7, 5.5];
+/// let test = one_sample_t_test(&data, 5.0).unwrap();
+/// assert_eq!(test.df(), 7.0);
+/// // only the one-sided alternative of a larger mean is significant
+/// assert!(test.p_value() > 0.05);
+/// assert!(test.p_value_greater() < 0.05);
+/// ```
+pub fn one_sample_t_test(data: &[f64], mu: f64) -> Result<TTest> {
+    if !mu.is_finite() {
+        return Err(StatsError::BadParams);
+    }
+    let (mean, variance) = mean_variance(data)?;
+    if variance == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let sd = variance.sqrt();
+    let n = data.len() as f64;
+    Ok(TTest {
+        estimate: mean - mu,
+        std_error: sd / n.sqrt(),
+        df: n - 1.0,
+        effect_size: (mean - mu) / sd,
+    })
+}
+
+/// Tests whether the means of the populations of the independent samples
+/// `a` and `b` are equal with Student's two-sample t-test, which assumes
+/// that the populations have equal variances
+///
+/// # Formula
+///
+/// ```ignore
+/// s_p^2 = ((n_a - 1) s_a^2 + (n_b - 1) s_b^2) / (n_a + n_b - 2)
+/// t = (x̄_a - x̄_b) / (s_p sqrt(1 / n_a + 1 / n_b))
+/// d = (x̄_a - x̄_b) / s_p
+/// ```
+///
+/// with `n_a + n_b - 2` degrees of freedom
+///
+/// # Errors
+///
+/// Returns an error if `a` or `b` has fewer than two values or a value
+/// that is not finite, or if all values in each sample are equal
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::two_sample_t_test;
+///
+/// let a = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 4.7, 5.5];
+/// let b = [4.4, 4.8, 5.0, 4.1, 4.6, 5.2, 4.3];
+/// let test = two_sample_t_test(&a, &b).unwrap();
+/// assert_eq!(test.df(), 13.0);
+/// assert!(test.p_value() < 0.01);
+/// ```
+pub fn two_sample_t_test(a: &[f64], b: &[f64]) -> Result<TTest> {
+    let (mean_a, var_a) = mean_variance(a)?;
+    let (mean_b, var_b) = mean_variance(b)?;
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let df = n_a + n_b - 2.0;
+    let pooled = ((n_a - 1.0) * var_a + (n_b - 1.0) * var_b) / df;
+    if pooled == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let sd = pooled.sqrt();
+    Ok(TTest {
+        estimate: mean_a - mean_b,
+        std_error: sd * (1.0 / n_a + 1.0 / n_b).sqrt(),
+        df,
+        effect_size: (mean_a - mean_b) / sd,
+    })
+}
+
+/// Tests whether the means of the populations of the independent samples
+/// `a` and `b` are equal with Welch's t-test, which unlike
+/// `two_sample_t_test` allows the populations to have different variances
+///
+/// # Formula
+///
+/// ```ignore
+/// t = (x̄_a - x̄_b) / sqrt(s_a^2 / n_a + s_b^2 / n_b)
+/// ν = (s_a^2 / n_a + s_b^2 / n_b)^2 / ((s_a^2 / n_a)^2 / (n_a - 1) + (s_b^2 / n_b)^2 / (n_b - 1))
+/// d = (x̄_a - x̄_b) / sqrt((s_a^2 + s_b^2) / 2)
+/// ```
+///
+/// where the degrees of freedom `ν` are the Welch-Satterthwaite
+/// approximation
+///
+/// # Errors
+///
+/// Returns an error if `a` or `b` has fewer than two values or a value
+/// that is not finite, or if all values in each sample are equal
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::welch_t_test;
+///
+/// let a = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 4.7, 5.5];
+/// let b = [2.4, 6.8, 5.0, 1.1, 7.6, 3.2, 4.3];
+/// let test = welch_t_test(&a, &b).unwrap();
+/// assert!(test.df() < 13.0);
+/// assert!(test.p_value() > 0.05);
+/// ```
+pub fn welch_t_test(a: &[f64], b: &[f64]) -> Result<TTest> {
+    let (mean_a, var_a) = mean_variance(a)?;
+    let (mean_b, var_b) = mean_variance(b)?;
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let (se2_a, se2_b) = (var_a / n_a, var_b / n_b);
+    let se2 = se2_a + se2_b;
+    if se2 == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    Ok(TTest {
+        estimate: mean_a - mean_b,
+        std_error: se2.sqrt(),
+        df: se2 * se2 / (se2_a * se2_a / (n_a - 1.0) + se2_b * se2_b / (n_b - 1.0)),
+        effect_size: (mean_a - mean_b) / (0.5 * (var_a + var_b)).sqrt(),
+    })
+}
+
+/// Tests whether the mean of the differences `a_i - b_i` of the paired
+/// samples `a` and `b` is zero with the paired t-test, the one-sample
+/// t-test of the differences
+///
+/// # Formula
+///
+/// ```ignore
+/// t = d̄ / (s_d / sqrt(n))
+/// d = d̄ / s_d
+/// ```
+///
+/// where `d̄` and `s_d` are the mean and the sample standard deviation of
+/// the `n` differences, with `n - 1` degrees of freedom
+///
+/// # Errors
+///
+/// Returns an error if `a` and `b` have different lengths or fewer than
+/// two values, if a value is not finite, or if all differences are equal
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::paired_t_test;
+///
+/// let before = [72.0, 80.0, 65.0, 91.0, 77.0, 84.0];
+/// let after = [70.0, 77.0, 64.0, 86.0, 76.0, 80.0];
+/// let test = paired_t_test(&before, &after).unwrap();
+/// assert!((test.estimate() - 8.0 / 3.0).abs() < 1e-14);
+/// assert!(test.p_value_greater() < 0.01);
+/// ```
+pub fn paired_t_test(a: &[f64], b: &[f64]) -> Result<TTest> {
+    if a.len() != b.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    let differences: Vec<f64> = a.iter().zip(b).map(|(x, y)| x - y).collect();
+    one_sample_t_test(&differences, 0.0)
+}
+
+/// Returns the mean and the unbiased sample variance of `data`, failing if
+/// it has fewer than two values or a value that is not finite
+fn mean_variance(data: &[f64]) -> Result<(f64, f64)> {
+    if data.len() < 2 || data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::BadParams);
+    }
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let ss: f64 = data.iter().map(|x| (x - mean) * (x - mean)).sum();
+    Ok((mean, ss / (n - 1.0)))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: [f64; 8] = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 4.7, 5.5];
+    const B: [f64; 7] = [4.4, 4.8, 5.0, 4.1, 4.6, 5.2, 4.3];
+
+    fn check(test: TTest, statistic: f64, df: f64, p_value: f64, p_value_greater: f64, effect_size: f64, ci: (f64, f64)) {
+        assert_almost_eq!(test.statistic(), statistic, 1e-13);
+        assert_almost_eq!(test.df(), df, 1e-13);
+        assert_almost_eq!(test.p_value(), p_value, 1e-14);
+        assert_almost_eq!(test.p_value_greater(), p_value_greater, 1e-14);
+        assert_almost_eq!(test.p_value_less(), 1.0 - p_value_greater, 1e-14);
+        assert_almost_eq!(test.effect_size(), effect_size, 1e-13);
+        let (lower, upper) = test.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(lower, ci.0, 1e-12);
+        assert_almost_eq!(upper, ci.1, 1e-12);
+    }
+
+    // reference values from mpmath
+
+    #[test]
+    fn test_one_sample() {
+        let test = one_sample_t_test(&A, 5.0).unwrap();
+        assert_almost_eq!(test.estimate(), 0.3625, 1e-14);
+        check(test, 2.2936754945615347, 7.0, 0.055506027049096465, 0.027753013524548233, 0.81093674802293464, (-0.011212974322134814, 0.73621297432213468));
+        // far in the tail the p-values keep their precision
+        let data: Vec<f64> = (0..10).map(|i| 10.0 + i as f64 * 1e-3).collect();
+        let test = one_sample_t_test(&data, 0.0).unwrap();
+        assert_almost_eq!(test.p_value() / 3.42818037042763e-33, 1.0, 1e-9);
+        assert_eq!(test.p_value_less(), 1.0);
+        // the test is symmetric
+        let test = one_sample_t_test(&A, 5.6).unwrap();
+        assert_almost_eq!(test.p_value_less() + test.p_value_greater(), 1.0, 1e-15);
+        assert!(test.p_value() <= 1.0);
+    }
+
+    #[test]
+    fn test_two_sample() {
+        let test = two_sample_t_test(&A, &B).unwrap();
+        assert_almost_eq!(test.estimate(), 5.3625 - 32.4 / 7.0, 1e-14);
+        check(test, 3.3474427753627633, 13.0, 0.0052464415314353798, 0.0026232207657176899, 1.7324662283604209, (0.26026661864881933, 1.2075905242083235));
+        // swapping the samples flips the sign
+        let swapped = two_sample_t_test(&B, &A).unwrap();
+        assert_almost_eq!(swapped.statistic(), -test.statistic(), 1e-14);
+        assert_almost_eq!(swapped.p_value_less(), test.p_value_greater(), 1e-15);
+    }
+
+    #[test]
+    fn test_welch() {
+        let b = [2.4, 6.8, 5.0, 1.1, 7.6, 3.2, 4.3];
+        let test = welch_t_test(&A, &b).unwrap();
+        check(test, 1.1378604081181957, 6.3857889565812838, 0.29606258971748737, 0.14803129485874368, 0.60686513045007785, (-1.1413341470596614, 3.1806198613453757));
+        // for equal sample sizes and variances the degrees of freedom are those
+        // of the pooled test
+        let c: Vec<f64> = A.iter().map(|x| 10.0 - x).collect();
+        let welch = welch_t_test(&A, &c).unwrap();
+        let pooled = two_sample_t_test(&A, &c).unwrap();
+        assert_almost_eq!(welch.df(), 14.0, 1e-12);
+        assert_almost_eq!(welch.statistic(), pooled.statistic(), 1e-13);
+        assert_almost_eq!(welch.effect_size(), pooled.effect_size(), 1e-13);
+    }
+
+    #[test]
+    fn test_paired() {
+        let before = [72.0, 80.0, 65.0, 91.0, 77.0, 84.0];
+        let after = [70.0, 77.0, 64.0, 86.0, 76.0, 80.0];
+        let test = paired_t_test(&before, &after).unwrap();
+        check(test, 4.0, 5.0, 0.010323415480831454, 0.0051617077404157269, 1.6329931618554521, (0.95294544290912299, 4.3803878904242103));
+    }
+
+    #[test]
+    fn test_bad_params() {
+        assert!(one_sample_t_test(&[1.0], 0.0).is_err());
+        assert!(one_sample_t_test(&[1.0, 2.0], f64::NAN).is_err());
+        assert!(one_sample_t_test(&[1.0, f64::INFINITY], 0.0).is_err());
+        assert!(one_sample_t_test(&[2.0, 2.0, 2.0], 0.0).is_err());
+        assert!(two_sample_t_test(&A, &[1.0]).is_err());
+        assert!(two_sample_t_test(&[1.0, 1.0], &[2.0, 2.0]).is_err());
+        assert!(welch_t_test(&[1.0, 1.0], &[2.0, 2.0]).is_err());
+        assert!(welch_t_test(&[1.0, f64::NAN], &A).is_err());
+        assert!(paired_t_test(&A, &B).is_err());
+        assert!(paired_t_test(&[1.0, 2.0], &[0.0, 1.0]).is_err());
+        let test = one_sample_t_test(&A, 5.0).unwrap();
+        assert!(test.confidence_interval(0.0).is_err());
+        assert!(test.confidence_interval(1.0).is_err());
+        assert!(test.confidence_interval(f64::NAN).is_err());
+    }
+}
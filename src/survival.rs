@@ -0,0 +1,1211 @@
+//! Provides estimators for right-censored survival data: the
+//! Kaplan-Meier and Nelson-Aalen step estimators, the kernel smoothing of
+//! the latter into a continuous hazard rate with cross-validated bandwidth,
+//! the restricted mean survival time, and the Aalen-Johansen estimator and
+//! Gray's test for competing risks
+
+use crate::consts;
+use crate::distribution::{ChiSquared, ContinuousCDF, Normal};
+use crate::fp;
+use crate::function::erf;
+use crate::{Result, StatsError};
+use nalgebra::{DMatrix, DVector};
+use std::f64;
+
+/// Validates survival times against the length `len` of their event
+/// indicators and returns the indices that sort them
+fn sorted_order(times: &[f64], len: usize) -> Result<Vec<usize>> {
+    if times.len() != len {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if times.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    if times.iter().any(|&t| !t.is_finite() || t < 0.0) {
+        return Err(StatsError::ArgNotNegative("times"));
+    }
+    let mut order: Vec<usize> = (0..times.len()).collect();
+    order.sort_by(|&a, &b| times[a].partial_cmp(&times[b]).unwrap());
+    Ok(order)
+}
+
+/// Returns the distinct event times in increasing order together with the
+/// number of subjects at risk and the number of events at each, for the
+/// observed `times` and whether each ended in an event
+fn risk_table(times: &[f64], events: &[bool]) -> Result<Vec<(f64, f64, f64)>> {
+    let order = sorted_order(times, events.len())?;
+    let mut table = Vec::new();
+    let mut i = 0;
+    while i < order.len() {
+        let t = times[order[i]];
+        let at_risk = (order.len() - i) as f64;
+        let mut deaths = 0.0;
+        while i < order.len() && times[order[i]] == t {
+            if events[order[i]] {
+                deaths += 1.0;
+            }
+            i += 1;
+        }
+        if deaths > 0.0 {
+            table.push((t, at_risk, deaths));
+        }
+    }
+    Ok(table)
+}
+
+/// Implements the [Nelson-Aalen
+/// estimator](https://en.wikipedia.org/wiki/Nelson%E2%80%93Aalen_estimator)
+/// of the cumulative hazard from right-censored survival times, a step
+/// function increasing by `d_i / n_i` at each distinct event time `t_i`
+/// with `d_i` events among the `n_i` subjects still at risk
+///
+/// # Examples
+///
+/// ```
+/// use statrs::survival::NelsonAalen;
+///
+/// // the third subject is censored at time 2
+/// let times = [1.0, 2.0, 2.0, 3.0];
+/// let events = [true, true, false, true];
+/// let na = NelsonAalen::new(&times, &events).unwrap();
+/// assert_eq!(na.cumulative_hazard(2.5), 1.0 / 4.0 + 1.0 / 3.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NelsonAalen {
+    times: Vec<f64>,
+    increments: Vec<f64>,
+    variances: Vec<f64>,
+    cumulative: Vec<f64>,
+    cumulative_variance: Vec<f64>,
+}
+
+impl NelsonAalen {
+    /// Constructs the estimator from the observed `times` and whether each
+    /// ended in an event (`true`) or was censored (`false`). Subjects
+    /// censored at an event time are taken to be at risk at that time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `times` and `events` differ in length, if `times`
+    /// is empty or if any time is negative or not finite
+    pub fn new(times: &[f64], events: &[bool]) -> Result<NelsonAalen> {
+        let mut na = NelsonAalen {
+            times: Vec::new(),
+            increments: Vec::new(),
+            variances: Vec::new(),
+            cumulative: Vec::new(),
+            cumulative_variance: Vec::new(),
+        };
+        let (mut hazard, mut variance) = (0.0, 0.0);
+        for (t, at_risk, deaths) in risk_table(times, events)? {
+            let increment = deaths / at_risk;
+            hazard += increment;
+            variance += deaths / (at_risk * at_risk);
+            na.times.push(t);
+            na.increments.push(increment);
+            na.variances.push(deaths / (at_risk * at_risk));
+            na.cumulative.push(hazard);
+            na.cumulative_variance.push(variance);
+        }
+        Ok(na)
+    }
+
+    /// Returns the distinct event times in increasing order
+    pub fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Returns the jumps `d_i / n_i` of the cumulative hazard at the event
+    /// times
+    pub fn increments(&self) -> &[f64] {
+        &self.increments
+    }
+
+    /// Returns the estimated cumulative hazard at `t`
+    pub fn cumulative_hazard(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&s| s <= t);
+        if i == 0 {
+            0.0
+        } else {
+            self.cumulative[i - 1]
+        }
+    }
+
+    /// Returns the estimated variance `Σ d_i / n_i^2` of the cumulative
+    /// hazard at `t`
+    pub fn variance(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&s| s <= t);
+        if i == 0 {
+            0.0
+        } else {
+            self.cumulative_variance[i - 1]
+        }
+    }
+
+    /// Returns the Fleming-Harrington estimate `exp(-H(t))` of the
+    /// survival function at `t`, where `H` is the cumulative hazard
+    pub fn survival(&self, t: f64) -> f64 {
+        fp::exp(-self.cumulative_hazard(t))
+    }
+}
+
+/// Implements the [Kaplan-Meier
+/// estimator](https://en.wikipedia.org/wiki/Kaplan%E2%80%93Meier_estimator)
+/// of the survival function from right-censored survival times, a step
+/// function multiplied by `1 - d_i / n_i` at each distinct event time `t_i`
+/// with `d_i` events among the `n_i` subjects still at risk
+///
+/// # Examples
+///
+/// ```
+/// use statrs::survival::KaplanMeier;
+///
+/// // the third subject is censored at time 2
+/// let times = [1.0, 2.0, 2.0, 3.0];
+/// let events = [true, true, false, true];
+/// let km = KaplanMeier::new(&times, &events).unwrap();
+/// assert_eq!(km.survival(2.5), 3.0 / 4.0 * 2.0 / 3.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct KaplanMeier {
+    times: Vec<f64>,
+    at_risk: Vec<f64>,
+    deaths: Vec<f64>,
+    survival: Vec<f64>,
+    greenwood: Vec<f64>,
+    last_time: f64,
+}
+
+impl KaplanMeier {
+    /// Constructs the estimator from the observed `times` and whether each
+    /// ended in an event (`true`) or was censored (`false`). Subjects
+    /// censored at an event time are taken to be at risk at that time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `times` and `events` differ in length, if `times`
+    /// is empty or if any time is negative or not finite
+    pub fn new(times: &[f64], events: &[bool]) -> Result<KaplanMeier> {
+        let table = risk_table(times, events)?;
+        let mut km = KaplanMeier {
+            times: Vec::with_capacity(table.len()),
+            at_risk: Vec::with_capacity(table.len()),
+            deaths: Vec::with_capacity(table.len()),
+            survival: Vec::with_capacity(table.len()),
+            greenwood: Vec::with_capacity(table.len()),
+            last_time: times.iter().cloned().fold(0.0, f64::max),
+        };
+        let (mut survival, mut greenwood) = (1.0, 0.0);
+        for (t, at_risk, deaths) in table {
+            survival *= 1.0 - deaths / at_risk;
+            if deaths < at_risk {
+                greenwood += deaths / (at_risk * (at_risk - deaths));
+            }
+            km.times.push(t);
+            km.at_risk.push(at_risk);
+            km.deaths.push(deaths);
+            km.survival.push(survival);
+            km.greenwood.push(greenwood);
+        }
+        Ok(km)
+    }
+
+    /// Returns the distinct event times in increasing order
+    pub fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Returns the number of subjects at risk at each event time
+    pub fn at_risk(&self) -> &[f64] {
+        &self.at_risk
+    }
+
+    /// Returns the number of events at each event time
+    pub fn deaths(&self) -> &[f64] {
+        &self.deaths
+    }
+
+    /// Returns the largest observed time, event or censored, beyond which
+    /// the survival function is not identified
+    pub fn last_time(&self) -> f64 {
+        self.last_time
+    }
+
+    /// Returns the estimated survival function at `t`
+    pub fn survival(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&s| s <= t);
+        if i == 0 {
+            1.0
+        } else {
+            self.survival[i - 1]
+        }
+    }
+
+    /// Returns Greenwood's estimate `S(t)^2 Σ d_i / (n_i (n_i - d_i))` of
+    /// the variance of the survival function at `t`
+    pub fn variance(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&s| s <= t);
+        if i == 0 {
+            0.0
+        } else {
+            fp::powi(self.survival[i - 1], 2) * self.greenwood[i - 1]
+        }
+    }
+}
+
+/// Implements the
+/// [Aalen-Johansen](https://en.wikipedia.org/wiki/Aalen%E2%80%93Johansen_estimator)
+/// estimator of the cumulative incidence functions of competing risks, the
+/// probability of having failed from each cause by a given time
+///
+/// # Formula
+///
+/// ```ignore
+/// F_k(t) = Σ_{t_i <= t} S(t_i-) d_ki / n_i
+/// ```
+///
+/// where `t_i` are the event times, `d_ki` the events from cause `k` among
+/// the `n_i` subjects at risk and `S` the Kaplan-Meier estimate of the
+/// probability of having failed from no cause
+///
+/// # Examples
+///
+/// ```
+/// use statrs::survival::AalenJohansen;
+///
+/// // causes 1 and 2, with 0 marking a censored time
+/// let times = [1.0, 2.0, 3.0, 4.0];
+/// let causes = [1, 2, 0, 1];
+/// let aj = AalenJohansen::new(&times, &causes).unwrap();
+/// assert_eq!(aj.cumulative_incidence(1, 1.0), 0.25);
+/// assert_eq!(aj.cumulative_incidence(2, 2.0), 0.25);
+/// assert_eq!(aj.cumulative_incidence(1, 4.0), 0.75);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AalenJohansen {
+    times: Vec<f64>,
+    survival: Vec<f64>,
+    incidence: Vec<Vec<f64>>,
+    size: usize,
+    censoring_times: Vec<f64>,
+    censoring: Vec<f64>,
+}
+
+impl AalenJohansen {
+    /// Constructs the estimator from the observed `times` and the cause of
+    /// failure of each, numbered from one, with zero marking a censored
+    /// time
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `times` and `causes` differ in length, if `times`
+    /// is empty or if any time is negative or not finite
+    pub fn new(times: &[f64], causes: &[usize]) -> Result<AalenJohansen> {
+        let order = sorted_order(times, causes.len())?;
+        let num_causes = causes.iter().cloned().max().unwrap_or(0);
+        let mut aj = AalenJohansen {
+            times: Vec::new(),
+            survival: Vec::new(),
+            incidence: vec![Vec::new(); num_causes],
+            size: order.len(),
+            censoring_times: Vec::new(),
+            censoring: Vec::new(),
+        };
+        let mut survival = 1.0;
+        let mut censoring = 1.0;
+        let mut incidence = vec![0.0; num_causes];
+        let mut deaths = vec![0.0; num_causes];
+        let mut i = 0;
+        while i < order.len() {
+            let t = times[order[i]];
+            let at_risk = (order.len() - i) as f64;
+            deaths.iter_mut().for_each(|d| *d = 0.0);
+            let mut censored = 0.0;
+            while i < order.len() && times[order[i]] == t {
+                if causes[order[i]] > 0 {
+                    deaths[causes[order[i]] - 1] += 1.0;
+                } else {
+                    censored += 1.0;
+                }
+                i += 1;
+            }
+            if censored > 0.0 {
+                censoring *= 1.0 - censored / at_risk;
+                aj.censoring_times.push(t);
+                aj.censoring.push(censoring);
+            }
+            let total: f64 = deaths.iter().sum();
+            if total == 0.0 {
+                continue;
+            }
+            for (k, &d) in deaths.iter().enumerate() {
+                incidence[k] += survival * d / at_risk;
+                aj.incidence[k].push(incidence[k]);
+            }
+            survival *= 1.0 - total / at_risk;
+            aj.times.push(t);
+            aj.survival.push(survival);
+        }
+        Ok(aj)
+    }
+
+    /// Returns the number of causes, the largest cause observed
+    pub fn causes(&self) -> usize {
+        self.incidence.len()
+    }
+
+    /// Returns the distinct times at which a failure from any cause was
+    /// observed, in increasing order
+    pub fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Returns the estimated probability of having failed from `cause` by
+    /// time `t`, which is zero for causes that were never observed
+    pub fn cumulative_incidence(&self, cause: usize, t: f64) -> f64 {
+        let i = self.times.partition_point(|&s| s <= t);
+        match self.incidence.get(cause.wrapping_sub(1)) {
+            Some(incidence) if i > 0 => incidence[i - 1],
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the estimated probability of not having failed from any
+    /// cause by time `t`, i.e. the Kaplan-Meier estimate treating all
+    /// causes alike
+    pub fn survival(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&s| s <= t);
+        if i == 0 {
+            1.0
+        } else {
+            self.survival[i - 1]
+        }
+    }
+
+    /// Returns the number of subjects, the Kaplan-Meier estimate of the
+    /// probability of remaining uncensored and the cumulative incidence of
+    /// `cause` just before `t`
+    fn before(&self, cause: usize, t: f64) -> (f64, f64, f64) {
+        let j = self.censoring_times.partition_point(|&s| s < t);
+        let censoring = if j == 0 { 1.0 } else { self.censoring[j - 1] };
+        let i = self.times.partition_point(|&s| s < t);
+        let incidence = match self.incidence.get(cause - 1) {
+            Some(incidence) if i > 0 => incidence[i - 1],
+            _ => 0.0,
+        };
+        (self.size as f64, censoring, incidence)
+    }
+}
+
+/// The kernel with which `KernelHazard` smooths the Nelson-Aalen
+/// increments
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum HazardKernel {
+    /// The Epanechnikov kernel `3 (1 - u^2) / 4` on `[-1, 1]`
+    Epanechnikov,
+    /// The uniform kernel `1 / 2` on `[-1, 1]`
+    Uniform,
+    /// The standard normal density
+    Gaussian,
+}
+
+impl HazardKernel {
+    /// Returns the kernel density at `u`
+    fn density(self, u: f64) -> f64 {
+        match self {
+            HazardKernel::Epanechnikov if u.abs() <= 1.0 => 0.75 * (1.0 - u * u),
+            HazardKernel::Uniform if u.abs() <= 1.0 => 0.5,
+            HazardKernel::Gaussian => fp::exp(-0.5 * u * u) / consts::SQRT_2PI,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the integral of the kernel density up to `u`
+    fn cdf(self, u: f64) -> f64 {
+        match self {
+            HazardKernel::Gaussian => 0.5 * erf::erfc(-u / f64::consts::SQRT_2),
+            _ if u <= -1.0 => 0.0,
+            _ if u >= 1.0 => 1.0,
+            HazardKernel::Epanechnikov => 0.5 + 0.75 * u - 0.25 * u * u * u,
+            HazardKernel::Uniform => 0.5 * (u + 1.0),
+        }
+    }
+
+    /// Returns the convolution of the kernel density with itself at `u`
+    fn convolution(self, u: f64) -> f64 {
+        let a = u.abs();
+        match self {
+            HazardKernel::Gaussian => fp::exp(-0.25 * u * u) / (2.0 * f64::consts::PI.sqrt()),
+            _ if a >= 2.0 => 0.0,
+            HazardKernel::Epanechnikov => {
+                3.0 / 160.0 * fp::powi(2.0 - a, 3) * (a * a + 6.0 * a + 4.0)
+            }
+            HazardKernel::Uniform => 0.25 * (2.0 - a),
+        }
+    }
+}
+
+/// Implements the Ramlau-Hansen kernel estimator of the hazard rate,
+/// which smooths the increments of the Nelson-Aalen estimator with a
+/// kernel of bandwidth `b`
+///
+/// # Formula
+///
+/// ```ignore
+/// h(t) = Σ K((t - t_i) / b) ΔH_i / b
+/// ```
+///
+/// where `t_i` are the event times, `ΔH_i` the Nelson-Aalen increments and
+/// `K` the kernel
+///
+/// # Remarks
+///
+/// No boundary correction is applied, so the estimate is biased downwards
+/// within one bandwidth of time zero and of the last event time. The
+/// smoothed survival function `exp(-∫_0^t h(s) ds)` accordingly only
+/// counts the kernel mass at positive times.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::survival::{HazardKernel, KernelHazard, NelsonAalen};
+///
+/// let times: Vec<f64> = (1..=100).map(|i| i as f64).collect();
+/// let events = vec![true; 100];
+/// let na = NelsonAalen::new(&times, &events).unwrap();
+/// let kh = KernelHazard::new(&na, HazardKernel::Epanechnikov, 10.0).unwrap();
+/// // at time 50 about one in fifty of the remaining subjects fails per unit time
+/// assert!((kh.hazard(50.0) - 1.0 / 51.0).abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct KernelHazard {
+    times: Vec<f64>,
+    increments: Vec<f64>,
+    variances: Vec<f64>,
+    kernel: HazardKernel,
+    bandwidth: f64,
+}
+
+impl KernelHazard {
+    /// Constructs the kernel hazard estimator smoothing the increments of
+    /// `estimator` with `kernel` and bandwidth `bandwidth`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bandwidth` is not positive and finite
+    pub fn new(
+        estimator: &NelsonAalen,
+        kernel: HazardKernel,
+        bandwidth: f64,
+    ) -> Result<KernelHazard> {
+        if !bandwidth.is_finite() || bandwidth <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("bandwidth"));
+        }
+        Ok(KernelHazard {
+            times: estimator.times.clone(),
+            increments: estimator.increments.clone(),
+            variances: estimator.variances.clone(),
+            kernel,
+            bandwidth,
+        })
+    }
+
+    /// Constructs the kernel hazard estimator with the bandwidth minimising
+    /// the least squares cross-validation criterion of Ramlau-Hansen
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// CV(b) = ∫ h_b(t)^2 dt - 2 Σ_{i != j} K((t_i - t_j) / b) ΔH_i ΔH_j / b
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// The criterion is minimised over 100 bandwidths spaced evenly on a log
+    /// scale between 1/100 and 1/2 of the range of the event times. Each
+    /// evaluation costs time quadratic in the number of event times for the
+    /// Gaussian kernel, and proportional to the number of pairs of event
+    /// times within two bandwidths of each other for the compact kernels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `estimator` has fewer than two distinct event
+    /// times
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::survival::{HazardKernel, KernelHazard, NelsonAalen};
+    ///
+    /// let times: Vec<f64> = (1..=200).map(|i| (i as f64 * 0.618).fract() * 10.0).collect();
+    /// let events: Vec<bool> = (0..200).map(|i| i % 4 != 0).collect();
+    /// let na = NelsonAalen::new(&times, &events).unwrap();
+    /// let kh = KernelHazard::cross_validated(&na, HazardKernel::Epanechnikov).unwrap();
+    /// assert!(kh.bandwidth() >= 0.1 && kh.bandwidth() <= 5.0);
+    /// ```
+    pub fn cross_validated(estimator: &NelsonAalen, kernel: HazardKernel) -> Result<KernelHazard> {
+        let t = &estimator.times;
+        let dh = &estimator.increments;
+        if t.len() < 2 {
+            return Err(StatsError::BadParams);
+        }
+        let range = t[t.len() - 1] - t[0];
+        let (lower, upper) = (0.01 * range, 0.5 * range);
+        let criterion = |b: f64| {
+            let mut cv = 0.0;
+            for i in 0..t.len() {
+                cv += kernel.convolution(0.0) * dh[i] * dh[i] / b;
+                for j in (0..i).rev() {
+                    let u = (t[i] - t[j]) / b;
+                    if u >= 2.0 && kernel != HazardKernel::Gaussian {
+                        // compact kernels and their convolutions vanish here
+                        break;
+                    }
+                    cv +=
+                        2.0 * dh[i] * dh[j] * (kernel.convolution(u) - 2.0 * kernel.density(u)) / b;
+                }
+            }
+            cv
+        };
+        let best = (0..100)
+            .map(|k| lower * fp::powf(upper / lower, k as f64 / 99.0))
+            .map(|b| (b, criterion(b)))
+            .fold((upper, f64::INFINITY), |best, (b, cv)| {
+                if cv < best.1 {
+                    (b, cv)
+                } else {
+                    best
+                }
+            });
+        KernelHazard::new(estimator, kernel, best.0)
+    }
+
+    /// Returns the kernel
+    pub fn kernel(&self) -> HazardKernel {
+        self.kernel
+    }
+
+    /// Returns the bandwidth
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Returns the smoothed hazard rate at `t`
+    pub fn hazard(&self, t: f64) -> f64 {
+        let b = self.bandwidth;
+        self.times
+            .iter()
+            .zip(self.increments.iter())
+            .map(|(&s, &dh)| self.kernel.density((t - s) / b) * dh)
+            .sum::<f64>()
+            / b
+    }
+
+    /// Returns the estimated variance `Σ K((t - t_i) / b)^2 d_i / n_i^2 / b^2`
+    /// of the smoothed hazard rate at `t`
+    pub fn variance(&self, t: f64) -> f64 {
+        let b = self.bandwidth;
+        self.times
+            .iter()
+            .zip(self.variances.iter())
+            .map(|(&s, &v)| fp::powi(self.kernel.density((t - s) / b), 2) * v)
+            .sum::<f64>()
+            / (b * b)
+    }
+
+    /// Returns the integral of the smoothed hazard rate from zero to `t`
+    pub fn cumulative_hazard(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        let b = self.bandwidth;
+        self.times
+            .iter()
+            .zip(self.increments.iter())
+            .map(|(&s, &dh)| (self.kernel.cdf((t - s) / b) - self.kernel.cdf(-s / b)) * dh)
+            .sum()
+    }
+
+    /// Returns the smoothed survival function `exp(-∫_0^t h(s) ds)` at `t`
+    pub fn survival(&self, t: f64) -> f64 {
+        fp::exp(-self.cumulative_hazard(t))
+    }
+}
+
+/// The restricted mean survival time up to a horizon and its variance, as
+/// returned by `rmst`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rmst {
+    tau: f64,
+    estimate: f64,
+    variance: f64,
+}
+
+impl Rmst {
+    /// Returns the horizon `τ`
+    pub fn tau(&self) -> f64 {
+        self.tau
+    }
+
+    /// Returns the estimated restricted mean survival time
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the estimated variance of the restricted mean survival time
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+
+    /// Returns the standard error of the restricted mean survival time
+    pub fn std_error(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+/// Computes the restricted mean survival time `∫_0^τ S(t) dt`, the
+/// expected survival time up to the horizon `tau`, under the Kaplan-Meier
+/// estimate `S` of the survival function
+///
+/// # Formula
+///
+/// ```ignore
+/// Var = Σ_{t_i < τ} A_i^2 d_i / (n_i (n_i - d_i)),    A_i = ∫_{t_i}^τ S(t) dt
+/// ```
+///
+/// where `t_i` are the event times with `d_i` events among `n_i` at risk
+///
+/// # Errors
+///
+/// Returns an error if `tau` is not positive or exceeds the largest
+/// observed time, beyond which the survival function is not identified
+///
+/// # Examples
+///
+/// ```
+/// use statrs::survival::{rmst, KaplanMeier};
+///
+/// // without censoring the restricted mean is the mean of min(T, τ)
+/// let km = KaplanMeier::new(&[1.0, 2.0, 3.0, 4.0], &[true; 4]).unwrap();
+/// let r = rmst(&km, 3.0).unwrap();
+/// assert_eq!(r.estimate(), (1.0 + 2.0 + 3.0 + 3.0) / 4.0);
+/// ```
+pub fn rmst(kaplan_meier: &KaplanMeier, tau: f64) -> Result<Rmst> {
+    if !tau.is_finite() || tau <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("tau"));
+    }
+    if tau > kaplan_meier.last_time {
+        return Err(StatsError::ArgLte("tau", kaplan_meier.last_time));
+    }
+    // the area under the survival curve up to each event time before τ
+    let mut areas = Vec::new();
+    let (mut area, mut previous, mut survival) = (0.0, 0.0, 1.0);
+    for (i, &t) in kaplan_meier.times.iter().enumerate() {
+        if t >= tau {
+            break;
+        }
+        area += survival * (t - previous);
+        areas.push(area);
+        previous = t;
+        survival = kaplan_meier.survival[i];
+    }
+    let estimate = area + survival * (tau - previous);
+    let variance = areas
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| kaplan_meier.deaths[i] < kaplan_meier.at_risk[i])
+        .map(|(i, &a)| {
+            let (n, d) = (kaplan_meier.at_risk[i], kaplan_meier.deaths[i]);
+            fp::powi(estimate - a, 2) * d / (n * (n - d))
+        })
+        .sum();
+    Ok(Rmst {
+        tau,
+        estimate,
+        variance,
+    })
+}
+
+/// The difference in restricted mean survival time between two groups, as
+/// returned by `rmst_difference`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RmstDifference {
+    estimate: f64,
+    std_error: f64,
+    p_value: f64,
+    confidence_interval: (f64, f64),
+}
+
+impl RmstDifference {
+    /// Returns the estimated difference, the first group's restricted mean
+    /// survival time less the second's
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the standard error of the difference
+    pub fn std_error(&self) -> f64 {
+        self.std_error
+    }
+
+    /// Returns the z statistic of the difference
+    pub fn z(&self) -> f64 {
+        self.estimate / self.std_error
+    }
+
+    /// Returns the two-sided p-value of the test of no difference
+    pub fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    /// Returns the confidence interval of the difference
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        self.confidence_interval
+    }
+}
+
+/// Compares the restricted mean survival times up to `tau` of two
+/// independent groups, with a two-sided z test of no difference and a
+/// confidence interval at level `confidence`
+///
+/// # Errors
+///
+/// Returns an error if `tau` is not positive or exceeds the largest
+/// observed time of either group, or if `confidence` is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::survival::{rmst_difference, KaplanMeier};
+///
+/// let events = [true, true, false, true, false, false];
+/// let treated = KaplanMeier::new(&[3.0, 5.0, 6.0, 8.0, 9.0, 10.0], &events).unwrap();
+/// let control = KaplanMeier::new(&[1.0, 2.0, 2.0, 4.0, 5.0, 10.0], &events).unwrap();
+/// let diff = rmst_difference(&treated, &control, 10.0, 0.95).unwrap();
+/// assert!(diff.estimate() > 0.0);
+/// let (lower, upper) = diff.confidence_interval();
+/// assert!(lower < diff.estimate() && diff.estimate() < upper);
+/// ```
+pub fn rmst_difference(
+    first: &KaplanMeier,
+    second: &KaplanMeier,
+    tau: f64,
+    confidence: f64,
+) -> Result<RmstDifference> {
+    if confidence <= 0.0 || confidence >= 1.0 || confidence.is_nan() {
+        return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+    }
+    let (a, b) = (rmst(first, tau)?, rmst(second, tau)?);
+    let estimate = a.estimate - b.estimate;
+    let std_error = (a.variance + b.variance).sqrt();
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let p_value = if std_error > 0.0 {
+        2.0 * normal.sf((estimate / std_error).abs())
+    } else if estimate == 0.0 {
+        1.0
+    } else {
+        0.0
+    };
+    let z = normal.inverse_cdf(0.5 + 0.5 * confidence);
+    Ok(RmstDifference {
+        estimate,
+        std_error,
+        p_value,
+        confidence_interval: (estimate - z * std_error, estimate + z * std_error),
+    })
+}
+
+/// The result of `gray_test`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrayTest {
+    statistic: f64,
+    df: u64,
+    p_value: f64,
+}
+
+impl GrayTest {
+    /// Returns the chi-squared test statistic
+    pub fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    /// Returns the degrees of freedom, one less than the number of groups
+    pub fn df(&self) -> u64 {
+        self.df
+    }
+
+    /// Returns the p-value of the test
+    pub fn p_value(&self) -> f64 {
+        self.p_value
+    }
+}
+
+/// Performs Gray's test of equal cumulative incidence of `cause` across the
+/// groups labelled by `groups`, from the observed `times` and the cause of
+/// failure of each, numbered from one, with zero marking a censored time
+///
+/// # Formula
+///
+/// ```ignore
+/// z_g = Σ_t (d_g(t) - R_g(t) d(t) / R(t)),    R_g(t) = n_g G_g(t-) (1 - F_g(t-))
+/// ```
+///
+/// where `d_g(t)` are the failures from `cause` among the `n_g` subjects
+/// of group `g` at time `t`, `G_g` the Kaplan-Meier estimate of remaining
+/// uncensored, `F_g` the Aalen-Johansen cumulative incidence of `cause`,
+/// and `d` and `R` the
+/// sums over groups. The statistic `z' V^-1 z` over all but the last group
+/// is compared with a chi-squared distribution with one degree of freedom
+/// fewer than the number of groups.
+///
+/// # Remarks
+///
+/// The subdistribution risk sets `R_g` keep subjects who failed from
+/// competing causes, weighted by their estimated probability of remaining
+/// uncensored. The covariance `V` is the hypergeometric log-rank covariance
+/// on these risk sets, which is exact without censoring, where the test is
+/// the log-rank test with competing failures never failing from `cause`,
+/// and otherwise approximates Gray's covariance estimator.
+///
+/// # Errors
+///
+/// Returns an error if the inputs differ in length, if `times` is empty or
+/// has a negative or non-finite value, if `cause` is zero, if there are
+/// fewer than two groups or if no failure from `cause` was observed
+///
+/// # Examples
+///
+/// ```
+/// use statrs::survival::gray_test;
+///
+/// let times = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+/// let causes = [1, 1, 2, 1, 0, 2, 1, 2, 1, 0];
+/// let groups = [0, 1, 0, 0, 1, 1, 0, 1, 1, 1];
+/// let test = gray_test(&times, &causes, &groups, 1).unwrap();
+/// assert_eq!(test.df(), 1);
+/// assert!(test.p_value() > 0.05);
+/// ```
+pub fn gray_test(
+    times: &[f64],
+    causes: &[usize],
+    groups: &[usize],
+    cause: usize,
+) -> Result<GrayTest> {
+    if groups.len() != times.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if cause == 0 {
+        return Err(StatsError::ArgMustBePositive("cause"));
+    }
+    let mut labels = groups.to_vec();
+    labels.sort_unstable();
+    labels.dedup();
+    if labels.len() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let estimators = labels
+        .iter()
+        .map(|&label| {
+            let (t, c): (Vec<f64>, Vec<usize>) = (0..times.len())
+                .filter(|&i| groups[i] == label)
+                .map(|i| (times[i], causes[i]))
+                .unzip();
+            AalenJohansen::new(&t, &c)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut event_times: Vec<f64> = (0..times.len())
+        .filter(|&i| causes[i] == cause)
+        .map(|i| times[i])
+        .collect();
+    if event_times.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    event_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    event_times.dedup();
+
+    let m = labels.len() - 1;
+    let mut z = DVector::<f64>::zeros(m);
+    let mut v = DMatrix::<f64>::zeros(m, m);
+    for &t in &event_times {
+        let (risk, deaths): (Vec<f64>, Vec<f64>) = labels
+            .iter()
+            .zip(estimators.iter())
+            .map(|(&label, aj)| {
+                let (size, censoring, incidence) = aj.before(cause, t);
+                let risk = size * censoring * (1.0 - incidence);
+                let deaths = (0..times.len())
+                    .filter(|&i| groups[i] == label && times[i] == t && causes[i] == cause)
+                    .count() as f64;
+                (risk, deaths)
+            })
+            .unzip();
+        let total_risk: f64 = risk.iter().sum();
+        let total_deaths: f64 = deaths.iter().sum();
+        let ties = if total_risk > 1.0 {
+            (total_risk - total_deaths) / (total_risk - 1.0)
+        } else {
+            1.0
+        };
+        for g in 0..m {
+            let share = risk[g] / total_risk;
+            z[g] += deaths[g] - total_deaths * share;
+            for h in 0..m {
+                let delta = if g == h { 1.0 } else { 0.0 };
+                v[(g, h)] += total_deaths * share * (delta - risk[h] / total_risk) * ties;
+            }
+        }
+    }
+    let statistic = match v.clone().try_inverse() {
+        Some(inverse) => (z.transpose() * inverse * &z)[(0, 0)],
+        None => return Err(StatsError::BadParams),
+    };
+    let df = m as u64;
+    let p_value = ChiSquared::new(df as f64).unwrap().sf(statistic);
+    Ok(GrayTest {
+        statistic,
+        df,
+        p_value,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Exp;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::Rng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_nelson_aalen() {
+        let times = [1.0, 2.0, 2.0, 3.0, 4.0, 5.0];
+        let events = [true, true, false, true, false, true];
+        let na = NelsonAalen::new(&times, &events).unwrap();
+        assert_eq!(na.times(), &[1.0, 2.0, 3.0, 5.0]);
+        assert_eq!(na.cumulative_hazard(0.5), 0.0);
+        assert_almost_eq!(na.cumulative_hazard(2.0), 1.0 / 6.0 + 1.0 / 5.0, 1e-15);
+        assert_almost_eq!(na.cumulative_hazard(4.9), 1.0 / 6.0 + 1.0 / 5.0 + 1.0 / 3.0, 1e-15);
+        assert_almost_eq!(na.cumulative_hazard(5.0), 1.0 / 6.0 + 1.0 / 5.0 + 1.0 / 3.0 + 1.0, 1e-15);
+        assert_almost_eq!(na.variance(3.0), 1.0 / 36.0 + 1.0 / 25.0 + 1.0 / 9.0, 1e-15);
+        assert_almost_eq!(na.survival(1.0), (-1.0f64 / 6.0).exp(), 1e-15);
+        // tied events share the risk set
+        let tied = NelsonAalen::new(&[2.0, 2.0, 3.0], &[true, true, true]).unwrap();
+        assert_eq!(tied.increments(), &[2.0 / 3.0, 1.0]);
+
+        assert!(NelsonAalen::new(&[1.0], &[true, false]).is_err());
+        assert!(NelsonAalen::new(&[], &[]).is_err());
+        assert!(NelsonAalen::new(&[-1.0], &[true]).is_err());
+        assert!(NelsonAalen::new(&[f64::NAN], &[true]).is_err());
+    }
+
+    #[test]
+    fn test_kaplan_meier() {
+        let times = [1.0, 2.0, 2.0, 3.0, 4.0, 5.0];
+        let events = [true, true, false, true, false, true];
+        let km = KaplanMeier::new(&times, &events).unwrap();
+        assert_eq!(km.times(), &[1.0, 2.0, 3.0, 5.0]);
+        assert_eq!(km.at_risk(), &[6.0, 5.0, 3.0, 1.0]);
+        assert_eq!(km.deaths(), &[1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(km.last_time(), 5.0);
+        assert_eq!(km.survival(0.5), 1.0);
+        assert_almost_eq!(km.survival(2.0), 2.0 / 3.0, 1e-15);
+        assert_almost_eq!(km.survival(4.0), 4.0 / 9.0, 1e-15);
+        assert_eq!(km.survival(5.0), 0.0);
+        assert_eq!(km.variance(0.5), 0.0);
+        assert_almost_eq!(km.variance(2.0), 4.0 / 9.0 * (1.0 / 30.0 + 1.0 / 20.0), 1e-15);
+        assert!(KaplanMeier::new(&[1.0, 2.0], &[true]).is_err());
+    }
+
+    #[test]
+    fn test_rmst() {
+        let times = [1.0, 2.0, 2.0, 3.0, 4.0, 5.0];
+        let events = [true, true, false, true, false, true];
+        let km = KaplanMeier::new(&times, &events).unwrap();
+        // reference values from exact rational arithmetic
+        let r = rmst(&km, 4.0).unwrap();
+        assert_eq!(r.tau(), 4.0);
+        assert_almost_eq!(r.estimate(), 53.0 / 18.0, 1e-15);
+        assert_almost_eq!(r.variance(), 143.0 / 648.0, 1e-15);
+        assert_almost_eq!(r.std_error(), (143.0f64 / 648.0).sqrt(), 1e-15);
+        let r = rmst(&km, 2.5).unwrap();
+        assert_almost_eq!(r.estimate(), 13.0 / 6.0, 1e-15);
+        assert_almost_eq!(r.variance(), 11.0 / 216.0, 1e-15);
+        // the whole follow-up with the last subject dying
+        assert!(rmst(&km, 5.0).unwrap().variance().is_finite());
+
+        // without censoring, the variance is that of the mean of min(T, τ)
+        let km = KaplanMeier::new(&[1.0, 2.0, 3.0, 4.0], &[true; 4]).unwrap();
+        let r = rmst(&km, 4.0).unwrap();
+        assert_eq!(r.estimate(), 2.5);
+        assert_almost_eq!(r.variance(), 1.25 / 4.0, 1e-15);
+
+        assert!(rmst(&km, 0.0).is_err());
+        assert!(rmst(&km, f64::NAN).is_err());
+        assert!(matches!(rmst(&km, 4.5), Err(StatsError::ArgLte("tau", _))));
+    }
+
+    #[test]
+    fn test_rmst_difference() {
+        // exponential lifetimes with uniform censoring, where the restricted
+        // mean survival time is (1 - e^(-λτ)) / λ
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut group = |rate: f64| {
+            let life = Exp::new(rate).unwrap();
+            let (mut times, mut events) = (Vec::new(), Vec::new());
+            for _ in 0..1000 {
+                let (x, c) = (life.sample(&mut rng), rng.gen_range(2.0..6.0));
+                times.push(x.min(c));
+                events.push(x <= c);
+            }
+            KaplanMeier::new(&times, &events).unwrap()
+        };
+        let (slow, fast) = (group(0.5), group(1.0));
+        let tau = 2.0;
+        let diff = rmst_difference(&slow, &fast, tau, 0.95).unwrap();
+        let expected = (1.0 - (-0.5 * tau).exp()) / 0.5 - (1.0 - (-tau).exp());
+        let (lower, upper) = diff.confidence_interval();
+        assert!(lower < expected && expected < upper);
+        assert!(diff.p_value() < 1e-6);
+        assert_almost_eq!(diff.z(), diff.estimate() / diff.std_error(), 1e-15);
+
+        let same = rmst_difference(&slow, &slow, tau, 0.95).unwrap();
+        assert_eq!(same.estimate(), 0.0);
+        assert_eq!(same.p_value(), 1.0);
+        assert!(rmst_difference(&slow, &fast, tau, 1.0).is_err());
+        assert!(rmst_difference(&slow, &fast, 100.0, 0.95).is_err());
+    }
+
+    #[test]
+    fn test_aalen_johansen() {
+        let times = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let causes = [1, 2, 0, 1, 2, 1];
+        let aj = AalenJohansen::new(&times, &causes).unwrap();
+        assert_eq!(aj.causes(), 2);
+        assert_eq!(aj.times(), &[1.0, 2.0, 4.0, 5.0, 6.0]);
+        // reference values from exact rational arithmetic
+        assert_almost_eq!(aj.cumulative_incidence(1, 3.0), 1.0 / 6.0, 1e-15);
+        assert_almost_eq!(aj.cumulative_incidence(1, 4.0), 7.0 / 18.0, 1e-15);
+        assert_almost_eq!(aj.cumulative_incidence(2, 5.0), 7.0 / 18.0, 1e-15);
+        assert_almost_eq!(aj.cumulative_incidence(1, 6.0), 11.0 / 18.0, 1e-15);
+        assert_eq!(aj.cumulative_incidence(1, 0.5), 0.0);
+        assert_eq!(aj.cumulative_incidence(0, 6.0), 0.0);
+        assert_eq!(aj.cumulative_incidence(3, 6.0), 0.0);
+        for &t in [0.5, 1.0, 2.5, 4.0, 5.5, 6.0].iter() {
+            let total = aj.survival(t) + aj.cumulative_incidence(1, t) + aj.cumulative_incidence(2, t);
+            assert_almost_eq!(total, 1.0, 1e-15);
+        }
+        // with a single cause the incidence complements Kaplan-Meier
+        let events = [true, true, false, true, false, true];
+        let single: Vec<usize> = events.iter().map(|&e| e as usize).collect();
+        let aj = AalenJohansen::new(&times, &single).unwrap();
+        let km = KaplanMeier::new(&times, &events).unwrap();
+        for &t in times.iter() {
+            assert_almost_eq!(aj.cumulative_incidence(1, t), 1.0 - km.survival(t), 1e-15);
+        }
+        assert!(AalenJohansen::new(&[1.0], &[1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_gray_test() {
+        // without competing events or censoring it is the log-rank test,
+        // with reference values from exact rational arithmetic
+        let times = [1.0, 3.0, 4.0, 6.0, 8.0, 2.0, 5.0, 7.0, 9.0, 10.0, 11.0];
+        let groups = [0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1];
+        let test = gray_test(&times, &[1; 11], &groups, 1).unwrap();
+        assert_almost_eq!(test.statistic(), 2.9224341527338975, 1e-12);
+        assert_eq!(test.df(), 1);
+        // and with competing events, those never fail from the cause tested
+        let causes = [1, 2, 1, 1, 2, 1, 2, 1, 1, 1, 2];
+        let test = gray_test(&times, &causes, &groups, 1).unwrap();
+        assert_almost_eq!(test.statistic(), 0.05761201407385379, 1e-12);
+
+        // three groups with censoring, differing in the incidence of cause 1
+        let mut rng = StdRng::seed_from_u64(4);
+        let (mut times, mut causes, mut groups) = (Vec::new(), Vec::new(), Vec::new());
+        for g in 0..3 {
+            let rate_1 = if g == 2 { 1.0 } else { 0.5 };
+            for _ in 0..300 {
+                let t1 = Exp::new(rate_1).unwrap().sample(&mut rng);
+                let t2 = Exp::new(0.5).unwrap().sample(&mut rng);
+                let c = rng.gen_range(0.5..4.0);
+                let t = t1.min(t2).min(c);
+                times.push(t);
+                causes.push(if t == c { 0 } else if t == t1 { 1 } else { 2 });
+                groups.push(g);
+            }
+        }
+        let test = gray_test(&times, &causes, &groups, 1).unwrap();
+        assert_eq!(test.df(), 2);
+        assert!(test.p_value() < 1e-6);
+        let (same_t, same_c, same_g): (Vec<f64>, Vec<usize>, Vec<usize>) = (0..times.len())
+            .filter(|&i| groups[i] < 2)
+            .map(|i| (times[i], causes[i], groups[i]))
+            .fold((vec![], vec![], vec![]), |mut acc, (t, c, g)| { acc.0.push(t); acc.1.push(c); acc.2.push(g); acc });
+        assert!(gray_test(&same_t, &same_c, &same_g, 1).unwrap().p_value() > 0.01);
+
+        assert!(gray_test(&[1.0, 2.0], &[1, 1], &[0, 0], 1).is_err());
+        assert!(gray_test(&[1.0, 2.0], &[1, 1], &[0, 1], 0).is_err());
+        assert!(gray_test(&[1.0, 2.0], &[2, 2], &[0, 1], 1).is_err());
+        assert!(gray_test(&[1.0, 2.0], &[1, 1], &[0], 1).is_err());
+    }
+
+    #[test]
+    fn test_kernels() {
+        for &k in [HazardKernel::Epanechnikov, HazardKernel::Uniform, HazardKernel::Gaussian].iter() {
+            let h = 1e-3;
+            let grid = || (-6000..6000).map(|i| (i as f64 + 0.5) * h);
+            // the density integrates to one and to its cdf
+            assert_almost_eq!(grid().map(|u| k.density(u) * h).sum::<f64>(), 1.0, 1e-6);
+            assert_almost_eq!(grid().take_while(|&u| u < 0.3).map(|u| k.density(u) * h).sum::<f64>(), k.cdf(0.3), 1e-3);
+            // the self-convolution at u = 0.7
+            let conv = grid().map(|v| k.density(v) * k.density(0.7 - v) * h).sum::<f64>();
+            assert_almost_eq!(conv, k.convolution(0.7), 1e-3);
+            assert_almost_eq!(grid().map(|v| k.density(v).powi(2) * h).sum::<f64>(), k.convolution(0.0), 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_kernel_hazard_constant() {
+        // exponential lifetimes with independent exponential censoring
+        let mut rng = StdRng::seed_from_u64(0);
+        let life = Exp::new(0.5).unwrap();
+        let censor = Exp::new(0.2).unwrap();
+        let (mut times, mut events) = (Vec::new(), Vec::new());
+        for _ in 0..3000 {
+            let (x, c) = (life.sample(&mut rng), censor.sample(&mut rng));
+            times.push(x.min(c));
+            events.push(x <= c);
+        }
+        let na = NelsonAalen::new(&times, &events).unwrap();
+        let kh = KernelHazard::new(&na, HazardKernel::Epanechnikov, 0.5).unwrap();
+        for &t in [1.0, 2.0, 3.0].iter() {
+            assert_almost_eq!(kh.hazard(t), 0.5, 0.06);
+            assert!(kh.variance(t) > 0.0 && kh.variance(t) < 0.01);
+            assert_almost_eq!(kh.survival(t), (-0.5 * t).exp(), 0.04);
+            // trailing the step estimate by the kernel mass lost below zero, h b 3 / 16
+            assert_almost_eq!(na.cumulative_hazard(t) - kh.cumulative_hazard(t), 0.5 * 0.5 * 3.0 / 16.0, 0.04);
+        }
+        // the estimate is halved at the origin without boundary correction
+        assert!(kh.hazard(0.0) < 0.35);
+        assert_eq!(kh.cumulative_hazard(-1.0), 0.0);
+
+        let na = NelsonAalen::new(&times[..500], &events[..500]).unwrap();
+        let cv = KernelHazard::cross_validated(&na, HazardKernel::Epanechnikov).unwrap();
+        assert!(cv.bandwidth() > 0.1);
+        assert_almost_eq!(cv.hazard(2.0), 0.5, 0.15);
+        let cv = KernelHazard::cross_validated(&na, HazardKernel::Gaussian).unwrap();
+        assert_eq!(cv.kernel(), HazardKernel::Gaussian);
+        assert!(cv.bandwidth() > 0.1);
+        assert_almost_eq!(cv.hazard(2.0), 0.5, 0.15);
+    }
+
+    #[test]
+    fn test_kernel_hazard_errors() {
+        let na = NelsonAalen::new(&[1.0, 2.0], &[true, false]).unwrap();
+        assert!(KernelHazard::new(&na, HazardKernel::Uniform, 0.0).is_err());
+        assert!(KernelHazard::new(&na, HazardKernel::Uniform, f64::INFINITY).is_err());
+        assert!(KernelHazard::cross_validated(&na, HazardKernel::Uniform).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+//! Provides data transforms whose parameters are learned from one data set
+//! and can then be reapplied unchanged to new data, e.g. to preprocess a
+//! training and a test split identically, together with rank-based
+//! normalizations and randomized quantizers
+pub use self::normalize::{quantile_normalize, rank_inverse_normal, RankScore};
+pub use self::power::{box_cox, yeo_johnson, BoxCox, YeoJohnson};
+pub use self::quantize::{Dither, StochasticRounder};
+pub use self::winsorize::{Clipper, Winsorizer};
+
+mod normalize;
+mod power;
+mod quantize;
+mod winsorize;
+
+/// The `Transform` trait provides an interface for element-wise data
+/// transforms with fixed (typically fitted) parameters
+pub trait Transform {
+    /// Applies the transform to a single value `x`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::{Clipper, Transform};
+    ///
+    /// let c = Clipper::new(0.0, 1.0).unwrap();
+    /// assert_eq!(c.apply(1.5), 1.0);
+    /// ```
+    fn apply(&self, x: f64) -> f64;
+
+    /// Applies the transform to every element of `data`, returning the
+    /// transformed values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::{Clipper, Transform};
+    ///
+    /// let c = Clipper::new(0.0, 1.0).unwrap();
+    /// assert_eq!(c.transform(&[-1.0, 0.5, 2.0]), vec![0.0, 0.5, 1.0]);
+    /// ```
+    fn transform(&self, data: &[f64]) -> Vec<f64> {
+        data.iter().map(|&x| self.apply(x)).collect()
+    }
+
+    /// Applies the transform to every element of `data` in place
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::{Clipper, Transform};
+    ///
+    /// let c = Clipper::new(0.0, 1.0).unwrap();
+    /// let mut data = [-1.0, 0.5, 2.0];
+    /// c.transform_inplace(&mut data);
+    /// assert_eq!(data, [0.0, 0.5, 1.0]);
+    /// ```
+    fn transform_inplace(&self, data: &mut [f64]) {
+        for x in data.iter_mut() {
+            *x = self.apply(*x);
+        }
+    }
+}
+
+/// The `InvertibleTransform` trait provides an interface for element-wise
+/// transforms that can be undone, e.g. to map predictions made on the
+/// transformed scale back to the original scale
+pub trait InvertibleTransform: Transform {
+    /// Applies the inverse transform to a single value `y`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::{BoxCox, InvertibleTransform};
+    ///
+    /// let bc = BoxCox::new(0.0).unwrap();
+    /// assert_eq!(bc.invert(0.0), 1.0);
+    /// ```
+    fn invert(&self, y: f64) -> f64;
+
+    /// Applies the inverse transform to every element of `data`, returning
+    /// the values on the original scale
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::{BoxCox, InvertibleTransform};
+    ///
+    /// let bc = BoxCox::new(0.0).unwrap();
+    /// assert_eq!(bc.inverse_transform(&[0.0, 0.0]), vec![1.0, 1.0]);
+    /// ```
+    fn inverse_transform(&self, data: &[f64]) -> Vec<f64> {
+        data.iter().map(|&y| self.invert(y)).collect()
+    }
+}
@@ -0,0 +1,208 @@
+use crate::distribution::{ContinuousCDF, Normal};
+use crate::statistics::{Data, OrderStatistics, RankTieBreaker};
+use crate::{Result, StatsError};
+
+/// Specifies how ranks are converted into probabilities before applying the
+/// inverse normal cdf in `rank_inverse_normal`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RankScore {
+    /// Blom scores, `(r - 3 / 8) / (n + 1 / 4)`
+    Blom,
+    /// Van der Waerden scores, `r / (n + 1)`
+    VanDerWaerden,
+}
+
+impl RankScore {
+    fn probability(self, rank: f64, n: f64) -> f64 {
+        match self {
+            RankScore::Blom => (rank - 0.375) / (n + 0.25),
+            RankScore::VanDerWaerden => rank / (n + 1.0),
+        }
+    }
+}
+
+/// Applies the rank-based inverse normal transform to `data`, replacing each
+/// value by the standard normal quantile of its rank score so that the
+/// result is approximately standard normally distributed regardless of the
+/// distribution of `data`
+///
+/// # Remarks
+///
+/// Tied values receive the average of their ranks and therefore the same
+/// transformed value
+///
+/// # Formula
+///
+/// ```ignore
+/// Φ^-1(p(r_i, n))
+/// ```
+///
+/// where `r_i` is the rank of the `i`th value, `n` the number of values,
+/// `p` the rank score (see `RankScore`) and `Φ^-1` the inverse cdf of the
+/// standard normal distribution
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or contains a `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::transform::{rank_inverse_normal, RankScore};
+///
+/// let z = rank_inverse_normal(&[3.0, 100.0, -2.0], RankScore::VanDerWaerden).unwrap();
+/// assert!((z[0] - 0.0).abs() < 1e-15);
+/// assert!((z[1] - 0.6744897501960817).abs() < 1e-15);
+/// assert!((z[2] + 0.6744897501960817).abs() < 1e-15);
+/// ```
+pub fn rank_inverse_normal(data: &[f64], score: RankScore) -> Result<Vec<f64>> {
+    if data.is_empty() || data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let n = data.len() as f64;
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let ranks = Data::new(data.to_vec()).ranks(RankTieBreaker::Average);
+    Ok(ranks
+        .into_iter()
+        .map(|r| normal.inverse_cdf(score.probability(r, n)))
+        .collect())
+}
+
+/// Applies [quantile
+/// normalization](https://en.wikipedia.org/wiki/Quantile_normalization) to
+/// `arrays`, making their empirical distributions identical while
+/// preserving the ordering of the values within each array
+///
+/// # Remarks
+///
+/// The reference distribution is the mean across arrays of the sorted
+/// values, i.e. the `k`th smallest value of every array is replaced by the
+/// mean of the `k`th smallest values of all arrays. Tied values within an
+/// array are replaced by the mean of the reference values over the ranks
+/// they occupy so that they stay tied.
+///
+/// # Errors
+///
+/// Returns an error if `arrays` is empty, if the arrays are empty or do not
+/// all have the same length, or if any value is `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::transform::quantile_normalize;
+///
+/// let normalized = quantile_normalize(&[vec![5.0, 2.0, 3.0], vec![4.0, 1.0, 6.0]]).unwrap();
+/// assert_eq!(normalized, vec![vec![5.5, 1.5, 3.5], vec![3.5, 1.5, 5.5]]);
+/// ```
+pub fn quantile_normalize<T: AsRef<[f64]>>(arrays: &[T]) -> Result<Vec<Vec<f64>>> {
+    let n = match arrays.first() {
+        Some(first) if !first.as_ref().is_empty() => first.as_ref().len(),
+        _ => return Err(StatsError::BadParams),
+    };
+    if arrays.iter().any(|a| a.as_ref().len() != n) {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if arrays.iter().any(|a| a.as_ref().iter().any(|x| x.is_nan())) {
+        return Err(StatsError::BadParams);
+    }
+
+    let orders: Vec<Vec<usize>> = arrays.iter().map(|a| sort_order(a.as_ref())).collect();
+    let m = arrays.len() as f64;
+    let mut reference = vec![0.0; n];
+    for (a, order) in arrays.iter().zip(orders.iter()) {
+        for (r, &i) in reference.iter_mut().zip(order.iter()) {
+            *r += a.as_ref()[i] / m;
+        }
+    }
+
+    Ok(arrays
+        .iter()
+        .zip(orders.iter())
+        .map(|(a, order)| {
+            let a = a.as_ref();
+            let mut out = vec![0.0; n];
+            let mut start = 0;
+            while start < n {
+                let mut end = start + 1;
+                while end < n && a[order[end]] == a[order[start]] {
+                    end += 1;
+                }
+                let value = reference[start..end].iter().sum::<f64>() / (end - start) as f64;
+                for &i in &order[start..end] {
+                    out[i] = value;
+                }
+                start = end;
+            }
+            out
+        })
+        .collect())
+}
+
+/// Returns the indices of `data` in ascending order of their values
+fn sort_order(data: &[f64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..data.len()).collect();
+    order.sort_by(|&i, &j| data[i].partial_cmp(&data[j]).unwrap());
+    order
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_inverse_normal_blom() {
+        let z = rank_inverse_normal(&[10.0, 20.0, 30.0, 40.0], RankScore::Blom).unwrap();
+        // Φ^-1((r - 3/8) / 4.25)
+        assert_almost_eq!(z[0], -1.0491313979639711, 1e-12);
+        assert_almost_eq!(z[1], -0.29930691046566704, 1e-12);
+        assert_almost_eq!(z[2], 0.29930691046566704, 1e-12);
+        assert_almost_eq!(z[3], 1.0491313979639711, 1e-12);
+    }
+
+    #[test]
+    fn test_rank_inverse_normal_ties() {
+        let z = rank_inverse_normal(&[1.0, 2.0, 2.0, 3.0], RankScore::VanDerWaerden).unwrap();
+        assert_eq!(z[1], z[2]);
+        assert_eq!(z[1], 0.0);
+        assert_almost_eq!(z[0], -z[3], 1e-15);
+    }
+
+    #[test]
+    fn test_rank_inverse_normal_bad_input() {
+        assert!(rank_inverse_normal(&[], RankScore::Blom).is_err());
+        assert!(rank_inverse_normal(&[1.0, f64::NAN], RankScore::Blom).is_err());
+    }
+
+    #[test]
+    fn test_quantile_normalize() {
+        let arrays = vec![
+            vec![5.0, 2.0, 3.0, 4.0],
+            vec![4.0, 1.0, 4.0, 2.0],
+            vec![3.0, 4.0, 6.0, 8.0],
+        ];
+        // sorted columns: [2, 3, 4, 5], [1, 2, 4, 4], [3, 4, 6, 8]
+        // reference: [2, 3, 14 / 3, 17 / 3]
+        let normalized = quantile_normalize(&arrays).unwrap();
+        let expected = [
+            [17.0 / 3.0, 2.0, 3.0, 14.0 / 3.0],
+            [31.0 / 6.0, 2.0, 31.0 / 6.0, 3.0],
+            [2.0, 3.0, 14.0 / 3.0, 17.0 / 3.0],
+        ];
+        for (a, b) in normalized.iter().zip(expected.iter()) {
+            for (x, y) in a.iter().zip(b.iter()) {
+                assert_almost_eq!(*x, *y, 1e-14);
+            }
+        }
+        assert_eq!(normalized[1][0], normalized[1][2]);
+    }
+
+    #[test]
+    fn test_quantile_normalize_bad_input() {
+        let empty: Vec<Vec<f64>> = vec![];
+        assert!(quantile_normalize(&empty).is_err());
+        assert!(quantile_normalize(&[vec![], vec![]]).is_err());
+        assert!(quantile_normalize(&[vec![1.0], vec![1.0, 2.0]]).is_err());
+        assert!(quantile_normalize(&[vec![1.0], vec![f64::NAN]]).is_err());
+    }
+}
@@ -0,0 +1,476 @@
+use crate::fp;
+use crate::function::{erf, optimize};
+use crate::transform::{InvertibleTransform, Transform};
+use crate::{Result, StatsError};
+
+/// Fits the [Box-Cox](https://en.wikipedia.org/wiki/Power_transform#Box%E2%80%93Cox_transformation)
+/// transform to `data` and returns the transformed data together with the
+/// fitted transform.
+/// This is a convenience wrapper around `BoxCox::fit`.
+///
+/// # Errors
+///
+/// Returns an error under the same conditions as `BoxCox::fit`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::transform::box_cox;
+///
+/// let (transformed, bc) = box_cox(&[1.0, 2.0, 4.0, 8.0, 16.0]).unwrap();
+/// assert!(bc.lambda().abs() < 0.1);
+/// assert_eq!(transformed.len(), 5);
+/// ```
+pub fn box_cox(data: &[f64]) -> Result<(Vec<f64>, BoxCox)> {
+    let bc = BoxCox::fit(data)?;
+    Ok((bc.transform(data), bc))
+}
+
+/// Fits the [Yeo-Johnson](https://en.wikipedia.org/wiki/Power_transform#Yeo%E2%80%93Johnson_transformation)
+/// transform to `data` and returns the transformed data together with the
+/// fitted transform.
+/// This is a convenience wrapper around `YeoJohnson::fit`.
+///
+/// # Errors
+///
+/// Returns an error under the same conditions as `YeoJohnson::fit`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::transform::yeo_johnson;
+///
+/// let (transformed, yj) = yeo_johnson(&[-1.0, 0.0, 1.0, 3.0, 7.0, 15.0]).unwrap();
+/// assert!(yj.lambda() < 1.0);
+/// assert_eq!(transformed.len(), 6);
+/// ```
+pub fn yeo_johnson(data: &[f64]) -> Result<(Vec<f64>, YeoJohnson)> {
+    let yj = YeoJohnson::fit(data)?;
+    Ok((yj.transform(data), yj))
+}
+
+/// Implements the Box-Cox power transform for strictly positive data
+///
+/// # Formula
+///
+/// ```ignore
+/// (x^λ - 1) / λ   if λ != 0
+/// ln(x)           if λ == 0
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::transform::{BoxCox, InvertibleTransform, Transform};
+///
+/// let bc = BoxCox::new(0.5).unwrap();
+/// assert_eq!(bc.apply(4.0), 2.0);
+/// assert_eq!(bc.invert(2.0), 4.0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoxCox {
+    lambda: f64,
+}
+
+impl BoxCox {
+    /// Constructs a new Box-Cox transform with power parameter `lambda`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lambda` is `NaN` or infinite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::BoxCox;
+    ///
+    /// let mut result = BoxCox::new(0.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = BoxCox::new(f64::NAN);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(lambda: f64) -> Result<BoxCox> {
+        if lambda.is_finite() {
+            Ok(BoxCox { lambda })
+        } else {
+            Err(StatsError::BadParams)
+        }
+    }
+
+    /// Estimates `lambda` by maximizing the profile log-likelihood
+    /// `BoxCox::ln_likelihood` of `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` contains fewer than two distinct values,
+    /// a non-positive, infinite or `NaN` value, or if the maximization fails
+    /// to converge
+    pub fn fit(data: &[f64]) -> Result<BoxCox> {
+        check_data(data, true)?;
+        let lambda = fit_lambda(|l| BoxCox::ln_likelihood(data, l))?;
+        Ok(BoxCox { lambda })
+    }
+
+    /// Returns the power parameter
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+
+    /// Returns the profile log-likelihood of `lambda` for `data`, assuming
+    /// the transformed data is normally distributed, up to an additive
+    /// constant. Returns `f64::NAN` if `data` contains a non-positive value.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -(n / 2) * ln(σ^2(λ)) + (λ - 1) * Σ ln(x_i)
+    /// ```
+    ///
+    /// where `σ^2(λ)` is the (biased) variance of the transformed data
+    pub fn ln_likelihood(data: &[f64], lambda: f64) -> f64 {
+        let bc = BoxCox { lambda };
+        let jacobian: f64 = data.iter().map(|x| fp::ln(*x)).sum();
+        profile_ln_likelihood(&bc, data, jacobian * (lambda - 1.0))
+    }
+
+    /// Returns the profile-likelihood confidence interval of `lambda` at
+    /// confidence level `level` for `data`, i.e. the set of `lambda` whose
+    /// likelihood-ratio statistic against the maximum is below the `level`
+    /// quantile of the chi-squared distribution with one degree of freedom.
+    /// A bound is infinite if the likelihood does not drop far enough on
+    /// that side.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)` or under the same
+    /// conditions as `BoxCox::fit`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::BoxCox;
+    ///
+    /// let data = [0.5, 1.0, 1.5, 2.5, 3.0, 4.5, 6.0, 9.0, 12.0, 20.0];
+    /// let bc = BoxCox::fit(&data).unwrap();
+    /// let (lo, hi) = BoxCox::lambda_confidence_interval(&data, 0.95).unwrap();
+    /// assert!(lo < bc.lambda() && bc.lambda() < hi);
+    /// ```
+    pub fn lambda_confidence_interval(data: &[f64], level: f64) -> Result<(f64, f64)> {
+        let bc = BoxCox::fit(data)?;
+        profile_interval(|l| BoxCox::ln_likelihood(data, l), bc.lambda, level)
+    }
+}
+
+impl Transform for BoxCox {
+    /// Transforms `x`, returning `f64::NAN` if `x` is negative
+    fn apply(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            f64::NAN
+        } else if self.lambda == 0.0 {
+            fp::ln(x)
+        } else {
+            fp::exp_m1(self.lambda * fp::ln(x)) / self.lambda
+        }
+    }
+}
+
+impl InvertibleTransform for BoxCox {
+    /// Inverts the transform at `y`, returning `f64::NAN` if `y` lies
+    /// outside the image of the transform
+    fn invert(&self, y: f64) -> f64 {
+        if self.lambda == 0.0 {
+            fp::exp(y)
+        } else {
+            let t = fp::ln_1p(self.lambda * y);
+            if t.is_nan() {
+                f64::NAN
+            } else {
+                fp::exp(t / self.lambda)
+            }
+        }
+    }
+}
+
+/// Implements the Yeo-Johnson power transform, an extension of the Box-Cox
+/// transform to data on the whole real line
+///
+/// # Formula
+///
+/// ```ignore
+/// ((x + 1)^λ - 1) / λ                  if x >= 0, λ != 0
+/// ln(x + 1)                            if x >= 0, λ == 0
+/// -((1 - x)^(2 - λ) - 1) / (2 - λ)     if x < 0, λ != 2
+/// -ln(1 - x)                           if x < 0, λ == 2
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::transform::{InvertibleTransform, Transform, YeoJohnson};
+///
+/// let yj = YeoJohnson::new(1.0).unwrap();
+/// assert_eq!(yj.apply(-3.0), -3.0);
+/// assert_eq!(yj.invert(5.0), 5.0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct YeoJohnson {
+    lambda: f64,
+}
+
+impl YeoJohnson {
+    /// Constructs a new Yeo-Johnson transform with power parameter `lambda`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lambda` is `NaN` or infinite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::YeoJohnson;
+    ///
+    /// let mut result = YeoJohnson::new(2.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = YeoJohnson::new(f64::INFINITY);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(lambda: f64) -> Result<YeoJohnson> {
+        if lambda.is_finite() {
+            Ok(YeoJohnson { lambda })
+        } else {
+            Err(StatsError::BadParams)
+        }
+    }
+
+    /// Estimates `lambda` by maximizing the profile log-likelihood
+    /// `YeoJohnson::ln_likelihood` of `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` contains fewer than two distinct values,
+    /// an infinite or `NaN` value, or if the maximization fails to converge
+    pub fn fit(data: &[f64]) -> Result<YeoJohnson> {
+        check_data(data, false)?;
+        let lambda = fit_lambda(|l| YeoJohnson::ln_likelihood(data, l))?;
+        Ok(YeoJohnson { lambda })
+    }
+
+    /// Returns the power parameter
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+
+    /// Returns the profile log-likelihood of `lambda` for `data`, assuming
+    /// the transformed data is normally distributed, up to an additive
+    /// constant
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -(n / 2) * ln(σ^2(λ)) + (λ - 1) * Σ sgn(x_i) * ln(|x_i| + 1)
+    /// ```
+    ///
+    /// where `σ^2(λ)` is the (biased) variance of the transformed data
+    pub fn ln_likelihood(data: &[f64], lambda: f64) -> f64 {
+        let yj = YeoJohnson { lambda };
+        let jacobian: f64 = data.iter().map(|x| x.signum() * fp::ln_1p(x.abs())).sum();
+        profile_ln_likelihood(&yj, data, jacobian * (lambda - 1.0))
+    }
+
+    /// Returns the profile-likelihood confidence interval of `lambda` at
+    /// confidence level `level` for `data`. See
+    /// `BoxCox::lambda_confidence_interval` for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)` or under the same
+    /// conditions as `YeoJohnson::fit`
+    pub fn lambda_confidence_interval(data: &[f64], level: f64) -> Result<(f64, f64)> {
+        let yj = YeoJohnson::fit(data)?;
+        profile_interval(|l| YeoJohnson::ln_likelihood(data, l), yj.lambda, level)
+    }
+}
+
+impl Transform for YeoJohnson {
+    fn apply(&self, x: f64) -> f64 {
+        let l = self.lambda;
+        if x >= 0.0 {
+            if l == 0.0 {
+                fp::ln_1p(x)
+            } else {
+                fp::exp_m1(l * fp::ln_1p(x)) / l
+            }
+        } else if l == 2.0 {
+            -fp::ln_1p(-x)
+        } else {
+            -fp::exp_m1((2.0 - l) * fp::ln_1p(-x)) / (2.0 - l)
+        }
+    }
+}
+
+impl InvertibleTransform for YeoJohnson {
+    /// Inverts the transform at `y`, returning `f64::NAN` if `y` lies
+    /// outside the image of the transform
+    fn invert(&self, y: f64) -> f64 {
+        let l = self.lambda;
+        if y >= 0.0 {
+            if l == 0.0 {
+                fp::exp_m1(y)
+            } else {
+                fp::exp_m1(fp::ln_1p(l * y) / l)
+            }
+        } else if l == 2.0 {
+            -fp::exp_m1(-y)
+        } else {
+            -fp::exp_m1(fp::ln_1p(-(2.0 - l) * y) / (2.0 - l))
+        }
+    }
+}
+
+/// Checks that `data` is finite with at least two distinct values and, if
+/// `positive` is set, strictly positive
+fn check_data(data: &[f64], positive: bool) -> Result<()> {
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::BadParams);
+    }
+    if positive && data.iter().any(|&x| x <= 0.0) {
+        return Err(StatsError::ArgMustBePositive("data"));
+    }
+    match data.first() {
+        Some(first) if data.iter().any(|x| x != first) => Ok(()),
+        _ => Err(StatsError::BadParams),
+    }
+}
+
+/// Returns `-(n / 2) * ln(σ^2) + ln_jacobian` where `σ^2` is the biased
+/// variance of `data` under `transform`
+fn profile_ln_likelihood<T: Transform>(transform: &T, data: &[f64], ln_jacobian: f64) -> f64 {
+    let n = data.len() as f64;
+    let y = transform.transform(data);
+    let mean = y.iter().sum::<f64>() / n;
+    let var = y.iter().map(|y| (y - mean) * (y - mean)).sum::<f64>() / n;
+    -0.5 * n * fp::ln(var) + ln_jacobian
+}
+
+/// Maximizes the profile log-likelihood `llf` over `lambda`
+fn fit_lambda<F: Fn(f64) -> f64>(llf: F) -> Result<f64> {
+    let (lambda, _) = optimize::brent_minimize(|l| -llf(l), -2.0, 2.0, 1e-10)?;
+    Ok(lambda)
+}
+
+/// Returns the interval around the maximizer `lambda` of `llf` on which
+/// the likelihood-ratio statistic stays below the chi-squared quantile
+fn profile_interval<F: Fn(f64) -> f64>(llf: F, lambda: f64, level: f64) -> Result<(f64, f64)> {
+    if level.is_nan() || level <= 0.0 || level >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("level", 0.0, 1.0));
+    }
+    // the chi-squared quantile with one degree of freedom is the square of
+    // the two-sided normal quantile
+    let z = erf::erf_inv(level);
+    let target = llf(lambda) - z * z;
+    let g = |l: f64| llf(l) - target;
+    let bound = |dir: f64| -> Result<f64> {
+        let mut step = 0.25;
+        while step < 1e3 {
+            let l = lambda + dir * step;
+            if g(l) < 0.0 {
+                return optimize::brent_root(g, lambda, l, 1e-10);
+            }
+            step *= 2.0;
+        }
+        Ok(dir * f64::INFINITY)
+    };
+    Ok((bound(-1.0)?, bound(1.0)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{ContinuousCDF, Normal};
+
+    // normal scores, i.e. a "perfectly" normal sample
+    fn normal_scores(n: usize) -> Vec<f64> {
+        let norm = Normal::new(0.0, 1.0).unwrap();
+        (1..=n)
+            .map(|i| norm.inverse_cdf((i as f64 - 0.5) / n as f64))
+            .collect()
+    }
+
+    #[test]
+    fn test_box_cox_apply_invert() {
+        for &l in &[-2.0, -0.5, 0.0, 1e-12, 0.5, 1.0, 3.0] {
+            let bc = BoxCox::new(l).unwrap();
+            for &x in &[0.01, 0.5, 1.0, 2.0, 100.0] {
+                assert_almost_eq!(bc.invert(bc.apply(x)), x, 1e-10 * x);
+            }
+        }
+        let bc = BoxCox::new(0.0).unwrap();
+        assert_eq!(bc.apply(std::f64::consts::E), 1.0);
+        assert!(bc.apply(-1.0).is_nan());
+        let bc = BoxCox::new(2.0).unwrap();
+        assert_almost_eq!(bc.apply(3.0), 4.0, 1e-14);
+        assert!(bc.invert(-1.0).is_nan());
+    }
+
+    #[test]
+    fn test_yeo_johnson_apply_invert() {
+        for &l in &[-2.0, -0.5, 0.0, 0.5, 1.0, 2.0, 3.0] {
+            let yj = YeoJohnson::new(l).unwrap();
+            for &x in &[-50.0, -1.0, -0.01, 0.0, 0.01, 1.0, 50.0] {
+                assert_almost_eq!(yj.invert(yj.apply(x)), x, 1e-10 * x.abs().max(1.0));
+            }
+        }
+        let yj = YeoJohnson::new(0.0).unwrap();
+        assert_almost_eq!(yj.apply(1.0), 2f64.ln(), 1e-15);
+        assert_almost_eq!(yj.apply(-1.0), -1.5, 1e-15);
+        let yj = YeoJohnson::new(2.0).unwrap();
+        assert_almost_eq!(yj.apply(-1.0), -(2f64.ln()), 1e-15);
+        assert_almost_eq!(yj.apply(1.0), 1.5, 1e-15);
+    }
+
+    #[test]
+    fn test_box_cox_fit() {
+        // exponentiated normal scores are normalized by the log transform
+        let data: Vec<f64> = normal_scores(50).iter().map(|z| z.exp()).collect();
+        let bc = BoxCox::fit(&data).unwrap();
+        assert!(bc.lambda().abs() < 0.05);
+        let (lo, hi) = BoxCox::lambda_confidence_interval(&data, 0.95).unwrap();
+        assert!(lo < 0.0 && 0.0 < hi);
+        let crit = 3.841458820694124;
+        let max = BoxCox::ln_likelihood(&data, bc.lambda());
+        assert_almost_eq!(max - BoxCox::ln_likelihood(&data, lo), crit / 2.0, 1e-8);
+        assert_almost_eq!(max - BoxCox::ln_likelihood(&data, hi), crit / 2.0, 1e-8);
+
+        // squared (shifted) normal scores are normalized by the square root
+        let data: Vec<f64> = normal_scores(50)
+            .iter()
+            .map(|z| (3.0 + z) * (3.0 + z))
+            .collect();
+        let bc = BoxCox::fit(&data).unwrap();
+        assert!((bc.lambda() - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_yeo_johnson_fit() {
+        // already normal data needs no transform
+        let data = normal_scores(50);
+        let yj = YeoJohnson::fit(&data).unwrap();
+        assert!((yj.lambda() - 1.0).abs() < 0.05);
+        let (lo, hi) = YeoJohnson::lambda_confidence_interval(&data, 0.9).unwrap();
+        assert!(lo < 1.0 && 1.0 < hi);
+    }
+
+    #[test]
+    fn test_bad_fit() {
+        assert!(BoxCox::fit(&[1.0, 2.0, -1.0]).is_err());
+        assert!(BoxCox::fit(&[1.0, 2.0, 0.0]).is_err());
+        assert!(BoxCox::fit(&[1.0, 1.0, 1.0]).is_err());
+        assert!(BoxCox::fit(&[]).is_err());
+        assert!(YeoJohnson::fit(&[1.0, f64::NAN]).is_err());
+        assert!(YeoJohnson::fit(&[2.0]).is_err());
+        assert!(BoxCox::lambda_confidence_interval(&[1.0, 2.0, 3.0], 1.0).is_err());
+        assert!(YeoJohnson::lambda_confidence_interval(&[1.0, 2.0, 3.0], 0.0).is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use crate::distribution::{Triangular, Uniform};
+use crate::{Result, StatsError};
+use rand::distributions::Distribution;
+use rand::Rng;
+
+/// Implements [stochastic
+/// rounding](https://en.wikipedia.org/wiki/Rounding#Stochastic_rounding) of
+/// floats to the grid `offset + k * step`, where `k` is an integer.
+///
+/// A value strictly between two grid points is rounded up with probability
+/// proportional to its distance from the lower grid point and down
+/// otherwise, so the rounded value is an unbiased estimate of the input,
+/// i.e. `E[round(x)] = x`. Values already on the grid are returned
+/// unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::transform::StochasticRounder;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let r = StochasticRounder::new(0.5).unwrap();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let y = r.round(1.2, &mut rng);
+/// assert!(y == 1.0 || y == 1.5);
+/// assert_eq!(r.round(2.5, &mut rng), 2.5);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StochasticRounder {
+    step: f64,
+    offset: f64,
+}
+
+impl StochasticRounder {
+    /// Constructs a new stochastic rounder onto the grid of multiples of
+    /// `step`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `step` is `NaN`, infinite or not positive
+    pub fn new(step: f64) -> Result<StochasticRounder> {
+        StochasticRounder::with_offset(step, 0.0)
+    }
+
+    /// Constructs a new stochastic rounder onto the grid
+    /// `offset + k * step`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `step` is `NaN`, infinite or not positive, or if
+    /// `offset` is not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::StochasticRounder;
+    ///
+    /// let mut result = StochasticRounder::with_offset(1.0, 0.5);
+    /// assert!(result.is_ok());
+    ///
+    /// result = StochasticRounder::with_offset(0.0, 0.5);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn with_offset(step: f64, offset: f64) -> Result<StochasticRounder> {
+        if !step.is_finite() || step <= 0.0 {
+            Err(StatsError::ArgMustBePositive("step"))
+        } else if !offset.is_finite() {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(StochasticRounder { step, offset })
+        }
+    }
+
+    /// Returns the spacing of the grid
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Returns the offset of the grid
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Stochastically rounds `x` to one of its two neighbouring grid points.
+    /// Non-finite values are returned unchanged.
+    pub fn round<R: Rng + ?Sized>(&self, x: f64, rng: &mut R) -> f64 {
+        if !x.is_finite() {
+            return x;
+        }
+        let scaled = (x - self.offset) / self.step;
+        let lower = scaled.floor();
+        let frac = scaled - lower;
+        let k = if frac > 0.0 && rng.gen::<f64>() < frac {
+            lower + 1.0
+        } else {
+            lower
+        };
+        self.offset + k * self.step
+    }
+
+    /// Stochastically rounds every element of `data`, returning the rounded
+    /// values
+    pub fn round_all<R: Rng + ?Sized>(&self, data: &[f64], rng: &mut R) -> Vec<f64> {
+        data.iter().map(|&x| self.round(x, rng)).collect()
+    }
+}
+
+/// Implements [dithered
+/// quantization](https://en.wikipedia.org/wiki/Dither) to the grid of
+/// multiples of `step`, adding noise drawn from a distribution `D` before
+/// rounding to the nearest grid point.
+///
+/// # Remarks
+///
+/// With `uniform` dither on `[-step / 2, step / 2]` and subtractive
+/// quantization the quantization error is uniformly distributed and
+/// independent of the input. With `triangular` dither on `[-step, step]`
+/// the first two moments of the (non-subtractive) quantization error are
+/// independent of the input; in particular the quantizer is unbiased.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::transform::Dither;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let d = Dither::triangular(0.25).unwrap();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let y = d.quantize(0.3, &mut rng);
+/// assert_eq!((y / 0.25).fract(), 0.0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Dither<D> {
+    step: f64,
+    noise: D,
+}
+
+impl Dither<Uniform> {
+    /// Constructs a quantizer with uniform dither on `[-step / 2, step / 2]`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `step` is `NaN`, infinite or not positive
+    pub fn uniform(step: f64) -> Result<Dither<Uniform>> {
+        check_step(step)?;
+        Dither::with_noise(step, Uniform::new(-0.5 * step, 0.5 * step)?)
+    }
+}
+
+impl Dither<Triangular> {
+    /// Constructs a quantizer with triangular dither on `[-step, step]`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `step` is `NaN`, infinite or not positive
+    pub fn triangular(step: f64) -> Result<Dither<Triangular>> {
+        check_step(step)?;
+        Dither::with_noise(step, Triangular::new(-step, step, 0.0)?)
+    }
+}
+
+impl<D: Distribution<f64>> Dither<D> {
+    /// Constructs a quantizer to the grid of multiples of `step` using
+    /// dither noise drawn from `noise`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `step` is `NaN`, infinite or not positive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    /// use statrs::transform::Dither;
+    ///
+    /// let mut result = Dither::with_noise(1.0, Normal::new(0.0, 0.1).unwrap());
+    /// assert!(result.is_ok());
+    ///
+    /// result = Dither::with_noise(-1.0, Normal::new(0.0, 0.1).unwrap());
+    /// assert!(result.is_err());
+    /// ```
+    pub fn with_noise(step: f64, noise: D) -> Result<Dither<D>> {
+        check_step(step)?;
+        Ok(Dither { step, noise })
+    }
+
+    /// Returns the spacing of the grid
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Returns the distribution of the dither noise
+    pub fn noise(&self) -> &D {
+        &self.noise
+    }
+
+    /// Quantizes `x + n` to the nearest grid point, where `n` is a draw of
+    /// the dither noise
+    pub fn quantize<R: Rng + ?Sized>(&self, x: f64, rng: &mut R) -> f64 {
+        let n = self.noise.sample(rng);
+        self.nearest(x + n)
+    }
+
+    /// Quantizes `x + n` to the nearest grid point and subtracts the dither
+    /// `n` again, as done when the receiver knows the dither sequence
+    pub fn quantize_subtractive<R: Rng + ?Sized>(&self, x: f64, rng: &mut R) -> f64 {
+        let n = self.noise.sample(rng);
+        self.nearest(x + n) - n
+    }
+
+    /// Quantizes every element of `data` with fresh dither noise, returning
+    /// the quantized values
+    pub fn quantize_all<R: Rng + ?Sized>(&self, data: &[f64], rng: &mut R) -> Vec<f64> {
+        data.iter().map(|&x| self.quantize(x, rng)).collect()
+    }
+
+    fn nearest(&self, x: f64) -> f64 {
+        (x / self.step).round() * self.step
+    }
+}
+
+fn check_step(step: f64) -> Result<()> {
+    if !step.is_finite() || step <= 0.0 {
+        Err(StatsError::ArgMustBePositive("step"))
+    } else {
+        Ok(())
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bad_create() {
+        assert!(StochasticRounder::new(0.0).is_err());
+        assert!(StochasticRounder::new(f64::NAN).is_err());
+        assert!(StochasticRounder::with_offset(1.0, f64::INFINITY).is_err());
+        assert!(Dither::uniform(-1.0).is_err());
+        assert!(Dither::triangular(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_stochastic_round_neighbours() {
+        let r = StochasticRounder::with_offset(0.5, 0.1).unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let y = r.round(0.85, &mut rng);
+            assert!(y == 0.6 || y == 1.1);
+        }
+        assert_eq!(r.round(0.6, &mut rng), 0.6);
+        assert_eq!(r.round(f64::INFINITY, &mut rng), f64::INFINITY);
+        assert!(r.round(f64::NAN, &mut rng).is_nan());
+    }
+
+    #[test]
+    fn test_stochastic_round_unbiased() {
+        let r = StochasticRounder::new(1.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(2);
+        let n = 100_000;
+        for &x in &[0.1, 0.5, -2.3, 7.9] {
+            let mean = r.round_all(&vec![x; n], &mut rng).iter().sum::<f64>() / n as f64;
+            // the standard error is at most 0.5 / sqrt(n)
+            assert_almost_eq!(mean, x, 0.01);
+        }
+    }
+
+    #[test]
+    fn test_dither_unbiased() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let n = 100_000;
+        let tri = Dither::triangular(1.0).unwrap();
+        let uni = Dither::uniform(1.0).unwrap();
+        for &x in &[0.2, 0.5, -1.7] {
+            let mean = tri.quantize_all(&vec![x; n], &mut rng).iter().sum::<f64>() / n as f64;
+            assert_almost_eq!(mean, x, 0.01);
+            let err: Vec<f64> = (0..n).map(|_| uni.quantize_subtractive(x, &mut rng) - x).collect();
+            let mean = err.iter().sum::<f64>() / n as f64;
+            let var = err.iter().map(|e| e * e).sum::<f64>() / n as f64;
+            assert_almost_eq!(mean, 0.0, 0.01);
+            assert_almost_eq!(var, 1.0 / 12.0, 0.005);
+        }
+    }
+
+    #[test]
+    fn test_dither_on_grid() {
+        let d = Dither::uniform(0.25).unwrap();
+        let mut rng = StdRng::seed_from_u64(4);
+        for y in d.quantize_all(&[0.1, 0.3, -0.9, 2.0], &mut rng) {
+            assert_eq!((y / 0.25).fract(), 0.0);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use crate::transform::Transform;
+use crate::{Result, StatsError};
+
+/// Implements [winsorization](https://en.wikipedia.org/wiki/Winsorizing),
+/// replacing values in the tails of the data with the most extreme values
+/// that are kept.
+///
+/// The bounds are learned once by `fit` and reused by every subsequent
+/// `transform`, so that new data is winsorized against the bounds of the
+/// data the winsorizer was fitted on.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::transform::{Transform, Winsorizer};
+///
+/// let train = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 100.0];
+/// let w = Winsorizer::fit(&train, (0.1, 0.1)).unwrap();
+/// assert_eq!((w.lower(), w.upper()), (2.0, 9.0));
+/// assert_eq!(w.transform(&[0.0, 5.0, 50.0]), vec![2.0, 5.0, 9.0]);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Winsorizer {
+    clipper: Clipper,
+}
+
+impl Winsorizer {
+    /// Learns the winsorizing bounds from `data`, where `limits` holds the
+    /// proportions of observations to replace in the lower and upper tail
+    /// respectively.
+    ///
+    /// # Remarks
+    ///
+    /// With `n` observations and sorted data `x_(1) <= ... <= x_(n)`, the
+    /// learned bounds are `x_(k_l + 1)` and `x_(n - k_u)` where
+    /// `k_l = floor(limits.0 * n)` and `k_u = floor(limits.1 * n)`, i.e. the
+    /// `k_l` smallest and the `k_u` largest observations are replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or contains a `NaN`, if either
+    /// limit is `NaN` or not in `[0, 1]`, or if the limits would replace
+    /// every observation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::Winsorizer;
+    ///
+    /// let mut result = Winsorizer::fit(&[1.0, 2.0, 3.0], (0.0, 0.4));
+    /// assert!(result.is_ok());
+    ///
+    /// result = Winsorizer::fit(&[1.0, 2.0, 3.0], (0.7, 0.4));
+    /// assert!(result.is_err());
+    /// ```
+    pub fn fit(data: &[f64], limits: (f64, f64)) -> Result<Winsorizer> {
+        let (lower, upper) = limits;
+        if lower.is_nan() || !(0.0..=1.0).contains(&lower) {
+            return Err(StatsError::ArgIntervalIncl("lower limit", 0.0, 1.0));
+        }
+        if upper.is_nan() || !(0.0..=1.0).contains(&upper) {
+            return Err(StatsError::ArgIntervalIncl("upper limit", 0.0, 1.0));
+        }
+        let sorted = sorted_copy(data)?;
+        let n = sorted.len();
+        let k_lower = (lower * n as f64).floor() as usize;
+        let k_upper = (upper * n as f64).floor() as usize;
+        if k_lower + k_upper >= n {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Winsorizer {
+            clipper: Clipper {
+                lower: sorted[k_lower],
+                upper: sorted[n - 1 - k_upper],
+            },
+        })
+    }
+
+    /// Returns the learned lower bound
+    pub fn lower(&self) -> f64 {
+        self.clipper.lower
+    }
+
+    /// Returns the learned upper bound
+    pub fn upper(&self) -> f64 {
+        self.clipper.upper
+    }
+}
+
+impl Transform for Winsorizer {
+    /// Winsorizes `x` against the learned bounds
+    fn apply(&self, x: f64) -> f64 {
+        self.clipper.apply(x)
+    }
+}
+
+/// Implements clipping of data to the closed interval `[lower, upper]`,
+/// with bounds that are either given explicitly or learned from the range
+/// of a data set.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::transform::{Clipper, Transform};
+///
+/// let c = Clipper::fit(&[3.0, -1.0, 2.0]).unwrap();
+/// assert_eq!((c.lower(), c.upper()), (-1.0, 3.0));
+/// assert_eq!(c.transform(&[-5.0, 0.0, 5.0]), vec![-1.0, 0.0, 3.0]);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Clipper {
+    lower: f64,
+    upper: f64,
+}
+
+impl Clipper {
+    /// Constructs a new clipping transform to the interval `[lower, upper]`.
+    /// Infinite bounds leave the corresponding side unclipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lower` or `upper` are `NaN` or if
+    /// `lower > upper`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::transform::Clipper;
+    ///
+    /// let mut result = Clipper::new(0.0, f64::INFINITY);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Clipper::new(1.0, 0.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(lower: f64, upper: f64) -> Result<Clipper> {
+        if lower.is_nan() || upper.is_nan() {
+            Err(StatsError::BadParams)
+        } else if lower > upper {
+            Err(StatsError::ArgLteArg("lower", "upper"))
+        } else {
+            Ok(Clipper { lower, upper })
+        }
+    }
+
+    /// Learns the clipping bounds as the minimum and maximum of `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or contains a `NaN`
+    pub fn fit(data: &[f64]) -> Result<Clipper> {
+        let sorted = sorted_copy(data)?;
+        Ok(Clipper {
+            lower: sorted[0],
+            upper: sorted[sorted.len() - 1],
+        })
+    }
+
+    /// Returns the lower bound
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Returns the upper bound
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+}
+
+impl Transform for Clipper {
+    /// Clips `x` to `[lower, upper]`. `NaN` values are passed through
+    /// unchanged.
+    fn apply(&self, x: f64) -> f64 {
+        x.clamp(self.lower, self.upper)
+    }
+}
+
+/// Returns a sorted copy of `data`, or an error if `data` is empty or
+/// contains a `NaN`
+fn sorted_copy(data: &[f64]) -> Result<Vec<f64>> {
+    if data.is_empty() || data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_winsorizer_fit() {
+        let data = [9.0, -3.0, 4.0, 1.0, 100.0, 0.0, 2.0, -50.0, 7.0, 5.0];
+        let w = Winsorizer::fit(&data, (0.2, 0.1)).unwrap();
+        assert_eq!(w.lower(), 0.0);
+        assert_eq!(w.upper(), 9.0);
+        let w = Winsorizer::fit(&data, (0.0, 0.0)).unwrap();
+        assert_eq!(w.lower(), -50.0);
+        assert_eq!(w.upper(), 100.0);
+        let w = Winsorizer::fit(&data, (0.5, 0.4)).unwrap();
+        assert_eq!(w.lower(), w.upper());
+    }
+
+    #[test]
+    fn test_winsorizer_bad_fit() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        assert!(Winsorizer::fit(&[], (0.1, 0.1)).is_err());
+        assert!(Winsorizer::fit(&[1.0, f64::NAN], (0.1, 0.1)).is_err());
+        assert!(Winsorizer::fit(&data, (-0.1, 0.1)).is_err());
+        assert!(Winsorizer::fit(&data, (0.1, f64::NAN)).is_err());
+        assert!(Winsorizer::fit(&data, (0.5, 0.5)).is_err());
+        assert!(Winsorizer::fit(&data, (1.0, 0.0)).is_err());
+    }
+
+    #[test]
+    fn test_winsorizer_transform_reuses_bounds() {
+        let train = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let w = Winsorizer::fit(&train, (0.2, 0.2)).unwrap();
+        assert_eq!(w.transform(&train), vec![2.0, 2.0, 3.0, 4.0, 4.0]);
+        let mut test = [-10.0, 3.5, 10.0];
+        w.transform_inplace(&mut test);
+        assert_eq!(test, [2.0, 3.5, 4.0]);
+    }
+
+    #[test]
+    fn test_clipper() {
+        let c = Clipper::new(f64::NEG_INFINITY, 0.0).unwrap();
+        assert_eq!(c.apply(-1e300), -1e300);
+        assert_eq!(c.apply(1.0), 0.0);
+        assert!(c.apply(f64::NAN).is_nan());
+        assert!(Clipper::new(f64::NAN, 0.0).is_err());
+        assert!(Clipper::new(1.0, 0.0).is_err());
+        assert!(Clipper::fit(&[]).is_err());
+        let c = Clipper::fit(&[0.5, 0.25, 0.75]).unwrap();
+        assert_eq!(c, Clipper::new(0.25, 0.75).unwrap());
+    }
+}